@@ -134,6 +134,7 @@ fn benchmark_options_comparison(c: &mut Criterion) {
         max_repairs: 0,
         fast_repair: false,
         report_repairs: false,
+        ..Default::default()
     };
 
     let json_strict = r#"{"name": "John", "items": [1, 2, 3], "data": {"a": 1, "b": 2}}"#;
@@ -0,0 +1,204 @@
+// this_file: benches/preallocation.rs
+
+//! Measures the effect of `ParserOptions::preallocate` on large flat containers,
+//! on documents shaped like the `canada.json` and `citm_catalog.json` corpora,
+//! and on small documents, where the pre-scan must not cost latency.
+//!
+//! A counting allocator records how often memory is allocated and reallocated
+//! while filling containers, which is what the comma pre-scan is meant to
+//! reduce. Vectors grow through `realloc`; hash maps allocate a new table.
+//!
+//! The real corpora are used when they are placed in `bench-data/large/`;
+//! otherwise generated documents of the same shape stand in for them.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use vexy_json::{parse_with_options, ParserOptions};
+
+/// System allocator wrapper that counts calls to `alloc` and `realloc`.
+struct CountingAllocator;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn large_array(len: usize) -> String {
+    format!(
+        "[{}]",
+        (0..len)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn large_object(len: usize) -> String {
+    format!(
+        "{{{}}}",
+        (0..len)
+            .map(|i| format!(r#""key{i}": {i}"#))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Reads `bench-data/large/{name}`, or generates a stand-in with `generate`.
+fn corpus(name: &str, generate: fn() -> String) -> String {
+    std::fs::read_to_string(format!("bench-data/large/{name}")).unwrap_or_else(|_| generate())
+}
+
+/// A GeoJSON polygon like `canada.json`: 480 rings of about 230 coordinate
+/// pairs each, stored as small arrays of floats.
+fn canada_like() -> String {
+    let rings = (0..480)
+        .map(|ring| {
+            let points = (0..(180 + ring % 100))
+                .map(|i| {
+                    let t = (ring * 1000 + i) as f64;
+                    format!("[{:.15},{:.15}]", -65.0 - t * 1e-4, 43.0 + t.sin())
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("[{points}]")
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{"type":"FeatureCollection","features":[{{"type":"Feature","properties":{{"name":"Canada"}},"geometry":{{"type":"Polygon","coordinates":[{rings}]}}}}]}}"#
+    )
+}
+
+/// A ticketing catalogue like `citm_catalog.json`: maps from numeric ids to
+/// names and events, and a list of performances with nested price and seat
+/// category lists.
+fn citm_like() -> String {
+    let names = |count: usize, base: usize| {
+        (0..count)
+            .map(|i| format!(r#""{}":"Name {i}""#, base + i))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let events = (0..184)
+        .map(|i| {
+            let id = 138_586_341 + i;
+            format!(
+                r#""{id}":{{"description":null,"id":{id},"logo":"/images/UE0AAAAACEKo6QAAAAZDSVRN","name":"Event {i}","subTopicIds":[337184269,337184283],"subjectCode":null,"subtitle":null,"topicIds":[324846099,107888604]}}"#
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let performances = (0..243)
+        .map(|i| {
+            let prices = (0..(2 + i % 4))
+                .map(|p| {
+                    format!(
+                        r#"{{"amount":{},"audienceSubCategoryId":337100890,"seatCategoryId":{}}}"#,
+                        90_250 - p * 10_000,
+                        338_937_295 + p
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            let areas = (0..(3 + i % 8))
+                .map(|a| format!(r#"{{"areaId":{},"blockIds":[]}}"#, 205_705_999 + a))
+                .collect::<Vec<_>>()
+                .join(",");
+            format!(
+                r#"{{"eventId":{},"id":{},"logo":null,"name":null,"prices":[{prices}],"seatCategories":[{{"areas":[{areas}],"seatCategoryId":338937295}}],"seatMapImage":null,"start":{},"venueCode":"PLEYEL_PLEYEL"}}"#,
+                138_586_341 + i % 184,
+                339_887_544 + i,
+                1_372_701_600_000u64 + i as u64 * 86_400_000
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        r#"{{"areaNames":{{{}}},"audienceSubCategoryNames":{{{}}},"blockNames":{{}},"events":{{{events}}},"performances":[{performances}],"seatCategoryNames":{{{}}},"subTopicNames":{{{}}},"subjectNames":{{}},"topicNames":{{{}}},"venueNames":{{"PLEYEL_PLEYEL":"Salle Pleyel"}}}}"#,
+        names(17, 205_705_993),
+        names(1, 337_100_890),
+        names(64, 338_937_235),
+        names(19, 337_184_262),
+        names(4, 107_888_604)
+    )
+}
+
+fn options(preallocate: bool) -> ParserOptions {
+    ParserOptions {
+        preallocate,
+        ..Default::default()
+    }
+}
+
+/// Returns the `(allocations, reallocations)` performed while parsing `json` once.
+fn count_allocations(json: &str, preallocate: bool) -> (usize, usize) {
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let reallocs = REALLOCS.load(Ordering::Relaxed);
+    let value = parse_with_options(json, options(preallocate)).unwrap();
+    let counts = (
+        ALLOCS.load(Ordering::Relaxed) - allocs,
+        REALLOCS.load(Ordering::Relaxed) - reallocs,
+    );
+    drop(value);
+    counts
+}
+
+fn bench_preallocation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("preallocation");
+    group.sample_size(20);
+
+    let inputs = [
+        ("array_100k", large_array(100_000)),
+        ("object_100k", large_object(100_000)),
+        ("canada", corpus("canada.json", canada_like)),
+        ("citm_catalog", corpus("citm_catalog.json", citm_like)),
+        (
+            "small_config",
+            include_str!("../bench-data/small/config_app.json").to_string(),
+        ),
+        (
+            "small_api",
+            include_str!("../bench-data/small/api_users.json").to_string(),
+        ),
+    ];
+
+    for (name, json) in &inputs {
+        for preallocate in [false, true] {
+            let (allocs, reallocs) = count_allocations(json, preallocate);
+            println!(
+                "{name} (preallocate={preallocate}): {allocs} allocations, {reallocs} reallocations"
+            );
+        }
+
+        for preallocate in [false, true] {
+            let id = if preallocate { "preallocate" } else { "grow" };
+            group.bench_with_input(BenchmarkId::new(id, name), json, |b, json| {
+                b.iter(|| parse_with_options(black_box(json), options(preallocate)))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_preallocation);
+criterion_main!(benches);
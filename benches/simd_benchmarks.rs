@@ -1,6 +1,7 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use std::hint::black_box;
 use vexy_json_core::optimization::simd::*;
+use vexy_json_core::optimization::{unescape_string_cow, unescape_string_optimized};
 
 fn generate_test_strings() -> Vec<(&'static str, String)> {
     vec![
@@ -118,13 +119,35 @@ fn benchmark_string_unescaping(c: &mut Criterion) {
     group.finish();
 }
 
+fn benchmark_escape_free_unescaping(c: &mut Criterion) {
+    let test_cases = vec![
+        ("small", "hello world test string".to_string()),
+        ("medium", "a".repeat(100)),
+        ("large", "x".repeat(1000)),
+    ];
+
+    let mut group = c.benchmark_group("escape_free_unescaping");
+
+    for (name, test_string) in &test_cases {
+        group.bench_with_input(BenchmarkId::new("owned", name), test_string, |b, s| {
+            b.iter(|| unescape_string_optimized(black_box(s)))
+        });
+        group.bench_with_input(BenchmarkId::new("cow", name), test_string, |b, s| {
+            b.iter(|| unescape_string_cow(black_box(s)))
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     simd_benches,
     benchmark_backslash_detection,
     benchmark_string_validation,
     benchmark_whitespace_skipping,
     benchmark_number_parsing,
-    benchmark_string_unescaping
+    benchmark_string_unescaping,
+    benchmark_escape_free_unescaping
 );
 
 criterion_main!(simd_benches);
@@ -75,10 +75,8 @@ fn bench_extreme_deep_nesting(c: &mut Criterion) {
                 &deep_object,
                 |b, json| {
                     b.iter(|| {
-                        match parse_recursive(black_box(json), black_box(options.clone())) {
-                            Ok(_) => {}
-                            Err(_) => {} // Expected for very deep structures
-                        }
+                        // Expected for very deep structures
+                        let _ = parse_recursive(black_box(json), black_box(options.clone()));
                     });
                 },
             );
@@ -88,10 +86,8 @@ fn bench_extreme_deep_nesting(c: &mut Criterion) {
                 &deep_array,
                 |b, json| {
                     b.iter(|| {
-                        match parse_recursive(black_box(json), black_box(options.clone())) {
-                            Ok(_) => {}
-                            Err(_) => {} // Expected for very deep structures
-                        }
+                        // Expected for very deep structures
+                        let _ = parse_recursive(black_box(json), black_box(options.clone()));
                     });
                 },
             );
@@ -101,10 +97,8 @@ fn bench_extreme_deep_nesting(c: &mut Criterion) {
                 &mixed_structure,
                 |b, json| {
                     b.iter(|| {
-                        match parse_recursive(black_box(json), black_box(options.clone())) {
-                            Ok(_) => {}
-                            Err(_) => {} // Expected for very deep structures
-                        }
+                        // Expected for very deep structures
+                        let _ = parse_recursive(black_box(json), black_box(options.clone()));
                     });
                 },
             );
@@ -116,10 +110,8 @@ fn bench_extreme_deep_nesting(c: &mut Criterion) {
             &deep_object,
             |b, json| {
                 b.iter(|| {
-                    match parse_iterative(black_box(json), black_box(options.clone())) {
-                        Ok(_) => {}
-                        Err(_) => {} // May fail due to depth limits, not stack overflow
-                    }
+                    // May fail due to depth limits, not stack overflow
+                    let _ = parse_iterative(black_box(json), black_box(options.clone()));
                 });
             },
         );
@@ -129,10 +121,8 @@ fn bench_extreme_deep_nesting(c: &mut Criterion) {
             &deep_array,
             |b, json| {
                 b.iter(|| {
-                    match parse_iterative(black_box(json), black_box(options.clone())) {
-                        Ok(_) => {}
-                        Err(_) => {} // May fail due to depth limits, not stack overflow
-                    }
+                    // May fail due to depth limits, not stack overflow
+                    let _ = parse_iterative(black_box(json), black_box(options.clone()));
                 });
             },
         );
@@ -142,10 +132,8 @@ fn bench_extreme_deep_nesting(c: &mut Criterion) {
             &mixed_structure,
             |b, json| {
                 b.iter(|| {
-                    match parse_iterative(black_box(json), black_box(options.clone())) {
-                        Ok(_) => {}
-                        Err(_) => {} // May fail due to depth limits, not stack overflow
-                    }
+                    // May fail due to depth limits, not stack overflow
+                    let _ = parse_iterative(black_box(json), black_box(options.clone()));
                 });
             },
         );
@@ -158,8 +146,10 @@ fn bench_with_high_depth_limit(c: &mut Criterion) {
     let mut group = c.benchmark_group("high_depth_limit");
 
     // Test with high depth limit to see how parsers handle it
-    let mut high_depth_options = ParserOptions::default();
-    high_depth_options.max_depth = 5000;
+    let high_depth_options = ParserOptions {
+        max_depth: 5000,
+        ..Default::default()
+    };
 
     let depths = [1000, 2000, 3000, 4000];
 
@@ -173,13 +163,9 @@ fn bench_with_high_depth_limit(c: &mut Criterion) {
                 &deep_object,
                 |b, json| {
                     b.iter(|| {
-                        match parse_recursive(
-                            black_box(json),
-                            black_box(high_depth_options.clone()),
-                        ) {
-                            Ok(_) => {}
-                            Err(_) => {} // Expected for very deep structures
-                        }
+                        // Expected for very deep structures
+                        let _ =
+                            parse_recursive(black_box(json), black_box(high_depth_options.clone()));
                     });
                 },
             );
@@ -191,10 +177,8 @@ fn bench_with_high_depth_limit(c: &mut Criterion) {
             &deep_object,
             |b, json| {
                 b.iter(|| {
-                    match parse_iterative(black_box(json), black_box(high_depth_options.clone())) {
-                        Ok(_) => {}
-                        Err(_) => {} // Should handle deeper structures better
-                    }
+                    // Should handle deeper structures better
+                    let _ = parse_iterative(black_box(json), black_box(high_depth_options.clone()));
                 });
             },
         );
@@ -323,22 +307,18 @@ fn bench_pathological_cases(c: &mut Criterion) {
     // Test iterative parser with pathological cases
     group.bench_function("iterative_alternating_structure", |b| {
         b.iter(|| {
-            match parse_iterative(
+            // May fail due to depth limits
+            let _ = parse_iterative(
                 black_box(&alternating_structure),
                 black_box(options.clone()),
-            ) {
-                Ok(_) => {}
-                Err(_) => {} // May fail due to depth limits
-            }
+            );
         });
     });
 
     group.bench_function("iterative_deep_single_array", |b| {
         b.iter(|| {
-            match parse_iterative(black_box(&deep_single_array), black_box(options.clone())) {
-                Ok(_) => {}
-                Err(_) => {} // May fail due to depth limits
-            }
+            // May fail due to depth limits
+            let _ = parse_iterative(black_box(&deep_single_array), black_box(options.clone()));
         });
     });
 
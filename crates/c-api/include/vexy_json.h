@@ -17,10 +17,35 @@
 extern "C" {
 #endif
 
+/** Keep the value of the last occurrence of a repeated key */
+#define VEXY_JSON_DUPLICATE_KEYS_LAST_WINS 0
+/** Keep the value of the first occurrence of a repeated key */
+#define VEXY_JSON_DUPLICATE_KEYS_FIRST_WINS 1
+/** Fail the parse at the second occurrence of a key */
+#define VEXY_JSON_DUPLICATE_KEYS_ERROR 2
+/** Gather the values of a repeated key into an array in document order */
+#define VEXY_JSON_DUPLICATE_KEYS_COLLECT 3
+
 /**
  * @brief Parser options for configuring vexy_json behavior
+ *
+ * This struct grows as options are added. Always initialize it with
+ * vexy_json_options_init() before setting fields:
+ *
+ * @code
+ * VexyJsonParserOptions opts;
+ * vexy_json_options_init(&opts, sizeof(opts));
+ * opts.allow_comments = false;
+ * VexyJsonParseResult result = vexy_json_parse_with_options(input, &opts);
+ * @endcode
+ *
+ * struct_size then records which fields your code was compiled with. A
+ * newer library reads only those and uses defaults for fields added since,
+ * so programs keep working when the shared library is upgraded. Fields are
+ * only ever appended, never reordered or removed.
  */
 typedef struct VexyJsonParserOptions {
+    size_t struct_size;     // sizeof the struct the caller compiled against
     bool allow_comments;
     bool allow_trailing_commas;
     bool allow_unquoted_keys;
@@ -32,6 +57,12 @@ typedef struct VexyJsonParserOptions {
     uint32_t max_repairs;
     bool fast_repair;
     bool report_repairs;
+    uint32_t duplicate_keys;           // One of VEXY_JSON_DUPLICATE_KEYS_*
+    bool preserve_number_precision;    // Read integers exactly; fail on numbers that would be rounded
+    size_t max_string_length;          // Maximum string value length in bytes, 0 for no limit
+    size_t max_array_length;           // Maximum elements in an array, 0 for no limit
+    size_t max_object_members;         // Maximum distinct keys in an object, 0 for no limit
+    size_t max_key_length;             // Maximum object key length in bytes, 0 for no limit
 } VexyJsonParserOptions;
 
 /**
@@ -92,7 +123,7 @@ VexyJsonParseResult vexy_json_parse(const char* input);
  * 
  * @warning This function requires careful memory management:
  * - input must be null or point to a valid null-terminated UTF-8 string
- * - options must be null or point to a valid VexyJsonParserOptions struct
+ * - options must be null or initialized with vexy_json_options_init()
  * - The returned result must be freed using vexy_json_free_result()
  * - Do not use returned pointers after freeing the result
  */
@@ -106,19 +137,51 @@ VexyJsonParseResult vexy_json_parse_with_options(const char* input, const VexyJs
  * 
  * @warning This function requires careful memory management:
  * - input must be null or point to a valid null-terminated UTF-8 string
- * - options must be null or point to a valid VexyJsonParserOptions struct
+ * - options must be null or initialized with vexy_json_options_init()
  * - The returned result must be freed using vexy_json_free_detailed_result()
  * - Do not use returned pointers after freeing the result
  */
 VexyJsonDetailedResult vexy_json_parse_detailed(const char* input, const VexyJsonParserOptions* options);
 
+/**
+ * @brief Parse JSON forgivingly and re-emit it minified
+ * @param input The JSON string to parse (null-terminated, UTF-8)
+ * @return Result whose json field holds the minified JSON (must be freed with vexy_json_free_result)
+ * 
+ * Key order follows the input; comments and trailing commas are dropped.
+ * The output is identical to `vexy-json --compact`.
+ * 
+ * @warning This function requires careful memory management:
+ * - input must be null or point to a valid null-terminated UTF-8 string
+ * - The returned result must be freed using vexy_json_free_result()
+ * - Do not use returned pointers after freeing the result
+ */
+VexyJsonParseResult vexy_json_minify(const char* input);
+
+/**
+ * @brief Parse JSON forgivingly and re-emit it pretty-printed
+ * @param input The JSON string to parse (null-terminated, UTF-8)
+ * @param indent Number of spaces per nesting level
+ * @return Result whose json field holds the formatted JSON (must be freed with vexy_json_free_result)
+ * 
+ * Key order follows the input; comments and trailing commas are dropped.
+ * The output is identical to `vexy-json --pretty --indent <indent>`.
+ * 
+ * @warning This function requires careful memory management:
+ * - input must be null or point to a valid null-terminated UTF-8 string
+ * - The returned result must be freed using vexy_json_free_result()
+ * - Do not use returned pointers after freeing the result
+ */
+VexyJsonParseResult vexy_json_format(const char* input, uint32_t indent);
+
 /**
  * @brief Create a new parser instance
  * @param options Parser options (can be null for defaults)
- * @return Parser handle (must be freed with vexy_json_parser_free)
+ * @return Parser handle (must be freed with vexy_json_parser_free), or null if
+ *         the options are invalid
  * 
  * @warning This function requires careful memory management:
- * - options must be null or point to a valid VexyJsonParserOptions struct
+ * - options must be null or initialized with vexy_json_options_init()
  * - The returned parser must be freed using vexy_json_parser_free()
  * - Do not use the parser after freeing it
  * - The parser is not thread-safe; use separate instances for concurrent access
@@ -178,9 +241,23 @@ void vexy_json_free_detailed_result(VexyJsonDetailedResult result);
 /**
  * @brief Get default parser options
  * @return Default options
+ *
+ * The result has this library version's layout; prefer
+ * vexy_json_options_init(), which works with any version of the struct.
  */
 VexyJsonParserOptions vexy_json_default_options(void);
 
+/**
+ * @brief Initialize parser options to their defaults
+ * @param options Options to initialize
+ * @param size sizeof(*options) as seen by the caller
+ * @return false, writing nothing, if options is null or size is too small
+ *
+ * Only fields that fit in size bytes are written, and struct_size is set
+ * to size.
+ */
+bool vexy_json_options_init(VexyJsonParserOptions* options, size_t size);
+
 #ifdef __cplusplus
 }
 #endif
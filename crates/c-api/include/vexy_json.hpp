@@ -49,7 +49,7 @@ struct Repair {
  */
 class ParserOptions {
 public:
-    ParserOptions() : options_(vexy_json_default_options()) {}
+    ParserOptions() { vexy_json_options_init(&options_, sizeof(options_)); }
     
     ParserOptions& allowComments(bool value = true) {
         options_.allow_comments = value;
@@ -106,6 +106,36 @@ public:
         return *this;
     }
     
+    ParserOptions& duplicateKeys(uint32_t policy) {
+        options_.duplicate_keys = policy;
+        return *this;
+    }
+    
+    ParserOptions& preserveNumberPrecision(bool value = true) {
+        options_.preserve_number_precision = value;
+        return *this;
+    }
+    
+    ParserOptions& maxStringLength(size_t length) {
+        options_.max_string_length = length;
+        return *this;
+    }
+    
+    ParserOptions& maxArrayLength(size_t length) {
+        options_.max_array_length = length;
+        return *this;
+    }
+    
+    ParserOptions& maxObjectMembers(size_t count) {
+        options_.max_object_members = count;
+        return *this;
+    }
+    
+    ParserOptions& maxKeyLength(size_t length) {
+        options_.max_key_length = length;
+        return *this;
+    }
+    
     const vexy_json_parser_options* get() const { return &options_; }
     
 private:
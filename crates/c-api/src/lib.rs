@@ -5,13 +5,33 @@
 
 use libc::{c_char, size_t};
 use std::ffi::{CStr, CString};
-use std::ptr;
+use std::{mem, ptr};
 use vexy_json_core::ast::Value;
-use vexy_json_core::{parse, parse_with_options, ParserOptions};
+use vexy_json_core::{
+    minify, parse, parse_with_options, prettify, DuplicateKeyPolicy, Limits, ParserOptions,
+};
+
+/// Keep the value of the last occurrence of a repeated key
+pub const VEXY_JSON_DUPLICATE_KEYS_LAST_WINS: u32 = 0;
+/// Keep the value of the first occurrence of a repeated key
+pub const VEXY_JSON_DUPLICATE_KEYS_FIRST_WINS: u32 = 1;
+/// Fail the parse at the second occurrence of a key
+pub const VEXY_JSON_DUPLICATE_KEYS_ERROR: u32 = 2;
+/// Gather the values of a repeated key into an array in document order
+pub const VEXY_JSON_DUPLICATE_KEYS_COLLECT: u32 = 3;
 
 /// Parser options for configuring vexy_json behavior
+///
+/// The struct grows as options are added, so callers always initialize it
+/// with `vexy_json_options_init(&opts, sizeof(opts))` before setting fields.
+/// That records in `struct_size` which fields the caller knows about, and
+/// the library reads only those, using defaults for fields added after the
+/// caller was compiled. New fields are only ever appended, each starting at
+/// or after the size of the previous version of the struct.
 #[repr(C)]
 pub struct VexyJsonParserOptions {
+    /// Size in bytes of the struct the caller compiled against
+    pub struct_size: size_t,
     pub allow_comments: bool,
     pub allow_trailing_commas: bool,
     pub allow_unquoted_keys: bool,
@@ -23,6 +43,18 @@ pub struct VexyJsonParserOptions {
     pub max_repairs: u32,
     pub fast_repair: bool,
     pub report_repairs: bool,
+    /// One of the `VEXY_JSON_DUPLICATE_KEYS_*` constants
+    pub duplicate_keys: u32,
+    /// Read integers exactly and fail on numbers that would be rounded
+    pub preserve_number_precision: bool,
+    /// Maximum length in bytes of a string value, or 0 for no limit
+    pub max_string_length: size_t,
+    /// Maximum number of elements in an array, or 0 for no limit
+    pub max_array_length: size_t,
+    /// Maximum number of distinct keys in an object, or 0 for no limit
+    pub max_object_members: size_t,
+    /// Maximum length in bytes of an object key, or 0 for no limit
+    pub max_key_length: size_t,
 }
 
 /// Result of parsing JSON
@@ -79,7 +111,7 @@ pub unsafe extern "C" fn vexy_json_parse(input: *const c_char) -> VexyJsonParseR
     if input.is_null() {
         return VexyJsonParseResult {
             json: ptr::null_mut(),
-            error: CString::new("Input is null").unwrap().into_raw(),
+            error: to_c_string("Input is null"),
         };
     }
 
@@ -88,7 +120,7 @@ pub unsafe extern "C" fn vexy_json_parse(input: *const c_char) -> VexyJsonParseR
         Err(_) => {
             return VexyJsonParseResult {
                 json: ptr::null_mut(),
-                error: CString::new("Invalid UTF-8 input").unwrap().into_raw(),
+                error: to_c_string("Invalid UTF-8 input"),
             };
         }
     };
@@ -96,19 +128,17 @@ pub unsafe extern "C" fn vexy_json_parse(input: *const c_char) -> VexyJsonParseR
     match parse(input_str) {
         Ok(value) => match value_to_json_string(&value) {
             Ok(json_str) => VexyJsonParseResult {
-                json: CString::new(json_str).unwrap().into_raw(),
+                json: to_c_string(json_str),
                 error: ptr::null_mut(),
             },
             Err(e) => VexyJsonParseResult {
                 json: ptr::null_mut(),
-                error: CString::new(format!("Serialization error: {e}"))
-                    .unwrap()
-                    .into_raw(),
+                error: to_c_string(format!("Serialization error: {e}")),
             },
         },
         Err(e) => VexyJsonParseResult {
             json: ptr::null_mut(),
-            error: CString::new(format!("{e}")).unwrap().into_raw(),
+            error: to_c_string(format!("{e}")),
         },
     }
 }
@@ -125,7 +155,8 @@ pub unsafe extern "C" fn vexy_json_parse(input: *const c_char) -> VexyJsonParseR
 ///
 /// The caller must ensure:
 /// - `input` is either null or points to a valid null-terminated UTF-8 string
-/// - `options` is either null or points to a valid `VexyJsonParserOptions` struct
+/// - `options` is either null or points to options set up by `vexy_json_options_init`,
+///   with at least `struct_size` readable bytes
 /// - The returned `VexyJsonParseResult` is eventually freed using `vexy_json_free_result`
 /// - The returned pointers in the result are not used after being freed
 #[no_mangle]
@@ -136,7 +167,7 @@ pub unsafe extern "C" fn vexy_json_parse_with_options(
     if input.is_null() {
         return VexyJsonParseResult {
             json: ptr::null_mut(),
-            error: CString::new("Input is null").unwrap().into_raw(),
+            error: to_c_string("Input is null"),
         };
     }
 
@@ -149,29 +180,35 @@ pub unsafe extern "C" fn vexy_json_parse_with_options(
         Err(_) => {
             return VexyJsonParseResult {
                 json: ptr::null_mut(),
-                error: CString::new("Invalid UTF-8 input").unwrap().into_raw(),
+                error: to_c_string("Invalid UTF-8 input"),
             };
         }
     };
 
-    let rust_options = c_options_to_rust(&*options);
+    let rust_options = match read_options(options) {
+        Ok(options) => options,
+        Err(e) => {
+            return VexyJsonParseResult {
+                json: ptr::null_mut(),
+                error: to_c_string(e),
+            };
+        }
+    };
 
     match parse_with_options(input_str, rust_options) {
         Ok(value) => match value_to_json_string(&value) {
             Ok(json_str) => VexyJsonParseResult {
-                json: CString::new(json_str).unwrap().into_raw(),
+                json: to_c_string(json_str),
                 error: ptr::null_mut(),
             },
             Err(e) => VexyJsonParseResult {
                 json: ptr::null_mut(),
-                error: CString::new(format!("Serialization error: {e}"))
-                    .unwrap()
-                    .into_raw(),
+                error: to_c_string(format!("Serialization error: {e}")),
             },
         },
         Err(e) => VexyJsonParseResult {
             json: ptr::null_mut(),
-            error: CString::new(format!("{e}")).unwrap().into_raw(),
+            error: to_c_string(format!("{e}")),
         },
     }
 }
@@ -188,7 +225,8 @@ pub unsafe extern "C" fn vexy_json_parse_with_options(
 ///
 /// The caller must ensure:
 /// - `input` is either null or points to a valid null-terminated UTF-8 string
-/// - `options` is either null or points to a valid `VexyJsonParserOptions` struct
+/// - `options` is either null or points to options set up by `vexy_json_options_init`,
+///   with at least `struct_size` readable bytes
 /// - The returned `VexyJsonDetailedResult` is eventually freed using `vexy_json_free_detailed_result`
 /// - The returned pointers in the result are not used after being freed
 #[no_mangle]
@@ -212,8 +250,93 @@ pub unsafe extern "C" fn vexy_json_parse_detailed(
     }
 }
 
+/// Parse JSON forgivingly and re-emit it as minified JSON
+///
+/// Key order follows the input; comments and trailing commas are dropped.
+/// The output is identical to `vexy-json --compact`.
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// - Dereferences a raw pointer (`input`)
+/// - Assumes `input` points to a valid null-terminated C string
+/// - Returns raw pointers that must be freed using `vexy_json_free_result`
+///
+/// The caller must ensure:
+/// - `input` is either null or points to a valid null-terminated UTF-8 string
+/// - The returned `VexyJsonParseResult` is eventually freed using `vexy_json_free_result`
+/// - The returned pointers in the result are not used after being freed
+#[no_mangle]
+pub unsafe extern "C" fn vexy_json_minify(input: *const c_char) -> VexyJsonParseResult {
+    format_input(input, |input| minify(input, ParserOptions::default()))
+}
+
+/// Parse JSON forgivingly and re-emit it as pretty-printed JSON
+///
+/// Key order follows the input; comments and trailing commas are dropped.
+/// Nested values are indented by `indent` spaces per level, matching
+/// `vexy-json --pretty --indent <indent>`.
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// - Dereferences a raw pointer (`input`)
+/// - Assumes `input` points to a valid null-terminated C string
+/// - Returns raw pointers that must be freed using `vexy_json_free_result`
+///
+/// The caller must ensure:
+/// - `input` is either null or points to a valid null-terminated UTF-8 string
+/// - The returned `VexyJsonParseResult` is eventually freed using `vexy_json_free_result`
+/// - The returned pointers in the result are not used after being freed
+#[no_mangle]
+pub unsafe extern "C" fn vexy_json_format(
+    input: *const c_char,
+    indent: u32,
+) -> VexyJsonParseResult {
+    format_input(input, |input| {
+        prettify(input, indent as usize, ParserOptions::default())
+    })
+}
+
+/// Shared input handling for the formatting entry points
+unsafe fn format_input(
+    input: *const c_char,
+    format: impl FnOnce(&str) -> vexy_json_core::Result<String>,
+) -> VexyJsonParseResult {
+    if input.is_null() {
+        return VexyJsonParseResult {
+            json: ptr::null_mut(),
+            error: to_c_string("Input is null"),
+        };
+    }
+
+    let input_str = match CStr::from_ptr(input).to_str() {
+        Ok(s) => s,
+        Err(_) => {
+            return VexyJsonParseResult {
+                json: ptr::null_mut(),
+                error: to_c_string("Invalid UTF-8 input"),
+            };
+        }
+    };
+
+    match format(input_str) {
+        Ok(json_str) => VexyJsonParseResult {
+            json: to_c_string(json_str),
+            error: ptr::null_mut(),
+        },
+        Err(e) => VexyJsonParseResult {
+            json: ptr::null_mut(),
+            error: to_c_string(format!("{e}")),
+        },
+    }
+}
+
 /// Create a new parser instance
 ///
+/// Returns null if `options` is invalid, for example if it was not
+/// initialized with `vexy_json_options_init`.
+///
 /// # Safety
 ///
 /// This function is unsafe because it:
@@ -222,7 +345,8 @@ pub unsafe extern "C" fn vexy_json_parse_detailed(
 /// - Returns a raw pointer that must be freed using `vexy_json_parser_free`
 ///
 /// The caller must ensure:
-/// - `options` is either null or points to a valid `VexyJsonParserOptions` struct
+/// - `options` is either null or points to options set up by `vexy_json_options_init`,
+///   with at least `struct_size` readable bytes
 /// - The returned parser pointer is eventually freed using `vexy_json_parser_free`
 /// - The returned parser pointer is not used after being freed
 /// - The returned parser pointer is not shared across threads without proper synchronization
@@ -233,7 +357,10 @@ pub unsafe extern "C" fn vexy_json_parser_new(
     let rust_options = if options.is_null() {
         ParserOptions::default()
     } else {
-        c_options_to_rust(&*options)
+        match read_options(options) {
+            Ok(options) => options,
+            Err(_) => return ptr::null_mut(),
+        }
     };
 
     let parser = Box::new(VexyJsonParser {
@@ -267,14 +394,14 @@ pub unsafe extern "C" fn vexy_json_parser_parse(
     if parser.is_null() {
         return VexyJsonParseResult {
             json: ptr::null_mut(),
-            error: CString::new("Parser is null").unwrap().into_raw(),
+            error: to_c_string("Parser is null"),
         };
     }
 
     if input.is_null() {
         return VexyJsonParseResult {
             json: ptr::null_mut(),
-            error: CString::new("Input is null").unwrap().into_raw(),
+            error: to_c_string("Input is null"),
         };
     }
 
@@ -284,7 +411,7 @@ pub unsafe extern "C" fn vexy_json_parser_parse(
         Err(_) => {
             return VexyJsonParseResult {
                 json: ptr::null_mut(),
-                error: CString::new("Invalid UTF-8 input").unwrap().into_raw(),
+                error: to_c_string("Invalid UTF-8 input"),
             };
         }
     };
@@ -292,19 +419,17 @@ pub unsafe extern "C" fn vexy_json_parser_parse(
     match parse_with_options(input_str, parser_ref.options.clone()) {
         Ok(value) => match value_to_json_string(&value) {
             Ok(json_str) => VexyJsonParseResult {
-                json: CString::new(json_str).unwrap().into_raw(),
+                json: to_c_string(json_str),
                 error: ptr::null_mut(),
             },
             Err(e) => VexyJsonParseResult {
                 json: ptr::null_mut(),
-                error: CString::new(format!("Serialization error: {e}"))
-                    .unwrap()
-                    .into_raw(),
+                error: to_c_string(format!("Serialization error: {e}")),
             },
         },
         Err(e) => VexyJsonParseResult {
             json: ptr::null_mut(),
-            error: CString::new(format!("{e}")).unwrap().into_raw(),
+            error: to_c_string(format!("{e}")),
         },
     }
 }
@@ -378,15 +503,106 @@ pub unsafe extern "C" fn vexy_json_free_detailed_result(result: VexyJsonDetailed
 }
 
 /// Get default parser options
+///
+/// The result has the layout of this version of the library, so it is only
+/// safe to use from code compiled against the matching header. Prefer
+/// `vexy_json_options_init`, which works with any version of the struct.
 #[no_mangle]
 pub extern "C" fn vexy_json_default_options() -> VexyJsonParserOptions {
     let rust_options = ParserOptions::default();
     rust_options_to_c(&rust_options)
 }
 
+/// Initialize parser options to their defaults
+///
+/// Pass `sizeof(*options)` as `size`: only the fields that fit in `size`
+/// bytes are written, and `struct_size` is set to `size` so that later
+/// calls read the same fields. Returns false, writing nothing, if
+/// `options` is null or `size` is too small to hold `struct_size`.
+///
+/// # Safety
+///
+/// The caller must ensure:
+/// - `options` is either null or points to at least `size` writable bytes
+#[no_mangle]
+pub unsafe extern "C" fn vexy_json_options_init(
+    options: *mut VexyJsonParserOptions,
+    size: size_t,
+) -> bool {
+    if options.is_null() || size < mem::size_of::<size_t>() {
+        return false;
+    }
+    let defaults = vexy_json_default_options();
+    for (offset, len) in declared_fields(size) {
+        let from = (&defaults as *const VexyJsonParserOptions as *const u8).add(offset);
+        ptr::copy_nonoverlapping(from, (options as *mut u8).add(offset), len);
+    }
+    ptr::write(options as *mut size_t, size);
+    true
+}
+
+/// Byte ranges, as `(offset, len)`, of the option fields after
+/// `struct_size` that lie entirely within the first `size` bytes
+fn declared_fields(size: size_t) -> impl Iterator<Item = (usize, usize)> {
+    let options = vexy_json_default_options();
+    let base = &options as *const VexyJsonParserOptions as usize;
+    macro_rules! field {
+        ($name:ident) => {
+            (
+                ptr::addr_of!(options.$name) as usize - base,
+                mem::size_of_val(&options.$name),
+            )
+        };
+    }
+    let fields = [
+        field!(allow_comments),
+        field!(allow_trailing_commas),
+        field!(allow_unquoted_keys),
+        field!(allow_single_quotes),
+        field!(implicit_top_level),
+        field!(newline_as_comma),
+        field!(max_depth),
+        field!(enable_repair),
+        field!(max_repairs),
+        field!(fast_repair),
+        field!(report_repairs),
+        field!(duplicate_keys),
+        field!(preserve_number_precision),
+        field!(max_string_length),
+        field!(max_array_length),
+        field!(max_object_members),
+        field!(max_key_length),
+    ];
+    fields
+        .into_iter()
+        .filter(move |(offset, len)| offset + len <= size)
+}
+
+/// Read caller options, using defaults for fields beyond their `struct_size`
+unsafe fn read_options(options: *const VexyJsonParserOptions) -> Result<ParserOptions, String> {
+    let size = ptr::read(options as *const size_t);
+    if size < mem::size_of::<size_t>() {
+        return Err("Options are not initialized; call vexy_json_options_init".into());
+    }
+    let mut declared = vexy_json_default_options();
+    for (offset, len) in declared_fields(size) {
+        let to = (&mut declared as *mut VexyJsonParserOptions as *mut u8).add(offset);
+        ptr::copy_nonoverlapping((options as *const u8).add(offset), to, len);
+    }
+    c_options_to_rust(&declared)
+}
+
 /// Convert C options to Rust options
-fn c_options_to_rust(options: &VexyJsonParserOptions) -> ParserOptions {
-    ParserOptions {
+fn c_options_to_rust(options: &VexyJsonParserOptions) -> Result<ParserOptions, String> {
+    let duplicate_keys = match options.duplicate_keys {
+        VEXY_JSON_DUPLICATE_KEYS_LAST_WINS => DuplicateKeyPolicy::LastWins,
+        VEXY_JSON_DUPLICATE_KEYS_FIRST_WINS => DuplicateKeyPolicy::FirstWins,
+        VEXY_JSON_DUPLICATE_KEYS_ERROR => DuplicateKeyPolicy::Error,
+        VEXY_JSON_DUPLICATE_KEYS_COLLECT => DuplicateKeyPolicy::CollectIntoArray,
+        other => return Err(format!("Unknown duplicate_keys policy {other}")),
+    };
+    let limit = |max: size_t| if max == 0 { None } else { Some(max) };
+    Ok(ParserOptions {
         allow_comments: options.allow_comments,
         allow_trailing_commas: options.allow_trailing_commas,
         allow_unquoted_keys: options.allow_unquoted_keys,
@@ -398,12 +614,22 @@ fn c_options_to_rust(options: &VexyJsonParserOptions) -> ParserOptions {
         max_repairs: options.max_repairs as usize,
         fast_repair: options.fast_repair,
         report_repairs: options.report_repairs,
-    }
+        duplicate_keys,
+        preserve_number_precision: options.preserve_number_precision,
+        limits: Limits {
+            max_array_len: limit(options.max_array_length),
+            max_object_members: limit(options.max_object_members),
+            max_key_len: limit(options.max_key_length),
+            max_string_len: limit(options.max_string_length),
+        },
+        ..Default::default()
+    })
 }
 
 /// Convert Rust options to C options
 fn rust_options_to_c(options: &ParserOptions) -> VexyJsonParserOptions {
     VexyJsonParserOptions {
+        struct_size: mem::size_of::<VexyJsonParserOptions>(),
         allow_comments: options.allow_comments,
         allow_trailing_commas: options.allow_trailing_commas,
         allow_unquoted_keys: options.allow_unquoted_keys,
@@ -415,10 +641,249 @@ fn rust_options_to_c(options: &ParserOptions) -> VexyJsonParserOptions {
         max_repairs: options.max_repairs as u32,
         fast_repair: options.fast_repair,
         report_repairs: options.report_repairs,
+        duplicate_keys: match options.duplicate_keys {
+            DuplicateKeyPolicy::LastWins => VEXY_JSON_DUPLICATE_KEYS_LAST_WINS,
+            DuplicateKeyPolicy::FirstWins => VEXY_JSON_DUPLICATE_KEYS_FIRST_WINS,
+            DuplicateKeyPolicy::Error => VEXY_JSON_DUPLICATE_KEYS_ERROR,
+            DuplicateKeyPolicy::CollectIntoArray => VEXY_JSON_DUPLICATE_KEYS_COLLECT,
+        },
+        preserve_number_precision: options.preserve_number_precision,
+        max_string_length: options.limits.max_string_len.unwrap_or(0),
+        max_array_length: options.limits.max_array_len.unwrap_or(0),
+        max_object_members: options.limits.max_object_members.unwrap_or(0),
+        max_key_length: options.limits.max_key_len.unwrap_or(0),
     }
 }
 
+/// Hand `s` to the caller as a C string
+///
+/// A C string ends at its first NUL, so any NUL in `s` is written as the JSON
+/// escape `\u0000` rather than truncating the string.
+fn to_c_string(s: impl Into<String>) -> *mut c_char {
+    let s = s.into().replace('\0', "\\u0000");
+    CString::new(s).unwrap_or_default().into_raw()
+}
+
 /// Convert a Value to a JSON string
 fn value_to_json_string(value: &Value) -> Result<String, serde_json::Error> {
     serde_json::to_string(value)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSY_CONFIG: &str = include_str!("../tests/fixtures/messy_config.jsonc");
+
+    /// Calls a formatting entry point the way C would and takes ownership of the result.
+    fn call(
+        f: impl FnOnce(*const c_char) -> VexyJsonParseResult,
+        input: &str,
+    ) -> Result<String, String> {
+        let input = CString::new(input).unwrap();
+        let result = f(input.as_ptr());
+        let output = unsafe {
+            if result.error.is_null() {
+                Ok(CStr::from_ptr(result.json).to_str().unwrap().to_string())
+            } else {
+                Err(CStr::from_ptr(result.error).to_str().unwrap().to_string())
+            }
+        };
+        unsafe { vexy_json_free_result(result) };
+        output
+    }
+
+    #[test]
+    fn test_minify_messy_config() {
+        let minified = call(|p| unsafe { vexy_json_minify(p) }, MESSY_CONFIG).unwrap();
+        assert_eq!(
+            minified,
+            r#"{"name":"edge-proxy","listen":{"port":8080,"host":"0.0.0.0"},"upstreams":[{"url":"http://a.internal","weight":3},{"url":"http://b.internal","weight":1}],"debug":false}"#
+        );
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        let minified = call(|p| unsafe { vexy_json_minify(p) }, MESSY_CONFIG).unwrap();
+        let formatted = call(|p| unsafe { vexy_json_format(p, 4) }, MESSY_CONFIG).unwrap();
+        assert!(formatted.starts_with("{\n    \"name\": \"edge-proxy\",\n    \"listen\": {\n"));
+
+        let reformatted = call(|p| unsafe { vexy_json_format(p, 4) }, &minified).unwrap();
+        assert_eq!(reformatted, formatted);
+        let reminified = call(|p| unsafe { vexy_json_minify(p) }, &formatted).unwrap();
+        assert_eq!(reminified, minified);
+    }
+
+    /// The options struct as it was before `duplicate_keys` was added
+    #[repr(C)]
+    struct OldOptions {
+        struct_size: size_t,
+        allow_comments: bool,
+        allow_trailing_commas: bool,
+        allow_unquoted_keys: bool,
+        allow_single_quotes: bool,
+        implicit_top_level: bool,
+        newline_as_comma: bool,
+        max_depth: u32,
+        enable_repair: bool,
+        max_repairs: u32,
+        fast_repair: bool,
+        report_repairs: bool,
+    }
+
+    /// An old caller's options followed by memory the library must not touch
+    #[repr(C)]
+    struct OldCaller {
+        options: OldOptions,
+        beyond: [u8; 32],
+    }
+
+    fn parse_with(input: &str, options: *const VexyJsonParserOptions) -> Result<String, String> {
+        call(
+            |p| unsafe { vexy_json_parse_with_options(p, options) },
+            input,
+        )
+    }
+
+    fn parse_default(input: &str) -> String {
+        call(|p| unsafe { vexy_json_parse(p) }, input).unwrap()
+    }
+
+    fn init() -> VexyJsonParserOptions {
+        let mut options = mem::MaybeUninit::<VexyJsonParserOptions>::uninit();
+        let size = mem::size_of::<VexyJsonParserOptions>();
+        assert!(unsafe { vexy_json_options_init(options.as_mut_ptr(), size) });
+        unsafe { options.assume_init() }
+    }
+
+    #[test]
+    fn test_options_init_fills_defaults() {
+        let options = init();
+        assert_eq!(options.struct_size, mem::size_of::<VexyJsonParserOptions>());
+        assert!(options.allow_comments);
+        assert_eq!(options.max_depth, 128);
+        assert_eq!(options.duplicate_keys, VEXY_JSON_DUPLICATE_KEYS_LAST_WINS);
+        assert!(!options.preserve_number_precision);
+        assert_eq!(options.max_string_length, 0);
+        assert_eq!(options.max_array_length, 0);
+
+        assert!(!unsafe { vexy_json_options_init(ptr::null_mut(), 64) });
+        let mut small = 0usize;
+        let small = &mut small as *mut size_t as *mut VexyJsonParserOptions;
+        assert!(!unsafe { vexy_json_options_init(small, 2) });
+    }
+
+    #[test]
+    fn test_old_caller_with_truncated_struct() {
+        // New fields start after the old struct, not in its tail padding
+        let first_new_field = {
+            let options = init();
+            ptr::addr_of!(options.duplicate_keys) as usize - ptr::addr_of!(options) as usize
+        };
+        assert!(mem::size_of::<OldOptions>() <= first_new_field);
+
+        let mut caller = OldCaller {
+            options: unsafe { mem::zeroed() },
+            beyond: [0xAB; 32],
+        };
+        let size = mem::size_of::<OldOptions>();
+        let options = &mut caller.options as *mut OldOptions as *mut VexyJsonParserOptions;
+        assert!(unsafe { vexy_json_options_init(options, size) });
+        assert_eq!(caller.beyond, [0xAB; 32]);
+        assert_eq!(caller.options.struct_size, size);
+        assert!(caller.options.allow_comments && caller.options.report_repairs);
+        assert_eq!(caller.options.max_depth, 128);
+
+        // Had the library read the 0xAB bytes as duplicate_keys, it would
+        // reject the policy; instead the default applies
+        assert_eq!(
+            parse_with(r#"{"a": 1, "a": 2}"#, options).unwrap(),
+            parse_default("{a: 2}")
+        );
+        caller.options.allow_comments = false;
+        let options = &caller.options as *const OldOptions as *const VexyJsonParserOptions;
+        assert!(parse_with("[1] // note", options).is_err());
+
+        let parser = unsafe { vexy_json_parser_new(options) };
+        assert!(!parser.is_null());
+        unsafe { vexy_json_parser_free(parser) };
+    }
+
+    #[test]
+    fn test_new_options() {
+        let mut options = init();
+        options.duplicate_keys = VEXY_JSON_DUPLICATE_KEYS_FIRST_WINS;
+        assert_eq!(
+            parse_with(r#"{"a": 1, "a": 2}"#, &options).unwrap(),
+            parse_default("{a: 1}")
+        );
+        options.duplicate_keys = VEXY_JSON_DUPLICATE_KEYS_ERROR;
+        let error = parse_with(r#"{"a": 1, "a": 2}"#, &options).unwrap_err();
+        assert!(error.starts_with("Duplicate key"), "{error}");
+        options.duplicate_keys = VEXY_JSON_DUPLICATE_KEYS_COLLECT;
+        assert_eq!(
+            parse_with(r#"{"a": 1, "a": 2}"#, &options).unwrap(),
+            parse_default("{a: [1, 2]}")
+        );
+        options.duplicate_keys = 7;
+        let error = parse_with("{}", &options).unwrap_err();
+        assert_eq!(error, "Unknown duplicate_keys policy 7");
+
+        let mut options = init();
+        options.max_string_length = 3;
+        assert!(parse_with("['abc']", &options).is_ok());
+        assert!(parse_with("['abcd']", &options).is_err());
+
+        let mut options = init();
+        options.max_array_length = 2;
+        options.max_object_members = 1;
+        options.max_key_length = 3;
+        assert!(parse_with("{abc: [1, 2]}", &options).is_ok());
+        let error = parse_with("{abc: [1, 2, 3]}", &options).unwrap_err();
+        assert!(error.ends_with("at path $.abc[2]"), "{error}");
+        assert!(parse_with("{a: 1, b: 2}", &options).is_err());
+        assert!(parse_with("{abcd: 1}", &options).is_err());
+
+        let mut options = init();
+        options.preserve_number_precision = true;
+        assert_eq!(
+            parse_with("9007199254740993", &options).unwrap(),
+            "9007199254740993"
+        );
+        assert!(parse_with("[0.10000000000000000001]", &options).is_err());
+    }
+
+    #[test]
+    fn test_uninitialized_options_rejected() {
+        let mut options = init();
+        options.struct_size = 0;
+        let error = parse_with("[1]", &options).unwrap_err();
+        assert!(error.contains("vexy_json_options_init"), "{error}");
+        assert!(unsafe { vexy_json_parser_new(&options) }.is_null());
+    }
+
+    #[test]
+    fn test_format_errors() {
+        let error = call(|p| unsafe { vexy_json_minify(p) }, r#"{"a": }"#).unwrap_err();
+        assert!(error.contains("position"), "{error}");
+
+        let result = unsafe { vexy_json_format(ptr::null(), 2) };
+        assert!(result.json.is_null() && !result.error.is_null());
+        unsafe { vexy_json_free_result(result) };
+    }
+
+    #[test]
+    fn test_nul_in_output_is_escaped() {
+        let input = r#"{"a": "x\u0000y"}"#;
+        for result in [
+            call(|p| unsafe { vexy_json_parse(p) }, input),
+            call(|p| unsafe { vexy_json_minify(p) }, input),
+            call(|p| unsafe { vexy_json_format(p, 2) }, input),
+        ] {
+            let json = result.unwrap();
+            assert!(json.contains(r#""x\u0000y""#), "{json}");
+        }
+        let message = unsafe { CString::from_raw(to_c_string("a\0b")) };
+        assert_eq!(message.to_str(), Ok(r"a\u0000b"));
+    }
+}
@@ -0,0 +1,229 @@
+// this_file: crates/cli/src/http.rs
+
+//! Reading inputs from http(s) URLs, with the `http` feature.
+
+use crate::{
+    calculate_line_column, decode_utf8, print_error, process_content, report_watch_result, CliArgs,
+    CliError, Result,
+};
+use colored::*;
+use encoding_rs::{DecoderResult, Encoding, UTF_8};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use std::error::Error as _;
+use std::sync::OnceLock;
+use std::time::Duration;
+use vexy_json_core::error::ErrorCode;
+
+/// A fetched input, with the entity tag to ask the server whether it changed.
+pub(crate) struct Fetched {
+    pub(crate) content: String,
+    pub(crate) etag: Option<String>,
+}
+
+/// Fetches `url`, giving up after `timeout`.
+pub(crate) fn fetch(url: &str, timeout: Duration) -> Result<Fetched> {
+    // The blocking client may not run on a thread that drives the async
+    // runtime, as main does
+    tokio::task::block_in_place(|| fetch_if_changed(url, timeout, None))
+        .map(|fetched| fetched.expect(UNCONDITIONAL))
+}
+
+const UNCONDITIONAL: &str = "a request without If-None-Match is never answered with 304";
+
+/// Fetches `url` unless the server reports that it still matches `etag`,
+/// on a blocking thread of its own so that the runtime goes on watching
+/// files while the request waits.
+async fn poll(url: &str, timeout: Duration, etag: Option<&str>) -> Result<Option<Fetched>> {
+    let url = url.to_string();
+    let etag = etag.map(str::to_string);
+    tokio::task::spawn_blocking(move || fetch_if_changed(&url, timeout, etag.as_deref()))
+        .await
+        .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+}
+
+/// Fetches `url` unless the server reports that it still matches `etag`.
+///
+/// Proxies are taken from the `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY`
+/// environment variables.
+fn fetch_if_changed(url: &str, timeout: Duration, etag: Option<&str>) -> Result<Option<Fetched>> {
+    let failed = |error: reqwest::Error| CliError::FetchFailed {
+        url: url.to_string(),
+        message: describe(error),
+    };
+
+    let mut request = client().map_err(failed)?.get(url).timeout(timeout);
+    if let Some(etag) = etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    let response = request.send().map_err(failed)?;
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED && etag.is_some() {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(CliError::HttpStatus {
+            url: url.to_string(),
+            status,
+        });
+    }
+
+    let etag = header(&response, ETAG).map(str::to_string);
+    let charset = header(&response, CONTENT_TYPE)
+        .and_then(charset)
+        .map(str::to_string);
+    let bytes = response.bytes().map_err(failed)?;
+    let content = decode(bytes.to_vec(), charset.as_deref(), url)?;
+    Ok(Some(Fetched { content, etag }))
+}
+
+fn client() -> reqwest::Result<&'static Client> {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+    let client = Client::builder()
+        .user_agent(concat!("vexy_json/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+fn header(response: &Response, name: reqwest::header::HeaderName) -> Option<&str> {
+    response.headers().get(name)?.to_str().ok()
+}
+
+/// Returns the `charset` parameter of a Content-Type header.
+fn charset(content_type: &str) -> Option<&str> {
+    content_type.split(';').skip(1).find_map(|parameter| {
+        let (name, value) = parameter.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// Decodes a response body in the charset the server declares, UTF-8 if
+/// none. A byte order mark takes precedence, as it does in browsers.
+///
+/// UTF-8 is decoded as files are, and malformed input in any charset is an
+/// error at the offending byte rather than replaced.
+fn decode(bytes: Vec<u8>, charset: Option<&str>, url: &str) -> Result<String> {
+    let declared = match charset {
+        None => UTF_8,
+        Some(label) => {
+            Encoding::for_label(label.as_bytes()).ok_or_else(|| CliError::FetchFailed {
+                url: url.to_string(),
+                message: format!("unsupported charset '{label}'"),
+            })?
+        }
+    };
+    let (encoding, bom_len) = Encoding::for_bom(&bytes).unwrap_or((declared, 0));
+    if encoding == UTF_8 {
+        // A UTF-8 byte order mark is skipped by the parser, as for files
+        return decode_utf8(bytes, url);
+    }
+
+    let body = &bytes[bom_len..];
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let capacity = decoder
+        .max_utf8_buffer_length_without_replacement(body.len())
+        .expect("response body too large to decode");
+    let mut content = String::with_capacity(capacity);
+    let (result, read) = decoder.decode_to_string_without_replacement(body, &mut content, true);
+    match result {
+        DecoderResult::InputEmpty => Ok(content),
+        DecoderResult::Malformed(malformed, unread) => {
+            let position = bom_len + read - malformed as usize - unread as usize;
+            let (line, col) = calculate_line_column(&content, content.len());
+            Err(CliError::ParseError {
+                file: url.to_string(),
+                line,
+                col,
+                message: format!(
+                    "Invalid {} sequence at position {position}",
+                    encoding.name()
+                ),
+                code: ErrorCode::Custom,
+            })
+        }
+        DecoderResult::OutputFull => unreachable!("the buffer fits the whole body"),
+    }
+}
+
+/// Describes a request failure with its causes, such as a refused
+/// connection, leaving out the URL reported next to it.
+fn describe(error: reqwest::Error) -> String {
+    let error = error.without_url();
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+/// A URL polled in watch mode.
+struct Polled {
+    url: String,
+    etag: Option<String>,
+    /// The content last processed, or `None` if the last request failed
+    content: Option<String>,
+}
+
+/// Processes each URL, then requests it again every `--poll-interval`,
+/// processing it whenever its content changed.
+///
+/// The server is asked with If-None-Match whether the content changed when
+/// it sent an ETag; otherwise the content is compared with the last one.
+/// A failing request is reported once, not on every poll.
+pub(crate) async fn watch_urls(urls: Vec<String>, args: &CliArgs) -> Result<()> {
+    if urls.is_empty() {
+        return Ok(());
+    }
+    let timeout = Duration::from_secs(args.timeout);
+
+    let mut polled = Vec::with_capacity(urls.len());
+    for url in urls {
+        let fetched = poll(&url, timeout, None).await;
+        let (etag, content) = match fetched.map(|fetched| fetched.expect(UNCONDITIONAL)) {
+            Ok(fetched) => {
+                report_watch_result(process_content(&fetched.content, &url, args), args)?;
+                (fetched.etag, Some(fetched.content))
+            }
+            Err(e) => {
+                print_error(&e, args);
+                (None, None)
+            }
+        };
+        polled.push(Polled { url, etag, content });
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_secs(args.poll_interval));
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        for input in &mut polled {
+            let etag = input.content.as_ref().and(input.etag.as_deref());
+            match poll(&input.url, timeout, etag).await {
+                Ok(None) => {}
+                Ok(Some(fetched)) if input.content.as_ref() == Some(&fetched.content) => {
+                    input.etag = fetched.etag;
+                }
+                Ok(Some(fetched)) => {
+                    eprintln!("\n{} {}", "URL changed:".yellow(), input.url);
+                    let result = process_content(&fetched.content, &input.url, args);
+                    report_watch_result(result, args)?;
+                    input.etag = fetched.etag;
+                    input.content = Some(fetched.content);
+                }
+                Err(e) => {
+                    if input.content.take().is_some() {
+                        print_error(&e, args);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,30 +2,52 @@
 
 //! Enhanced vexy_json CLI with comprehensive JSON processing capabilities.
 
-use clap::{Args, Parser};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use colored::*;
 use rayon::prelude::*;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
 use std::fs;
-use std::io::{self, Read, Write};
-use std::path::PathBuf;
+use std::io::{self, BufRead, Read, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 use thiserror::Error;
-use tokio::time::{sleep, Duration};
+use tokio::time::{timeout, Duration};
 use vexy_json_core::ast::Value;
-use vexy_json_core::error::{EnhancedParseResult, ParsingTier, RepairType};
+use vexy_json_core::error::{
+    display_width, excerpt_line, expand_tabs, CompatWarning, EnhancedParseResult, ErrorCode,
+    ParsingTier, RepairType, DEFAULT_CONTEXT_WIDTH,
+};
+use vexy_json_core::format::{
+    strip_comments, to_compact_string, to_pretty_string_with_comments, KeyOrder, LineEnding,
+    SourceComments, StripCommentsOptions,
+};
 use vexy_json_core::{
-    parse_with_detailed_repair_tracking, parse_with_fallback, ParallelConfig, ParallelParser,
-    ParserOptions,
+    analyze, check_homogeneous, is_concatenated_with_options, normalize_keys,
+    parse_with_detailed_repair_tracking, parse_with_fallback, tokenize_with_options, FormatStyle,
+    KeyCase, NdJsonParser, ParallelConfig, ParallelParser, ParserBackend, ParserOptions,
 };
 
+#[cfg(feature = "http")]
+mod http;
+
 #[derive(Parser, Debug)]
 #[clap(
     name = "vexy_json", 
     version = env!("VEXY_JSON_VERSION", env!("CARGO_PKG_VERSION")), 
     about = "A forgiving JSON parser and processor",
-    long_about = "vexy_json processes JSON with forgiving syntax including comments, trailing commas, unquoted keys, and more."
+    long_about = "vexy_json processes JSON with forgiving syntax including comments, trailing commas, unquoted keys, and more.",
+    after_help = "Exit status: 0 if every input parsed, 1 if any input failed to parse, 2 on usage or I/O errors.\nOutput is only written once every input has parsed successfully.",
+    args_conflicts_with_subcommands = true
 )]
 struct CliArgs {
-    /// Input files to process (if none provided, reads from stdin)
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Input files to process (if none provided, reads from stdin); with the
+    /// http feature, http(s) URLs are fetched
     #[clap(value_name = "FILE")]
     files: Vec<PathBuf>,
 
@@ -33,6 +55,10 @@ struct CliArgs {
     #[clap(short = 'o', long = "output", value_name = "FILE")]
     output: Option<PathBuf>,
 
+    /// Write one output per input file into this directory, keeping file names
+    #[clap(long = "output-dir", value_name = "DIR", conflicts_with = "output")]
+    output_dir: Option<PathBuf>,
+
     /// Pretty print with indentation
     #[clap(short = 'p', long = "pretty", conflicts_with = "compact")]
     pretty: bool,
@@ -45,6 +71,19 @@ struct CliArgs {
     #[clap(short = 'c', long = "compact")]
     compact: bool,
 
+    /// Line break to write between lines of pretty and NDJSON output
+    #[clap(long = "line-ending", value_enum, default_value = "lf")]
+    line_ending: LineEndingArg,
+
+    /// End the output with exactly one line break (the default when writing
+    /// to files)
+    #[clap(long = "trailing-newline", overrides_with = "no_trailing_newline")]
+    trailing_newline: bool,
+
+    /// End the output without a line break, even when writing to files
+    #[clap(long = "no-trailing-newline", overrides_with = "trailing_newline")]
+    no_trailing_newline: bool,
+
     /// Validate JSON without output
     #[clap(short = 'v', long = "validate")]
     validate: bool,
@@ -53,14 +92,44 @@ struct CliArgs {
     #[clap(short = 'w', long = "watch")]
     watch: bool,
 
+    /// Seconds to wait for each http(s) input to be fetched
+    #[cfg(feature = "http")]
+    #[clap(long = "timeout", value_name = "SECONDS", default_value = "30")]
+    timeout: u64,
+
+    /// Seconds between requests for each http(s) input in watch mode
+    #[cfg(feature = "http")]
+    #[clap(
+        long = "poll-interval",
+        value_name = "SECONDS",
+        default_value = "5",
+        requires = "watch",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    poll_interval: u64,
+
     /// Process multiple files in parallel
     #[clap(short = 'j', long = "parallel")]
     parallel: bool,
 
+    /// Number of files to process at once with --parallel and --watch
+    /// (default: number of CPU cores)
+    #[clap(long = "jobs", value_name = "N")]
+    jobs: Option<NonZeroUsize>,
+
     /// Show detailed error information
     #[clap(long = "verbose-errors")]
     verbose_errors: bool,
 
+    /// Characters of a long line to show either side of an error with
+    /// --verbose-errors
+    #[clap(long = "context-width", value_name = "N", default_value_t = DEFAULT_CONTEXT_WIDTH)]
+    context_width: usize,
+
+    /// Format of error reports on stderr
+    #[clap(long = "error-format", value_enum, default_value = "human")]
+    error_format: ErrorFormat,
+
     /// Enable JSON repair functionality
     #[clap(short = 'r', long = "repair")]
     repair: bool,
@@ -73,6 +142,34 @@ struct CliArgs {
     #[clap(long = "fallback")]
     fallback: bool,
 
+    /// Warn about every construct outside strict JSON, suggesting the strict form
+    #[clap(long = "warn-compat", conflicts_with_all = ["ndjson", "concat", "parallel_parse"])]
+    warn_compat: bool,
+
+    /// Fail on any construct outside strict JSON, reporting each one
+    #[clap(long = "deny-compat", conflicts_with_all = ["ndjson", "concat", "parallel_parse"])]
+    deny_compat: bool,
+
+    /// Fail if an array mixes value types, reporting each element whose type
+    /// differs from the first in its array; integers and floats are alike
+    #[clap(
+        long = "check-homogeneous",
+        conflicts_with_all = ["ndjson", "concat", "parallel_parse", "strip_comments"]
+    )]
+    check_homogeneous: bool,
+
+    /// Rewrite every object key in one naming convention, failing if two
+    /// keys of an object would get the same name
+    #[clap(
+        long = "normalize-keys",
+        value_enum,
+        value_name = "CASE",
+        conflicts_with_all = [
+            "ndjson", "concat", "parallel_parse", "strip_comments", "preserve_comments",
+        ]
+    )]
+    normalize_keys: Option<KeyCaseArg>,
+
     /// Enable parallel parsing for large JSON files
     #[clap(long = "parallel-parse")]
     parallel_parse: bool,
@@ -89,11 +186,157 @@ struct CliArgs {
     #[clap(long = "ndjson")]
     ndjson: bool,
 
+    /// Parse concatenated documents such as {"a":1}{"b":2}; detected
+    /// automatically when objects or arrays directly follow each other
+    #[clap(long = "concat", conflicts_with = "ndjson")]
+    concat: bool,
+
+    /// Write the elements of a top-level array as NDJSON, each compactly on
+    /// a line of its own
+    #[clap(
+        long = "to-ndjson",
+        conflicts_with_all = [
+            "ndjson", "concat", "parallel_parse", "strip_comments", "preserve_comments",
+            "pretty", "validate", "warn_compat", "deny_compat", "check_homogeneous",
+            "normalize_keys", "repair_details",
+        ]
+    )]
+    to_ndjson: bool,
+
+    /// Read NDJSON a line at a time and write its values as one array; with
+    /// --to-ndjson, each value is written as soon as its line is read
+    #[clap(
+        long = "from-ndjson",
+        conflicts_with_all = [
+            "ndjson", "concat", "parallel_parse", "strip_comments", "preserve_comments",
+            "validate", "stats", "watch", "output_dir", "parallel", "warn_compat",
+            "deny_compat", "check_homogeneous", "normalize_keys", "repair_details",
+            "debug_tokens",
+        ]
+    )]
+    from_ndjson: bool,
+
+    /// Print parse statistics to stderr after processing
+    #[clap(long = "stats")]
+    stats: bool,
+
+    /// Format of the --stats report
+    #[clap(
+        long = "stats-format",
+        value_enum,
+        default_value = "human",
+        requires = "stats"
+    )]
+    stats_format: StatsFormat,
+
+    /// Remove comments and trailing commas, leaving every other byte as
+    /// written, instead of parsing
+    #[clap(
+        long = "strip-comments",
+        conflicts_with_all = [
+            "validate", "stats", "ndjson", "concat", "parallel_parse",
+            "warn_compat", "deny_compat", "pretty", "compact",
+        ]
+    )]
+    strip_comments: bool,
+
+    /// Keep every line in place when stripping comments, so that line
+    /// numbers still match the input
+    #[clap(long = "keep-newlines", requires = "strip_comments")]
+    keep_newlines: bool,
+
+    /// Keep comments when reformatting, each next to the value it belongs
+    /// to; the output is then JSONC
+    #[clap(
+        long = "preserve-comments",
+        conflicts_with_all = [
+            "compact", "strip_comments", "validate", "ndjson", "concat", "parallel_parse",
+        ]
+    )]
+    preserve_comments: bool,
+
+    /// Print the tokens the parser sees, one per line, instead of parsing
+    #[clap(long = "debug-tokens", conflicts_with_all = ["validate", "watch", "stats"])]
+    debug_tokens: bool,
+
     /// Parser options
     #[clap(flatten)]
     parser_opts: ParserOptionsArgs,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum StatsFormat {
+    /// Readable summary
+    Human,
+    /// A JSON object with per-file entries and totals
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ErrorFormat {
+    /// Readable messages, with source context under --verbose-errors
+    Human,
+    /// One JSON object per error and line, with its code, position and
+    /// suggestions
+    Json,
+}
+
+/// The choices of [`LineEnding`] on the command line.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum LineEndingArg {
+    /// \n
+    Lf,
+    /// \r\n
+    Crlf,
+}
+
+impl From<LineEndingArg> for LineEnding {
+    fn from(line_ending: LineEndingArg) -> Self {
+        match line_ending {
+            LineEndingArg::Lf => LineEnding::Lf,
+            LineEndingArg::Crlf => LineEnding::CrLf,
+        }
+    }
+}
+
+/// The choices of [`KeyCase`] on the command line.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyCaseArg {
+    /// userId
+    #[value(name = "camelCase")]
+    Camel,
+    /// user_id
+    #[value(name = "snake_case")]
+    Snake,
+    /// user-id
+    #[value(name = "kebab-case")]
+    Kebab,
+    /// userid
+    #[value(name = "lowercase")]
+    Lower,
+}
+
+impl From<KeyCaseArg> for KeyCase {
+    fn from(case: KeyCaseArg) -> Self {
+        match case {
+            KeyCaseArg::Camel => KeyCase::CamelCase,
+            KeyCaseArg::Snake => KeyCase::SnakeCase,
+            KeyCaseArg::Kebab => KeyCase::KebabCase,
+            KeyCaseArg::Lower => KeyCase::LowerCase,
+        }
+    }
+}
+
 #[derive(Args, Debug)]
 struct ParserOptionsArgs {
     /// Disable comment parsing
@@ -123,6 +366,37 @@ struct ParserOptionsArgs {
     /// Maximum parsing depth
     #[clap(long = "max-depth", default_value = "128")]
     max_depth: usize,
+
+    /// Parser implementation for input that is not strict JSON
+    #[clap(long = "backend", value_enum, default_value = "auto")]
+    backend: Backend,
+}
+
+/// The choices of [`ParserBackend`] on the command line.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Backend {
+    /// Pick per input: the hybrid parser unless nesting may be too deep for it
+    Auto,
+    /// The default parser, supporting every option
+    Hybrid,
+    /// Recursive descent parser
+    Recursive,
+    /// Stack-based parser for deeply nested input
+    Iterative,
+    /// Allocation-optimized parser
+    OptimizedV3,
+}
+
+impl From<Backend> for ParserBackend {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Auto => ParserBackend::Auto,
+            Backend::Hybrid => ParserBackend::Hybrid,
+            Backend::Recursive => ParserBackend::Recursive,
+            Backend::Iterative => ParserBackend::Iterative,
+            Backend::OptimizedV3 => ParserBackend::OptimizedV3,
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -133,6 +407,7 @@ enum CliError {
         line: usize,
         col: usize,
         message: String,
+        code: ErrorCode,
     },
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
@@ -140,200 +415,713 @@ enum CliError {
     WatchError(#[from] notify::Error),
     #[error("File not found: {0}")]
     FileNotFound(String),
+    #[error("{0}")]
+    Usage(String),
+    #[error("{file}: {message}")]
+    CheckFailed { file: String, message: String },
+    #[cfg(feature = "http")]
+    #[error("{url}: HTTP {status}")]
+    HttpStatus {
+        url: String,
+        status: reqwest::StatusCode,
+    },
+    #[cfg(feature = "http")]
+    #[error("Could not fetch {url}: {message}")]
+    FetchFailed { url: String, message: String },
+}
+
+impl CliError {
+    /// Returns the process exit status this error maps to.
+    fn exit_code(&self) -> i32 {
+        match self {
+            CliError::ParseError { .. } | CliError::CheckFailed { .. } => EXIT_PARSE_FAILURE,
+            _ => EXIT_USAGE_OR_IO,
+        }
+    }
+
+    /// Returns the parser error code this error corresponds to, if any.
+    fn code(&self) -> Option<ErrorCode> {
+        match self {
+            CliError::ParseError { code, .. } => Some(*code),
+            CliError::CheckFailed { .. } => Some(ErrorCode::Custom),
+            CliError::IoError(_) | CliError::FileNotFound(_) => Some(ErrorCode::Io),
+            #[cfg(feature = "http")]
+            CliError::HttpStatus { .. } | CliError::FetchFailed { .. } => Some(ErrorCode::Io),
+            CliError::WatchError(_) | CliError::Usage(_) => None,
+        }
+    }
+
+    /// Returns whether this error is a closed stdout, which is not reported.
+    fn is_broken_pipe(&self) -> bool {
+        matches!(self, CliError::IoError(e) if e.kind() == io::ErrorKind::BrokenPipe)
+    }
 }
 
 type Result<T> = std::result::Result<T, CliError>;
 
+/// Exit status when every input parsed successfully.
+const EXIT_SUCCESS: i32 = 0;
+/// Exit status when at least one input failed to parse.
+const EXIT_PARSE_FAILURE: i32 = 1;
+/// Exit status for usage and I/O errors (clap uses the same code for bad arguments).
+const EXIT_USAGE_OR_IO: i32 = 2;
+
 #[tokio::main]
 async fn main() {
     let args = CliArgs::parse();
 
-    let result = if args.watch {
-        watch_mode(&args).await
-    } else if args.files.is_empty() {
-        process_stdin(&args).await
-    } else if args.parallel && args.files.len() > 1 {
-        process_files_parallel(&args)
+    if let Some(Command::Completions { shell }) = args.command {
+        let mut stdout = io::stdout().lock();
+        clap_complete::generate(
+            shell,
+            &mut CliArgs::command(),
+            env!("CARGO_BIN_NAME"),
+            &mut stdout,
+        );
+        std::process::exit(EXIT_SUCCESS);
+    }
+
+    let code = if args.watch {
+        match watch_mode(&args).await {
+            Ok(()) => EXIT_SUCCESS,
+            Err(e) => report_error(&e, &args),
+        }
     } else {
-        process_files_sequential(&args).await
+        match run(&args) {
+            Ok(code) => code,
+            Err(e) => report_error(&e, &args),
+        }
     };
 
-    if let Err(e) = result {
-        print_error(&e, &args);
-        std::process::exit(1);
+    std::process::exit(code);
+}
+
+/// Prints `error` unless it is a broken pipe, and returns the matching exit status.
+fn report_error(error: &CliError, args: &CliArgs) -> i32 {
+    if error.is_broken_pipe() {
+        return EXIT_SUCCESS;
     }
+    print_error(error, args);
+    error.exit_code()
 }
 
-async fn process_stdin(args: &CliArgs) -> Result<()> {
-    let mut input = String::new();
-    io::stdin().read_to_string(&mut input)?;
+/// Processes every input and writes the results, returning the exit status.
+///
+/// All inputs are parsed before anything is written, so a failing input never
+/// leaves partial output behind.
+fn run(args: &CliArgs) -> Result<i32> {
+    check_output_args(args)?;
+    if args.debug_tokens {
+        return debug_tokens(args);
+    }
+    if args.from_ndjson {
+        return convert_from_ndjson(args);
+    }
+    let started = Instant::now();
+
+    let results: Vec<Result<(String, ParseStats)>> = if args.files.is_empty() {
+        match read_stdin()? {
+            Some(input) => vec![process_content(&input, "<stdin>", args)],
+            None => {
+                if !args.validate {
+                    eprintln!("{}", "No input provided".yellow());
+                }
+                return Ok(EXIT_SUCCESS);
+            }
+        }
+    } else if args.parallel && args.files.len() > 1 {
+        process_files(&file_pool(args)?, &args.files, args)
+    } else {
+        args.files
+            .iter()
+            .map(|file| process_single_file(file, args))
+            .collect()
+    };
+
+    if args.stats {
+        let sources: Vec<String> = if args.files.is_empty() {
+            vec!["<stdin>".to_string()]
+        } else {
+            args.files.iter().map(|f| f.display().to_string()).collect()
+        };
+        print_stats(&sources, &results, started.elapsed(), args.stats_format);
+    }
 
-    if input.trim().is_empty() {
-        if !args.validate {
-            eprintln!("{}", "No input provided".yellow());
+    let mut code = EXIT_SUCCESS;
+    for result in &results {
+        if let Err(e) = result {
+            code = code.max(e.exit_code());
         }
-        return Ok(());
     }
 
-    process_content(&input, "<stdin>", args)?;
-    Ok(())
+    if args.validate {
+        let mut report = String::new();
+        for result in &results {
+            match result {
+                Ok((status, _)) => report.push_str(&format!("{status}\n")),
+                Err(e) => print_error(e, args),
+            }
+        }
+        let invalid = results.iter().filter(|result| result.is_err()).count();
+        report.push_str(&format!(
+            "{} valid, {invalid} invalid\n",
+            results.len() - invalid
+        ));
+        write_stdout(&report)?;
+        return Ok(code);
+    }
+
+    if code != EXIT_SUCCESS {
+        for result in &results {
+            if let Err(e) = result {
+                print_error(e, args);
+            }
+        }
+        return Ok(code);
+    }
+
+    if let Some(dir) = &args.output_dir {
+        fs::create_dir_all(dir)?;
+        for (file, result) in args.files.iter().zip(&results) {
+            if let (Some(name), Ok((output, _))) = (file.file_name(), result) {
+                fs::write(dir.join(name), &*with_trailing_newline(output, true, args))?;
+            }
+        }
+    } else {
+        let outputs: Vec<&str> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok().map(|(output, _)| output.as_str()))
+            .collect();
+        let line_ending = LineEnding::from(args.line_ending).as_str();
+        write_output(&outputs.join(line_ending), args)?;
+    }
+
+    Ok(EXIT_SUCCESS)
 }
 
-async fn process_files_sequential(args: &CliArgs) -> Result<()> {
-    for file in &args.files {
-        process_single_file(file, args)?;
+/// Rejects output flag combinations whose destination would be ambiguous.
+fn check_output_args(args: &CliArgs) -> Result<()> {
+    if args.output.is_some() && args.files.len() > 1 {
+        return Err(CliError::Usage(
+            "--output cannot be used with multiple input files; use --output-dir instead"
+                .to_string(),
+        ));
     }
+
+    if args.output_dir.is_some() {
+        if args.files.is_empty() {
+            return Err(CliError::Usage(
+                "--output-dir requires at least one input file".to_string(),
+            ));
+        }
+        let mut names = HashSet::new();
+        for file in &args.files {
+            match file.file_name() {
+                Some(name) if names.insert(name) => {}
+                _ => {
+                    return Err(CliError::Usage(format!(
+                        "cannot write '{}' to --output-dir: duplicate or missing file name",
+                        file.display()
+                    )))
+                }
+            }
+        }
+    }
+
     Ok(())
 }
 
-fn process_files_parallel(args: &CliArgs) -> Result<()> {
-    let results: std::result::Result<Vec<_>, _> = args
-        .files
-        .par_iter()
-        .map(|file| process_single_file(file, args))
-        .collect();
+/// Reads all of stdin as raw bytes, returning `None` when it is blank.
+fn read_stdin() -> Result<Option<String>> {
+    let mut bytes = Vec::new();
+    io::stdin().read_to_end(&mut bytes)?;
+    let input = decode_utf8(bytes, "<stdin>")?;
+    Ok((!input.trim().is_empty()).then_some(input))
+}
 
-    results?;
-    Ok(())
+/// Decodes input bytes, reporting invalid UTF-8 as a parse error at the offending byte.
+fn decode_utf8(bytes: Vec<u8>, source: &str) -> Result<String> {
+    String::from_utf8(bytes).map_err(|e| {
+        let position = e.utf8_error().valid_up_to();
+        let valid = String::from_utf8_lossy(&e.as_bytes()[..position]);
+        let (line, col) = calculate_line_column(&valid, position);
+        CliError::ParseError {
+            file: source.to_string(),
+            line,
+            col,
+            message: format!("Invalid UTF-8 sequence at position {position}"),
+            code: ErrorCode::InvalidUtf8,
+        }
+    })
 }
 
-fn process_single_file(file: &PathBuf, args: &CliArgs) -> Result<()> {
+fn read_file(file: &Path, args: &CliArgs) -> Result<String> {
+    if let Some(url) = input_url(file) {
+        return read_url(url, args);
+    }
     if !file.exists() {
         return Err(CliError::FileNotFound(file.display().to_string()));
     }
+    decode_utf8(fs::read(file)?, &file.display().to_string())
+}
 
-    let content = fs::read_to_string(file)?;
-    process_content(&content, &file.display().to_string(), args)?;
-    Ok(())
+/// Returns `file` as a URL if it names an http(s) resource.
+fn input_url(file: &Path) -> Option<&str> {
+    let file = file.to_str()?;
+    let is_scheme = |scheme: &str| {
+        file.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    };
+    (is_scheme("http://") || is_scheme("https://")).then_some(file)
+}
+
+#[cfg(feature = "http")]
+fn read_url(url: &str, args: &CliArgs) -> Result<String> {
+    http::fetch(url, Duration::from_secs(args.timeout)).map(|fetched| fetched.content)
+}
+
+#[cfg(not(feature = "http"))]
+fn read_url(url: &str, _args: &CliArgs) -> Result<String> {
+    Err(CliError::Usage(format!(
+        "cannot read {url}: URL inputs need vexy_json built with the http feature"
+    )))
+}
+
+fn process_single_file(file: &Path, args: &CliArgs) -> Result<(String, ParseStats)> {
+    let content = read_file(file, args)?;
+    process_content(&content, &file.display().to_string(), args)
+}
+
+/// Creates the worker pool that processes files, with `--jobs` threads.
+fn file_pool(args: &CliArgs) -> Result<rayon::ThreadPool> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.map_or(0, NonZeroUsize::get))
+        .build()
+        .map_err(|e| CliError::IoError(io::Error::new(io::ErrorKind::Other, e)))
+}
+
+/// Processes `files` on `pool`, returning their results in the same order.
+fn process_files(
+    pool: &rayon::ThreadPool,
+    files: &[PathBuf],
+    args: &CliArgs,
+) -> Vec<Result<(String, ParseStats)>> {
+    pool.install(|| {
+        files
+            .par_iter()
+            .map(|file| process_single_file(file, args))
+            .collect()
+    })
+}
+
+/// Prints the token stream the parser sees for each input, one token per
+/// line with its byte span and source text.
+fn debug_tokens(args: &CliArgs) -> Result<i32> {
+    let options = create_parser_options(&args.parser_opts);
+    let inputs: Vec<(String, String)> = if args.files.is_empty() {
+        read_stdin()?
+            .map(|input| ("<stdin>".to_string(), input))
+            .into_iter()
+            .collect()
+    } else {
+        args.files
+            .iter()
+            .map(|file| Ok((file.display().to_string(), read_file(file, args)?)))
+            .collect::<Result<_>>()?
+    };
+
+    let mut dump = String::new();
+    let mut code = EXIT_SUCCESS;
+    for (source, content) in &inputs {
+        if inputs.len() > 1 {
+            dump.push_str(&format!("==> {source} <==\n"));
+        }
+        match tokenize_with_options(content, &options) {
+            Ok(tokens) => {
+                for (token, span, text) in tokens {
+                    dump.push_str(&format!(
+                        "{}..{}\t{token:?}\t{text:?}\n",
+                        span.start, span.end
+                    ));
+                }
+            }
+            Err(error) => {
+                let error = format_parse_error(&error, source, content);
+                code = code.max(error.exit_code());
+                print_error(&error, args);
+            }
+        }
+    }
+
+    if code == EXIT_SUCCESS {
+        write_output(&dump, args)?;
+    }
+    Ok(code)
 }
 
-fn process_content(content: &str, source: &str, args: &CliArgs) -> Result<()> {
+/// Parses `content` and returns the text to emit: the formatted value, or a
+/// status line in validate mode, along with statistics about the parse.
+fn process_content(content: &str, source: &str, args: &CliArgs) -> Result<(String, ParseStats)> {
+    let start = Instant::now();
+    let parser_options = ParserOptions {
+        collect_compat_warnings: args.warn_compat || args.deny_compat,
+        ..create_parser_options(&args.parser_opts)
+    };
     // Check if parallel parsing is requested
-    if args.ndjson {
+    let (output, mut stats) = if args.strip_comments {
+        let options = StripCommentsOptions {
+            keep_newlines: args.keep_newlines,
+            ..Default::default()
+        };
+        let output = strip_comments(content, &options)
+            .map_err(|e| format_parse_error(&e, source, content))?;
+        (output, ParseStats::default())
+    } else if args.to_ndjson {
+        process_to_ndjson_content(content, source, args)?
+    } else if args.ndjson {
         // Parse as NDJSON
-        return process_ndjson_content(content, source, args);
+        process_ndjson_content(content, source, args)?
+    } else if args.concat || reads_as_concatenated(content, &parser_options) {
+        process_concatenated_content(content, source, args)?
     } else if args.parallel_parse {
         // Use parallel parsing for large files
-        return process_parallel_content(content, source, args);
+        process_parallel_content(content, source, args)?
+    } else {
+        process_forgiving_content(content, source, parser_options, args)?
+    };
+    stats.bytes = content.len();
+    stats.elapsed = start.elapsed();
+    Ok((output, stats))
+}
+
+/// Reads each input as NDJSON, a line at a time, and writes one array of its
+/// values per input; with --to-ndjson, each value is instead written as a
+/// line of its own as soon as it is read, keeping its key order.
+///
+/// Arrays are only written once every input has parsed, whereas values
+/// passed through may already be written when a later line fails.
+fn convert_from_ndjson(args: &CliArgs) -> Result<i32> {
+    let mut passthrough: Option<Box<dyn Write>> = match (&args.output, args.to_ndjson) {
+        (_, false) => None,
+        (Some(output), true) => Some(Box::new(io::BufWriter::new(fs::File::create(output)?))),
+        (None, true) => Some(Box::new(io::BufWriter::new(io::stdout().lock()))),
+    };
+
+    let inputs: Vec<Option<&PathBuf>> = if args.files.is_empty() {
+        vec![None]
+    } else {
+        args.files.iter().map(Some).collect()
+    };
+    let mut arrays = Vec::new();
+    let mut code = EXIT_SUCCESS;
+    for input in inputs {
+        let source = input.map_or("<stdin>".to_string(), |file| file.display().to_string());
+        let result = match open_input(input, args) {
+            Ok(reader) => read_ndjson(reader, &source, args, passthrough.as_mut()),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(values) => arrays.push(Value::Array(values)),
+            Err(e) if e.is_broken_pipe() => return Err(e),
+            Err(e) => {
+                code = code.max(e.exit_code());
+                print_error(&e, args);
+            }
+        }
     }
 
-    let parser_options = create_parser_options(&args.parser_opts);
+    if let Some(writer) = &mut passthrough {
+        writer.flush()?;
+    } else if code == EXIT_SUCCESS {
+        let outputs: Vec<String> = arrays
+            .iter()
+            .map(|array| format_output(array, &KeyOrder::default(), args))
+            .collect();
+        let line_ending = LineEnding::from(args.line_ending).as_str();
+        write_output(&outputs.join(line_ending), args)?;
+    }
+    Ok(code)
+}
 
+/// Opens a file, or stdin if `file` is `None`, to be read a line at a time.
+/// URLs are fetched whole.
+fn open_input(file: Option<&PathBuf>, args: &CliArgs) -> Result<Box<dyn BufRead>> {
+    match file {
+        None => Ok(Box::new(io::stdin().lock())),
+        Some(file) if input_url(file).is_some() => Ok(Box::new(io::Cursor::new(
+            read_file(file, args)?.into_bytes(),
+        ))),
+        Some(file) if !file.exists() => Err(CliError::FileNotFound(file.display().to_string())),
+        Some(file) => Ok(Box::new(io::BufReader::new(fs::File::open(file)?))),
+    }
+}
+
+/// Feeds `reader` to the streaming NDJSON parser a line at a time. Values
+/// are written to `passthrough` as their lines are parsed if it is given,
+/// and collected otherwise.
+fn read_ndjson(
+    mut reader: impl BufRead,
+    source: &str,
+    args: &CliArgs,
+    mut passthrough: Option<&mut Box<dyn Write>>,
+) -> Result<Vec<Value>> {
+    let options = create_parser_options(&args.parser_opts);
+    let mut parser = NdJsonParser::with_options(options.clone());
+    let mut values = Vec::new();
+    let mut bytes = Vec::new();
+    let mut number = 0;
+    while reader.read_until(b'\n', &mut bytes)? > 0 {
+        number += 1;
+        let at_line = |mut error: CliError| {
+            if let CliError::ParseError { line, .. } = &mut error {
+                *line = number;
+            }
+            error
+        };
+        let mut line = decode_utf8(std::mem::take(&mut bytes), source).map_err(at_line)?;
+        // Ending every line lets the parser yield its value right away
+        if !line.ends_with('\n') {
+            line.push('\n');
+        }
+        let parsed = parser
+            .feed(&line)
+            .map_err(|e| at_line(format_parse_error(&e, source, &line)))?;
+        match &mut passthrough {
+            Some(writer) => {
+                let order = KeyOrder::from_source_with_options(&line, &options);
+                for value in &parsed {
+                    writer.write_all(ndjson_line(value, &order, args).as_bytes())?;
+                }
+            }
+            None => values.extend(parsed),
+        }
+    }
+    Ok(values)
+}
+
+/// Whether to read `content` as concatenated documents without `--concat`:
+/// when `options` find one document directly followed by another, unless
+/// they collect compatibility warnings, which documents parsed apart do not
+/// report.
+fn reads_as_concatenated(content: &str, options: &ParserOptions) -> bool {
+    !options.collect_compat_warnings && is_concatenated_with_options(content, options)
+}
+
+/// Parses `content` with the tiered fallback parser.
+fn process_forgiving_content(
+    content: &str,
+    source: &str,
+    parser_options: ParserOptions,
+    args: &CliArgs,
+) -> Result<(String, ParseStats)> {
     // Choose parsing strategy based on CLI options
     if args.repair_details {
         // Use detailed repair tracking
         let result = parse_with_detailed_repair_tracking(content, parser_options);
         if result.errors.is_empty() {
-            if args.validate {
-                print_validation_result_with_repair(source, &result, args);
+            print_repair_summary(&result, args);
+            report_type_mismatches(&result.value, source, args)?;
+            let value = rename_keys(&result.value, source, args)?;
+            let output = if args.validate {
+                format_validation_result_with_repair(source, &result, args)
             } else {
-                let formatted = format_output(&result.value, args);
-                write_output(&formatted, args)?;
+                format_parsed(&value, content, args)
+            };
+            let stats = ParseStats::of_result(&result);
+            // Values from --backend iterative may nest deeper than dropping
+            // them the usual way allows
+            if let Cow::Owned(renamed) = value {
+                renamed.drop_iteratively();
             }
-            print_repair_summary(&result, args);
+            result.value.drop_iteratively();
+            Ok((output, stats))
         } else {
             // Create error from the first error in the result
             let first_error = &result.errors[0];
             let cli_error = format_parse_error(first_error, source, content);
-            return Err(cli_error);
+            Err(cli_error)
         }
     } else {
         // Use fallback parsing by default (fast → forgiving → repair)
         let result = parse_with_fallback(content, parser_options);
         if result.errors.is_empty() {
-            if args.validate {
-                print_validation_result_with_repair(source, &result, args);
-            } else {
-                let formatted = format_output(&result.value, args);
-                write_output(&formatted, args)?;
-            }
+            report_compat_warnings(&result.compat_warnings, source, content, args)?;
+            report_type_mismatches(&result.value, source, args)?;
+            let value = rename_keys(&result.value, source, args)?;
             if args.repair_details {
                 print_repair_info(&result, args);
             }
+            let output = if args.validate {
+                format_validation_result_with_repair(source, &result, args)
+            } else {
+                format_parsed(&value, content, args)
+            };
+            let stats = ParseStats::of_result(&result);
+            // Values from --backend iterative may nest deeper than dropping
+            // them the usual way allows
+            if let Cow::Owned(renamed) = value {
+                renamed.drop_iteratively();
+            }
+            result.value.drop_iteratively();
+            Ok((output, stats))
         } else {
             // Create error from the first error in the result
             let first_error = &result.errors[0];
             let cli_error = format_parse_error(first_error, source, content);
-            return Err(cli_error);
+            Err(cli_error)
         }
     }
-
-    Ok(())
 }
 
-fn process_parallel_content(content: &str, source: &str, args: &CliArgs) -> Result<()> {
+fn process_parallel_content(
+    content: &str,
+    source: &str,
+    args: &CliArgs,
+) -> Result<(String, ParseStats)> {
     let config = ParallelConfig {
         min_chunk_size: args.chunk_size,
         max_threads: args.max_threads,
         optimize_chunks: true,
+        ..Default::default()
     };
 
     let parser = ParallelParser::with_config(config);
 
     match parser.parse(content) {
         Ok(value) => {
-            if args.validate {
-                println!(
+            let output = if args.validate {
+                format!(
                     "{} {}",
                     "✓".green(),
                     format!("{source} is valid JSON").green()
-                );
+                )
             } else {
-                let formatted = format_output(&value, args);
-                write_output(&formatted, args)?;
-            }
-        }
-        Err(e) => {
-            let cli_error = format_parse_error(&e, source, content);
-            return Err(cli_error);
+                format_parsed(&value, content, args)
+            };
+            Ok((output, ParseStats::of_values([&value])))
         }
+        Err(e) => Err(format_parse_error(&e, source, content)),
     }
-
-    Ok(())
 }
 
-fn process_ndjson_content(content: &str, source: &str, args: &CliArgs) -> Result<()> {
+fn process_ndjson_content(
+    content: &str,
+    source: &str,
+    args: &CliArgs,
+) -> Result<(String, ParseStats)> {
     let config = ParallelConfig {
         min_chunk_size: args.chunk_size,
         max_threads: args.max_threads,
         optimize_chunks: true,
+        ..Default::default()
     };
 
     let parser = ParallelParser::with_config(config);
 
     match parser.parse_ndjson(content) {
-        Ok(values) => {
-            if args.validate {
-                println!(
-                    "{} {} parsed {} JSON objects",
-                    "✓".green(),
-                    source.green(),
-                    values.len()
-                );
-            } else {
-                for (i, value) in values.iter().enumerate() {
-                    let formatted = format_output(value, args);
-                    if i > 0 && args.pretty {
-                        println!(); // Add blank line between objects in pretty mode
-                    }
-                    write_output(&formatted, args)?;
-                    if !args.pretty {
-                        println!(); // Add newline for NDJSON output
-                    }
+        Ok(values) => Ok(format_documents(&values, source, args)),
+        Err(e) => Err(format_parse_error(&e, source, content)),
+    }
+}
+
+/// Parses `content`, which must hold a top-level array, and writes each of
+/// its elements as a line of NDJSON, keeping its key order.
+fn process_to_ndjson_content(
+    content: &str,
+    source: &str,
+    args: &CliArgs,
+) -> Result<(String, ParseStats)> {
+    let result = parse_with_fallback(content, create_parser_options(&args.parser_opts));
+    if let Some(error) = result.errors.first() {
+        return Err(format_parse_error(error, source, content));
+    }
+    let Value::Array(items) = &result.value else {
+        return Err(CliError::CheckFailed {
+            file: source.to_string(),
+            message: "--to-ndjson needs a top-level array".to_string(),
+        });
+    };
+
+    let order =
+        KeyOrder::from_source_with_options(content, &create_parser_options(&args.parser_opts));
+    let empty = KeyOrder::default();
+    let mut output = String::new();
+    for (i, item) in items.iter().enumerate() {
+        output.push_str(&ndjson_line(item, order.item(i).unwrap_or(&empty), args));
+    }
+    Ok((output, ParseStats::of_result(&result)))
+}
+
+/// Writes `value` compactly, ending the line as --line-ending asks. Line
+/// breaks inside strings are escaped, so the value takes a single line.
+fn ndjson_line(value: &Value, order: &KeyOrder, args: &CliArgs) -> String {
+    let line_ending = LineEnding::from(args.line_ending).as_str();
+    format!("{}{line_ending}", to_compact_string(value, order))
+}
+
+/// Parses `content` as concatenated documents, such as `{"a":1}{"b":2}`.
+fn process_concatenated_content(
+    content: &str,
+    source: &str,
+    args: &CliArgs,
+) -> Result<(String, ParseStats)> {
+    let config = ParallelConfig {
+        min_chunk_size: args.chunk_size,
+        max_threads: args.max_threads,
+        optimize_chunks: true,
+        ..Default::default()
+    };
+
+    let parser = ParallelParser::with_config(config);
+
+    match parser.parse_concatenated(content) {
+        Ok(mut values) => {
+            for value in &mut values {
+                report_type_mismatches(value, source, args)?;
+                if let Cow::Owned(renamed) = rename_keys(value, source, args)? {
+                    *value = renamed;
                 }
             }
+            Ok(format_documents(&values, source, args))
         }
-        Err(e) => {
-            let cli_error = format_parse_error(&e, source, content);
-            return Err(cli_error);
-        }
+        Err(e) => Err(format_parse_error(&e, source, content)),
     }
+}
 
-    Ok(())
+/// Formats the documents of a multi-document input, one per line.
+fn format_documents(values: &[Value], source: &str, args: &CliArgs) -> (String, ParseStats) {
+    let stats = ParseStats::of_values(values);
+    if args.validate {
+        return (
+            format!(
+                "{} {} parsed {} JSON objects",
+                "✓".green(),
+                source.green(),
+                values.len()
+            ),
+            stats,
+        );
+    }
+    let line_ending = LineEnding::from(args.line_ending).as_str();
+    let mut output = String::new();
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 && args.pretty {
+            output.push_str(line_ending); // Add blank line between objects in pretty mode
+        }
+        output.push_str(&format_output(value, &KeyOrder::default(), args));
+        if !args.pretty {
+            output.push_str(line_ending); // One document per line
+        }
+    }
+    (output, stats)
 }
 
+/// How long watch mode waits for further changes before processing a burst
+/// of them, such as a checkout rewriting many files at once.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
+
 async fn watch_mode(args: &CliArgs) -> Result<()> {
-    use notify::{EventKind, RecursiveMode, Watcher};
+    use notify::{RecursiveMode, Watcher};
     use tokio::sync::mpsc;
 
     if args.files.is_empty() {
@@ -341,6 +1129,22 @@ async fn watch_mode(args: &CliArgs) -> Result<()> {
         return Ok(());
     }
 
+    // URLs are polled rather than watched
+    let (urls, files): (Vec<PathBuf>, Vec<PathBuf>) = args
+        .files
+        .iter()
+        .cloned()
+        .partition(|file| input_url(file).is_some());
+    let urls: Vec<String> = urls
+        .iter()
+        .filter_map(|url| input_url(url).map(str::to_string))
+        .collect();
+    #[cfg(not(feature = "http"))]
+    if let Some(url) = urls.first() {
+        // Fails, naming the feature needed
+        read_url(url, args)?;
+    }
+
     let (tx, mut rx) = mpsc::channel(100);
 
     let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
@@ -352,42 +1156,74 @@ async fn watch_mode(args: &CliArgs) -> Result<()> {
     })?;
 
     // Watch all specified files
-    for file in &args.files {
+    for file in &files {
         watcher.watch(file, RecursiveMode::NonRecursive)?;
     }
 
-    println!(
+    eprintln!(
         "{} {} files for changes... (Press Ctrl+C to stop)",
         "Watching".cyan().bold(),
         args.files.len()
     );
 
     // Process files initially
-    for file in &args.files {
-        if let Err(e) = process_single_file(file, args) {
-            print_error(&e, args);
-        }
+    let pool = file_pool(args)?;
+    for result in process_files(&pool, &files, args) {
+        report_watch_result(result, args)?;
     }
 
-    while let Some(event) = rx.recv().await {
-        if matches!(event.kind, EventKind::Modify(_)) {
-            for path in event.paths {
-                if args.files.contains(&path) {
-                    println!("\n{} {}", "File changed:".yellow(), path.display());
+    let watch_files = async {
+        while let Some(event) = rx.recv().await {
+            let mut changed = BTreeSet::new();
+            add_changed_files(event, &files, &mut changed);
+            if changed.is_empty() {
+                continue;
+            }
 
-                    // Small delay to ensure file write is complete
-                    sleep(Duration::from_millis(100)).await;
+            // Collect the rest of the burst, which also lets writes complete;
+            // a file changed several times is processed once
+            while let Ok(Some(event)) = timeout(WATCH_DEBOUNCE, rx.recv()).await {
+                add_changed_files(event, &files, &mut changed);
+            }
 
-                    if let Err(e) = process_single_file(&path, args) {
-                        print_error(&e, args);
-                    }
-                }
+            // Results print one file at a time, in path order
+            let changed: Vec<PathBuf> = changed.into_iter().collect();
+            let results = process_files(&pool, &changed, args);
+            for (path, result) in changed.iter().zip(results) {
+                eprintln!("\n{} {}", "File changed:".yellow(), path.display());
+                report_watch_result(result, args)?;
             }
         }
-    }
+        Ok::<_, CliError>(())
+    };
 
+    #[cfg(feature = "http")]
+    tokio::try_join!(watch_files, http::watch_urls(urls, args))?;
+    #[cfg(not(feature = "http"))]
+    watch_files.await?;
     Ok(())
 }
+
+/// Adds the watched files that `event` reports as modified to `changed`.
+fn add_changed_files(event: notify::Event, files: &[PathBuf], changed: &mut BTreeSet<PathBuf>) {
+    if matches!(event.kind, notify::EventKind::Modify(_)) {
+        changed.extend(event.paths.into_iter().filter(|path| files.contains(path)));
+    }
+}
+
+/// Writes the result of processing a watched file, reporting parse errors
+/// without stopping the watch.
+fn report_watch_result(result: Result<(String, ParseStats)>, args: &CliArgs) -> Result<()> {
+    match result {
+        Ok((output, _)) if args.validate => write_stdout(&format!("{output}\n")),
+        Ok((output, _)) => write_output(&output, args),
+        Err(e) => {
+            print_error(&e, args);
+            Ok(())
+        }
+    }
+}
+
 fn create_parser_options(args: &ParserOptionsArgs) -> ParserOptions {
     ParserOptions {
         allow_comments: !args.no_comments,
@@ -401,14 +1237,101 @@ fn create_parser_options(args: &ParserOptionsArgs) -> ParserOptions {
         max_repairs: 100,
         fast_repair: false,
         report_repairs: true,
+        backend: args.backend.into(),
+        ..Default::default()
+    }
+}
+
+/// Prints each compatibility warning against `source`, failing under
+/// `--deny-compat` if there are any.
+fn report_compat_warnings(
+    warnings: &[CompatWarning],
+    source: &str,
+    content: &str,
+    args: &CliArgs,
+) -> Result<()> {
+    let label = if args.deny_compat {
+        "error:".red().bold()
+    } else {
+        "warning:".yellow().bold()
+    };
+    for warning in warnings {
+        let (line, col) = calculate_line_column(content, warning.span.start);
+        eprintln!("{source}:{line}:{col}: {label} {warning}");
+    }
+
+    match warnings.first() {
+        Some(first) if args.deny_compat => {
+            let (line, col) = calculate_line_column(content, first.span.start);
+            Err(CliError::ParseError {
+                file: source.to_string(),
+                line,
+                col,
+                message: format!(
+                    "{} construct(s) outside strict JSON, denied by --deny-compat",
+                    warnings.len()
+                ),
+                code: ErrorCode::Custom,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Under `--check-homogeneous`, prints each array element whose type differs
+/// from the first in its array, failing if there are any.
+fn report_type_mismatches(value: &Value, source: &str, args: &CliArgs) -> Result<()> {
+    if !args.check_homogeneous {
+        return Ok(());
+    }
+    let mismatches = check_homogeneous(value);
+    for mismatch in &mismatches {
+        eprintln!("{source}: {} {mismatch}", "error:".red().bold());
+    }
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::CheckFailed {
+            file: source.to_string(),
+            message: format!(
+                "{} array element(s) of another type than the first, denied by --check-homogeneous",
+                mismatches.len()
+            ),
+        })
+    }
+}
+
+/// Under `--normalize-keys`, rewrites every object key of `value` in the
+/// chosen case, printing each object whose keys would get the same name and
+/// failing if there are any.
+fn rename_keys<'a>(value: &'a Value, source: &str, args: &CliArgs) -> Result<Cow<'a, Value>> {
+    let Some(case) = args.normalize_keys else {
+        return Ok(Cow::Borrowed(value));
+    };
+    let case = KeyCase::from(case);
+    let mut renamed = value.clone();
+    match normalize_keys(&mut renamed, case) {
+        Ok(()) => Ok(Cow::Owned(renamed)),
+        Err(collisions) => {
+            for collision in &collisions {
+                eprintln!("{source}: {} {collision}", "error:".red().bold());
+            }
+            Err(CliError::CheckFailed {
+                file: source.to_string(),
+                message: format!(
+                    "{} object(s) with keys that are the same in {case}, denied by --normalize-keys",
+                    collisions.len()
+                ),
+            })
+        }
     }
 }
 
-fn print_validation_result_with_repair(
+fn format_validation_result_with_repair(
     source: &str,
     result: &EnhancedParseResult<Value>,
     args: &CliArgs,
-) {
+) -> String {
     let status = match result.parsing_tier {
         ParsingTier::Fast => "✓ Valid JSON (fast path)".green(),
         ParsingTier::Forgiving => "✓ Valid JSON (forgiving path)".yellow(),
@@ -416,97 +1339,105 @@ fn print_validation_result_with_repair(
     };
 
     if args.files.len() > 1 || args.watch {
-        println!("{source}: {status}");
+        format!("{source}: {status}")
     } else {
-        println!("{status}");
+        status.to_string()
     }
 }
 
 fn print_repair_info(result: &EnhancedParseResult<Value>, _args: &CliArgs) {
     if !result.repairs.is_empty() {
-        println!("{}", "Repair actions performed:".yellow().bold());
+        eprintln!("{}", "Repair actions performed:".yellow().bold());
         for action in &result.repairs {
             match action.action_type {
                 RepairType::InsertBracket => {
-                    println!(
+                    eprintln!(
                         "  • Added missing bracket at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::RemoveBracket => {
-                    println!(
+                    eprintln!(
                         "  • Removed extra bracket at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::ReplaceBracket => {
-                    println!(
+                    eprintln!(
                         "  • Replaced '{}' with '{}' at position {}: {}",
                         action.original, action.replacement, action.position, action.description
                     );
                 }
                 RepairType::BalanceQuotes => {
-                    println!(
+                    eprintln!(
                         "  • Balanced quotes at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::InsertComma => {
-                    println!(
+                    eprintln!(
                         "  • Added comma at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::RemoveComma => {
-                    println!(
+                    eprintln!(
                         "  • Removed comma at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::InsertText => {
-                    println!(
+                    eprintln!(
                         "  • Inserted text at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::ReplaceText => {
-                    println!(
+                    eprintln!(
                         "  • Replaced text at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::ReplaceQuotes => {
-                    println!(
+                    eprintln!(
                         "  • Replaced quotes at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::TypeCoercion => {
-                    println!(
+                    eprintln!(
                         "  • Applied type coercion at position {}: {}",
                         action.position, action.description
                     );
                 }
                 RepairType::QuoteKey => {
-                    println!(
+                    eprintln!(
                         "  • Added quotes to object key at position {}: {}",
                         action.position, action.description
                     );
                 }
+                RepairType::DiscardTrailing => {
+                    eprintln!(
+                        "  • Discarded {} trailing bytes at position {}: {}",
+                        action.original.len(),
+                        action.position,
+                        action.description
+                    );
+                }
             }
         }
     }
 }
 
 fn print_repair_summary(result: &EnhancedParseResult<Value>, _args: &CliArgs) {
-    println!("{}", "=== Repair Summary ===".cyan().bold());
-    println!("Parsing tier: {:?}", result.parsing_tier);
-    println!("Repair actions: {}", result.repairs.len());
+    eprintln!("{}", "=== Repair Summary ===".cyan().bold());
+    eprintln!("Parsing tier: {:?}", result.parsing_tier);
+    eprintln!("Repair actions: {}", result.repairs.len());
 
     if !result.repairs.is_empty() {
-        println!("\n{}", "Detailed repair actions:".yellow().bold());
+        eprintln!("\n{}", "Detailed repair actions:".yellow().bold());
         for (i, action) in result.repairs.iter().enumerate() {
-            println!(
+            eprintln!(
                 "{}. {:?} at position {}: {}",
                 i + 1,
                 action.action_type,
@@ -517,138 +1448,322 @@ fn print_repair_summary(result: &EnhancedParseResult<Value>, _args: &CliArgs) {
     }
 
     if !result.errors.is_empty() {
-        println!("\n{}", "Errors encountered:".red().bold());
+        eprintln!("\n{}", "Errors encountered:".red().bold());
         for (i, error) in result.errors.iter().enumerate() {
-            println!("{}. {}", i + 1, error);
+            eprintln!("{}. {}", i + 1, error);
         }
     }
 }
 
-fn format_output(value: &Value, args: &CliArgs) -> String {
-    if args.compact {
-        format_json_compact(value)
-    } else if args.pretty {
-        format_json_pretty(value, args.indent)
-    } else {
-        // Default: compact for single values, pretty for objects/arrays
-        match value {
-            Value::Object(_) | Value::Array(_) => format_json_pretty(value, args.indent),
-            _ => format_json_compact(value),
-        }
-    }
+/// Statistics gathered while processing one input.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ParseStats {
+    /// Input size in bytes
+    bytes: usize,
+    /// Number of values, counting containers as well as their contents
+    values: usize,
+    /// Deepest container nesting; zero for a lone scalar
+    max_depth: usize,
+    /// Total length of string values and object keys in bytes
+    string_bytes: usize,
+    /// Estimated heap footprint of the parsed values in bytes
+    heap_bytes: usize,
+    /// Tier that parsed the input, when the fallback parser was used
+    tier: Option<ParsingTier>,
+    /// Number of repairs applied
+    repairs: usize,
+    /// Time spent processing the input
+    elapsed: Duration,
 }
 
-fn format_json_compact(value: &Value) -> String {
-    match value {
-        Value::Null => "null".to_string(),
-        Value::Bool(b) => b.to_string(),
-        Value::Number(n) => n.to_string(),
-        Value::String(s) => format!("\"{}\"", escape_json_string(s)),
-        Value::Array(arr) => {
-            let items: Vec<String> = arr.iter().map(format_json_compact).collect();
-            format!("[{}]", items.join(","))
+impl ParseStats {
+    /// Counts the values in a fallback parse along with its tier and repairs.
+    fn of_result(result: &EnhancedParseResult<Value>) -> Self {
+        ParseStats {
+            tier: Some(result.parsing_tier),
+            repairs: result.repairs.len(),
+            ..Self::of_values([&result.value])
         }
-        Value::Object(obj) => {
-            let pairs: Vec<String> = obj
-                .iter()
-                .map(|(k, v)| format!("\"{}\":{}", escape_json_string(k), format_json_compact(v)))
-                .collect();
-            format!("{{{}}}", pairs.join(","))
+    }
+
+    /// Counts the values in `values` and how deeply they nest.
+    fn of_values<'a>(values: impl IntoIterator<Item = &'a Value>) -> Self {
+        let mut stats = ParseStats::default();
+        for value in values {
+            let document = analyze(value);
+            stats.values += document.values();
+            stats.max_depth = stats.max_depth.max(document.max_depth);
+            stats.string_bytes += document.string_bytes;
+            stats.heap_bytes += document.heap_bytes;
         }
+        stats
     }
 }
 
-fn format_json_pretty(value: &Value, indent: usize) -> String {
-    format_value_with_indent(value, 0, indent)
+/// Statistics totalled over every input.
+#[derive(Debug, Default, PartialEq)]
+struct StatsSummary {
+    files: usize,
+    failed: usize,
+    bytes: usize,
+    values: usize,
+    max_depth: usize,
+    string_bytes: usize,
+    heap_bytes: usize,
+    repairs: usize,
+    fast: usize,
+    forgiving: usize,
+    repaired: usize,
+    /// Sum of the per-input processing times
+    parse_time: Duration,
 }
 
-fn format_value_with_indent(value: &Value, current_indent: usize, indent_size: usize) -> String {
-    let spaces = " ".repeat(current_indent);
-    let inner_spaces = " ".repeat(current_indent + indent_size);
-
-    match value {
-        Value::Null => "null".to_string(),
-        Value::Bool(b) => b.to_string(),
-        Value::Number(n) => n.to_string(),
-        Value::String(s) => format!("\"{}\"", escape_json_string(s)),
-        Value::Array(arr) => {
-            if arr.is_empty() {
-                "[]".to_string()
-            } else {
-                let mut result = "[\n".to_string();
-                for (i, item) in arr.iter().enumerate() {
-                    result.push_str(&inner_spaces);
-                    result.push_str(&format_value_with_indent(
-                        item,
-                        current_indent + indent_size,
-                        indent_size,
-                    ));
-                    if i < arr.len() - 1 {
-                        result.push(',');
-                    }
-                    result.push('\n');
-                }
-                result.push_str(&spaces);
-                result.push(']');
-                result
+impl StatsSummary {
+    /// Totals the statistics of each input; `None` marks a failed input.
+    fn from_stats<'a>(stats: impl IntoIterator<Item = Option<&'a ParseStats>>) -> Self {
+        let mut summary = StatsSummary::default();
+        for stats in stats {
+            summary.files += 1;
+            let Some(stats) = stats else {
+                summary.failed += 1;
+                continue;
+            };
+            summary.bytes += stats.bytes;
+            summary.values += stats.values;
+            summary.max_depth = summary.max_depth.max(stats.max_depth);
+            summary.string_bytes += stats.string_bytes;
+            summary.heap_bytes += stats.heap_bytes;
+            summary.repairs += stats.repairs;
+            match stats.tier {
+                Some(ParsingTier::Fast) => summary.fast += 1,
+                Some(ParsingTier::Forgiving) => summary.forgiving += 1,
+                Some(ParsingTier::Repair) => summary.repaired += 1,
+                None => {}
             }
+            summary.parse_time += stats.elapsed;
         }
-        Value::Object(obj) => {
-            if obj.is_empty() {
-                "{}".to_string()
-            } else {
-                let mut result = "{\n".to_string();
-                let mut entries: Vec<_> = obj.iter().collect();
-                entries.sort_by_key(|(k, _)| *k);
-
-                for (i, (key, value)) in entries.iter().enumerate() {
-                    result.push_str(&inner_spaces);
-                    result.push_str(&format!("\"{}\": ", escape_json_string(key)));
-                    result.push_str(&format_value_with_indent(
-                        value,
-                        current_indent + indent_size,
-                        indent_size,
-                    ));
-                    if i < entries.len() - 1 {
-                        result.push(',');
-                    }
-                    result.push('\n');
-                }
-                result.push_str(&spaces);
-                result.push('}');
-                result
+        summary
+    }
+}
+
+fn tier_name(tier: Option<ParsingTier>) -> &'static str {
+    match tier {
+        Some(ParsingTier::Fast) => "fast",
+        Some(ParsingTier::Forgiving) => "forgiving",
+        Some(ParsingTier::Repair) => "repair",
+        None => "n/a",
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Prints per-input statistics and their totals to stderr.
+///
+/// Inputs may have been processed in parallel, so `wall_time` can be less
+/// than the summed parse time.
+fn print_stats(
+    sources: &[String],
+    results: &[Result<(String, ParseStats)>],
+    wall_time: Duration,
+    format: StatsFormat,
+) {
+    let stats: Vec<Option<&ParseStats>> = results
+        .iter()
+        .map(|result| result.as_ref().ok().map(|(_, stats)| stats))
+        .collect();
+    let summary = StatsSummary::from_stats(stats.iter().copied());
+    match format {
+        StatsFormat::Human => print_stats_human(sources, &stats, &summary, wall_time),
+        StatsFormat::Json => eprintln!("{}", stats_json(sources, &stats, &summary, wall_time)),
+    }
+}
+
+fn print_stats_human(
+    sources: &[String],
+    stats: &[Option<&ParseStats>],
+    summary: &StatsSummary,
+    wall_time: Duration,
+) {
+    eprintln!("{}", "=== Parse Statistics ===".cyan().bold());
+    if stats.len() > 1 {
+        for (source, stats) in sources.iter().zip(stats) {
+            match stats {
+                Some(stats) => eprintln!(
+                    "{source}: {} bytes, {} values, depth {}, ~{} heap bytes, {} tier, {} repairs, {:.3} ms",
+                    stats.bytes,
+                    stats.values,
+                    stats.max_depth,
+                    stats.heap_bytes,
+                    tier_name(stats.tier),
+                    stats.repairs,
+                    millis(stats.elapsed)
+                ),
+                None => eprintln!("{source}: {}", "failed".red()),
             }
         }
+        eprintln!();
     }
+    eprintln!("Files: {} ({} failed)", summary.files, summary.failed);
+    eprintln!("Bytes: {}", summary.bytes);
+    eprintln!("Values: {}", summary.values);
+    eprintln!("Max depth: {}", summary.max_depth);
+    eprintln!("String bytes: {}", summary.string_bytes);
+    eprintln!("Estimated heap: {} bytes", summary.heap_bytes);
+    eprintln!(
+        "Tiers: {} fast, {} forgiving, {} repaired",
+        summary.fast, summary.forgiving, summary.repaired
+    );
+    eprintln!("Repairs: {}", summary.repairs);
+    eprintln!("Parse time: {:.3} ms", millis(summary.parse_time));
+    eprintln!("Wall time: {:.3} ms", millis(wall_time));
 }
 
-fn escape_json_string(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    for ch in s.chars() {
-        match ch {
-            '"' => result.push_str("\\\""),
-            '\\' => result.push_str("\\\\"),
-            '\n' => result.push_str("\\n"),
-            '\r' => result.push_str("\\r"),
-            '\t' => result.push_str("\\t"),
-            '\u{0008}' => result.push_str("\\b"),
-            '\u{000C}' => result.push_str("\\f"),
-            ch if ch.is_control() => {
-                result.push_str(&format!("\\u{:04x}", ch as u32));
+fn stats_json(
+    sources: &[String],
+    stats: &[Option<&ParseStats>],
+    summary: &StatsSummary,
+    wall_time: Duration,
+) -> String {
+    let files: Vec<String> = sources
+        .iter()
+        .zip(stats)
+        .map(|(source, stats)| {
+            let file = to_compact_string(&Value::String(source.clone()), &KeyOrder::default());
+            match stats {
+                Some(stats) => json_object(&[
+                    ("file", file),
+                    ("ok", "true".to_string()),
+                    ("bytes", stats.bytes.to_string()),
+                    ("values", stats.values.to_string()),
+                    ("max_depth", stats.max_depth.to_string()),
+                    ("string_bytes", stats.string_bytes.to_string()),
+                    ("heap_bytes", stats.heap_bytes.to_string()),
+                    (
+                        "tier",
+                        stats.tier.map_or("null".to_string(), |tier| {
+                            format!("\"{}\"", tier_name(Some(tier)))
+                        }),
+                    ),
+                    ("repairs", stats.repairs.to_string()),
+                    ("time_ms", format!("{:.3}", millis(stats.elapsed))),
+                ]),
+                None => json_object(&[("file", file), ("ok", "false".to_string())]),
             }
-            ch => result.push(ch),
+        })
+        .collect();
+    let tiers = json_object(&[
+        ("fast", summary.fast.to_string()),
+        ("forgiving", summary.forgiving.to_string()),
+        ("repair", summary.repaired.to_string()),
+    ]);
+    let totals = json_object(&[
+        ("files", summary.files.to_string()),
+        ("failed", summary.failed.to_string()),
+        ("bytes", summary.bytes.to_string()),
+        ("values", summary.values.to_string()),
+        ("max_depth", summary.max_depth.to_string()),
+        ("string_bytes", summary.string_bytes.to_string()),
+        ("heap_bytes", summary.heap_bytes.to_string()),
+        ("repairs", summary.repairs.to_string()),
+        ("tiers", tiers),
+        (
+            "parse_time_ms",
+            format!("{:.3}", millis(summary.parse_time)),
+        ),
+        ("wall_time_ms", format!("{:.3}", millis(wall_time))),
+    ]);
+    json_object(&[
+        ("files", format!("[{}]", files.join(","))),
+        ("totals", totals),
+    ])
+}
+
+/// Writes already-serialized `fields` as a JSON object, keeping their order.
+fn json_object(fields: &[(&str, String)]) -> String {
+    let members: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("\"{key}\":{value}"))
+        .collect();
+    format!("{{{}}}", members.join(","))
+}
+
+/// Formats a value parsed from `content`, keeping its key order, renamed
+/// under --normalize-keys, and, with --preserve-comments, its comments.
+fn format_parsed(value: &Value, content: &str, args: &CliArgs) -> String {
+    let mut order =
+        KeyOrder::from_source_with_options(content, &create_parser_options(&args.parser_opts));
+    if let Some(case) = args.normalize_keys {
+        order = order.rename_keys(&|key| KeyCase::from(case).convert(key));
+    }
+    if args.preserve_comments {
+        let comments = SourceComments::from_source(content);
+        let pretty = to_pretty_string_with_comments(value, args.indent, &order, &comments);
+        LineEnding::from(args.line_ending).convert(&pretty)
+    } else {
+        format_output(value, &order, args)
+    }
+}
+
+fn format_output(value: &Value, order: &KeyOrder, args: &CliArgs) -> String {
+    match format_style(value, args) {
+        FormatStyle::Minify => FormatStyle::Minify.format(value, order),
+        style => LineEnding::from(args.line_ending).convert(&style.format(value, order)),
+    }
+}
+
+/// The layout --compact and --pretty ask for. Without either, objects and
+/// arrays are indented and single values written compactly.
+fn format_style(value: &Value, args: &CliArgs) -> FormatStyle {
+    let beautify = FormatStyle::Beautify {
+        indent: args.indent,
+    };
+    if args.compact {
+        FormatStyle::Minify
+    } else if args.pretty {
+        beautify
+    } else {
+        match value {
+            Value::Object(_) | Value::Array(_) => beautify,
+            _ => FormatStyle::Minify,
         }
     }
-    result
 }
 
 fn write_output(content: &str, args: &CliArgs) -> Result<()> {
     if let Some(output_file) = &args.output {
-        fs::write(output_file, content)?;
+        fs::write(output_file, &*with_trailing_newline(content, true, args))?;
+    } else {
+        write_stdout(&with_trailing_newline(content, false, args))?;
+    }
+    Ok(())
+}
+
+/// Ends `content` with exactly one line break, or none, as the trailing
+/// newline flags ask. Without either flag, only output to a file gets one.
+fn with_trailing_newline<'a>(content: &'a str, to_file: bool, args: &CliArgs) -> Cow<'a, str> {
+    let newline = if args.trailing_newline || args.no_trailing_newline {
+        args.trailing_newline
+    } else if to_file {
+        true
+    } else {
+        return Cow::Borrowed(content);
+    };
+    let content = content.trim_end_matches(['\r', '\n']);
+    if newline {
+        let line_ending = LineEnding::from(args.line_ending).as_str();
+        Cow::Owned(format!("{content}{line_ending}"))
     } else {
-        print!("{content}");
-        io::stdout().flush()?;
+        Cow::Borrowed(content)
     }
+}
+
+/// Writes raw bytes to stdout; a closed pipe surfaces as an I/O error.
+fn write_stdout(content: &str) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(content.as_bytes())?;
+    stdout.flush()?;
     Ok(())
 }
 
@@ -664,6 +1779,7 @@ fn format_parse_error(error: &vexy_json_core::Error, file: &str, content: &str)
             line,
             col,
             message: error_str,
+            code: error.code(),
         }
     } else {
         CliError::ParseError {
@@ -671,6 +1787,7 @@ fn format_parse_error(error: &vexy_json_core::Error, file: &str, content: &str)
             line: 1,
             col: 1,
             message: error_str,
+            code: error.code(),
         }
     }
 }
@@ -708,6 +1825,10 @@ fn calculate_line_column(content: &str, position: usize) -> (usize, usize) {
 }
 
 fn print_error(error: &CliError, args: &CliArgs) {
+    if args.error_format == ErrorFormat::Json {
+        eprintln!("{}", error_json(error));
+        return;
+    }
     eprintln!("{}", "Error:".red().bold());
 
     if args.verbose_errors {
@@ -720,7 +1841,7 @@ fn print_error(error: &CliError, args: &CliArgs) {
         {
             if file != "<stdin>" {
                 if let Ok(content) = fs::read_to_string(file) {
-                    print_error_context(&content, *line, *col);
+                    print_error_context(&content, *line, *col, args.context_width);
                 }
             }
         }
@@ -735,7 +1856,75 @@ fn print_error(error: &CliError, args: &CliArgs) {
     }
 }
 
-fn print_error_context(content: &str, line: usize, col: usize) {
+/// Writes `error` as a single-line JSON object.
+///
+/// `line`, `column` and `file` are null for errors not tied to a place in
+/// an input, as is `code` for usage errors.
+fn error_json(error: &CliError) -> String {
+    let string = |s: &str| to_compact_string(&Value::String(s.to_string()), &KeyOrder::default());
+    let code = error.code();
+    let (file, line, col, message) = match error {
+        CliError::ParseError {
+            file,
+            line,
+            col,
+            message,
+            ..
+        } => (
+            string(file),
+            line.to_string(),
+            col.to_string(),
+            string(message),
+        ),
+        CliError::CheckFailed { file, message } => (
+            string(file),
+            "null".to_string(),
+            "null".to_string(),
+            string(message),
+        ),
+        #[cfg(feature = "http")]
+        CliError::HttpStatus { url, status } => (
+            string(url),
+            "null".to_string(),
+            "null".to_string(),
+            string(&format!("HTTP {status}")),
+        ),
+        #[cfg(feature = "http")]
+        CliError::FetchFailed { url, message } => (
+            string(url),
+            "null".to_string(),
+            "null".to_string(),
+            string(message),
+        ),
+        _ => (
+            "null".to_string(),
+            "null".to_string(),
+            "null".to_string(),
+            string(&error.to_string()),
+        ),
+    };
+    let suggestions: Vec<String> = code
+        .map(|code| code.suggestions())
+        .unwrap_or_default()
+        .into_iter()
+        .map(string)
+        .collect();
+    json_object(&[
+        (
+            "code",
+            code.map_or("null".to_string(), |code| string(code.as_str())),
+        ),
+        ("message", message),
+        ("file", file),
+        ("line", line),
+        ("column", col),
+        ("suggestions", format!("[{}]", suggestions.join(","))),
+    ])
+}
+
+/// Prints the lines around `line`, each cut down to `width` characters
+/// either side of `col`, with a caret under the error.
+fn print_error_context(content: &str, line: usize, col: usize, width: usize) {
     let lines: Vec<&str> = content.lines().collect();
     let start = line.saturating_sub(2);
     let end = std::cmp::min(line + 2, lines.len());
@@ -749,10 +1938,12 @@ fn print_error_context(content: &str, line: usize, col: usize) {
             format!("{line_num:4}   ").white().dimmed()
         };
 
-        eprintln!("{prefix}{line_content}");
+        let (excerpt, column) = excerpt_line(line_content, col, width);
+        eprintln!("{prefix}{}", expand_tabs(&excerpt));
 
         if line_num == line {
-            let pointer = " ".repeat(6 + col.saturating_sub(1)) + "^";
+            let before: String = excerpt.chars().take(column - 1).collect();
+            let pointer = " ".repeat(7 + display_width(&before)) + "^";
             eprintln!("{}", pointer.red().bold());
         }
     }
@@ -0,0 +1,65 @@
+// this_file: crates/cli/tests/debug_tokens.rs
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn vexy_json() -> Command {
+    Command::cargo_bin("vexy-json").unwrap()
+}
+
+#[test]
+fn test_debug_tokens_dumps_forgiving_stream() {
+    let output = vexy_json()
+        .arg("--debug-tokens")
+        .write_stdin("// config\n{a: 1\nb: 'x'}")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "10..11\tLeftBrace\t\"{\"\n\
+         11..12\tUnquotedString\t\"a\"\n\
+         12..13\tColon\t\":\"\n\
+         14..15\tNumber\t\"1\"\n\
+         16..17\tUnquotedString\t\"b\"\n\
+         17..18\tColon\t\":\"\n\
+         19..22\tString\t\"'x'\"\n\
+         22..23\tRightBrace\t\"}\"\n"
+    );
+}
+
+#[test]
+fn test_debug_tokens_headers_multiple_files() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.json");
+    let second = dir.path().join("second.json");
+    fs::write(&first, "[1]").unwrap();
+    fs::write(&second, "null").unwrap();
+
+    vexy_json()
+        .arg("--debug-tokens")
+        .args([&first, &second])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "==> {} <==\n0..1\tLeftBracket",
+            first.display()
+        )))
+        .stdout(predicate::str::contains(format!(
+            "==> {} <==\n0..4\tNull",
+            second.display()
+        )));
+}
+
+#[test]
+fn test_debug_tokens_reports_lexer_errors() {
+    vexy_json()
+        .arg("--debug-tokens")
+        .write_stdin("[1, @]")
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains("Unexpected character '@'"));
+}
@@ -0,0 +1,152 @@
+// this_file: crates/cli/tests/error_format.rs
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+use vexy_json_core::ast::Value;
+use vexy_json_core::{parse_with_fallback, ParserOptions, ParsingTier};
+
+fn vexy_json() -> Command {
+    Command::cargo_bin("vexy-json").unwrap()
+}
+
+/// Parses each line of `stderr`, checking it is strict JSON.
+fn error_reports(stderr: &[u8]) -> Vec<Value> {
+    String::from_utf8(stderr.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let result = parse_with_fallback(line, ParserOptions::default());
+            assert_eq!(result.parsing_tier, ParsingTier::Fast, "{line}");
+            result.value
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_error_as_json() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("bad.json");
+    fs::write(&path, "{\"a\": 1,\n \"b\": @}").unwrap();
+
+    let output = vexy_json()
+        .args(["--error-format", "json"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+
+    let reports = error_reports(&output.stderr);
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert_eq!(report["code"].as_str(), Some("E1001"));
+    assert_eq!(
+        report["message"].as_str(),
+        Some("Unexpected character '@' at position 15")
+    );
+    assert_eq!(report["file"].as_str(), Some(&*path.display().to_string()));
+    assert_eq!(report["line"].as_i64(), Some(2));
+    assert_eq!(report["column"].as_i64(), Some(7));
+    let suggestions = report["suggestions"].as_array().unwrap();
+    assert!(!suggestions.is_empty());
+    assert!(suggestions.iter().all(|s| s.as_str().is_some()));
+}
+
+#[test]
+fn test_one_line_per_failed_file() {
+    let dir = TempDir::new().unwrap();
+    let files = [
+        ("a.json", "[1, 2"),
+        ("b.json", "{\"a\": }"),
+        ("c.json", "[1e]"),
+    ];
+    let paths: Vec<_> = files
+        .iter()
+        .map(|(name, content)| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        })
+        .collect();
+
+    let output = vexy_json()
+        .args(["--error-format", "json", "--validate"])
+        .args(&paths)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let codes: Vec<_> = error_reports(&output.stderr)
+        .iter()
+        .map(|report| report["code"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(codes, ["E1008", "E1003"]);
+}
+
+#[test]
+fn test_io_error_as_json() {
+    let output = vexy_json()
+        .args(["--error-format", "json", "does-not-exist.json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let reports = error_reports(&output.stderr);
+    assert_eq!(reports[0]["code"].as_str(), Some("E1021"));
+    assert_eq!(reports[0]["line"], Value::Null);
+}
+
+#[test]
+fn test_long_line_context_is_cut_down() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("minified.json");
+    let half = "1,".repeat(2_500_000);
+    fs::write(&path, format!("[{half}@{}]", half.replace("1,", ",1"))).unwrap();
+
+    let output = vexy_json()
+        .env("NO_COLOR", "1")
+        .arg("--verbose-errors")
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.len() < 4096, "{} bytes", stderr.len());
+
+    // The context line comes after the message, which quotes the '@' too
+    let lines: Vec<&str> = stderr.lines().collect();
+    let context = lines.iter().rposition(|line| line.contains('@')).unwrap();
+    assert!(lines[context].starts_with("   1 > …1,1,"), "{stderr}");
+    assert!(lines[context].ends_with(",1…"), "{stderr}");
+    assert_eq!(
+        lines[context + 1].find('^').unwrap(),
+        lines[context].chars().position(|c| c == '@').unwrap()
+    );
+
+    let output = vexy_json()
+        .env("NO_COLOR", "1")
+        .args(["--verbose-errors", "--context-width", "4"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let expected = format!("\n   1 > …1,1,@,1,1…\n{}^\n", " ".repeat(12));
+    assert!(stderr.contains(&expected), "{stderr}");
+}
+
+#[test]
+fn test_caret_lines_up_after_tabs_and_wide_characters() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("wide.json");
+    fs::write(&path, "{\t\"日本\": @}").unwrap();
+
+    let output = vexy_json()
+        .env("NO_COLOR", "1")
+        .arg("--verbose-errors")
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    // Seven for the gutter, four for the tab and two for each ideograph
+    let expected = format!("\n   1 > {{    \"日本\": @}}\n{}^\n", " ".repeat(20));
+    assert!(stderr.contains(&expected), "{stderr}");
+}
@@ -0,0 +1,368 @@
+// this_file: crates/cli/tests/exit_codes.rs
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::process::Stdio;
+use tempfile::TempDir;
+
+fn vexy_json() -> Command {
+    Command::cargo_bin("vexy-json").unwrap()
+}
+
+/// Writes `files` into a fresh temp dir, returning it alongside the created paths.
+fn fixtures(files: &[(&str, &str)]) -> (TempDir, Vec<std::path::PathBuf>) {
+    let dir = TempDir::new().unwrap();
+    let paths = files
+        .iter()
+        .map(|(name, content)| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        })
+        .collect();
+    (dir, paths)
+}
+
+#[test]
+fn test_success_exits_zero() {
+    vexy_json()
+        .write_stdin("{a: 1, b: [1, 2,]}")
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains(r#""a""#));
+}
+
+#[test]
+fn test_parse_failure_exits_one_without_partial_output() {
+    let (_dir, paths) = fixtures(&[("good.json", r#"{"a": 1}"#), ("bad.json", r#"{"a": }"#)]);
+
+    vexy_json()
+        .args(&paths)
+        .assert()
+        .code(1)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("bad.json"));
+}
+
+#[test]
+fn test_invalid_utf8_is_a_parse_failure() {
+    vexy_json()
+        .write_stdin(b"[\"a\xff\"]".to_vec())
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("UTF-8"));
+}
+
+#[test]
+fn test_missing_file_exits_two() {
+    vexy_json()
+        .arg("does-not-exist.json")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("does-not-exist.json"));
+}
+
+#[test]
+fn test_validate_prints_summary() {
+    let (_dir, paths) = fixtures(&[
+        ("one.json", "[1]"),
+        ("two.json", "{a: 1}"),
+        ("three.json", r#"{"a": }"#),
+    ]);
+
+    vexy_json()
+        .arg("--validate")
+        .args(&paths)
+        .assert()
+        .code(1)
+        .stdout(predicate::str::ends_with("2 valid, 1 invalid\n"));
+
+    vexy_json()
+        .arg("--validate")
+        .args(&paths[..2])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::ends_with("2 valid, 0 invalid\n"));
+}
+
+#[test]
+fn test_multiple_files_with_output_is_refused() {
+    let (dir, paths) = fixtures(&[("a.json", "[1]"), ("b.json", "[2]")]);
+    let output = dir.path().join("out.json");
+
+    vexy_json()
+        .args(&paths)
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--output-dir"));
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_output_dir_writes_one_file_per_input() {
+    let (dir, paths) = fixtures(&[("a.json", "{a: 1}"), ("b.json", "[2,]")]);
+    let out_dir = dir.path().join("out");
+
+    vexy_json()
+        .args(&paths)
+        .arg("--output-dir")
+        .arg(&out_dir)
+        .assert()
+        .code(0)
+        .stdout(predicate::str::is_empty());
+
+    assert!(fs::read_to_string(out_dir.join("a.json"))
+        .unwrap()
+        .contains(r#""a""#));
+    assert!(fs::read_to_string(out_dir.join("b.json"))
+        .unwrap()
+        .contains('2'));
+}
+
+#[test]
+fn test_output_dir_rejects_duplicate_file_names() {
+    let (dir, _) = fixtures(&[]);
+    for sub in ["x", "y"] {
+        fs::create_dir(dir.path().join(sub)).unwrap();
+        fs::write(dir.path().join(sub).join("same.json"), "[1]").unwrap();
+    }
+
+    vexy_json()
+        .arg(dir.path().join("x/same.json"))
+        .arg(dir.path().join("y/same.json"))
+        .arg("--output-dir")
+        .arg(dir.path().join("out"))
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_broken_pipe_exits_zero_silently() {
+    // Large enough that the output cannot fit in the pipe buffer
+    let items: Vec<String> = (0..200_000).map(|i| i.to_string()).collect();
+    let (_dir, paths) = fixtures(&[("big.json", &format!("[{}]", items.join(",")))]);
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("vexy-json"))
+        .arg(&paths[0])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty(), "{:?}", output.stderr);
+}
+
+#[test]
+fn test_backend_selection() {
+    for backend in ["auto", "hybrid", "recursive", "iterative", "optimized-v3"] {
+        vexy_json()
+            .args(["--backend", backend, "--compact"])
+            .write_stdin("{a: 1, b: [true, null]}")
+            .assert()
+            .code(0)
+            .stdout(predicate::str::contains(r#"{"a":1,"b":[true,null]}"#));
+        vexy_json()
+            .args(["--backend", backend, "--compact"])
+            .write_stdin("{\n  \"a\": 1,\n  \"b\": [\n    true,\n    null\n  ]\n}\n")
+            .assert()
+            .code(0)
+            .stdout(predicate::str::contains(r#"{"a":1,"b":[true,null]}"#));
+    }
+    vexy_json()
+        .args(["--backend", "fastest"])
+        .write_stdin("1")
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_compat_warnings() {
+    let input = "{a: 1, // note\n b: [2,]}";
+    vexy_json()
+        .args(["--warn-compat", "--compact"])
+        .write_stdin(input)
+        .assert()
+        .code(0)
+        .stdout(r#"{"a":1,"b":[2]}"#)
+        .stderr(predicate::str::contains(
+            "<stdin>:1:2: warning: unquoted key at position 1",
+        ))
+        .stderr(predicate::str::contains("<stdin>:1:8: warning: comment"))
+        .stderr(predicate::str::contains(
+            "<stdin>:2:7: warning: trailing comma",
+        ));
+
+    vexy_json()
+        .arg("--deny-compat")
+        .write_stdin(input)
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains("error: unquoted key"))
+        .stderr(predicate::str::contains(
+            "4 construct(s) outside strict JSON",
+        ));
+
+    // Values that follow each other without commas
+    vexy_json()
+        .arg("--deny-compat")
+        .write_stdin("[1 2 3]")
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "<stdin>:1:3: error: missing comma at position 2; insert ,",
+        ))
+        .stderr(predicate::str::contains(
+            "<stdin>:1:5: error: missing comma at position 4; insert ,",
+        ))
+        .stderr(predicate::str::contains(
+            "2 construct(s) outside strict JSON",
+        ));
+
+    // Strict JSON passes
+    vexy_json()
+        .arg("--deny-compat")
+        .write_stdin(r#"{"a": [1, 2]}"#)
+        .assert()
+        .code(0);
+}
+
+#[test]
+fn test_check_homogeneous() {
+    vexy_json()
+        .args(["--check-homogeneous", "--compact"])
+        .write_stdin("{rows: [{a: 1}, {b: 2.5}], ids: [1, 2.5]}")
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains(r#""ids":[1,2.5]"#));
+
+    vexy_json()
+        .arg("--check-homogeneous")
+        .write_stdin("{ids: [1, '2', null], tags: [['a'], 'b']}")
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "<stdin>: error: $.ids[1]: expected integer like the first element, found string",
+        ))
+        .stderr(predicate::str::contains("$.ids[2]"))
+        .stderr(predicate::str::contains("$.tags[1]"))
+        .stderr(predicate::str::contains("3 array element(s)"));
+}
+
+#[test]
+fn test_normalize_keys() {
+    // Renamed keys keep their order from the source
+    vexy_json()
+        .args(["--normalize-keys", "snake_case", "--compact"])
+        .write_stdin("{zipCode: 1, HTTPStatus: {retryAfter: 2}, rows: [{userId: 3}]}")
+        .assert()
+        .code(0)
+        .stdout(r#"{"zip_code":1,"http_status":{"retry_after":2},"rows":[{"user_id":3}]}"#);
+
+    vexy_json()
+        .args(["--normalize-keys", "camelCase", "--compact"])
+        .write_stdin("{user_id: 1, 'http-status': 2}")
+        .assert()
+        .code(0)
+        .stdout(r#"{"userId":1,"httpStatus":2}"#);
+
+    vexy_json()
+        .args(["--normalize-keys", "snake_case"])
+        .write_stdin("{rows: [{userId: 1, user_id: 2}], UserId: 3}")
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains(
+            r#"<stdin>: error: $.rows[0]: keys "userId", "user_id" all become "user_id""#,
+        ))
+        .stderr(predicate::str::contains("1 object(s)"));
+
+    vexy_json()
+        .args(["--normalize-keys", "snake"])
+        .write_stdin("{}")
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_concatenated_documents() {
+    let input = r#"{"a":"}"}{"b":"\"{"}[1]"#;
+    let expected = "{\"a\":\"}\"}\n{\"b\":\"\\\"{\"}\n[1]\n";
+    // Detected without the flag
+    vexy_json()
+        .arg("--compact")
+        .write_stdin(input)
+        .assert()
+        .code(0)
+        .stdout(expected);
+
+    vexy_json()
+        .args(["--concat", "--compact"])
+        .write_stdin("{\"a\":\"}\"} \n\t{\"b\":\"\\\"{\"}\n\n[1]")
+        .assert()
+        .code(0)
+        .stdout(expected);
+
+    vexy_json()
+        .arg("--concat")
+        .write_stdin("{\"a\": 1}\n{\"b\": }")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("at position 15"));
+}
+
+#[test]
+fn test_concatenated_detection_follows_parser_options() {
+    // The brace in the comment only stays inside it where comments are allowed
+    let input = r#"{"a":1 /* } */}{"b":2}"#;
+    vexy_json()
+        .arg("--compact")
+        .write_stdin(input)
+        .assert()
+        .code(0)
+        .stdout("{\"a\":1}\n{\"b\":2}\n");
+    vexy_json()
+        .args(["--no-comments", "--compact"])
+        .write_stdin(input)
+        .assert()
+        .code(1);
+
+    // Detected documents are checked and renamed one by one
+    vexy_json()
+        .args(["--normalize-keys", "snake_case", "--compact"])
+        .write_stdin(r#"{"userId":1}{"zipCode":2}"#)
+        .assert()
+        .code(0)
+        .stdout("{\"user_id\":1}\n{\"zip_code\":2}\n");
+    vexy_json()
+        .arg("--check-homogeneous")
+        .write_stdin(r#"{"ids":[1]}{"ids":[1,"2"]}"#)
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "<stdin>: error: $.ids[1]: expected integer like the first element, found string",
+        ));
+}
+
+#[cfg(not(feature = "http"))]
+#[test]
+fn test_url_inputs_need_the_http_feature() {
+    for args in [
+        &["https://example.com/a.json"][..],
+        &["--watch", "http://x/a"],
+    ] {
+        vexy_json()
+            .args(args)
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains("built with the http feature"));
+    }
+}
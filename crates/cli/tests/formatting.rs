@@ -0,0 +1,228 @@
+// this_file: crates/cli/tests/formatting.rs
+
+use assert_cmd::Command;
+use vexy_json_core::{minify, prettify, ParserOptions};
+
+const JSONC: &str = "{\n  // retries\n  \"n\": 3, /* max */\n  \"url\": \"http://x\",\n}\n";
+
+const INPUT: &str = "// config\n{zeta: 1, alpha: [2, {y: true, b: null,}], 'mid': \"x\",}";
+
+fn vexy_json() -> Command {
+    Command::cargo_bin("vexy-json").unwrap()
+}
+
+#[test]
+fn test_output_preserves_key_order() {
+    vexy_json()
+        .arg("--compact")
+        .write_stdin(INPUT)
+        .assert()
+        .code(0)
+        .stdout(r#"{"zeta":1,"alpha":[2,{"y":true,"b":null}],"mid":"x"}"#);
+}
+
+#[test]
+fn test_key_order_of_large_strings() {
+    let long = "x".repeat(4_000_000);
+    vexy_json()
+        .arg("--compact")
+        .write_stdin(format!(r#"{{"b":"{long}","a":1}}"#))
+        .assert()
+        .code(0)
+        .stdout(format!(r#"{{"b":"{long}","a":1}}"#));
+}
+
+#[test]
+fn test_deeply_nested_output() {
+    let deep = format!("{}{{b:1,a:2}}{}", "[".repeat(100_000), "]".repeat(100_000));
+    vexy_json()
+        .args([
+            "--backend",
+            "iterative",
+            "--max-depth",
+            "200000",
+            "--compact",
+        ])
+        .write_stdin(deep)
+        .assert()
+        .code(0)
+        .stdout(format!(
+            r#"{}{{"b":1,"a":2}}{}"#,
+            "[".repeat(100_000),
+            "]".repeat(100_000)
+        ));
+}
+
+#[test]
+fn test_output_matches_core_formatter() {
+    let compact = minify(INPUT, ParserOptions::default()).unwrap();
+    vexy_json()
+        .arg("--compact")
+        .write_stdin(INPUT)
+        .assert()
+        .stdout(compact);
+
+    let pretty = prettify(INPUT, 4, ParserOptions::default()).unwrap();
+    vexy_json()
+        .args(["--pretty", "--indent", "4"])
+        .write_stdin(INPUT)
+        .assert()
+        .stdout(pretty);
+}
+
+#[test]
+fn test_strip_comments() {
+    vexy_json()
+        .arg("--strip-comments")
+        .write_stdin(JSONC)
+        .assert()
+        .code(0)
+        .stdout("{\n  \"n\": 3,\n  \"url\": \"http://x\"\n}\n");
+
+    vexy_json()
+        .args(["--strip-comments", "--keep-newlines"])
+        .write_stdin(JSONC)
+        .assert()
+        .code(0)
+        .stdout("{\n\n  \"n\": 3,\n  \"url\": \"http://x\"\n}\n");
+}
+
+#[test]
+fn test_preserve_comments() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("config.jsonc");
+    std::fs::write(
+        &path,
+        "// Service config\n{\n  name: 'svc',   // display name\n  /* Timeouts in ms */ timeouts: {connect: 100, read: 250,},\n  hosts: ['a', // primary\n          'b'],\n  // more to come\n}\n",
+    )
+    .unwrap();
+
+    vexy_json()
+        .args(["--pretty", "--preserve-comments"])
+        .arg(&path)
+        .assert()
+        .code(0)
+        .stdout(
+            "// Service config\n{\n  \"name\": \"svc\", // display name\n  /* Timeouts in ms */\n  \"timeouts\": {\n    \"connect\": 100,\n    \"read\": 250\n  },\n  \"hosts\": [\n    \"a\", // primary\n    \"b\"\n  ]\n  // more to come\n}",
+        );
+
+    // Without comments the output is the usual pretty form
+    let pretty = prettify(INPUT, 2, ParserOptions::default()).unwrap();
+    vexy_json()
+        .args(["--pretty", "--preserve-comments"])
+        .write_stdin(INPUT.trim_start_matches("// config\n"))
+        .assert()
+        .stdout(pretty);
+
+    vexy_json()
+        .args(["--compact", "--preserve-comments"])
+        .write_stdin(INPUT)
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_line_ending() {
+    let pretty = prettify(INPUT, 2, ParserOptions::default()).unwrap();
+    assert!(pretty.contains('\n') && !pretty.contains('\r'));
+    vexy_json()
+        .arg("--pretty")
+        .write_stdin(INPUT)
+        .assert()
+        .stdout(pretty.clone());
+    vexy_json()
+        .args(["--pretty", "--line-ending", "crlf"])
+        .write_stdin(INPUT)
+        .assert()
+        .code(0)
+        .stdout(pretty.replace('\n', "\r\n"));
+
+    // NDJSON documents are separated by the same line break
+    let ndjson = "{\"a\": 1}\n[2, \"x\\ny\"]\n";
+    for (line_ending, expected) in [
+        ("lf", "{\"a\":1}\n[2,\"x\\ny\"]\n"),
+        ("crlf", "{\"a\":1}\r\n[2,\"x\\ny\"]\r\n"),
+    ] {
+        vexy_json()
+            .args(["--ndjson", "--compact", "--line-ending", line_ending])
+            .write_stdin(ndjson)
+            .assert()
+            .code(0)
+            .stdout(expected);
+    }
+}
+
+#[test]
+fn test_trailing_newline() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let output = |args: &[&str], input: &str| {
+        let out = vexy_json().args(args).write_stdin(input).output().unwrap();
+        assert!(out.status.success(), "{args:?}");
+        String::from_utf8(out.stdout).unwrap()
+    };
+    let ends_with_one = |text: &str, line_ending: &str| {
+        text.ends_with(line_ending) && !text[..text.len() - line_ending.len()].ends_with('\n')
+    };
+    let ndjson = "{\"a\": 1}\n[2]\n";
+
+    for (args, input) in [
+        (&["--pretty"][..], INPUT),
+        (&["--compact"], INPUT),
+        (&["--ndjson", "--compact"], ndjson),
+        (&["--ndjson", "--pretty"], ndjson),
+    ] {
+        let with = output(&[args, &["--trailing-newline"]].concat(), input);
+        assert!(ends_with_one(&with, "\n"), "{args:?}: {with:?}");
+        let crlf = output(
+            &[args, &["--trailing-newline", "--line-ending", "crlf"]].concat(),
+            input,
+        );
+        assert!(ends_with_one(&crlf, "\r\n"), "{args:?}: {crlf:?}");
+        let without = output(&[args, &["--no-trailing-newline"]].concat(), input);
+        assert!(!without.ends_with('\n'), "{args:?}: {without:?}");
+        assert_eq!(without, with.trim_end());
+    }
+
+    // Files get one by default; the last flag given wins
+    let file = dir.path().join("out.json");
+    for (args, expected) in [
+        (&[][..], "[\n  1\n]\n"),
+        (&["--no-trailing-newline"], "[\n  1\n]"),
+        (
+            &["--no-trailing-newline", "--trailing-newline"],
+            "[\n  1\n]\n",
+        ),
+    ] {
+        vexy_json()
+            .args(args)
+            .arg("-o")
+            .arg(&file)
+            .write_stdin("[1]")
+            .assert()
+            .code(0);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), expected);
+    }
+
+    let input = dir.path().join("in.ndjson");
+    std::fs::write(&input, ndjson).unwrap();
+    vexy_json()
+        .args(["--ndjson", "--compact", "--output-dir"])
+        .arg(dir.path().join("out"))
+        .arg(&input)
+        .assert()
+        .code(0);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("out/in.ndjson")).unwrap(),
+        "{\"a\":1}\n[2]\n"
+    );
+}
+
+#[test]
+fn test_strip_unterminated_comment_fails() {
+    vexy_json()
+        .arg("--strip-comments")
+        .write_stdin("[1, /* open")
+        .assert()
+        .code(1)
+        .stdout("");
+}
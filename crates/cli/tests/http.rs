@@ -0,0 +1,359 @@
+// this_file: crates/cli/tests/http.rs
+
+//! http(s) URLs given as inputs are fetched from a server started by each
+//! test, and processed like files.
+
+#![cfg(feature = "http")]
+
+use assert_cmd::cargo::cargo_bin;
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A request as the test server saw it.
+struct Request {
+    target: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Starts a server on a free local port that answers every request with
+/// `respond`, returning its base URL.
+fn serve(respond: impl Fn(&Request) -> Vec<u8> + Send + Sync + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let respond = Arc::new(respond);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let respond = Arc::clone(&respond);
+            thread::spawn(move || answer(stream, &*respond));
+        }
+    });
+    base
+}
+
+fn answer(mut stream: TcpStream, respond: &dyn Fn(&Request) -> Vec<u8>) {
+    let mut head = Vec::new();
+    let mut byte = [0];
+    while !head.ends_with(b"\r\n\r\n") {
+        match stream.read(&mut byte) {
+            Ok(1) => head.push(byte[0]),
+            _ => return,
+        }
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut lines = head.lines();
+    let target = lines
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .unwrap_or_default()
+        .to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(": "))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let _ = stream.write_all(&respond(&Request { target, headers }));
+}
+
+fn response(status: &str, headers: &[(&str, &str)], body: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str("\r\n");
+    let mut response = response.into_bytes();
+    response.extend_from_slice(body);
+    response
+}
+
+fn vexy_json() -> Command {
+    let mut cmd = Command::cargo_bin("vexy-json").unwrap();
+    for proxy in ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY", "NO_PROXY"] {
+        cmd.env_remove(proxy).env_remove(proxy.to_lowercase());
+    }
+    cmd.env("NO_COLOR", "1");
+    cmd
+}
+
+#[test]
+fn test_url_input() {
+    let base = serve(|request| match request.target.as_str() {
+        "/thing" => response("200 OK", &[], b"{a: 1, b: [true,]}"),
+        _ => response("404 Not Found", &[], b""),
+    });
+
+    vexy_json()
+        .arg(format!("{base}/thing"))
+        .arg("--compact")
+        .assert()
+        .success()
+        .stdout(r#"{"a":1,"b":[true]}"#);
+
+    vexy_json()
+        .arg(format!("{base}/missing"))
+        .assert()
+        .code(2)
+        .stdout("")
+        .stderr(predicate::str::contains(format!(
+            "{base}/missing: HTTP 404 Not Found"
+        )));
+
+    vexy_json()
+        .args([
+            format!("{base}/missing"),
+            "--error-format".into(),
+            "json".into(),
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(format!(
+            r#"{{"code":"E1021","message":"HTTP 404 Not Found","file":"{base}/missing""#
+        )));
+}
+
+#[test]
+fn test_parse_errors_name_the_url() {
+    let base = serve(|_| response("200 OK", &[], b"{\"a\": 1,\n \"b\": @}"));
+    vexy_json()
+        .arg(format!("{base}/bad.json"))
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(format!("{base}/bad.json: ")));
+}
+
+#[test]
+fn test_charsets() {
+    let base = serve(|request| match request.target.as_str() {
+        "/latin1" => response(
+            "200 OK",
+            &[("Content-Type", "application/json; charset=ISO-8859-1")],
+            b"{\"name\": \"caf\xe9\"}",
+        ),
+        "/bom" => response(
+            "200 OK",
+            &[("Content-Type", "application/json; charset=\"utf-8\"")],
+            "\u{feff}{\"name\": \"café\"}".as_bytes(),
+        ),
+        "/utf16" => {
+            let body: Vec<u8> = "\u{feff}{\"name\": \"café\"}"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes)
+                .collect();
+            response("200 OK", &[("Content-Type", "application/json")], &body)
+        }
+        "/invalid" => response("200 OK", &[], b"{\"name\": \"caf\xe9\"}"),
+        _ => response(
+            "200 OK",
+            &[("Content-Type", "application/json; charset=klingon")],
+            b"{}",
+        ),
+    });
+
+    for path in ["/latin1", "/bom", "/utf16"] {
+        vexy_json()
+            .arg(format!("{base}{path}"))
+            .arg("--compact")
+            .assert()
+            .success()
+            .stdout(r#"{"name":"café"}"#);
+    }
+
+    // Without a charset, the body must be UTF-8, as files must
+    vexy_json()
+        .arg(format!("{base}/invalid"))
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(format!(
+            "{base}/invalid: Invalid UTF-8 sequence at position 13"
+        )));
+
+    vexy_json()
+        .arg(format!("{base}/unknown"))
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("unsupported charset 'klingon'"));
+}
+
+#[test]
+fn test_timeout_and_refused_connections() {
+    let base = serve(|_| {
+        thread::sleep(Duration::from_secs(10));
+        response("200 OK", &[], b"{}")
+    });
+    let started = Instant::now();
+    vexy_json()
+        .args([&format!("{base}/slow"), "--timeout", "1"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(format!(
+            "Could not fetch {base}/slow: "
+        )));
+    assert!(started.elapsed() < Duration::from_secs(8));
+
+    // Nothing listens on a port that was just freed
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    vexy_json()
+        .arg(format!("http://127.0.0.1:{port}/x"))
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(format!(
+            "Could not fetch http://127.0.0.1:{port}/x: "
+        )));
+}
+
+#[test]
+fn test_proxy_from_environment() {
+    // A proxy is sent the whole URL as the request target
+    let proxy = serve(|request| match request.target.as_str() {
+        "http://example.invalid/thing" => response("200 OK", &[], b"[1, 2]"),
+        _ => response("502 Bad Gateway", &[], b""),
+    });
+    vexy_json()
+        .arg("http://example.invalid/thing")
+        .arg("--compact")
+        .env("HTTP_PROXY", &proxy)
+        .assert()
+        .success()
+        .stdout("[1,2]");
+}
+
+#[test]
+fn test_watch_polls_with_etag() {
+    let version = Arc::new(AtomicUsize::new(1));
+    let not_modified = Arc::new(AtomicUsize::new(0));
+    let base = {
+        let version = Arc::clone(&version);
+        let not_modified = Arc::clone(&not_modified);
+        serve(move |request| {
+            let version = version.load(Ordering::SeqCst);
+            let etag = format!("\"v{version}\"");
+            if request.header("If-None-Match") == Some(etag.as_str()) {
+                not_modified.fetch_add(1, Ordering::SeqCst);
+                return response("304 Not Modified", &[("ETag", &etag)], b"");
+            }
+            let body = if version == 1 {
+                "{\"v\": 1}"
+            } else {
+                "{\"v\": @}"
+            };
+            response("200 OK", &[("ETag", &etag)], body.as_bytes())
+        })
+    };
+
+    let mut child = std::process::Command::new(cargo_bin("vexy-json"))
+        .args(["--watch", "--compact", "--poll-interval", "1"])
+        .arg(format!("{base}/config"))
+        .env("NO_COLOR", "1")
+        .env_remove("HTTP_PROXY")
+        .env_remove("http_proxy")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stderr = collect(child.stderr.take().unwrap());
+    let stdout = collect(child.stdout.take().unwrap());
+    let _guard = Kill(child);
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while not_modified.load(Ordering::SeqCst) < 2 && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(50));
+    }
+    assert!(not_modified.load(Ordering::SeqCst) >= 2);
+    assert!(stdout.lock().unwrap().contains(r#""v":1"#));
+    assert!(!stderr.lock().unwrap().contains("URL changed"));
+
+    version.store(2, Ordering::SeqCst);
+    while !stderr.lock().unwrap().contains("Unexpected") && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(50));
+    }
+    let stderr = stderr.lock().unwrap().clone();
+    assert!(
+        stderr.contains(&format!("URL changed: {base}/config")),
+        "{stderr}"
+    );
+    assert!(stderr.contains(&format!("{base}/config: ")), "{stderr}");
+}
+
+/// Collects what `pipe` yields as it arrives.
+fn collect(mut pipe: impl Read + Send + 'static) -> Arc<Mutex<String>> {
+    let collected = Arc::new(Mutex::new(String::new()));
+    let sink = Arc::clone(&collected);
+    thread::spawn(move || {
+        let mut buffer = [0; 4096];
+        while let Ok(n @ 1..) = pipe.read(&mut buffer) {
+            sink.lock()
+                .unwrap()
+                .push_str(&String::from_utf8_lossy(&buffer[..n]));
+        }
+    });
+    collected
+}
+
+struct Kill(Child);
+
+impl Drop for Kill {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+#[test]
+fn test_slow_url_does_not_stall_file_watching() {
+    let base = serve(|_| {
+        thread::sleep(Duration::from_secs(20));
+        response("200 OK", &[], b"{}")
+    });
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("config.json");
+    std::fs::write(&file, "{\"a\": 1}").unwrap();
+
+    let mut child = std::process::Command::new(cargo_bin("vexy-json"))
+        .args(["--watch", "--timeout", "60"])
+        .arg(&file)
+        .arg(format!("{base}/slow"))
+        .env("NO_COLOR", "1")
+        .env_remove("HTTP_PROXY")
+        .env_remove("http_proxy")
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stderr = collect(child.stderr.take().unwrap());
+    let _guard = Kill(child);
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while !stderr.lock().unwrap().contains("Watching") && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(50));
+    }
+    // While the URL is still being fetched, a changed file is processed
+    let changed = Instant::now();
+    std::fs::write(&file, "{\"a\": @}").unwrap();
+    while !stderr.lock().unwrap().contains("Unexpected") && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(50));
+    }
+    let stderr = stderr.lock().unwrap().clone();
+    assert!(stderr.contains("File changed:"), "{stderr}");
+    assert!(changed.elapsed() < Duration::from_secs(10), "{stderr}");
+}
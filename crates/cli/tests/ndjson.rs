@@ -0,0 +1,113 @@
+// this_file: crates/cli/tests/ndjson.rs
+
+//! --to-ndjson writes the elements of an array one per line, and
+//! --from-ndjson reads NDJSON lines back into an array or, with both flags,
+//! passes them through.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn vexy_json() -> Command {
+    let mut cmd = Command::cargo_bin("vexy-json").unwrap();
+    cmd.env("NO_COLOR", "1");
+    cmd
+}
+
+#[test]
+fn test_array_to_ndjson() {
+    vexy_json()
+        .arg("--to-ndjson")
+        .write_stdin("[{zeta: 1, alpha: 'a\\nb'}, [1, [2, []]], \"x\", null,]")
+        .assert()
+        .code(0)
+        .stdout("{\"zeta\":1,\"alpha\":\"a\\nb\"}\n[1,[2,[]]]\n\"x\"\nnull\n");
+
+    vexy_json()
+        .args(["--to-ndjson", "--line-ending", "crlf"])
+        .write_stdin("[1, {\"a\": 2}]")
+        .assert()
+        .code(0)
+        .stdout("1\r\n{\"a\":2}\r\n");
+
+    // An empty array has no lines
+    vexy_json()
+        .arg("--to-ndjson")
+        .write_stdin("[]")
+        .assert()
+        .code(0)
+        .stdout("");
+}
+
+#[test]
+fn test_to_ndjson_needs_an_array() {
+    for input in ["{\"a\": 1}", "42"] {
+        vexy_json()
+            .arg("--to-ndjson")
+            .write_stdin(input)
+            .assert()
+            .code(1)
+            .stdout("")
+            .stderr(predicate::str::contains(
+                "--to-ndjson needs a top-level array",
+            ));
+    }
+}
+
+#[test]
+fn test_ndjson_to_array() {
+    vexy_json()
+        .args(["--from-ndjson", "--compact"])
+        .write_stdin("{\"b\": 1, \"a\": [2]}\r\n\r\n[[1], []]\r\n\"last\"")
+        .assert()
+        .code(0)
+        .stdout(r#"[{"a":[2],"b":1},[[1],[]],"last"]"#);
+
+    vexy_json()
+        .args(["--from-ndjson", "--compact"])
+        .write_stdin("\n\n")
+        .assert()
+        .code(0)
+        .stdout("[]");
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let first = dir.path().join("first.ndjson");
+    let second = dir.path().join("second.ndjson");
+    std::fs::write(&first, "1\n2\n").unwrap();
+    std::fs::write(&second, "[3]\n").unwrap();
+    vexy_json()
+        .args(["--from-ndjson", "--compact"])
+        .arg(&first)
+        .arg(&second)
+        .assert()
+        .code(0)
+        .stdout("[1,2]\n[[3]]");
+}
+
+#[test]
+fn test_from_ndjson_errors_name_the_line() {
+    vexy_json()
+        .args(["--from-ndjson", "--compact"])
+        .write_stdin("{\"a\": 1}\r\n{oops\r\n[2]\r\n")
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains("Error on line 2"));
+}
+
+#[test]
+fn test_ndjson_passthrough() {
+    vexy_json()
+        .args(["--from-ndjson", "--to-ndjson"])
+        .write_stdin("{\"b\": 1, \"a\": \"x\\ny\"}\r\n\r\n  [1, [2]]  \r\n[]")
+        .assert()
+        .code(0)
+        .stdout("{\"b\":1,\"a\":\"x\\ny\"}\n[1,[2]]\n[]\n");
+
+    // Lines before a failing one have already been written
+    vexy_json()
+        .args(["--from-ndjson", "--to-ndjson"])
+        .write_stdin("1\n{oops\n3\n")
+        .assert()
+        .code(1)
+        .stdout("1\n");
+}
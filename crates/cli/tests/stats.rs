@@ -0,0 +1,122 @@
+// this_file: crates/cli/tests/stats.rs
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+use vexy_json_core::ast::{Number, Value};
+
+fn vexy_json() -> Command {
+    Command::cargo_bin("vexy-json").unwrap()
+}
+
+fn int(value: &Value) -> i64 {
+    value.as_i64().unwrap()
+}
+
+#[test]
+fn test_stats_json_totals_across_parallel_files() {
+    let dir = TempDir::new().unwrap();
+    let files = [
+        ("fast.json", r#"{"a": 1, "b": [1, 2]}"#),
+        ("forgiving.json", "// config\n{a: {b: {c: 1}}}"),
+        ("repair.json", "[1, [2, 3"),
+        ("broken.json", r#"{"a": }"#),
+    ];
+    let paths: Vec<_> = files
+        .iter()
+        .map(|(name, content)| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        })
+        .collect();
+
+    let output = vexy_json()
+        .args(["--stats", "--stats-format", "json", "--parallel"])
+        .args(&paths)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let report = vexy_json_core::parse(stderr.lines().next().unwrap()).unwrap();
+
+    let per_file = report["files"].as_array().unwrap();
+    assert_eq!(per_file.len(), 4);
+    let tiers: Vec<_> = per_file.iter().map(|file| file["tier"].as_str()).collect();
+    assert_eq!(
+        tiers,
+        [Some("fast"), Some("forgiving"), Some("repair"), None]
+    );
+    assert_eq!(per_file[3]["ok"], Value::Bool(false));
+    let values: Vec<_> = per_file[..3]
+        .iter()
+        .map(|file| int(&file["values"]))
+        .collect();
+    assert_eq!(values, [5, 4, 5]);
+
+    let totals = &report["totals"];
+    assert_eq!(int(&totals["files"]), 4);
+    assert_eq!(int(&totals["failed"]), 1);
+    let valid_bytes: usize = files[..3].iter().map(|(_, content)| content.len()).sum();
+    assert_eq!(int(&totals["bytes"]), valid_bytes as i64);
+    assert_eq!(int(&totals["values"]), 14);
+    assert_eq!(int(&totals["max_depth"]), 3);
+    // Keys "a", "b", "a", "b", "c"
+    assert_eq!(int(&totals["string_bytes"]), 5);
+    let heap: i64 = per_file[..3]
+        .iter()
+        .map(|file| int(&file["heap_bytes"]))
+        .sum();
+    assert!(heap > 0);
+    assert_eq!(int(&totals["heap_bytes"]), heap);
+    assert_eq!(int(&totals["repairs"]), 1);
+    for tier in ["fast", "forgiving", "repair"] {
+        assert_eq!(totals["tiers"][tier], Value::Number(Number::Integer(1)));
+    }
+    let summed: f64 = per_file[..3]
+        .iter()
+        .map(|file| file["time_ms"].as_f64().unwrap())
+        .sum();
+    assert!((totals["parse_time_ms"].as_f64().unwrap() - summed).abs() < 0.01);
+}
+
+#[test]
+fn test_stats_human_summary() {
+    vexy_json()
+        .arg("--stats")
+        .write_stdin("[1, {a: 2}")
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("\"a\""))
+        .stderr(
+            predicate::str::contains("Files: 1 (0 failed)")
+                .and(predicate::str::contains("Values: 4"))
+                .and(predicate::str::contains("String bytes: 1"))
+                .and(predicate::str::contains("Estimated heap: "))
+                .and(predicate::str::contains(
+                    "Tiers: 0 fast, 0 forgiving, 1 repaired",
+                )),
+        );
+}
+
+#[test]
+fn test_stats_format_requires_stats() {
+    vexy_json()
+        .args(["--stats-format", "json"])
+        .write_stdin("1")
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_completions() {
+    for shell in ["bash", "zsh", "fish"] {
+        vexy_json()
+            .args(["completions", shell])
+            .assert()
+            .code(0)
+            .stdout(predicate::str::contains("stats-format"));
+    }
+    vexy_json().args(["completions", "tcsh"]).assert().code(2);
+}
@@ -0,0 +1,139 @@
+// this_file: crates/cli/tests/watch.rs
+
+use assert_cmd::cargo::cargo_bin;
+use std::fs;
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// How long to wait for the command to write what a test expects.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A running `vexy-json --watch`, with its output collected as it arrives.
+struct Watch {
+    child: Child,
+    chunks: Receiver<(Stream, String)>,
+    output: Output,
+}
+
+#[derive(Clone, Copy)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// What the command has written so far.
+#[derive(Default)]
+struct Output {
+    stdout: String,
+    stderr: String,
+}
+
+impl Watch {
+    fn start(args: &[&str], files: &[std::path::PathBuf]) -> Self {
+        let mut child = Command::new(cargo_bin("vexy-json"))
+            .arg("--watch")
+            .args(args)
+            .args(files)
+            .env("NO_COLOR", "1")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let (sender, chunks) = mpsc::channel();
+        forward(child.stdout.take().unwrap(), Stream::Stdout, sender.clone());
+        forward(child.stderr.take().unwrap(), Stream::Stderr, sender);
+        Watch {
+            child,
+            chunks,
+            output: Output::default(),
+        }
+    }
+
+    /// Waits until the output satisfies `done`, returning it, and fails the
+    /// test if that takes longer than [`TIMEOUT`] or the command exits.
+    fn wait_for(&mut self, done: impl Fn(&Output) -> bool) -> &Output {
+        let deadline = Instant::now() + TIMEOUT;
+        while !done(&self.output) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.chunks.recv_timeout(remaining) {
+                Ok((Stream::Stdout, chunk)) => self.output.stdout.push_str(&chunk),
+                Ok((Stream::Stderr, chunk)) => self.output.stderr.push_str(&chunk),
+                Err(error) => panic!("{error} waiting for output:\n{}", self.output.stderr),
+            }
+        }
+        &self.output
+    }
+}
+
+/// Sends what `pipe` yields to `sender` as it is read.
+fn forward(mut pipe: impl Read + Send + 'static, stream: Stream, sender: Sender<(Stream, String)>) {
+    thread::spawn(move || {
+        let mut buffer = [0; 4096];
+        while let Ok(n @ 1..) = pipe.read(&mut buffer) {
+            let chunk = String::from_utf8_lossy(&buffer[..n]).into_owned();
+            if sender.send((stream, chunk)).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn test_burst_of_changes_prints_one_block_per_file_in_path_order() {
+    let dir = TempDir::new().unwrap();
+    let files: Vec<_> = (0..50)
+        .map(|i| {
+            let path = dir.path().join(format!("file{i:02}.json"));
+            fs::write(&path, format!("{{\"n\": {i}}}")).unwrap();
+            path
+        })
+        .collect();
+
+    let mut watch = Watch::start(&["--jobs", "4"], &files);
+    // Let the initial pass print every file before changing anything
+    watch.wait_for(|output| output.stdout.matches("\"n\"").count() >= files.len());
+
+    // Written out of order, each file breaking at a different place
+    for (i, path) in files.iter().enumerate().rev() {
+        fs::write(path, format!("{{\"n\": {i}, \"x\": @}}")).unwrap();
+    }
+    let stderr = &watch
+        .wait_for(|output| output.stderr.matches("Unexpected").count() >= files.len())
+        .stderr;
+
+    let burst = &stderr[stderr.find("File changed:").expect(stderr)..];
+    let blocks: Vec<&str> = burst.split("File changed: ").skip(1).collect();
+    let changed: Vec<&str> = blocks
+        .iter()
+        .map(|block| block.lines().next().unwrap())
+        .collect();
+    let expected: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+    assert_eq!(changed, expected, "{stderr}");
+
+    // Each block holds exactly its own file's error
+    for (block, file) in blocks.iter().zip(&expected) {
+        assert_eq!(block.matches("Error:").count(), 1, "{block}");
+        assert_eq!(block.matches(".json").count(), 2, "{block}");
+        assert!(block.contains(&format!("{file}: ")), "{block}");
+    }
+}
+
+#[test]
+fn test_jobs_must_be_positive() {
+    assert_cmd::Command::cargo_bin("vexy-json")
+        .unwrap()
+        .args(["--jobs", "0", "--parallel"])
+        .assert()
+        .code(2);
+}
@@ -7,11 +7,12 @@
 //! - Large file handling
 //! - Error recovery performance
 
-use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use std::fs;
+use std::{fs, hint::black_box};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use vexy_json_core::{
     parse, parse_optimized, parse_optimized_v2, parse_optimized_with_options, parse_v2_with_stats,
-    parse_with_stats, ParserOptions,
+    parse_with_options, parse_with_stats, ParserOptions,
 };
 
 /// Sample JSON documents for benchmarking
@@ -257,6 +258,7 @@ fn bench_error_recovery(c: &mut Criterion) {
         max_repairs: 20,
         fast_repair: false,
         report_repairs: true,
+        ..Default::default()
     };
 
     group.bench_function("malformed_json_recovery", |b| {
@@ -270,6 +272,42 @@ fn bench_error_recovery(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmarks the cost of tracking document paths for error reporting
+fn bench_path_tracking(c: &mut Criterion) {
+    let mut group = c.benchmark_group("path_tracking");
+
+    for track_paths in [false, true] {
+        let options = ParserOptions {
+            track_paths,
+            ..Default::default()
+        };
+        group.bench_with_input(
+            BenchmarkId::new("nested_object", track_paths),
+            &options,
+            |b, options| {
+                b.iter(|| {
+                    let result =
+                        parse_with_options(black_box(samples::NESTED_OBJECT), options.clone());
+                    assert!(result.is_ok());
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("array_of_objects", track_paths),
+            &options,
+            |b, options| {
+                b.iter(|| {
+                    let result =
+                        parse_with_options(black_box(samples::ARRAY_OF_OBJECTS), options.clone());
+                    assert!(result.is_ok());
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
 /// Benchmarks real-world JSON files if available
 fn bench_real_world(c: &mut Criterion) {
     // Try to load some real-world JSON files for benchmarking
@@ -323,6 +361,7 @@ criterion_group!(
     bench_memory_pool,
     bench_scaling,
     bench_error_recovery, // TODO: Fix error recovery for optimized parser
+    bench_path_tracking,
     bench_real_world
 );
 
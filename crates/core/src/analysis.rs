@@ -0,0 +1,241 @@
+// this_file: crates/core/src/analysis.rs
+
+//! Summary statistics for a parsed document.
+//!
+//! [`analyze`] walks a [`Value`] once and reports how many values of each
+//! kind it holds and how large and deep it is, which is what tools need to
+//! describe a document, or decide whether to cache it, without printing it.
+//!
+//! The walks here keep their own stack rather than recursing, so documents
+//! nested deeper than the thread's stack allows are measured like any other.
+
+use crate::ast::Value;
+use rustc_hash::FxHashMap;
+use std::collections::hash_map;
+use std::mem::size_of;
+use std::slice;
+
+/// Counts and sizes describing a document, as computed by [`analyze`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentStats {
+    /// Number of objects
+    pub objects: usize,
+    /// Number of arrays
+    pub arrays: usize,
+    /// Number of strings, not counting object keys
+    pub strings: usize,
+    /// Number of numbers
+    pub numbers: usize,
+    /// Number of booleans
+    pub booleans: usize,
+    /// Number of nulls
+    pub nulls: usize,
+    /// Deepest container nesting; zero for a lone scalar
+    pub max_depth: usize,
+    /// Number of object members across all objects
+    pub total_keys: usize,
+    /// Length in characters of the longest string value
+    pub longest_string: usize,
+    /// Total length in bytes of string values and object keys
+    pub string_bytes: usize,
+    /// Estimated bytes the document holds on the heap, not counting the
+    /// root [`Value`] itself
+    ///
+    /// This is computed from the capacities of strings, arrays and objects,
+    /// so it reflects spare capacity too, but not the allocator's own
+    /// overhead.
+    pub heap_bytes: usize,
+}
+
+impl DocumentStats {
+    /// Total number of values, counting containers as well as their contents.
+    pub fn values(&self) -> usize {
+        self.objects + self.arrays + self.strings + self.numbers + self.booleans + self.nulls
+    }
+}
+
+/// Computes [`DocumentStats`] for `value` in a single pass.
+pub fn analyze(value: &Value) -> DocumentStats {
+    let mut stats = DocumentStats::default();
+    for_each_value(value, |value, depth| match value {
+        Value::Null => stats.nulls += 1,
+        Value::Bool(_) => stats.booleans += 1,
+        Value::Number(_) => stats.numbers += 1,
+        Value::String(s) => {
+            stats.strings += 1;
+            stats.longest_string = stats.longest_string.max(s.chars().count());
+            stats.string_bytes += s.len();
+            stats.heap_bytes += s.capacity();
+        }
+        Value::Array(array) => {
+            stats.arrays += 1;
+            stats.max_depth = stats.max_depth.max(depth + 1);
+            stats.heap_bytes += array.capacity() * size_of::<Value>();
+        }
+        Value::Object(object) => {
+            stats.objects += 1;
+            stats.max_depth = stats.max_depth.max(depth + 1);
+            stats.total_keys += object.len();
+            stats.heap_bytes += table_bytes(object);
+            for key in object.keys() {
+                stats.string_bytes += key.len();
+                stats.heap_bytes += key.capacity();
+            }
+        }
+    });
+    stats
+}
+
+impl Value {
+    /// Computes [`DocumentStats`] for this value; see [`analyze`].
+    pub fn stats(&self) -> DocumentStats {
+        analyze(self)
+    }
+
+    /// Deepest container nesting in this value: zero for a scalar, one for
+    /// a flat array or object.
+    pub fn depth(&self) -> usize {
+        let mut max_depth = 0;
+        for_each_value(self, |value, depth| {
+            if matches!(value, Value::Array(_) | Value::Object(_)) {
+                max_depth = max_depth.max(depth + 1);
+            }
+        });
+        max_depth
+    }
+
+    /// Number of values in this value, counting itself and every container
+    /// as well as their contents.
+    pub fn len_recursive(&self) -> usize {
+        let mut count = 0;
+        for_each_value(self, |_, _| count += 1);
+        count
+    }
+}
+
+/// Estimated size of the hash table behind `object`: its buckets and their
+/// control bytes, which the table rounds up to a power of two holding
+/// `capacity` at a load factor of 7/8.
+fn table_bytes(object: &FxHashMap<String, Value>) -> usize {
+    if object.capacity() == 0 {
+        return 0;
+    }
+    let buckets = (object.capacity() * 8 / 7).next_power_of_two();
+    buckets * (size_of::<(String, Value)>() + 1) + GROUP_WIDTH
+}
+
+/// Control bytes a hash table allocates beyond one per bucket.
+const GROUP_WIDTH: usize = 16;
+
+/// Calls `f` with every value in `value`, parents before their contents,
+/// along with how many containers enclose it.
+///
+/// The stack holds one iterator per open container, so the walk needs
+/// memory in proportion to the depth of the document, not its size.
+fn for_each_value<'a>(value: &'a Value, mut f: impl FnMut(&'a Value, usize)) {
+    f(value, 0);
+    let mut stack: Vec<Children<'a>> = Children::of(value).into_iter().collect();
+    while let Some(children) = stack.last_mut() {
+        match children.next() {
+            Some(child) => {
+                f(child, stack.len());
+                stack.extend(Children::of(child));
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+}
+
+/// The values directly inside a container.
+enum Children<'a> {
+    Array(slice::Iter<'a, Value>),
+    Object(hash_map::Values<'a, String, Value>),
+}
+
+impl<'a> Children<'a> {
+    fn of(value: &'a Value) -> Option<Self> {
+        match value {
+            Value::Array(array) => Some(Children::Array(array.iter())),
+            Value::Object(object) => Some(Children::Object(object.values())),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Iterator for Children<'a> {
+    type Item = &'a Value;
+
+    fn next(&mut self) -> Option<&'a Value> {
+        match self {
+            Children::Array(values) => values.next(),
+            Children::Object(values) => values.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_analyze_nested_document() {
+        let value = parse(
+            r#"{
+                "name": "Zoë",
+                "tags": ["a", "bb", null],
+                "owner": {"id": 7, "active": true, "roles": [[1.5], []]},
+                "notes": "longest string"
+            }"#,
+        )
+        .unwrap();
+
+        let stats = analyze(&value);
+        assert_eq!(
+            stats,
+            DocumentStats {
+                objects: 2,
+                arrays: 4,
+                strings: 4,
+                numbers: 2,
+                booleans: 1,
+                nulls: 1,
+                max_depth: 4,
+                total_keys: 7,
+                longest_string: 14,
+                string_bytes: 52,
+                heap_bytes: stats.heap_bytes,
+            }
+        );
+        assert_eq!(stats.values(), 14);
+        assert_eq!(value.len_recursive(), 14);
+        assert_eq!(value.depth(), 4);
+    }
+
+    #[test]
+    fn test_analyze_scalars_and_empty_containers() {
+        let scalar = analyze(&Value::String("héllo".to_string()));
+        assert_eq!(scalar.max_depth, 0);
+        assert_eq!(scalar.longest_string, 5);
+        assert_eq!(scalar.values(), 1);
+
+        let empty = analyze(&parse("{}").unwrap());
+        assert_eq!(empty.max_depth, 1);
+        assert_eq!(empty.total_keys, 0);
+    }
+
+    #[test]
+    fn test_deeply_nested_document() {
+        let mut value = Value::Null;
+        for _ in 0..100_000 {
+            value = Value::Array(vec![value]);
+        }
+        assert_eq!(value.depth(), 100_000);
+        assert_eq!(value.len_recursive(), 100_001);
+        assert_eq!(value.stats().arrays, 100_000);
+        // Dropping the value recurses once per level
+        std::mem::forget(value);
+    }
+}
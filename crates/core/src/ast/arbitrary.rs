@@ -0,0 +1,121 @@
+// this_file: crates/core/src/ast/arbitrary.rs
+
+//! Generating values from fuzzer input, behind the `arbitrary` cargo feature.
+//!
+//! Code that processes parsed documents can then be fuzzed or property-tested
+//! directly, without going through JSON text. Generated values are always
+//! representable as JSON: floats are finite, and nesting and container sizes
+//! are bounded so that a single input cannot produce an enormous document.
+
+use crate::ast::{Number, Value};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use rustc_hash::FxHashMap;
+
+/// Deepest container nesting in a generated value.
+const MAX_DEPTH: usize = 8;
+
+/// Most elements or members in a generated array or object.
+const MAX_LEN: usize = 16;
+
+impl<'a> Arbitrary<'a> for Value {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_value(u, 0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Number {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if u.arbitrary()? {
+            Ok(Number::Integer(u.arbitrary()?))
+        } else {
+            let float: f64 = u.arbitrary()?;
+            Ok(Number::Float(if float.is_finite() { float } else { 0.0 }))
+        }
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(9))
+    }
+}
+
+fn arbitrary_value(u: &mut Unstructured<'_>, depth: usize) -> Result<Value> {
+    // Containers are only offered above the depth limit, and stop growing
+    // once the input runs out
+    let kinds = if depth < MAX_DEPTH { 6 } else { 4 };
+    Ok(match u.choose_index(kinds)? {
+        0 => Value::Null,
+        1 => Value::Bool(u.arbitrary()?),
+        2 => Value::Number(u.arbitrary()?),
+        3 => Value::String(u.arbitrary()?),
+        4 => {
+            let len = u.arbitrary_len::<Value>()?.min(MAX_LEN);
+            let mut array = Vec::with_capacity(len);
+            for _ in 0..len {
+                array.push(arbitrary_value(u, depth + 1)?);
+            }
+            Value::Array(array)
+        }
+        _ => {
+            let len = u.arbitrary_len::<(String, Value)>()?.min(MAX_LEN);
+            let mut object = FxHashMap::default();
+            for _ in 0..len {
+                let key = u.arbitrary()?;
+                object.insert(key, arbitrary_value(u, depth + 1)?);
+            }
+            Value::Object(object)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::CompareOptions;
+    use crate::format::{to_compact_string, KeyOrder};
+    use crate::parser::{parse_with_options, ParserOptions};
+
+    /// Deterministic bytes standing in for fuzzer input.
+    fn input(seed: u64) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+        (0..4096)
+            .map(|_| {
+                // xorshift64
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_generated_values_round_trip() {
+        // Integers beyond 2^53 are otherwise read through an f64
+        let options = ParserOptions {
+            preserve_number_precision: true,
+            ..Default::default()
+        };
+        for seed in 0..500 {
+            let bytes = input(seed);
+            let value = Value::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            assert!(value.depth() <= MAX_DEPTH);
+
+            let json = to_compact_string(&value, &KeyOrder::default());
+            let reparsed = parse_with_options(&json, options.clone())
+                .unwrap_or_else(|e| panic!("{e}: {json}"));
+            assert!(
+                value.equals_with(&reparsed, &CompareOptions::default()),
+                "{value:?} became {reparsed:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_same_input_same_value() {
+        let bytes = input(7);
+        assert_eq!(
+            Value::arbitrary(&mut Unstructured::new(&bytes)).unwrap(),
+            Value::arbitrary(&mut Unstructured::new(&bytes)).unwrap()
+        );
+    }
+}
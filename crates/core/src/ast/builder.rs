@@ -93,11 +93,9 @@ impl ValueBuilder {
 
     /// End the current object or array
     pub fn end(mut self) -> Result<Self> {
-        if self.stack.is_empty() {
+        let Some(completed) = self.stack.pop() else {
             return Err(Error::Custom("No object or array to end".to_string()));
-        }
-
-        let completed = self.stack.pop().unwrap();
+        };
         let value = match completed {
             BuilderState::Object { map, .. } => Value::Object(map),
             BuilderState::Array { vec } => Value::Array(vec),
@@ -199,6 +197,19 @@ impl ObjectBuilder {
         }
     }
 
+    /// Create an object builder with room for `capacity` entries
+    pub fn with_capacity(capacity: usize) -> Self {
+        ObjectBuilder {
+            map: FxHashMap::with_capacity_and_hasher(capacity, Default::default()),
+        }
+    }
+
+    /// Reserve room for at least `additional` more entries
+    pub fn reserve(mut self, additional: usize) -> Self {
+        self.map.reserve(additional);
+        self
+    }
+
     /// Add a key-value pair
     pub fn insert<S: Into<String>>(mut self, key: S, value: Value) -> Self {
         self.map.insert(key.into(), value);
@@ -253,6 +264,19 @@ impl ArrayBuilder {
         ArrayBuilder { vec: Vec::new() }
     }
 
+    /// Create an array builder with room for `capacity` values
+    pub fn with_capacity(capacity: usize) -> Self {
+        ArrayBuilder {
+            vec: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve room for at least `additional` more values
+    pub fn reserve(mut self, additional: usize) -> Self {
+        self.vec.reserve(additional);
+        self
+    }
+
     /// Add a value
     pub fn push(mut self, value: Value) -> Self {
         self.vec.push(value);
@@ -456,6 +480,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_builders_with_capacity() {
+        let value = ArrayBuilder::with_capacity(3)
+            .integer(1)
+            .reserve(10)
+            .integer(2)
+            .build()
+            .unwrap();
+        match value {
+            Value::Array(vec) => {
+                assert_eq!(vec.len(), 2);
+                assert!(vec.capacity() >= 11);
+            }
+            _ => panic!("Expected array"),
+        }
+
+        let value = ObjectBuilder::with_capacity(100)
+            .integer("a", 1)
+            .reserve(5)
+            .build()
+            .unwrap();
+        match value {
+            Value::Object(map) => {
+                assert_eq!(map.len(), 1);
+                assert!(map.capacity() >= 100);
+            }
+            _ => panic!("Expected object"),
+        }
+    }
+
     #[test]
     fn test_build_helpers() {
         use build::*;
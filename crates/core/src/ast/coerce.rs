@@ -0,0 +1,236 @@
+// this_file: crates/core/src/ast/coerce.rs
+
+//! Typed accessors reading dates, UUIDs, decimals and durations out of values.
+//!
+//! Each accessor is behind its own cargo feature (`datetime`, `uuid`,
+//! `decimal`, `duration`) so that builds which do not need them stay lean.
+//! Like the other `as_*` accessors, they return `None` for values that are
+//! not of the expected form rather than failing.
+
+#[cfg(feature = "decimal")]
+use crate::ast::Number;
+use crate::ast::Value;
+#[cfg(feature = "datetime")]
+use chrono::{DateTime, FixedOffset, Timelike};
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+#[cfg(feature = "decimal")]
+use std::str::FromStr;
+#[cfg(feature = "duration")]
+use std::time::Duration;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+impl Value {
+    /// Reads an RFC 3339 string such as `2024-03-01T12:30:00+05:30`,
+    /// keeping its UTC offset.
+    ///
+    /// Leap seconds (`23:59:60`) are rejected, as most consumers of
+    /// `DateTime` cannot represent them.
+    #[cfg(feature = "datetime")]
+    pub fn as_datetime(&self) -> Option<DateTime<FixedOffset>> {
+        let datetime = DateTime::parse_from_rfc3339(self.as_str()?).ok()?;
+        (datetime.nanosecond() < 1_000_000_000).then_some(datetime)
+    }
+
+    /// Reads a UUID string in any of its common forms: hyphenated, simple,
+    /// braced or `urn:uuid:`.
+    #[cfg(feature = "uuid")]
+    pub fn as_uuid(&self) -> Option<Uuid> {
+        Uuid::parse_str(self.as_str()?).ok()
+    }
+
+    /// Reads a number, or a string holding one, as a decimal.
+    ///
+    /// Strings may use scientific notation. Returns `None` for values
+    /// outside the range of [`Decimal`], and for NaN and infinities.
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self {
+            Value::Number(Number::Integer(i)) => Some(Decimal::from(*i)),
+            Value::Number(Number::Float(f)) => Decimal::try_from(*f).ok(),
+            Value::String(s) => Decimal::from_str(s)
+                .or_else(|_| Decimal::from_scientific(s))
+                .ok(),
+            _ => None,
+        }
+    }
+
+    /// Reads an ISO 8601 duration such as `P1DT2H30M` or `PT0.5S`.
+    ///
+    /// Weeks, days, hours, minutes and seconds are supported, with a
+    /// fraction allowed on seconds only. Years and months have no fixed
+    /// length, so durations using them return `None`, as do negative
+    /// durations.
+    #[cfg(feature = "duration")]
+    pub fn as_duration(&self) -> Option<Duration> {
+        parse_iso8601_duration(self.as_str()?)
+    }
+}
+
+/// Designators allowed before `T`, in order, with their length in seconds.
+#[cfg(feature = "duration")]
+const DATE_UNITS: &[(char, u64)] = &[('W', 7 * 86_400), ('D', 86_400)];
+
+/// Designators allowed after `T`, in order, with their length in seconds.
+#[cfg(feature = "duration")]
+const TIME_UNITS: &[(char, u64)] = &[('H', 3_600), ('M', 60), ('S', 1)];
+
+#[cfg(feature = "duration")]
+fn parse_iso8601_duration(s: &str) -> Option<Duration> {
+    let rest = s.strip_prefix('P')?;
+    let (date, time) = match rest.split_once('T') {
+        Some((_, "")) => return None,
+        Some((date, time)) => (date, time),
+        None if rest.is_empty() => return None,
+        None => (rest, ""),
+    };
+    let mut total = Duration::ZERO;
+    add_components(date, DATE_UNITS, &mut total)?;
+    add_components(time, TIME_UNITS, &mut total)?;
+    Some(total)
+}
+
+/// Adds each `<number><designator>` component of `text` to `total`,
+/// requiring designators to appear in the order of `units`.
+#[cfg(feature = "duration")]
+fn add_components(text: &str, units: &[(char, u64)], total: &mut Duration) -> Option<()> {
+    let mut rest = text;
+    let mut next_unit = 0;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')?;
+        let (number, tail) = rest.split_at(end);
+        let designator = tail.chars().next()?;
+        let offset = units[next_unit..]
+            .iter()
+            .position(|&(unit, _)| unit == designator)?;
+        let (_, unit_seconds) = units[next_unit + offset];
+        next_unit += offset + 1;
+        *total = total.checked_add(component(number, unit_seconds)?)?;
+        rest = &tail[designator.len_utf8()..];
+    }
+    Some(())
+}
+
+/// Converts one component's number of `unit_seconds`-long units.
+#[cfg(feature = "duration")]
+fn component(number: &str, unit_seconds: u64) -> Option<Duration> {
+    let (whole, fraction) = number.split_once(['.', ',']).unwrap_or((number, ""));
+    let fractional = whole.len() < number.len();
+    if whole.is_empty() || (fractional && (unit_seconds != 1 || fraction.is_empty())) {
+        return None;
+    }
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let seconds = whole.parse::<u64>().ok()?.checked_mul(unit_seconds)?;
+    // Nanoseconds from the first nine fractional digits
+    let nanos = fraction
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0u32, |nanos, digit| nanos * 10 + u32::from(digit - b'0'));
+    Some(Duration::new(seconds, nanos))
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "datetime",
+        feature = "uuid",
+        feature = "decimal",
+        feature = "duration"
+    )
+))]
+mod tests {
+    use super::*;
+    use crate::ast::Number;
+
+    #[cfg(feature = "datetime")]
+    #[test]
+    fn test_as_datetime() {
+        let value = Value::String("2024-03-01T12:30:00.25+05:30".to_string());
+        let datetime = value.as_datetime().unwrap();
+        assert_eq!(datetime.offset().local_minus_utc(), 5 * 3600 + 30 * 60);
+        assert_eq!(datetime.to_rfc3339(), "2024-03-01T12:30:00.250+05:30");
+
+        let utc = Value::String("2024-03-01T07:00:00.25Z".to_string());
+        assert_eq!(utc.as_datetime().unwrap(), datetime);
+
+        for invalid in ["2016-12-31T23:59:60Z", "2024-13-01T00:00:00Z", "2024-03-01"] {
+            assert!(Value::String(invalid.to_string()).as_datetime().is_none());
+        }
+        assert!(Value::Number(Number::Integer(0)).as_datetime().is_none());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_as_uuid() {
+        let text = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        let uuid = Value::String(text.to_string()).as_uuid().unwrap();
+        assert_eq!(uuid.to_string(), text);
+        let braced = Value::String(format!("{{{text}}}"));
+        assert_eq!(braced.as_uuid(), Some(uuid));
+
+        assert!(Value::String("67e55044-10b1".to_string())
+            .as_uuid()
+            .is_none());
+        assert!(Value::Number(Number::Integer(1)).as_uuid().is_none());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_as_decimal() {
+        let decimal = |s: &str| Decimal::from_str(s).unwrap();
+        assert_eq!(
+            Value::String("12.345".to_string()).as_decimal(),
+            Some(decimal("12.345"))
+        );
+        assert_eq!(
+            Value::String("1.5e3".to_string()).as_decimal(),
+            Some(decimal("1500"))
+        );
+        assert_eq!(
+            Value::Number(Number::Integer(i64::MIN)).as_decimal(),
+            Some(Decimal::from(i64::MIN))
+        );
+        assert_eq!(
+            Value::Number(Number::Float(0.5)).as_decimal(),
+            Some(decimal("0.5"))
+        );
+
+        let too_large = [
+            Value::Number(Number::Float(1e30)),
+            Value::Number(Number::Float(f64::NAN)),
+            Value::Number(Number::Float(f64::INFINITY)),
+            Value::String("79228162514264337593543950336".to_string()),
+            Value::String("1e40".to_string()),
+        ];
+        for value in too_large {
+            assert!(value.as_decimal().is_none(), "{value}");
+        }
+        assert!(Value::String("twelve".to_string()).as_decimal().is_none());
+        assert!(Value::Bool(true).as_decimal().is_none());
+    }
+
+    #[cfg(feature = "duration")]
+    #[test]
+    fn test_as_duration() {
+        let duration = |s: &str| Value::String(s.to_string()).as_duration();
+        assert_eq!(duration("PT1H30M"), Some(Duration::from_secs(5400)));
+        assert_eq!(
+            duration("P1DT2.5S"),
+            Some(Duration::from_millis(86_402_500))
+        );
+        assert_eq!(duration("P2W"), Some(Duration::from_secs(14 * 86_400)));
+        assert_eq!(duration("PT0,000000001S"), Some(Duration::from_nanos(1)));
+        assert_eq!(duration("P0D"), Some(Duration::ZERO));
+
+        for invalid in [
+            "P1Y", "P1M", "P", "PT", "PT30M1H", "PT1.5H", "PT1.S", "-PT1S", "1H",
+        ] {
+            assert_eq!(duration(invalid), None, "{invalid}");
+        }
+        assert!(Value::Number(Number::Integer(60)).as_duration().is_none());
+    }
+}
@@ -0,0 +1,412 @@
+// this_file: crates/core/src/ast/compare.rs
+
+//! Semantic comparison and diffing of values.
+//!
+//! Unlike `==`, these comparisons look at what a document means rather than
+//! how it was parsed: `1` equals `1.0`, floats may differ by a tolerance, and
+//! arrays can optionally be compared without regard to order. Object keys are
+//! never order-sensitive.
+//!
+//! NaN is deliberately treated as equal to NaN (and to nothing else) so that
+//! a document containing NaN still compares equal to itself.
+
+use crate::ast::{JsonPath, Number, PathSegment, Value};
+
+/// Settings for [`Value::equals_with`] and [`Value::diff_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CompareOptions {
+    /// Largest absolute difference at which two numbers are still equal.
+    pub epsilon: f64,
+    /// Compare arrays as multisets, ignoring the order of their elements.
+    pub unordered_arrays: bool,
+}
+
+impl CompareOptions {
+    /// Options comparing numbers within `epsilon` of each other as equal.
+    pub fn approx(epsilon: f64) -> Self {
+        Self {
+            epsilon,
+            ..Default::default()
+        }
+    }
+}
+
+/// A place where two values differ.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    /// Location of the difference, from the root of both values
+    pub path: JsonPath,
+    /// What differs there
+    pub kind: DiffKind,
+}
+
+/// How two values differ at a [`DiffEntry::path`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffKind {
+    /// Present only in the right-hand value.
+    Added(Value),
+    /// Present only in the left-hand value.
+    Removed(Value),
+    /// Present in both with different values.
+    Changed {
+        /// The left-hand value
+        left: Value,
+        /// The right-hand value
+        right: Value,
+    },
+}
+
+impl Value {
+    /// Compares values by meaning: numbers by numeric value, so that
+    /// `Integer(1)` equals `Float(1.0)`, and objects regardless of key order.
+    pub fn equals_semantic(&self, other: &Value) -> bool {
+        self.equals_with(other, &CompareOptions::default())
+    }
+
+    /// Like [`equals_semantic`](Self::equals_semantic), with numbers equal
+    /// when they differ by at most `epsilon`.
+    pub fn approx_eq(&self, other: &Value, epsilon: f64) -> bool {
+        self.equals_with(other, &CompareOptions::approx(epsilon))
+    }
+
+    /// Compares values semantically using `options`.
+    pub fn equals_with(&self, other: &Value, options: &CompareOptions) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => numbers_equal(a, b, options.epsilon),
+            (Value::Array(a), Value::Array(b)) if options.unordered_arrays => {
+                a.len() == b.len() && match_unordered(a, b, options).1.is_empty()
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.equals_with(b, options))
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(key, a)| b.get(key).is_some_and(|b| a.equals_with(b, options)))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Lists where `self` and `other` differ under semantic equality.
+    pub fn diff(&self, other: &Value) -> Vec<DiffEntry> {
+        self.diff_with(other, &CompareOptions::default())
+    }
+
+    /// Lists where `self` and `other` differ when compared using `options`.
+    ///
+    /// Object members are reported in key order. Unordered arrays report
+    /// unmatched elements at their own index on each side.
+    pub fn diff_with(&self, other: &Value, options: &CompareOptions) -> Vec<DiffEntry> {
+        let mut differ = Differ {
+            options,
+            path: JsonPath::new(),
+            entries: Vec::new(),
+        };
+        differ.diff(self, other);
+        differ.entries
+    }
+}
+
+/// Compares two numbers, exactly when both are whole.
+fn numbers_equal(a: &Number, b: &Number, epsilon: f64) -> bool {
+    if let (Some(a), Some(b)) = (whole(a), whole(b)) {
+        // Subtract exactly: large integers collapse together as f64
+        let difference = (a - b).unsigned_abs();
+        return difference == 0 || difference as f64 <= epsilon;
+    }
+    let (a, b) = (a.as_f64(), b.as_f64());
+    if a.is_nan() || b.is_nan() {
+        return a.is_nan() && b.is_nan();
+    }
+    a == b || (a - b).abs() <= epsilon
+}
+
+/// The exact value of a whole number, including floats past the range of
+/// an `i64` such as 2^63, which `as_i64` rejects.
+fn whole(n: &Number) -> Option<i128> {
+    match n {
+        Number::Integer(i) => Some(i128::from(*i)),
+        // Below 2^126, so that subtracting two of them cannot overflow
+        Number::Float(f) if f.fract() == 0.0 && f.abs() < 2f64.powi(126) => Some(*f as i128),
+        Number::Float(_) => None,
+    }
+}
+
+/// Pairs each element of `left` with an equal, unused element of `right`,
+/// returning the indices left unmatched on each side.
+fn match_unordered(
+    left: &[Value],
+    right: &[Value],
+    options: &CompareOptions,
+) -> (Vec<usize>, Vec<usize>) {
+    let mut owner = vec![None; right.len()];
+    let mut unmatched_left = Vec::new();
+    if options.epsilon == 0.0 {
+        // Exact equality is transitive, so any equal partner will do
+        for (i, item) in left.iter().enumerate() {
+            let found = (0..right.len())
+                .find(|&j| owner[j].is_none() && item.equals_with(&right[j], options));
+            match found {
+                Some(j) => owner[j] = Some(i),
+                None => unmatched_left.push(i),
+            }
+        }
+    } else {
+        // Within a tolerance it is not: with 0.5, `1.0` may take `0.6`, the
+        // only partner of `0.5`, when `1.4` would have done. Augmenting
+        // paths move such pairings along to find a maximum matching.
+        let partners: Vec<Vec<usize>> = left
+            .iter()
+            .map(|item| {
+                (0..right.len())
+                    .filter(|&j| item.equals_with(&right[j], options))
+                    .collect()
+            })
+            .collect();
+        for i in 0..left.len() {
+            if !augment(i, &partners, &mut owner) {
+                unmatched_left.push(i);
+            }
+        }
+    }
+    let unmatched_right = (0..right.len()).filter(|&j| owner[j].is_none()).collect();
+    (unmatched_left, unmatched_right)
+}
+
+/// Finds a partner for left element `start` among `partners`, taking one
+/// from another left element if that element can move to another partner
+/// in turn. On success, updates `owner`, the left element paired with each
+/// right element.
+fn augment(start: usize, partners: &[Vec<usize>], owner: &mut [Option<usize>]) -> bool {
+    let mut seen = vec![false; owner.len()];
+    // Left elements along the path, each with the next partner to try, and
+    // the right element that led from each to the next
+    let mut stack = vec![(start, 0)];
+    let mut path = Vec::new();
+    while let Some(top) = stack.last_mut() {
+        let (i, next) = *top;
+        top.1 += 1;
+        let Some(&j) = partners[i].get(next) else {
+            stack.pop();
+            path.pop();
+            continue;
+        };
+        if std::mem::replace(&mut seen[j], true) {
+            continue;
+        }
+        path.push(j);
+        match owner[j] {
+            Some(k) => stack.push((k, 0)),
+            None => {
+                for (&(i, _), &j) in stack.iter().zip(&path) {
+                    owner[j] = Some(i);
+                }
+                return true;
+            }
+        }
+    }
+    false
+}
+
+struct Differ<'o> {
+    options: &'o CompareOptions,
+    path: JsonPath,
+    entries: Vec<DiffEntry>,
+}
+
+impl Differ<'_> {
+    fn push(&mut self, kind: DiffKind) {
+        self.entries.push(DiffEntry {
+            path: self.path.clone(),
+            kind,
+        });
+    }
+
+    fn at(&mut self, segment: PathSegment, kind: DiffKind) {
+        self.path.push(segment);
+        self.push(kind);
+        self.path.pop();
+    }
+
+    fn diff(&mut self, left: &Value, right: &Value) {
+        match (left, right) {
+            (Value::Array(a), Value::Array(b)) if self.options.unordered_arrays => {
+                let (removed, added) = match_unordered(a, b, self.options);
+                for i in removed {
+                    self.at(PathSegment::Index(i), DiffKind::Removed(a[i].clone()));
+                }
+                for j in added {
+                    self.at(PathSegment::Index(j), DiffKind::Added(b[j].clone()));
+                }
+            }
+            (Value::Array(a), Value::Array(b)) => {
+                for (i, (a, b)) in a.iter().zip(b).enumerate() {
+                    self.path.push(PathSegment::Index(i));
+                    self.diff(a, b);
+                    self.path.pop();
+                }
+                for (i, item) in a.iter().enumerate().skip(b.len()) {
+                    self.at(PathSegment::Index(i), DiffKind::Removed(item.clone()));
+                }
+                for (i, item) in b.iter().enumerate().skip(a.len()) {
+                    self.at(PathSegment::Index(i), DiffKind::Added(item.clone()));
+                }
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+                keys.sort_unstable();
+                keys.dedup();
+                for key in keys {
+                    let segment = PathSegment::Key(key.clone());
+                    match (a.get(key), b.get(key)) {
+                        (Some(a), Some(b)) => {
+                            self.path.push(segment);
+                            self.diff(a, b);
+                            self.path.pop();
+                        }
+                        (Some(a), None) => self.at(segment, DiffKind::Removed(a.clone())),
+                        (None, Some(b)) => self.at(segment, DiffKind::Added(b.clone())),
+                        (None, None) => unreachable!("key comes from one of the objects"),
+                    }
+                }
+            }
+            _ if left.equals_with(right, self.options) => {}
+            _ => self.push(DiffKind::Changed {
+                left: left.clone(),
+                right: right.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_equals_semantic_numbers_and_key_order() {
+        let a = parse(r#"{"x": 1, "y": [2, {"z": 3}]}"#).unwrap();
+        let b = parse(r#"{"y": [2.0, {"z": 3.0}], "x": 1.0}"#).unwrap();
+        assert_ne!(a, b);
+        assert!(a.equals_semantic(&b));
+        assert!(!a.equals_semantic(&parse(r#"{"x": 1, "y": [{"z": 3}, 2]}"#).unwrap()));
+        assert!(!Value::Number(Number::Integer(1)).equals_semantic(&Value::Bool(true)));
+
+        // Whole numbers compare exactly, even beyond f64 precision
+        let big = Value::Number(Number::Integer(1 << 53));
+        let next = Value::Number(Number::Integer((1 << 53) + 1));
+        assert!(!big.equals_semantic(&next));
+
+        // 2^63 as a float is one past the largest i64, not equal to it
+        let max = Value::Number(Number::Integer(i64::MAX));
+        let past_max = Value::Number(Number::Float(i64::MAX as f64));
+        assert!(!max.equals_semantic(&past_max));
+        assert!(max.approx_eq(&past_max, 1.0));
+        assert!(past_max.equals_semantic(&Value::Number(Number::Float(2f64.powi(63)))));
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let a = parse("[0.1, 0.2, 0.30000000000000004]").unwrap();
+        let b = parse("[0.1, 0.2, 0.3]").unwrap();
+        assert!(!a.equals_semantic(&b));
+        assert!(a.approx_eq(&b, 1e-9));
+        assert!(!a.approx_eq(&parse("[0.1, 0.2, 0.31]").unwrap(), 1e-9));
+    }
+
+    #[test]
+    fn test_nan_equals_only_nan() {
+        let nan = Value::Number(Number::Float(f64::NAN));
+        assert!(nan.equals_semantic(&nan));
+        assert!(nan.approx_eq(&nan.clone(), 1e-9));
+        assert!(!nan.approx_eq(&Value::Number(Number::Float(0.0)), f64::INFINITY));
+
+        let inf = Value::Number(Number::Float(f64::INFINITY));
+        assert!(inf.approx_eq(&inf, 1e-9));
+        assert!(!inf.approx_eq(&Value::Number(Number::Float(f64::NEG_INFINITY)), 1e-9));
+    }
+
+    #[test]
+    fn test_unordered_arrays() {
+        let a = parse("[1, 2, 2, 3]").unwrap();
+        let b = parse("[2, 3, 1, 2]").unwrap();
+        let options = CompareOptions {
+            unordered_arrays: true,
+            ..Default::default()
+        };
+        assert!(!a.equals_semantic(&b));
+        assert!(a.equals_with(&b, &options));
+        assert!(!a.equals_with(&parse("[1, 2, 3, 3]").unwrap(), &options));
+
+        let diff = a.diff_with(&parse("[3, 2, 4, 1]").unwrap(), &options);
+        let paths: Vec<_> = diff.iter().map(|entry| entry.path.to_string()).collect();
+        assert_eq!(paths, ["$[2]", "$[2]"]);
+        assert_eq!(
+            diff[0].kind,
+            DiffKind::Removed(Value::Number(Number::Integer(2)))
+        );
+        assert_eq!(
+            diff[1].kind,
+            DiffKind::Added(Value::Number(Number::Integer(4)))
+        );
+    }
+
+    #[test]
+    fn test_unordered_arrays_within_epsilon() {
+        let options = CompareOptions {
+            epsilon: 0.5,
+            unordered_arrays: true,
+        };
+        let a = parse("[1.0, 0.5]").unwrap();
+        let b = parse("[0.6, 1.4]").unwrap();
+        assert!(a.equals_with(&b, &options));
+        assert!(b.equals_with(&a, &options));
+        assert!(a.diff_with(&b, &options).is_empty());
+
+        // Only one of 1.0 and 0.9 can pair with 0.6
+        let diff = parse("[1.0, 0.9, 2.0]")
+            .unwrap()
+            .diff_with(&parse("[0.6, 2.4, 9.0]").unwrap(), &options);
+        let paths: Vec<_> = diff.iter().map(|entry| entry.path.to_string()).collect();
+        assert_eq!(paths, ["$[1]", "$[2]"]);
+    }
+
+    #[test]
+    fn test_diff_paths() {
+        let a = parse(r#"{"a": 1, "b": [1, 2, 3], "c": {"d": "x"}, "e": null}"#).unwrap();
+        let b = parse(r#"{"a": 1.0, "b": [1, 5], "c": {"d": "y"}, "f": true}"#).unwrap();
+        let diff: Vec<_> = a
+            .diff(&b)
+            .into_iter()
+            .map(|entry| (entry.path.to_string(), entry.kind))
+            .collect();
+        assert_eq!(
+            diff,
+            [
+                (
+                    "$.b[1]".to_string(),
+                    DiffKind::Changed {
+                        left: Value::Number(Number::Integer(2)),
+                        right: Value::Number(Number::Integer(5)),
+                    }
+                ),
+                (
+                    "$.b[2]".to_string(),
+                    DiffKind::Removed(Value::Number(Number::Integer(3)))
+                ),
+                (
+                    "$.c.d".to_string(),
+                    DiffKind::Changed {
+                        left: Value::String("x".to_string()),
+                        right: Value::String("y".to_string()),
+                    }
+                ),
+                ("$.e".to_string(), DiffKind::Removed(Value::Null)),
+                ("$.f".to_string(), DiffKind::Added(Value::Bool(true))),
+            ]
+        );
+        assert!(a.diff(&a).is_empty());
+    }
+}
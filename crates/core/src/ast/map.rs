@@ -0,0 +1,188 @@
+// this_file: crates/core/src/ast/map.rs
+
+//! The maps that can hold the members of an object.
+//!
+//! [`ValueG`](super::ValueG) takes one of the kinds of map here as its type
+//! parameter, and stores every object in that kind of map:
+//!
+//! - [`Hashed`]: `FxHashMap`, the fastest, in no particular order. This is
+//!   what [`Value`](super::Value) uses.
+//! - [`Sorted`]: `BTreeMap`, always in key order.
+//! - [`Ordered`]: `IndexMap`, in the order members were inserted, which for
+//!   a parsed document is the order they were written in. Needs the
+//!   `indexmap` cargo feature.
+
+use rustc_hash::FxHashMap;
+use std::collections::{btree_map, hash_map, BTreeMap};
+
+/// The order in which a kind of map iterates its members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapOrder {
+    /// No particular order, which may differ from run to run
+    Arbitrary,
+    /// Sorted by key
+    Sorted,
+    /// The order in which members were inserted
+    Insertion,
+}
+
+/// A kind of map for the members of an object, from string keys to values
+/// of type `V`.
+///
+/// The maps in this module cover most needs; implement this for a marker
+/// type to store objects in a map of your own.
+pub trait MapLike {
+    /// The map itself
+    type Map<V>: Default
+        + IntoIterator<Item = (String, V)>
+        + FromIterator<(String, V)>
+        + Extend<(String, V)>;
+
+    /// Iterator over the members of a map, by reference
+    type Iter<'m, V: 'm>: Iterator<Item = (&'m String, &'m V)>;
+
+    /// The order in which the map iterates its members
+    const ORDER: MapOrder;
+
+    /// Returns the value of `key`.
+    fn get<'m, V>(map: &'m Self::Map<V>, key: &str) -> Option<&'m V>;
+
+    /// Sets the value of `key`, returning the value it replaces. A replaced
+    /// key keeps its place in the order.
+    fn insert<V>(map: &mut Self::Map<V>, key: String, value: V) -> Option<V>;
+
+    /// Removes `key`, returning its value.
+    fn remove<V>(map: &mut Self::Map<V>, key: &str) -> Option<V>;
+
+    /// Returns the number of members.
+    fn len<V>(map: &Self::Map<V>) -> usize;
+
+    /// Iterates over the members in the map's [`ORDER`](Self::ORDER).
+    fn iter<V>(map: &Self::Map<V>) -> Self::Iter<'_, V>;
+
+    /// Returns a copy of `map`.
+    fn clone_map<V: Clone>(map: &Self::Map<V>) -> Self::Map<V>;
+
+    /// Whether two maps have the same members, in any order.
+    fn eq_map<V: PartialEq>(a: &Self::Map<V>, b: &Self::Map<V>) -> bool;
+}
+
+/// Objects in an `FxHashMap`, in no particular order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Hashed;
+
+impl MapLike for Hashed {
+    type Map<V> = FxHashMap<String, V>;
+    type Iter<'m, V: 'm> = hash_map::Iter<'m, String, V>;
+
+    const ORDER: MapOrder = MapOrder::Arbitrary;
+
+    fn get<'m, V>(map: &'m Self::Map<V>, key: &str) -> Option<&'m V> {
+        map.get(key)
+    }
+
+    fn insert<V>(map: &mut Self::Map<V>, key: String, value: V) -> Option<V> {
+        map.insert(key, value)
+    }
+
+    fn remove<V>(map: &mut Self::Map<V>, key: &str) -> Option<V> {
+        map.remove(key)
+    }
+
+    fn len<V>(map: &Self::Map<V>) -> usize {
+        map.len()
+    }
+
+    fn iter<V>(map: &Self::Map<V>) -> Self::Iter<'_, V> {
+        map.iter()
+    }
+
+    fn clone_map<V: Clone>(map: &Self::Map<V>) -> Self::Map<V> {
+        map.clone()
+    }
+
+    fn eq_map<V: PartialEq>(a: &Self::Map<V>, b: &Self::Map<V>) -> bool {
+        a == b
+    }
+}
+
+/// Objects in a `BTreeMap`, sorted by key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sorted;
+
+impl MapLike for Sorted {
+    type Map<V> = BTreeMap<String, V>;
+    type Iter<'m, V: 'm> = btree_map::Iter<'m, String, V>;
+
+    const ORDER: MapOrder = MapOrder::Sorted;
+
+    fn get<'m, V>(map: &'m Self::Map<V>, key: &str) -> Option<&'m V> {
+        map.get(key)
+    }
+
+    fn insert<V>(map: &mut Self::Map<V>, key: String, value: V) -> Option<V> {
+        map.insert(key, value)
+    }
+
+    fn remove<V>(map: &mut Self::Map<V>, key: &str) -> Option<V> {
+        map.remove(key)
+    }
+
+    fn len<V>(map: &Self::Map<V>) -> usize {
+        map.len()
+    }
+
+    fn iter<V>(map: &Self::Map<V>) -> Self::Iter<'_, V> {
+        map.iter()
+    }
+
+    fn clone_map<V: Clone>(map: &Self::Map<V>) -> Self::Map<V> {
+        map.clone()
+    }
+
+    fn eq_map<V: PartialEq>(a: &Self::Map<V>, b: &Self::Map<V>) -> bool {
+        a == b
+    }
+}
+
+/// Objects in an `IndexMap`, in insertion order.
+#[cfg(feature = "indexmap")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ordered;
+
+#[cfg(feature = "indexmap")]
+impl MapLike for Ordered {
+    type Map<V> = indexmap::IndexMap<String, V>;
+    type Iter<'m, V: 'm> = indexmap::map::Iter<'m, String, V>;
+
+    const ORDER: MapOrder = MapOrder::Insertion;
+
+    fn get<'m, V>(map: &'m Self::Map<V>, key: &str) -> Option<&'m V> {
+        map.get(key)
+    }
+
+    fn insert<V>(map: &mut Self::Map<V>, key: String, value: V) -> Option<V> {
+        map.insert(key, value)
+    }
+
+    /// Shifts the members after `key` down, so the rest keep their order.
+    fn remove<V>(map: &mut Self::Map<V>, key: &str) -> Option<V> {
+        map.shift_remove(key)
+    }
+
+    fn len<V>(map: &Self::Map<V>) -> usize {
+        map.len()
+    }
+
+    fn iter<V>(map: &Self::Map<V>) -> Self::Iter<'_, V> {
+        map.iter()
+    }
+
+    fn clone_map<V: Clone>(map: &Self::Map<V>) -> Self::Map<V> {
+        map.clone()
+    }
+
+    fn eq_map<V: PartialEq>(a: &Self::Map<V>, b: &Self::Map<V>) -> bool {
+        a == b
+    }
+}
@@ -5,18 +5,35 @@
 //! This module contains the core AST types used throughout the vexy_json parser:
 //! - `Token`: Lexical tokens produced by the lexer
 //! - `Value`: Parsed JSON values with support for all vexy_json extensions
+//! - `ValueG`: The same values with objects in another kind of map, such as
+//!   `SortedValue` and `OrderedValue`
 //! - `Number`: Numeric value representation supporting integers and floats
 //!
 //! These types form the foundation of the parsing pipeline, from lexical analysis
 //! through to final value construction.
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
 pub mod builder;
+mod coerce;
+pub mod compare;
+pub mod map;
+#[cfg(feature = "serde")]
+mod serde;
 pub mod token;
 pub mod value;
 pub mod visitor;
 
 // Re-export all public types for convenient access
 pub use builder::{ArrayBuilder, ObjectBuilder, ValueBuilder};
+pub use compare::{CompareOptions, DiffEntry, DiffKind};
+#[cfg(feature = "indexmap")]
+pub use map::Ordered;
+pub use map::{Hashed, MapLike, MapOrder, Sorted};
 pub use token::Token;
-pub use value::{Number, Value};
-pub use visitor::{walk, walk_mut, walk_with_path, JsonPath, MutVisitor, PathVisitor, Visitor};
+#[cfg(feature = "indexmap")]
+pub use value::OrderedValue;
+pub use value::{Number, SortedValue, Value, ValueG};
+pub use visitor::{
+    walk, walk_mut, walk_with_path, JsonPath, MutVisitor, PathSegment, PathVisitor, Visitor,
+};
@@ -0,0 +1,162 @@
+// this_file: crates/core/src/ast/serde.rs
+
+//! Serializing and deserializing values with serde, behind the `serde` cargo
+//! feature.
+//!
+//! A [`Value`] maps onto serde's data model as the JSON it stands for: null
+//! as a unit, arrays as sequences and objects as maps with string keys, so
+//! that a `Value` can sit inside any serde struct and travel through any
+//! self-describing format. Members of `Value`'s objects are written in
+//! sorted key order, so the output is the same on every run; the other kinds
+//! of [`ValueG`] write them in their own order.
+//!
+//! Deserializing relies on the format describing its own data, as JSON and
+//! CBOR do. Formats such as bincode that need to be told what type comes
+//! next cannot hold a `Value`, and fail with their own error for
+//! `deserialize_any`.
+
+use crate::ast::{Hashed, MapLike, MapOrder, Number, Value, ValueG};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+impl<M: MapLike> Serialize for ValueG<M> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ValueG::Null => serializer.serialize_unit(),
+            ValueG::Bool(b) => serializer.serialize_bool(*b),
+            ValueG::Number(n) => n.serialize(serializer),
+            ValueG::String(s) => serializer.serialize_str(s),
+            ValueG::Array(arr) => serializer.collect_seq(arr),
+            ValueG::Object(obj) => {
+                let mut entries: Vec<_> = M::iter(obj).collect();
+                if M::ORDER == MapOrder::Arbitrary {
+                    entries.sort_unstable_by_key(|(key, _)| *key);
+                }
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl Serialize for Number {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Number::Integer(i) => serializer.serialize_i64(*i),
+            Number::Float(f) => serializer.serialize_f64(*f),
+        }
+    }
+}
+
+impl<'de, M: MapLike> Deserialize<'de> for ValueG<M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor(PhantomData))
+    }
+}
+
+impl<'de> Deserialize<'de> for Number {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.deserialize_any(ValueVisitor::<Hashed>(PhantomData))? {
+            Value::Number(n) => Ok(n),
+            other => Err(de::Error::invalid_type(unexpected(&other), &"a number")),
+        }
+    }
+}
+
+/// Builds a [`ValueG`] from whatever the format holds.
+struct ValueVisitor<M>(PhantomData<M>);
+
+impl<'de, M: MapLike> Visitor<'de> for ValueVisitor<M> {
+    type Value = ValueG<M>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<ValueG<M>, E> {
+        Ok(ValueG::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<ValueG<M>, E> {
+        Ok(ValueG::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<ValueG<M>, D::Error> {
+        ValueG::deserialize(deserializer)
+    }
+
+    fn visit_bool<E>(self, b: bool) -> Result<ValueG<M>, E> {
+        Ok(ValueG::Bool(b))
+    }
+
+    fn visit_i64<E>(self, i: i64) -> Result<ValueG<M>, E> {
+        Ok(ValueG::Number(Number::Integer(i)))
+    }
+
+    /// Integers beyond `i64::MAX` become floats, as the parser reads them.
+    fn visit_u64<E>(self, u: u64) -> Result<ValueG<M>, E> {
+        Ok(ValueG::Number(
+            i64::try_from(u).map_or(Number::Float(u as f64), Number::Integer),
+        ))
+    }
+
+    fn visit_i128<E>(self, i: i128) -> Result<ValueG<M>, E> {
+        Ok(ValueG::Number(
+            i64::try_from(i).map_or(Number::Float(i as f64), Number::Integer),
+        ))
+    }
+
+    fn visit_u128<E>(self, u: u128) -> Result<ValueG<M>, E> {
+        Ok(ValueG::Number(
+            i64::try_from(u).map_or(Number::Float(u as f64), Number::Integer),
+        ))
+    }
+
+    fn visit_f64<E>(self, f: f64) -> Result<ValueG<M>, E> {
+        Ok(ValueG::Number(Number::Float(f)))
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<ValueG<M>, E> {
+        Ok(ValueG::String(s.to_string()))
+    }
+
+    fn visit_string<E>(self, s: String) -> Result<ValueG<M>, E> {
+        Ok(ValueG::String(s))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ValueG<M>, A::Error> {
+        let mut arr = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(item) = seq.next_element()? {
+            arr.push(item);
+        }
+        Ok(ValueG::Array(arr))
+    }
+
+    /// Of members with the same key, the last wins, as in the parser's
+    /// default.
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ValueG<M>, A::Error> {
+        let mut obj = M::Map::default();
+        while let Some((key, value)) = map.next_entry::<String, ValueG<M>>()? {
+            M::insert(&mut obj, key, value);
+        }
+        Ok(ValueG::Object(obj))
+    }
+}
+
+/// Describes `value` for an invalid type error.
+fn unexpected(value: &Value) -> de::Unexpected<'_> {
+    match value {
+        Value::Null => de::Unexpected::Unit,
+        Value::Bool(b) => de::Unexpected::Bool(*b),
+        Value::Number(Number::Integer(i)) => de::Unexpected::Signed(*i),
+        Value::Number(Number::Float(f)) => de::Unexpected::Float(*f),
+        Value::String(s) => de::Unexpected::Str(s),
+        Value::Array(_) => de::Unexpected::Seq,
+        Value::Object(_) => de::Unexpected::Map,
+    }
+}
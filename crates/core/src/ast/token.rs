@@ -70,7 +70,7 @@ pub enum Token {
     SingleLineComment,
 
     /// Multi-line comment enclosed in '/* */'.
-    #[regex(r"/\*([^*]|\*[^/])*\*/")]
+    #[regex(r"/\*[^*]*\*+(?:[^/*][^*]*\*+)*/")]
     MultiLineComment,
 
     /// End of file/input. (Logos usually handles this implicitly)
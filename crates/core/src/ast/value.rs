@@ -6,21 +6,37 @@
 //! in the vexy_json AST. It supports all standard JSON types plus the extensions
 //! provided by vexy_json's forgiving syntax.
 
+use crate::ast::map::{Hashed, MapLike, MapOrder, Sorted};
+use crate::error::Error;
+use crate::format::{write_value, FloatFormat, KeyOrder, KeyQuoting};
+#[cfg(test)]
 use rustc_hash::FxHashMap;
 use std::fmt;
 use std::ops::Index;
+use std::str::FromStr;
+
+#[cfg(feature = "indexmap")]
+use crate::ast::map::Ordered;
 
-/// Represents any valid JSON value.
+/// Represents any valid JSON value, with objects held in the kind of map
+/// `M`.
 ///
 /// This enum can hold all JSON data types: null, boolean, number, string,
 /// array, and object.
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-/// Represents any valid JSON value.
-pub enum Value {
+///
+/// Values are `Send` and `Sync`, so they can be parsed on one thread and
+/// used on another. With the `serde` feature, they serialize and
+/// deserialize as the JSON they stand for, so they can be fields of serde
+/// structs. Reading one back needs a self-describing format such as JSON or
+/// CBOR; formats such as bincode cannot say which kind of value comes next
+/// and fail with an error about `deserialize_any`.
+///
+/// Most code uses [`Value`], whose objects are `FxHashMap`s. [`SortedValue`]
+/// keeps object members sorted by key and, with the `indexmap` feature,
+/// [`OrderedValue`] keeps them in the order they were written.
+/// [`parse_into`](crate::parser::parse_into) parses into any of them and
+/// [`into_map`](Self::into_map) converts between them.
+pub enum ValueG<M: MapLike = Hashed> {
     /// JSON null value.
     Null,
     /// JSON boolean value (true or false).
@@ -30,17 +46,87 @@ pub enum Value {
     /// JSON string value.
     String(String),
     /// JSON array containing a sequence of values.
-    Array(Vec<Value>),
+    Array(Vec<ValueG<M>>),
     /// JSON object containing key-value pairs.
-    Object(FxHashMap<String, Value>),
+    Object(M::Map<ValueG<M>>),
+}
+
+/// A value whose objects are `FxHashMap`s, the fastest kind of map, holding
+/// members in no particular order.
+pub type Value = ValueG<Hashed>;
+
+/// A value whose objects are `BTreeMap`s, holding members sorted by key.
+pub type SortedValue = ValueG<Sorted>;
+
+/// A value whose objects are `IndexMap`s, holding members in the order they
+/// were inserted. Parsed with [`parse_into`](crate::parser::parse_into),
+/// that is the order they were written in.
+#[cfg(feature = "indexmap")]
+pub type OrderedValue = ValueG<Ordered>;
+
+impl<M: MapLike> Clone for ValueG<M> {
+    fn clone(&self) -> Self {
+        match self {
+            ValueG::Null => ValueG::Null,
+            ValueG::Bool(b) => ValueG::Bool(*b),
+            ValueG::Number(n) => ValueG::Number(n.clone()),
+            ValueG::String(s) => ValueG::String(s.clone()),
+            ValueG::Array(arr) => ValueG::Array(arr.clone()),
+            ValueG::Object(obj) => ValueG::Object(M::clone_map(obj)),
+        }
+    }
+}
+
+/// Objects are equal when they have the same members, in any order.
+impl<M: MapLike> PartialEq for ValueG<M> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ValueG::Null, ValueG::Null) => true,
+            (ValueG::Bool(a), ValueG::Bool(b)) => a == b,
+            (ValueG::Number(a), ValueG::Number(b)) => a == b,
+            (ValueG::String(a), ValueG::String(b)) => a == b,
+            (ValueG::Array(a), ValueG::Array(b)) => a == b,
+            (ValueG::Object(a), ValueG::Object(b)) => M::eq_map(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl<M: MapLike> fmt::Debug for ValueG<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueG::Null => f.write_str("Null"),
+            ValueG::Bool(b) => f.debug_tuple("Bool").field(b).finish(),
+            ValueG::Number(n) => f.debug_tuple("Number").field(n).finish(),
+            ValueG::String(s) => f.debug_tuple("String").field(s).finish(),
+            ValueG::Array(arr) => f.debug_tuple("Array").field(arr).finish(),
+            ValueG::Object(obj) => f
+                .debug_tuple("Object")
+                .field(&DebugMembers::<M>(obj))
+                .finish(),
+        }
+    }
+}
+
+/// Shows the members of an object as a map, in the map's own order.
+struct DebugMembers<'a, M: MapLike>(&'a M::Map<ValueG<M>>);
+
+impl<M: MapLike> fmt::Debug for DebugMembers<'_, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(M::iter(self.0)).finish()
+    }
 }
 
 /// Represents a JSON number, which can be either an integer or floating point.
 ///
 /// This allows for more efficient representation and operations when the number
 /// is a whole number that fits in an i64.
+///
+/// Arithmetic follows one rule: two integers produce an integer, promoted to
+/// a float if the exact result does not fit in an i64, and any float operand
+/// produces a float. Checked operations return `None` only when the float
+/// result is infinite or NaN.
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Number {
     /// An integer value that fits in an i64.
     Integer(i64),
@@ -50,6 +136,8 @@ pub enum Number {
 
 impl Number {
     /// Converts the number to an f64 value.
+    ///
+    /// Integers beyond 2^53 lose precision.
     #[inline(always)]
     pub fn as_f64(&self) -> f64 {
         match self {
@@ -57,50 +145,127 @@ impl Number {
             Number::Float(f) => *f,
         }
     }
+
+    /// Returns the number as an i64 if it is an integer, or a float with no
+    /// fractional part that fits in an i64.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Number::Integer(i) => Some(*i),
+            // `i64::MAX as f64` rounds up to 2^63, which is out of range
+            Number::Float(f)
+                if f.fract() == 0.0 && *f >= i64::MIN as f64 && *f < i64::MAX as f64 =>
+            {
+                Some(*f as i64)
+            }
+            Number::Float(_) => None,
+        }
+    }
+
+    /// Returns the number as a u64 if it is a non-negative integer, or a
+    /// float with no fractional part that fits in a u64.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Number::Integer(i) => u64::try_from(*i).ok(),
+            Number::Float(f) if f.fract() == 0.0 && *f >= 0.0 && *f < u64::MAX as f64 => {
+                Some(*f as u64)
+            }
+            Number::Float(_) => None,
+        }
+    }
+
+    /// Returns true if the number is stored as an integer.
+    ///
+    /// Floats are not integers even when whole: `2.0` parses as a float.
+    #[inline(always)]
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Number::Integer(_))
+    }
+
+    /// Adds two numbers, promoting to a float on integer overflow.
+    ///
+    /// Returns `None` if the result is not a finite number.
+    pub fn checked_add(&self, other: &Number) -> Option<Number> {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => Some(
+                a.checked_add(*b)
+                    .map_or_else(|| Number::Float(*a as f64 + *b as f64), Number::Integer),
+            ),
+            _ => Self::finite(self.as_f64() + other.as_f64()),
+        }
+    }
+
+    /// Multiplies two numbers, promoting to a float on integer overflow.
+    ///
+    /// Returns `None` if the result is not a finite number.
+    pub fn checked_mul(&self, other: &Number) -> Option<Number> {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => Some(
+                a.checked_mul(*b)
+                    .map_or_else(|| Number::Float(*a as f64 * *b as f64), Number::Integer),
+            ),
+            _ => Self::finite(self.as_f64() * other.as_f64()),
+        }
+    }
+
+    fn finite(f: f64) -> Option<Number> {
+        f.is_finite().then_some(Number::Float(f))
+    }
 }
 
-impl Value {
+impl From<i64> for Number {
+    fn from(i: i64) -> Self {
+        Number::Integer(i)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(f: f64) -> Self {
+        Number::Float(f)
+    }
+}
+
+impl<M: MapLike> ValueG<M> {
     /// Returns true if the value is null.
     #[inline(always)]
     pub fn is_null(&self) -> bool {
-        matches!(self, Value::Null)
+        matches!(self, ValueG::Null)
     }
 
     /// Returns true if the value is a boolean.
     #[inline(always)]
     pub fn is_bool(&self) -> bool {
-        matches!(self, Value::Bool(_))
+        matches!(self, ValueG::Bool(_))
     }
 
     /// Returns true if the value is a number.
     #[inline(always)]
     pub fn is_number(&self) -> bool {
-        matches!(self, Value::Number(_))
+        matches!(self, ValueG::Number(_))
     }
 
     /// Returns true if the value is a string.
     #[inline(always)]
     pub fn is_string(&self) -> bool {
-        matches!(self, Value::String(_))
+        matches!(self, ValueG::String(_))
     }
 
     /// Returns true if the value is an array.
     #[inline(always)]
     pub fn is_array(&self) -> bool {
-        matches!(self, Value::Array(_))
+        matches!(self, ValueG::Array(_))
     }
 
     /// Returns true if the value is an object.
     #[inline(always)]
     pub fn is_object(&self) -> bool {
-        matches!(self, Value::Object(_))
+        matches!(self, ValueG::Object(_))
     }
 
     /// If the value is a boolean, returns the associated bool.
     /// Returns None otherwise.
     pub fn as_bool(&self) -> Option<bool> {
         match self {
-            Value::Bool(b) => Some(*b),
+            ValueG::Bool(b) => Some(*b),
             _ => None,
         }
     }
@@ -108,25 +273,26 @@ impl Value {
     /// If the value is a number, tries to return it as an i64.
     /// Returns None if the value is not a number or cannot be represented as i64.
     pub fn as_i64(&self) -> Option<i64> {
-        match self {
-            Value::Number(Number::Integer(i)) => Some(*i),
-            Value::Number(Number::Float(f)) => {
-                if f.fract() == 0.0 && *f >= i64::MIN as f64 && *f <= i64::MAX as f64 {
-                    Some(*f as i64)
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        }
+        self.as_number().and_then(Number::as_i64)
+    }
+
+    /// If the value is a number, tries to return it as a u64.
+    /// Returns None if the value is not a number or cannot be represented as u64.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_number().and_then(Number::as_u64)
     }
 
     /// If the value is a number, returns it as an f64.
     /// Returns None if the value is not a number.
     pub fn as_f64(&self) -> Option<f64> {
+        self.as_number().map(Number::as_f64)
+    }
+
+    /// If the value is a number, returns the associated Number.
+    /// Returns None otherwise.
+    pub fn as_number(&self) -> Option<&Number> {
         match self {
-            Value::Number(Number::Integer(i)) => Some(*i as f64),
-            Value::Number(Number::Float(f)) => Some(*f),
+            ValueG::Number(n) => Some(n),
             _ => None,
         }
     }
@@ -135,88 +301,335 @@ impl Value {
     /// Returns None otherwise.
     pub fn as_str(&self) -> Option<&str> {
         match self {
-            Value::String(s) => Some(s),
+            ValueG::String(s) => Some(s),
             _ => None,
         }
     }
 
     /// If the value is an array, returns a reference to the associated vector.
     /// Returns None otherwise.
-    pub fn as_array(&self) -> Option<&Vec<Value>> {
+    pub fn as_array(&self) -> Option<&Vec<ValueG<M>>> {
         match self {
-            Value::Array(a) => Some(a),
+            ValueG::Array(a) => Some(a),
             _ => None,
         }
     }
 
     /// If the value is an object, returns a reference to the associated map.
     /// Returns None otherwise.
-    pub fn as_object(&self) -> Option<&FxHashMap<String, Value>> {
+    pub fn as_object(&self) -> Option<&M::Map<ValueG<M>>> {
         match self {
-            Value::Object(o) => Some(o),
+            ValueG::Object(o) => Some(o),
             _ => None,
         }
     }
-}
 
-impl fmt::Display for Value {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// If the value is an object, returns the value of `key`.
+    /// Returns None if the value is not an object or has no such key.
+    ///
+    /// Unlike indexing with `value["key"]`, which gives [`Value::Null`]
+    /// both for a missing key and for one whose value is `null`, this tells
+    /// them apart: `{"a": null}` gives `Some(&Value::Null)` and `{}` gives
+    /// None.
+    ///
+    /// ```
+    /// use vexy_json_core::ast::Value;
+    /// use vexy_json_core::parse;
+    ///
+    /// let value = parse(r#"{"a": null}"#).unwrap();
+    /// assert_eq!(value.get_entry("a"), Some(&Value::Null));
+    /// assert_eq!(value.get_entry("b"), None);
+    /// assert_eq!(value["a"], value["b"]);
+    /// ```
+    pub fn get_entry(&self, key: &str) -> Option<&ValueG<M>> {
+        M::get(self.as_object()?, key)
+    }
+
+    /// If the value is an object, looks up `key` ignoring ASCII case.
+    /// Returns None if the value is not an object or has no such key.
+    ///
+    /// An exact match is preferred. Otherwise this scans every key, so it
+    /// costs O(n) in the size of the object; when several keys differ from
+    /// `key` only in case, the lexicographically smallest one wins.
+    pub fn get_ignore_case(&self, key: &str) -> Option<&ValueG<M>> {
+        let object = self.as_object()?;
+        if let Some(value) = M::get(object, key) {
+            return Some(value);
+        }
+        M::iter(object)
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, value)| value)
+    }
+
+    /// Shorthand for [`get_ignore_case`](Self::get_ignore_case).
+    pub fn get_ci(&self, key: &str) -> Option<&ValueG<M>> {
+        self.get_ignore_case(key)
+    }
+
+    /// If the value is an object, returns the value of the first of
+    /// `aliases` it has as a key, matched exactly.
+    /// Returns None if the value is not an object or has none of them.
+    ///
+    /// Useful when a field has been renamed over time, as in
+    /// `value.get_by_aliases(&["user_id", "userId", "uid"])`.
+    pub fn get_by_aliases(&self, aliases: &[&str]) -> Option<&ValueG<M>> {
+        let object = self.as_object()?;
+        aliases.iter().find_map(|alias| M::get(object, alias))
+    }
+
+    /// Converts the value to one whose objects are held in the kind of map
+    /// `N`, keeping the members of each object in their order here. Members
+    /// of [`Value`]'s objects, which have no order, are taken sorted by key.
+    ///
+    /// ```
+    /// use vexy_json_core::ast::{SortedValue, Value};
+    /// use vexy_json_core::parse;
+    ///
+    /// let value = parse("{b: 1, a: {d: 2, c: 3}}").unwrap();
+    /// let sorted: SortedValue = value.clone().into_map();
+    /// assert_eq!(sorted.to_string(), value.to_string());
+    /// assert_eq!(sorted.into_map::<vexy_json_core::ast::Hashed>(), value);
+    /// ```
+    pub fn into_map<N: MapLike>(self) -> ValueG<N> {
         match self {
-            Value::Null => write!(f, "null"),
-            Value::Bool(b) => write!(f, "{b}"),
-            Value::Number(n) => write!(f, "{n}"),
-            Value::String(s) => write!(f, "\"{s}\""),
-            Value::Array(arr) => {
-                write!(f, "[")?;
-                for (i, v) in arr.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "{v}")?;
+            ValueG::Null => ValueG::Null,
+            ValueG::Bool(b) => ValueG::Bool(b),
+            ValueG::Number(n) => ValueG::Number(n),
+            ValueG::String(s) => ValueG::String(s),
+            ValueG::Array(arr) => ValueG::Array(arr.into_iter().map(ValueG::into_map).collect()),
+            ValueG::Object(obj) => {
+                let mut members: Vec<_> = obj.into_iter().collect();
+                if M::ORDER == MapOrder::Arbitrary {
+                    members.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
                 }
-                write!(f, "]")
+                ValueG::Object(
+                    members
+                        .into_iter()
+                        .map(|(key, value)| (key, value.into_map()))
+                        .collect(),
+                )
             }
-            Value::Object(obj) => {
-                write!(f, "{{")?;
-                for (i, (k, v)) in obj.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "\"{k}\": {v}")?;
-                }
-                write!(f, "}}")
+        }
+    }
+
+    /// Drops the value without recursing once per level of nesting.
+    ///
+    /// Dropping a value the usual way recurses into each array and object,
+    /// so one nested deeper than the thread's stack allows, as the
+    /// [iterative parser](crate::parser::iterative) can produce, should be
+    /// dropped with this instead.
+    ///
+    /// ```
+    /// use vexy_json_core::ast::Value;
+    ///
+    /// let mut value = Value::Null;
+    /// for _ in 0..1_000_000 {
+    ///     value = Value::Array(vec![value]);
+    /// }
+    /// value.drop_iteratively();
+    /// ```
+    pub fn drop_iteratively(self) {
+        let mut values = vec![self];
+        while let Some(value) = values.pop() {
+            match value {
+                ValueG::Array(arr) => values.extend(arr),
+                ValueG::Object(obj) => values.extend(obj.into_iter().map(|(_, value)| value)),
+                _ => {}
             }
         }
     }
 }
 
+/// Values display as compact JSON, written as
+/// [`to_compact_string`](crate::format::to_compact_string) writes them and,
+/// like it, without recursing once per level. Members of [`Value`]'s objects
+/// are written in sorted key order, so the text is the same from run to run
+/// and on every platform. Other maps write members in their own order.
+impl<M: MapLike> fmt::Display for ValueG<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        write_value(
+            &mut out,
+            self,
+            &KeyOrder::default(),
+            None,
+            FloatFormat::Shortest,
+            KeyQuoting::Always,
+            0,
+        );
+        f.write_str(&out)
+    }
+}
+
 impl fmt::Display for Number {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Number::Integer(i) => write!(f, "{i}"),
+            // Written as `-0` it would read back as the integer 0
+            Number::Float(fl) if *fl == 0.0 && fl.is_sign_negative() => f.write_str("-0.0"),
             Number::Float(fl) => write!(f, "{fl}"),
         }
     }
 }
 
-impl Index<&str> for Value {
-    type Output = Value;
+/// Indexing an object by key gives [`Value::Null`] when the key is missing,
+/// as does indexing any other value, so `value["a"]["b"]` never panics. Use
+/// [`Value::get_entry`] to tell a missing key from a `null` value.
+impl<M: MapLike> Index<&str> for ValueG<M> {
+    type Output = ValueG<M>;
 
     fn index(&self, index: &str) -> &Self::Output {
         match self {
-            Value::Object(map) => map.get(index).unwrap_or(&Value::Null),
-            _ => &Value::Null,
+            ValueG::Object(map) => M::get(map, index).unwrap_or(&ValueG::Null),
+            _ => &ValueG::Null,
         }
     }
 }
 
-impl Index<usize> for Value {
-    type Output = Value;
+impl<M: MapLike> Index<usize> for ValueG<M> {
+    type Output = ValueG<M>;
 
     fn index(&self, index: usize) -> &Self::Output {
         match self {
-            Value::Array(arr) => arr.get(index).unwrap_or(&Value::Null),
-            _ => &Value::Null,
+            ValueG::Array(arr) => arr.get(index).unwrap_or(&ValueG::Null),
+            _ => &ValueG::Null,
+        }
+    }
+}
+
+/// Parses a value with the default [`ParserOptions`](crate::ParserOptions),
+/// so the forgiving syntax accepted by [`parse`](crate::parse) is accepted
+/// here too.
+///
+/// ```
+/// use vexy_json_core::ast::{Number, Value};
+///
+/// let value: Value = "{a: [1, 2]}".parse().unwrap();
+/// assert_eq!(value["a"][1], Value::Number(Number::Integer(2)));
+/// assert!("[1, ".parse::<Value>().is_err());
+/// ```
+impl FromStr for Value {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::parse(s)
+    }
+}
+
+/// Parses a value like [`FromStr`].
+///
+/// ```
+/// use vexy_json_core::ast::Value;
+///
+/// let value = Value::try_from("'quoted'").unwrap();
+/// assert_eq!(value, Value::String("quoted".to_string()));
+/// ```
+impl TryFrom<&str> for Value {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Parses a value like [`Value`]'s [`FromStr`], keeping object members
+/// sorted by key.
+impl FromStr for SortedValue {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::parser::parse_into(s)
+    }
+}
+
+/// Parses a value like [`Value`]'s [`FromStr`], keeping object members in
+/// the order they are written.
+///
+/// ```
+/// use vexy_json_core::ast::OrderedValue;
+///
+/// let value: OrderedValue = "{b: 1, a: 2}".parse().unwrap();
+/// assert_eq!(value.to_string(), r#"{"b":1,"a":2}"#);
+/// ```
+#[cfg(feature = "indexmap")]
+impl FromStr for OrderedValue {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::parser::parse_into(s)
+    }
+}
+
+/// Collects values into an array.
+///
+/// ```
+/// use vexy_json_core::ast::{Number, Value};
+///
+/// let value: Value = (1..=3).map(|n| Value::Number(Number::Integer(n))).collect();
+/// assert_eq!(value, "[1, 2, 3]".parse().unwrap());
+/// ```
+impl<M: MapLike> FromIterator<ValueG<M>> for ValueG<M> {
+    fn from_iter<I: IntoIterator<Item = ValueG<M>>>(iter: I) -> Self {
+        ValueG::Array(iter.into_iter().collect())
+    }
+}
+
+/// Collects key-value pairs into an object. As when parsing, the last
+/// value for a repeated key wins.
+///
+/// ```
+/// use vexy_json_core::ast::Value;
+///
+/// let pairs = [("a", Value::Null), ("b", Value::Bool(true)), ("a", Value::Bool(false))];
+/// let value: Value = pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+/// assert_eq!(value, "{a: false, b: true}".parse().unwrap());
+/// ```
+impl<M: MapLike> FromIterator<(String, ValueG<M>)> for ValueG<M> {
+    fn from_iter<I: IntoIterator<Item = (String, ValueG<M>)>>(iter: I) -> Self {
+        ValueG::Object(iter.into_iter().collect())
+    }
+}
+
+/// Appends values to an array. A null value becomes an empty array first.
+///
+/// ```
+/// use vexy_json_core::ast::Value;
+///
+/// let mut value = Value::Null;
+/// value.extend(["x".parse::<Value>().unwrap(), Value::Null]);
+/// assert_eq!(value, r#"["x", null]"#.parse().unwrap());
+/// ```
+///
+/// # Panics
+///
+/// Panics if the value is neither an array nor null.
+impl<M: MapLike> Extend<ValueG<M>> for ValueG<M> {
+    fn extend<I: IntoIterator<Item = ValueG<M>>>(&mut self, iter: I) {
+        if self.is_null() {
+            *self = ValueG::Array(Vec::new());
+        }
+        match self {
+            ValueG::Array(array) => array.extend(iter),
+            _ => panic!("cannot extend a non-array value with values"),
+        }
+    }
+}
+
+/// Inserts key-value pairs into an object, replacing the value of any key
+/// already present. A null value becomes an empty object first.
+///
+/// # Panics
+///
+/// Panics if the value is neither an object nor null.
+impl<M: MapLike> Extend<(String, ValueG<M>)> for ValueG<M> {
+    fn extend<I: IntoIterator<Item = (String, ValueG<M>)>>(&mut self, iter: I) {
+        if self.is_null() {
+            *self = ValueG::Object(Default::default());
+        }
+        match self {
+            ValueG::Object(object) => object.extend(iter),
+            _ => panic!("cannot extend a non-object value with key-value pairs"),
         }
     }
 }
@@ -375,6 +788,99 @@ mod tests {
         assert_eq!(arr_val["any"], Value::Null);
     }
 
+    #[test]
+    fn test_value_get_entry() {
+        let mut map = FxHashMap::default();
+        map.insert("a".to_string(), Value::Null);
+        let present_null = Value::Object(map);
+        let empty = Value::Object(FxHashMap::default());
+
+        assert_eq!(present_null.get_entry("a"), Some(&Value::Null));
+        assert_eq!(empty.get_entry("a"), None);
+        // Indexing cannot tell the two apart
+        assert_eq!(present_null["a"], empty["a"]);
+        assert_eq!(Value::Null.get_entry("a"), None);
+        assert_eq!(Value::Array(vec![Value::Null]).get_entry("0"), None);
+    }
+
+    #[test]
+    fn test_value_get_ci() {
+        let mut map = FxHashMap::default();
+        map.insert("name".to_string(), Value::String("John".to_string()));
+        map.insert("Age".to_string(), Value::Number(Number::Integer(30)));
+        map.insert("AGE".to_string(), Value::Number(Number::Integer(31)));
+        let obj_val = Value::Object(map);
+
+        assert_eq!(
+            obj_val.get_ci("Name"),
+            Some(&Value::String("John".to_string()))
+        );
+        assert_eq!(
+            obj_val.get_ci("NAME"),
+            Some(&Value::String("John".to_string()))
+        );
+        // An exact match wins over other casings
+        assert_eq!(
+            obj_val.get_ci("Age"),
+            Some(&Value::Number(Number::Integer(30)))
+        );
+        assert_eq!(
+            obj_val.get_ci("AGE"),
+            Some(&Value::Number(Number::Integer(31)))
+        );
+        // Otherwise the smallest matching key is chosen
+        assert_eq!(
+            obj_val.get_ci("age"),
+            Some(&Value::Number(Number::Integer(31)))
+        );
+        assert_eq!(obj_val.get_ci("missing"), None);
+        assert_eq!(Value::Array(vec![]).get_ci("name"), None);
+    }
+
+    #[test]
+    fn test_value_get_by_aliases() {
+        let mut map = FxHashMap::default();
+        map.insert("uid".to_string(), Value::Number(Number::Integer(1)));
+        map.insert("userId".to_string(), Value::Number(Number::Integer(2)));
+        let obj_val = Value::Object(map);
+
+        // The first alias present wins, whatever the key order
+        assert_eq!(
+            obj_val.get_by_aliases(&["user_id", "userId", "uid"]),
+            Some(&Value::Number(Number::Integer(2)))
+        );
+        assert_eq!(
+            obj_val.get_by_aliases(&["uid", "userId"]),
+            Some(&Value::Number(Number::Integer(1)))
+        );
+        // Aliases are matched exactly
+        assert_eq!(obj_val.get_by_aliases(&["UID", "user_id"]), None);
+        assert_eq!(obj_val.get_by_aliases(&[]), None);
+        assert_eq!(Value::Null.get_by_aliases(&["uid"]), None);
+    }
+
+    #[test]
+    fn test_value_extend_object() {
+        let mut obj_val = Value::Null;
+        obj_val.extend([("a".to_string(), Value::Bool(true))]);
+        obj_val.extend([
+            ("a".to_string(), Value::Bool(false)),
+            ("b".to_string(), Value::Null),
+        ]);
+
+        let object = obj_val.as_object().unwrap();
+        assert_eq!(object.len(), 2);
+        assert_eq!(object["a"], Value::Bool(false));
+        assert_eq!(object["b"], Value::Null);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot extend a non-array value")]
+    fn test_value_extend_wrong_kind() {
+        let mut obj_val = Value::Object(FxHashMap::default());
+        obj_val.extend([Value::Null]);
+    }
+
     #[test]
     fn test_value_index_usize() {
         let arr_val = Value::Array(vec![
@@ -425,7 +931,7 @@ mod tests {
             Value::Number(Number::Integer(3)),
         ]);
 
-        assert_eq!(arr_val.to_string(), "[1, 2, 3]");
+        assert_eq!(arr_val.to_string(), "[1,2,3]");
 
         let empty_arr = Value::Array(vec![]);
         assert_eq!(empty_arr.to_string(), "[]");
@@ -437,7 +943,7 @@ mod tests {
         map.insert("name".to_string(), Value::String("John".to_string()));
         let obj_val = Value::Object(map);
 
-        assert_eq!(obj_val.to_string(), "{\"name\": \"John\"}");
+        assert_eq!(obj_val.to_string(), "{\"name\":\"John\"}");
 
         let empty_obj = Value::Object(FxHashMap::default());
         assert_eq!(empty_obj.to_string(), "{}");
@@ -489,9 +995,110 @@ mod tests {
         let too_large = Value::Number(Number::Float(1e20));
         let negative_zero = Value::Number(Number::Float(-0.0));
 
-        assert_eq!(max_float.as_i64(), Some(i64::MAX));
+        // i64::MAX rounds up to 2^63 as a float, one past the largest i64
+        assert_eq!(max_float.as_i64(), None);
         assert_eq!(min_float.as_i64(), Some(i64::MIN));
         assert_eq!(too_large.as_i64(), None);
         assert_eq!(negative_zero.as_i64(), Some(0));
     }
+
+    #[test]
+    fn test_number_conversions() {
+        let int = Number::Integer(-5);
+        assert_eq!(int.as_f64(), -5.0);
+        assert_eq!(int.as_i64(), Some(-5));
+        assert_eq!(int.as_u64(), None);
+        assert!(int.is_integer());
+
+        let whole = Number::Float(7.0);
+        assert_eq!(whole.as_i64(), Some(7));
+        assert_eq!(whole.as_u64(), Some(7));
+        assert!(!whole.is_integer());
+
+        let fraction = Number::Float(2.5);
+        assert_eq!(fraction.as_f64(), 2.5);
+        assert_eq!(fraction.as_i64(), None);
+        assert_eq!(fraction.as_u64(), None);
+
+        assert_eq!(Number::Integer(i64::MAX).as_u64(), Some(i64::MAX as u64));
+        assert_eq!(
+            Number::Float(1e19).as_u64(),
+            Some(10_000_000_000_000_000_000)
+        );
+        assert_eq!(Number::Float(1e20).as_u64(), None);
+        // The bounds round up to 2^63 and 2^64 as floats, just out of range
+        assert_eq!(Number::Float(i64::MAX as f64).as_i64(), None);
+        assert_eq!(Number::Float(i64::MIN as f64).as_i64(), Some(i64::MIN));
+        assert_eq!(
+            Number::Float(9223372036854774784.0).as_i64(),
+            Some(9223372036854774784)
+        );
+        assert_eq!(Number::Float(u64::MAX as f64).as_u64(), None);
+        assert_eq!(
+            Number::Float(18446744073709549568.0).as_u64(),
+            Some(18446744073709549568)
+        );
+        assert_eq!(Number::Float(f64::NAN).as_i64(), None);
+
+        let value = Value::Number(Number::Integer(3));
+        assert_eq!(value.as_number(), Some(&Number::Integer(3)));
+        assert_eq!(value.as_u64(), Some(3));
+        assert_eq!(Value::Null.as_number(), None);
+    }
+
+    #[test]
+    fn test_number_checked_arithmetic() {
+        let two = Number::Integer(2);
+        assert_eq!(
+            two.checked_add(&Number::Integer(3)),
+            Some(Number::Integer(5))
+        );
+        assert_eq!(
+            two.checked_mul(&Number::Integer(-4)),
+            Some(Number::Integer(-8))
+        );
+
+        // Integer overflow promotes to a float
+        let max = Number::Integer(i64::MAX);
+        assert_eq!(
+            max.checked_add(&Number::Integer(1)),
+            Some(Number::Float(i64::MAX as f64 + 1.0))
+        );
+        assert_eq!(
+            max.checked_mul(&two),
+            Some(Number::Float(i64::MAX as f64 * 2.0))
+        );
+        assert_eq!(
+            Number::Integer(i64::MIN).checked_add(&Number::Integer(-1)),
+            Some(Number::Float(i64::MIN as f64 - 1.0))
+        );
+
+        // Any float operand gives a float, even when the result is whole
+        assert_eq!(
+            two.checked_add(&Number::Float(0.5)),
+            Some(Number::Float(2.5))
+        );
+        assert_eq!(
+            Number::Float(1.5).checked_mul(&two),
+            Some(Number::Float(3.0))
+        );
+        assert_eq!(
+            Number::Float(0.25).checked_add(&Number::Float(0.5)),
+            Some(Number::Float(0.75))
+        );
+
+        // Non-finite results are rejected
+        assert_eq!(Number::Float(f64::MAX).checked_mul(&two), None);
+        assert_eq!(
+            Number::Float(f64::MAX).checked_add(&Number::Float(f64::MAX)),
+            None
+        );
+        assert_eq!(Number::Float(f64::NAN).checked_add(&two), None);
+    }
+
+    #[test]
+    fn test_number_from_primitives() {
+        assert_eq!(Number::from(4), Number::Integer(4));
+        assert_eq!(Number::from(0.5), Number::Float(0.5));
+    }
 }
@@ -4,9 +4,10 @@
 //! the JSON AST, allowing for analysis, transformation, and validation.
 
 use crate::ast::{Number, Value};
-use crate::error::Result;
+use crate::error::{Error, Result};
 use rustc_hash::FxHashMap;
 use std::fmt;
+use std::str::FromStr;
 
 /// Visitor trait for traversing JSON values
 pub trait Visitor {
@@ -50,9 +51,9 @@ pub trait Visitor {
         Ok(())
     }
 
-    /// Visit an object value
+    /// Visit an object value, its members in sorted key order
     fn visit_object(&mut self, object: &FxHashMap<String, Value>) -> Result<()> {
-        for value in object.values() {
+        for (_, value) in sorted_entries(object) {
             self.visit_value(value)?;
         }
         Ok(())
@@ -101,9 +102,11 @@ pub trait MutVisitor {
         Ok(())
     }
 
-    /// Visit an object value
+    /// Visit an object value, its members in sorted key order
     fn visit_object_mut(&mut self, object: &mut FxHashMap<String, Value>) -> Result<()> {
-        for value in object.values_mut() {
+        let mut entries: Vec<_> = object.iter_mut().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        for (_, value) in entries {
             self.visit_value_mut(value)?;
         }
         Ok(())
@@ -154,13 +157,13 @@ pub trait PathVisitor {
         Ok(())
     }
 
-    /// Visit an object value with path
+    /// Visit an object value with path, its members in sorted key order
     fn visit_object_with_path(
         &mut self,
         object: &FxHashMap<String, Value>,
         path: &JsonPath,
     ) -> Result<()> {
-        for (key, value) in object {
+        for (key, value) in sorted_entries(object) {
             let mut child_path = path.clone();
             child_path.push(PathSegment::Key(key.clone()));
             self.visit_value_with_path(value, &child_path)?;
@@ -169,6 +172,14 @@ pub trait PathVisitor {
     }
 }
 
+/// The members of `object` sorted by key, so that walks visit them in the
+/// same order on every run and platform.
+fn sorted_entries(object: &FxHashMap<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<_> = object.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+    entries
+}
+
 /// JSON path representation
 #[derive(Debug, Clone, PartialEq)]
 pub struct JsonPath {
@@ -206,14 +217,32 @@ impl JsonPath {
     pub fn pop(&mut self) -> Option<PathSegment> {
         self.segments.pop()
     }
+
+    /// Segments from the root down
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
 }
 
 impl fmt::Display for JsonPath {
+    /// Writes keys made of letters, digits, `_`, `-` and `$` as `.key` and
+    /// any other as `['key']`, escaping `'` and `\` with a backslash, so
+    /// that different paths never display the same.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "$")?;
         for segment in &self.segments {
             match segment {
-                PathSegment::Key(key) => write!(f, ".{key}")?,
+                PathSegment::Key(key) if is_plain_key(key) => write!(f, ".{key}")?,
+                PathSegment::Key(key) => {
+                    f.write_str("['")?;
+                    for c in key.chars() {
+                        if matches!(c, '\'' | '\\') {
+                            f.write_str("\\")?;
+                        }
+                        write!(f, "{c}")?;
+                    }
+                    f.write_str("']")?;
+                }
                 PathSegment::Index(idx) => write!(f, "[{idx}]")?,
             }
         }
@@ -221,12 +250,120 @@ impl fmt::Display for JsonPath {
     }
 }
 
+/// Whether `key` is written as `.key` in a displayed [`JsonPath`].
+fn is_plain_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '$'))
+}
+
 impl Default for JsonPath {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Parses the `$.items[0].name` form that [`JsonPath`] displays as.
+///
+/// A key written after `.` ends at the next `.` or `[`; any key can be
+/// written as `['key']` or `["key"]`, with a backslash escaping the
+/// character after it.
+impl FromStr for JsonPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        let mut parsed = JsonPath::new();
+        for selector in parse_path(path)? {
+            parsed.push(match selector {
+                PathSelector::Key(key) => PathSegment::Key(key),
+                PathSelector::Index(index) => PathSegment::Index(index),
+                PathSelector::AnyKey | PathSelector::AnyIndex | PathSelector::Descendants => {
+                    return Err(Error::Custom(format!("Invalid path: {path}")))
+                }
+            });
+        }
+        Ok(parsed)
+    }
+}
+
+/// One step of a path as written, which may be a wildcard.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PathSelector {
+    /// `.key`, `['key']` or `["key"]`
+    Key(String),
+    /// `[0]`
+    Index(usize),
+    /// `.*`
+    AnyKey,
+    /// `[*]`
+    AnyIndex,
+    /// `..`, after which the next selector applies at any depth below
+    Descendants,
+}
+
+/// Parses `path`, written as [`JsonPath`] displays it, allowing `.*` and
+/// `[*]` as wildcards and `..` before a selector for descendants, as in
+/// `$..token` or `$..[0]`.
+pub(crate) fn parse_path(path: &str) -> Result<Vec<PathSelector>> {
+    let invalid = || Error::Custom(format!("Invalid path: {path}"));
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut selectors = Vec::new();
+
+    while !rest.is_empty() {
+        let mut key_start = rest.strip_prefix('.');
+        if let Some(after) = rest.strip_prefix("..") {
+            selectors.push(PathSelector::Descendants);
+            rest = after;
+            key_start = Some(after);
+        }
+        if let Some(after) = rest.strip_prefix('[') {
+            let (selector, after) = parse_bracket(after).ok_or_else(invalid)?;
+            selectors.push(selector);
+            rest = after;
+        } else if let Some(after) = key_start {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            selectors.push(match &after[..end] {
+                "" => return Err(invalid()),
+                "*" => PathSelector::AnyKey,
+                key => PathSelector::Key(key.to_string()),
+            });
+            rest = &after[end..];
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    Ok(selectors)
+}
+
+/// Parses what follows a `[`, through its `]`, returning the rest of the
+/// path after it.
+fn parse_bracket(after: &str) -> Option<(PathSelector, &str)> {
+    let Some(quote) = after.chars().next().filter(|c| matches!(c, '\'' | '"')) else {
+        let end = after.find(']')?;
+        let selector = match &after[..end] {
+            "*" => PathSelector::AnyIndex,
+            index => PathSelector::Index(index.parse().ok()?),
+        };
+        return Some((selector, &after[end + 1..]));
+    };
+
+    let mut key = String::new();
+    let mut chars = after[1..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => key.push(chars.next()?.1),
+            c if c == quote => {
+                let rest = after[1 + i + 1..].strip_prefix(']')?;
+                return Some((PathSelector::Key(key), rest));
+            }
+            c => key.push(c),
+        }
+    }
+    None
+}
+
 /// Helper function to walk a value with a visitor
 pub fn walk<V: Visitor>(value: &Value, visitor: &mut V) -> Result<()> {
     visitor.visit_value(value)
@@ -420,6 +557,47 @@ mod tests {
         assert_eq!(results[0], Value::String("Alice".to_string()));
     }
 
+    #[test]
+    fn test_json_path_round_trip() {
+        let path: JsonPath = "$.items[3].name".parse().unwrap();
+        assert_eq!(
+            path.segments(),
+            [
+                PathSegment::Key("items".to_string()),
+                PathSegment::Index(3),
+                PathSegment::Key("name".to_string()),
+            ]
+        );
+        assert_eq!(path.to_string(), "$.items[3].name");
+        assert!("$".parse::<JsonPath>().unwrap().segments().is_empty());
+
+        for invalid in ["items", "$..a", "$[x]", "$[1", "$a", "$.*", "$[*]", "$['a]"] {
+            assert!(invalid.parse::<JsonPath>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_json_path_quoted_keys() {
+        for (keys, displayed) in [
+            (&["a.b"][..], "$['a.b']"),
+            (&["a", "b"], "$.a.b"),
+            (&["a b", "x-y_z$1"], "$['a b'].x-y_z$1"),
+            (&["", "*"], "$['']['*']"),
+            (&["a]b", "it's", "\\"], "$['a]b']['it\\'s']['\\\\']"),
+        ] {
+            let mut path = JsonPath::new();
+            for key in keys {
+                path.push(PathSegment::Key(key.to_string()));
+            }
+            assert_eq!(path.to_string(), displayed);
+            assert_eq!(displayed.parse::<JsonPath>().unwrap(), path);
+        }
+        assert_eq!(
+            r#"$["a.b"]"#.parse::<JsonPath>().unwrap().to_string(),
+            "$['a.b']"
+        );
+    }
+
     #[test]
     fn test_mut_visitor() {
         let mut value = Value::Array(vec![
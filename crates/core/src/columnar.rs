@@ -0,0 +1,351 @@
+// this_file: crates/core/src/columnar.rs
+
+//! Experimental columnar representation of arrays of records.
+//!
+//! Analytics workloads often scan one field across many similar objects,
+//! which a tree of [`Value`]s scatters across the heap. [`parse_records`]
+//! instead transposes a top-level array of objects into one typed vector per
+//! field, filling in nulls where a record lacks a field.
+
+use crate::ast::{Number, Value};
+use crate::error::{Error, Result};
+use crate::parser::{parse_with_options, ParserOptions};
+use rustc_hash::FxHashMap;
+
+/// The values of one field across all records.
+///
+/// Columns holding a single JSON type are stored as a typed vector, with
+/// `None` for records where the field is missing or null. Integers and
+/// floats together are stored as floats.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    /// Every value is null or missing; holds the number of rows
+    Null(usize),
+    /// Booleans
+    Bool(Vec<Option<bool>>),
+    /// Integers
+    Integer(Vec<Option<i64>>),
+    /// Floats, or a mix of floats and integers
+    Float(Vec<Option<f64>>),
+    /// Strings
+    String(Vec<Option<String>>),
+    /// Values of differing types, arrays or objects; missing fields are
+    /// [`Value::Null`]
+    Mixed(Vec<Value>),
+}
+
+impl Column {
+    /// Returns the number of rows in the column.
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Null(len) => *len,
+            Column::Bool(values) => values.len(),
+            Column::Integer(values) => values.len(),
+            Column::Float(values) => values.len(),
+            Column::String(values) => values.len(),
+            Column::Mixed(values) => values.len(),
+        }
+    }
+
+    /// Returns true if the column has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if row `index` is null or missing, or out of range.
+    pub fn is_null(&self, index: usize) -> bool {
+        match self {
+            Column::Null(_) => true,
+            Column::Bool(values) => values.get(index).map_or(true, Option::is_none),
+            Column::Integer(values) => values.get(index).map_or(true, Option::is_none),
+            Column::Float(values) => values.get(index).map_or(true, Option::is_none),
+            Column::String(values) => values.get(index).map_or(true, Option::is_none),
+            Column::Mixed(values) => values.get(index).map_or(true, Value::is_null),
+        }
+    }
+
+    /// Returns the number of null or missing values.
+    pub fn null_count(&self) -> usize {
+        (0..self.len()).filter(|&index| self.is_null(index)).count()
+    }
+
+    /// Returns row `index` as a value, or `None` if out of range.
+    pub fn get(&self, index: usize) -> Option<Value> {
+        if index >= self.len() {
+            return None;
+        }
+        let value = match self {
+            Column::Null(_) => Value::Null,
+            Column::Bool(values) => values[index].map_or(Value::Null, Value::Bool),
+            Column::Integer(values) => {
+                values[index].map_or(Value::Null, |i| Value::Number(Number::Integer(i)))
+            }
+            Column::Float(values) => {
+                values[index].map_or(Value::Null, |f| Value::Number(Number::Float(f)))
+            }
+            Column::String(values) => values[index]
+                .as_ref()
+                .map_or(Value::Null, |s| Value::String(s.clone())),
+            Column::Mixed(values) => values[index].clone(),
+        };
+        Some(value)
+    }
+
+    /// Builds the narrowest column type holding `values`.
+    fn from_values(values: Vec<Value>) -> Self {
+        let mut kind = Kind::Null;
+        for value in &values {
+            kind = kind.widen(value);
+            if kind == Kind::Mixed {
+                return Column::Mixed(values);
+            }
+        }
+
+        let values = values.into_iter();
+        match kind {
+            Kind::Null => Column::Null(values.len()),
+            Kind::Bool => Column::Bool(values.map(|value| value.as_bool()).collect()),
+            Kind::Integer => Column::Integer(values.map(|value| value.as_i64()).collect()),
+            Kind::Float => Column::Float(values.map(|value| value.as_f64()).collect()),
+            Kind::String => Column::String(
+                values
+                    .map(|value| match value {
+                        Value::String(s) => Some(s),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Kind::Mixed => unreachable!("mixed columns return early"),
+        }
+    }
+}
+
+/// The narrowest column type seen so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Null,
+    Bool,
+    Integer,
+    Float,
+    String,
+    Mixed,
+}
+
+impl Kind {
+    fn widen(self, value: &Value) -> Kind {
+        let kind = match value {
+            Value::Null => return self,
+            Value::Bool(_) => Kind::Bool,
+            Value::Number(Number::Integer(_)) => Kind::Integer,
+            Value::Number(Number::Float(_)) => Kind::Float,
+            Value::String(_) => Kind::String,
+            Value::Array(_) | Value::Object(_) => Kind::Mixed,
+        };
+        match (self, kind) {
+            (Kind::Null, kind) => kind,
+            (current, kind) if current == kind => current,
+            (Kind::Integer, Kind::Float) | (Kind::Float, Kind::Integer) => Kind::Float,
+            _ => Kind::Mixed,
+        }
+    }
+}
+
+/// Records stored column by column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordBatch {
+    num_rows: usize,
+    /// Columns sorted by field name
+    columns: Vec<(String, Column)>,
+}
+
+impl RecordBatch {
+    /// Transposes an array of objects into columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not an array or any element is not an
+    /// object.
+    pub fn from_value(value: Value) -> Result<Self> {
+        let Value::Array(records) = value else {
+            return Err(Error::Custom(format!(
+                "expected an array of records, found {}",
+                type_name(&value)
+            )));
+        };
+
+        let num_rows = records.len();
+        let mut fields: FxHashMap<String, Vec<Value>> = FxHashMap::default();
+        for (row, record) in records.into_iter().enumerate() {
+            let Value::Object(record) = record else {
+                return Err(Error::Custom(format!(
+                    "record {row} is {}, not an object",
+                    type_name(&record)
+                )));
+            };
+            for (field, value) in record {
+                let column = fields.entry(field).or_default();
+                // Fill in rows that lacked this field
+                column.resize(row, Value::Null);
+                column.push(value);
+            }
+        }
+
+        let mut columns: Vec<_> = fields
+            .into_iter()
+            .map(|(field, mut values)| {
+                values.resize(num_rows, Value::Null);
+                (field, Column::from_values(values))
+            })
+            .collect();
+        columns.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(RecordBatch { num_rows, columns })
+    }
+
+    /// Returns the number of records.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of distinct fields.
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the column for `field`.
+    pub fn column(&self, field: &str) -> Option<&Column> {
+        self.columns
+            .binary_search_by(|(name, _)| name.as_str().cmp(field))
+            .ok()
+            .map(|index| &self.columns[index].1)
+    }
+
+    /// Returns the field names in sorted order.
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.columns.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the columns with their field names, sorted by name.
+    pub fn columns(&self) -> impl Iterator<Item = (&str, &Column)> {
+        self.columns
+            .iter()
+            .map(|(name, column)| (name.as_str(), column))
+    }
+
+    /// Rebuilds record `index` as an object, omitting null fields.
+    pub fn row(&self, index: usize) -> Option<Value> {
+        if index >= self.num_rows {
+            return None;
+        }
+        let record = self
+            .columns
+            .iter()
+            .filter(|(_, column)| !column.is_null(index))
+            .filter_map(|(name, column)| Some((name.clone(), column.get(index)?)))
+            .collect();
+        Some(Value::Object(record))
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Parses a top-level array of objects into columns.
+///
+/// # Errors
+///
+/// Returns the parse error if `input` is invalid, or an error if it is not
+/// an array of objects.
+pub fn parse_records(input: &str) -> Result<RecordBatch> {
+    parse_records_with_options(input, ParserOptions::default())
+}
+
+/// Parses a top-level array of objects into columns using custom options.
+///
+/// # Errors
+///
+/// Returns the parse error if `input` is invalid, or an error if it is not
+/// an array of objects.
+pub fn parse_records_with_options(input: &str, options: ParserOptions) -> Result<RecordBatch> {
+    RecordBatch::from_value(parse_with_options(input, options)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_align_with_null_filling() {
+        let input = r#"[
+            {"id": 1, "name": "a", "score": 1.5},
+            {"id": 2, "active": true},
+            {"id": 3, "name": "c", "score": 2, "active": false},
+        ]"#;
+        let batch = parse_records(input).unwrap();
+
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(
+            batch.field_names().collect::<Vec<_>>(),
+            ["active", "id", "name", "score"]
+        );
+        assert_eq!(
+            batch.column("id"),
+            Some(&Column::Integer(vec![Some(1), Some(2), Some(3)]))
+        );
+        assert_eq!(
+            batch.column("name"),
+            Some(&Column::String(vec![
+                Some("a".to_string()),
+                None,
+                Some("c".to_string())
+            ]))
+        );
+        assert_eq!(
+            batch.column("score"),
+            Some(&Column::Float(vec![Some(1.5), None, Some(2.0)]))
+        );
+        assert_eq!(
+            batch.column("active"),
+            Some(&Column::Bool(vec![None, Some(true), Some(false)]))
+        );
+        for (_, column) in batch.columns() {
+            assert_eq!(column.len(), 3);
+        }
+        assert_eq!(batch.column("active").unwrap().null_count(), 1);
+        assert_eq!(
+            batch.row(1),
+            Some(crate::parse(r#"{"id": 2, "active": true}"#).unwrap())
+        );
+        assert_eq!(batch.row(3), None);
+    }
+
+    #[test]
+    fn test_mixed_and_null_columns() {
+        let batch = parse_records(r#"[{"a": 1, "b": null}, {"a": "x"}, {"a": [1]}]"#).unwrap();
+        assert_eq!(batch.column("b"), Some(&Column::Null(3)));
+        let a = batch.column("a").unwrap();
+        assert!(matches!(a, Column::Mixed(_)));
+        assert_eq!(a.get(1), Some(Value::String("x".to_string())));
+        assert!(batch.column("c").is_none());
+
+        let empty = parse_records("[]").unwrap();
+        assert_eq!((empty.num_rows(), empty.num_columns()), (0, 0));
+    }
+
+    #[test]
+    fn test_rejects_non_records() {
+        assert!(parse_records(r#"{"a": 1}"#).is_err());
+        let error = parse_records(r#"[{"a": 1}, 2]"#).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Custom error: record 1 is a number, not an object"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! ML-based pattern recognition for error recovery
 //!
 //! This module implements machine learning-inspired pattern recognition
-//! for common JSON parsing errors.
+//! for common JSON parsing errors. It is only built with the `ml-recovery`
+//! feature, which also provides
+//! [`parse_with_ml_recovery`](crate::parser::parse_with_ml_recovery).
 
 #![allow(dead_code)]
 
@@ -88,6 +90,17 @@ enum FixOperation {
     },
 }
 
+/// A learned pattern that matched an error, with the fix it suggests.
+#[derive(Debug, Clone)]
+pub struct PatternMatch {
+    /// Identifier of the pattern, such as `missing_closing_brace`
+    pub pattern: String,
+    /// How well the error fits the pattern, between 0.0 and 1.0
+    pub score: f64,
+    /// The fix the pattern suggests
+    pub suggestion: RecoverySuggestion,
+}
+
 /// Record of a successful fix
 #[derive(Debug, Clone)]
 struct SuccessfulFix {
@@ -221,6 +234,16 @@ impl MLPatternRecognizer {
 
     /// Recognize patterns and suggest fixes
     pub fn recognize_and_suggest(&mut self, context: &ErrorContext) -> Vec<RecoverySuggestion> {
+        self.matches(context)
+            .into_iter()
+            .map(|matched| matched.suggestion)
+            .collect()
+    }
+
+    /// Patterns that match the error in `context`, best first, each with
+    /// the fix it suggests. Patterns whose fix cannot be applied to the
+    /// input are left out.
+    pub fn matches(&self, context: &ErrorContext) -> Vec<PatternMatch> {
         // Extract features
         let features = self.extract_features(context);
 
@@ -235,21 +258,25 @@ impl MLPatternRecognizer {
             .collect();
 
         // Sort by score
-        pattern_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        pattern_scores.sort_by(|a, b| b.1.total_cmp(&a.1));
 
         // Generate suggestions for high-scoring patterns
-        let mut suggestions = Vec::new();
+        let mut matches = Vec::new();
         for (pattern_id, score) in pattern_scores {
             if score > 0.5 {
                 if let Some(pattern) = self.patterns.get(&pattern_id) {
                     if let Some(suggestion) = self.generate_suggestion(pattern, context, score) {
-                        suggestions.push(suggestion);
+                        matches.push(PatternMatch {
+                            pattern: pattern_id,
+                            score,
+                            suggestion,
+                        });
                     }
                 }
             }
         }
 
-        suggestions
+        matches
     }
 
     /// Extract features from context
@@ -306,68 +333,49 @@ impl MLPatternRecognizer {
         })
     }
 
-    /// Apply fix template to generate fixed input
+    /// Apply fix template to generate fixed input.
+    ///
+    /// Returns None when the fix would split a character or reach outside
+    /// the input; an insertion past the end is made at the end.
     fn apply_fix_template(&self, template: &FixTemplate, context: &ErrorContext) -> Option<String> {
+        let input = &context.input;
+        let at = |offset: i32| {
+            let position = context.position.saturating_add_signed(offset as isize);
+            position.min(input.len())
+        };
+        let replace = |start: usize, end: usize, text: &str| {
+            input.get(start..end)?;
+            let mut fixed = input.clone();
+            fixed.replace_range(start..end, text);
+            Some(fixed)
+        };
         match template {
             FixTemplate::InsertChar { char, offset } => {
-                let position = (context.position as i32 + offset).max(0) as usize;
-                let mut fixed = context.input.clone();
-                if position <= fixed.len() {
-                    fixed.insert(position, *char);
-                    Some(fixed)
-                } else {
-                    fixed.push(*char);
-                    Some(fixed)
-                }
+                let position = at(*offset);
+                replace(position, position, char.encode_utf8(&mut [0; 4]))
             }
             FixTemplate::InsertString { string, offset } => {
-                let position = (context.position as i32 + offset).max(0) as usize;
-                let mut fixed = context.input.clone();
-                if position <= fixed.len() {
-                    fixed.insert_str(position, string);
-                    Some(fixed)
-                } else {
-                    fixed.push_str(string);
-                    Some(fixed)
-                }
+                let position = at(*offset);
+                replace(position, position, string)
             }
             FixTemplate::ReplaceRange {
                 start,
                 end,
                 replacement,
-            } => {
-                let start_pos = (context.position as i32 + start).max(0) as usize;
-                let end_pos = (context.position as i32 + end).max(0) as usize;
-                let mut fixed = context.input.clone();
-                fixed.replace_range(start_pos..end_pos, replacement);
-                Some(fixed)
-            }
-            FixTemplate::RemoveRange { start, end } => {
-                let start_pos = (context.position as i32 + start).max(0) as usize;
-                let end_pos = (context.position as i32 + end).max(0) as usize;
-                let mut fixed = context.input.clone();
-                fixed.replace_range(start_pos..end_pos, "");
-                Some(fixed)
-            }
+            } => replace(at(*start), at(*end), replacement),
+            FixTemplate::RemoveRange { start, end } => replace(at(*start), at(*end), ""),
             FixTemplate::Complex(operations) => {
-                let mut fixed = context.input.clone();
+                let mut fixed = input.clone();
                 for op in operations {
-                    match op {
+                    let (start, end, text) = match op {
                         FixOperation::Insert { position, text } => {
-                            if *position <= fixed.len() {
-                                fixed.insert_str(*position, text);
-                            }
-                        }
-                        FixOperation::Delete { start, end } => {
-                            if *start <= fixed.len() && *end <= fixed.len() && start <= end {
-                                fixed.replace_range(*start..*end, "");
-                            }
-                        }
-                        FixOperation::Replace { start, end, text } => {
-                            if *start <= fixed.len() && *end <= fixed.len() && start <= end {
-                                fixed.replace_range(*start..*end, text);
-                            }
+                            (*position, *position, text.as_str())
                         }
+                        FixOperation::Delete { start, end } => (*start, *end, ""),
+                        FixOperation::Replace { start, end, text } => (*start, *end, text.as_str()),
+                    };
+                    if fixed.get(start..end).is_some() {
+                        fixed.replace_range(start..end, text);
                     }
                 }
                 Some(fixed)
@@ -487,7 +495,7 @@ impl FeatureExtractor for StructuralBalanceExtractor {
         // Check if at end of input
         features.push(Feature {
             name: "at_end_of_input".to_string(),
-            value: if context.position >= context.input.len() - 1 {
+            value: if context.position + 1 >= context.input.len() {
                 1.0
             } else {
                 0.0
@@ -565,11 +573,12 @@ impl FeatureExtractor for ErrorTypeExtractor {
                 }
                 
                 // Check if we're between values
-                if position > &0 && position < &context.input.len() {
-                    let before = &context.input[..*position];
-                    let after = &context.input[*position..];
+                if position > &0 && position < &context.input.len() && context.input.is_char_boundary(*position) {
+                    let before = context.input[..*position].trim_end();
+                    let after = context.input[*position..].trim_start();
                     
-                    // Check if we have a value before and after
+                    // Check if we have a value before and after, whatever
+                    // whitespace lies between them
                     if (before.ends_with('"') || before.chars().last().is_some_and(|c| c.is_numeric())) &&
                        (after.starts_with('"') || after.starts_with('[') || after.starts_with('{')) {
                         features.push(Feature {
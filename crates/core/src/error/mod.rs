@@ -23,6 +23,10 @@ pub mod span;
 pub mod terminal;
 /// Error type definitions and implementations with structured error codes.
 pub mod types;
+/// Names for invisible and easily confused Unicode characters.
+pub mod unicode;
+/// Warnings about forgiving transformations that change a document's shape.
+pub mod warning;
 
 /// Utility traits and helper functions for error analysis.
 pub mod utils;
@@ -31,13 +35,18 @@ pub mod utils;
 pub mod recovery_v2;
 
 /// ML-based pattern recognition for error recovery.
+#[cfg(feature = "ml-recovery")]
 pub mod ml_patterns;
 
 // Re-export public API for backward compatibility
 pub use result::{ParseResult, Result};
-pub use span::{ContextWindow, EnhancedSpan, LineCol, Span};
-pub use types::{Error, ErrorCode};
+pub use span::{
+    display_width, excerpt_line, expand_tabs, ContextWindow, EnhancedSpan, LineCol, Span,
+    DEFAULT_CONTEXT_WIDTH,
+};
+pub use types::{BudgetLimit, Error, ErrorCode};
 pub use utils::{ErrorHelper, ErrorSeverity, ErrorUtils};
+pub use warning::{CompatKind, CompatWarning, Warning, WarningKind};
 
 // Re-export repair functionality
 pub use repair::{EnhancedParseResult, ParsingTier, RepairAction, RepairType};
@@ -57,4 +66,5 @@ pub use reporter::{
 pub use recovery_v2::{ErrorRecoveryEngineV2, RecoverySuggestion, SuggestionCategory, ErrorContext};
 
 // Re-export ML pattern recognition components
-pub use ml_patterns::MLPatternRecognizer;
+#[cfg(feature = "ml-recovery")]
+pub use ml_patterns::{MLPatternRecognizer, PatternMatch};
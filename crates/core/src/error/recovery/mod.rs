@@ -1,6 +1,7 @@
 // this_file: crates/core/src/error/recovery/mod.rs
 
 use crate::error::{Error, ErrorCode, Span};
+use regex::Regex;
 use rustc_hash::FxHashMap;
 /// Context-aware error recovery rules.
 pub mod context;
@@ -8,8 +9,62 @@ pub mod context;
 pub mod strategies;
 
 use context::ContextRule;
+use strategies::find_trailing_comma;
 pub use strategies::RecoveryStrategy;
 
+/// A single quick fix for a parse error, ready to be previewed or applied.
+///
+/// This is the top-level counterpart of [`RecoveryStrategy`]: it carries the
+/// description, confidence and affected span so tools can present the fix
+/// without matching on the strategy themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoverySuggestion {
+    /// Human-readable description of the fix.
+    pub description: String,
+    /// Confidence level of the fix (0.0 to 1.0).
+    pub confidence: f32,
+    /// The span of the input affected by the fix.
+    pub span: Span,
+    /// The underlying recovery strategy.
+    pub strategy: RecoveryStrategy,
+}
+
+impl RecoverySuggestion {
+    /// Creates a suggestion from a strategy, using `fallback_span` when the
+    /// strategy has no location of its own.
+    pub fn from_strategy(strategy: RecoveryStrategy, fallback_span: Span) -> Self {
+        RecoverySuggestion {
+            description: strategy.description(),
+            confidence: strategy.confidence(),
+            span: strategy.span().unwrap_or(fallback_span),
+            strategy,
+        }
+    }
+
+    /// Returns `input` with this single fix applied.
+    pub fn apply(&self, input: &str) -> String {
+        self.strategy.apply(input)
+    }
+}
+
+/// Analyzes a parse error and returns quick-fix suggestions, most confident first.
+///
+/// Each suggestion can be previewed with [`RecoverySuggestion::apply`], which
+/// returns `input` with just that fix applied.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{analyze_error, Error};
+///
+/// let input = "[1, 2,]";
+/// let suggestions = analyze_error(input, &Error::TrailingComma(6));
+/// assert_eq!(suggestions[0].apply(input), "[1, 2]");
+/// ```
+pub fn analyze_error(input: &str, error: &Error) -> Vec<RecoverySuggestion> {
+    ErrorRecoveryAnalyzer::new().suggest(error, input)
+}
+
 /// Analyzes parsing errors and suggests recovery strategies.
 #[derive(Debug)]
 pub struct ErrorRecoveryAnalyzer {
@@ -49,10 +104,29 @@ impl ErrorRecoveryAnalyzer {
             ErrorCode::RepairFailed => self.analyze_repair_failed(error, input),
             ErrorCode::MaxRepairsExceeded => self.analyze_max_repairs_exceeded(error, input),
             ErrorCode::InvalidChunk => self.analyze_invalid_chunk(error, input),
+            ErrorCode::DuplicateKey => self.analyze_duplicate_key(error, input),
             _ => self.analyze_context_rules(error, input),
         }
     }
 
+    /// Analyzes an error and returns suggestions sorted by descending confidence.
+    ///
+    /// The sort is stable, so strategies of equal confidence keep the order in
+    /// which the analysis ranked them.
+    pub fn suggest(&self, error: &Error, input: &str) -> Vec<RecoverySuggestion> {
+        let fallback_span = error
+            .span()
+            .map(|span| Span::new(span.start.min(input.len()), span.end.min(input.len())))
+            .unwrap_or_default();
+        let mut suggestions: Vec<_> = self
+            .analyze_error(error, input)
+            .into_iter()
+            .map(|strategy| RecoverySuggestion::from_strategy(strategy, fallback_span))
+            .collect();
+        suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        suggestions
+    }
+
     /// Analyzes errors using context-aware rules.
     fn analyze_context_rules(&self, error: &Error, input: &str) -> Vec<RecoveryStrategy> {
         let mut strategies = Vec::new();
@@ -60,23 +134,24 @@ impl ErrorRecoveryAnalyzer {
             let context_str = self.get_context(input, &span, 50); // Get a larger context
 
             for rule in &self.context_rules {
-                if self.matches_context_pattern(&context_str, &rule.pattern) {
-                    // For now, just add the strategy. More sophisticated logic could
-                    // involve confidence levels and combining strategies.
-                    strategies.push(rule.strategy.clone());
+                if rule.pattern.is_match(&context_str) {
+                    // Rules are position-independent; anchor them at the error.
+                    let strategy = match &rule.strategy {
+                        RecoveryStrategy::AddClosing { delimiter, .. } => {
+                            RecoveryStrategy::AddClosing {
+                                position: span.start.min(input.len()),
+                                delimiter: *delimiter,
+                            }
+                        }
+                        strategy => strategy.clone(),
+                    };
+                    strategies.push(strategy);
                 }
             }
         }
         strategies
     }
 
-    /// Checks if the given context string matches the provided regex pattern.
-    fn matches_context_pattern(&self, context: &str, pattern: &str) -> bool {
-        // This is a placeholder. A real implementation would use a regex engine.
-        // For now, a simple substring check or basic pattern matching.
-        context.contains(pattern)
-    }
-
     /// Initializes default error patterns and their recovery strategies.
     fn init_default_patterns(&mut self) {
         // Common syntax errors
@@ -110,7 +185,7 @@ impl ErrorRecoveryAnalyzer {
     fn init_context_rules(&mut self) {
         // Rule for detecting missing closing braces
         self.context_rules.push(ContextRule {
-            pattern: r"^\s*$".to_string(), // End of input
+            pattern: Regex::new(r"^\s*$").expect("valid context rule pattern"), // End of input
             strategy: RecoveryStrategy::AddClosing {
                 position: 0,
                 delimiter: '}',
@@ -120,7 +195,7 @@ impl ErrorRecoveryAnalyzer {
 
         // Rule for detecting missing closing brackets
         self.context_rules.push(ContextRule {
-            pattern: r"^\s*$".to_string(), // End of input
+            pattern: Regex::new(r"^\s*$").expect("valid context rule pattern"), // End of input
             strategy: RecoveryStrategy::AddClosing {
                 position: 0,
                 delimiter: ']',
@@ -134,7 +209,7 @@ impl ErrorRecoveryAnalyzer {
         let mut strategies = Vec::new();
 
         if let Some(span) = error.span() {
-            let char_at_pos = input.chars().nth(span.start).unwrap_or('\0');
+            let char_at_pos = char_at(input, span.start);
             let context = self.get_context(input, &span, 10);
 
             match char_at_pos {
@@ -145,16 +220,30 @@ impl ErrorRecoveryAnalyzer {
                     });
                 }
                 '\'' => {
-                    // Single quote instead of double quote
-                    strategies.push(RecoveryStrategy::ReplaceText {
-                        span,
-                        replacement: "\"".to_string(),
-                    });
+                    // Single-quoted string: requote the whole literal
+                    match single_quoted_span(input, span.start) {
+                        Some(string_span) => {
+                            let content = &input[string_span.start + 1..string_span.end - 1];
+                            strategies.push(RecoveryStrategy::ReplaceText {
+                                span: string_span,
+                                replacement: format!(
+                                    "\"{}\"",
+                                    content.replace("\\'", "'").replace('"', "\\\"")
+                                ),
+                            });
+                        }
+                        None => strategies.push(RecoveryStrategy::ReplaceText {
+                            span,
+                            replacement: "\"".to_string(),
+                        }),
+                    }
                 }
                 _ => {
                     // Check if it's an unquoted key
                     if self.looks_like_unquoted_key(&context) {
-                        strategies.push(RecoveryStrategy::QuoteKey { span });
+                        strategies.push(RecoveryStrategy::QuoteKey {
+                            span: identifier_span(input, span.start),
+                        });
                     } else {
                         // Generic character removal
                         strategies.push(RecoveryStrategy::RemoveText { span });
@@ -171,9 +260,8 @@ impl ErrorRecoveryAnalyzer {
         let mut strategies = Vec::new();
         let input_len = input.len();
 
-        // Count unclosed brackets and braces
-        let mut brace_count = 0;
-        let mut bracket_count = 0;
+        // Track unclosed brackets and braces in nesting order
+        let mut open_delimiters = Vec::new();
         let mut in_string = false;
         let mut escape_next = false;
 
@@ -186,26 +274,20 @@ impl ErrorRecoveryAnalyzer {
             match ch {
                 '\\' if in_string => escape_next = true,
                 '"' => in_string = !in_string,
-                '{' if !in_string => brace_count += 1,
-                '}' if !in_string => brace_count -= 1,
-                '[' if !in_string => bracket_count += 1,
-                ']' if !in_string => bracket_count -= 1,
+                '{' if !in_string => open_delimiters.push('}'),
+                '[' if !in_string => open_delimiters.push(']'),
+                '}' | ']' if !in_string => {
+                    open_delimiters.pop();
+                }
                 _ => {}
             }
         }
 
-        // Add missing closing delimiters
-        for _ in 0..brace_count {
+        // Add missing closing delimiters, innermost first
+        for delimiter in open_delimiters.into_iter().rev() {
             strategies.push(RecoveryStrategy::AddClosing {
                 position: input_len,
-                delimiter: '}',
-            });
-        }
-
-        for _ in 0..bracket_count {
-            strategies.push(RecoveryStrategy::AddClosing {
-                position: input_len,
-                delimiter: ']',
+                delimiter,
             });
         }
 
@@ -224,9 +306,7 @@ impl ErrorRecoveryAnalyzer {
     fn analyze_invalid_string(&self, error: &Error, input: &str) -> Vec<RecoveryStrategy> {
         let mut strategies = Vec::new();
 
-        if let Some(span) = error.span() {
-            let string_content = span.extract(input);
-
+        if let Some((span, string_content)) = error_text(error, input) {
             // Check for unescaped quotes
             if string_content.contains('"') {
                 strategies.push(RecoveryStrategy::ReplaceText {
@@ -259,9 +339,7 @@ impl ErrorRecoveryAnalyzer {
     fn analyze_invalid_number(&self, error: &Error, input: &str) -> Vec<RecoveryStrategy> {
         let mut strategies = Vec::new();
 
-        if let Some(span) = error.span() {
-            let number_text = span.extract(input);
-
+        if let Some((span, number_text)) = error_text(error, input) {
             // Try to fix common number format issues
             if let Ok(corrected) = self.fix_number_format(number_text) {
                 strategies.push(RecoveryStrategy::RepairNumber {
@@ -271,8 +349,10 @@ impl ErrorRecoveryAnalyzer {
             }
 
             // If it looks like a quoted number, suggest removing quotes
-            if number_text.starts_with('"') && number_text.ends_with('"') {
-                let unquoted = &number_text[1..number_text.len() - 1];
+            if let Some(unquoted) = number_text
+                .strip_prefix('"')
+                .and_then(|rest| rest.strip_suffix('"'))
+            {
                 if unquoted.parse::<f64>().is_ok() {
                     strategies.push(RecoveryStrategy::ReplaceText {
                         span,
@@ -289,9 +369,7 @@ impl ErrorRecoveryAnalyzer {
     fn analyze_invalid_escape(&self, error: &Error, input: &str) -> Vec<RecoveryStrategy> {
         let mut strategies = Vec::new();
 
-        if let Some(span) = error.span() {
-            let escape_sequence = span.extract(input);
-
+        if let Some((span, escape_sequence)) = error_text(error, input) {
             // Common escape sequence fixes
             let fixed_escape = match escape_sequence {
                 "\\'" => "\\'", // Single quote doesn't need escaping in JSON
@@ -312,12 +390,12 @@ impl ErrorRecoveryAnalyzer {
     }
 
     /// Analyzes trailing comma errors.
-    fn analyze_trailing_comma(&self, error: &Error, _input: &str) -> Vec<RecoveryStrategy> {
+    fn analyze_trailing_comma(&self, error: &Error, input: &str) -> Vec<RecoveryStrategy> {
         let mut strategies = Vec::new();
 
         if let Some(span) = error.span() {
             strategies.push(RecoveryStrategy::RemoveTrailingComma {
-                position: span.start,
+                position: find_trailing_comma(input, span.start).unwrap_or(span.start),
             });
         }
 
@@ -371,7 +449,7 @@ impl ErrorRecoveryAnalyzer {
     }
 
     /// Analyzes duplicate key errors.
-    fn _analyze_duplicate_key(&self, _error: &Error, _input: &str) -> Vec<RecoveryStrategy> {
+    fn analyze_duplicate_key(&self, _error: &Error, _input: &str) -> Vec<RecoveryStrategy> {
         vec![RecoveryStrategy::ManualIntervention {
             suggestions: vec![
                 "Remove or rename one of the duplicate keys".to_string(),
@@ -386,7 +464,7 @@ impl ErrorRecoveryAnalyzer {
         let mut strategies = Vec::new();
 
         if let Some(span) = error.span() {
-            let char_at_pos = input.chars().nth(span.start).unwrap_or('\0');
+            let char_at_pos = char_at(input, span.start);
 
             match char_at_pos {
                 ']' => {
@@ -441,21 +519,21 @@ impl ErrorRecoveryAnalyzer {
     fn analyze_invalid_unicode(&self, error: &Error, input: &str) -> Vec<RecoveryStrategy> {
         let mut strategies = Vec::new();
 
-        if let Some(span) = error.span() {
-            let unicode_escape = span.extract(input);
-
+        if let Some((span, unicode_escape)) = error_text(error, input) {
             // Try to fix common Unicode escape issues
             if let Some(hex_part) = unicode_escape.strip_prefix("\\u") {
-                if hex_part.len() < 4 {
+                let digits = hex_part.chars().count();
+                if digits < 4 {
                     // Pad with zeros
                     let padded = format!("\\u{hex_part:0>4}");
                     strategies.push(RecoveryStrategy::ReplaceText {
                         span,
                         replacement: padded,
                     });
-                } else if hex_part.len() > 4 {
+                } else if digits > 4 {
                     // Truncate to 4 digits
-                    let truncated = format!("\\u{}", &hex_part[..4]);
+                    let truncated: String = hex_part.chars().take(4).collect();
+                    let truncated = format!("\\u{truncated}");
                     strategies.push(RecoveryStrategy::ReplaceText {
                         span,
                         replacement: truncated,
@@ -491,16 +569,36 @@ impl ErrorRecoveryAnalyzer {
 
         if let Some(span) = error.span() {
             let context = self.get_context(input, &span, 20);
+            let (expected, found) = match error {
+                Error::Expected {
+                    expected, found, ..
+                } => (expected.as_str(), found.as_str()),
+                _ => ("", ""),
+            };
 
-            // Check if we're missing a comma
-            if self.looks_like_missing_comma(&context) {
+            if found.contains("Eof") || span.start >= input.trim_end().len() {
+                // Ran out of input: close whatever is still open
+                strategies.extend(self.analyze_unexpected_eof(error, input));
+            } else if found.contains("UnquotedString") && expected.contains("key") {
+                strategies.push(RecoveryStrategy::QuoteKey {
+                    span: identifier_span(input, span.start),
+                });
+            } else if let Some(delimiter) = missing_closer(expected, input, span.start) {
+                strategies.push(RecoveryStrategy::AddClosing {
+                    position: span.start,
+                    delimiter,
+                });
+            } else if expected.starts_with(':') {
+                strategies.push(RecoveryStrategy::InsertText {
+                    position: span.start,
+                    text: ":".to_string(),
+                });
+            } else if expected.starts_with(',') || self.looks_like_missing_comma(&context) {
                 strategies.push(RecoveryStrategy::AddComma {
                     position: span.start,
                 });
-            }
-
-            // Check if we need a colon for object key-value pairs
-            if context.contains('"') && !context.contains(':') {
+            } else if context.contains('"') && !context.contains(':') {
+                // Check if we need a colon for object key-value pairs
                 strategies.push(RecoveryStrategy::InsertText {
                     position: span.start,
                     text: ":".to_string(),
@@ -589,8 +687,17 @@ impl ErrorRecoveryAnalyzer {
 
     /// Gets context around a span for analysis.
     fn get_context(&self, input: &str, span: &Span, context_size: usize) -> String {
-        let start = span.start.saturating_sub(context_size);
-        let end = (span.end + context_size).min(input.len());
+        let mut start = span.start.saturating_sub(context_size).min(input.len());
+        while !input.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = span
+            .end
+            .saturating_add(context_size)
+            .clamp(start, input.len());
+        while !input.is_char_boundary(end) {
+            end += 1;
+        }
         input[start..end].to_string()
     }
 
@@ -651,6 +758,55 @@ impl ErrorRecoveryAnalyzer {
     }
 }
 
+/// Returns the error's span with the text it covers, if it is a valid slice of `input`.
+fn error_text<'a>(error: &Error, input: &'a str) -> Option<(Span, &'a str)> {
+    let span = error.span()?;
+    Some((span, span.try_extract(input)?))
+}
+
+/// Returns the character starting at byte `position`, or `'\0'` if there is none.
+fn char_at(input: &str, position: usize) -> char {
+    input
+        .get(position..)
+        .and_then(|rest| rest.chars().next())
+        .unwrap_or('\0')
+}
+
+/// Returns the closer an error expected where `input` has one of the other
+/// type at `position`, which means the structure it closes is still open.
+fn missing_closer(expected: &str, input: &str, position: usize) -> Option<char> {
+    match char_at(input, position) {
+        '}' if expected.contains(']') => Some(']'),
+        ']' if expected.contains('}') => Some('}'),
+        _ => None,
+    }
+}
+
+/// Returns the span of the identifier-like key starting at `start`.
+fn identifier_span(input: &str, start: usize) -> Span {
+    let rest = input.get(start..).unwrap_or("");
+    let len = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$' || c == '-'))
+        .unwrap_or(rest.len());
+    Span::new(start, start + len)
+}
+
+/// Returns the span of the single-quoted string opening at `start`, quotes included.
+fn single_quoted_span(input: &str, start: usize) -> Option<Span> {
+    let rest = input.get(start + 1..)?;
+    let mut escaped = false;
+    for (offset, ch) in rest.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\'' => return Some(Span::new(start, start + 1 + offset + 1)),
+            '\n' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
 impl Default for ErrorRecoveryAnalyzer {
     fn default() -> Self {
         Self::new()
@@ -749,4 +905,39 @@ mod tests {
         // Test invalid number
         assert!(analyzer.fix_number_format("abc").is_err());
     }
+
+    #[test]
+    fn test_spans_from_another_input_do_not_panic() {
+        let analyzer = ErrorRecoveryAnalyzer::new();
+        // Spans measured against a longer or differently encoded input
+        let input = "[\"é\", 1_000]";
+        for error in [
+            Error::InvalidNumber(0).with_span(Span::new(9, 40)),
+            Error::InvalidEscape(0).with_span(Span::new(3, 4)),
+            Error::InvalidUnicode(0).with_span(Span::new(2, 3)),
+            Error::InvalidNumber(0).with_span(Span::new(30, 32)),
+        ] {
+            let strategies = analyzer.analyze_error(&error, input);
+            assert!(strategies
+                .iter()
+                .all(|s| !matches!(s, RecoveryStrategy::ReplaceText { .. })));
+        }
+
+        // The same error with a span that fits still gets its fix
+        let error = Error::InvalidNumber(7).with_span(Span::new(7, 12));
+        assert!(!analyzer.analyze_error(&error, input).is_empty());
+    }
+
+    #[test]
+    fn test_overlong_unicode_escape_is_truncated_by_character() {
+        let analyzer = ErrorRecoveryAnalyzer::new();
+        // Cutting at four bytes would split the 'é'
+        let input = r#""\u000é9""#;
+        let error = Error::InvalidUnicode(1).with_span(Span::new(1, 9));
+        let strategies = analyzer.analyze_error(&error, input);
+        assert!(strategies.iter().any(|s| matches!(
+            s,
+            RecoveryStrategy::ReplaceText { replacement, .. } if replacement == "\\u000é"
+        )));
+    }
 }
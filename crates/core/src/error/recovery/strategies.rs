@@ -106,6 +106,83 @@ impl RecoveryStrategy {
         }
     }
 
+    /// Returns the span of input affected by this strategy.
+    ///
+    /// Insertions yield an empty span at the insertion point. Returns `None`
+    /// for [`RecoveryStrategy::ManualIntervention`], which has no location.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            RecoveryStrategy::InsertText { position, .. }
+            | RecoveryStrategy::AddClosing { position, .. }
+            | RecoveryStrategy::AddComma { position } => Some(Span::new(*position, *position)),
+            RecoveryStrategy::RemoveTrailingComma { position } => Some(Span::single(*position)),
+            RecoveryStrategy::RemoveText { span }
+            | RecoveryStrategy::ReplaceText { span, .. }
+            | RecoveryStrategy::FixQuoting { span, .. }
+            | RecoveryStrategy::RepairNumber { span, .. }
+            | RecoveryStrategy::QuoteKey { span }
+            | RecoveryStrategy::FixComment { span } => Some(*span),
+            RecoveryStrategy::ManualIntervention { .. } => None,
+        }
+    }
+
+    /// Returns `input` with this single fix applied.
+    ///
+    /// Positions and spans that fall outside `input` or split a UTF-8
+    /// character leave the input unchanged, as does
+    /// [`RecoveryStrategy::ManualIntervention`].
+    pub fn apply(&self, input: &str) -> String {
+        match self {
+            RecoveryStrategy::InsertText { position, text } => {
+                splice(input, Span::new(*position, *position), text)
+            }
+            RecoveryStrategy::RemoveText { span } => splice(input, *span, ""),
+            RecoveryStrategy::ReplaceText { span, replacement }
+            | RecoveryStrategy::RepairNumber {
+                span,
+                corrected_value: replacement,
+            } => splice(input, *span, replacement),
+            RecoveryStrategy::AddClosing {
+                position,
+                delimiter,
+            } => splice(
+                input,
+                Span::new(*position, *position),
+                delimiter.encode_utf8(&mut [0; 4]),
+            ),
+            RecoveryStrategy::FixQuoting { span, quote_char } => match span.try_extract(input) {
+                Some(text) => {
+                    let content = text.trim_matches(|c| c == '"' || c == '\'');
+                    splice(input, *span, &format!("{quote_char}{content}{quote_char}"))
+                }
+                None => input.to_string(),
+            },
+            RecoveryStrategy::AddComma { position } => {
+                splice(input, Span::new(*position, *position), ",")
+            }
+            RecoveryStrategy::RemoveTrailingComma { position } => {
+                match find_trailing_comma(input, *position) {
+                    Some(comma) => splice(input, Span::single(comma), ""),
+                    None => input.to_string(),
+                }
+            }
+            RecoveryStrategy::QuoteKey { span } => match span.try_extract(input) {
+                Some(text) => {
+                    let key = text.trim_matches('\'').replace('"', "\\\"");
+                    splice(input, *span, &format!("\"{key}\""))
+                }
+                None => input.to_string(),
+            },
+            RecoveryStrategy::FixComment { span } => match span.try_extract(input) {
+                Some(text) if text.starts_with("/*") && !text.ends_with("*/") => {
+                    splice(input, Span::new(span.end, span.end), " */")
+                }
+                _ => input.to_string(),
+            },
+            RecoveryStrategy::ManualIntervention { .. } => input.to_string(),
+        }
+    }
+
     /// Returns the confidence level for this recovery strategy (0.0 to 1.0).
     pub fn confidence(&self) -> f32 {
         match self {
@@ -123,3 +200,29 @@ impl RecoveryStrategy {
         }
     }
 }
+
+/// Replaces the text covered by `span` with `replacement`.
+fn splice(input: &str, span: Span, replacement: &str) -> String {
+    if span.try_extract(input).is_none() {
+        return input.to_string();
+    }
+    let mut output = String::with_capacity(input.len() + replacement.len());
+    output.push_str(&input[..span.start]);
+    output.push_str(replacement);
+    output.push_str(&input[span.end..]);
+    output
+}
+
+/// Locates the comma a trailing-comma error refers to.
+///
+/// Parsers report either the comma itself or the closing delimiter after it,
+/// so this looks at `position` first and then backwards over whitespace.
+pub(super) fn find_trailing_comma(input: &str, position: usize) -> Option<usize> {
+    let bytes = input.as_bytes();
+    if bytes.get(position) == Some(&b',') {
+        return Some(position);
+    }
+    let before = input.get(..position.min(input.len()))?;
+    let trimmed = before.trim_end();
+    trimmed.ends_with(',').then(|| trimmed.len() - 1)
+}
@@ -1,7 +1,8 @@
 //! Smart error recovery engine for vexy_json parsing
 //!
 //! This module implements advanced error recovery strategies including:
-//! - ML-based pattern recognition for common errors
+//! - ML-based pattern recognition for common errors, with the
+//!   `ml-recovery` feature
 //! - Context-aware repair suggestions
 
 #![allow(dead_code)]
@@ -10,6 +11,7 @@
 
 use crate::ast::{Token, Value};
 use crate::error::{Error, Span};
+#[cfg(feature = "ml-recovery")]
 use crate::error::ml_patterns::MLPatternRecognizer;
 use regex::Regex;
 use rustc_hash::FxHashMap;
@@ -56,6 +58,7 @@ pub struct ErrorRecoveryEngineV2 {
     /// Pattern database for simple pattern recognition
     pattern_db: PatternDatabase,
     /// ML-based pattern recognizer for advanced recognition
+    #[cfg(feature = "ml-recovery")]
     ml_recognizer: MLPatternRecognizer,
     /// Context analyzer
     context_analyzer: ContextAnalyzer,
@@ -72,7 +75,9 @@ pub struct RecoveryConfig {
     pub max_suggestions: usize,
     /// Minimum confidence threshold
     pub min_confidence: f64,
-    /// Enable ML-based pattern recognition
+    /// Enable ML-based pattern recognition. Without the `ml-recovery`
+    /// feature there is no recognizer, and only the simple pattern
+    /// database is consulted.
     pub enable_ml: bool,
     /// Enable context-aware suggestions
     pub enable_context: bool,
@@ -175,6 +180,7 @@ impl ErrorRecoveryEngineV2 {
     pub fn with_config(config: RecoveryConfig) -> Self {
         let mut engine = ErrorRecoveryEngineV2 {
             pattern_db: PatternDatabase::new(),
+            #[cfg(feature = "ml-recovery")]
             ml_recognizer: MLPatternRecognizer::new(),
             context_analyzer: ContextAnalyzer::new(),
             strategies: Vec::new(),
@@ -206,8 +212,8 @@ impl ErrorRecoveryEngineV2 {
         // Try ML-based pattern recognition if enabled
         if self.config.enable_ml {
             // Use the sophisticated ML recognizer first
-            let ml_suggestions = self.ml_recognizer.recognize_and_suggest(context);
-            suggestions.extend(ml_suggestions);
+            #[cfg(feature = "ml-recovery")]
+            suggestions.extend(self.ml_recognizer.recognize_and_suggest(context));
             
             // Also try simple pattern matching as a fallback
             suggestions.extend(self.pattern_db.find_matches(context));
@@ -227,7 +233,7 @@ impl ErrorRecoveryEngineV2 {
         }
 
         // Sort by confidence and limit
-        suggestions.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
+        suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
         
         // Remove duplicates based on fixed_input
         let mut seen_fixes = std::collections::HashSet::new();
@@ -393,7 +399,9 @@ impl PatternDatabase {
         let mut suggestions = Vec::new();
         
         // Check each pattern against the error context
-        let pattern_ids: Vec<String> = self.patterns.keys().cloned().collect();
+        // Sorted, so patterns of equal confidence rank alike on every run
+        let mut pattern_ids: Vec<String> = self.patterns.keys().cloned().collect();
+        pattern_ids.sort_unstable();
         for pattern_id in pattern_ids {
             if let Some(pattern) = self.patterns.get(&pattern_id) {
                 let pattern = pattern.clone();
@@ -554,6 +562,13 @@ impl BracketMatchingStrategy {
             bracket_pairs: vec![('{', '}'), ('[', ']'), ('(', ')')],
         }
     }
+
+    fn closer_of(&self, open: char) -> Option<char> {
+        self.bracket_pairs
+            .iter()
+            .find(|(o, _)| *o == open)
+            .map(|(_, close)| *close)
+    }
 }
 
 impl RecoveryStrategy for BracketMatchingStrategy {
@@ -566,17 +581,33 @@ impl RecoveryStrategy for BracketMatchingStrategy {
 
         // Count bracket pairs
         let mut stack = Vec::new();
-        for ch in context.input.chars() {
+        for (index, ch) in context.input.char_indices() {
             for (open, close) in &self.bracket_pairs {
                 if ch == *open {
                     stack.push(ch);
                 } else if ch == *close {
-                    if let Some(last) = stack.last() {
-                        if *last == *open {
+                    if let Some(&last) = stack.last() {
+                        if last == *open {
                             stack.pop();
-                        } else {
-                            // Mismatched bracket
-                            // Generate suggestion
+                        } else if let Some(expected) = self.closer_of(last) {
+                            // Mismatched bracket: close the inner one first,
+                            // as in `[1}` for `[1]}`
+                            let mut fixed = context.input.clone();
+                            fixed.insert(index, expected);
+                            suggestions.push(RecoverySuggestion {
+                                description: format!("Add missing '{expected}' before '{ch}'"),
+                                confidence: 0.85,
+                                fixed_input: fixed,
+                                category: SuggestionCategory::MissingBracket,
+                                fix_location: Span {
+                                    start: index,
+                                    end: index,
+                                },
+                            });
+                            stack.pop();
+                            if stack.last() == Some(open) {
+                                stack.pop();
+                            }
                         }
                     }
                 }
@@ -694,8 +725,8 @@ impl RecoveryStrategy for CommaSuggestionStrategy {
                 let before_pos = context.position.saturating_sub(10);
                 let after_pos = (context.position + 10).min(context.input.len());
                 
-                if after_pos > before_pos && after_pos <= context.input.len() {
-                    let context_str = &context.input[before_pos..after_pos];
+                let window = Span::new(before_pos, after_pos).try_extract(&context.input);
+                if let Some(context_str) = window.filter(|_| context.input.is_char_boundary(context.position)) {
                     
                     // Simple heuristic: if we see two values adjacent without comma
                     if context_str.contains("\" \"") || context_str.contains("} {") || 
@@ -741,7 +772,7 @@ impl RecoveryStrategy for TypeCoercionStrategy {
         
         // Look for quoted values that could be unquoted
         let search_end = context.position.min(context.input.len());
-        if let Some(pos) = context.input[..search_end].rfind('"') {
+        if let Some(pos) = context.input.get(..search_end).and_then(|before| before.rfind('"')) {
             let value_start = pos + 1;
             if let Some(end_pos) = context.input[value_start..].find('"') {
                 let value = &context.input[value_start..value_start + end_pos];
@@ -869,6 +900,24 @@ mod tests {
         assert!(first.fixed_input.ends_with('}'));
     }
 
+    #[test]
+    fn test_mismatched_bracket() {
+        let context = ErrorContext {
+            error: Error::UnexpectedChar('}', 11),
+            input: r#"{"a": [1, 2}"#.to_string(),
+            position: 11,
+            tokens_before: vec![],
+            partial_ast: None,
+            parsing_context: "in_array".to_string(),
+        };
+
+        let suggestions = BracketMatchingStrategy::new().recover(&context);
+        assert_eq!(suggestions[0].fixed_input, r#"{"a": [1, 2]}"#);
+        assert_eq!(suggestions[0].fix_location, Span::new(11, 11));
+        // The brace closes the object, so nothing is left unclosed
+        assert_eq!(suggestions.len(), 1);
+    }
+
     #[test]
     fn test_quote_inference() {
         let mut engine = ErrorRecoveryEngineV2::new();
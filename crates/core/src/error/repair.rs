@@ -3,7 +3,9 @@
 //! Repair functionality types and structures for JSON error recovery.
 
 use super::recovery_v2::SuggestionCategory;
+use super::span::Span;
 use super::types::Error;
+use super::warning::{CompatWarning, Warning};
 
 /// Represents which parsing tier was used to successfully parse the input.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +18,18 @@ pub enum ParsingTier {
     Repair,
 }
 
+impl ParsingTier {
+    /// The tier's name in lower case: `"fast"`, `"forgiving"` or `"repair"`,
+    /// as the language bindings report it.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ParsingTier::Fast => "fast",
+            ParsingTier::Forgiving => "forgiving",
+            ParsingTier::Repair => "repair",
+        }
+    }
+}
+
 impl From<SuggestionCategory> for RepairType {
     fn from(category: SuggestionCategory) -> Self {
         match category {
@@ -47,6 +61,50 @@ pub struct RepairAction {
     pub replacement: String,
     /// Human-readable description of what was repaired
     pub description: String,
+    /// The parse error that made the repair necessary, with positions in the
+    /// original input; `None` for repairs not made in answer to an error
+    pub cause: Option<Error>,
+}
+
+impl RepairAction {
+    /// The repair that turns `input` into `fixed`: one change covering
+    /// everything between the text they start with and the text they end
+    /// with in common.
+    pub(crate) fn between(
+        input: &str,
+        fixed: &str,
+        action_type: RepairType,
+        description: String,
+        cause: Error,
+    ) -> Self {
+        let prefix: usize = input
+            .chars()
+            .zip(fixed.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        let suffix: usize = input[prefix..]
+            .chars()
+            .rev()
+            .zip(fixed[prefix..].chars().rev())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        RepairAction {
+            action_type,
+            position: prefix,
+            original: input[prefix..input.len() - suffix].to_string(),
+            replacement: fixed[prefix..fixed.len() - suffix].to_string(),
+            description,
+            cause: Some(cause),
+        }
+    }
+
+    /// The bytes of the original input the error that caused this repair
+    /// covers.
+    pub fn cause_span(&self) -> Option<Span> {
+        self.cause.as_ref().and_then(Error::span)
+    }
 }
 
 /// Types of repair actions that can be performed.
@@ -74,6 +132,8 @@ pub enum RepairType {
     TypeCoercion,
     /// Added quotes to an unquoted object key
     QuoteKey,
+    /// Discarded unmatched input after the end of the document
+    DiscardTrailing,
 }
 
 /// Enhanced result type that includes repair information and error tracking.
@@ -87,6 +147,16 @@ pub struct EnhancedParseResult<T> {
     pub repairs: Vec<RepairAction>,
     /// Which parsing tier was used
     pub parsing_tier: ParsingTier,
+    /// Number of bytes after the end of the document that repair discarded
+    pub discarded_trailing_bytes: usize,
+    /// Forgiving transformations applied, when
+    /// [`ParserOptions::collect_warnings`](crate::ParserOptions::collect_warnings)
+    /// is enabled
+    pub warnings: Vec<Warning>,
+    /// Constructs outside strict JSON, when
+    /// [`ParserOptions::collect_compat_warnings`](crate::ParserOptions::collect_compat_warnings)
+    /// is enabled
+    pub compat_warnings: Vec<CompatWarning>,
 }
 
 impl<T> EnhancedParseResult<T> {
@@ -97,6 +167,9 @@ impl<T> EnhancedParseResult<T> {
             errors: Vec::new(),
             repairs: Vec::new(),
             parsing_tier: tier,
+            discarded_trailing_bytes: 0,
+            warnings: Vec::new(),
+            compat_warnings: Vec::new(),
         }
     }
 
@@ -105,8 +178,11 @@ impl<T> EnhancedParseResult<T> {
         Self {
             value,
             errors: Vec::new(),
+            discarded_trailing_bytes: discarded_trailing_bytes(&repairs),
             repairs,
             parsing_tier: tier,
+            warnings: Vec::new(),
+            compat_warnings: Vec::new(),
         }
     }
 
@@ -117,6 +193,9 @@ impl<T> EnhancedParseResult<T> {
             errors,
             repairs: Vec::new(),
             parsing_tier: tier,
+            discarded_trailing_bytes: 0,
+            warnings: Vec::new(),
+            compat_warnings: Vec::new(),
         }
     }
 
@@ -130,11 +209,26 @@ impl<T> EnhancedParseResult<T> {
         Self {
             value,
             errors,
+            discarded_trailing_bytes: discarded_trailing_bytes(&repairs),
             repairs,
             parsing_tier: tier,
+            warnings: Vec::new(),
+            compat_warnings: Vec::new(),
         }
     }
 
+    /// Attaches warnings collected while parsing
+    pub fn with_warnings(mut self, warnings: Vec<Warning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Attaches compatibility warnings collected while parsing
+    pub fn with_compat_warnings(mut self, compat_warnings: Vec<CompatWarning>) -> Self {
+        self.compat_warnings = compat_warnings;
+        self
+    }
+
     /// Returns true if parsing was successful (no errors)
     #[inline(always)]
     pub fn is_success(&self) -> bool {
@@ -146,6 +240,11 @@ impl<T> EnhancedParseResult<T> {
         !self.repairs.is_empty()
     }
 
+    /// Returns true if repair dropped input after the end of the document
+    pub fn discarded_trailing_input(&self) -> bool {
+        self.discarded_trailing_bytes > 0
+    }
+
     /// Returns the parsing tier that was used
     pub fn get_performance_tier(&self) -> ParsingTier {
         self.parsing_tier
@@ -194,3 +293,47 @@ where
         }
     }
 }
+
+/// Makes `repairs`, sorted by position, in `input`, or returns `None` if
+/// they overlap or do not match it.
+pub(crate) fn apply_repairs(input: &str, repairs: &[&RepairAction]) -> Option<String> {
+    let mut text = String::with_capacity(input.len());
+    let mut cursor = 0;
+    for repair in repairs {
+        let end = repair.position.checked_add(repair.original.len())?;
+        if repair.position < cursor || input.get(repair.position..end)? != repair.original {
+            return None;
+        }
+        text.push_str(&input[cursor..repair.position]);
+        text.push_str(&repair.replacement);
+        cursor = end;
+    }
+    text.push_str(&input[cursor..]);
+    Some(text)
+}
+
+/// Maps `position` in the text [`apply_repairs`] made back to the input,
+/// taking a position inside a replacement to the start of the repair.
+pub(crate) fn original_position(repairs: &[&RepairAction], position: usize) -> usize {
+    let mut shift = 0isize;
+    for repair in repairs {
+        let start = repair.position.saturating_add_signed(shift);
+        if position < start {
+            break;
+        }
+        if position < start + repair.replacement.len().max(1) {
+            return repair.position;
+        }
+        shift += repair.replacement.len() as isize - repair.original.len() as isize;
+    }
+    position.saturating_add_signed(-shift)
+}
+
+/// Sums the bytes removed by [`RepairType::DiscardTrailing`] actions.
+fn discarded_trailing_bytes(repairs: &[RepairAction]) -> usize {
+    repairs
+        .iter()
+        .filter(|action| action.action_type == RepairType::DiscardTrailing)
+        .map(|action| action.original.len())
+        .sum()
+}
@@ -8,7 +8,7 @@
 
 use crate::error::recovery::ErrorRecoveryAnalyzer;
 use crate::error::terminal::{ColorScheme, TerminalFormatter};
-use crate::error::{Error, Span};
+use crate::error::{Error, Span, DEFAULT_CONTEXT_WIDTH};
 
 /// Configuration for error report formatting and content.
 #[derive(Debug, Clone)]
@@ -17,6 +17,9 @@ pub struct ReportConfig {
     pub include_context: bool,
     /// Number of context lines to show before and after the error
     pub context_lines: usize,
+    /// Most characters of each context line to show either side of the
+    /// error's column; longer lines are cut short with `…`
+    pub context_width: usize,
     /// Whether to include error code and description
     pub include_error_code: bool,
     /// Whether to include suggestions for fixing the error
@@ -34,6 +37,7 @@ impl Default for ReportConfig {
         Self {
             include_context: true,
             context_lines: 2,
+            context_width: DEFAULT_CONTEXT_WIDTH,
             include_error_code: true,
             include_suggestions: true,
             include_recovery: true,
@@ -49,6 +53,7 @@ impl ReportConfig {
         Self {
             include_context: false,
             context_lines: 0,
+            context_width: DEFAULT_CONTEXT_WIDTH,
             include_error_code: false,
             include_suggestions: false,
             include_recovery: false,
@@ -62,6 +67,7 @@ impl ReportConfig {
         Self {
             include_context: true,
             context_lines: 3,
+            context_width: DEFAULT_CONTEXT_WIDTH,
             include_error_code: true,
             include_suggestions: true,
             include_recovery: true,
@@ -82,6 +88,13 @@ impl ReportConfig {
         self
     }
 
+    /// Sets how many characters of a long line to show either side of the
+    /// error's column.
+    pub fn with_context_width(mut self, width: usize) -> Self {
+        self.context_width = width;
+        self
+    }
+
     /// Sets the maximum line width for text wrapping.
     pub fn with_max_width(mut self, width: usize) -> Self {
         self.max_line_width = width;
@@ -181,7 +194,11 @@ impl ErrorReporter {
 
     /// Adds the context section showing the error location in the input.
     fn add_context_section(&self, report: &mut String, span: &Span, input: &str) {
-        let context = span.context_window(input, self.config.context_lines);
+        let context = span.context_window_with_width(
+            input,
+            self.config.context_lines,
+            self.config.context_width,
+        );
 
         if !context.lines.is_empty() {
             report.push('\n');
@@ -3,12 +3,21 @@
 //! Span types for precise error location reporting with line/column information.
 
 use crate::error::terminal::{ColorScheme, TerminalFormatter};
+use crate::error::unicode::char_width;
+use std::borrow::Cow;
+
+/// Characters shown either side of an error's column when its line is long.
+pub const DEFAULT_CONTEXT_WIDTH: usize = 120;
+
+/// Columns a tab advances in printed context.
+const TAB_WIDTH: usize = 4;
 
 /// Represents a span of text in the input for error reporting.
 ///
 /// Spans provide precise location information including both byte positions
 /// and line/column coordinates for better error reporting and debugging.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Span {
     /// Start position in the input (byte offset)
     pub start: usize,
@@ -37,15 +46,21 @@ pub struct EnhancedSpan {
 }
 
 /// Context window showing lines around an error location.
+///
+/// Lines longer than the window's width are cut down to the part around the
+/// error's column, with `…` marking where text was left out.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContextWindow {
     /// Lines of context around the error
     pub lines: Vec<String>,
+    /// Line number of the first of `lines` in the input (1-based)
+    pub first_line: usize,
     /// Index of the line containing the error (0-based within context)
     pub error_line: usize,
-    /// Column where the error starts (1-based)
+    /// Column where the error starts, in characters of its excerpted line
+    /// (1-based)
     pub error_column: usize,
-    /// Length of the error span
+    /// Length in characters of the part of the error span on its first line
     pub error_length: usize,
 }
 
@@ -101,32 +116,80 @@ impl Span {
     }
 
     /// Extracts the text covered by this span from the input.
+    ///
+    /// A span running past the end of `input` is cut short at its end.
+    ///
+    /// # Panics
+    ///
+    /// If the span starts after it ends, or either end falls inside a
+    /// multi-byte character. Use [`Span::try_extract`] for spans that may
+    /// come from a different input, such as one before transcoding.
     pub fn extract<'a>(&self, input: &'a str) -> &'a str {
         let start = self.start.min(input.len());
         let end = self.end.min(input.len());
         &input[start..end]
     }
 
-    /// Returns a context window around this span.
+    /// Extracts the text covered by this span, if it is a valid slice of `input`.
+    ///
+    /// Returns `None` rather than panicking when the span runs past the end
+    /// of `input`, starts after it ends, or splits a multi-byte character.
+    pub fn try_extract<'a>(&self, input: &'a str) -> Option<&'a str> {
+        input.get(self.start..self.end)
+    }
+
+    /// Returns a context window around this span, showing at most
+    /// [`DEFAULT_CONTEXT_WIDTH`] characters either side of its start.
+    ///
+    /// Any span gives a window, even one from another input: it is first
+    /// cut short at the end of `input`, and widened to whole characters.
+    /// An error just after a final line break is shown on an empty line of
+    /// its own.
     pub fn context_window(&self, input: &str, context_size: usize) -> ContextWindow {
-        let start_line = byte_to_line_col(input, self.start);
-        let end_line = byte_to_line_col(input, self.end);
+        self.context_window_with_width(input, context_size, DEFAULT_CONTEXT_WIDTH)
+    }
+
+    /// Like [`Span::context_window`], showing at most `width` characters of
+    /// each line either side of the span's start.
+    ///
+    /// Every line is cut to the same columns, so that they stay lined up.
+    pub fn context_window_with_width(
+        &self,
+        input: &str,
+        context_size: usize,
+        width: usize,
+    ) -> ContextWindow {
+        let start = floor_char_boundary(input, self.start);
+        let end = ceil_char_boundary(input, self.end).max(start);
+        let start_line = byte_to_line_col(input, start);
+        let end_line = byte_to_line_col(input, end);
 
         // Get lines around the error
-        let lines: Vec<&str> = input.lines().collect();
+        let mut lines: Vec<&str> = input.lines().collect();
+        if start_line.line > lines.len() {
+            lines.push("");
+        }
         let context_start = start_line.line.saturating_sub(context_size + 1);
         let context_end = (end_line.line + context_size).min(lines.len());
 
+        let (first, error_index, last) =
+            excerpt_bounds(lines[start_line.line - 1], start_line.column, width);
         let context_lines: Vec<String> = lines[context_start..context_end]
             .iter()
-            .map(|s| s.to_string())
+            .map(|line| excerpt(line, first, last))
             .collect();
+        let error_text = input[start..end].split('\n').next().unwrap_or_default();
 
         ContextWindow {
             lines: context_lines,
+            first_line: context_start + 1,
             error_line: start_line.line - context_start - 1,
-            error_column: start_line.column,
-            error_length: self.len(),
+            error_column: error_index - first + 1 + usize::from(first > 0),
+            error_length: error_text
+                .trim_end_matches('\r')
+                .chars()
+                .count()
+                .min(last.saturating_sub(error_index)),
         }
     }
 }
@@ -176,28 +239,24 @@ impl ContextWindow {
 
         for (i, line) in self.lines.iter().enumerate() {
             // Add line number
-            result.push_str(&format!("{:4} | ", i + 1));
+            result.push_str(&format!("{:4} | ", self.first_line + i));
 
             if i == self.error_line {
                 // This is the error line - add highlighting
-                let before = &line[..self.error_column.saturating_sub(1)];
-                let error_start = self.error_column.saturating_sub(1);
-                let error_end = (error_start + self.error_length).min(line.len());
-                let error_text = &line[error_start..error_end];
-                let after = &line[error_end..];
-
-                result.push_str(before);
-                result.push_str(&format!(">>{error_text}<<")); // Simple highlighting
-                result.push_str(after);
+                let (before, error_text, after) = self.split_error_line(line);
+
+                result.push_str(&expand_tabs(before));
+                result.push_str(&format!(">>{}<<", expand_tabs(error_text))); // Simple highlighting
+                result.push_str(&expand_tabs(after));
                 result.push('\n');
 
                 // Add error pointer line
                 result.push_str("     | ");
-                result.push_str(&" ".repeat(self.error_column.saturating_sub(1)));
-                result.push_str(&"^".repeat(self.error_length.max(1)));
+                result.push_str(&" ".repeat(display_width(before)));
+                result.push_str(&"^".repeat(display_width(error_text).max(1)));
                 result.push('\n');
             } else {
-                result.push_str(line);
+                result.push_str(&expand_tabs(line));
                 result.push('\n');
             }
         }
@@ -213,34 +272,31 @@ impl ContextWindow {
         let mut result = String::new();
 
         for (i, line) in self.lines.iter().enumerate() {
-            let line_num = i + 1;
+            let line_num = self.first_line + i;
 
             if i == self.error_line {
                 // This is the error line - add colored highlighting
-                let before = &line[..self.error_column.saturating_sub(1)];
-                let error_start = self.error_column.saturating_sub(1);
-                let error_end = (error_start + self.error_length).min(line.len());
-                let error_text = &line[error_start..error_end];
-                let after = &line[error_end..];
+                let (before, error_text, after) = self.split_error_line(line);
 
                 // Format line number with error style
                 result.push_str(&formatter.format_line_number(line_num));
 
                 // Format the line with error highlighting
-                result.push_str(before);
-                result.push_str(&formatter.colorize(error_text, ColorScheme::Error));
-                result.push_str(after);
+                result.push_str(&expand_tabs(before));
+                result.push_str(&formatter.colorize(&expand_tabs(error_text), ColorScheme::Error));
+                result.push_str(&expand_tabs(after));
                 result.push('\n');
 
                 // Add error pointer line with colored arrows
-                result.push_str(
-                    &formatter.format_error_pointer(self.error_column, self.error_length.max(1)),
-                );
+                result.push_str(&formatter.format_error_pointer(
+                    display_width(before) + 1,
+                    display_width(error_text).max(1),
+                ));
                 result.push('\n');
             } else {
                 // Regular context line
                 result.push_str(&formatter.format_line_number(line_num));
-                result.push_str(line);
+                result.push_str(&expand_tabs(line));
                 result.push('\n');
             }
         }
@@ -265,6 +321,119 @@ impl ContextWindow {
         let formatter = TerminalFormatter::without_colors();
         self.format_with_formatter(&formatter)
     }
+
+    /// Splits the error line into the text before the error, the error and
+    /// the text after it, by character so as never to split one.
+    fn split_error_line<'a>(&self, line: &'a str) -> (&'a str, &'a str, &'a str) {
+        let byte_at = |chars: usize| {
+            line.char_indices()
+                .nth(chars)
+                .map_or(line.len(), |(index, _)| index)
+        };
+        let error_start = byte_at(self.error_column.saturating_sub(1));
+        let error_end = byte_at(self.error_column.saturating_sub(1) + self.error_length);
+        (
+            &line[..error_start],
+            &line[error_start..error_end],
+            &line[error_end..],
+        )
+    }
+}
+
+/// Cuts `line` down to at most `width` characters either side of `column`
+/// (1-based, in characters), marking each end where text was left out with
+/// `…`, so that an error in a long minified document can be shown without
+/// printing the whole of it.
+///
+/// Returns the excerpt and the column of the same character within it.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::error::span::excerpt_line;
+///
+/// assert_eq!(excerpt_line("[1, 2, 3, @, 5]", 11, 3), ("…3, @, 5…".to_string(), 5));
+/// assert_eq!(excerpt_line("[@]", 2, 3), ("[@]".to_string(), 2));
+/// ```
+pub fn excerpt_line(line: &str, column: usize, width: usize) -> (String, usize) {
+    let (first, index, last) = excerpt_bounds(line, column, width);
+    (
+        excerpt(line, first, last),
+        index - first + 1 + usize::from(first > 0),
+    )
+}
+
+/// Returns how many columns `text` takes up when printed as context: a
+/// tab counts as four, and wide characters such as CJK ideographs as two.
+///
+/// This is how many spaces go before a caret pointing just past `text`.
+pub fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|ch| {
+            if ch == '\t' {
+                TAB_WIDTH
+            } else {
+                char_width(ch)
+            }
+        })
+        .sum()
+}
+
+/// `text` with its tabs as spaces, so that it lines up with a caret below.
+pub fn expand_tabs(text: &str) -> Cow<'_, str> {
+    if text.contains('\t') {
+        Cow::Owned(text.replace('\t', &" ".repeat(TAB_WIDTH)))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// The range of characters of `line` an excerpt around `column` shows, up
+/// to `width` either side of it, and the index of the character at
+/// `column`, taken no further than just past the end of `line`.
+fn excerpt_bounds(line: &str, column: usize, width: usize) -> (usize, usize, usize) {
+    let index = column.saturating_sub(1).min(line.chars().count());
+    (index.saturating_sub(width), index, index + width + 1)
+}
+
+/// The characters `first..last` of `line`, with `…` at either end where
+/// some of it was left out.
+fn excerpt(line: &str, first: usize, last: usize) -> String {
+    let byte_at = |from: usize, chars: usize| {
+        line[from..]
+            .char_indices()
+            .nth(chars)
+            .map_or(line.len(), |(index, _)| from + index)
+    };
+    let start = byte_at(0, first);
+    let end = byte_at(start, last - first);
+    let mut excerpt = String::with_capacity(end - start + 6);
+    if start > 0 {
+        excerpt.push('…');
+    }
+    excerpt.push_str(&line[start..end]);
+    if end < line.len() {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+/// The start of the character at or before `position`, within `input`.
+fn floor_char_boundary(input: &str, position: usize) -> usize {
+    let mut position = position.min(input.len());
+    while !input.is_char_boundary(position) {
+        position -= 1;
+    }
+    position
+}
+
+/// The end of the character containing `position`, within `input`.
+fn ceil_char_boundary(input: &str, position: usize) -> usize {
+    let mut position = position.min(input.len());
+    while !input.is_char_boundary(position) {
+        position += 1;
+    }
+    position
 }
 
 /// Converts a byte position to line/column coordinates.
@@ -356,4 +525,19 @@ mod tests {
         let span = Span::new(6, 11); // "world"
         assert_eq!(span.extract(input), "world");
     }
+
+    #[test]
+    fn test_span_try_extract() {
+        let input = "héllo";
+        assert_eq!(Span::new(0, 3).try_extract(input), Some("hé"));
+        assert_eq!(Span::new(6, 6).try_extract(input), Some(""));
+        // Past the end
+        assert_eq!(Span::new(3, 12).try_extract(input), None);
+        assert_eq!(Span::new(7, 8).try_extract(input), None);
+        // Inside the two bytes of 'é'
+        assert_eq!(Span::new(2, 4).try_extract(input), None);
+        assert_eq!(Span::new(0, 2).try_extract(input), None);
+        // Reversed
+        assert_eq!(Span::new(4, 3).try_extract(input), None);
+    }
 }
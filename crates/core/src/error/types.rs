@@ -1,7 +1,10 @@
 // this_file: src/error/types.rs
 
+use crate::ast::JsonPath;
 use crate::error::terminal::{ColorScheme, TerminalFormatter};
+use crate::error::unicode::describe_char;
 use crate::error::Span;
+use std::borrow::Cow;
 use thiserror::Error;
 
 /// Structured error codes for programmatic error handling.
@@ -44,6 +47,16 @@ pub enum ErrorCode {
     InvalidUtf8,
     /// E1017: Invalid chunk for parallel processing
     InvalidChunk,
+    /// E1018: Object key repeated
+    DuplicateKey,
+    /// E1019: Configured size limit exceeded
+    LimitExceeded,
+    /// E1020: Parse budget exhausted or parse cancelled
+    BudgetExceeded,
+    /// E1021: Input could not be read
+    Io,
+    /// E1022: Object key empty or only whitespace
+    BlankKey,
 }
 
 impl ErrorCode {
@@ -67,6 +80,11 @@ impl ErrorCode {
             ErrorCode::MaxRepairsExceeded => "E1015",
             ErrorCode::InvalidUtf8 => "E1016",
             ErrorCode::InvalidChunk => "E1017",
+            ErrorCode::DuplicateKey => "E1018",
+            ErrorCode::LimitExceeded => "E1019",
+            ErrorCode::BudgetExceeded => "E1020",
+            ErrorCode::Io => "E1021",
+            ErrorCode::BlankKey => "E1022",
         }
     }
 
@@ -90,6 +108,11 @@ impl ErrorCode {
             ErrorCode::MaxRepairsExceeded => "Maximum repair attempts exceeded",
             ErrorCode::InvalidUtf8 => "Invalid UTF-8 sequence",
             ErrorCode::InvalidChunk => "Invalid chunk for parallel processing",
+            ErrorCode::DuplicateKey => "Object key repeated",
+            ErrorCode::LimitExceeded => "Configured size limit exceeded",
+            ErrorCode::BudgetExceeded => "Parse budget exhausted or parse cancelled",
+            ErrorCode::Io => "Input could not be read",
+            ErrorCode::BlankKey => "Object key empty or only whitespace",
         }
     }
 
@@ -198,6 +221,31 @@ impl ErrorCode {
                 "Try sequential parsing instead of parallel",
                 "Check for corrupted input data",
             ],
+            ErrorCode::DuplicateKey => vec![
+                "Remove or rename one of the duplicate keys",
+                "Merge the values if appropriate",
+                "Use an array if multiple values are intended",
+                "Choose a different duplicate key policy in the parser options",
+            ],
+            ErrorCode::LimitExceeded => vec![
+                "Check that the input is the document you expected",
+                "Raise the limit in the parser options if the input is trusted",
+                "Split large values into smaller ones",
+            ],
+            ErrorCode::BudgetExceeded => vec![
+                "Check that the input is the document you expected",
+                "Allow more time or memory in the parse budget if the input is trusted",
+                "Use the streaming parser for very large inputs",
+            ],
+            ErrorCode::Io => vec![
+                "Check that the file exists and is readable",
+                "Check the path for typos",
+            ],
+            ErrorCode::BlankKey => vec![
+                "Check whether the input was truncated or corrupted",
+                "Give the member a descriptive key",
+                "Disable reject_blank_keys in the parser options if blank keys are expected",
+            ],
         }
     }
 }
@@ -217,7 +265,8 @@ impl std::fmt::Display for ErrorCode {
 pub enum Error {
     /// Unexpected character encountered during parsing.
     /// Contains the character and its position in the input.
-    #[error("Unexpected character '{0}' at position {1}")]
+    /// Invisible characters are shown by code point and name.
+    #[error("Unexpected character {ch} at position {pos}", ch = describe_char(*.0), pos = .1)]
     UnexpectedChar(char, usize),
 
     /// Unexpected end of input while parsing.
@@ -252,7 +301,11 @@ pub enum Error {
 
     /// Expected a specific token or value but found something else.
     /// This is the most flexible error type for parser expectations.
-    #[error("Expected {expected} but found {found} at position {position}")]
+    /// The message shows no more than the first 80 characters of `found`.
+    #[error(
+        "Expected {expected} but found {found} at position {position}",
+        found = shorten_found(.found)
+    )]
     Expected {
         /// Description of what was expected.
         expected: String,
@@ -282,6 +335,29 @@ pub enum Error {
         source: Box<Error>,
     },
 
+    /// An error that covers a range of the input rather than a single position,
+    /// such as a member skipped by best-effort parsing.
+    #[error("{source}")]
+    Spanned {
+        /// The range of input the error applies to.
+        span: Span,
+        /// The underlying error.
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// An error annotated with where in the document it occurred, reported
+    /// when [`ParserOptions::track_paths`](crate::ParserOptions::track_paths)
+    /// is enabled.
+    #[error("{source} at path {path}")]
+    AtPath {
+        /// Path from the document root to the value being parsed.
+        path: JsonPath,
+        /// The underlying error.
+        #[source]
+        source: Box<Error>,
+    },
+
     /// JSON repair failed with the given error message.
     #[error("JSON repair failed: {0}")]
     RepairFailed(String),
@@ -305,6 +381,92 @@ pub enum Error {
     /// Invalid chunk detected during parallel processing.
     #[error("Invalid chunk: {0}")]
     InvalidChunk(String),
+
+    /// An object key appeared more than once and
+    /// [`ParserOptions::duplicate_keys`](crate::ParserOptions::duplicate_keys)
+    /// rejects duplicates.
+    #[error("Duplicate key {key:?} at position {position}")]
+    DuplicateKey {
+        /// The repeated key.
+        key: String,
+        /// Position of the repeated occurrence.
+        position: usize,
+    },
+
+    /// An object key was empty or only whitespace and
+    /// [`ParserOptions::reject_blank_keys`](crate::ParserOptions::reject_blank_keys)
+    /// is enabled.
+    #[error("Blank key {key:?} at position {position}")]
+    BlankKey {
+        /// The key as written, after unescaping.
+        key: String,
+        /// Position of the key.
+        position: usize,
+    },
+
+    /// A value exceeded a size limit set in the parser options.
+    #[error("{what} exceeds the limit of {limit} at position {position}")]
+    LimitExceeded {
+        /// What was measured, such as `string length`.
+        what: String,
+        /// The configured limit.
+        limit: usize,
+        /// Position of the value that exceeded it.
+        position: usize,
+    },
+
+    /// A parse ran out of its [`ParseBudget`](crate::ParseBudget) or was
+    /// cancelled.
+    #[error("Parse stopped at position {position}: {limit}")]
+    BudgetExceeded {
+        /// Which limit stopped the parse.
+        limit: BudgetLimit,
+        /// How far into the input parsing got.
+        position: usize,
+    },
+
+    /// Reading the input failed.
+    #[error("I/O error: {message}")]
+    Io {
+        /// What kind of failure it was.
+        kind: std::io::ErrorKind,
+        /// Description of the failure, naming the file if there is one.
+        message: String,
+    },
+}
+
+/// The limit of a [`ParseBudget`](crate::ParseBudget) that stopped a parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BudgetLimit {
+    /// The deadline passed.
+    Deadline,
+    /// The parsed value grew past the memory limit.
+    Memory,
+    /// The cancel flag was set.
+    Cancelled,
+}
+
+impl std::fmt::Display for BudgetLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BudgetLimit::Deadline => "deadline passed",
+            BudgetLimit::Memory => "memory limit exceeded",
+            BudgetLimit::Cancelled => "cancelled",
+        })
+    }
+}
+
+/// Most characters of the found text an [`Error::Expected`] message shows.
+const MAX_FOUND_CHARS: usize = 80;
+
+/// Cuts `found` short at [`MAX_FOUND_CHARS`] characters, ending it with `…`,
+/// so that a huge token cannot swamp an error message. Shortening twice
+/// gives the same text.
+pub(crate) fn shorten_found(found: &str) -> Cow<'_, str> {
+    match found.char_indices().nth(MAX_FOUND_CHARS) {
+        Some((end, _)) => Cow::Owned(format!("{}…", &found[..end])),
+        None => Cow::Borrowed(found),
+    }
 }
 
 impl Error {
@@ -326,12 +488,18 @@ impl Error {
             Error::DepthLimitExceeded(_) => ErrorCode::DepthLimitExceeded,
             Error::Custom(_) => ErrorCode::Custom,
             Error::WithContext { .. } => ErrorCode::WithContext,
+            Error::Spanned { source, .. } | Error::AtPath { source, .. } => source.code(),
             Error::RepairFailed(_) => ErrorCode::RepairFailed,
             Error::BracketMismatch(_, _, _) => ErrorCode::BracketMismatch,
             Error::UnbalancedBrackets(_, _) => ErrorCode::UnbalancedBrackets,
             Error::MaxRepairsExceeded(_) => ErrorCode::MaxRepairsExceeded,
             Error::InvalidUtf8(_) => ErrorCode::InvalidUtf8,
             Error::InvalidChunk(_) => ErrorCode::InvalidChunk,
+            Error::DuplicateKey { .. } => ErrorCode::DuplicateKey,
+            Error::BlankKey { .. } => ErrorCode::BlankKey,
+            Error::LimitExceeded { .. } => ErrorCode::LimitExceeded,
+            Error::BudgetExceeded { .. } => ErrorCode::BudgetExceeded,
+            Error::Io { .. } => ErrorCode::Io,
         }
     }
 
@@ -352,13 +520,19 @@ impl Error {
             | Error::Expected { position: pos, .. }
             | Error::DepthLimitExceeded(pos)
             | Error::BracketMismatch(pos, _, _)
-            | Error::InvalidUtf8(pos) => Some(*pos),
-            Error::WithContext { source, .. } => source.position(),
+            | Error::InvalidUtf8(pos)
+            | Error::DuplicateKey { position: pos, .. }
+            | Error::BlankKey { position: pos, .. }
+            | Error::LimitExceeded { position: pos, .. }
+            | Error::BudgetExceeded { position: pos, .. } => Some(*pos),
+            Error::WithContext { source, .. } | Error::AtPath { source, .. } => source.position(),
+            Error::Spanned { span, .. } => Some(span.start),
             Error::Custom(_)
             | Error::RepairFailed(_)
             | Error::UnbalancedBrackets(_, _)
             | Error::MaxRepairsExceeded(_)
-            | Error::InvalidChunk(_) => None,
+            | Error::InvalidChunk(_)
+            | Error::Io { .. } => None,
         }
     }
 
@@ -367,7 +541,21 @@ impl Error {
     /// This provides more precise location information than just a position,
     /// including the range of characters that caused the error.
     pub fn span(&self) -> Option<Span> {
-        self.position().map(Span::single)
+        match self {
+            Error::Spanned { span, .. } => Some(*span),
+            Error::WithContext { source, .. } | Error::AtPath { source, .. } => source.span(),
+            _ => self.position().map(Span::single),
+        }
+    }
+
+    /// Returns the document path at which the error occurred, if it was
+    /// tracked.
+    pub fn path(&self) -> Option<&JsonPath> {
+        match self {
+            Error::AtPath { path, .. } => Some(path),
+            Error::WithContext { source, .. } | Error::Spanned { source, .. } => source.path(),
+            _ => None,
+        }
     }
 
     /// Returns suggestions for fixing this error.
@@ -459,6 +647,14 @@ impl Error {
         }
     }
 
+    /// Attaches the range of input this error applies to.
+    pub fn with_span(self, span: Span) -> Self {
+        Error::Spanned {
+            span,
+            source: Box::new(self),
+        }
+    }
+
     /// Creates a new error with a custom context message and span.
     ///
     /// This is useful when you need to provide additional context about
@@ -482,6 +678,59 @@ impl Error {
         )
     }
 
+    /// Creates an [`Error::Io`] for a failure to read `path`.
+    pub(crate) fn io(path: &std::path::Path, error: std::io::Error) -> Self {
+        Error::Io {
+            kind: error.kind(),
+            message: format!("{}: {error}", path.display()),
+        }
+    }
+
+    /// Moves this error `offset` bytes later, for an error found in a slice
+    /// of the input that starts at `offset`.
+    pub(crate) fn offset_by(self, offset: usize) -> Self {
+        self.map_positions(&|position| position + offset)
+    }
+
+    /// Moves every position in this error through `map`, for an error found
+    /// in text derived from the input.
+    pub(crate) fn map_positions(mut self, map: &impl Fn(usize) -> usize) -> Self {
+        self.remap(map);
+        self
+    }
+
+    fn remap(&mut self, map: &impl Fn(usize) -> usize) {
+        match self {
+            Error::UnexpectedChar(_, pos)
+            | Error::UnexpectedEof(pos)
+            | Error::InvalidNumber(pos)
+            | Error::InvalidEscape(pos)
+            | Error::InvalidUnicode(pos)
+            | Error::UnterminatedString(pos)
+            | Error::TrailingComma(pos)
+            | Error::Expected { position: pos, .. }
+            | Error::DepthLimitExceeded(pos)
+            | Error::BracketMismatch(pos, _, _)
+            | Error::InvalidUtf8(pos)
+            | Error::DuplicateKey { position: pos, .. }
+            | Error::BlankKey { position: pos, .. }
+            | Error::LimitExceeded { position: pos, .. }
+            | Error::BudgetExceeded { position: pos, .. } => *pos = map(*pos),
+            Error::Spanned { span, source } => {
+                span.start = map(span.start);
+                span.end = map(span.end);
+                source.remap(map);
+            }
+            Error::WithContext { source, .. } | Error::AtPath { source, .. } => source.remap(map),
+            Error::Custom(_)
+            | Error::RepairFailed(_)
+            | Error::UnbalancedBrackets(_, _)
+            | Error::MaxRepairsExceeded(_)
+            | Error::InvalidChunk(_)
+            | Error::Io { .. } => {}
+        }
+    }
+
     /// Checks if this error is related to number parsing.
     ///
     /// Useful for error categorization and specialized error handling.
@@ -702,6 +951,12 @@ mod tests {
         let error = Error::UnexpectedChar('x', 5);
         assert_eq!(error.to_string(), "Unexpected character 'x' at position 5");
 
+        let error = Error::UnexpectedChar('\u{200B}', 7);
+        assert_eq!(
+            error.to_string(),
+            "Unexpected character U+200B ZERO WIDTH SPACE at position 7"
+        );
+
         let error = Error::InvalidNumber(10);
         assert_eq!(error.to_string(), "Invalid number format at position 10");
 
@@ -712,6 +967,22 @@ mod tests {
         };
         assert_eq!(error.to_string(), "Expected value but found EOF at position 15");
 
+        let error = Error::Expected {
+            expected: "a number within the range of f64".to_string(),
+            found: "9".repeat(10_000),
+            position: 0,
+        };
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Expected a number within the range of f64 but found {}… at position 0",
+                "9".repeat(80)
+            )
+        );
+        let found = "é".repeat(81);
+        let shortened = shorten_found(&found);
+        assert_eq!(shorten_found(&shortened), shortened);
+
         let error = Error::Custom("Custom error message".to_string());
         assert_eq!(error.to_string(), "Custom error: Custom error message");
     }
@@ -0,0 +1,143 @@
+// this_file: crates/core/src/error/unicode.rs
+
+//! Names for invisible and easily confused Unicode characters.
+//!
+//! Text pasted from web pages and word processors often carries characters
+//! that render as nothing or as an ordinary space. Errors name them by code
+//! point so that users can find what the parser tripped over.
+
+/// Notable characters and their Unicode names, sorted by code point.
+const NOTABLE_CHARS: &[(char, &str)] = &[
+    ('\u{00A0}', "NO-BREAK SPACE"),
+    ('\u{00AD}', "SOFT HYPHEN"),
+    ('\u{034F}', "COMBINING GRAPHEME JOINER"),
+    ('\u{061C}', "ARABIC LETTER MARK"),
+    ('\u{180E}', "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{2000}', "EN QUAD"),
+    ('\u{2001}', "EM QUAD"),
+    ('\u{2002}', "EN SPACE"),
+    ('\u{2003}', "EM SPACE"),
+    ('\u{2004}', "THREE-PER-EM SPACE"),
+    ('\u{2005}', "FOUR-PER-EM SPACE"),
+    ('\u{2006}', "SIX-PER-EM SPACE"),
+    ('\u{2007}', "FIGURE SPACE"),
+    ('\u{2008}', "PUNCTUATION SPACE"),
+    ('\u{2009}', "THIN SPACE"),
+    ('\u{200A}', "HAIR SPACE"),
+    ('\u{200B}', "ZERO WIDTH SPACE"),
+    ('\u{200C}', "ZERO WIDTH NON-JOINER"),
+    ('\u{200D}', "ZERO WIDTH JOINER"),
+    ('\u{200E}', "LEFT-TO-RIGHT MARK"),
+    ('\u{200F}', "RIGHT-TO-LEFT MARK"),
+    ('\u{2028}', "LINE SEPARATOR"),
+    ('\u{2029}', "PARAGRAPH SEPARATOR"),
+    ('\u{202A}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202B}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202C}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{202D}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202E}', "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{202F}', "NARROW NO-BREAK SPACE"),
+    ('\u{205F}', "MEDIUM MATHEMATICAL SPACE"),
+    ('\u{2060}', "WORD JOINER"),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', "FIRST STRONG ISOLATE"),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+    ('\u{3000}', "IDEOGRAPHIC SPACE"),
+    ('\u{3164}', "HANGUL FILLER"),
+    ('\u{FEFF}', "ZERO WIDTH NO-BREAK SPACE"),
+    ('\u{FFFD}', "REPLACEMENT CHARACTER"),
+];
+
+/// Returns the Unicode name of `ch` if it is invisible or easily mistaken
+/// for an ordinary space.
+pub fn char_name(ch: char) -> Option<&'static str> {
+    NOTABLE_CHARS
+        .binary_search_by(|(notable, _)| notable.cmp(&ch))
+        .ok()
+        .map(|index| NOTABLE_CHARS[index].1)
+}
+
+/// Describes `ch` for an error message: quoted if it is visible, otherwise
+/// by code point, and by name where known (`U+200B ZERO WIDTH SPACE`).
+pub fn describe_char(ch: char) -> String {
+    match char_name(ch) {
+        Some(name) => format!("U+{:04X} {name}", u32::from(ch)),
+        None if ch.is_control() => format!("U+{:04X}", u32::from(ch)),
+        None => format!("'{ch}'"),
+    }
+}
+
+/// Ranges of characters a terminal draws two columns wide.
+const WIDE_RANGES: &[(char, char)] = &[
+    ('\u{1100}', '\u{115F}'),
+    ('\u{2E80}', '\u{303E}'),
+    ('\u{3041}', '\u{33FF}'),
+    ('\u{3400}', '\u{4DBF}'),
+    ('\u{4E00}', '\u{9FFF}'),
+    ('\u{A000}', '\u{A4CF}'),
+    ('\u{AC00}', '\u{D7A3}'),
+    ('\u{F900}', '\u{FAFF}'),
+    ('\u{FE30}', '\u{FE4F}'),
+    ('\u{FF00}', '\u{FF60}'),
+    ('\u{FFE0}', '\u{FFE6}'),
+    ('\u{1F300}', '\u{1F64F}'),
+    ('\u{1F900}', '\u{1F9FF}'),
+    ('\u{20000}', '\u{3FFFD}'),
+];
+
+/// Returns how many terminal columns `ch` takes up: none for combining
+/// marks, control characters and the zero-width characters, two for CJK
+/// ideographs, fullwidth forms and most emoji, and one otherwise.
+///
+/// An approximation good enough to line a caret up under a character.
+pub fn char_width(ch: char) -> usize {
+    let zero_width = ch.is_control()
+        || matches!(
+            ch,
+            '\u{0300}'..='\u{036F}'
+                | '\u{200B}'..='\u{200F}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{FE00}'..='\u{FE0F}'
+                | '\u{FEFF}'
+        );
+    if zero_width {
+        0
+    } else if WIDE_RANGES
+        .iter()
+        .any(|&(first, last)| (first..=last).contains(&ch))
+    {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notable_chars_sorted() {
+        assert!(NOTABLE_CHARS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn test_describe_char() {
+        assert_eq!(describe_char('x'), "'x'");
+        assert_eq!(describe_char('é'), "'é'");
+        assert_eq!(describe_char('\u{200B}'), "U+200B ZERO WIDTH SPACE");
+        assert_eq!(describe_char('\u{A0}'), "U+00A0 NO-BREAK SPACE");
+        assert_eq!(describe_char('\u{7}'), "U+0007");
+    }
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('é'), 1);
+        assert_eq!(char_width('日'), 2);
+        assert_eq!(char_width('😀'), 2);
+        assert_eq!(char_width('\u{0301}'), 0);
+        assert_eq!(char_width('\u{200B}'), 0);
+    }
+}
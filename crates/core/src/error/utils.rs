@@ -36,6 +36,7 @@ impl ErrorHelper {
             Error::TrailingComma(_) => ErrorSeverity::Low,
             Error::Custom(_) => ErrorSeverity::Low,
             Error::WithContext { .. } => ErrorSeverity::Medium,
+            Error::Spanned { source, .. } | Error::AtPath { source, .. } => Self::severity(source),
             Error::InvalidEscape(_) | Error::InvalidUnicode(_) => ErrorSeverity::Medium,
             Error::UnexpectedChar(_, _)
             | Error::UnexpectedEof(_)
@@ -48,7 +49,12 @@ impl ErrorHelper {
             | Error::UnbalancedBrackets(_, _)
             | Error::MaxRepairsExceeded(_)
             | Error::InvalidUtf8(_)
-            | Error::InvalidChunk(_) => ErrorSeverity::High,
+            | Error::InvalidChunk(_)
+            | Error::DuplicateKey { .. }
+            | Error::BlankKey { .. }
+            | Error::LimitExceeded { .. }
+            | Error::BudgetExceeded { .. }
+            | Error::Io { .. } => ErrorSeverity::High,
         }
     }
 
@@ -91,6 +97,14 @@ impl ErrorHelper {
             Error::DepthLimitExceeded(_) => false,
             Error::InvalidUtf8(_) => false,
             Error::InvalidChunk(_) => false,
+            Error::DuplicateKey { .. } => false,
+            Error::BlankKey { .. } => false,
+            Error::LimitExceeded { .. } => false,
+            Error::BudgetExceeded { .. } => false,
+            Error::Io { .. } => false,
+            Error::Spanned { source, .. } | Error::AtPath { source, .. } => {
+                Self::is_recoverable(source)
+            }
             _ => true,
         }
     }
@@ -108,11 +122,17 @@ impl ErrorHelper {
             Error::DepthLimitExceeded(_) => "limits",
             Error::Custom(_) => "custom",
             Error::WithContext { .. } => "context",
+            Error::Spanned { source, .. } | Error::AtPath { source, .. } => {
+                Self::categorize(source)
+            }
             Error::RepairFailed(_) => "repair",
             Error::BracketMismatch(_, _, _) | Error::UnbalancedBrackets(_, _) => "brackets",
             Error::MaxRepairsExceeded(_) => "repair",
             Error::InvalidUtf8(_) => "encoding",
             Error::InvalidChunk(_) => "parallel",
+            Error::DuplicateKey { .. } | Error::BlankKey { .. } => "structure",
+            Error::LimitExceeded { .. } | Error::BudgetExceeded { .. } => "limits",
+            Error::Io { .. } => "io",
         }
     }
 }
@@ -158,13 +178,19 @@ impl ErrorUtils for Error {
             | Error::Expected { position: pos, .. }
             | Error::DepthLimitExceeded(pos)
             | Error::BracketMismatch(pos, _, _)
-            | Error::InvalidUtf8(pos) => Some(*pos),
-            Error::WithContext { source, .. } => source.position(),
+            | Error::InvalidUtf8(pos)
+            | Error::DuplicateKey { position: pos, .. }
+            | Error::BlankKey { position: pos, .. }
+            | Error::LimitExceeded { position: pos, .. }
+            | Error::BudgetExceeded { position: pos, .. } => Some(*pos),
+            Error::WithContext { source, .. } | Error::AtPath { source, .. } => source.position(),
+            Error::Spanned { span, .. } => Some(span.start),
             Error::Custom(_)
             | Error::RepairFailed(_)
             | Error::UnbalancedBrackets(_, _)
             | Error::MaxRepairsExceeded(_)
-            | Error::InvalidChunk(_) => None,
+            | Error::InvalidChunk(_)
+            | Error::Io { .. } => None,
         }
     }
 
@@ -248,6 +274,7 @@ pub fn detect_repairs(original: &str, repaired: &str) -> Vec<RepairAction> {
                 original: bracket_char.to_string(),
                 replacement: format!("{bracket_char} (count: {orig_count} → {rep_count})"),
                 description: format!("Balanced {bracket_char} brackets"),
+                cause: None,
             });
         }
     }
@@ -261,6 +288,7 @@ pub fn detect_repairs(original: &str, repaired: &str) -> Vec<RepairAction> {
                 original: String::new(),
                 replacement: bracket_char.to_string(),
                 description: format!("Added {bracket_char} brackets"),
+                cause: None,
             });
         }
     }
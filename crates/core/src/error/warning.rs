@@ -0,0 +1,147 @@
+// this_file: crates/core/src/error/warning.rs
+
+//! Warnings about forgiving transformations that change a document's shape.
+//!
+//! Forgiving parsing accepts input that strict JSON rejects, and some of
+//! that input can be read more than one way: a line break splitting two
+//! values, or bare `key: value` pairs becoming an object. These parses
+//! succeed, so warnings are the only record that the parser made a choice.
+//!
+//! Compatibility warnings go further and report every construct outside
+//! strict JSON, along with the strict text that would replace it, so that
+//! producers of forgiving input can be nudged towards strict JSON.
+
+use crate::error::{RepairType, Span};
+use std::fmt;
+
+/// The forgiving transformation a [`Warning`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningKind {
+    /// A line break separated two values or members in place of a comma.
+    NewlineAsComma,
+    /// Top-level `key: value` pairs were wrapped in an object.
+    ImplicitObject,
+    /// Several top-level values were wrapped in an array.
+    ImplicitArray,
+    /// An invalid UTF-8 sequence was replaced with U+FFFD, under
+    /// [`InvalidUtf8Policy::Replace`](crate::parser::InvalidUtf8Policy::Replace).
+    InvalidUtf8,
+    /// A number too large for an `f64` was read as infinity. Strict options
+    /// reject it instead; see
+    /// [`ParserOptions::is_strict`](crate::ParserOptions::is_strict).
+    NumberOverflow,
+}
+
+/// A forgiving transformation applied while parsing, collected when
+/// [`ParserOptions::collect_warnings`](crate::ParserOptions::collect_warnings)
+/// is enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// What was transformed
+    pub kind: WarningKind,
+    /// Human-readable description
+    pub message: String,
+    /// The input the transformation applies to
+    pub span: Span,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.span.start)
+    }
+}
+
+/// A construct outside strict JSON that a [`CompatWarning`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompatKind {
+    /// A `//`, `#` or `/* */` comment.
+    Comment,
+    /// A comma before a closing bracket or brace.
+    TrailingComma,
+    /// An object key that is an unquoted word or a number.
+    UnquotedKey,
+    /// A string in single quotes.
+    SingleQuotedString,
+    /// A string value without quotes.
+    UnquotedString,
+    /// A number strict JSON cannot spell that way, such as `0x1F`, `+1` or
+    /// `.5`.
+    NonStandardNumber,
+    /// An empty array element, read as `null`.
+    MissingValue,
+    /// A line break separating two values or members in place of a comma.
+    NewlineAsComma,
+    /// Two values or members with nothing but spaces between them, as in
+    /// `[1 2 3]`.
+    MissingComma,
+    /// Top-level values or members without enclosing brackets or braces.
+    ImplicitTopLevel,
+}
+
+impl CompatKind {
+    /// The repair that turns this construct into strict JSON.
+    pub fn repair_type(self) -> RepairType {
+        match self {
+            CompatKind::Comment | CompatKind::NonStandardNumber => RepairType::ReplaceText,
+            CompatKind::TrailingComma => RepairType::RemoveComma,
+            CompatKind::UnquotedKey => RepairType::QuoteKey,
+            CompatKind::SingleQuotedString => RepairType::ReplaceQuotes,
+            CompatKind::UnquotedString => RepairType::BalanceQuotes,
+            CompatKind::MissingValue => RepairType::InsertText,
+            CompatKind::NewlineAsComma | CompatKind::MissingComma => RepairType::InsertComma,
+            CompatKind::ImplicitTopLevel => RepairType::InsertBracket,
+        }
+    }
+
+    /// Describes the construct.
+    pub fn description(self) -> &'static str {
+        match self {
+            CompatKind::Comment => "comment",
+            CompatKind::TrailingComma => "trailing comma",
+            CompatKind::UnquotedKey => "unquoted key",
+            CompatKind::SingleQuotedString => "single-quoted string",
+            CompatKind::UnquotedString => "unquoted string",
+            CompatKind::NonStandardNumber => "non-standard number",
+            CompatKind::MissingValue => "missing array element",
+            CompatKind::NewlineAsComma => "line break used as a comma",
+            CompatKind::MissingComma => "missing comma",
+            CompatKind::ImplicitTopLevel => "top level without enclosing brackets",
+        }
+    }
+}
+
+/// A construct outside strict JSON that a forgiving parse accepted,
+/// collected when
+/// [`ParserOptions::collect_compat_warnings`](crate::ParserOptions::collect_compat_warnings)
+/// is enabled.
+///
+/// Replacing the text at `span` with `replacement` removes the construct.
+/// Only an [`ImplicitTopLevel`](CompatKind::ImplicitTopLevel) span overlaps
+/// others, as its replacement wraps all the text it covers in brackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatWarning {
+    /// What was found
+    pub kind: CompatKind,
+    /// The input the construct occupies
+    pub span: Span,
+    /// Strict JSON text to put in place of `span`
+    pub replacement: String,
+}
+
+impl fmt::Display for CompatWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at position {}",
+            self.kind.description(),
+            self.span.start
+        )?;
+        // Long or multi-line replacements would drown the message
+        match self.replacement.as_str() {
+            "" => write!(f, "; remove it"),
+            r if r.len() > 40 || r.contains('\n') => Ok(()),
+            r if self.span.is_empty() => write!(f, "; insert {r}"),
+            r => write!(f, "; use {r} instead"),
+        }
+    }
+}
@@ -0,0 +1,1488 @@
+// this_file: crates/core/src/format.rs
+
+//! Serializing values back to standard JSON text.
+//!
+//! [`Value`] objects do not remember the order of their keys, so the writers
+//! here take a [`KeyOrder`] recovered from the source text. Keys it does not
+//! mention are written in sorted order, which keeps output deterministic.
+//! JSON has no spelling for NaN or the infinities, so such floats, including
+//! numbers that overflowed when parsed, are written as `null`.
+//!
+//! [`to_json5_string`] writes JSON5 instead, leaving keys that are valid
+//! identifiers unquoted if asked.
+//!
+//! [`strip_comments`] instead edits the source text, leaving everything but
+//! comments as written, while [`to_pretty_string_with_comments`] reformats a
+//! document and puts its comments back next to the values they belong to.
+
+use crate::ast::{Hashed, MapLike, MapOrder, Number, Token, Value, ValueG};
+use crate::error::{Error, Result};
+use crate::lexer::tokenize_with_comment_styles;
+use crate::optimization::unescape_string_optimized;
+use crate::parser::{create_parser_lexer, parse_with_fallback, CommentStyles, ParserOptions};
+use rustc_hash::FxHashMap;
+
+/// The order in which object keys appear in a source document.
+///
+/// Mirrors the document's shape: object members carry the order of their own
+/// values, array items that of each element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyOrder {
+    keys: Vec<(String, KeyOrder)>,
+    items: Vec<KeyOrder>,
+}
+
+impl KeyOrder {
+    /// Recovers key order from `input`.
+    ///
+    /// Forgiving syntax (comments, unquoted keys, trailing commas, implicit
+    /// top-level containers) is understood. The input is read with the same
+    /// lexer the default parser uses, and scanned without recursion, so any
+    /// document that parses has its order recovered. Input that cannot be
+    /// tokenized yields an empty order.
+    pub fn from_source(input: &str) -> Self {
+        Self::from_source_with_options(input, &ParserOptions::default())
+    }
+
+    /// Like [`from_source`](Self::from_source), reading `input` as the
+    /// parser does under `options`, so the order matches the value parsed
+    /// from it with them.
+    pub fn from_source_with_options(input: &str, options: &ParserOptions) -> Self {
+        let mut lexer = create_parser_lexer(input, options);
+        let mut tokens = Vec::new();
+        loop {
+            match lexer.next_token() {
+                Ok((Token::Eof, _)) => break,
+                Ok((token, span)) => tokens.push((token, &input[span.start..span.end])),
+                Err(_) => return Self::default(),
+            }
+        }
+        OrderScanner { tokens, pos: 0 }.document()
+    }
+
+    /// Records the order in which `value`'s objects iterate their keys.
+    pub fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Object(obj) => Self {
+                keys: obj
+                    .iter()
+                    .map(|(key, item)| (key.clone(), Self::from_value(item)))
+                    .collect(),
+                items: Vec::new(),
+            },
+            Value::Array(arr) => Self {
+                keys: Vec::new(),
+                items: arr.iter().map(Self::from_value).collect(),
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Renames every key with `rename`, keeping the order, for a value
+    /// whose keys were rewritten as by
+    /// [`normalize_keys`](crate::transform::normalize_keys).
+    pub fn rename_keys(&self, rename: &impl Fn(&str) -> String) -> Self {
+        Self {
+            keys: self
+                .keys
+                .iter()
+                .map(|(key, order)| (rename(key), order.rename_keys(rename)))
+                .collect(),
+            items: self
+                .items
+                .iter()
+                .map(|order| order.rename_keys(rename))
+                .collect(),
+        }
+    }
+
+    /// Returns the order of the `index`th element of an array, for writing
+    /// elements one at a time.
+    pub fn item(&self, index: usize) -> Option<&KeyOrder> {
+        self.items.get(index)
+    }
+
+    /// Returns the keys of an object in document order, each with the order
+    /// of its value. A repeated key appears once per occurrence.
+    pub(crate) fn members(&self) -> &[(String, KeyOrder)] {
+        &self.keys
+    }
+}
+
+/// Freed without recursion, like [`Value`], so the order of a document of
+/// any depth can be dropped.
+impl Drop for KeyOrder {
+    fn drop(&mut self) {
+        let mut nested: Vec<KeyOrder> = Vec::new();
+        self.take_nested(&mut nested);
+        while let Some(mut order) = nested.pop() {
+            order.take_nested(&mut nested);
+        }
+    }
+}
+
+impl KeyOrder {
+    /// Moves the orders this one holds onto `nested`.
+    fn take_nested(&mut self, nested: &mut Vec<KeyOrder>) {
+        nested.extend(std::mem::take(&mut self.items));
+        nested.extend(
+            std::mem::take(&mut self.keys)
+                .into_iter()
+                .map(|(_, order)| order),
+        );
+    }
+}
+
+/// Walks the token stream, recording keys without building values.
+struct OrderScanner<'a> {
+    tokens: Vec<(Token, &'a str)>,
+    pos: usize,
+}
+
+/// A container [`OrderScanner`] has entered and not yet left.
+struct OpenOrder {
+    order: KeyOrder,
+    /// Whether the container is an object rather than an array
+    object: bool,
+    /// The key the container is the value of, in an enclosing object
+    key: Option<String>,
+}
+
+impl OrderScanner<'_> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.pos).map(|(token, _)| *token)
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.peek(), Some(Token::Comma | Token::Newline)) {
+            self.pos += 1;
+        }
+    }
+
+    fn document(&mut self) -> KeyOrder {
+        while self.peek() == Some(Token::Newline) {
+            self.pos += 1;
+        }
+        let implicit_object = !matches!(self.peek(), Some(Token::LeftBrace | Token::LeftBracket))
+            && self.tokens.get(self.pos + 1).map(|(token, _)| *token) == Some(Token::Colon);
+        if implicit_object {
+            return self.container(true);
+        }
+
+        let first = self.value();
+        self.skip_separators();
+        if self.peek().is_none() {
+            return first;
+        }
+        // Implicit top-level array
+        let mut rest = self.container(false);
+        rest.items.insert(0, first);
+        rest
+    }
+
+    fn value(&mut self) -> KeyOrder {
+        let token = self.peek();
+        self.pos += 1;
+        match token {
+            Some(Token::LeftBrace) => self.container(true),
+            Some(Token::LeftBracket) => self.container(false),
+            _ => KeyOrder::default(),
+        }
+    }
+
+    /// Scans the members of an object, or the elements of an array, whose
+    /// opening bracket has been read, up to and past its closing bracket.
+    ///
+    /// Nested containers are kept on a stack rather than recursed into, so
+    /// documents of any depth can be scanned.
+    fn container(&mut self, object: bool) -> KeyOrder {
+        let mut stack = vec![OpenOrder {
+            order: KeyOrder::default(),
+            object,
+            key: None,
+        }];
+        loop {
+            self.skip_separators();
+            let open = stack
+                .last_mut()
+                .expect("the outermost container is never popped");
+            let closed = match (self.peek(), open.object) {
+                (None, _)
+                | (Some(Token::RightBracket), true)
+                | (Some(Token::RightBrace), false) => true,
+                (Some(Token::RightBrace), true) | (Some(Token::RightBracket), false) => {
+                    self.pos += 1;
+                    true
+                }
+                _ => false,
+            };
+            if closed {
+                let done = stack
+                    .pop()
+                    .expect("the outermost container is never popped");
+                match (stack.last_mut(), done.key) {
+                    (None, _) => return done.order,
+                    (Some(parent), Some(key)) => parent.order.keys.push((key, done.order)),
+                    (Some(parent), None) => parent.order.items.push(done.order),
+                }
+                continue;
+            }
+
+            let key = if open.object {
+                let (token, text) = self.tokens[self.pos];
+                self.pos += 1;
+                if self.peek() != Some(Token::Colon) {
+                    continue;
+                }
+                self.pos += 1;
+                Some(key_text(token, text))
+            } else {
+                None
+            };
+            let token = self.peek();
+            self.pos += 1;
+            let object = match token {
+                Some(Token::LeftBrace) => true,
+                Some(Token::LeftBracket) => false,
+                _ => {
+                    match key {
+                        Some(key) => open.order.keys.push((key, KeyOrder::default())),
+                        None => open.order.items.push(KeyOrder::default()),
+                    }
+                    continue;
+                }
+            };
+            stack.push(OpenOrder {
+                order: KeyOrder::default(),
+                object,
+                key,
+            });
+        }
+    }
+}
+
+/// The comments in a source document, each placed by the member or element
+/// it belongs to, for [`to_pretty_string_with_comments`].
+///
+/// A comment on the line after a value, as in `"port": 80, // default`,
+/// belongs to that value. Other comments belong to the next member or
+/// element of the same container, or, with none left, to the container's
+/// closing bracket. Comments before the document and after it are kept as
+/// well.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceComments {
+    root: CommentNode,
+    /// Comments on the lines after the document.
+    end: Vec<String>,
+}
+
+impl SourceComments {
+    /// Recovers the comments in `input`, with their delimiters.
+    ///
+    /// Forgiving syntax is understood as by [`KeyOrder::from_source`]. Input
+    /// that cannot be tokenized yields no comments.
+    pub fn from_source(input: &str) -> Self {
+        Self::from_source_with_comment_styles(input, CommentStyles::default())
+    }
+
+    /// Like [`from_source`](Self::from_source), recognizing only the comments
+    /// written in `styles`.
+    pub fn from_source_with_comment_styles(input: &str, styles: CommentStyles) -> Self {
+        let Ok(tokens) = tokenize_with_comment_styles(input, styles) else {
+            return Self::default();
+        };
+        let tokens = tokens
+            .into_iter()
+            .map(|(token, span)| (token, &input[span.start..span.end]))
+            .collect();
+        CommentScanner { tokens, pos: 0 }.document()
+    }
+
+    /// Whether `input` had no comments.
+    pub fn is_empty(&self) -> bool {
+        self.end.is_empty() && self.root.is_empty()
+    }
+}
+
+/// The comments belonging to one value, and to those inside it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CommentNode {
+    /// On the lines before the value, or before its key.
+    leading: Vec<String>,
+    /// After the value on the line where it ends.
+    trailing: Vec<String>,
+    /// After the last member of a container, before its closing bracket.
+    closing: Vec<String>,
+    keys: Vec<(String, CommentNode)>,
+    items: Vec<CommentNode>,
+}
+
+impl CommentNode {
+    fn key(&self, key: &str) -> Option<&CommentNode> {
+        self.keys
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, comments)| comments)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.leading.is_empty()
+            && self.trailing.is_empty()
+            && self.closing.is_empty()
+            && self.keys.iter().all(|(_, node)| node.is_empty())
+            && self.items.iter().all(CommentNode::is_empty)
+    }
+}
+
+/// Walks the token stream like [`OrderScanner`], attaching comments to the
+/// values around them.
+struct CommentScanner<'a> {
+    tokens: Vec<(Token, &'a str)>,
+    pos: usize,
+}
+
+impl CommentScanner<'_> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.pos).map(|(token, _)| *token)
+    }
+
+    /// Skips separators, returning the comments passed on the current line
+    /// and those on later lines.
+    fn comments(&mut self) -> (Vec<String>, Vec<String>) {
+        let (mut same_line, mut later) = (Vec::new(), Vec::new());
+        let mut new_line = false;
+        while let Some(&(token, text)) = self.tokens.get(self.pos) {
+            match token {
+                Token::Newline => new_line = true,
+                Token::Comma => {}
+                Token::SingleLineComment | Token::MultiLineComment if new_line => {
+                    later.push(text.to_string())
+                }
+                Token::SingleLineComment | Token::MultiLineComment => {
+                    same_line.push(text.to_string())
+                }
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        (same_line, later)
+    }
+
+    /// Every comment up to the next token, wherever it is.
+    fn all_comments(&mut self) -> Vec<String> {
+        let (mut comments, later) = self.comments();
+        comments.extend(later);
+        comments
+    }
+
+    /// The token after the current one, skipping comments and line breaks.
+    fn peek_second(&self) -> Option<Token> {
+        self.tokens[self.pos..]
+            .iter()
+            .skip(1)
+            .map(|(token, _)| *token)
+            .find(|token| {
+                !matches!(
+                    token,
+                    Token::SingleLineComment | Token::MultiLineComment | Token::Newline
+                )
+            })
+    }
+
+    fn document(&mut self) -> SourceComments {
+        let leading = self.all_comments();
+        let implicit_object = !matches!(self.peek(), Some(Token::LeftBrace | Token::LeftBracket))
+            && self.peek_second() == Some(Token::Colon);
+        let mut root = CommentNode::default();
+        if implicit_object {
+            self.members(&mut root, leading);
+        } else {
+            let mut first = self.value();
+            first.leading = leading;
+            let (trailing, later) = self.comments();
+            first.trailing = trailing;
+            if self.peek().is_none() {
+                return SourceComments {
+                    root: first,
+                    end: later,
+                };
+            }
+            // Implicit top-level array
+            root.items.push(first);
+            self.items(&mut root, later);
+        }
+        SourceComments {
+            end: std::mem::take(&mut root.closing),
+            root,
+        }
+    }
+
+    fn value(&mut self) -> CommentNode {
+        let token = self.peek();
+        self.pos += 1;
+        let mut node = CommentNode::default();
+        match token {
+            Some(Token::LeftBrace) => {
+                let leading = self.all_comments();
+                self.members(&mut node, leading);
+            }
+            Some(Token::LeftBracket) => {
+                let leading = self.all_comments();
+                self.items(&mut node, leading);
+            }
+            _ => {}
+        }
+        node
+    }
+
+    /// Reads members up to the closing brace, the first of them preceded by
+    /// the comments in `leading`.
+    fn members(&mut self, node: &mut CommentNode, mut leading: Vec<String>) {
+        loop {
+            match self.tokens.get(self.pos) {
+                None | Some((Token::RightBracket, _)) => break,
+                Some((Token::RightBrace, _)) => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(&(token, text)) => {
+                    self.pos += 1;
+                    leading.extend(self.all_comments());
+                    if self.peek() != Some(Token::Colon) {
+                        continue;
+                    }
+                    self.pos += 1;
+                    leading.extend(self.all_comments());
+                    let mut member = self.value();
+                    let (trailing, later) = self.comments();
+                    member.leading = std::mem::replace(&mut leading, later);
+                    member.trailing = trailing;
+                    node.keys.push((key_text(token, text), member));
+                }
+            }
+        }
+        node.closing = leading;
+    }
+
+    /// Reads elements up to the closing bracket, the first of them preceded
+    /// by the comments in `leading`.
+    fn items(&mut self, node: &mut CommentNode, mut leading: Vec<String>) {
+        loop {
+            match self.peek() {
+                None | Some(Token::RightBrace) => break,
+                Some(Token::RightBracket) => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(_) => {
+                    let mut item = self.value();
+                    let (trailing, later) = self.comments();
+                    item.leading = std::mem::replace(&mut leading, later);
+                    item.trailing = trailing;
+                    node.items.push(item);
+                }
+            }
+        }
+        node.closing = leading;
+    }
+}
+
+/// Decodes the text of a key token.
+fn key_text(token: Token, text: &str) -> String {
+    if token == Token::String && text.len() >= 2 {
+        let content = &text[1..text.len() - 1];
+        unescape_string_optimized(content).unwrap_or_else(|_| content.to_string())
+    } else {
+        text.to_string()
+    }
+}
+
+/// How floating-point numbers are written. Integers are always written in
+/// full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// The fewest digits that read back as the same `f64`.
+    #[default]
+    Shortest,
+    /// Exactly this many digits after the decimal point, as in `0.30` for
+    /// `0.1 + 0.2` with two decimals.
+    Fixed(usize),
+    /// Rounded to this many significant digits, then written as with
+    /// [`Shortest`](FloatFormat::Shortest). Zero is taken as one.
+    Significant(usize),
+}
+
+/// Whether object keys are quoted in JSON5 output.
+///
+/// Standard JSON output always quotes keys, whatever this says.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeyQuoting {
+    /// Every key in double quotes, as in JSON.
+    #[default]
+    Always,
+    /// Keys that are identifiers, such as `validKey` or `$ref`, left bare;
+    /// others, such as `"123abc"` or `"a-b"`, quoted.
+    MinimalJson5,
+}
+
+impl KeyQuoting {
+    /// Whether `key` is written in quotes.
+    ///
+    /// Only ASCII identifiers are left bare: a letter, `_` or `$`, then
+    /// letters, digits, `_` or `$`, other than `true`, `false` and `null`.
+    /// Quoting a key is never wrong, and keys written this way read back
+    /// with this crate's parser as well as any other JSON5 reader.
+    pub fn quotes(self, key: &str) -> bool {
+        match self {
+            KeyQuoting::Always => true,
+            KeyQuoting::MinimalJson5 => !is_identifier(key),
+        }
+    }
+}
+
+/// Whether `key` is an ASCII JSON5 identifier other than a literal.
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    !matches!(key, "true" | "false" | "null")
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// The line break written between lines of output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, as the writers here produce.
+    #[default]
+    Lf,
+    /// `\r\n`, as Windows tools expect.
+    CrLf,
+}
+
+impl LineEnding {
+    /// The line break itself.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// Rewrites every `\n` or `\r\n` line break in `text` as this one.
+    ///
+    /// Line breaks inside JSON strings are always escaped, so only those
+    /// between lines and within comments are changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vexy_json_core::format::{to_pretty_string, KeyOrder, LineEnding};
+    ///
+    /// let value = vexy_json_core::parse("[1, 'a\\nb']").unwrap();
+    /// let pretty = to_pretty_string(&value, 2, &KeyOrder::default());
+    /// assert_eq!(LineEnding::CrLf.convert(&pretty), "[\r\n  1,\r\n  \"a\\nb\"\r\n]");
+    /// ```
+    pub fn convert(self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut lines = text.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            if lines.peek().is_some() {
+                out.push_str(line.strip_suffix('\r').unwrap_or(line));
+                out.push_str(self.as_str());
+            } else {
+                out.push_str(line);
+            }
+        }
+        out
+    }
+}
+
+/// Writes `value` as JSON with no insignificant whitespace.
+pub fn to_compact_string(value: &Value, order: &KeyOrder) -> String {
+    to_string_with_floats(value, None, order, FloatFormat::Shortest)
+}
+
+/// Writes `value` as JSON, one member per line, indented by `indent` spaces
+/// per level.
+pub fn to_pretty_string(value: &Value, indent: usize, order: &KeyOrder) -> String {
+    to_string_with_floats(value, Some(indent), order, FloatFormat::Shortest)
+}
+
+/// Writes `value` as JSON with floats formatted as `floats` asks, compact
+/// when `indent` is `None` and as [`to_pretty_string`] would otherwise.
+///
+/// Only [`FloatFormat::Shortest`] is sure to read back as the same value.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::format::{to_string_with_floats, FloatFormat, KeyOrder};
+/// use vexy_json_core::ast::{Number, Value};
+///
+/// let value = Value::Number(Number::Float(0.1 + 0.2));
+/// let order = KeyOrder::default();
+/// assert_eq!(to_string_with_floats(&value, None, &order, FloatFormat::Shortest), "0.30000000000000004");
+/// assert_eq!(to_string_with_floats(&value, None, &order, FloatFormat::Fixed(2)), "0.30");
+/// assert_eq!(to_string_with_floats(&value, None, &order, FloatFormat::Significant(3)), "0.3");
+/// ```
+pub fn to_string_with_floats(
+    value: &Value,
+    indent: Option<usize>,
+    order: &KeyOrder,
+    floats: FloatFormat,
+) -> String {
+    to_string_with(value, indent, order, floats, KeyQuoting::Always)
+}
+
+/// Writes `value` as JSON5, compact when `indent` is `None` and one member
+/// per line otherwise, with keys quoted as `keys` asks.
+///
+/// Apart from its keys, the output is the same as the JSON writers give.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::format::{to_json5_string, KeyOrder, KeyQuoting};
+///
+/// let input = r#"{"validKey": 1, "123abc": 2}"#;
+/// let value = vexy_json_core::parse(input).unwrap();
+/// let order = KeyOrder::from_source(input);
+/// assert_eq!(
+///     to_json5_string(&value, None, &order, KeyQuoting::MinimalJson5),
+///     r#"{validKey:1,"123abc":2}"#
+/// );
+/// assert_eq!(
+///     to_json5_string(&value, None, &order, KeyQuoting::Always),
+///     r#"{"validKey":1,"123abc":2}"#
+/// );
+/// ```
+pub fn to_json5_string(
+    value: &Value,
+    indent: Option<usize>,
+    order: &KeyOrder,
+    keys: KeyQuoting,
+) -> String {
+    to_string_with(value, indent, order, FloatFormat::Shortest, keys)
+}
+
+/// Writes `value` with floats written as `floats` asks and keys quoted as
+/// `keys` asks.
+pub(crate) fn to_string_with(
+    value: &Value,
+    indent: Option<usize>,
+    order: &KeyOrder,
+    floats: FloatFormat,
+    keys: KeyQuoting,
+) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, order, indent, floats, keys, 0);
+    out
+}
+
+/// Writes `value` as [`to_pretty_string`] does, with the comments recovered
+/// from its source put back by the values they belong to.
+///
+/// The result is JSONC rather than JSON unless there were no comments.
+/// Comments belonging to a key the value no longer has are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::format::{to_pretty_string_with_comments, KeyOrder, SourceComments};
+///
+/// let input = "// Server\n{port: 80, // default\n host: 'x'}";
+/// let value = vexy_json_core::parse(input).unwrap();
+/// let pretty = to_pretty_string_with_comments(
+///     &value,
+///     2,
+///     &KeyOrder::from_source(input),
+///     &SourceComments::from_source(input),
+/// );
+/// assert_eq!(pretty, "// Server\n{\n  \"port\": 80, // default\n  \"host\": \"x\"\n}");
+/// ```
+pub fn to_pretty_string_with_comments(
+    value: &Value,
+    indent: usize,
+    order: &KeyOrder,
+    comments: &SourceComments,
+) -> String {
+    let mut out = String::new();
+    for comment in &comments.root.leading {
+        out.push_str(comment);
+        out.push('\n');
+    }
+    write_commented(&mut out, value, order, &comments.root, indent, 0);
+    write_trailing(&mut out, &comments.root.trailing);
+    for comment in &comments.end {
+        out.push('\n');
+        out.push_str(comment);
+    }
+    out
+}
+
+/// Parses `input` forgivingly and writes it back as compact JSON.
+///
+/// Key order follows the source; comments and trailing commas are dropped.
+///
+/// # Errors
+///
+/// Returns the first parse error if `input` cannot be parsed.
+pub fn minify(input: &str, options: ParserOptions) -> Result<String> {
+    let order = KeyOrder::from_source_with_options(input, &options);
+    let value = parse_for_format(input, options)?;
+    Ok(to_compact_string(&value, &order))
+}
+
+/// Parses `input` forgivingly and writes it back as indented JSON.
+///
+/// Key order follows the source; comments and trailing commas are dropped.
+///
+/// # Errors
+///
+/// Returns the first parse error if `input` cannot be parsed.
+pub fn prettify(input: &str, indent: usize, options: ParserOptions) -> Result<String> {
+    let order = KeyOrder::from_source_with_options(input, &options);
+    let value = parse_for_format(input, options)?;
+    Ok(to_pretty_string(&value, indent, &order))
+}
+
+fn parse_for_format(input: &str, options: ParserOptions) -> Result<Value> {
+    let mut result = parse_with_fallback(input, options);
+    if result.errors.is_empty() {
+        Ok(result.value)
+    } else {
+        Err(result.errors.swap_remove(0))
+    }
+}
+
+/// What [`strip_comments`] removes besides comments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StripCommentsOptions {
+    /// Whether to also remove commas directly before a closing bracket or
+    /// brace.
+    pub remove_trailing_commas: bool,
+    /// Whether every line keeps its number, for source maps and error
+    /// positions: the line breaks inside block comments are kept, and lines
+    /// holding only a comment are left empty rather than removed.
+    pub keep_newlines: bool,
+    /// The comment syntaxes to recognize. A `#` that does not start a
+    /// comment is part of an unquoted string and is kept.
+    pub comment_styles: CommentStyles,
+}
+
+impl Default for StripCommentsOptions {
+    fn default() -> Self {
+        Self {
+            remove_trailing_commas: true,
+            keep_newlines: false,
+            comment_styles: CommentStyles::default(),
+        }
+    }
+}
+
+/// Removes the comments from `input`, copying every other byte unchanged.
+///
+/// Unlike [`minify`], nothing is parsed or rewritten: key order, spacing and
+/// number spellings stay as they are, so JSONC with only comments and
+/// trailing commas beyond standard JSON becomes standard JSON with a minimal
+/// diff. Comment markers inside strings are string content and are kept.
+///
+/// Spaces and tabs before a comment that ends its line go with it, and
+/// unless [`keep_newlines`](StripCommentsOptions::keep_newlines) is set, so
+/// does a line holding only a comment. A comment between two tokens that
+/// would otherwise run together, as in `1/* */2`, becomes a space.
+///
+/// # Errors
+///
+/// Returns the tokenizer's error for input it cannot read, such as an
+/// unterminated string, and [`Error::Expected`] at the opening `/*` of an
+/// unterminated comment.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::format::{strip_comments, StripCommentsOptions};
+///
+/// let input = "{\n  // retries\n  \"n\": 3, /* max */\n  \"url\": \"http://x\",\n}";
+/// let stripped = strip_comments(input, &StripCommentsOptions::default()).unwrap();
+/// assert_eq!(stripped, "{\n  \"n\": 3,\n  \"url\": \"http://x\"\n}");
+/// ```
+pub fn strip_comments(input: &str, options: &StripCommentsOptions) -> Result<String> {
+    let styles = options.comment_styles;
+    let tokens = tokenize_with_comment_styles(input, styles).map_err(|error| match error {
+        Error::UnexpectedChar('/', position)
+            if styles.block && input[position..].starts_with("/*") =>
+        {
+            Error::Expected {
+                expected: "*/ to close the comment".to_string(),
+                found: "end of input".to_string(),
+                position,
+            }
+        }
+        error => error,
+    })?;
+
+    // Byte ranges to drop, in order, each with the text that replaces it
+    let mut removals: Vec<(usize, usize, String)> = Vec::new();
+    for (i, (token, span)) in tokens.iter().enumerate() {
+        match token {
+            Token::SingleLineComment | Token::MultiLineComment => {
+                removals.push(comment_removal(input, span.start, span.end, options));
+            }
+            Token::Comma if options.remove_trailing_commas => {
+                let next = tokens[i + 1..]
+                    .iter()
+                    .map(|(token, _)| token)
+                    .find(|token| {
+                        !matches!(
+                            token,
+                            Token::SingleLineComment | Token::MultiLineComment | Token::Newline
+                        )
+                    });
+                if matches!(next, Some(Token::RightBrace | Token::RightBracket)) {
+                    removals.push((span.start, span.end, String::new()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = String::with_capacity(input.len());
+    let mut copied = 0;
+    for (start, end, replacement) in removals {
+        out.push_str(&input[copied..start]);
+        out.push_str(&replacement);
+        copied = end;
+    }
+    out.push_str(&input[copied..]);
+    Ok(out)
+}
+
+/// The range to remove for the comment at `start..end`, and what replaces it.
+fn comment_removal(
+    input: &str,
+    start: usize,
+    end: usize,
+    options: &StripCommentsOptions,
+) -> (usize, usize, String) {
+    let bytes = input.as_bytes();
+    // Only a block comment has line breaks of its own
+    let breaks: String = if options.keep_newlines {
+        input[start..end]
+            .chars()
+            .filter(|c| matches!(c, '\r' | '\n'))
+            .collect()
+    } else {
+        String::new()
+    };
+
+    let is_blank = |byte: &&u8| matches!(byte, b' ' | b'\t');
+    let indent = bytes[..start].iter().rev().take_while(is_blank).count();
+    let line_end = end + bytes[end..].iter().take_while(is_blank).count();
+    let line_break = match &bytes[line_end..] {
+        [b'\r', b'\n', ..] => 2,
+        [b'\n' | b'\r', ..] => 1,
+        [] => 0,
+        _ => {
+            // More follows on the line, which must not run into what precedes
+            let joins = |byte: Option<&u8>| {
+                byte.is_some_and(|byte| !byte.is_ascii_whitespace() && !b"{}[]:,".contains(byte))
+            };
+            let replacement =
+                if breaks.is_empty() && joins(bytes[..start].last()) && joins(bytes.get(end)) {
+                    " ".to_string()
+                } else {
+                    breaks
+                };
+            return (start, end, replacement);
+        }
+    };
+
+    let comment_start = start - indent;
+    let whole_line = comment_start == 0 || matches!(bytes[comment_start - 1], b'\n' | b'\r');
+    if whole_line && !options.keep_newlines {
+        (comment_start, line_end + line_break, breaks)
+    } else {
+        (comment_start, line_end, breaks)
+    }
+}
+
+/// Writes `value`, which sits `depth` containers deep.
+///
+/// Open containers are kept on a stack of their own rather than recursed
+/// into, so documents nested deeper than the thread's stack allows, as the
+/// iterative parser produces, are written like any other.
+pub(crate) fn write_value<M: MapLike>(
+    out: &mut String,
+    value: &ValueG<M>,
+    order: &KeyOrder,
+    indent: Option<usize>,
+    floats: FloatFormat,
+    keys: KeyQuoting,
+    depth: usize,
+) {
+    let mut stack: Vec<OpenContainer<'_, M>> = Vec::new();
+    let mut next = Some((value, order));
+    loop {
+        if let Some((value, order)) = next.take() {
+            let members = match value {
+                ValueG::Array(arr) if !arr.is_empty() => {
+                    out.push('[');
+                    Members::Array(arr.iter().enumerate())
+                }
+                ValueG::Object(obj) if M::len(obj) > 0 => {
+                    out.push('{');
+                    Members::Object(ordered_entries::<M>(obj, order).into_iter())
+                }
+                ValueG::Array(_) => {
+                    out.push_str("[]");
+                    Members::None
+                }
+                ValueG::Object(_) => {
+                    out.push_str("{}");
+                    Members::None
+                }
+                ValueG::Null => {
+                    out.push_str("null");
+                    Members::None
+                }
+                ValueG::Bool(b) => {
+                    out.push_str(if *b { "true" } else { "false" });
+                    Members::None
+                }
+                ValueG::Number(Number::Float(f)) => {
+                    write_float(out, *f, floats);
+                    Members::None
+                }
+                ValueG::Number(n) => {
+                    out.push_str(&n.to_string());
+                    Members::None
+                }
+                ValueG::String(s) => {
+                    write_string(out, s);
+                    Members::None
+                }
+            };
+            if !matches!(members, Members::None) {
+                stack.push(OpenContainer {
+                    members,
+                    order,
+                    first: true,
+                });
+            }
+        }
+
+        let member_depth = depth + stack.len();
+        let Some(open) = stack.last_mut() else {
+            return;
+        };
+        let member = match &mut open.members {
+            Members::Array(items) => items
+                .next()
+                .map(|(i, item)| (None, item, open.order.items.get(i))),
+            Members::Object(entries) => entries
+                .next()
+                .map(|(key, item, item_order)| (Some(key), item, item_order)),
+            Members::None => unreachable!("scalars are never pushed"),
+        };
+        match member {
+            Some((key, item, item_order)) => {
+                if !open.first {
+                    out.push(',');
+                }
+                open.first = false;
+                newline(out, indent, member_depth);
+                if let Some(key) = key {
+                    if keys.quotes(key) {
+                        write_string(out, key);
+                    } else {
+                        out.push_str(key);
+                    }
+                    out.push(':');
+                    if indent.is_some() {
+                        out.push(' ');
+                    }
+                }
+                next = Some((item, item_order.unwrap_or(EMPTY_ORDER)));
+            }
+            None => {
+                let close = match open.members {
+                    Members::Array(_) => ']',
+                    _ => '}',
+                };
+                stack.pop();
+                newline(out, indent, member_depth - 1);
+                out.push(close);
+            }
+        }
+    }
+}
+
+/// The order of a value whose source order is unknown.
+const EMPTY_ORDER: &KeyOrder = &KeyOrder {
+    keys: Vec::new(),
+    items: Vec::new(),
+};
+
+/// An array or object [`write_value`] has started and not yet closed.
+struct OpenContainer<'a, M: MapLike> {
+    members: Members<'a, M>,
+    order: &'a KeyOrder,
+    /// Whether no member has been written yet
+    first: bool,
+}
+
+/// The members of a container still to be written.
+enum Members<'a, M: MapLike> {
+    None,
+    Array(std::iter::Enumerate<std::slice::Iter<'a, ValueG<M>>>),
+    Object(std::vec::IntoIter<OrderedEntry<'a, M>>),
+}
+
+fn write_float(out: &mut String, f: f64, floats: FloatFormat) {
+    match floats {
+        _ if !f.is_finite() => out.push_str("null"),
+        FloatFormat::Shortest if f.fract() == 0.0 && f.abs() >= MAX_EXACT_FLOAT => {
+            // Written out in full, this would read back as an integer, which
+            // need not be the float's value
+            out.push_str(&format!("{f:e}"))
+        }
+        FloatFormat::Shortest if f == 0.0 && f.is_sign_negative() => out.push_str("-0.0"),
+        FloatFormat::Shortest => out.push_str(&f.to_string()),
+        FloatFormat::Fixed(decimals) => out.push_str(&format!("{f:.decimals$}")),
+        FloatFormat::Significant(digits) => {
+            let precision = digits.max(1) - 1;
+            let rounded = format!("{f:.precision$e}").parse().unwrap_or(f);
+            write_float(out, rounded, FloatFormat::Shortest)
+        }
+    }
+}
+
+/// A member of an object, with the order of its value if known.
+type OrderedEntry<'a, M> = (&'a String, &'a ValueG<M>, Option<&'a KeyOrder>);
+
+/// The members of `obj` in the order `order` gives, then the rest in the
+/// map's own order, or sorted if it has none.
+fn ordered_entries<'v, M: MapLike>(
+    obj: &'v M::Map<ValueG<M>>,
+    order: &'v KeyOrder,
+) -> Vec<OrderedEntry<'v, M>> {
+    // The first occurrence of a repeated key decides its place
+    let mut known: FxHashMap<&str, (usize, &KeyOrder)> = FxHashMap::default();
+    for (rank, (key, item_order)) in order.keys.iter().enumerate() {
+        known.entry(key.as_str()).or_insert((rank, item_order));
+    }
+    let mut entries: Vec<_> = M::iter(obj)
+        .map(|(key, item)| {
+            let (rank, item_order) = known.get(key.as_str()).copied().unzip();
+            (rank.unwrap_or(usize::MAX), key, item, item_order)
+        })
+        .collect();
+    if M::ORDER == MapOrder::Arbitrary {
+        entries.sort_unstable_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    } else {
+        entries.sort_by_key(|entry| entry.0);
+    }
+    entries
+        .into_iter()
+        .map(|(_, key, item, item_order)| (key, item, item_order))
+        .collect()
+}
+
+/// Writes `value` indented, with the comments in `comments` around its
+/// members and elements.
+fn write_commented(
+    out: &mut String,
+    value: &Value,
+    order: &KeyOrder,
+    comments: &CommentNode,
+    indent: usize,
+    depth: usize,
+) {
+    let (empty_order, no_comments) = (KeyOrder::default(), CommentNode::default());
+    let (open, close) = match value {
+        Value::Array(_) => ('[', ']'),
+        Value::Object(_) => ('{', '}'),
+        _ => {
+            write_value(
+                out,
+                value,
+                order,
+                Some(indent),
+                FloatFormat::Shortest,
+                KeyQuoting::Always,
+                depth,
+            );
+            return;
+        }
+    };
+    let members: Vec<(Option<&String>, &Value, &KeyOrder, &CommentNode)> = match value {
+        Value::Array(arr) => arr
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let item_order = order.items.get(i).unwrap_or(&empty_order);
+                let item_comments = comments.items.get(i).unwrap_or(&no_comments);
+                (None, item, item_order, item_comments)
+            })
+            .collect(),
+        Value::Object(obj) => ordered_entries::<Hashed>(obj, order)
+            .into_iter()
+            .map(|(key, item, item_order)| {
+                let item_order = item_order.unwrap_or(&empty_order);
+                let item_comments = comments.key(key).unwrap_or(&no_comments);
+                (Some(key), item, item_order, item_comments)
+            })
+            .collect(),
+        _ => unreachable!(),
+    };
+    if members.is_empty() && comments.closing.is_empty() {
+        out.push(open);
+        out.push(close);
+        return;
+    }
+
+    out.push(open);
+    let count = members.len();
+    for (i, (key, item, item_order, item_comments)) in members.into_iter().enumerate() {
+        write_leading(out, &item_comments.leading, indent, depth + 1);
+        newline(out, Some(indent), depth + 1);
+        if let Some(key) = key {
+            write_string(out, key);
+            out.push_str(": ");
+        }
+        write_commented(out, item, item_order, item_comments, indent, depth + 1);
+        if i + 1 < count {
+            out.push(',');
+        }
+        write_trailing(out, &item_comments.trailing);
+    }
+    write_leading(out, &comments.closing, indent, depth + 1);
+    newline(out, Some(indent), depth);
+    out.push(close);
+}
+
+/// Writes each comment on a line of its own.
+fn write_leading(out: &mut String, comments: &[String], indent: usize, depth: usize) {
+    for comment in comments {
+        newline(out, Some(indent), depth);
+        out.push_str(comment);
+    }
+}
+
+/// Writes comments at the end of the current line.
+fn write_trailing(out: &mut String, comments: &[String]) {
+    for comment in comments {
+        out.push(' ');
+        out.push_str(comment);
+    }
+}
+
+/// 2^53: integral floats from here on are written with an exponent.
+const MAX_EXACT_FLOAT: f64 = 9_007_199_254_740_992.0;
+
+fn newline(out: &mut String, indent: Option<usize>, depth: usize) {
+    if let Some(indent) = indent {
+        out.push('\n');
+        out.push_str(&" ".repeat(indent * depth));
+    }
+}
+
+/// Writes `s` as a quoted JSON string literal.
+pub(crate) fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{0008}' => out.push_str("\\b"),
+            '\u{000C}' => out.push_str("\\f"),
+            ch if ch.is_control() => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minify_preserves_key_order() {
+        let input = r#"
+            // service config
+            {
+                zeta: 1,
+                "alpha": [3, 2, {"y": true, "x": null,},],
+                'mid': "a\"b", /* trailing */
+            }
+        "#;
+        let minified = minify(input, ParserOptions::default()).unwrap();
+        assert_eq!(
+            minified,
+            r#"{"zeta":1,"alpha":[3,2,{"y":true,"x":null}],"mid":"a\"b"}"#
+        );
+    }
+
+    #[test]
+    fn test_prettify_layout() {
+        let pretty = prettify("{b: [1, {}], a: []}", 2, ParserOptions::default()).unwrap();
+        assert_eq!(
+            pretty,
+            "{\n  \"b\": [\n    1,\n    {}\n  ],\n  \"a\": []\n}"
+        );
+    }
+
+    #[test]
+    fn test_implicit_top_level_order() {
+        let minified = minify("b: 1\na: 2", ParserOptions::default()).unwrap();
+        assert_eq!(minified, r#"{"b":1,"a":2}"#);
+    }
+
+    #[test]
+    fn test_keys_without_order_are_sorted() {
+        let value = crate::parse(r#"{"b": 1, "a": 2}"#).unwrap();
+        assert_eq!(
+            to_compact_string(&value, &KeyOrder::default()),
+            r#"{"a":2,"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_minify_reports_errors() {
+        let options = ParserOptions {
+            enable_repair: false,
+            ..Default::default()
+        };
+        assert!(minify(r#"{"a": }"#, options).is_err());
+    }
+
+    #[test]
+    fn test_non_finite_floats_are_null() {
+        let value = crate::parse("[1e999, -1e999]").unwrap();
+        let order = KeyOrder::default();
+        assert_eq!(to_compact_string(&value, &order), "[null,null]");
+        let nan = Value::Number(Number::Float(f64::NAN));
+        for floats in [
+            FloatFormat::Shortest,
+            FloatFormat::Fixed(2),
+            FloatFormat::Significant(3),
+        ] {
+            assert_eq!(to_string_with_floats(&nan, None, &order, floats), "null");
+        }
+    }
+
+    #[test]
+    fn test_hex_digits_after_unicode_escape() {
+        let value = Value::String("\0ab\tc\u{1f}g".to_string());
+        let json = to_compact_string(&value, &KeyOrder::default());
+        assert_eq!(json, r#""\u0000ab\tc\u001fg""#);
+        assert_eq!(crate::parse(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let text = "{\n  // one\r\n  \"a\": 1\n}\r";
+        assert_eq!(
+            LineEnding::CrLf.convert(text),
+            "{\r\n  // one\r\n  \"a\": 1\r\n}\r"
+        );
+        assert_eq!(LineEnding::Lf.convert(text), "{\n  // one\n  \"a\": 1\n}\r");
+        assert_eq!(LineEnding::CrLf.convert(""), "");
+    }
+
+    #[test]
+    fn test_float_formats() {
+        let floats = |values: &[f64], format| {
+            let value = Value::Array(
+                values
+                    .iter()
+                    .map(|f| Value::Number(Number::Float(*f)))
+                    .collect(),
+            );
+            to_string_with_floats(&value, None, &KeyOrder::default(), format)
+        };
+        let values = [0.1, 0.1 + 0.2, 2.0 / 3.0, -1.005, 12345.678];
+
+        assert_eq!(
+            floats(&values, FloatFormat::Shortest),
+            "[0.1,0.30000000000000004,0.6666666666666666,-1.005,12345.678]"
+        );
+        assert_eq!(
+            floats(&values, FloatFormat::Fixed(2)),
+            "[0.10,0.30,0.67,-1.00,12345.68]"
+        );
+        assert_eq!(
+            floats(&values, FloatFormat::Significant(3)),
+            "[0.1,0.3,0.667,-1,12300]"
+        );
+        assert_eq!(
+            floats(&[0.5, 1e-7], FloatFormat::Significant(0)),
+            "[0.5,0.0000001]"
+        );
+
+        // Integers are left alone
+        let value = Value::Number(Number::Integer(123456789));
+        let order = KeyOrder::default();
+        assert_eq!(
+            to_string_with_floats(&value, None, &order, FloatFormat::Fixed(2)),
+            "123456789"
+        );
+        assert_eq!(
+            to_string_with_floats(&value, None, &order, FloatFormat::Significant(2)),
+            "123456789"
+        );
+    }
+
+    #[test]
+    fn test_large_integral_floats_use_an_exponent() {
+        let value = Value::Array(vec![
+            Value::Number(Number::Float(2.0)),
+            Value::Number(Number::Float(-8.104909490768182e18)),
+            Value::Number(Number::Float(1e300)),
+        ]);
+        assert_eq!(
+            to_compact_string(&value, &KeyOrder::default()),
+            "[2,-8.104909490768182e18,1e300]"
+        );
+    }
+
+    fn pretty_with_comments(input: &str) -> String {
+        let value = parse_for_format(input, ParserOptions::default()).unwrap();
+        to_pretty_string_with_comments(
+            &value,
+            2,
+            &KeyOrder::from_source(input),
+            &SourceComments::from_source(input),
+        )
+    }
+
+    #[test]
+    fn test_comments_kept_by_their_values() {
+        let input = "// Service settings\n{name: 'svc', // display name\n  /* Timeouts,\n     in ms */\n  timeouts: [100, // connect\n 250,\n // more later\n], nested: {a: 1 /* one */}, empty: {\n// none yet\n},\n  // last\n} // done\n// end of file\n";
+        assert_eq!(
+            pretty_with_comments(input),
+            "// Service settings\n{\n  \"name\": \"svc\", // display name\n  /* Timeouts,\n     in ms */\n  \"timeouts\": [\n    100, // connect\n    250\n    // more later\n  ],\n  \"nested\": {\n    \"a\": 1 /* one */\n  },\n  \"empty\": {\n    // none yet\n  }\n  // last\n} // done\n// end of file"
+        );
+    }
+
+    #[test]
+    fn test_comments_in_implicit_top_level() {
+        assert_eq!(
+            pretty_with_comments("// first\na: 1 // one\n\n// second\nb: [true]\n"),
+            "{\n  // first\n  \"a\": 1, // one\n  // second\n  \"b\": [\n    true\n  ]\n}"
+        );
+        assert_eq!(
+            pretty_with_comments("1, // one\n2 /* two */"),
+            "[\n  1, // one\n  2 /* two */\n]"
+        );
+    }
+
+    #[test]
+    fn test_without_comments_matches_pretty() {
+        let input = "{b: [1, {}], a: [], c: {d: null}}";
+        assert!(SourceComments::from_source(input).is_empty());
+        assert_eq!(
+            pretty_with_comments(input),
+            prettify(input, 2, ParserOptions::default()).unwrap()
+        );
+        assert!(!SourceComments::from_source("1 // x").is_empty());
+    }
+
+    const JSONC: &str = "// Service settings\n{\n    \"name\": \"svc // not a comment\", // display name\n    /* Timeouts,\n       in ms */\n    \"timeouts\": [100, 250,], \"url\": \"http://x/*y*/\",\n    \"n\": 1/* inline */2,\n}\n";
+
+    #[test]
+    fn test_strip_comments() {
+        let stripped = strip_comments(JSONC, &StripCommentsOptions::default()).unwrap();
+        assert_eq!(
+            stripped,
+            "{\n    \"name\": \"svc // not a comment\",\n    \"timeouts\": [100, 250], \"url\": \"http://x/*y*/\",\n    \"n\": 1 2\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_strip_comments_keeping_newlines() {
+        let options = StripCommentsOptions {
+            remove_trailing_commas: false,
+            keep_newlines: true,
+            ..Default::default()
+        };
+        let stripped = strip_comments(JSONC, &options).unwrap();
+        assert_eq!(stripped.lines().count(), JSONC.lines().count());
+        assert_eq!(
+            stripped,
+            "\n{\n    \"name\": \"svc // not a comment\",\n\n\n    \"timeouts\": [100, 250,], \"url\": \"http://x/*y*/\",\n    \"n\": 1 2,\n}\n"
+        );
+
+        let crlf = "[1, /* a\r\nb */ 2]\r\n";
+        assert_eq!(strip_comments(crlf, &options).unwrap(), "[1, \r\n 2]\r\n");
+    }
+
+    #[test]
+    fn test_strip_comments_output_is_standard_json() {
+        let input = "{\"a\": [1, 2, /* last */], // end\n \"b\": {\"c\": null,},}";
+        let stripped = strip_comments(input, &StripCommentsOptions::default()).unwrap();
+        assert_eq!(stripped, "{\"a\": [1, 2 ],\n \"b\": {\"c\": null}}");
+        assert!(serde_json::from_str::<serde_json::Value>(&stripped).is_ok());
+    }
+
+    #[test]
+    fn test_strip_unterminated_comment() {
+        let error = strip_comments("[1, /* open", &StripCommentsOptions::default()).unwrap_err();
+        assert_eq!(error.position(), Some(4));
+        assert!(matches!(error, Error::Expected { .. }));
+    }
+
+    #[test]
+    fn test_write_deeply_nested_document() {
+        let depth = 50_000;
+        let mut value = Value::Array(vec![Value::Object(FxHashMap::from_iter([(
+            "a".to_string(),
+            Value::Null,
+        )]))]);
+        for _ in 1..depth {
+            value = Value::Array(vec![value]);
+        }
+
+        let compact = to_compact_string(&value, &KeyOrder::default());
+        assert_eq!(
+            compact,
+            format!("{}{{\"a\":null}}{}", "[".repeat(depth), "]".repeat(depth))
+        );
+        let pretty = to_pretty_string(&value, 0, &KeyOrder::default());
+        assert_eq!(pretty.lines().count(), 2 * depth + 3);
+        assert_eq!(pretty.lines().nth(depth + 1), Some("\"a\": null"));
+        assert_eq!(value.to_string(), compact);
+        let order = KeyOrder::from_source(&compact);
+        assert_eq!(to_compact_string(&value, &order), compact);
+        value.drop_iteratively();
+    }
+
+    #[test]
+    fn test_json5_key_quoting() {
+        let input = r#"{"validKey": 1, "123abc": {"_a$1": [true], "a-b": 2, "": 3, "null": 4}}"#;
+        let value = crate::parse(input).unwrap();
+        let order = KeyOrder::from_source(input);
+
+        let minimal = to_json5_string(&value, None, &order, KeyQuoting::MinimalJson5);
+        assert_eq!(
+            minimal,
+            r#"{validKey:1,"123abc":{_a$1:[true],"a-b":2,"":3,"null":4}}"#
+        );
+        assert_eq!(crate::parse(&minimal).unwrap(), value);
+        assert_eq!(
+            to_json5_string(&value, Some(2), &order, KeyQuoting::MinimalJson5),
+            "{\n  validKey: 1,\n  \"123abc\": {\n    _a$1: [\n      true\n    ],\n    \"a-b\": 2,\n    \"\": 3,\n    \"null\": 4\n  }\n}"
+        );
+
+        // Standard JSON output quotes every key
+        let always = to_json5_string(&value, None, &order, KeyQuoting::Always);
+        assert_eq!(always, to_compact_string(&value, &order));
+        assert!(always.starts_with(r#"{"validKey":1,"123abc":{"_a$1""#));
+    }
+}
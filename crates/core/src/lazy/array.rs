@@ -2,70 +2,192 @@
 
 use crate::ast::{Token, Value};
 use crate::error::{Error, Result, Span};
-use crate::lazy::{LazyParser, LazyValue};
-use std::sync::Arc;
+use crate::lazy::{defer_value, LazyParser, LazyValue};
+use crate::parser::ParserOptions;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 /// A lazy JSON array that parses elements on-demand.
+///
+/// Arrays read with [`LazyArray::parse`] also locate their elements on
+/// demand: finding element `n` scans the source only as far as `n`, and
+/// the offsets found are kept for later lookups.
 #[derive(Debug, Clone)]
 pub struct LazyArray {
-    /// Vector of lazy values
-    pub(super) elements: Vec<LazyValue>,
+    /// Elements found so far, shared between clones
+    scan: Arc<Mutex<ArrayScan>>,
     /// The original input for deferred parsing
-    #[allow(dead_code)]
     pub(super) input: Arc<str>,
     /// Parser options
-    #[allow(dead_code)]
-    pub(super) options: crate::parser::ParserOptions,
+    pub(super) options: ParserOptions,
+}
+
+/// Progress through the source text of a [`LazyArray`].
+#[derive(Debug)]
+struct ArrayScan {
+    elements: Vec<LazyValue>,
+    /// Offset just past the last element found, or `None` once the whole
+    /// array has been scanned
+    resume: Option<usize>,
+    /// Error that stopped the scan early
+    error: Option<Error>,
+    lazy_threshold: usize,
 }
 
 impl LazyArray {
     /// Creates a new lazy array.
-    pub fn new(input: Arc<str>, options: crate::parser::ParserOptions) -> Self {
+    pub fn new(input: Arc<str>, options: ParserOptions) -> Self {
         LazyArray {
-            elements: Vec::new(),
+            scan: Arc::new(Mutex::new(ArrayScan {
+                elements: Vec::new(),
+                resume: None,
+                error: None,
+                lazy_threshold: 0,
+            })),
             input,
             options,
         }
     }
 
+    /// Reads the array in `input`, locating elements only as they are
+    /// accessed.
+    ///
+    /// Elements shorter than the default lazy threshold are parsed as soon
+    /// as they are found; use [`LazyParser::parse_lazy_array`] to choose the
+    /// threshold.
+    pub fn parse(input: &str, options: ParserOptions) -> Result<Self> {
+        LazyParser::new(input, options).parse_lazy_array()
+    }
+
     /// Adds an element to the lazy array.
     pub fn push(&mut self, value: LazyValue) {
-        self.elements.push(value);
+        self.scan_to(usize::MAX).elements.push(value);
     }
 
-    /// Gets an element by index, evaluating it lazily if needed.
-    pub fn get(&self, index: usize) -> Option<Result<Value>> {
-        self.elements.get(index).map(|lazy_val| lazy_val.evaluate())
+    /// Gets an element by index without evaluating it.
+    ///
+    /// Only the elements up to `index` are located. The returned value
+    /// shares its cache with this array, so evaluating it resolves the
+    /// element here too.
+    pub fn get(&self, index: usize) -> Option<LazyValue> {
+        self.scan_to(index).elements.get(index).cloned()
     }
 
-    /// Gets an element without evaluating it.
-    pub fn get_lazy(&self, index: usize) -> Option<&LazyValue> {
-        self.elements.get(index)
+    /// Gets an element by index, evaluating it if needed.
+    pub fn get_value(&self, index: usize) -> Option<Result<Value>> {
+        self.get(index).map(|lazy_val| lazy_val.evaluate())
     }
 
-    /// Returns the length of the array.
+    /// Returns the length of the array, locating but not evaluating every
+    /// element.
+    ///
+    /// Malformed source text ends the array early; [`evaluate_all`](Self::evaluate_all)
+    /// reports the error.
     pub fn len(&self) -> usize {
-        self.elements.len()
+        self.scan_to(usize::MAX).elements.len()
     }
 
     /// Checks if the array is empty.
     pub fn is_empty(&self) -> bool {
-        self.elements.is_empty()
+        self.get(0).is_none()
     }
 
-    /// Returns an iterator over the lazy values.
-    pub fn iter(&self) -> impl Iterator<Item = &LazyValue> {
-        self.elements.iter()
+    /// Returns an iterator over the lazy values, locating them as it goes.
+    pub fn iter(&self) -> impl Iterator<Item = LazyValue> + '_ {
+        (0..).map_while(|index| self.get(index))
     }
 
     /// Forces evaluation of all elements and returns a regular Value::Array.
     pub fn evaluate_all(&self) -> Result<Value> {
+        let scan = self.scan_to(usize::MAX);
+        if let Some(error) = &scan.error {
+            return Err(error.clone());
+        }
         let mut resolved_arr = Vec::new();
-        for lazy_val in &self.elements {
+        for lazy_val in &scan.elements {
             resolved_arr.push(lazy_val.evaluate()?);
         }
         Ok(Value::Array(resolved_arr))
     }
+
+    /// Returns the number of elements located so far.
+    pub fn located(&self) -> usize {
+        self.lock_scan().elements.len()
+    }
+
+    fn lock_scan(&self) -> MutexGuard<'_, ArrayScan> {
+        // Each step of a scan leaves it consistent, so one interrupted by a
+        // panic can be resumed
+        self.scan.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locates elements until the one at `index` is found or the array
+    /// ends.
+    fn scan_to(&self, index: usize) -> MutexGuard<'_, ArrayScan> {
+        let mut scan = self.lock_scan();
+        while scan.elements.len() <= index {
+            let Some(offset) = scan.resume else {
+                break;
+            };
+            let first = scan.elements.is_empty();
+            match self.next_element(offset, first, scan.lazy_threshold) {
+                Ok(Some((value, end))) => {
+                    scan.elements.push(value);
+                    scan.resume = Some(end);
+                }
+                Ok(None) => scan.resume = None,
+                Err(error) => {
+                    scan.resume = None;
+                    scan.error = Some(error);
+                }
+            }
+        }
+        scan
+    }
+
+    /// Locates the element after `offset`, returning it with the offset of
+    /// its end, or `None` at the closing bracket.
+    fn next_element(
+        &self,
+        offset: usize,
+        first: bool,
+        lazy_threshold: usize,
+    ) -> Result<Option<(LazyValue, usize)>> {
+        let mut parser = LazyParser::new(&self.input[offset..], self.options.clone());
+        let mut separated = first;
+        let mut comma = false;
+        loop {
+            let (token, span) = parser.next_token()?;
+            match token {
+                Token::RightBracket if comma && !self.options.allow_trailing_commas => {
+                    return Err(Error::Expected {
+                        expected: "value".to_string(),
+                        found: format!("{token:?}"),
+                        position: offset + span.start,
+                    });
+                }
+                Token::RightBracket => return Ok(None),
+                Token::Newline => separated |= self.options.newline_as_comma,
+                Token::Comma if !separated => {
+                    separated = true;
+                    comma = true;
+                }
+                Token::Eof => return Err(Error::UnexpectedEof(offset + span.start)),
+                _ if separated && token != Token::Comma => {
+                    let span = parser.skip_value(token, span)?;
+                    let span = Span::new(offset + span.start, offset + span.end);
+                    let value = defer_value(&self.input, span, &self.options, lazy_threshold)?;
+                    return Ok(Some((value, span.end)));
+                }
+                _ => {
+                    return Err(Error::Expected {
+                        expected: if separated { "value" } else { "comma or ]" }.to_string(),
+                        found: format!("{token:?}"),
+                        position: offset + span.start,
+                    });
+                }
+            }
+        }
+    }
 }
 
 impl<'a> LazyParser<'a> {
@@ -78,6 +200,29 @@ impl<'a> LazyParser<'a> {
 
 
 
+    /// Reads a top-level array, locating its elements only as they are
+    /// accessed. Elements at least as long as the lazy threshold are
+    /// deferred.
+    pub fn parse_lazy_array(&mut self) -> Result<LazyArray> {
+        self.skip_newlines()?;
+        let (token, span) = self.next_token()?;
+        if token != Token::LeftBracket {
+            return Err(Error::Expected {
+                expected: "[".to_string(),
+                found: format!("{token:?}"),
+                position: span.start,
+            });
+        }
+
+        let array = LazyArray::new(Arc::from(self.input), self.options.clone());
+        {
+            let mut scan = array.lock_scan();
+            scan.resume = Some(span.end);
+            scan.lazy_threshold = self.lazy_threshold;
+        }
+        Ok(array)
+    }
+
     /// Parses an array immediately using standard parsing.
     pub(super) fn parse_array_immediate(&mut self) -> Result<Value> {
         let mut array = Vec::new();
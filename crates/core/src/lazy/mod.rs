@@ -5,7 +5,7 @@ use crate::error::{Error, Result, Span};
 use crate::lexer::Lexer;
 use crate::parser::ParserOptions;
 use rustc_hash::FxHashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 /// Lazy array parsing and iteration.
 pub mod array;
@@ -20,6 +20,9 @@ pub use array::LazyArray;
 pub use object::LazyObject;
 
 /// A lazily-evaluated JSON value that is parsed on-demand.
+///
+/// Clones share the cache behind a mutex, so a value resolved on one thread
+/// is not parsed again on another.
 #[derive(Debug, Clone)]
 pub enum LazyValue {
     /// An already-parsed value
@@ -77,7 +80,7 @@ impl LazyValue {
             } => {
                 // Check cache first
                 {
-                    let cache_guard = cache.lock().unwrap();
+                    let cache_guard = lock_cache(cache);
                     if let Some(cached_value) = cache_guard.as_ref() {
                         return Ok(cached_value.clone());
                     }
@@ -94,7 +97,7 @@ impl LazyValue {
 
                 // Cache the result
                 {
-                    let mut cache_guard = cache.lock().unwrap();
+                    let mut cache_guard = lock_cache(cache);
                     *cache_guard = Some(resolved_value.clone());
                 }
 
@@ -133,11 +136,17 @@ impl LazyValue {
     pub fn try_get_resolved(&self) -> Option<Value> {
         match self {
             LazyValue::Resolved(value) => Some(value.clone()),
-            LazyValue::Deferred { cache, .. } => cache.lock().unwrap().clone(),
+            LazyValue::Deferred { cache, .. } => lock_cache(cache).clone(),
         }
     }
 }
 
+/// Locks the cache of a deferred value, which a panic while it was held
+/// cannot have left half-written.
+fn lock_cache(cache: &Mutex<Option<Value>>) -> MutexGuard<'_, Option<Value>> {
+    cache.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
 impl<'a> LazyParser<'a> {
     /// Creates a new lazy parser.
     pub fn new(input: &'a str, options: ParserOptions) -> Self {
@@ -173,6 +182,39 @@ impl<'a> LazyParser<'a> {
             .map(|&(token, span)| (token, span))
     }
 
+    /// Consumes newline tokens.
+    pub(super) fn skip_newlines(&mut self) -> Result<()> {
+        while self.peek_token()?.0 == Token::Newline {
+            self.next_token()?;
+        }
+        Ok(())
+    }
+
+    /// Consumes the value starting with `token` and returns its span without
+    /// parsing it.
+    ///
+    /// Containers are matched by nesting depth alone; their contents are
+    /// checked when the value is evaluated.
+    pub(super) fn skip_value(&mut self, token: Token, span: Span) -> Result<Span> {
+        let mut depth = match token {
+            Token::LeftBrace | Token::LeftBracket => 1usize,
+            Token::Eof => return Err(Error::UnexpectedEof(span.start)),
+            _ => return Ok(span),
+        };
+        let mut end = span.end;
+        while depth > 0 {
+            let (token, token_span) = self.next_token()?;
+            match token {
+                Token::LeftBrace | Token::LeftBracket => depth += 1,
+                Token::RightBrace | Token::RightBracket => depth -= 1,
+                Token::Eof => return Err(Error::UnexpectedEof(token_span.start)),
+                _ => {}
+            }
+            end = token_span.end;
+        }
+        Ok(Span::new(span.start, end))
+    }
+
     /// Parses a value, deciding whether to evaluate immediately or defer.
     pub(super) fn parse_value(&mut self, token: Token, span: Span) -> Result<Value> {
         match token {
@@ -191,6 +233,22 @@ impl<'a> LazyParser<'a> {
     }
 }
 
+/// Defers the value at `span` of `input`, or parses it straight away if it
+/// is shorter than `lazy_threshold` bytes.
+pub(super) fn defer_value(
+    input: &Arc<str>,
+    span: Span,
+    options: &ParserOptions,
+    lazy_threshold: usize,
+) -> Result<LazyValue> {
+    let value = LazyValue::deferred(Arc::clone(input), span, options.clone());
+    if span.end - span.start < lazy_threshold {
+        Ok(LazyValue::resolved(value.evaluate()?))
+    } else {
+        Ok(value)
+    }
+}
+
 /// Creates a lazy parser and parses the input with lazy evaluation.
 pub fn parse_lazy(input: &str) -> Result<Value> {
     let mut parser = LazyParser::new(input, ParserOptions::default());
@@ -268,4 +326,89 @@ mod tests {
             _ => panic!("Expected array"),
         }
     }
+
+    #[test]
+    fn test_lazy_object_resolves_only_accessed_field() {
+        let input = r#"{"a": {"x": [1, 2]}, "b": [1, 2, 3], c: "s"}"#;
+        let mut parser = LazyParser::new(input, ParserOptions::default());
+        parser.set_lazy_threshold(0);
+        let object = parser.parse_lazy_object().unwrap();
+
+        let mut keys: Vec<_> = object.keys().collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["a", "b", "c"]);
+        assert!(object.get("missing").is_none());
+        for key in ["a", "b", "c"] {
+            assert!(object.get_lazy(key).unwrap().try_get_resolved().is_none());
+        }
+
+        let b = object.get("b").unwrap().evaluate().unwrap();
+        assert_eq!(b, parse_lazy("[1, 2, 3]").unwrap());
+        assert_eq!(object.get_lazy("b").unwrap().try_get_resolved(), Some(b));
+        assert!(object.get_lazy("a").unwrap().try_get_resolved().is_none());
+        assert!(object.get_lazy("c").unwrap().try_get_resolved().is_none());
+    }
+
+    #[test]
+    fn test_lazy_object_threshold() {
+        let big = vec!["0"; 1000].join(", ");
+        let input = format!(r#"{{"small": 1, "big": [{big}]}}"#);
+        let object = LazyObject::parse(&input, ParserOptions::default()).unwrap();
+
+        assert!(object.get("small").unwrap().is_resolved());
+        let big = object.get("big").unwrap();
+        assert!(!big.is_resolved());
+        match big.evaluate().unwrap() {
+            Value::Array(items) => assert_eq!(items.len(), 1000),
+            other => panic!("Expected array, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_lazy_object_unterminated_value() {
+        let result = LazyObject::parse(r#"{"a": [1, {"b": 2}"#, ParserOptions::default());
+        assert!(matches!(result, Err(Error::UnexpectedEof(_))));
+    }
+
+    #[test]
+    fn test_lazy_array_random_access() {
+        let items: Vec<_> = (0..10_000).map(|i| format!(r#"{{"i": {i}}}"#)).collect();
+        let input = format!("[{}]", items.join(", "));
+        let mut parser = LazyParser::new(&input, ParserOptions::default());
+        parser.set_lazy_threshold(0);
+        let array = parser.parse_lazy_array().unwrap();
+        assert_eq!(array.located(), 0);
+
+        let element = array.get(5000).unwrap();
+        assert_eq!(array.located(), 5001);
+        assert!(element.try_get_resolved().is_none());
+        assert_eq!(
+            element.evaluate().unwrap()["i"],
+            Value::Number(crate::ast::Number::Integer(5000))
+        );
+        assert!(array.get(5000).unwrap().try_get_resolved().is_some());
+        assert!(array.get(4999).unwrap().try_get_resolved().is_none());
+        assert_eq!(array.located(), 5001);
+
+        assert!(array.get(10_000).is_none());
+        assert_eq!(array.len(), 10_000);
+        assert!(array.get(9999).unwrap().try_get_resolved().is_none());
+    }
+
+    #[test]
+    fn test_lazy_array_separators_and_errors() {
+        let array = LazyArray::parse("[1,\n [2, 3]\n 'x',\n]", ParserOptions::default()).unwrap();
+        assert_eq!(array.len(), 3);
+        assert_eq!(
+            array.evaluate_all().unwrap(),
+            parse_lazy("[1, [2, 3], 'x']").unwrap()
+        );
+        let empty = LazyArray::parse("[]", ParserOptions::default()).unwrap();
+        assert!(empty.is_empty());
+
+        let array = LazyArray::parse("[1, 2 3]", ParserOptions::default()).unwrap();
+        assert_eq!(array.len(), 2);
+        assert!(array.get(2).is_none());
+        assert!(array.evaluate_all().is_err());
+    }
 }
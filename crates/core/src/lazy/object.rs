@@ -2,7 +2,8 @@
 
 use crate::ast::{Token, Value};
 use crate::error::{Error, Result, Span};
-use crate::lazy::{LazyParser, LazyValue};
+use crate::lazy::{defer_value, LazyParser, LazyValue};
+use crate::parser::ParserOptions;
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
 
@@ -16,12 +17,12 @@ pub struct LazyObject {
     pub(super) input: Arc<str>,
     /// Parser options
     #[allow(dead_code)]
-    pub(super) options: crate::parser::ParserOptions,
+    pub(super) options: ParserOptions,
 }
 
 impl LazyObject {
     /// Creates a new lazy object.
-    pub fn new(input: Arc<str>, options: crate::parser::ParserOptions) -> Self {
+    pub fn new(input: Arc<str>, options: ParserOptions) -> Self {
         LazyObject {
             fields: FxHashMap::default(),
             input,
@@ -34,8 +35,25 @@ impl LazyObject {
         self.fields.insert(key, value);
     }
 
-    /// Gets a field by key, evaluating it lazily if needed.
-    pub fn get(&self, key: &str) -> Option<Result<Value>> {
+    /// Scans the object in `input`, locating each member's value without
+    /// parsing it.
+    ///
+    /// Values shorter than the default lazy threshold are parsed straight
+    /// away; use [`LazyParser::parse_lazy_object`] to choose the threshold.
+    pub fn parse(input: &str, options: ParserOptions) -> Result<Self> {
+        LazyParser::new(input, options).parse_lazy_object()
+    }
+
+    /// Gets a field by key without evaluating it.
+    ///
+    /// The returned value shares its cache with this object, so evaluating
+    /// it resolves the field here too.
+    pub fn get(&self, key: &str) -> Option<LazyValue> {
+        self.fields.get(key).cloned()
+    }
+
+    /// Gets a field by key, evaluating it if needed.
+    pub fn get_value(&self, key: &str) -> Option<Result<Value>> {
         self.fields.get(key).map(|lazy_val| lazy_val.evaluate())
     }
 
@@ -45,8 +63,18 @@ impl LazyObject {
     }
 
     /// Lists all available keys without evaluating values.
-    pub fn keys(&self) -> impl Iterator<Item = &String> {
-        self.fields.keys()
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.fields.keys().map(String::as_str)
+    }
+
+    /// Returns the number of fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Checks if the object has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
     }
 
     /// Checks if a key exists without evaluating the value.
@@ -74,83 +102,103 @@ impl<'a> LazyParser<'a> {
 
 
 
+    /// Parses a top-level object, deferring member values at least as long
+    /// as the lazy threshold.
+    pub fn parse_lazy_object(&mut self) -> Result<LazyObject> {
+        let input: Arc<str> = Arc::from(self.input);
+        let mut object = LazyObject::new(Arc::clone(&input), self.options.clone());
+
+        self.skip_newlines()?;
+        let (token, span) = self.next_token()?;
+        if token != Token::LeftBrace {
+            return Err(Error::Expected {
+                expected: "{".to_string(),
+                found: format!("{token:?}"),
+                position: span.start,
+            });
+        }
+
+        let mut first = true;
+        while let Some(key) = self.next_member_key(first)? {
+            first = false;
+            let (value_token, value_span) = self.next_token()?;
+            let value_span = self.skip_value(value_token, value_span)?;
+            let value = defer_value(&input, value_span, &self.options, self.lazy_threshold)?;
+            object.insert(key, value);
+        }
+
+        Ok(object)
+    }
+
     /// Parses an object immediately using standard parsing.
     pub(super) fn parse_object_immediate(&mut self) -> Result<Value> {
         let mut object = FxHashMap::default();
-        let mut first = true;
 
-        loop {
-            // Skip newlines
-            loop {
-                let (next_token, _) = self.peek_token()?;
-                if next_token == Token::Newline {
-                    self.next_token()?;
-                } else {
-                    break;
-                }
-            }
+        let mut first = true;
+        while let Some(key) = self.next_member_key(first)? {
+            first = false;
+            let (value_token, value_span) = self.next_token()?;
+            let value = self.parse_value(value_token, value_span)?;
+            object.insert(key, value);
+        }
 
-            // Check for end of object
-            let (next_token, _) = self.peek_token()?;
-            if next_token == Token::RightBrace {
-                self.next_token()?;
-                break;
-            }
+        Ok(Value::Object(object))
+    }
 
-            // Handle comma between elements
-            if !first {
-                let (token, span) = self.next_token()?;
-                match token {
-                    Token::Comma => {}
-                    Token::Newline if self.options.newline_as_comma => {}
-                    Token::RightBrace if self.options.allow_trailing_commas => break,
-                    _ => {
-                        return Err(Error::Expected {
-                            expected: "comma or }".to_string(),
-                            found: format!("{token:?}"),
-                            position: span.start,
-                        });
-                    }
-                }
-            }
-            first = false;
+    /// Consumes the separator, key and colon of the next object member,
+    /// returning `None` once the closing brace has been consumed.
+    fn next_member_key(&mut self, first: bool) -> Result<Option<String>> {
+        self.skip_newlines()?;
 
-            // Parse key
-            let (key_token, key_span) = self.next_token()?;
-            let key = match key_token {
-                Token::String => self.parse_string_content(key_span)?,
-                Token::UnquotedString if self.options.allow_unquoted_keys => {
-                    self.input[key_span.start..key_span.end].to_string()
-                }
-                _ => {
-                    return Err(Error::Expected {
-                        expected: "string key".to_string(),
-                        found: format!("{key_token:?}"),
-                        position: key_span.start,
-                    });
-                }
-            };
+        // Check for end of object
+        let (next_token, _) = self.peek_token()?;
+        if next_token == Token::RightBrace {
+            self.next_token()?;
+            return Ok(None);
+        }
 
-            // Expect colon
-            let (colon_token, colon_span) = self.next_token()?;
-            match colon_token {
-                Token::Colon => {}
+        // Handle comma between elements
+        if !first {
+            let (token, span) = self.next_token()?;
+            match token {
+                Token::Comma => {}
+                Token::Newline if self.options.newline_as_comma => {}
+                Token::RightBrace if self.options.allow_trailing_commas => return Ok(None),
                 _ => {
                     return Err(Error::Expected {
-                        expected: "colon".to_string(),
-                        found: format!("{colon_token:?}"),
-                        position: colon_span.start,
+                        expected: "comma or }".to_string(),
+                        found: format!("{token:?}"),
+                        position: span.start,
                     });
                 }
             }
-
-            // Parse value
-            let (value_token, value_span) = self.next_token()?;
-            let value = self.parse_value(value_token, value_span)?;
-
-            object.insert(key, value);
         }
 
-        Ok(Value::Object(object))
+        // Parse key
+        let (key_token, key_span) = self.next_token()?;
+        let key = match key_token {
+            Token::String => self.parse_string_content(key_span)?,
+            Token::UnquotedString if self.options.allow_unquoted_keys => {
+                self.input[key_span.start..key_span.end].to_string()
+            }
+            _ => {
+                return Err(Error::Expected {
+                    expected: "string key".to_string(),
+                    found: format!("{key_token:?}"),
+                    position: key_span.start,
+                });
+            }
+        };
+
+        // Expect colon
+        let (colon_token, colon_span) = self.next_token()?;
+        match colon_token {
+            Token::Colon => Ok(Some(key)),
+            _ => Err(Error::Expected {
+                expected: "colon".to_string(),
+                found: format!("{colon_token:?}"),
+                position: colon_span.start,
+            }),
+        }
     }
 }
@@ -38,7 +38,12 @@ impl<'a> FastLexer<'a> {
 
         FastLexer {
             input: input.as_bytes(),
-            position: 0,
+            // A leading byte order mark is always whitespace
+            position: if input.starts_with('\u{FEFF}') {
+                '\u{FEFF}'.len_utf8()
+            } else {
+                0
+            },
             peeked: None,
             config,
             stats: LexerStats::default(),
@@ -57,12 +62,31 @@ impl<'a> FastLexer<'a> {
                     self.position += 1;
                     // Newline handling for newline_as_comma would go here
                 }
+                0x80.. if self.config.unicode_whitespace => {
+                    let ch = self.current_char();
+                    if !is_unicode_whitespace(ch) {
+                        break;
+                    }
+                    self.position += ch.len_utf8();
+                }
                 _ => break,
             }
         }
         self.position > start
     }
 
+    /// Decodes the character starting at the current position.
+    fn current_char(&self) -> char {
+        let end = (self.position + 4).min(self.input.len());
+        let bytes = &self.input[self.position..end];
+        let valid = match std::str::from_utf8(bytes) {
+            Ok(text) => text,
+            // The window may cut off the character after this one
+            Err(error) => std::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap_or(""),
+        };
+        valid.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
     /// Parse a string token
     #[inline]
     fn parse_string(&mut self, quote: u8) -> Result<(Token, Span)> {
@@ -247,6 +271,7 @@ impl<'a> FastLexer<'a> {
                 b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'$' | b'-' => {
                     self.position += 1;
                 }
+                b'#' if !self.hash_comments() => self.position += 1,
                 _ => break,
             }
         }
@@ -264,6 +289,12 @@ impl<'a> FastLexer<'a> {
         Ok((token, Span::new(start, self.position)))
     }
 
+    /// Whether `#` starts a comment rather than being part of a word
+    #[inline]
+    fn hash_comments(&self) -> bool {
+        self.config.mode != crate::lexer::LexerMode::Strict && self.config.comment_styles.hash
+    }
+
     /// Skip single-line comment
     #[inline]
     fn skip_single_line_comment(&mut self) {
@@ -275,14 +306,18 @@ impl<'a> FastLexer<'a> {
         }
     }
 
-    /// Skip multi-line comment
+    /// Skip multi-line comment, nested ones too if
+    /// [`LexerConfig::nested_comments`] is enabled
     #[inline]
     fn skip_multi_line_comment(&mut self) -> Result<()> {
         let start = self.position - 2; // We already consumed /*
         let mut depth = 1;
 
         while self.position + 1 < self.input.len() {
-            if self.input[self.position] == b'/' && self.input[self.position + 1] == b'*' {
+            if self.config.nested_comments
+                && self.input[self.position] == b'/'
+                && self.input[self.position + 1] == b'*'
+            {
                 self.position += 2;
                 depth += 1;
             } else if self.input[self.position] == b'*' && self.input[self.position + 1] == b'/' {
@@ -299,6 +334,19 @@ impl<'a> FastLexer<'a> {
         Err(Error::UnexpectedEof(start))
     }
 
+    /// Enforces [`LexerConfig::max_comment_length`] on the comment that
+    /// started at `start` and ends at the current position
+    fn check_comment_length(&self, start: usize) -> Result<()> {
+        match self.config.max_comment_length {
+            Some(max) if self.position - start > max => Err(Error::LimitExceeded {
+                what: "comment length".to_string(),
+                limit: max,
+                position: start,
+            }),
+            _ => Ok(()),
+        }
+    }
+
     /// Get next token implementation
     fn next_token_impl(&mut self) -> Result<(Token, Span)> {
         loop {
@@ -348,14 +396,22 @@ impl<'a> FastLexer<'a> {
                 b'/' => {
                     if self.position + 1 < self.input.len() {
                         match self.input[self.position + 1] {
-                            b'/' if self.config.mode != crate::lexer::LexerMode::Strict => {
+                            b'/' if self.config.mode != crate::lexer::LexerMode::Strict
+                                && self.config.comment_styles.line_slash =>
+                            {
+                                let start = self.position;
                                 self.position += 2;
                                 self.skip_single_line_comment();
+                                self.check_comment_length(start)?;
                                 continue; // Skip comment and continue
                             }
-                            b'*' if self.config.mode != crate::lexer::LexerMode::Strict => {
+                            b'*' if self.config.mode != crate::lexer::LexerMode::Strict
+                                && self.config.comment_styles.block =>
+                            {
+                                let start = self.position;
                                 self.position += 2;
                                 self.skip_multi_line_comment()?;
+                                self.check_comment_length(start)?;
                                 continue; // Skip comment and continue
                             }
                             _ => {}
@@ -363,24 +419,33 @@ impl<'a> FastLexer<'a> {
                     }
                     return Err(Error::UnexpectedChar('/', self.position));
                 }
-                b'#' if self.config.mode != crate::lexer::LexerMode::Strict => {
+                b'#' if self.hash_comments() => {
+                    let start = self.position;
                     self.position += 1;
                     self.skip_single_line_comment();
+                    self.check_comment_length(start)?;
                     continue; // Skip comment and continue
                 }
-                b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'$' => {
-                    if self.config.mode != crate::lexer::LexerMode::Strict {
-                        return self.parse_identifier();
-                    } else {
-                        return Err(Error::UnexpectedChar(ch as char, self.position));
-                    }
+                b'a'..=b'z' | b'A'..=b'Z' | b'_' | b'$' | b'#'
+                    if self.config.mode != crate::lexer::LexerMode::Strict =>
+                {
+                    return self.parse_identifier();
                 }
-                _ => return Err(Error::UnexpectedChar(ch as char, self.position)),
+                _ => return Err(Error::UnexpectedChar(self.current_char(), self.position)),
             }
         }
     }
 }
 
+/// Whether `ch` is one of the non-ASCII spaces accepted when
+/// [`LexerConfig::unicode_whitespace`] is enabled.
+fn is_unicode_whitespace(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{00A0}' | '\u{2000}'..='\u{200A}' | '\u{2028}' | '\u{2029}' | '\u{FEFF}'
+    )
+}
+
 impl<'a> JsonLexer for FastLexer<'a> {
     fn position(&self) -> usize {
         self.position
@@ -518,4 +583,40 @@ mod tests {
         assert_eq!(stats.bytes_processed, 7);
         assert!(stats.time_ns > 0);
     }
+
+    #[test]
+    fn test_fast_lexer_unicode_whitespace() {
+        let input = "\u{FEFF}[1,\u{A0}\u{A0}2\u{2003}\u{2028}]";
+        let mut lexer = FastLexer::new(input, LexerConfig::default());
+        assert_eq!(
+            lexer.next_token().unwrap(),
+            (Token::LeftBracket, Span::new(3, 4))
+        );
+        lexer.next_token().unwrap();
+        lexer.next_token().unwrap();
+        assert_eq!(lexer.next_token(), Err(Error::UnexpectedChar('\u{A0}', 6)));
+
+        let config = LexerConfig {
+            unicode_whitespace: true,
+            ..Default::default()
+        };
+        let mut lexer = FastLexer::new(input, config);
+        let mut tokens = Vec::new();
+        loop {
+            match lexer.next_token().unwrap().0 {
+                Token::Eof => break,
+                token => tokens.push(token),
+            }
+        }
+        assert_eq!(
+            tokens,
+            [
+                Token::LeftBracket,
+                Token::Number,
+                Token::Comma,
+                Token::Number,
+                Token::RightBracket
+            ]
+        );
+    }
 }
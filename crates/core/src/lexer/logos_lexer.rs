@@ -15,33 +15,47 @@ pub struct LogosLexer<'a> {
     peeked: Option<(Token, Span)>,
     line: usize,
     column: usize,
-    last_newline_pos: usize,
+    /// Input offset up to which `line` and `column` have been counted.
+    scanned_pos: usize,
 }
 
 impl<'a> LogosLexer<'a> {
     /// Creates a new logos-based lexer
     pub fn new(input: &'a str) -> Self {
+        let mut lexer = Token::lexer(input);
+        // A leading byte order mark is always whitespace
+        if input.starts_with('\u{FEFF}') {
+            lexer.bump('\u{FEFF}'.len_utf8());
+        }
         LogosLexer {
-            lexer: Token::lexer(input),
+            lexer,
             input,
             peeked: None,
             line: 1,
             column: 1,
-            last_newline_pos: 0,
+            scanned_pos: 0,
         }
     }
 
+    /// Creates a lexer over `input` that starts reading at byte `position`
+    pub(crate) fn starting_at(input: &'a str, position: usize) -> Self {
+        let mut lexer = Self::new(input);
+        let skipped = lexer.lexer.span().end;
+        lexer.lexer.bump(position - skipped);
+        lexer
+    }
+
     fn update_position(&mut self, span: &Span) {
-        let text = &self.input[self.last_newline_pos..span.start];
+        let text = &self.input[self.scanned_pos..span.start];
         for ch in text.chars() {
             if ch == '\n' {
                 self.line += 1;
                 self.column = 1;
-                self.last_newline_pos = span.start;
             } else {
                 self.column += 1;
             }
         }
+        self.scanned_pos = span.start;
     }
 
     // Backward compatibility methods
@@ -163,4 +177,18 @@ mod tests {
         let (line, _col) = lexer.line_col();
         assert_eq!(line, 2);
     }
+
+    #[test]
+    fn test_leading_bom_skipped() {
+        let mut lexer = LogosLexer::new("\u{FEFF}[\u{FEFF}]");
+        assert_eq!(
+            lexer.next_token().unwrap(),
+            (Token::LeftBracket, Span::new(3, 4))
+        );
+        // Only a leading mark is skipped
+        assert_eq!(
+            lexer.next_token(),
+            Err(Error::UnexpectedChar('\u{FEFF}', 4))
+        );
+    }
 }
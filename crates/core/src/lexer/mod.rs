@@ -7,7 +7,9 @@
 //! - Streaming lexer for incremental parsing
 
 use crate::ast::Token;
-use crate::error::{Result, Span};
+use crate::error::{Error, Result, Span};
+use crate::parser::{create_parser_lexer, CommentStyles, ParserOptions};
+use std::time::Instant;
 
 /// Core lexer trait defining the interface for all lexer implementations
 pub trait JsonLexer {
@@ -74,6 +76,15 @@ pub struct LexerConfig {
     pub collect_stats: bool,
     /// Buffer size for streaming mode
     pub buffer_size: usize,
+    /// Treat no-break, typographic and Unicode line/paragraph separator
+    /// spaces as whitespace
+    pub unicode_whitespace: bool,
+    /// Let `/*` inside a block comment open a nested comment
+    pub nested_comments: bool,
+    /// Maximum length in bytes of a comment, delimiters included
+    pub max_comment_length: Option<usize>,
+    /// Comment syntaxes to recognize outside [`LexerMode::Strict`]
+    pub comment_styles: CommentStyles,
 }
 
 impl Default for LexerConfig {
@@ -84,6 +95,10 @@ impl Default for LexerConfig {
             track_positions: true,
             collect_stats: false,
             buffer_size: 8192,
+            unicode_whitespace: false,
+            nested_comments: true,
+            max_comment_length: None,
+            comment_styles: CommentStyles::default(),
         }
     }
 }
@@ -112,6 +127,99 @@ pub fn create_lexer<'a>(input: &'a str, config: LexerConfig) -> Box<dyn JsonLexe
     }
 }
 
+/// Tokenize the whole input, retaining comment and newline tokens.
+///
+/// The parser discards comments, but tools such as formatters need them. Every
+/// returned span indexes into `input`, so `&input[span.start..span.end]` yields
+/// the exact source text of the token, including comment delimiters. The
+/// trailing [`Token::Eof`] is not included.
+pub fn tokenize(input: &str) -> Result<Vec<(Token, Span)>> {
+    tokenize_with_comment_styles(input, CommentStyles::default())
+}
+
+/// Like [`tokenize`], recognizing only the comments written in `styles`.
+///
+/// As in the parser, a `#` that does not start a comment is part of an
+/// unquoted string, and a `/` that does not is an error.
+pub fn tokenize_with_comment_styles(
+    input: &str,
+    styles: CommentStyles,
+) -> Result<Vec<(Token, Span)>> {
+    let mut lexer = LogosLexer::new(input);
+    let mut tokens: Vec<(Token, Span)> = Vec::new();
+    loop {
+        let (token, span) = lexer.next_token()?;
+        match token {
+            Token::Eof => return Ok(tokens),
+            Token::SingleLineComment | Token::MultiLineComment
+                if !styles.allows(&input[span.start..span.end]) =>
+            {
+                if !input[span.start..].starts_with('#') {
+                    return Err(Error::UnexpectedChar('/', span.start));
+                }
+                // The word the `#` is in, running on after it
+                let end = input[span.start..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || "_$-#".contains(c)))
+                    .map_or(input.len(), |len| span.start + len);
+                let mut start = span.start;
+                if let Some(&(
+                    Token::UnquotedString | Token::True | Token::False | Token::Null,
+                    before,
+                )) = tokens.last()
+                {
+                    if before.end == start {
+                        start = before.start;
+                        tokens.pop();
+                    }
+                }
+                tokens.push((Token::UnquotedString, Span::new(start, end)));
+                lexer = LogosLexer::starting_at(input, end);
+            }
+            _ => tokens.push((token, span)),
+        }
+    }
+}
+
+/// A token paired with its span and the source text it covers.
+pub type SourceToken<'a> = (Token, Span, &'a str);
+
+/// Tokenize the whole input exactly as [`Parser`](crate::Parser) would with
+/// `options`, pairing each token with its source text.
+///
+/// This is a debugging aid for inputs that parse unexpectedly. With any
+/// forgiving feature enabled the parser's lexer drops comments and line
+/// breaks, so the result can differ from [`tokenize`]. The trailing
+/// [`Token::Eof`] is not included.
+pub fn tokenize_with_options<'a>(
+    input: &'a str,
+    options: &ParserOptions,
+) -> Result<Vec<SourceToken<'a>>> {
+    tokenize_with_stats(input, options).map(|(tokens, _)| tokens)
+}
+
+/// Like [`tokenize_with_options`], also returning statistics for the run.
+pub fn tokenize_with_stats<'a>(
+    input: &'a str,
+    options: &ParserOptions,
+) -> Result<(Vec<SourceToken<'a>>, LexerStats)> {
+    let start = Instant::now();
+    let mut lexer = create_parser_lexer(input, options);
+    let mut tokens = Vec::new();
+    loop {
+        let (token, span) = lexer.next_token()?;
+        if token == Token::Eof {
+            let stats = LexerStats {
+                tokens_count: tokens.len(),
+                bytes_processed: span.end,
+                time_ns: start.elapsed().as_nanos() as u64,
+                errors_count: 0,
+            };
+            return Ok((tokens, stats));
+        }
+        tokens.push((token, span, &input[span.start..span.end]));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,4 +241,40 @@ mod tests {
         let strict_lexer = create_lexer(input, strict_config);
         assert!(!strict_lexer.is_eof());
     }
+
+    #[test]
+    fn test_tokenize_with_comment_styles() {
+        let input = "[a#b, #c, true#] // x";
+        let styles = CommentStyles {
+            hash: false,
+            ..Default::default()
+        };
+        let tokens: Vec<_> = tokenize_with_comment_styles(input, styles)
+            .unwrap()
+            .into_iter()
+            .map(|(token, span)| (token, &input[span.start..span.end]))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Token::LeftBracket, "["),
+                (Token::UnquotedString, "a#b"),
+                (Token::Comma, ","),
+                (Token::UnquotedString, "#c"),
+                (Token::Comma, ","),
+                (Token::UnquotedString, "true#"),
+                (Token::RightBracket, "]"),
+                (Token::SingleLineComment, "// x"),
+            ]
+        );
+
+        let styles = CommentStyles {
+            line_slash: false,
+            ..styles
+        };
+        assert_eq!(
+            tokenize_with_comment_styles(input, styles),
+            Err(Error::UnexpectedChar('/', 17))
+        );
+    }
 }
@@ -5,15 +5,28 @@
     unused_lifetimes,
     unused_qualifications
 )]
+#![cfg_attr(
+    test,
+    allow(clippy::approx_constant, clippy::field_reassign_with_default)
+)]
 
 //! Core parsing logic for vexy_json, a forgiving JSON parser.
 
+/// Summary statistics for parsed documents.
+pub mod analysis;
+
 /// Abstract Syntax Tree components for vexy_json parsing.
 pub mod ast;
 
+/// Experimental columnar representation of arrays of records.
+pub mod columnar;
+
 /// Error types and result type alias for the vexy_json parser.
 pub mod error;
 
+/// Serialization of values back to standard JSON text.
+pub mod format;
+
 /// Lexical analyzer (tokenizer) for vexy_json parsing.
 pub mod lexer;
 
@@ -35,6 +48,9 @@ pub mod plugin;
 /// JSON repair functionality for bracket balancing.
 pub mod repair;
 
+/// JSON Schema inference from sample documents.
+pub mod schema;
+
 /// JSON transformation utilities.
 pub mod transform;
 
@@ -44,32 +60,66 @@ pub mod parallel;
 /// Parallel chunked processing for large JSON files.
 pub mod parallel_chunked;
 
+/// Assertions for testing code that produces values.
+#[cfg(feature = "testing")]
+pub mod testing;
+
 #[cfg(feature = "serde")]
 /// WebAssembly bindings for browser usage.
 #[cfg(feature = "wasm")]
 pub use ast::{Number, Token, Value};
+pub use error::recovery::{analyze_error, RecoverySuggestion};
 pub use error::{EnhancedParseResult, ParsingTier, RepairAction, RepairType};
-pub use error::{Error, ParseResult, Result};
+pub use error::{
+    BudgetLimit, CompatKind, CompatWarning, Error, ParseResult, Result, Warning, WarningKind,
+};
+pub use analysis::{analyze, DocumentStats};
+pub use format::{
+    minify, prettify, strip_comments, to_json5_string, FloatFormat, KeyOrder, KeyQuoting,
+    LineEnding, SourceComments, StripCommentsOptions,
+};
 pub use lazy::{
     parse_lazy, parse_lazy_with_options, parse_lazy_with_threshold, LazyArray, LazyObject,
     LazyParser, LazyValue,
 };
-pub use lexer::Lexer;
-pub use parallel::{parse_ndjson_parallel, parse_parallel, ParallelConfig, ParallelParser};
+pub use lexer::{
+    tokenize, tokenize_with_comment_styles, tokenize_with_options, tokenize_with_stats, Lexer,
+    LexerStats, SourceToken,
+};
+pub use parallel::{
+    parse_concatenated_parallel, parse_ndjson_parallel, parse_parallel, ParallelConfig,
+    ParallelParser,
+};
 pub use parser::{
-    parse, parse_iterative, parse_optimized, parse_optimized_v2, parse_optimized_v2_with_options,
-    parse_optimized_v3, parse_optimized_v3_with_options, parse_optimized_with_options, 
-    parse_recursive, parse_v2_with_stats, parse_v3_with_stats,
+    is_concatenated, is_concatenated_with_options, parse, parse_array_iter, parse_bytes,
+    parse_bytes_with_options, parse_bytes_with_warnings, parse_concatenated,
+    parse_concatenated_with_options, parse_file, parse_file_with_options, parse_into,
+    parse_into_with_options, parse_iterative, parse_optimized, parse_optimized_v2,
+    parse_optimized_v2_with_options, parse_optimized_v3, parse_optimized_v3_with_options,
+    parse_optimized_with_options, parse_partial, parse_recursive, parse_to_pairs,
+    parse_v2_with_stats, parse_v3_with_stats, parse_with_budget,
     parse_with_detailed_repair_tracking, parse_with_fallback, parse_with_options, parse_with_stats,
-    IterativeParser, Parser, ParserOptions, RecursiveDescentParser,
+    ArrayElements, CommentStyles, DuplicateKeyPolicy, EmptyInputBehavior, InvalidUtf8Policy,
+    IterativeParser, JsonParser, Limits, NumberOverflowPolicy, ParseBudget, Parser, ParserBackend,
+    ParserOptions, ParserPool, ParserPoolStats, RecursiveDescentParser, UnquotedValueMode,
+};
+#[cfg(feature = "ml-recovery")]
+pub use parser::parse_with_ml_recovery;
+pub use repair::{
+    smart_repair, JsonRepairer, RepairCache, RepairCacheConfig, RepairCacheStats, SharedRepairCache,
 };
-pub use repair::JsonRepairer;
 pub use streaming::{
-    parse_streaming, parse_streaming_with_config, BufferedStreamingConfig, BufferedStreamingParser,
-    NdJsonParser, SimpleStreamingLexer, StreamingEvent, StreamingParser, StreamingValueBuilder,
+    build_index, events_from_file, events_from_file_with_options, ndjson_to_array, parse_streaming,
+    parse_streaming_with_config, to_ndjson, validate_stream, BufferedStreamingConfig,
+    BufferedStreamingParser, FileEvents, IndexSpec, JsonIndex, NdJsonParser, SimpleStreamingLexer,
+    StreamCheckpoint, StreamingEvent, StreamingParser, StreamingValueBuilder,
 };
 pub use transform::{
-    normalize, normalize_with_options, optimize, optimize_with_options, AstOptimizer,
-    CanonicalNormalizer, CleanupNormalizer, InternerStats, JsonNormalizer, MemoryOptimizer,
-    NormalizerOptions, OptimizerOptions, OptimizerStats, PerformanceOptimizer, StringInterner,
+    check_homogeneous, check_homogeneous_with_options, coerce_scalars, flatten, normalize,
+    normalize_keys, normalize_with_options, optimize, optimize_with_options, redact, reformat,
+    reformat_with_options, sort_array_by, sort_arrays, to_preview_string, unflatten, AstOptimizer,
+    CanonicalNormalizer, CleanupNormalizer, CoerceOptions, FormatStyle, HomogeneousOptions,
+    InternerStats, JsonNormalizer, KeyCase, KeyCollision, MemoryOptimizer, NormalizerOptions,
+    OptimizerOptions, OptimizerStats, PerformanceOptimizer, StringInterner, TypeMismatch,
+    ValueType,
 };
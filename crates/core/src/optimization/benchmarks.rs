@@ -6,7 +6,10 @@
 //! and compare the performance of SIMD vs scalar implementations across
 //! various string processing operations.
 
+use crate::ast::{Token, Value};
+use crate::lexer::{create_lexer, LexerConfig, LexerMode};
 use crate::optimization::simd::*;
+use crate::parser::{parse_with_options, ParserOptions};
 use std::time::{Duration, Instant};
 
 /// Performance monitoring and benchmarking utilities for SIMD optimizations.
@@ -15,6 +18,8 @@ pub struct PerformanceMonitor {
     pub simd_times: Vec<Duration>,
     /// Collection of scalar operation execution times
     pub scalar_times: Vec<Duration>,
+    /// Collection of per-iteration parse times
+    pub parse_times: Vec<Duration>,
     /// Test data strings used for benchmarking
     pub test_data: Vec<String>,
 }
@@ -31,6 +36,7 @@ impl PerformanceMonitor {
         Self {
             simd_times: Vec::new(),
             scalar_times: Vec::new(),
+            parse_times: Vec::new(),
             test_data: Self::generate_test_data(),
         }
     }
@@ -100,6 +106,7 @@ impl PerformanceMonitor {
             scalar_time: scalar_total,
             speedup: scalar_total.as_nanos() as f64 / simd_total.as_nanos() as f64,
             iterations,
+            ..Default::default()
         }
     }
 
@@ -131,6 +138,7 @@ impl PerformanceMonitor {
             scalar_time: scalar_total,
             speedup: scalar_total.as_nanos() as f64 / simd_total.as_nanos() as f64,
             iterations,
+            ..Default::default()
         }
     }
 
@@ -162,6 +170,7 @@ impl PerformanceMonitor {
             scalar_time: scalar_total,
             speedup: scalar_total.as_nanos() as f64 / simd_total.as_nanos() as f64,
             iterations,
+            ..Default::default()
         }
     }
 
@@ -193,6 +202,49 @@ impl PerformanceMonitor {
             scalar_time: scalar_total,
             speedup: scalar_total.as_nanos() as f64 / simd_total.as_nanos() as f64,
             iterations,
+            ..Default::default()
+        }
+    }
+
+    /// Benchmark parsing `input` with `options`, recording each iteration's
+    /// time in [`parse_times`](Self::parse_times).
+    ///
+    /// Input that fails to parse is timed up to the error and reports no
+    /// allocations.
+    pub fn benchmark_parse(
+        &mut self,
+        input: &str,
+        options: &ParserOptions,
+        iterations: u32,
+    ) -> BenchmarkResult {
+        let mut total = Duration::new(0, 0);
+        let mut parsed = None;
+        for _ in 0..iterations {
+            let start = Instant::now();
+            let result = parse_with_options(input, options.clone());
+            let elapsed = start.elapsed();
+            self.parse_times.push(elapsed);
+            total += elapsed;
+            parsed = result.ok();
+        }
+
+        let bytes = input.len();
+        let seconds = total.as_secs_f64();
+        let throughput_mb_s = if seconds > 0.0 {
+            (bytes as f64 * f64::from(iterations)) / seconds / 1_000_000.0
+        } else {
+            0.0
+        };
+
+        BenchmarkResult {
+            operation: "Parse".to_string(),
+            simd_time: total,
+            iterations,
+            bytes,
+            throughput_mb_s,
+            allocations: parsed.as_ref().map_or(0, count_allocations),
+            tokens: count_tokens(input, options),
+            ..Default::default()
         }
     }
 
@@ -207,33 +259,99 @@ impl PerformanceMonitor {
     }
 }
 
+/// Benchmark parsing `input` with `options` `iterations` times.
+///
+/// Reports the total parse time as [`BenchmarkResult::simd_time`] along with
+/// throughput, the allocations held by the parsed value and the number of
+/// tokens in the input.
+pub fn benchmark_parse(input: &str, options: ParserOptions, iterations: u32) -> BenchmarkResult {
+    PerformanceMonitor::new().benchmark_parse(input, &options, iterations)
+}
+
 /// Results from a benchmark run.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct BenchmarkResult {
     /// Name of the benchmarked operation
     pub operation: String,
-    /// Time taken by SIMD implementation
+    /// Time taken by SIMD implementation, or by the parser for parse benchmarks
     pub simd_time: Duration,
-    /// Time taken by scalar implementation
+    /// Time taken by scalar implementation (zero for parse benchmarks)
     pub scalar_time: Duration,
-    /// Performance speedup ratio (scalar_time / simd_time)
+    /// Performance speedup ratio (scalar_time / simd_time, zero for parse benchmarks)
     pub speedup: f64,
     /// Number of iterations performed in the benchmark
     pub iterations: u32,
+    /// Input size in bytes (parse benchmarks only)
+    pub bytes: usize,
+    /// Parse throughput in megabytes per second (parse benchmarks only)
+    pub throughput_mb_s: f64,
+    /// Heap allocations held by the parsed value (parse benchmarks only)
+    pub allocations: usize,
+    /// Number of tokens in the input (parse benchmarks only)
+    pub tokens: usize,
 }
 
 impl BenchmarkResult {
     /// Display the benchmark results in a formatted way.
     pub fn display(&self) {
         println!("=== {} Benchmark ===", self.operation);
-        println!("SIMD Time:   {:?}", self.simd_time);
-        println!("Scalar Time: {:?}", self.scalar_time);
-        println!("Speedup:     {:.2}x", self.speedup);
+        if self.bytes > 0 {
+            println!("Parse Time:  {:?}", self.simd_time);
+            println!("Throughput:  {:.2} MB/s", self.throughput_mb_s);
+            println!("Allocations: {}", self.allocations);
+            println!("Tokens:      {}", self.tokens);
+        } else {
+            println!("SIMD Time:   {:?}", self.simd_time);
+            println!("Scalar Time: {:?}", self.scalar_time);
+            println!("Speedup:     {:.2}x", self.speedup);
+        }
         println!("Iterations:  {}", self.iterations);
         println!();
     }
 }
 
+/// Counts the heap blocks owned by `value`: one per non-empty string, key,
+/// array and object.
+fn count_allocations(value: &Value) -> usize {
+    match value {
+        Value::String(s) => usize::from(!s.is_empty()),
+        Value::Array(items) => {
+            usize::from(!items.is_empty()) + items.iter().map(count_allocations).sum::<usize>()
+        }
+        Value::Object(members) => {
+            usize::from(!members.is_empty())
+                + members
+                    .iter()
+                    .map(|(key, item)| usize::from(!key.is_empty()) + count_allocations(item))
+                    .sum::<usize>()
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => 0,
+    }
+}
+
+/// Counts the tokens the parser's lexer produces for `input`, up to the end of
+/// input or the first lexical error.
+fn count_tokens(input: &str, options: &ParserOptions) -> usize {
+    let config = LexerConfig {
+        mode: if options.allow_comments {
+            LexerMode::Forgiving
+        } else {
+            LexerMode::Strict
+        },
+        max_depth: options.max_depth,
+        ..Default::default()
+    };
+    let mut lexer = create_lexer(input, config);
+    let mut tokens = 0;
+    while let Ok((token, _)) = lexer.next_token() {
+        if token == Token::Eof {
+            break;
+        }
+        tokens += 1;
+    }
+    tokens
+}
+
 // Scalar implementations for comparison
 fn validate_json_string_scalar(s: &str) -> bool {
     for byte in s.bytes() {
@@ -361,6 +479,22 @@ mod tests {
         assert_eq!(result.iterations, 100);
     }
 
+    #[test]
+    fn test_benchmark_parse() {
+        let input = r#"{"name": "vexy", "tags": ["a", "b"], "count": 3, "ok": true}"#;
+        let result = benchmark_parse(input, ParserOptions::default(), 50);
+
+        assert_eq!(result.operation, "Parse");
+        assert_eq!(result.iterations, 50);
+        assert_eq!(result.bytes, input.len());
+        assert!(result.simd_time.as_nanos() > 0);
+        assert!(result.throughput_mb_s > 0.0 && result.throughput_mb_s.is_finite());
+        // Object, four keys, the array and its two strings, and "vexy"
+        assert_eq!(result.allocations, 9);
+        // { k : v , k : [ v , v ] , k : v , k : v }
+        assert_eq!(result.tokens, 21);
+    }
+
     #[test]
     fn test_run_all_benchmarks() {
         let mut monitor = PerformanceMonitor::new();
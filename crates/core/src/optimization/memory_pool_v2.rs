@@ -89,10 +89,8 @@ impl OptimizedMemoryPool {
         // Update average allocation size
         let total_allocs = self.stats.allocations.get();
         let total_bytes = self.stats.total_allocated.get();
-        if total_allocs > 0 {
-            self.stats
-                .avg_allocation_size
-                .set(total_bytes / total_allocs);
+        if let Some(avg) = total_bytes.checked_div(total_allocs) {
+            self.stats.avg_allocation_size.set(avg);
         }
 
         // Check if pooling is beneficial for this allocation
@@ -109,11 +107,9 @@ impl OptimizedMemoryPool {
 
             if self.use_thread_local {
                 THREAD_LOCAL_POOL.with(|pool| {
-                    let mut pool_ref = pool.borrow_mut();
-                    if pool_ref.is_none() {
-                        *pool_ref = Some(FastMemoryPool::new(DEFAULT_BLOCK_SIZE));
-                    }
-                    pool_ref.as_mut().unwrap().allocate(size)
+                    pool.borrow_mut()
+                        .get_or_insert_with(|| FastMemoryPool::new(DEFAULT_BLOCK_SIZE))
+                        .allocate(size)
                 })
             } else {
                 // Fall back to direct allocation if thread-local not available
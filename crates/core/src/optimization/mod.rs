@@ -14,7 +14,7 @@ pub mod string_parser;
 pub mod value_builder;
 pub mod zero_copy;
 
-pub use benchmarks::{BenchmarkResult, PerformanceMonitor};
+pub use benchmarks::{benchmark_parse, BenchmarkResult, PerformanceMonitor};
 pub use memory_pool::{MemoryPool, MemoryPoolStats, ScopedMemoryPool};
 pub use memory_pool_v2::{OptimizedMemoryPool, PoolStats, ScopedOptimizedPool};
 pub use memory_pool_v3::{
@@ -26,7 +26,8 @@ pub use simd::{
     validate_json_string_simd,
 };
 pub use string_parser::{
-    extract_string_content, parse_number_optimized, unescape_string_optimized,
+    extract_string_content, extract_string_cow, parse_number_optimized, unescape_string_cow,
+    unescape_string_optimized,
 };
 pub use value_builder::ValueBuilder;
 pub use zero_copy::{parse_number_fast, parse_string_zero_copy};
@@ -291,11 +291,9 @@ fn parse_integer_fast(s: &str) -> Result<f64> {
         }
     }
 
-    if negative {
-        result = -result;
-    }
-
-    Ok(result as f64)
+    // Negating the float rather than the integer keeps the sign of `-0`
+    let value = result as f64;
+    Ok(if negative { -value } else { value })
 }
 
 /// SIMD-accelerated string unescaping with optimized common cases.
@@ -327,7 +325,7 @@ fn unescape_with_simd_scan(s: &str) -> Result<String> {
 
         if let Some(pos) = backslash_pos {
             // Copy the unescaped portion
-            result.push_str(std::str::from_utf8(&bytes[i..i + pos]).unwrap());
+            result.push_str(&s[i..i + pos]);
             i += pos;
 
             // Process the escape sequence
@@ -344,8 +342,7 @@ fn unescape_with_simd_scan(s: &str) -> Result<String> {
                     b'f' => result.push('\x0C'),
                     b'u' => {
                         // Unicode escape sequence
-                        if i + 5 < bytes.len() {
-                            let hex_str = std::str::from_utf8(&bytes[i + 2..i + 6]).unwrap();
+                        if let Some(hex_str) = s.get(i + 2..i + 6) {
                             if let Ok(code) = u32::from_str_radix(hex_str, 16) {
                                 if let Some(unicode_char) = std::char::from_u32(code) {
                                     result.push(unicode_char);
@@ -356,10 +353,17 @@ fn unescape_with_simd_scan(s: &str) -> Result<String> {
                         }
                         return Err(Error::InvalidEscape(i));
                     }
-                    other => {
+                    other if other.is_ascii() => {
                         result.push('\\');
                         result.push(other as char);
                     }
+                    _ => {
+                        // Keep the backslash; the multi-byte character after
+                        // it is copied with the text that follows
+                        result.push('\\');
+                        i += 1;
+                        continue;
+                    }
                 }
                 i += 2;
             } else {
@@ -368,7 +372,7 @@ fn unescape_with_simd_scan(s: &str) -> Result<String> {
             }
         } else {
             // No more backslashes, copy the rest
-            result.push_str(std::str::from_utf8(&bytes[i..]).unwrap());
+            result.push_str(&s[i..]);
             break;
         }
     }
@@ -491,4 +495,11 @@ mod tests {
         let long_string = "a".repeat(1000);
         assert_eq!(unescape_string_simd(&long_string).unwrap(), long_string);
     }
+
+    #[test]
+    fn test_unescape_string_simd_multibyte_after_backslash() {
+        assert_eq!(unescape_string_simd("a\\éb").unwrap(), "a\\éb");
+        assert!(unescape_string_simd("\\u00é").is_err());
+        assert_eq!(unescape_string_simd("\\u00e9é").unwrap(), "éé");
+    }
 }
@@ -6,16 +6,31 @@
 //! unnecessary allocations and use more efficient algorithms.
 
 use crate::error::{Error, Result};
+use crate::optimization::simd::has_backslash_simd;
+use crate::parser::number::decimal_number;
+use std::borrow::Cow;
 
 /// Fast string unescaping that avoids allocations when no escapes are present.
 #[inline]
 pub fn unescape_string_optimized(s: &str) -> Result<String> {
-    // Fast path: if no backslashes, return as-is (avoid allocation)
-    if !s.contains('\\') {
-        return Ok(s.to_string());
+    unescape_string_cow(s).map(Cow::into_owned)
+}
+
+/// String unescaping that borrows `s` when it contains no escape sequences.
+///
+/// Only strings with a backslash are copied, so escape-free strings, the
+/// common case, cost no allocation.
+#[inline]
+pub fn unescape_string_cow(s: &str) -> Result<Cow<'_, str>> {
+    if has_backslash_simd(s) {
+        unescape_escaped(s).map(Cow::Owned)
+    } else {
+        Ok(Cow::Borrowed(s))
     }
+}
 
-    // Slow path: process escape sequences
+/// Processes the escape sequences in `s`, which contains at least one.
+fn unescape_escaped(s: &str) -> Result<String> {
     let mut result = String::with_capacity(s.len()); // Pre-allocate expected size
     let mut chars = s.chars();
 
@@ -63,14 +78,6 @@ fn parse_unicode_escape(chars: &mut std::str::Chars<'_>) -> Result<char> {
         code = (code << 4) | digit;
     }
 
-    // Check if there's an additional hex digit immediately following
-    // In strict JSON, \uXXXX should be exactly 4 hex digits
-    if let Some(next_char) = chars.clone().next() {
-        if next_char.is_ascii_hexdigit() {
-            return Err(Error::InvalidEscape(0));
-        }
-    }
-
     std::char::from_u32(code).ok_or(Error::InvalidUnicode(0))
 }
 
@@ -105,18 +112,19 @@ pub fn extract_string_content(slice: &str) -> Result<&str> {
     }
 }
 
+/// Extracts the content of a quoted string and unescapes it, borrowing from
+/// `slice` when there is nothing to unescape.
+#[inline]
+pub fn extract_string_cow(slice: &str) -> Result<Cow<'_, str>> {
+    unescape_string_cow(extract_string_content(slice)?)
+}
+
 /// Fast number parsing optimized for common cases.
 #[inline]
 pub fn parse_number_optimized(s: &str) -> Result<f64> {
-    // Fast path for integers without scientific notation
-    if !s.contains('.') && !s.contains('e') && !s.contains('E') {
-        if let Ok(int_val) = s.parse::<i64>() {
-            return Ok(int_val as f64);
-        }
-    }
-
-    // Fallback to standard parsing
-    s.parse::<f64>().map_err(|_| Error::InvalidNumber(0))
+    decimal_number(s)
+        .map(|number| number.as_f64())
+        .ok_or(Error::InvalidNumber(0))
 }
 
 #[cfg(test)]
@@ -135,6 +143,30 @@ mod tests {
         assert_eq!(result, "hello\nworld\t!");
     }
 
+    #[test]
+    fn test_unescape_cow_borrows_only_without_escapes() {
+        let long = "x".repeat(100);
+        for input in ["hello world", "", long.as_str()] {
+            assert!(matches!(unescape_string_cow(input).unwrap(), Cow::Borrowed(s) if s == input));
+        }
+
+        let escaped = format!("{long}\\n");
+        for (input, expected) in [("a\\tb", "a\tb"), (escaped.as_str(), &format!("{long}\n"))] {
+            match unescape_string_cow(input).unwrap() {
+                Cow::Owned(s) => assert_eq!(s, *expected),
+                Cow::Borrowed(s) => panic!("expected an owned string, got {s:?}"),
+            }
+        }
+
+        let quoted = "\"plain\"";
+        assert!(matches!(
+            extract_string_cow(quoted).unwrap(),
+            Cow::Borrowed("plain")
+        ));
+        assert_eq!(extract_string_cow("'it\\'s'").unwrap(), "it's");
+        assert!(extract_string_cow("'bad\\u12'").is_err());
+    }
+
     #[test]
     fn test_extract_string_content() {
         assert_eq!(extract_string_content("\"hello\"").unwrap(), "hello");
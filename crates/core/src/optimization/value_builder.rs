@@ -64,7 +64,7 @@ impl ValueBuilder {
         let number_value = if num.fract() == 0.0
             && num.is_finite()
             && num >= i64::MIN as f64
-            && num <= i64::MAX as f64
+            && num < i64::MAX as f64
         {
             Number::Integer(num as i64)
         } else {
@@ -4,6 +4,7 @@
 
 use crate::ast::{Number, Value};
 use crate::error::{Error, Result};
+use crate::parser::number::decimal_number;
 use std::borrow::Cow;
 
 /// Zero-copy string parsing that avoids allocations when possible.
@@ -95,19 +96,9 @@ fn unescape_minimal(s: &str) -> Result<String> {
 /// Fast number parsing for common integer cases.
 #[inline]
 pub fn parse_number_fast(s: &str) -> Result<Value> {
-    // Fast path for simple integers
-    if s.len() <= 10 && !s.contains('.') && !s.contains('e') && !s.contains('E') {
-        if let Ok(int_val) = s.parse::<i64>() {
-            if int_val >= i32::MIN as i64 && int_val <= i32::MAX as i64 {
-                return Ok(Value::Number(Number::Float(int_val as f64)));
-            }
-        }
-    }
-
-    // Standard float parsing
-    match s.parse::<f64>() {
-        Ok(f) => Ok(Value::Number(Number::Float(f))),
-        Err(_) => Err(Error::InvalidNumber(0)),
+    match decimal_number(s) {
+        Some(number) => Ok(Value::Number(Number::Float(number.as_f64()))),
+        None => Err(Error::InvalidNumber(0)),
     }
 }
 
@@ -10,14 +10,18 @@
 //! The parallel parser can handle:
 //! - Large single JSON objects/arrays
 //! - NDJSON (newline-delimited JSON) files
+//! - Concatenated documents such as `{"a":1}{"b":2}`
 //! - JSON arrays with many elements
 
 use crate::{
     ast::Value,
     error::{Error, Result},
     parse,
+    parser::{document_ranges, is_concatenated, parse_with_budget, ParseBudget, ParserOptions},
 };
 use rayon::prelude::*;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 /// Configuration for parallel parsing
 #[derive(Debug, Clone)]
@@ -28,6 +32,8 @@ pub struct ParallelConfig {
     pub max_threads: usize,
     /// Whether to enable chunk optimization (may use more memory)
     pub optimize_chunks: bool,
+    /// Flag that cancels parsing of every chunk once set
+    pub cancel_flag: Option<Arc<AtomicBool>>,
 }
 
 impl Default for ParallelConfig {
@@ -36,6 +42,7 @@ impl Default for ParallelConfig {
             min_chunk_size: 64 * 1024, // 64KB
             max_threads: 0,            // Auto-detect
             optimize_chunks: true,
+            cancel_flag: None,
         }
     }
 }
@@ -62,7 +69,7 @@ impl ParallelParser {
     pub fn parse(&self, input: &str) -> Result<Value> {
         // For small inputs, use regular parsing
         if input.len() < self.config.min_chunk_size {
-            return parse(input);
+            return self.parse_chunk(input);
         }
 
         // Detect input type and choose appropriate strategy
@@ -74,7 +81,8 @@ impl ParallelParser {
                 // For consistency, return a single array containing all NDJSON objects
                 Ok(Value::Array(values))
             }
-            InputType::Single => parse(input),
+            InputType::Concatenated => Ok(Value::Array(self.parse_concatenated(input)?)),
+            InputType::Single => self.parse_chunk(input),
         }
     }
 
@@ -87,7 +95,7 @@ impl ParallelParser {
 
         if lines.len() < 2 {
             // Single line, parse normally
-            let value = parse(input)?;
+            let value = self.parse_chunk(input)?;
             return Ok(vec![value]);
         }
 
@@ -101,19 +109,48 @@ impl ParallelParser {
             pool.install(|| {
                 lines
                     .par_iter()
-                    .map(|line| parse(line))
+                    .map(|line| self.parse_chunk(line))
                     .collect::<Result<Vec<_>>>()
             })
         } else {
             lines
                 .par_iter()
-                .map(|line| parse(line))
+                .map(|line| self.parse_chunk(line))
                 .collect::<Result<Vec<_>>>()
         };
 
         result
     }
 
+    /// Parse concatenated documents, such as `{"a":1}{"b":2}`, in parallel
+    ///
+    /// Documents may also be separated by spaces and line breaks. They are
+    /// returned in input order, and an error is positioned in `input`; see
+    /// [`parse_concatenated`](crate::parse_concatenated) for the sequential
+    /// equivalent.
+    pub fn parse_concatenated(&self, input: &str) -> Result<Vec<Value>> {
+        let ranges = document_ranges(input, &ParserOptions::default());
+        let parse_document = |range: &std::ops::Range<usize>| {
+            self.parse_chunk(&input[range.clone()])
+                .map_err(|error| error.offset_by(range.start))
+        };
+
+        if ranges.len() < 2 {
+            return ranges.iter().map(parse_document).collect();
+        }
+
+        if self.config.max_threads > 0 {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(self.config.max_threads)
+                .build()
+                .map_err(|e| Error::Custom(format!("Failed to create thread pool: {e}")))?;
+
+            pool.install(|| ranges.par_iter().map(parse_document).collect())
+        } else {
+            ranges.par_iter().map(parse_document).collect()
+        }
+    }
+
     /// Detect the type of JSON input
     fn detect_input_type(&self, input: &str) -> Result<InputType> {
         let trimmed = input.trim();
@@ -122,6 +159,10 @@ impl ParallelParser {
             return Err(Error::Custom("Empty input".to_string()));
         }
 
+        if is_concatenated(input) {
+            return Ok(InputType::Concatenated);
+        }
+
         // Check for NDJSON (multiple lines with JSON objects)
         if input.lines().filter(|line| !line.trim().is_empty()).count() > 1 {
             let first_line = input
@@ -147,7 +188,7 @@ impl ParallelParser {
 
         if chunks.len() <= 1 {
             // Single chunk, parse normally
-            return parse(input);
+            return self.parse_chunk(input);
         }
 
         // Parse chunks in parallel
@@ -185,7 +226,7 @@ impl ParallelParser {
     fn parse_large_object(&self, input: &str) -> Result<Value> {
         // For now, parse objects normally since they're harder to parallelize
         // In the future, we could parallelize parsing of large nested arrays/objects
-        parse(input)
+        self.parse_chunk(input)
     }
 
     /// Split a JSON array into chunks that can be parsed in parallel
@@ -290,9 +331,21 @@ impl ParallelParser {
         Ok(elements)
     }
 
+    /// Parse one piece of the input, honoring the cancel flag
+    fn parse_chunk(&self, input: &str) -> Result<Value> {
+        match &self.config.cancel_flag {
+            Some(flag) => parse_with_budget(
+                input,
+                ParserOptions::default(),
+                ParseBudget::new().with_cancel_flag(flag.clone()),
+            ),
+            None => parse(input),
+        }
+    }
+
     /// Parse a chunk of array elements
     fn parse_array_chunk(&self, chunk: &str) -> Result<Vec<Value>> {
-        let parsed = parse(chunk)?;
+        let parsed = self.parse_chunk(chunk)?;
         match parsed {
             Value::Array(elements) => Ok(elements),
             single => Ok(vec![single]),
@@ -317,6 +370,8 @@ enum InputType {
     Object,
     /// Newline-delimited JSON
     NdJson,
+    /// Documents written one after another, as in `{"a":1}{"b":2}`
+    Concatenated,
 }
 
 /// Convenience function for parallel parsing with default settings
@@ -329,6 +384,11 @@ pub fn parse_ndjson_parallel(input: &str) -> Result<Vec<Value>> {
     ParallelParser::new().parse_ndjson(input)
 }
 
+/// Convenience function for parallel parsing of concatenated documents
+pub fn parse_concatenated_parallel(input: &str) -> Result<Vec<Value>> {
+    ParallelParser::new().parse_concatenated(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,5 +460,11 @@ mod tests {
                 .unwrap(),
             InputType::NdJson
         );
+        assert_eq!(
+            parser
+                .detect_input_type("{\"a\": 1}{\"b\": 2}\n{\"c\": 3}")
+                .unwrap(),
+            InputType::Concatenated
+        );
     }
 }
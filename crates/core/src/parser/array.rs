@@ -2,6 +2,7 @@
 
 use crate::ast::{Token, Value};
 use crate::error::{Error, Result};
+use crate::parser::path::PathEntry;
 use crate::parser::Parser;
 
 impl<'a> Parser<'a> {
@@ -9,19 +10,25 @@ impl<'a> Parser<'a> {
         self.check_depth()?;
         self.state.depth += 1;
 
-        if !matches!(self.current_token, Some((Token::LeftBracket, _))) {
-            return Err(Error::Expected {
-                expected: "[".to_string(),
-                found: format!("{:?}", self.current_token),
-                position: self.lexer.position(),
-            });
-        }
-        self.advance()?;
+        let open = match self.current_token {
+            Some((Token::LeftBracket, span)) => span,
+            _ => {
+                return Err(Error::Expected {
+                    expected: "[".to_string(),
+                    found: format!("{:?}", self.current_token),
+                    position: self.token_position(),
+                });
+            }
+        };
+        self.lex_into(PathEntry::Index(0), Self::advance)?;
 
-        let mut array = Vec::new();
+        let mut array = Vec::with_capacity(self.container_capacity_hint(open));
 
         loop {
-            self.skip_comments_and_newlines()?;
+            self.lex_into(
+                PathEntry::Index(array.len()),
+                Self::skip_comments_and_newlines,
+            )?;
 
             if let Some((Token::RightBracket, _)) = self.current_token {
                 self.advance()?;
@@ -30,12 +37,16 @@ impl<'a> Parser<'a> {
 
             // Check if we have a separator (comma or newline) which means null value
             if self.is_separator() {
+                self.check_array_len(array.len())?;
                 array.push(Value::Null);
-                self.advance()?;
+                self.note_missing_value(self.state.span);
+                self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
                 // Check for consecutive separators
                 while self.is_separator() {
+                    self.check_array_len(array.len())?;
                     array.push(Value::Null);
-                    self.advance()?;
+                    self.note_missing_value(self.state.span);
+                    self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
                 }
                 // After handling separators, continue to next iteration to either
                 // parse the next value or handle end of array
@@ -43,89 +54,93 @@ impl<'a> Parser<'a> {
             }
 
             // Parse value (only reached if not a separator)
-            let value = self.parse_value()?;
+            let value = self.parse_value_at(PathEntry::Index(array.len()))?;
             array.push(value);
+            self.finish_array_element(array.len())?;
+        }
 
-            // Check for separator or end (only after parsing a value)
-            // Skip comments first, but don't skip newlines yet - we need to check if newlines are separators
-            self.skip_comments()?;
-
-            match self.current_token {
-                Some((Token::Comma, _)) => {
-                    self.advance()?;
-                    self.skip_comments_and_newlines()?;
-                    // Check for trailing comma
-                    if matches!(self.current_token, Some((Token::RightBracket, _)))
-                        && !self.options.allow_trailing_commas
-                    {
-                        return Err(Error::TrailingComma(self.lexer.position()));
-                    }
-                }
-                Some((Token::Newline, _)) if self.options.newline_as_comma => {
-                    self.advance()?;
-                    self.skip_comments_and_newlines()?;
-                    // Check for trailing newline
-                    if matches!(self.current_token, Some((Token::RightBracket, _)))
-                        && !self.options.allow_trailing_commas
-                    {
-                        return Err(Error::TrailingComma(self.lexer.position()));
+        self.state.depth -= 1;
+        Ok(Value::Array(array))
+    }
+
+    /// Consumes what follows an array element: a comma or, with
+    /// `newline_as_comma`, a line break. Leaves the closing bracket, or the
+    /// next element after a line break that only comments separate, as the
+    /// current token. `next` is the index of the element that may follow.
+    pub(super) fn finish_array_element(&mut self, next: usize) -> Result<()> {
+        // Check for separator or end (only after parsing a value)
+        // Skip comments first, but don't skip newlines yet - we need to check if newlines are separators
+        self.skip_comments()?;
+
+        match self.current_token {
+            Some((Token::Comma, comma)) => {
+                self.lex_into(PathEntry::Index(next), Self::advance_past_separator)?;
+                // Check for trailing comma
+                if matches!(self.current_token, Some((Token::RightBracket, _))) {
+                    if !self.options.allow_trailing_commas {
+                        return Err(Error::TrailingComma(comma.start));
                     }
+                    self.note_trailing_comma(comma);
                 }
-                Some((Token::RightBracket, _)) => {
-                    continue;
+            }
+            Some((Token::Newline, newline)) if self.options.newline_as_comma => {
+                self.lex_into(PathEntry::Index(next), Self::advance_past_separator)?;
+                // Check for trailing newline
+                if matches!(self.current_token, Some((Token::RightBracket, _)))
+                    && !self.options.allow_trailing_commas
+                {
+                    return Err(Error::TrailingComma(newline.start));
                 }
-                _ => {
-                    // If we have newline_as_comma enabled, try skipping comments and newlines
-                    // to see if we find a separator or end token after comments
-                    if self.options.newline_as_comma {
-                        // Save the current state in case we need to restore
-                        let _saved_pos = self.lexer.position();
-                        let saved_token = self.current_token;
-
-                        // Skip any additional comments and newlines
-                        self.skip_comments_and_newlines()?;
-
-                        match self.current_token {
-                            Some((Token::RightBracket, _)) => {
-                                // Found the end after skipping comments/newlines
-                                continue;
-                            }
-                            Some((Token::Number, _))
-                            | Some((Token::String, _))
-                            | Some((Token::UnquotedString, _))
-                            | Some((Token::True, _))
-                            | Some((Token::False, _))
-                            | Some((Token::Null, _))
-                            | Some((Token::LeftBrace, _))
-                            | Some((Token::LeftBracket, _)) => {
-                                // Found a value after comments/newlines, which means the newlines were separators
-                                // Continue to next iteration to parse this value
-                                continue;
-                            }
-                            _ => {
-                                // TODO: Restore state and return error with the original token
-                                // self.lexer.set_position(saved_pos);
-                                self.current_token = saved_token;
-                                return Err(Error::Expected {
-                                    expected: ", or ] or newline".to_string(),
-                                    found: format!("{:?}", self.current_token),
-                                    position: self.lexer.position(),
-                                });
-                            }
+            }
+            Some((Token::RightBracket, _)) => {}
+            _ => {
+                // If we have newline_as_comma enabled, try skipping comments and newlines
+                // to see if we find a separator or end token after comments
+                if self.options.newline_as_comma {
+                    // Save the current state in case we need to restore
+                    let _saved_pos = self.lexer.position();
+                    let saved_token = self.current_token;
+
+                    // Skip any additional comments and newlines
+                    self.lex_into(PathEntry::Index(next), Self::skip_comments_and_newlines)?;
+
+                    match self.current_token {
+                        Some((Token::RightBracket, _)) => {
+                            // Found the end after skipping comments/newlines
+                        }
+                        Some((Token::Number, _))
+                        | Some((Token::String, _))
+                        | Some((Token::UnquotedString, _))
+                        | Some((Token::True, _))
+                        | Some((Token::False, _))
+                        | Some((Token::Null, _))
+                        | Some((Token::LeftBrace, _))
+                        | Some((Token::LeftBracket, _)) => {
+                            // Found a value after comments/newlines, which means the newlines were separators
+                            // Continue to next iteration to parse this value
+                            self.note_missing_comma();
+                        }
+                        _ => {
+                            // TODO: Restore state and return error with the original token
+                            // self.lexer.set_position(saved_pos);
+                            self.current_token = saved_token;
+                            return Err(Error::Expected {
+                                expected: ", or ] or newline".to_string(),
+                                found: format!("{:?}", self.current_token),
+                                position: self.token_position(),
+                            });
                         }
-                    } else {
-                        return Err(Error::Expected {
-                            expected: ", or ] or newline".to_string(),
-                            found: format!("{:?}", self.current_token),
-                            position: self.lexer.position(),
-                        });
                     }
+                } else {
+                    return Err(Error::Expected {
+                        expected: ", or ] or newline".to_string(),
+                        found: format!("{:?}", self.current_token),
+                        position: self.token_position(),
+                    });
                 }
             }
         }
-
-        self.state.depth -= 1;
-        Ok(Value::Array(array))
+        Ok(())
     }
 }
 
@@ -367,4 +382,89 @@ mod tests {
         // This should fail because whitespace is not a valid separator
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_preallocate_produces_identical_output() {
+        let input = r#"[1, "a,b", [2, 3], {"k": [4, 5], 'q': "]"}, null,
+            // comment, with commas
+            true, 6]"#;
+        let mut options = ParserOptions::default();
+        let expected = Parser::new(input, options.clone()).parse().unwrap();
+
+        options.preallocate = true;
+        let result = Parser::new(input, options.clone()).parse().unwrap();
+        assert_eq!(result, expected);
+
+        let padded = pad_to_scanned_size(input);
+        let result = Parser::new(&padded, options).parse().unwrap();
+        assert_eq!(result, expected);
+    }
+
+    /// Appends whitespace so that `preallocate` scans `input`.
+    fn pad_to_scanned_size(input: &str) -> String {
+        format!("{input:<0$}", crate::parser::PREALLOCATE_MIN_INPUT)
+    }
+
+    #[test]
+    fn test_preallocate_reserves_top_level_elements() {
+        let input = "[1, [2, 3], {\"a\": 4, \"b\": 5}, \"x,y\", 6]";
+        let mut options = ParserOptions::default();
+        options.preallocate = true;
+        let capacity = |input: &str| match Parser::new(input, options.clone()).parse().unwrap() {
+            Value::Array(items) => {
+                assert_eq!(items.len(), 5);
+                items.capacity()
+            }
+            other => panic!("expected array, got {other:?}"),
+        };
+        assert_eq!(capacity(&pad_to_scanned_size(input)), 5);
+        // Small inputs are not scanned, and growing element by element
+        // rounds up to 8
+        assert_eq!(capacity(input), 8);
+    }
+
+    #[test]
+    fn test_preallocate_reserves_nested_elements() {
+        use crate::parser::scan_container_lens;
+
+        let input = r#"[[1, 2, 3], ["]", [4, 5]], {"a": [6, 7, 8, 9], "b": []}]"#;
+        let mut nested = Default::default();
+        assert_eq!(scan_container_lens(input, 1, &mut nested), 3);
+        assert_eq!(
+            Vec::from(nested),
+            [(2, 3), (13, 2), (19, 2), (28, 2), (34, 4), (53, 0)]
+        );
+
+        let mut options = ParserOptions::default();
+        options.preallocate = true;
+        let value = Parser::new(&pad_to_scanned_size(input), options)
+            .parse()
+            .unwrap();
+        let capacity = |path: &[usize]| {
+            let mut value = &value;
+            for &index in path {
+                value = &value.as_array().unwrap()[index];
+            }
+            value.as_array().unwrap().capacity()
+        };
+        assert_eq!(capacity(&[0]), 3);
+        assert_eq!(capacity(&[1]), 2);
+        assert_eq!(capacity(&[1, 1]), 2);
+        assert_eq!(value[2]["a"].as_array().unwrap().capacity(), 4);
+    }
+
+    #[test]
+    fn test_preallocate_scan_is_bounded() {
+        use crate::parser::{scan_container_lens, PREALLOCATE_SCAN_LIMIT};
+
+        // Each element takes 8 bytes, so only part of the array is scanned
+        let input = format!("[{}]", vec!["1000000"; 20_000].join(","));
+        let hint = scan_container_lens(&input, 1, &mut Default::default());
+        assert_eq!(hint, PREALLOCATE_SCAN_LIMIT / 8 + 1);
+
+        let mut options = ParserOptions::default();
+        options.preallocate = true;
+        let value = Parser::new(&input, options).parse().unwrap();
+        assert_eq!(value.as_array().map(Vec::len), Some(20_000));
+    }
 }
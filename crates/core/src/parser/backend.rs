@@ -0,0 +1,389 @@
+// this_file: crates/core/src/parser/backend.rs
+
+//! A common interface to the parser implementations, and the choice between
+//! them.
+
+use crate::ast::Value;
+use crate::error::{CompatWarning, Result, Warning};
+use crate::parser::iterative::{IterativeParser, ParseContext};
+use crate::parser::optimized_v3::OptimizedParserV3;
+use crate::parser::recursive::RecursiveDescentParser;
+use crate::parser::{
+    nesting_depth, CommentStyles, DuplicateKeyPolicy, Parser, ParserOptions, UnquotedValueMode,
+};
+use std::collections::VecDeque;
+
+/// A parser that can parse any input with any options.
+///
+/// Every parser implementation implements this, so that callers can switch
+/// between them, for instance with [`ParserBackend::parser`].
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{ParserBackend, ParserOptions};
+///
+/// let options = ParserOptions::default();
+/// for backend in ParserBackend::ALL {
+///     let value = backend.parser().parse_with("[1, 2]", &options).unwrap();
+///     assert_eq!(value.as_array().map(Vec::len), Some(2));
+/// }
+/// ```
+pub trait JsonParser {
+    /// Parses `input` with `options`.
+    fn parse_with(&mut self, input: &str, options: &ParserOptions) -> Result<Value>;
+
+    /// Takes the warnings recorded by the last call to
+    /// [`parse_with`](Self::parse_with).
+    ///
+    /// Only [`Parser`] records warnings; the others return none.
+    fn take_warnings(&mut self) -> Vec<Warning> {
+        Vec::new()
+    }
+
+    /// Takes the compatibility warnings recorded by the last call to
+    /// [`parse_with`](Self::parse_with).
+    ///
+    /// Only [`Parser`] records them; the others return none.
+    fn take_compat_warnings(&mut self) -> Vec<CompatWarning> {
+        Vec::new()
+    }
+}
+
+/// Which parser implementation [`parse_with_options`](crate::parse_with_options)
+/// and [`parse_with_fallback`](crate::parse_with_fallback) use.
+///
+/// Only the hybrid [`Parser`] implements every option and all of the
+/// forgiving syntax. The others honour `max_depth` and some of the syntax
+/// options, but not implicit top-level values, warnings, paths or the value
+/// limits, and may reject other forgiving input that [`Parser`] accepts.
+/// Like [`Parser`], they read line breaks as whitespace, or between values
+/// as separators under [`newline_as_comma`](ParserOptions::newline_as_comma).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParserBackend {
+    /// Choose for each input; see [`select`](Self::select).
+    #[default]
+    Auto,
+    /// [`Parser`], which implements every option.
+    Hybrid,
+    /// [`RecursiveDescentParser`]
+    Recursive,
+    /// [`IterativeParser`], which keeps its own stack and so handles any
+    /// depth of nesting.
+    Iterative,
+    /// [`OptimizedParserV3`]
+    OptimizedV3,
+}
+
+impl ParserBackend {
+    /// Every backend other than [`Auto`](Self::Auto).
+    pub const ALL: [ParserBackend; 4] = [
+        ParserBackend::Hybrid,
+        ParserBackend::Recursive,
+        ParserBackend::Iterative,
+        ParserBackend::OptimizedV3,
+    ];
+
+    /// Nesting depth past which [`Auto`](Self::Auto) avoids the hybrid
+    /// parser, which recurses once per level.
+    pub const RECURSION_SAFE_DEPTH: usize = 512;
+
+    /// The backend to use for `input` under `options`.
+    ///
+    /// Backends other than [`Auto`](Self::Auto) return themselves. `Auto`
+    /// picks [`Hybrid`](Self::Hybrid), except that it picks
+    /// [`Iterative`](Self::Iterative) when both
+    ///
+    /// - the brackets in `input` nest deeper than
+    ///   [`RECURSION_SAFE_DEPTH`](Self::RECURSION_SAFE_DEPTH) levels, and
+    ///   `max_depth` allows that, and
+    /// - `input` is on a single line, as minified JSON is, and uses no
+    ///   option or escape that only the hybrid parser implements.
+    ///
+    /// Deep input that uses other forgiving syntax the iterative parser
+    /// lacks then fails to parse, where the hybrid parser could exhaust the
+    /// stack.
+    pub fn select(self, input: &str, options: &ParserOptions) -> ParserBackend {
+        match self {
+            ParserBackend::Auto
+                if options.max_depth > Self::RECURSION_SAFE_DEPTH
+                    && !needs_hybrid(input, options)
+                    && nesting_depth(input, options) > Self::RECURSION_SAFE_DEPTH =>
+            {
+                ParserBackend::Iterative
+            }
+            ParserBackend::Auto => ParserBackend::Hybrid,
+            backend => backend,
+        }
+    }
+
+    /// A parser for this backend.
+    ///
+    /// [`Auto`](Self::Auto) gives a parser that selects a backend on each
+    /// call.
+    pub fn parser(self) -> Box<dyn JsonParser> {
+        let options = ParserOptions::default();
+        match self {
+            ParserBackend::Auto => Box::new(AutoParser::default()),
+            ParserBackend::Hybrid => Box::new(Parser::new("", options)),
+            ParserBackend::Recursive => Box::new(RecursiveDescentParser::new("", options)),
+            ParserBackend::Iterative => Box::new(IterativeParser::new("", options)),
+            ParserBackend::OptimizedV3 => Box::new(OptimizedParserV3::new("", options)),
+        }
+    }
+}
+
+/// Whether parsing `input` with `options` needs a feature that only the
+/// hybrid parser implements.
+fn needs_hybrid(input: &str, options: &ParserOptions) -> bool {
+    input.contains(['\n', '\r'])
+        || options.implicit_top_level
+        || options.track_paths
+        || options.collect_warnings
+        || options.collect_compat_warnings
+        || options.preserve_number_precision
+        || options.duplicate_keys != DuplicateKeyPolicy::LastWins
+        || options.unquoted_value_mode != UnquotedValueMode::SingleToken
+        || options.limits.is_limited()
+        || options.max_comment_length.is_some()
+        || options.comment_styles != CommentStyles::default()
+        || options.reject_blank_keys
+        // The other parsers' lexer neither nests comments nor skips Unicode spaces
+        || (options.allow_comments && options.allow_nested_block_comments && input.contains("/*"))
+        || (options.allow_unicode_whitespace && !input.is_ascii())
+        || has_extended_escape(input)
+}
+
+/// Whether a string in `input` uses an escape beyond JSON's, such as `\x41`
+/// or `\'`, which only the hybrid parser reads.
+fn has_extended_escape(input: &str) -> bool {
+    let mut bytes = input.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'\\'
+            && !bytes
+                .next()
+                .is_some_and(|next| b"\"\\/bfnrtu".contains(&next))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Allocations that one parse leaves for the next, so that a parser kept
+/// between inputs, as [`ParserPool`](crate::ParserPool) keeps them, does not
+/// make them afresh.
+#[derive(Debug, Default)]
+pub(crate) struct ParseBuffers {
+    /// Stack of open containers, for [`IterativeParser`]
+    stack: Vec<ParseContext>,
+    /// Element counts of nested containers, for [`Parser`] with
+    /// [`ParserOptions::preallocate`]
+    capacity_hints: VecDeque<(usize, usize)>,
+}
+
+impl ParseBuffers {
+    /// Bytes allocated for the buffers.
+    pub(crate) fn capacity_bytes(&self) -> usize {
+        self.stack.capacity() * size_of::<ParseContext>()
+            + self.capacity_hints.capacity() * size_of::<(usize, usize)>()
+    }
+}
+
+/// The parser [`ParserBackend::Auto`] gives, which selects a backend for each
+/// input. Unlike the parsers it hands each input to, it can be sent between
+/// threads, and it keeps their buffers from one input to the next.
+#[derive(Default)]
+pub(crate) struct AutoParser {
+    /// Backend to select from; any other than `Auto` is always used
+    backend: ParserBackend,
+    buffers: ParseBuffers,
+    warnings: Vec<Warning>,
+    compat_warnings: Vec<CompatWarning>,
+}
+
+impl AutoParser {
+    /// A parser that uses `backend`, selecting for each input if it is
+    /// [`ParserBackend::Auto`].
+    pub(crate) fn new(backend: ParserBackend) -> Self {
+        AutoParser {
+            backend,
+            ..Default::default()
+        }
+    }
+
+    /// The buffers kept for the next input.
+    pub(crate) fn buffers(&self) -> &ParseBuffers {
+        &self.buffers
+    }
+}
+
+impl JsonParser for AutoParser {
+    fn parse_with(&mut self, input: &str, options: &ParserOptions) -> Result<Value> {
+        self.warnings.clear();
+        self.compat_warnings.clear();
+        let buffers = &mut self.buffers;
+        match self.backend.select(input, options) {
+            ParserBackend::Hybrid => {
+                let mut parser = Parser::new(input, options.clone());
+                parser.capacity_hints = std::mem::take(&mut buffers.capacity_hints);
+                let result = parser.parse();
+                buffers.capacity_hints = std::mem::take(&mut parser.capacity_hints);
+                buffers.capacity_hints.clear();
+                self.warnings = parser.take_warnings();
+                self.compat_warnings = parser.take_compat_warnings();
+                result
+            }
+            ParserBackend::Iterative => {
+                let mut parser = IterativeParser::new(input, options.clone())
+                    .with_stack(std::mem::take(&mut buffers.stack));
+                let result = parser.parse();
+                buffers.stack = parser.take_stack();
+                result
+            }
+            backend => backend.parser().parse_with(input, options),
+        }
+    }
+
+    fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    fn take_compat_warnings(&mut self) -> Vec<CompatWarning> {
+        std::mem::take(&mut self.compat_warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::EmptyInputBehavior;
+
+    fn strict_deep() -> ParserOptions {
+        ParserOptions {
+            implicit_top_level: false,
+            max_depth: 10_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_auto_selects_iterative_for_deep_inputs() {
+        let deep = format!("{}{}", "[".repeat(2000), "]".repeat(2000));
+        let auto = ParserBackend::Auto;
+        assert_eq!(auto.select(&deep, &strict_deep()), ParserBackend::Iterative);
+
+        // Shallow however long, split across lines, or the depth limit
+        // keeps nesting shallow
+        assert_eq!(auto.select("[[1]]", &strict_deep()), ParserBackend::Hybrid);
+        let long = format!("[{}1]", "1 ".repeat(2000));
+        assert_eq!(auto.select(&long, &strict_deep()), ParserBackend::Hybrid);
+        let bracket_strings = format!("[{}]", r#""[[[[", "#.repeat(1000));
+        assert_eq!(
+            auto.select(&bracket_strings, &strict_deep()),
+            ParserBackend::Hybrid
+        );
+        let lines = format!("{deep}\n");
+        assert_eq!(auto.select(&lines, &strict_deep()), ParserBackend::Hybrid);
+        let shallow = ParserOptions {
+            max_depth: 128,
+            ..strict_deep()
+        };
+        assert_eq!(auto.select(&deep, &shallow), ParserBackend::Hybrid);
+
+        // Only the hybrid parser has implicit top-level values or reads
+        // escapes beyond JSON's
+        assert_eq!(
+            auto.select(&deep, &ParserOptions::default()),
+            ParserBackend::Hybrid
+        );
+        let escaped = format!(r#"{}"\x41"{}"#, "[".repeat(2000), "]".repeat(2000));
+        assert_eq!(auto.select(&escaped, &strict_deep()), ParserBackend::Hybrid);
+        let escaped = format!(r#"{}"\\x41"{}"#, "[".repeat(2000), "]".repeat(2000));
+        assert_eq!(
+            auto.select(&escaped, &strict_deep()),
+            ParserBackend::Iterative
+        );
+
+        assert_eq!(
+            ParserBackend::Recursive.select(&deep, &strict_deep()),
+            ParserBackend::Recursive
+        );
+    }
+
+    #[test]
+    fn test_auto_parser_parses_deep_input() {
+        let deep = format!("{}1{}", "[".repeat(5000), "]".repeat(5000));
+        let mut parser = ParserBackend::Auto.parser();
+        let mut value = parser.parse_with(&deep, &strict_deep()).unwrap();
+        let mut depth = 0;
+        while let Value::Array(mut items) = value {
+            value = items.pop().unwrap();
+            depth += 1;
+        }
+        assert_eq!(depth, 5000);
+    }
+
+    #[test]
+    fn test_every_backend_honours_empty_input_behavior() {
+        let parse = |backend: ParserBackend, input, behavior| {
+            let options = ParserOptions {
+                empty_input_behavior: behavior,
+                ..Default::default()
+            };
+            backend.parser().parse_with(input, &options)
+        };
+        for backend in ParserBackend::ALL {
+            for input in ["", " \n// c\n", "/* c */ "] {
+                let context = format!("{backend:?} {input:?}");
+                assert_eq!(
+                    parse(backend, input, EmptyInputBehavior::Null),
+                    Ok(Value::Null),
+                    "{context}"
+                );
+                assert_eq!(
+                    parse(backend, input, EmptyInputBehavior::EmptyArray),
+                    Ok(Value::Array(Vec::new())),
+                    "{context}"
+                );
+                assert_eq!(
+                    parse(backend, input, EmptyInputBehavior::Error),
+                    Err(crate::Error::UnexpectedEof(input.len())),
+                    "{context}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_backend_reads_line_breaks() {
+        let pretty = "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\":\n    {}\n}\n";
+        let separated = "{\n  \"a\": [\n    1\n    2\n  ]\n  \"b\": {}\n}";
+        for newline_as_comma in [true, false] {
+            let options = ParserOptions {
+                newline_as_comma,
+                implicit_top_level: false,
+                ..Default::default()
+            };
+            let expected = Parser::new("", options.clone())
+                .parse_with(r#"{"a": [1, 2], "b": {}}"#, &options)
+                .unwrap();
+            for backend in ParserBackend::ALL {
+                let context = format!("{backend:?} newline_as_comma: {newline_as_comma}");
+                let mut parser = backend.parser();
+                assert_eq!(
+                    parser.parse_with(pretty, &options).as_ref(),
+                    Ok(&expected),
+                    "{context}"
+                );
+                let separated = parser.parse_with(separated, &options);
+                if newline_as_comma {
+                    assert_eq!(separated.as_ref(), Ok(&expected), "{context}");
+                } else {
+                    assert!(separated.is_err(), "{context}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// this_file: crates/core/src/parser/budget.rs
+
+//! Time, memory and cancellation limits for a single parse.
+
+use crate::ast::Value;
+use crate::error::{BudgetLimit, Error, Result};
+use crate::parser::{Parser, ParserOptions};
+use std::mem::size_of;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Limits on the resources one parse may use.
+///
+/// A parse that exceeds any of them stops with [`Error::BudgetExceeded`].
+/// The deadline and cancel flag are checked every few hundred tokens, so a
+/// parse may run slightly past them; memory is an estimate of the size of
+/// the values built so far, not a count of actual allocations.
+#[derive(Debug, Clone, Default)]
+pub struct ParseBudget {
+    /// Time by which the parse must finish.
+    pub deadline: Option<Instant>,
+    /// Approximate number of bytes the parsed value may occupy.
+    pub max_memory: Option<usize>,
+    /// Flag that another thread sets to abandon the parse.
+    pub cancel_flag: Option<Arc<AtomicBool>>,
+}
+
+impl ParseBudget {
+    /// Creates a budget with no limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the deadline to `timeout` from now.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    /// Sets the approximate memory limit in bytes.
+    pub fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    /// Sets the flag that cancels the parse once it becomes `true`.
+    pub fn with_cancel_flag(mut self, cancel_flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = Some(cancel_flag);
+        self
+    }
+
+    /// Returns the limit that has run out, other than memory, if any.
+    pub(crate) fn exhausted(&self) -> Option<BudgetLimit> {
+        if is_cancelled(self.cancel_flag.as_ref()) {
+            Some(BudgetLimit::Cancelled)
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Some(BudgetLimit::Deadline)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether `flag` is present and set.
+pub(crate) fn is_cancelled(flag: Option<&Arc<AtomicBool>>) -> bool {
+    flag.is_some_and(|flag| flag.load(Ordering::Relaxed))
+}
+
+/// A [`ParseBudget`] and what a parse has used of it.
+#[derive(Debug)]
+pub(crate) struct BudgetTracker {
+    budget: ParseBudget,
+    tokens: u32,
+    memory: usize,
+}
+
+impl BudgetTracker {
+    /// Tokens read between checks of the clock and the cancel flag.
+    const CHECK_INTERVAL: u32 = 256;
+
+    pub(super) fn new(budget: ParseBudget) -> Self {
+        Self {
+            budget,
+            tokens: 0,
+            memory: 0,
+        }
+    }
+
+    /// Counts a token ending at `position`, checking the deadline and the
+    /// cancel flag every [`CHECK_INTERVAL`](Self::CHECK_INTERVAL) tokens.
+    pub(super) fn on_token(&mut self, position: usize) -> Result<()> {
+        self.tokens = self.tokens.wrapping_add(1);
+        if self.tokens % Self::CHECK_INTERVAL != 0 {
+            return Ok(());
+        }
+        self.check(position)
+    }
+
+    /// Checks the deadline and the cancel flag now.
+    pub(super) fn check(&self, position: usize) -> Result<()> {
+        match self.budget.exhausted() {
+            Some(limit) => Err(Error::BudgetExceeded { limit, position }),
+            None => Ok(()),
+        }
+    }
+
+    /// Accounts for `value`, parsed up to `position`.
+    pub(super) fn charge_value(&mut self, value: &Value, position: usize) -> Result<()> {
+        let heap = match value {
+            Value::String(s) => s.len(),
+            _ => 0,
+        };
+        self.charge(size_of::<Value>() + heap, position)
+    }
+
+    /// Accounts for an object key, parsed up to `position`.
+    pub(super) fn charge_key(&mut self, key: &str, position: usize) -> Result<()> {
+        self.charge(size_of::<String>() + key.len(), position)
+    }
+
+    fn charge(&mut self, bytes: usize, position: usize) -> Result<()> {
+        self.memory += bytes;
+        match self.budget.max_memory {
+            Some(max) if self.memory > max => Err(Error::BudgetExceeded {
+                limit: BudgetLimit::Memory,
+                position,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parses `input` within `budget`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use vexy_json_core::{parse_with_budget, ParseBudget, ParserOptions};
+///
+/// let budget = ParseBudget::new()
+///     .with_timeout(Duration::from_secs(1))
+///     .with_max_memory(1024);
+/// assert!(parse_with_budget("[1, 2, 3]", ParserOptions::default(), budget.clone()).is_ok());
+///
+/// let large = format!("[{}]", vec!["1"; 1000].join(","));
+/// assert!(parse_with_budget(&large, ParserOptions::default(), budget).is_err());
+/// ```
+pub fn parse_with_budget(
+    input: &str,
+    options: ParserOptions,
+    budget: ParseBudget,
+) -> Result<Value> {
+    let mut parser = Parser::new(input, options).with_budget(budget);
+    parser.parse()
+}
@@ -0,0 +1,90 @@
+// this_file: crates/core/src/parser/bytes.rs
+
+//! Parsing input that arrives as bytes rather than `&str`.
+
+use crate::ast::Value;
+use crate::error::{Error, Result, Span, Warning, WarningKind};
+use crate::parser::{InvalidUtf8Policy, Parser, ParserOptions};
+use std::borrow::Cow;
+
+/// Parses UTF-8 bytes with default options.
+///
+/// Invalid UTF-8 is an [`Error::InvalidUtf8`] at the byte offset of the
+/// first invalid sequence.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::parse_bytes;
+///
+/// assert!(parse_bytes(b"{key: [1, 2]}").is_ok());
+/// assert!(parse_bytes(b"[\"\xFF\"]").is_err());
+/// ```
+pub fn parse_bytes(input: &[u8]) -> Result<Value> {
+    parse_bytes_with_options(input, ParserOptions::default())
+}
+
+/// Parses UTF-8 bytes with custom options, decoding invalid UTF-8 as
+/// [`ParserOptions::invalid_utf8_policy`] says.
+pub fn parse_bytes_with_options(input: &[u8], options: ParserOptions) -> Result<Value> {
+    parse_bytes_with_warnings(input, options).map(|(value, _)| value)
+}
+
+/// Like [`parse_bytes_with_options`], also returning warnings.
+///
+/// With [`InvalidUtf8Policy::Replace`], each replaced sequence is reported
+/// as a [`WarningKind::InvalidUtf8`] warning whose span is a byte range of
+/// `input`. These come first and are always recorded. They are followed by
+/// the parser's own warnings when
+/// [`ParserOptions::collect_warnings`] is enabled, whose spans, like error
+/// positions, index into the decoded text. The two agree up to the first
+/// replacement.
+pub fn parse_bytes_with_warnings(
+    input: &[u8],
+    options: ParserOptions,
+) -> Result<(Value, Vec<Warning>)> {
+    let (text, mut warnings) = decode(input, options.invalid_utf8_policy)?;
+    let mut parser = Parser::new(&text, options);
+    let value = parser.parse()?;
+    warnings.extend(parser.take_warnings());
+    Ok((value, warnings))
+}
+
+/// Decodes `input` under `policy`, returning the text and a warning for
+/// each invalid sequence replaced.
+fn decode(input: &[u8], policy: InvalidUtf8Policy) -> Result<(Cow<'_, str>, Vec<Warning>)> {
+    let error = match std::str::from_utf8(input) {
+        Ok(text) => return Ok((Cow::Borrowed(text), Vec::new())),
+        Err(error) => error,
+    };
+    if policy == InvalidUtf8Policy::Error {
+        return Err(Error::InvalidUtf8(error.valid_up_to()));
+    }
+
+    let mut text = String::with_capacity(input.len());
+    let mut warnings = Vec::new();
+    let mut offset = 0;
+    while offset < input.len() {
+        let error = match std::str::from_utf8(&input[offset..]) {
+            Ok(valid) => {
+                text.push_str(valid);
+                break;
+            }
+            Err(error) => error,
+        };
+        let start = offset + error.valid_up_to();
+        // The prefix was just validated, so this cannot fail
+        text.push_str(std::str::from_utf8(&input[offset..start]).unwrap_or_default());
+        text.push(char::REPLACEMENT_CHARACTER);
+
+        // A sequence cut off by the end of input has no error length
+        let end = error.error_len().map_or(input.len(), |len| start + len);
+        warnings.push(Warning {
+            kind: WarningKind::InvalidUtf8,
+            message: "invalid UTF-8 replaced with U+FFFD".to_string(),
+            span: Span::new(start, end),
+        });
+        offset = end;
+    }
+    Ok((Cow::Owned(text), warnings))
+}
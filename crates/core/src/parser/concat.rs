@@ -0,0 +1,336 @@
+// this_file: crates/core/src/parser/concat.rs
+
+//! Concatenated documents: values written one after another with nothing or
+//! only whitespace between them, such as `{"a":1}{"b":2}`.
+
+use crate::ast::Value;
+use crate::error::Result;
+use crate::parser::{parse_with_options, ParserOptions};
+use std::ops::Range;
+
+/// Parses a stream of concatenated documents with default options.
+///
+/// Documents may follow each other directly, as log shippers often write
+/// them, or be separated by any mix of spaces and line breaks.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{parse, parse_concatenated};
+///
+/// let values = parse_concatenated(r#"{"a":1}{"b":"}"} [2]"#).unwrap();
+/// assert_eq!(values.len(), 3);
+/// assert_eq!(values[1], parse(r#"{"b": "}"}"#).unwrap());
+/// ```
+pub fn parse_concatenated(input: &str) -> Result<Vec<Value>> {
+    parse_concatenated_with_options(input, ParserOptions::default())
+}
+
+/// Parses a stream of concatenated documents with custom options.
+///
+/// Each document is parsed on its own with `options`, in order. The first
+/// that fails stops the parse, with an error positioned in `input` rather
+/// than in the document.
+pub fn parse_concatenated_with_options(input: &str, options: ParserOptions) -> Result<Vec<Value>> {
+    document_ranges(input, &options)
+        .into_iter()
+        .map(|range| {
+            let start = range.start;
+            parse_with_options(&input[range], options.clone())
+                .map_err(|error| error.offset_by(start))
+        })
+        .collect()
+}
+
+/// Whether `input` starts with an object or array followed directly by
+/// another, as in `{"a":1}{"b":2}`.
+///
+/// Input in which documents are separated by commas or line breaks is
+/// already read as an implicit top-level array, so only this form needs
+/// [`parse_concatenated`] to be chosen for it. Comments are recognised as
+/// the default options do; see [`is_concatenated_with_options`].
+pub fn is_concatenated(input: &str) -> bool {
+    is_concatenated_with_options(input, &ParserOptions::default())
+}
+
+/// Like [`is_concatenated`], recognising the comments `options` allows.
+pub fn is_concatenated_with_options(input: &str, options: &ParserOptions) -> bool {
+    let bytes = input.as_bytes();
+    let start = skip_separators(bytes, bom_len(input), options);
+    if !matches!(bytes.get(start), Some(b'{' | b'[')) {
+        return false;
+    }
+    let end = document_end(bytes, start, options);
+    matches!(bytes.get(end), Some(b'{' | b'['))
+}
+
+/// The byte ranges of the documents in `input`, in order.
+///
+/// The scan tracks nesting and skips over strings in either kind of quote
+/// and over the comments `options` allows, so brackets and escaped quotes
+/// inside them do not end a document. A document left open runs to the end
+/// of the input, and parsing it then reports what is missing.
+pub(crate) fn document_ranges(input: &str, options: &ParserOptions) -> Vec<Range<usize>> {
+    let bytes = input.as_bytes();
+    let mut ranges = Vec::new();
+    let mut start = skip_separators(bytes, bom_len(input), options);
+    while start < bytes.len() {
+        let end = document_end(bytes, start, options);
+        ranges.push(start..end);
+        start = skip_separators(bytes, end, options);
+    }
+    ranges
+}
+
+/// Whether `input` holds nothing but whitespace and the comments `options`
+/// allows, and so reads as [`ParserOptions::empty_input_behavior`] says.
+pub(crate) fn is_blank(input: &str, options: &ParserOptions) -> bool {
+    let bytes = input.as_bytes();
+    let mut i = bom_len(input);
+    loop {
+        i = skip_separators(bytes, i, options);
+        match input[i..].chars().next() {
+            None => return true,
+            Some(c) if options.allow_unicode_whitespace && c.is_whitespace() => i += c.len_utf8(),
+            Some(_) => return false,
+        }
+    }
+}
+
+/// How deeply the brackets in `input` nest, skipping strings and the
+/// comments `options` allows as [`document_ranges`] does.
+pub(crate) fn nesting_depth(input: &str, options: &ParserOptions) -> usize {
+    let bytes = input.as_bytes();
+    let (mut depth, mut deepest) = (0usize, 0usize);
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(end) = comment_end(bytes, i, options) {
+            i = end;
+            continue;
+        }
+        match bytes[i] {
+            b'"' => {
+                i = string_end(bytes, i);
+                continue;
+            }
+            b'\'' if options.allow_single_quotes => {
+                i = string_end(bytes, i);
+                continue;
+            }
+            b'{' | b'[' => {
+                depth += 1;
+                deepest = deepest.max(depth);
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        i += 1;
+    }
+    deepest
+}
+
+fn bom_len(input: &str) -> usize {
+    if input.starts_with('\u{feff}') {
+        '\u{feff}'.len_utf8()
+    } else {
+        0
+    }
+}
+
+/// Skips whitespace and comments from `i`.
+fn skip_separators(bytes: &[u8], mut i: usize, options: &ParserOptions) -> usize {
+    loop {
+        if bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        } else if let Some(end) = comment_end(bytes, i, options) {
+            i = end;
+        } else {
+            return i;
+        }
+    }
+}
+
+/// The end of the document starting at `start`: after the bracket that
+/// closes it, the quote that ends it, or, for any other value, before the
+/// next whitespace, bracket, quote or comment.
+fn document_end(bytes: &[u8], start: usize, options: &ParserOptions) -> usize {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if depth == 0 && i > start && (byte.is_ascii_whitespace() || b"{[\"'".contains(&byte)) {
+            return i;
+        }
+        if let Some(end) = comment_end(bytes, i, options) {
+            if depth == 0 {
+                return i;
+            }
+            i = end;
+            continue;
+        }
+        match byte {
+            b'"' | b'\'' => {
+                i = string_end(bytes, i);
+                if depth == 0 {
+                    return i;
+                }
+                continue;
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                // A stray closing bracket is a document of its own, which
+                // then fails to parse
+                if depth <= 1 {
+                    return i + 1;
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// The end of the comment starting at `start`, if one the options allow
+/// starts there: the end of its line, or after the `*/` that closes it.
+fn comment_end(bytes: &[u8], start: usize, options: &ParserOptions) -> Option<usize> {
+    if !options.allow_comments {
+        return None;
+    }
+    let styles = options.comment_styles;
+    let rest = &bytes[start..];
+    let line_end = || {
+        rest.iter()
+            .position(|&b| b == b'\n')
+            .map_or(bytes.len(), |at| start + at)
+    };
+    if (styles.line_slash && rest.starts_with(b"//")) || (styles.hash && rest.starts_with(b"#")) {
+        return Some(line_end());
+    }
+    if !(styles.block && rest.starts_with(b"/*")) {
+        return None;
+    }
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") && (depth == 0 || options.allow_nested_block_comments) {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return Some(i);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    Some(bytes.len())
+}
+
+/// The end of the string whose opening quote is at `start`, after its
+/// closing quote.
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            byte if byte == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::CommentStyles;
+
+    fn documents(input: &str) -> Vec<&str> {
+        documents_with(input, &ParserOptions::default())
+    }
+
+    fn documents_with<'a>(input: &'a str, options: &ParserOptions) -> Vec<&'a str> {
+        document_ranges(input, options)
+            .into_iter()
+            .map(|range| &input[range])
+            .collect()
+    }
+
+    #[test]
+    fn test_document_ranges() {
+        assert_eq!(
+            documents(r#"{"a":"}"}["\"]"]  1 true"s"{}"#),
+            [r#"{"a":"}"}"#, r#"["\"]"]"#, "1", "true", r#""s""#, "{}"]
+        );
+        assert_eq!(documents("\u{feff} [1]\n\t{}\r\n"), ["[1]", "{}"]);
+        assert_eq!(documents("[[1], {]"), ["[[1], {]"]);
+        assert_eq!(documents("} {}"), ["}", "{}"]);
+        assert!(documents(" \n ").is_empty());
+    }
+
+    #[test]
+    fn test_document_ranges_skip_comments() {
+        assert_eq!(
+            documents(r#"{"a": 1 /* } */}{"b":2}"#),
+            [r#"{"a": 1 /* } */}"#, r#"{"b":2}"#]
+        );
+        assert_eq!(
+            documents("[1, // ]\n 2] # {\n{\"c\": /* /* ] */ } */ 3}"),
+            ["[1, // ]\n 2]", "{\"c\": /* /* ] */ } */ 3}"]
+        );
+        assert_eq!(documents("1// c\n2"), ["1", "2"]);
+        assert!(is_concatenated(r#"{"a": 1 /* } */}{"b":2}"#));
+        assert_eq!(
+            parse_concatenated(r#"{"a": 1 /* } */}{"b":2}"#).unwrap(),
+            [
+                crate::parse(r#"{"a": 1}"#).unwrap(),
+                crate::parse(r#"{"b": 2}"#).unwrap()
+            ]
+        );
+
+        // Only the comments the options allow are skipped
+        let options = ParserOptions {
+            allow_nested_block_comments: false,
+            comment_styles: CommentStyles {
+                hash: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            documents_with("[/* /* */ 1]{a: #fff}", &options),
+            ["[/* /* */ 1]", "{a: #fff}"]
+        );
+        let options = ParserOptions {
+            allow_comments: false,
+            ..Default::default()
+        };
+        assert_eq!(documents_with("[1, /* ] */", &options), ["[1, /* ]", "*/"]);
+    }
+
+    #[test]
+    fn test_blank_input_and_nesting_depth() {
+        let options = ParserOptions::default();
+        for blank in ["", " \n\t", "\u{feff}// c\n/* d */", "\u{a0}# e"] {
+            assert!(is_blank(blank, &options), "{blank:?}");
+        }
+        assert!(!is_blank(" 1 ", &options));
+        let strict = ParserOptions {
+            allow_comments: false,
+            allow_unicode_whitespace: false,
+            ..Default::default()
+        };
+        assert!(!is_blank("// c", &strict));
+        assert!(!is_blank("\u{a0}", &strict));
+
+        assert_eq!(nesting_depth("1", &options), 0);
+        assert_eq!(nesting_depth("[[1], {a: [2]}]", &options), 3);
+        assert_eq!(nesting_depth(r#"["[[", '[', /* [ */ {}]"#, &options), 2);
+        assert_eq!(nesting_depth(&"[".repeat(600), &options), 600);
+    }
+}
@@ -0,0 +1,268 @@
+// this_file: crates/core/src/parser/elements.rs
+
+//! Pull-based parsing of a top-level array, one element at a time.
+
+use crate::ast::{Token, Value};
+use crate::error::{Error, Result, Span};
+use crate::parser::path::PathEntry;
+use crate::parser::{Parser, ParserOptions};
+
+/// Parses a document holding a single array, yielding its elements one at
+/// a time.
+///
+/// Only the opening bracket is read up front; it fails if the document
+/// holds anything other than an array. Each element is then parsed in full
+/// as the iterator reaches it, so memory use depends on the largest element
+/// rather than the whole array. Separators follow `options` as in
+/// [`parse_with_options`](crate::parse_with_options): trailing commas,
+/// line breaks as commas and comments between elements, and a separator
+/// with no value before it is read as `null`.
+///
+/// Each element comes with the byte span of its text in `input`. The first
+/// error, whether in an element, between elements or after the closing
+/// bracket, ends the iteration.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{parse_array_iter, ParserOptions};
+///
+/// let input = r#"[{"id": 1}, {"id": 2},]"#;
+/// let mut ids = Vec::new();
+/// for element in parse_array_iter(input, ParserOptions::default()).unwrap() {
+///     let (value, span) = element.unwrap();
+///     assert_eq!(&input[span.start..span.end], format!(r#"{{"id": {}}}"#, ids.len() + 1));
+///     ids.push(value["id"].as_i64().unwrap());
+/// }
+/// assert_eq!(ids, [1, 2]);
+///
+/// assert!(parse_array_iter(r#"{"id": 1}"#, ParserOptions::default()).is_err());
+/// ```
+pub fn parse_array_iter(input: &str, options: ParserOptions) -> Result<ArrayElements<'_>> {
+    let mut parser = Parser::new(input, options);
+    parser.advance()?;
+    parser.skip_comments_and_newlines()?;
+    if !matches!(parser.current_token, Some((Token::LeftBracket, _))) {
+        return Err(Error::Expected {
+            expected: "[".to_string(),
+            found: format!("{:?}", parser.current_token.as_ref().map(|(t, _)| t)),
+            position: parser.token_position(),
+        });
+    }
+    parser.check_depth()?;
+    parser.state.depth += 1;
+    parser.advance()?;
+    Ok(ArrayElements {
+        parser,
+        index: 0,
+        after: After::Start,
+    })
+}
+
+/// Iterator over the elements of a top-level array, returned by
+/// [`parse_array_iter`].
+pub struct ArrayElements<'a> {
+    parser: Parser<'a>,
+    /// Index of the next element.
+    index: usize,
+    after: After,
+}
+
+/// What the iterator last read.
+#[derive(Clone, Copy, PartialEq)]
+enum After {
+    /// The opening bracket.
+    Start,
+    /// An element, whose separator has not been read yet.
+    Element,
+    /// A separator with no value before it.
+    Missing,
+    /// The end of the document, or an error.
+    Done,
+}
+
+impl ArrayElements<'_> {
+    fn next_element(&mut self) -> Result<Option<(Value, Span)>> {
+        match self.after {
+            After::Element => self.parser.finish_array_element(self.index)?,
+            // Further separators straight after a missing value are missing
+            // values too, as in `[1,,,2]`
+            After::Missing if self.parser.is_separator() => return self.missing_value().map(Some),
+            _ => {}
+        }
+
+        self.parser.lex_into(
+            PathEntry::Index(self.index),
+            Parser::skip_comments_and_newlines,
+        )?;
+        if self.parser.is_separator() {
+            return self.missing_value().map(Some);
+        }
+        let parser = &mut self.parser;
+        if let Some((Token::RightBracket, _)) = parser.current_token {
+            parser.state.depth -= 1;
+            parser.advance()?;
+            parser.skip_comments_and_newlines()?;
+            if !matches!(parser.current_token, Some((Token::Eof, _))) {
+                return Err(Error::Expected {
+                    expected: "end of input".to_string(),
+                    found: format!("{:?}", parser.current_token.as_ref().map(|(t, _)| t)),
+                    position: parser.token_position(),
+                });
+            }
+            return Ok(None);
+        }
+
+        let start = parser.state.span.start;
+        let value = parser.parse_value_at(PathEntry::Index(self.index))?;
+        let span = Span::new(start, parser.state.previous_end);
+        self.index += 1;
+        self.after = After::Element;
+        Ok(Some((value, span)))
+    }
+
+    /// Reads the separator at the current token as a `null` element, with
+    /// an empty span where the separator starts.
+    fn missing_value(&mut self) -> Result<(Value, Span)> {
+        self.parser.check_array_len(self.index)?;
+        let separator = self.parser.state.span;
+        self.parser.note_missing_value(separator);
+        self.parser
+            .lex_into(PathEntry::Index(self.index + 1), Parser::advance)?;
+        self.index += 1;
+        self.after = After::Missing;
+        Ok((Value::Null, Span::new(separator.start, separator.start)))
+    }
+}
+
+impl Iterator for ArrayElements<'_> {
+    type Item = Result<(Value, Span)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.after == After::Done {
+            return None;
+        }
+        match self.next_element() {
+            Ok(Some(element)) => Some(Ok(element)),
+            Ok(None) => {
+                self.after = After::Done;
+                None
+            }
+            Err(error) => {
+                self.after = After::Done;
+                Some(Err(self.parser.annotate_error(error)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Number;
+    use crate::parser::parse_with_options;
+
+    fn elements(input: &str, options: ParserOptions) -> Vec<(Value, &str)> {
+        parse_array_iter(input, options)
+            .unwrap()
+            .map(|element| {
+                let (value, span) = element.unwrap();
+                (value, &input[span.start..span.end])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_elements_match_full_parse() {
+        let options = ParserOptions {
+            newline_as_comma: true,
+            ..Default::default()
+        };
+        for input in [
+            "[]",
+            " \n[1, 'two', {three: [3]}, null] \n",
+            "[1,,,2,]",
+            "[,1]",
+            "[\n  1 // one\n  2\n  /* three */ [3]\n]",
+            "// leading comment\n[true,\n false,\n]",
+            "\u{feff}[\"é\", 2]",
+        ] {
+            let values: Vec<Value> = elements(input, options.clone())
+                .into_iter()
+                .map(|(value, _)| value)
+                .collect();
+            assert_eq!(
+                Value::Array(values),
+                parse_with_options(input, options.clone()).unwrap(),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_element_spans() {
+        let input = "[1, \"a,b\" ,{\"k\": [2, 3]},\n  -4.5e1 ,,]";
+        let spans: Vec<&str> = elements(input, ParserOptions::default())
+            .into_iter()
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(spans, ["1", "\"a,b\"", "{\"k\": [2, 3]}", "-4.5e1", ""]);
+    }
+
+    #[test]
+    fn test_top_level_must_be_an_array() {
+        for input in ["{\"a\": 1}", "1, 2", "", "  // nothing\n"] {
+            let error = parse_array_iter(input, ParserOptions::default())
+                .err()
+                .unwrap_or_else(|| panic!("{input}"));
+            assert!(error.to_string().contains("Expected [ "), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_errors_end_the_iteration() {
+        // Elements before the bad one are still yielded
+        let mut iter = parse_array_iter("[1, 2, @, 4]", ParserOptions::default()).unwrap();
+        assert_eq!(
+            iter.next().unwrap().unwrap().0,
+            Value::Number(Number::Integer(1))
+        );
+        assert_eq!(
+            iter.next().unwrap().unwrap().0,
+            Value::Number(Number::Integer(2))
+        );
+        let error = iter.next().unwrap().unwrap_err();
+        assert_eq!(error.position(), Some(7));
+        assert!(iter.next().is_none());
+
+        let strict = ParserOptions {
+            allow_trailing_commas: false,
+            ..Default::default()
+        };
+        let results: Vec<_> = parse_array_iter("[1, 2,]", strict).unwrap().collect();
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[2], Err(Error::TrailingComma(_))));
+
+        for input in ["[1, 2", "[1] [2]", "[1: 2]"] {
+            let results: Vec<_> = parse_array_iter(input, ParserOptions::default())
+                .unwrap()
+                .collect();
+            assert!(results.last().unwrap().is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_errors_carry_paths() {
+        let options = ParserOptions {
+            track_paths: true,
+            ..Default::default()
+        };
+        let input = "[{}, {\"a\": [1, 2]}, {\"b\": @}]";
+        let error = parse_array_iter(input, options.clone())
+            .unwrap()
+            .find_map(|element| element.err())
+            .unwrap();
+        assert!(error.to_string().ends_with("at path $[2].b"), "{error}");
+        assert_eq!(error, parse_with_options(input, options).unwrap_err());
+    }
+}
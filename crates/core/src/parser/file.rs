@@ -0,0 +1,37 @@
+// this_file: crates/core/src/parser/file.rs
+
+//! Parsing input read from a file.
+
+use crate::ast::Value;
+use crate::error::{Error, Result};
+use crate::parser::{parse_bytes_with_options, ParserOptions};
+use std::path::Path;
+
+/// Parses the file at `path` with default options.
+///
+/// A byte order mark at the start of the file is skipped. For files too
+/// large to hold in memory, stream events with
+/// [`events_from_file`](crate::streaming::events_from_file) instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use vexy_json_core::parse_file;
+///
+/// let config = parse_file("config.json").unwrap();
+/// println!("{}", config["name"]);
+/// ```
+pub fn parse_file(path: impl AsRef<Path>) -> Result<Value> {
+    parse_file_with_options(path, ParserOptions::default())
+}
+
+/// Parses the file at `path` with custom options.
+///
+/// The file is read into memory in one piece, then parsed as
+/// [`parse_bytes_with_options`] would parse its contents. Failure to read
+/// it is an [`Error::Io`].
+pub fn parse_file_with_options(path: impl AsRef<Path>, options: ParserOptions) -> Result<Value> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path).map_err(|error| Error::io(path, error))?;
+    parse_bytes_with_options(&bytes, options)
+}
@@ -0,0 +1,98 @@
+// this_file: crates/core/src/parser/into.rs
+
+//! Parsing into values whose objects are held in a chosen kind of map.
+
+use crate::ast::{MapLike, MapOrder, Value, ValueG};
+use crate::error::Result;
+use crate::format::KeyOrder;
+use crate::parser::{parse_with_options, DuplicateKeyPolicy, ParserOptions};
+use rustc_hash::FxHashMap;
+
+/// Parses `input` with the default options into a value whose objects are
+/// held in the kind of map `M`.
+///
+/// With a map that keeps insertion order, such as
+/// [`Ordered`](crate::ast::Ordered), object members come out in the order
+/// they are written. A key written more than once keeps the place of its
+/// first occurrence.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::ast::SortedValue;
+/// use vexy_json_core::parse_into;
+///
+/// let value: SortedValue = parse_into("{b: 1, a: {d: 2, c: 3}}").unwrap();
+/// assert_eq!(value.to_string(), r#"{"a":{"c":3,"d":2},"b":1}"#);
+/// ```
+pub fn parse_into<M: MapLike>(input: &str) -> Result<ValueG<M>> {
+    parse_into_with_options(input, ParserOptions::default())
+}
+
+/// Parses `input` with custom options into a value whose objects are held
+/// in the kind of map `M`, as [`parse_into`] does.
+pub fn parse_into_with_options<M: MapLike>(
+    input: &str,
+    options: ParserOptions,
+) -> Result<ValueG<M>> {
+    let first_wins = options.duplicate_keys == DuplicateKeyPolicy::FirstWins;
+    let order = match M::ORDER {
+        MapOrder::Insertion => Some(KeyOrder::from_source_with_options(input, &options)),
+        MapOrder::Arbitrary | MapOrder::Sorted => None,
+    };
+    let value = parse_with_options(input, options)?;
+    Ok(match order {
+        Some(order) => in_source_order(value, &order, first_wins),
+        None => value.into_map(),
+    })
+}
+
+/// Converts `value`, inserting object members in the order `order` recorded
+/// from the source. Members it does not record, such as those of repaired
+/// input, follow in sorted key order.
+fn in_source_order<M: MapLike>(value: Value, order: &KeyOrder, first_wins: bool) -> ValueG<M> {
+    match value {
+        Value::Array(arr) => ValueG::Array(
+            arr.into_iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    in_source_order(
+                        item,
+                        order.item(i).unwrap_or(&KeyOrder::default()),
+                        first_wins,
+                    )
+                })
+                .collect(),
+        ),
+        Value::Object(mut obj) => {
+            let members = order.members();
+            // The value kept is that of the first or last occurrence, so its
+            // own members follow that occurrence's order
+            let mut kept: FxHashMap<&str, &KeyOrder> = FxHashMap::default();
+            for (key, item_order) in members {
+                if !first_wins || !kept.contains_key(key.as_str()) {
+                    kept.insert(key, item_order);
+                }
+            }
+            let mut map = M::Map::default();
+            for (key, _) in members {
+                let Some(item) = obj.remove(key) else {
+                    continue;
+                };
+                let item_order = kept[key.as_str()];
+                M::insert(
+                    &mut map,
+                    key.clone(),
+                    in_source_order(item, item_order, first_wins),
+                );
+            }
+            let mut rest: Vec<_> = obj.into_iter().collect();
+            rest.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+            for (key, item) in rest {
+                M::insert(&mut map, key, item.into_map());
+            }
+            ValueG::Object(map)
+        }
+        other => other.into_map(),
+    }
+}
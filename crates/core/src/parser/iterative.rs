@@ -6,15 +6,16 @@
 //! on deeply nested JSON structures. It's particularly useful for parsing large
 //! JSON documents with deep nesting.
 
-use crate::ast::{Number, Token, Value};
+use crate::ast::{Token, Value};
 use crate::error::{Error, Result, Span};
 use crate::lexer::{JsonLexer, Lexer};
-use crate::parser::ParserOptions;
+use crate::parser::number::read_number;
+use crate::parser::{is_blank, JsonParser, ParserOptions};
 use rustc_hash::FxHashMap;
 
 /// Parsing context for the iterative parser stack.
 #[derive(Debug, Clone)]
-enum ParseContext {
+pub(crate) enum ParseContext {
     /// Parsing a value at the top level
     Value,
     /// Parsing an object - need to parse key-value pairs
@@ -25,11 +26,15 @@ enum ParseContext {
         current_key: Option<String>,
         /// Whether we're expecting a key (true) or value (false)
         expecting_key: bool,
+        /// Where the member currently being parsed starts
+        member_start: usize,
     },
     /// Parsing an array - need to parse elements
     Array {
         /// The array being built
         array: Vec<Value>,
+        /// Where the element currently being parsed starts
+        member_start: usize,
     },
 }
 
@@ -38,6 +43,7 @@ enum ParseContext {
 /// This parser uses an explicit stack to track parsing context, making it
 /// suitable for parsing deeply nested JSON without stack overflow concerns.
 pub struct IterativeParser<'a> {
+    input: &'a str,
     lexer: Lexer<'a>,
     current_token: Option<(Token, Span)>,
     options: ParserOptions,
@@ -45,44 +51,68 @@ pub struct IterativeParser<'a> {
     parse_stack: Vec<ParseContext>,
     /// The final result value
     result: Option<Value>,
+    /// Whether to record errors and keep going instead of failing
+    recover: bool,
+    /// Errors recorded while recovering
+    errors: Vec<Error>,
+    /// End of the last token consumed from the input
+    consumed_end: usize,
+    /// Whether the last advance failed, leaving `current_token` already consumed
+    stale_token: bool,
+    /// Whether a value is being parsed for the innermost container
+    in_value: bool,
 }
 
 impl<'a> IterativeParser<'a> {
     /// Creates a new iterative parser.
     pub fn new(input: &'a str, options: ParserOptions) -> Self {
         Self {
+            input,
             lexer: Lexer::new(input),
             current_token: None,
             options,
             parse_stack: Vec::new(),
             result: None,
+            recover: false,
+            errors: Vec::new(),
+            consumed_end: 0,
+            stale_token: false,
+            in_value: false,
         }
     }
 
+    /// Builds the stack in `stack`, an empty one left by an earlier parse,
+    /// rather than in a new allocation.
+    pub(crate) fn with_stack(mut self, stack: Vec<ParseContext>) -> Self {
+        debug_assert!(stack.is_empty());
+        self.parse_stack = stack;
+        self
+    }
+
+    /// Takes the stack, emptied, to hand on to a later parse.
+    pub(crate) fn take_stack(&mut self) -> Vec<ParseContext> {
+        let mut stack = std::mem::take(&mut self.parse_stack);
+        stack.clear();
+        stack
+    }
+
     /// Parses the input and returns the parsed value.
     pub fn parse(&mut self) -> Result<Value> {
+        if is_blank(self.input, &self.options) {
+            return self.options.empty_input_behavior.value(self.input.len());
+        }
         self.advance()?;
+        self.skip_newlines()?;
 
         // Start with a top-level value context
         self.parse_stack.push(ParseContext::Value);
 
         // Main parsing loop
         while !self.parse_stack.is_empty() {
-            match self.parse_stack.last() {
-                Some(ParseContext::Value) => {
-                    self.parse_stack.pop();
-                    self.parse_value_and_push()?;
-                }
-                Some(ParseContext::Object { .. }) => {
-                    self.parse_object_step()?;
-                }
-                Some(ParseContext::Array { .. }) => {
-                    self.parse_array_step()?;
-                }
-                None => unreachable!(),
-            }
+            self.step()?;
         }
 
+        self.skip_newlines()?;
         self.expect_eof()?;
 
         self.result
@@ -90,10 +120,240 @@ impl<'a> IterativeParser<'a> {
             .ok_or_else(|| Error::Custom("No result produced".to_string()))
     }
 
-    /// Advances to the next token, skipping comments if allowed.
+    /// Parses as much of the input as possible, never failing.
+    ///
+    /// Each error is recorded with the span of the member it spoiled and
+    /// parsing resumes at the next comma or closing bracket of the enclosing
+    /// container. Members whose value could not be parsed are kept as
+    /// [`Value::Null`] placeholders; containers left open at the end of input
+    /// are closed.
+    pub fn parse_partial(mut self) -> (Value, Vec<Error>) {
+        self.recover = true;
+
+        while let Err(error) = self.advance().and_then(|()| self.skip_newlines()) {
+            let span = Span::new(self.lexer.span().start, self.lexer.span().end);
+            self.errors.push(error.with_span(span));
+        }
+        let root_start = self.current_span().start;
+        self.parse_stack.push(ParseContext::Value);
+
+        while !self.parse_stack.is_empty() {
+            if let Err(error) = self.step() {
+                self.recover_from(error, root_start);
+            }
+        }
+
+        let _ = self.skip_newlines();
+        if !matches!(self.peek(), Some(Token::Eof)) {
+            let start = self.current_span().start;
+            let error = self.expect_eof().unwrap_err();
+            let end = self.lexer.span().end.max(start);
+            self.errors.push(error.with_span(Span::new(start, end)));
+        }
+
+        (self.result.take().unwrap_or(Value::Null), self.errors)
+    }
+
+    /// Runs one step of the parsing loop for the context on top of the stack.
+    fn step(&mut self) -> Result<()> {
+        match self.parse_stack.last() {
+            Some(ParseContext::Value) => {
+                self.parse_stack.pop();
+                self.in_value = true;
+                self.parse_value_and_push()?;
+                self.in_value = false;
+            }
+            Some(ParseContext::Object { .. }) => {
+                self.parse_object_step()?;
+            }
+            Some(ParseContext::Array { .. }) => {
+                self.parse_array_step()?;
+            }
+            None => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Records `error` against the member being parsed, skips to the next
+    /// comma or closing bracket at the same level and resumes from there.
+    fn recover_from(&mut self, error: Error, root_start: usize) {
+        while matches!(self.parse_stack.last(), Some(ParseContext::Value)) {
+            self.parse_stack.pop();
+        }
+        self.result = None;
+        let in_value = std::mem::take(&mut self.in_value);
+
+        let Some(member_start) = self.parse_stack.last().map(|context| match context {
+            ParseContext::Object { member_start, .. }
+            | ParseContext::Array { member_start, .. } => *member_start,
+            ParseContext::Value => unreachable!(),
+        }) else {
+            // The top-level value itself is broken; nothing after it can be trusted
+            while !matches!(self.peek(), Some(Token::Eof)) {
+                let _ = self.advance();
+            }
+            let span = Span::new(root_start, self.consumed_end.max(root_start));
+            self.errors.push(error.with_span(span));
+            self.result = Some(Value::Null);
+            return;
+        };
+
+        self.skip_to_sync();
+        let end = self.consumed_end.max(member_start);
+        self.errors
+            .push(error.with_span(Span::new(member_start, end)));
+
+        if in_value || end > member_start {
+            self.add_placeholder();
+        }
+
+        match self.peek() {
+            Some(Token::Comma) => {
+                let _ = self.advance().and_then(|()| self.skip_newlines());
+                if matches!(self.peek(), Some(Token::RightBrace | Token::RightBracket)) {
+                    self.close_innermost();
+                } else {
+                    self.expect_next_member();
+                }
+            }
+            Some(Token::Newline) => {
+                let _ = self.advance().and_then(|()| self.skip_newlines());
+                self.expect_next_member();
+            }
+            Some(Token::RightBrace | Token::RightBracket) => self.close_innermost(),
+            _ => self.close_all(),
+        }
+    }
+
+    /// Skips tokens until a comma, newline separator or closing bracket at the
+    /// current nesting level, or the end of input.
+    fn skip_to_sync(&mut self) {
+        let mut depth = 0usize;
+        if std::mem::take(&mut self.stale_token) {
+            // The current token was consumed before the lexer failed
+            if matches!(self.peek(), Some(Token::LeftBrace | Token::LeftBracket)) {
+                depth += 1;
+            }
+            let _ = self.advance();
+        }
+
+        loop {
+            if !self.stale_token {
+                match self.peek() {
+                    Some(Token::Eof) | None => break,
+                    Some(Token::Comma | Token::RightBrace | Token::RightBracket) if depth == 0 => {
+                        break
+                    }
+                    Some(Token::Newline) if depth == 0 && self.options.newline_as_comma => break,
+                    Some(Token::LeftBrace | Token::LeftBracket) => depth += 1,
+                    Some(Token::RightBrace | Token::RightBracket) => depth -= 1,
+                    _ => {}
+                }
+            }
+            // Lexer errors inside the skipped member are covered by its error
+            let _ = self.advance();
+        }
+    }
+
+    /// Stores a [`Value::Null`] placeholder for the member that failed.
+    fn add_placeholder(&mut self) {
+        match self.parse_stack.last_mut() {
+            Some(ParseContext::Object {
+                object,
+                current_key,
+                ..
+            }) => {
+                if let Some(key) = current_key.take() {
+                    object.insert(key, Value::Null);
+                }
+            }
+            Some(ParseContext::Array { array, .. }) => array.push(Value::Null),
+            _ => {}
+        }
+    }
+
+    /// Prepares the innermost container to parse its next member.
+    fn expect_next_member(&mut self) {
+        let start = self.current_span().start;
+        match self.parse_stack.last_mut() {
+            Some(ParseContext::Object {
+                current_key,
+                expecting_key,
+                member_start,
+                ..
+            }) => {
+                *current_key = None;
+                *expecting_key = true;
+                *member_start = start;
+            }
+            Some(ParseContext::Array { member_start, .. }) => {
+                *member_start = start;
+                self.parse_stack.push(ParseContext::Value);
+            }
+            _ => {}
+        }
+    }
+
+    /// Consumes the closing bracket and finishes the innermost container.
+    fn close_innermost(&mut self) {
+        let _ = self.advance();
+        let value = match self.parse_stack.pop() {
+            Some(ParseContext::Object { object, .. }) => Value::Object(object),
+            Some(ParseContext::Array { array, .. }) => Value::Array(array),
+            _ => Value::Null,
+        };
+        self.result = Some(value);
+    }
+
+    /// Closes every open container at the end of input.
+    fn close_all(&mut self) {
+        let mut value = self.result.take();
+        while let Some(context) = self.parse_stack.pop() {
+            value = match context {
+                ParseContext::Value => value,
+                ParseContext::Object {
+                    mut object,
+                    current_key,
+                    ..
+                } => {
+                    if let Some(key) = current_key {
+                        object.insert(key, value.take().unwrap_or(Value::Null));
+                    }
+                    Some(Value::Object(object))
+                }
+                ParseContext::Array { mut array, .. } => {
+                    array.extend(value.take());
+                    Some(Value::Array(array))
+                }
+            };
+        }
+        self.result = value;
+    }
+
+    /// Replaces the context on top of the stack, keeping values parsed so far
+    /// when a step fails part-way.
+    fn restore(&mut self, context: ParseContext) {
+        if let Some(top) = self.parse_stack.last_mut() {
+            *top = context;
+        }
+    }
+
+    /// Advances to the next token, skipping comments if allowed and line
+    /// breaks unless they separate values.
     fn advance(&mut self) -> Result<()> {
+        if let Some((_, span)) = &self.current_token {
+            self.consumed_end = self.consumed_end.max(span.end);
+        }
         loop {
-            let (token, span) = self.lexer.next_token_with_span()?;
+            let (token, span) = match self.lexer.next_token_with_span() {
+                Ok(next) => next,
+                Err(error) => {
+                    self.stale_token = true;
+                    self.consumed_end = self.consumed_end.max(self.lexer.span().end);
+                    return Err(error);
+                }
+            };
+            self.stale_token = false;
             self.current_token = Some((token, span));
 
             match &self.current_token.as_ref().unwrap().0 {
@@ -104,12 +364,22 @@ impl<'a> IterativeParser<'a> {
                         return Err(Error::Custom("Comments are not allowed".to_string()));
                     }
                 }
+                Token::Newline if !self.options.newline_as_comma => continue,
                 _ => break,
             }
         }
         Ok(())
     }
 
+    /// Skips line breaks where they cannot separate values, which
+    /// [`advance`](Self::advance) only keeps under `newline_as_comma`.
+    fn skip_newlines(&mut self) -> Result<()> {
+        while matches!(self.peek(), Some(Token::Newline)) {
+            self.advance()?;
+        }
+        Ok(())
+    }
+
     /// Gets the current token without consuming it.
     fn peek(&self) -> Option<&Token> {
         self.current_token.as_ref().map(|(token, _)| token)
@@ -141,18 +411,23 @@ impl<'a> IterativeParser<'a> {
         match self.peek() {
             Some(Token::LeftBrace) => {
                 self.advance()?;
+                self.skip_newlines()?;
                 self.parse_stack.push(ParseContext::Object {
                     object: FxHashMap::default(),
                     current_key: None,
                     expecting_key: true,
+                    member_start: self.current_span().start,
                 });
                 // Don't return a value - the object context will handle it
                 Ok(Value::Null)
             }
             Some(Token::LeftBracket) => {
                 self.advance()?;
-                self.parse_stack
-                    .push(ParseContext::Array { array: Vec::new() });
+                self.skip_newlines()?;
+                self.parse_stack.push(ParseContext::Array {
+                    array: Vec::new(),
+                    member_start: self.current_span().start,
+                });
                 // Don't return a value - the array context will handle it
                 Ok(Value::Null)
             }
@@ -191,16 +466,21 @@ impl<'a> IterativeParser<'a> {
         match self.peek() {
             Some(Token::LeftBrace) => {
                 self.advance()?;
+                self.skip_newlines()?;
                 self.parse_stack.push(ParseContext::Object {
                     object: FxHashMap::default(),
                     current_key: None,
                     expecting_key: true,
+                    member_start: self.current_span().start,
                 });
             }
             Some(Token::LeftBracket) => {
                 self.advance()?;
-                self.parse_stack
-                    .push(ParseContext::Array { array: Vec::new() });
+                self.skip_newlines()?;
+                self.parse_stack.push(ParseContext::Array {
+                    array: Vec::new(),
+                    member_start: self.current_span().start,
+                });
             }
             _ => {
                 let value = self.parse_value()?;
@@ -223,6 +503,7 @@ impl<'a> IterativeParser<'a> {
             mut object,
             current_key,
             expecting_key,
+            member_start,
         } = context
         {
             // Handle empty object or immediate close
@@ -235,8 +516,18 @@ impl<'a> IterativeParser<'a> {
             }
 
             if expecting_key {
+                let member_start = self.current_span().start;
+                if let Some(ParseContext::Object {
+                    member_start: start,
+                    ..
+                }) = self.parse_stack.last_mut()
+                {
+                    *start = member_start;
+                }
+
                 // Parse key
                 let key = self.parse_object_key()?;
+                self.skip_newlines()?;
 
                 // Expect colon
                 if !matches!(self.peek(), Some(Token::Colon)) {
@@ -247,6 +538,7 @@ impl<'a> IterativeParser<'a> {
                     });
                 }
                 self.advance()?;
+                self.skip_newlines()?;
 
                 // Update context to expecting value
                 self.parse_stack.pop();
@@ -254,6 +546,7 @@ impl<'a> IterativeParser<'a> {
                     object,
                     current_key: Some(key),
                     expecting_key: false,
+                    member_start,
                 });
 
                 // Push value parsing context
@@ -268,7 +561,15 @@ impl<'a> IterativeParser<'a> {
                     // Check for continuation
                     match self.peek() {
                         Some(Token::Comma) => {
-                            self.advance()?;
+                            if let Err(error) = self.advance().and_then(|()| self.skip_newlines()) {
+                                self.restore(ParseContext::Object {
+                                    object,
+                                    current_key: None,
+                                    expecting_key: true,
+                                    member_start: self.lexer.span().start,
+                                });
+                                return Err(error);
+                            }
 
                             // Handle trailing comma
                             if let Some(Token::RightBrace) = self.peek() {
@@ -279,6 +580,12 @@ impl<'a> IterativeParser<'a> {
                                     self.push_value(value)?;
                                     return Ok(());
                                 } else {
+                                    self.restore(ParseContext::Object {
+                                        object,
+                                        current_key: None,
+                                        expecting_key: true,
+                                        member_start: self.current_span().start,
+                                    });
                                     return Err(Error::Custom(
                                         "Trailing comma not allowed".to_string(),
                                     ));
@@ -291,6 +598,7 @@ impl<'a> IterativeParser<'a> {
                                 object,
                                 current_key: None,
                                 expecting_key: true,
+                                member_start,
                             });
                         }
                         Some(Token::RightBrace) => {
@@ -300,7 +608,15 @@ impl<'a> IterativeParser<'a> {
                             self.push_value(value)?;
                         }
                         Some(Token::Newline) if self.options.newline_as_comma => {
-                            self.advance()?;
+                            if let Err(error) = self.advance().and_then(|()| self.skip_newlines()) {
+                                self.restore(ParseContext::Object {
+                                    object,
+                                    current_key: None,
+                                    expecting_key: true,
+                                    member_start: self.lexer.span().start,
+                                });
+                                return Err(error);
+                            }
 
                             // Handle trailing newline
                             if let Some(Token::RightBrace) = self.peek() {
@@ -317,14 +633,22 @@ impl<'a> IterativeParser<'a> {
                                 object,
                                 current_key: None,
                                 expecting_key: true,
+                                member_start,
                             });
                         }
                         Some(token) => {
-                            return Err(Error::Expected {
+                            let error = Error::Expected {
                                 expected: "comma or closing brace".to_string(),
                                 found: format!("{token:?}"),
                                 position: self.current_span().start,
+                            };
+                            self.restore(ParseContext::Object {
+                                object,
+                                current_key: None,
+                                expecting_key: true,
+                                member_start: self.current_span().start,
                             });
+                            return Err(error);
                         }
                         None => {
                             return Err(Error::Expected {
@@ -352,7 +676,11 @@ impl<'a> IterativeParser<'a> {
 
         let context = self.parse_stack.last().unwrap().clone();
 
-        if let ParseContext::Array { mut array } = context {
+        if let ParseContext::Array {
+            mut array,
+            member_start,
+        } = context
+        {
             // Check if this is the first step (no value parsed yet)
             if self.result.is_none() {
                 // Handle empty array
@@ -365,7 +693,10 @@ impl<'a> IterativeParser<'a> {
                 }
                 // Parse first element
                 self.parse_stack.pop();
-                self.parse_stack.push(ParseContext::Array { array });
+                self.parse_stack.push(ParseContext::Array {
+                    array,
+                    member_start,
+                });
                 self.parse_stack.push(ParseContext::Value);
                 return Ok(());
             }
@@ -377,7 +708,13 @@ impl<'a> IterativeParser<'a> {
                 // Check for continuation
                 match self.peek() {
                     Some(Token::Comma) => {
-                        self.advance()?;
+                        if let Err(error) = self.advance().and_then(|()| self.skip_newlines()) {
+                            self.restore(ParseContext::Array {
+                                array,
+                                member_start: self.lexer.span().start,
+                            });
+                            return Err(error);
+                        }
 
                         // Handle trailing comma
                         if let Some(Token::RightBracket) = self.peek() {
@@ -388,6 +725,10 @@ impl<'a> IterativeParser<'a> {
                                 self.push_value(value)?;
                                 return Ok(());
                             } else {
+                                self.restore(ParseContext::Array {
+                                    array,
+                                    member_start: self.current_span().start,
+                                });
                                 return Err(Error::Custom(
                                     "Trailing comma not allowed".to_string(),
                                 ));
@@ -396,7 +737,10 @@ impl<'a> IterativeParser<'a> {
 
                         // Continue parsing next element
                         self.parse_stack.pop();
-                        self.parse_stack.push(ParseContext::Array { array });
+                        self.parse_stack.push(ParseContext::Array {
+                            array,
+                            member_start: self.current_span().start,
+                        });
                         self.parse_stack.push(ParseContext::Value);
                     }
                     Some(Token::RightBracket) => {
@@ -406,7 +750,13 @@ impl<'a> IterativeParser<'a> {
                         self.push_value(value)?;
                     }
                     Some(Token::Newline) if self.options.newline_as_comma => {
-                        self.advance()?;
+                        if let Err(error) = self.advance().and_then(|()| self.skip_newlines()) {
+                            self.restore(ParseContext::Array {
+                                array,
+                                member_start: self.lexer.span().start,
+                            });
+                            return Err(error);
+                        }
 
                         // Handle trailing newline
                         if let Some(Token::RightBracket) = self.peek() {
@@ -419,15 +769,23 @@ impl<'a> IterativeParser<'a> {
 
                         // Continue parsing next element
                         self.parse_stack.pop();
-                        self.parse_stack.push(ParseContext::Array { array });
+                        self.parse_stack.push(ParseContext::Array {
+                            array,
+                            member_start: self.current_span().start,
+                        });
                         self.parse_stack.push(ParseContext::Value);
                     }
                     Some(token) => {
-                        return Err(Error::Expected {
+                        let error = Error::Expected {
                             expected: "comma or closing bracket".to_string(),
                             found: format!("{token:?}"),
                             position: self.current_span().start,
+                        };
+                        self.restore(ParseContext::Array {
+                            array,
+                            member_start: self.current_span().start,
                         });
+                        return Err(error);
                     }
                     None => {
                         return Err(Error::Expected {
@@ -577,20 +935,9 @@ impl<'a> IterativeParser<'a> {
     fn parse_number(&mut self) -> Result<Value> {
         if let Some((Token::Number, span)) = self.current_token {
             let text = self.lexer.span_text(&span);
-
-            // Try to parse as integer first
-            if let Ok(int_val) = text.parse::<i64>() {
-                self.advance()?;
-                return Ok(Value::Number(Number::Integer(int_val)));
-            }
-
-            // Try to parse as float
-            if let Ok(float_val) = text.parse::<f64>() {
-                self.advance()?;
-                return Ok(Value::Number(Number::Float(float_val)));
-            }
-
-            Err(Error::InvalidNumber(span.start))
+            let number = read_number(text, span.start, &self.options)?;
+            self.advance()?;
+            Ok(Value::Number(number))
         } else {
             Err(Error::Expected {
                 expected: "number".to_string(),
@@ -608,6 +955,12 @@ impl<'a> IterativeParser<'a> {
 
 }
 
+impl JsonParser for IterativeParser<'_> {
+    fn parse_with(&mut self, input: &str, options: &ParserOptions) -> Result<Value> {
+        IterativeParser::new(input, options.clone()).parse()
+    }
+}
+
 /// Parses JSON using the stack-based iterative parser.
 ///
 /// This function provides a stack-based iterative parser that can handle
@@ -633,9 +986,36 @@ pub fn parse_iterative(input: &str, options: ParserOptions) -> Result<Value> {
     parser.parse()
 }
 
+/// Parses as much of the input as possible, returning the best-effort value
+/// together with every error encountered.
+///
+/// Unlike repair, the input text is never changed: members that cannot be
+/// parsed are reported with their span and replaced by [`Value::Null`], and
+/// parsing resumes at the next comma or closing bracket. This never fails,
+/// which makes it suitable for editor tooling working on broken documents.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::parser::{parse_partial, ParserOptions};
+///
+/// let input = r#"{"a": 1, "b": :, "c": 3}"#;
+/// let (value, errors) = parse_partial(input, ParserOptions::default());
+///
+/// assert_eq!(value["a"].as_i64(), Some(1));
+/// assert_eq!(value["c"].as_i64(), Some(3));
+/// assert_eq!(errors.len(), 1);
+/// let span = errors[0].span().unwrap();
+/// assert_eq!(&input[span.start..span.end], r#""b": :"#);
+/// ```
+pub fn parse_partial(input: &str, options: ParserOptions) -> (Value, Vec<Error>) {
+    IterativeParser::new(input, options).parse_partial()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ast::Number;
 
     #[test]
     fn test_parse_null() {
@@ -771,4 +1151,109 @@ mod tests {
 
         assert_eq!(result, Value::Object(expected));
     }
+
+    #[test]
+    fn test_parse_partial_keeps_good_members() {
+        let members: Vec<String> = (0..10)
+            .map(|i| {
+                if i == 4 {
+                    r#""k4": :"#.to_string()
+                } else {
+                    format!(r#""k{i}": {i}"#)
+                }
+            })
+            .collect();
+        let json = format!("{{{}}}", members.join(", "));
+
+        let (value, errors) = parse_partial(&json, ParserOptions::default());
+        let object = value.as_object().unwrap();
+        for i in (0..10).filter(|&i| i != 4) {
+            assert_eq!(object[&format!("k{i}")].as_i64(), Some(i));
+        }
+        assert_eq!(object["k4"], Value::Null);
+
+        assert_eq!(errors.len(), 1);
+        let span = errors[0].span().unwrap();
+        assert_eq!(&json[span.start..span.end], r#""k4": :"#);
+    }
+
+    #[test]
+    fn test_parse_partial_arrays_and_unclosed_input() {
+        let (value, errors) = parse_partial("[1, , 3]", ParserOptions::default());
+        assert_eq!(
+            value,
+            parse_iterative("[1, null, 3]", ParserOptions::default()).unwrap()
+        );
+        assert_eq!(errors.len(), 1);
+
+        let (value, errors) = parse_partial("[1, @, 3]", ParserOptions::default());
+        assert_eq!(
+            value,
+            parse_iterative("[1, null, 3]", ParserOptions::default()).unwrap()
+        );
+        assert_eq!(errors[0].span(), Some(Span::new(4, 5)));
+
+        let (value, errors) = parse_partial(r#"{"a": [1, 2"#, ParserOptions::default());
+        assert_eq!(
+            value,
+            parse_iterative(r#"{"a": [1, 2]}"#, ParserOptions::default()).unwrap()
+        );
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_partial_valid_input() {
+        let json = r#"{"a": [1, {"b": null}], "c": "d"}"#;
+        let (value, errors) = parse_partial(json, ParserOptions::default());
+        assert!(errors.is_empty());
+        assert_eq!(
+            value,
+            parse_iterative(json, ParserOptions::default()).unwrap()
+        );
+
+        // Strict parsing still fails on the first error
+        assert!(parse_iterative(r#"{"a": :, "b": 2}"#, ParserOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_parse_multiline_documents() {
+        let mut separated = ParserOptions::default();
+        separated.newline_as_comma = true;
+        let mut plain = ParserOptions::default();
+        plain.newline_as_comma = false;
+
+        let json = "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\":\n    null\n}\n";
+        let expected = parse_iterative(r#"{"a": [1, 2], "b": null}"#, plain.clone()).unwrap();
+        for options in [&separated, &plain] {
+            assert_eq!(parse_iterative(json, options.clone()).unwrap(), expected);
+            let (value, errors) = parse_partial(json, options.clone());
+            assert!(errors.is_empty(), "{errors:?}");
+            assert_eq!(value, expected);
+        }
+
+        // Only with newline_as_comma does a line break separate values
+        let json = "[1\n2]";
+        assert_eq!(
+            parse_iterative(json, separated.clone()).unwrap(),
+            parse_iterative("[1, 2]", plain.clone()).unwrap()
+        );
+        assert!(parse_iterative(json, plain.clone()).is_err());
+    }
+
+    #[test]
+    fn test_parse_partial_multiline_error() {
+        let json = "{\n\"a\":1,\n\"b\": [1,,2],\n\"c\":3}";
+        let expected = parse_iterative(
+            r#"{"a": 1, "b": [1, null, 2], "c": 3}"#,
+            ParserOptions::default(),
+        )
+        .unwrap();
+        for newline_as_comma in [true, false] {
+            let mut options = ParserOptions::default();
+            options.newline_as_comma = newline_as_comma;
+            let (value, errors) = parse_partial(json, options);
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            assert_eq!(value, expected);
+        }
+    }
 }
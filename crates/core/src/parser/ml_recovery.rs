@@ -0,0 +1,118 @@
+// this_file: crates/core/src/parser/ml_recovery.rs
+
+//! A fourth parsing tier, after repair, that tries the fixes of the
+//! patterns [`MLPatternRecognizer`] has learned.
+
+use super::{check_repair_budget, parse_with_fallback, ParserOptions};
+use crate::ast::Value;
+use crate::error::repair::{original_position, EnhancedParseResult, ParsingTier, RepairAction};
+use crate::error::{ErrorContext, MLPatternRecognizer, PatternMatch};
+
+/// Parses `input` like [`parse_with_fallback`] and, if that fails, tries the
+/// fix of each learned pattern that matches the error, best first.
+///
+/// Returns the result with the pattern whose fix was kept, if any. The fix
+/// is listed among the repairs at the [`ParsingTier::Repair`] tier, with
+/// every position referring to `input`. Nothing beyond
+/// [`parse_with_fallback`] is tried unless
+/// [`ParserOptions::enable_repair`] is set.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::parser::{parse_with_ml_recovery, ParserOptions};
+///
+/// let (result, pattern) = parse_with_ml_recovery(r#"{"a": 'b"}"#, ParserOptions::default());
+/// assert!(result.is_success());
+/// assert_eq!(pattern.unwrap().pattern, "unmatched_quote");
+/// assert_eq!(result.repairs[0].replacement, "\"");
+/// ```
+pub fn parse_with_ml_recovery(
+    input: &str,
+    options: ParserOptions,
+) -> (EnhancedParseResult<Value>, Option<PatternMatch>) {
+    let result = parse_with_fallback(input, options.clone());
+    let error = match result.errors.first() {
+        Some(error) if options.enable_repair => error.clone(),
+        _ => return (result, None),
+    };
+
+    let mut position = error.position().unwrap_or(input.len()).min(input.len());
+    while !input.is_char_boundary(position) {
+        position -= 1;
+    }
+    let context = ErrorContext {
+        error: error.clone(),
+        input: input.to_string(),
+        position,
+        tokens_before: Vec::new(),
+        partial_ast: None,
+        parsing_context: container_at(input, position).to_string(),
+    };
+    for matched in MLPatternRecognizer::new().matches(&context) {
+        let fixed = matched.suggestion.fixed_input.as_str();
+        let inner = parse_with_fallback(fixed, options.clone());
+        if !inner.is_success() {
+            continue;
+        }
+
+        let fix = RepairAction::between(
+            input,
+            fixed,
+            matched.suggestion.category.clone().into(),
+            matched.suggestion.description.clone(),
+            error.clone(),
+        );
+        let mut repairs: Vec<RepairAction> = inner
+            .repairs
+            .into_iter()
+            .map(|mut repair| {
+                repair.position = original_position(&[&fix], repair.position);
+                repair.cause = repair.cause.map(|error| {
+                    error.map_positions(&|position| original_position(&[&fix], position))
+                });
+                repair
+            })
+            .collect();
+        repairs.push(fix);
+        repairs.sort_by_key(|repair| repair.position);
+        let recovered = check_repair_budget(
+            EnhancedParseResult::success_with_repairs(inner.value, repairs, ParsingTier::Repair),
+            &options,
+        );
+        if recovered.is_success() {
+            return (recovered, Some(matched));
+        }
+    }
+    (result, None)
+}
+
+/// The innermost container open at `position`, as the recognizer's
+/// contextual features expect it.
+fn container_at(input: &str, position: usize) -> &'static str {
+    let mut open = Vec::new();
+    let mut quote = None;
+    let mut escaped = false;
+    for c in input[..position].chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '{' | '[' => open.push(c),
+                '}' | ']' => {
+                    open.pop();
+                }
+                _ => {}
+            },
+        }
+    }
+    match (quote, open.last()) {
+        (Some(_), _) => "in_string",
+        (None, Some('{')) => "in_object",
+        (None, Some(_)) => "in_array",
+        (None, None) => "top_level",
+    }
+}
@@ -2,10 +2,19 @@
 
 /// Array parsing functionality.
 pub mod array;
+mod backend;
 /// Boolean value parsing.
 pub mod boolean;
+mod budget;
+mod bytes;
+mod concat;
+mod elements;
+mod file;
+mod into;
 /// Stack-based iterative parser implementation.
 pub mod iterative;
+#[cfg(feature = "ml-recovery")]
+mod ml_recovery;
 /// Null value parsing.
 pub mod null;
 /// Number parsing with integer and float support.
@@ -15,24 +24,52 @@ pub mod object;
 pub mod optimized;
 pub mod optimized_v2;
 pub mod optimized_v3;
+mod pairs;
+mod path;
+mod pool;
 /// Clean recursive descent parser implementation.
 pub mod recursive;
 /// Parser state management.
 pub mod state;
 /// String parsing with escape sequence handling.
 pub mod string;
+mod warnings;
 
 use self::boolean::{parse_false, parse_true};
+use self::budget::BudgetTracker;
 use self::null::parse_null;
-use self::number::parse_number_token;
+use self::number::{check_range, exact_number, exponent_number, overflowed, parse_number_token};
+use self::path::PathEntry;
 use self::string::parse_string_token;
 use crate::ast::{Number, Token, Value};
-use crate::error::repair::{EnhancedParseResult, ParsingTier, RepairAction};
-use crate::error::{Error, ErrorContext, ErrorRecoveryEngineV2, Result, Span};
+use crate::error::recovery_v2::RecoveryConfig;
+use crate::error::repair::{
+    apply_repairs, original_position, EnhancedParseResult, ParsingTier, RepairAction,
+};
+use crate::error::{
+    CompatWarning, Error, ErrorContext, ErrorRecoveryEngineV2, Result, Span, Warning,
+};
 use crate::lexer::{FastLexer, JsonLexer, Lexer, LexerConfig, LexerMode};
 use crate::optimization::ValueBuilder;
-use crate::repair::JsonRepairer;
-pub use iterative::{parse_iterative, IterativeParser};
+use crate::repair::punctuation::punctuation_repairs;
+use crate::repair::{JsonRepairer, SharedRepairCache};
+pub use backend::{JsonParser, ParserBackend};
+pub(crate) use budget::is_cancelled;
+pub use budget::{parse_with_budget, ParseBudget};
+pub use bytes::{parse_bytes, parse_bytes_with_options, parse_bytes_with_warnings};
+pub(crate) use concat::{document_ranges, is_blank, nesting_depth};
+pub use concat::{
+    is_concatenated, is_concatenated_with_options, parse_concatenated,
+    parse_concatenated_with_options,
+};
+pub use elements::{parse_array_iter, ArrayElements};
+pub use file::{parse_file, parse_file_with_options};
+pub use into::{parse_into, parse_into_with_options};
+pub use pool::{ParserPool, ParserPoolStats};
+pub use iterative::{parse_iterative, parse_partial, IterativeParser};
+#[cfg(feature = "ml-recovery")]
+pub use ml_recovery::parse_with_ml_recovery;
+use object::Members;
 pub use optimized::{
     parse_optimized, parse_optimized_with_options, parse_with_stats, OptimizedParser,
 };
@@ -42,9 +79,14 @@ pub use optimized_v2::{
 pub use optimized_v3::{
     parse_optimized_v3, parse_optimized_v3_with_options, parse_v3_with_stats, OptimizedParserV3,
 };
+pub use pairs::parse_to_pairs;
+use pairs::TopLevelPairs;
 pub use recursive::{parse_recursive, RecursiveDescentParser};
 use rustc_hash::FxHashMap;
 pub use state::ParserState;
+use std::collections::hash_map::Entry;
+use std::collections::VecDeque;
+use std::sync::Arc;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -59,6 +101,18 @@ use serde::{Deserialize, Serialize};
 pub struct ParserOptions {
     /// Whether to allow single-line and multi-line comments.
     pub allow_comments: bool,
+    /// Whether `/*` inside a block comment opens a nested comment, so that
+    /// `/* a /* b */ c */` is a single comment.
+    ///
+    /// JSON5 and JavaScript comments do not nest; disable this to match
+    /// them, so that the first `*/` ends the comment.
+    pub allow_nested_block_comments: bool,
+    /// Maximum length in bytes of a comment, delimiters included, or `None`
+    /// for no limit. Longer comments fail with [`Error::LimitExceeded`].
+    pub max_comment_length: Option<usize>,
+    /// Which comment syntaxes [`allow_comments`](Self::allow_comments)
+    /// turns on.
+    pub comment_styles: CommentStyles,
     /// Whether to allow trailing commas in arrays and objects.
     pub allow_trailing_commas: bool,
     /// Whether to allow unquoted object keys (e.g., {key: "value"}).
@@ -67,6 +121,12 @@ pub struct ParserOptions {
     pub allow_single_quotes: bool,
     /// Whether to allow implicit top-level objects and arrays.
     /// When enabled, `key: value` becomes `{key: value}` and `1, 2, 3` becomes `[1, 2, 3]`.
+    ///
+    /// The first top-level token decides: a key followed by `:` starts an
+    /// implicit object, and every later entry must then be `key: value`.
+    /// Anything else starts a single value or an implicit array, in which a
+    /// later `key:` is an error. Indentation is not significant, so indented
+    /// lines are further members rather than a nested block.
     pub implicit_top_level: bool,
     /// Whether to treat newlines as commas in arrays and objects.
     pub newline_as_comma: bool,
@@ -80,12 +140,335 @@ pub struct ParserOptions {
     pub fast_repair: bool,
     /// Report all repairs made.
     pub report_repairs: bool,
+    /// Pre-size arrays and objects from a cheap scan of their top-level commas.
+    ///
+    /// This avoids repeated reallocation when filling large containers at
+    /// the cost of an extra pass over the first 64 KiB of a container's
+    /// source text, which also sizes the containers nested in it. Inputs
+    /// shorter than 4 KiB are not scanned.
+    pub preallocate: bool,
+    /// Maximum number of trailing bytes repair may discard after the end of
+    /// the document before the parse is treated as a failure.
+    ///
+    /// `None` accepts any amount; the count is always reported in
+    /// [`EnhancedParseResult::discarded_trailing_bytes`].
+    pub max_trailing_discard: Option<usize>,
+    /// What to return for input with no value in it: nothing but whitespace,
+    /// newlines and comments.
+    pub empty_input_behavior: EmptyInputBehavior,
+    /// Whether whitespace alone may separate top-level values, so that
+    /// `1 2 3` parses as `[1, 2, 3]`.
+    ///
+    /// Only applies when [`implicit_top_level`](Self::implicit_top_level) is
+    /// also enabled. When disabled, a second value after the first is an
+    /// "expected end of input" error.
+    pub space_separated_values: bool,
+    /// How far an unquoted string value reaches: `{key: hello world}` is
+    /// the word `hello` followed by a stray `world` under
+    /// [`SingleToken`](UnquotedValueMode::SingleToken), and the string
+    /// `"hello world"` under [`RestOfLine`](UnquotedValueMode::RestOfLine).
+    pub unquoted_value_mode: UnquotedValueMode,
+    /// Whether to record where in the document each error occurred.
+    ///
+    /// Errors are then wrapped in [`Error::AtPath`], whose message ends with
+    /// e.g. `at path $.items[3].name`. Keys are only decoded when an error is
+    /// reported, so the cost while parsing is a push and pop per value.
+    pub track_paths: bool,
+    /// Cache for repair results, shared across parses.
+    ///
+    /// Each repairing parse otherwise starts with an empty cache of its own,
+    /// so set this to reuse repairs of inputs seen before. Ignored when
+    /// [`fast_repair`](Self::fast_repair) is enabled.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub repair_cache: Option<SharedRepairCache>,
+    /// Whether to treat non-ASCII spaces as whitespace: no-break space
+    /// (U+00A0), the typographic spaces U+2000 to U+200A, the line and
+    /// paragraph separators U+2028 and U+2029, and byte order marks (U+FEFF)
+    /// anywhere in the input.
+    ///
+    /// A byte order mark at the very start of the input is skipped
+    /// regardless of this option.
+    pub allow_unicode_whitespace: bool,
+    /// Whether to record forgiving transformations that change the shape
+    /// of the document, such as a line break splitting two values or
+    /// top-level members becoming an implicit object.
+    ///
+    /// Read them with [`Parser::warnings`], or from
+    /// [`EnhancedParseResult::warnings`] when [`parse_with_fallback`] uses
+    /// its forgiving tier. Input accepted by the strict tier needs no
+    /// forgiving transformations, and repaired input reports its repairs
+    /// instead.
+    pub collect_warnings: bool,
+    /// Whether to record every construct outside strict JSON that the
+    /// parse accepts, such as comments, trailing commas and unquoted keys,
+    /// together with the strict text that would replace it.
+    ///
+    /// Read them with [`Parser::compat_warnings`], or from
+    /// [`EnhancedParseResult::compat_warnings`] when [`parse_with_fallback`]
+    /// uses its forgiving tier.
+    pub collect_compat_warnings: bool,
+    /// What [`parse_bytes`] and its variants do with bytes that are not
+    /// valid UTF-8.
+    pub invalid_utf8_policy: InvalidUtf8Policy,
+    /// What to do when an object has the same key more than once.
+    pub duplicate_keys: DuplicateKeyPolicy,
+    /// Bounds on the size of arrays, objects, keys and strings, none by
+    /// default.
+    pub limits: Limits,
+    /// Whether an object key that is empty or only whitespace, such as `""`
+    /// or `"  "`, fails with [`Error::BlankKey`].
+    ///
+    /// Such keys are valid JSON but rarely intended; they often mean the
+    /// input was truncated or corrupted on its way in.
+    pub reject_blank_keys: bool,
+    /// Whether every number must keep the exact value it was written with.
+    ///
+    /// Decimal integers that fit in an `i64` are always read exactly; with
+    /// this option a number that would be rounded through an `f64`, such as
+    /// `12345678901234567890` or `0.10000000000000000001`, is an error
+    /// instead.
+    pub preserve_number_precision: bool,
+    /// What a number beyond the range of `f64`, such as `1e400` or an
+    /// integer of hundreds of digits, reads as.
+    ///
+    /// Decimal integers beyond the range of `i64` are read as the nearest
+    /// `f64`, so only those beyond the range of `f64` overflow. To keep every
+    /// digit of such numbers, read them through [`StreamingParser`], whose
+    /// [`StreamingEvent::Number`] events hold the text as written.
+    ///
+    /// [`StreamingParser`]: crate::StreamingParser
+    /// [`StreamingEvent::Number`]: crate::StreamingEvent::Number
+    pub number_overflow: NumberOverflowPolicy,
+    /// Whether a whole number written with an exponent, such as `1e2`,
+    /// `1E2` or `1.5e2`, stays a [`Number::Float`] as its syntax suggests.
+    ///
+    /// Enabled by default. When disabled, such numbers become a
+    /// [`Number::Integer`] if they fit in an `i64`, so `1e2` reads as `100`
+    /// rather than `100.0`. Numbers written with a decimal point and no
+    /// exponent, such as `100.0`, are floats either way.
+    pub exponent_as_float: bool,
+    /// Whether the repair tier of [`parse_with_fallback`] undoes the
+    /// punctuation word processors substitute into pasted text: curly quotes
+    /// become straight, an en or em dash before a digit becomes a minus sign
+    /// and a no-break space becomes a space, unless
+    /// [`allow_unicode_whitespace`](Self::allow_unicode_whitespace) already
+    /// reads it as one.
+    ///
+    /// Each replacement is reported as a repair, made once parsing fails at
+    /// or after it. String content written between straight quotes is never
+    /// changed.
+    pub normalize_unicode_punctuation: bool,
+    /// Which parser implementation [`parse_with_options`] and
+    /// [`parse_with_fallback`] use.
+    pub backend: ParserBackend,
+}
+
+/// How the parser treats an object key that appears more than once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DuplicateKeyPolicy {
+    /// Keep the value of the last occurrence, as `JSON.parse` does.
+    #[default]
+    LastWins,
+    /// Keep the value of the first occurrence and ignore later ones.
+    FirstWins,
+    /// Fail with [`Error::DuplicateKey`] at the second occurrence.
+    Error,
+    /// Keep every value, gathering those of a repeated key into an array in
+    /// document order: `{"a": 1, "a": 2}` reads as `{"a": [1, 2]}`.
+    ///
+    /// The result alone does not tell a gathered array from a key that was
+    /// written once with an array value, so writing it back out gives
+    /// `{"a": [1, 2]}` rather than the repeated keys. Use [`parse_to_pairs`]
+    /// where the difference matters.
+    CollectIntoArray,
+}
+
+/// How [`parse_bytes`] treats bytes that are not valid UTF-8.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InvalidUtf8Policy {
+    /// Fail with [`Error::InvalidUtf8`] at the first invalid sequence.
+    #[default]
+    Error,
+    /// Replace each invalid sequence with U+FFFD before parsing, recording
+    /// where in a [`WarningKind::InvalidUtf8`](crate::WarningKind::InvalidUtf8)
+    /// warning.
+    Replace,
+}
+
+/// Bounds on the size of individual values, for reading input from sources
+/// that are not trusted. Each is `None` for no limit.
+///
+/// A value past a limit fails with [`Error::LimitExceeded`], whose `what`
+/// names the limit, wrapped in [`Error::AtPath`] to say where in the
+/// document it was found: `array length exceeds the limit of 2 at position
+/// 7 at path $.items[2]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Limits {
+    /// Maximum number of elements in one array, counting those left empty
+    /// between commas. The error is at the first element past the limit.
+    pub max_array_len: Option<usize>,
+    /// Maximum number of distinct keys in one object; repeated keys count
+    /// once. The error is at the first key past the limit.
+    pub max_object_members: Option<usize>,
+    /// Maximum length in bytes of an object key after unescaping.
+    pub max_key_len: Option<usize>,
+    /// Maximum length in bytes of a string value after unescaping. Object
+    /// keys are not strings in this sense; see
+    /// [`max_key_len`](Self::max_key_len).
+    pub max_string_len: Option<usize>,
+}
+
+impl Limits {
+    /// Limits that ordinary documents stay well within, for input from
+    /// untrusted sources: a million elements in an array, ten thousand
+    /// members in an object, keys of 1 KiB and strings of 1 MiB.
+    pub fn hardened() -> Self {
+        Limits {
+            max_array_len: Some(1_000_000),
+            max_object_members: Some(10_000),
+            max_key_len: Some(1024),
+            max_string_len: Some(1 << 20),
+        }
+    }
+
+    /// Whether any limit is set.
+    pub fn is_limited(&self) -> bool {
+        *self != Limits::default()
+    }
+}
+
+/// How the parser reads a number beyond the range of `f64`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NumberOverflowPolicy {
+    /// Fail under [strict](ParserOptions::is_strict) options, and otherwise
+    /// read the number as infinity.
+    #[default]
+    Auto,
+    /// Read the number as infinity with its sign, as `JSON.parse` does.
+    Infinity,
+    /// Fail with [`Error::Expected`] at the number.
+    Error,
+}
+
+impl NumberOverflowPolicy {
+    /// Whether a number that overflows fails under `options`.
+    pub(crate) fn fails(self, options: &ParserOptions) -> bool {
+        match self {
+            NumberOverflowPolicy::Auto => options.is_strict(),
+            NumberOverflowPolicy::Infinity => false,
+            NumberOverflowPolicy::Error => true,
+        }
+    }
+}
+
+/// How the parser treats input that contains no value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EmptyInputBehavior {
+    /// Return `null`.
+    #[default]
+    Null,
+    /// Fail with [`Error::UnexpectedEof`].
+    Error,
+    /// Return an empty object.
+    EmptyObject,
+    /// Return an empty array.
+    EmptyArray,
+}
+
+impl EmptyInputBehavior {
+    /// The result of parsing `input_len` bytes that hold no value.
+    pub(crate) fn value(self, input_len: usize) -> Result<Value> {
+        match self {
+            EmptyInputBehavior::Null => Ok(Value::Null),
+            EmptyInputBehavior::Error => Err(Error::UnexpectedEof(input_len)),
+            EmptyInputBehavior::EmptyObject => Ok(Value::Object(FxHashMap::default())),
+            EmptyInputBehavior::EmptyArray => Ok(Value::Array(Vec::new())),
+        }
+    }
+}
+
+/// How far an unquoted string value reaches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UnquotedValueMode {
+    /// The value is a single word; whatever follows it must be a separator
+    /// or the end of its container.
+    #[default]
+    SingleToken,
+    /// The value runs on over the words, numbers and keywords after it on
+    /// the same line, up to the next comma, bracket, brace, comment or line
+    /// break, keeping the spaces between them. Quoted strings are not taken
+    /// in.
+    RestOfLine,
+}
+
+impl UnquotedValueMode {
+    /// Whether an unquoted value continues over `token`, which follows it
+    /// after the text `gap`.
+    pub(crate) fn continues(self, token: Token, gap: &str) -> bool {
+        self == UnquotedValueMode::RestOfLine
+            && matches!(
+                token,
+                Token::UnquotedString | Token::Number | Token::True | Token::False | Token::Null
+            )
+            && gap.chars().all(|c| c.is_whitespace() && c != '\n' && c != '\r')
+    }
+}
+
+/// The comment syntaxes to recognize, all of them by default.
+///
+/// A `#` is an ordinary character of unquoted keys and values when
+/// [`hash`](Self::hash) is off, so that `{color: #fff}` holds the string
+/// `"#fff"`. Comment markers inside quoted strings never start a comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CommentStyles {
+    /// `// ...` to the end of the line
+    pub line_slash: bool,
+    /// `/* ... */`
+    pub block: bool,
+    /// `# ...` to the end of the line, as in shell scripts and YAML
+    pub hash: bool,
+}
+
+impl Default for CommentStyles {
+    fn default() -> Self {
+        CommentStyles {
+            line_slash: true,
+            block: true,
+            hash: true,
+        }
+    }
+}
+
+impl CommentStyles {
+    /// Whether `comment`, delimiters included, is written in one of the
+    /// enabled styles.
+    pub(crate) fn allows(&self, comment: &str) -> bool {
+        if comment.starts_with('#') {
+            self.hash
+        } else if comment.starts_with("/*") {
+            self.block
+        } else {
+            self.line_slash
+        }
+    }
 }
 
 impl Default for ParserOptions {
     fn default() -> Self {
         ParserOptions {
             allow_comments: true,
+            allow_nested_block_comments: true,
+            max_comment_length: None,
+            comment_styles: CommentStyles::default(),
             allow_trailing_commas: true,
             allow_unquoted_keys: true,
             allow_single_quotes: true,
@@ -96,10 +479,60 @@ impl Default for ParserOptions {
             max_repairs: 100,
             fast_repair: false,
             report_repairs: true,
+            preallocate: false,
+            max_trailing_discard: None,
+            empty_input_behavior: EmptyInputBehavior::Null,
+            space_separated_values: false,
+            unquoted_value_mode: UnquotedValueMode::SingleToken,
+            track_paths: false,
+            repair_cache: None,
+            allow_unicode_whitespace: true,
+            collect_warnings: false,
+            collect_compat_warnings: false,
+            invalid_utf8_policy: InvalidUtf8Policy::Error,
+            duplicate_keys: DuplicateKeyPolicy::LastWins,
+            limits: Limits::default(),
+            reject_blank_keys: false,
+            preserve_number_precision: false,
+            number_overflow: NumberOverflowPolicy::Auto,
+            exponent_as_float: true,
+            normalize_unicode_punctuation: true,
+            backend: ParserBackend::Auto,
         }
     }
 }
 
+impl ParserOptions {
+    /// Whether these options accept nothing outside strict JSON syntax:
+    /// no comments, trailing commas, unquoted keys, single quotes, implicit
+    /// top-level values or newlines as commas.
+    ///
+    /// Strict options also reject a number too large for an `f64`, which
+    /// forgiving options read as infinity with a
+    /// [`WarningKind::NumberOverflow`](crate::WarningKind::NumberOverflow)
+    /// warning.
+    pub fn is_strict(&self) -> bool {
+        !(self.allow_comments
+            || self.allow_trailing_commas
+            || self.allow_unquoted_keys
+            || self.allow_single_quotes
+            || self.implicit_top_level
+            || self.newline_as_comma)
+    }
+
+    /// Whether [`parse_with_fallback`] may hand strict JSON to serde_json
+    /// first. serde_json knows none of the options that change what valid
+    /// JSON reads as, so it is only used while they all keep their defaults.
+    pub(crate) fn fast_tier_safe(&self) -> bool {
+        !self.limits.is_limited()
+            && self.duplicate_keys == DuplicateKeyPolicy::LastWins
+            && !self.reject_blank_keys
+            && !self.preserve_number_precision
+            && self.exponent_as_float
+            && self.max_depth == ParserOptions::default().max_depth
+    }
+}
+
 /// The vexy_json parser.
 ///
 /// Parses tokens from a Lexer into a Value tree structure.
@@ -116,63 +549,110 @@ pub struct Parser<'a> {
     /// Value builder for optimized object and array construction
     #[allow(dead_code)]
     pub(super) value_builder: ValueBuilder,
+    /// Keys and indices leading to the value being parsed, when
+    /// [`ParserOptions::track_paths`] is enabled.
+    pub(super) path: Vec<PathEntry<'a>>,
+    /// Forgiving transformations applied, when
+    /// [`ParserOptions::collect_warnings`] is enabled.
+    pub(super) warnings: Vec<Warning>,
+    /// Constructs outside strict JSON, when
+    /// [`ParserOptions::collect_compat_warnings`] is enabled.
+    pub(super) compat_warnings: Vec<CompatWarning>,
+    /// Limits set with [`with_budget`](Parser::with_budget).
+    pub(super) budget: Option<BudgetTracker>,
+    /// Members of the top-level object, when recording for
+    /// [`parse_to_pairs`].
+    pub(super) top_level_pairs: Option<TopLevelPairs>,
+    /// Element counts of nested containers found by the last
+    /// [`ParserOptions::preallocate`] scan, in source order.
+    pub(super) capacity_hints: VecDeque<(usize, usize)>,
+}
+
+/// Creates the lexer that [`Parser`] uses for `input` under `options`.
+///
+/// Any forgiving feature selects the hand-written [`FastLexer`]; fully
+/// strict options use the logos-based [`Lexer`].
+pub fn create_parser_lexer<'a>(input: &'a str, options: &ParserOptions) -> Box<dyn JsonLexer + 'a> {
+    // Determine if we need forgiving features
+    let needs_forgiving = !options.is_strict()
+        // Only the fast lexer skips Unicode spaces, which ASCII input lacks
+        || (options.allow_unicode_whitespace && !input.is_ascii());
+
+    if needs_forgiving {
+        // Use FastLexer with forgiving mode for non-strict parsing
+        let config = LexerConfig {
+            mode: if options.allow_comments {
+                LexerMode::Forgiving
+            } else {
+                LexerMode::Strict
+            },
+            collect_stats: false,
+            buffer_size: 8192,
+            max_depth: options.max_depth,
+            track_positions: true,
+            unicode_whitespace: options.allow_unicode_whitespace,
+            nested_comments: options.allow_nested_block_comments,
+            max_comment_length: options.max_comment_length,
+            comment_styles: options.comment_styles,
+        };
+        Box::new(FastLexer::new(input, config))
+    } else {
+        // Use LogosLexer for strict parsing
+        Box::new(Lexer::new(input))
+    }
 }
 
 impl<'a> Parser<'a> {
     /// Creates a new parser with the given input and options.
     pub fn new(input: &'a str, options: ParserOptions) -> Self {
-        // Determine if we need forgiving features
-        let needs_forgiving = options.allow_comments
-            || options.allow_trailing_commas
-            || options.allow_unquoted_keys
-            || options.allow_single_quotes
-            || options.implicit_top_level
-            || options.newline_as_comma;
-
-        // Create appropriate lexer based on options
-        let lexer: Box<dyn JsonLexer + 'a> = if needs_forgiving {
-            // Use FastLexer with forgiving mode for non-strict parsing
-            let config = LexerConfig {
-                mode: if options.allow_comments {
-                    LexerMode::Forgiving
-                } else {
-                    LexerMode::Strict
-                },
-                collect_stats: false,
-                buffer_size: 8192,
-                max_depth: options.max_depth,
-                track_positions: true,
-            };
-            Box::new(FastLexer::new(input, config))
-        } else {
-            // Use LogosLexer for strict parsing
-            Box::new(Lexer::new(input))
-        };
-
         Parser {
-            lexer,
+            lexer: create_parser_lexer(input, &options),
             original_input: input,
             options,
             current_token: None, // Will be populated by first advance()
             state: ParserState::new(),
             value_builder: ValueBuilder::new(),
+            path: Vec::new(),
+            warnings: Vec::new(),
+            compat_warnings: Vec::new(),
+            budget: None,
+            top_level_pairs: None,
+            capacity_hints: VecDeque::new(),
         }
     }
 
+    /// Limits the time and memory the parse may use, and lets another
+    /// thread cancel it.
+    pub fn with_budget(mut self, budget: ParseBudget) -> Self {
+        self.budget = Some(BudgetTracker::new(budget));
+        self
+    }
+
     /// Parses the input and returns a Value.
     ///
     /// This is the main entry point for parsing. It handles:
-    /// - Empty input (returns null)
+    /// - Empty input (see [`ParserOptions::empty_input_behavior`])
     /// - Single values
     /// - Implicit arrays (when multiple comma-separated values are found)
     /// - Implicit objects (when key:value pairs are found at top level)
     pub fn parse(&mut self) -> Result<Value> {
+        self.path.clear();
+        self.warnings.clear();
+        self.compat_warnings.clear();
+        if let Some(budget) = &self.budget {
+            budget.check(0)?;
+        }
+        self.parse_document()
+            .map_err(|error| self.annotate_error(error))
+    }
+
+    fn parse_document(&mut self) -> Result<Value> {
         self.advance()?;
         self.skip_comments()?;
 
         // Handle empty input - check if we have only whitespace/newlines
         if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
-            return Ok(Value::Null);
+            return self.empty_input_value();
         }
 
         // Check if input is only newlines and whitespace (effectively empty)
@@ -190,45 +670,48 @@ impl<'a> Parser<'a> {
         {
             self.skip_comments_and_newlines()?;
             if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
-                return Ok(Value::Null);
+                return self.empty_input_value();
             }
         }
 
+        let start = self.state.span.start;
+
         // Check if it starts with a separator (implicit array with null first element)
         if self.is_separator() && self.options.implicit_top_level {
+            self.check_array_len(0)?;
             let mut array = vec![Value::Null];
-            self.advance()?;
+            self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
 
             loop {
-                self.skip_comments_and_newlines()?;
+                self.lex_into(
+                    PathEntry::Index(array.len()),
+                    Self::skip_comments_and_newlines,
+                )?;
                 if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
                     break;
                 }
 
                 // Check for consecutive separators (which mean null values)
                 if self.is_separator() {
+                    self.check_array_len(array.len())?;
                     array.push(Value::Null);
-                    self.advance()?;
+                    self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
                     continue;
                 }
 
-                array.push(self.parse_value()?);
+                array.push(self.parse_value_at(PathEntry::Index(array.len()))?);
 
                 self.skip_comments_and_newlines()?;
                 if self.is_separator() {
-                    self.advance()?;
+                    self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
                 } else if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
                     break;
                 } else {
-                    return Err(Error::Expected {
-                        expected: ", or newline or end of input".to_string(),
-                        found: format!("{:?}", self.current_token.as_ref().map(|(t, _)| t)),
-                        position: self.lexer.position(),
-                    });
+                    return Err(self.top_level_separator_error(", or newline or end of input"));
                 }
             }
 
-            return Ok(Value::Array(array));
+            return Ok(self.implicit_array(start, array));
         }
 
         // Try to parse as a regular value first (with implicit object support if enabled)
@@ -260,44 +743,46 @@ impl<'a> Parser<'a> {
                 {
                     // Treat the explicit structure as the first element of an implicit array
                     let mut array = vec![first_value];
-                    self.advance()?;
+                    self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
 
                     loop {
-                        self.skip_comments_and_newlines()?;
+                        self.lex_into(
+                            PathEntry::Index(array.len()),
+                            Self::skip_comments_and_newlines,
+                        )?;
                         if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
                             break;
                         }
 
                         // Check for consecutive separators (which mean null values)
                         if self.is_separator() {
+                            self.check_array_len(array.len())?;
                             array.push(Value::Null);
-                            self.advance()?;
+                            self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
                             continue;
                         }
 
-                        array.push(self.parse_value()?);
+                        array.push(self.parse_value_at(PathEntry::Index(array.len()))?);
 
                         self.skip_comments_and_newlines()?;
                         if self.is_separator() {
-                            self.advance()?;
+                            self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
                         } else if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
                             break;
                         } else {
-                            return Err(Error::Expected {
-                                expected: ", or newline or end of input".to_string(),
-                                found: format!("{:?}", self.current_token.as_ref().map(|(t, _)| t)),
-                                position: self.lexer.position(),
-                            });
+                            return Err(
+                                self.top_level_separator_error(", or newline or end of input")
+                            );
                         }
                     }
 
-                    Ok(Value::Array(array))
+                    Ok(self.implicit_array(start, array))
                 } else {
                     // For explicit JSON structures (arrays/objects), require end of input
                     Err(Error::Expected {
                         expected: "end of input".to_string(),
                         found: format!("{:?}", self.current_token.as_ref().map(|(t, _)| t)),
-                        position: self.lexer.position(),
+                        position: self.token_position(),
                     })
                 }
             }
@@ -318,8 +803,7 @@ impl<'a> Parser<'a> {
                         Some(&Token::Newline)
                     )
                 {
-                    self.advance()?;
-                    self.skip_comments_and_newlines()?;
+                    self.lex_into(PathEntry::Index(1), Self::advance_past_separator)?;
 
                     // If we reach EOF after skipping newlines/comments, the newline was trailing
                     if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
@@ -328,7 +812,7 @@ impl<'a> Parser<'a> {
                         // There's content after the newline, so this is a real separator
                         // We need to create an implicit array
                         let mut array = vec![first_value];
-                        array.push(self.parse_value()?);
+                        array.push(self.parse_value_at(PathEntry::Index(array.len()))?);
 
                         loop {
                             self.skip_comments_and_newlines()?;
@@ -337,84 +821,101 @@ impl<'a> Parser<'a> {
                             }
 
                             if self.is_separator() {
-                                self.advance()?;
-                                self.skip_comments_and_newlines()?;
+                                self.lex_into(
+                                    PathEntry::Index(array.len()),
+                                    Self::advance_past_separator,
+                                )?;
                                 if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof)
                                 {
                                     break;
                                 }
+                            } else if !self.whitespace_separates_values() {
+                                return Err(
+                                    self.top_level_separator_error("separator or end of input")
+                                );
+                            } else {
+                                self.note_missing_comma();
                             }
 
-                            array.push(self.parse_value()?);
+                            array.push(self.parse_value_at(PathEntry::Index(array.len()))?);
                         }
 
-                        return Ok(Value::Array(array));
+                        return Ok(self.implicit_array(start, array));
                     }
                 }
 
                 // It's an implicit array (for commas)
                 if self.options.implicit_top_level {
                     let mut array = vec![first_value];
-                    self.advance()?;
+                    self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
 
                     loop {
-                        self.skip_comments_and_newlines()?;
+                        self.lex_into(
+                            PathEntry::Index(array.len()),
+                            Self::skip_comments_and_newlines,
+                        )?;
                         if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
                             break;
                         }
 
                         // Check for consecutive separators (which mean null values)
                         if self.is_separator() {
+                            self.check_array_len(array.len())?;
                             array.push(Value::Null);
-                            self.advance()?;
+                            self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
                             continue;
                         }
 
-                        array.push(self.parse_value()?);
+                        array.push(self.parse_value_at(PathEntry::Index(array.len()))?);
 
                         self.skip_comments_and_newlines()?;
                         if self.is_separator() {
-                            self.advance()?;
+                            self.lex_into(PathEntry::Index(array.len()), Self::advance)?;
                         } else if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
                             break;
                         } else {
-                            return Err(Error::Expected {
-                                expected: ", or newline or end of input".to_string(),
-                                found: format!("{:?}", self.current_token.as_ref().map(|(t, _)| t)),
-                                position: self.lexer.position(),
-                            });
+                            return Err(
+                                self.top_level_separator_error(", or newline or end of input")
+                            );
                         }
                     }
 
-                    Ok(Value::Array(array))
+                    Ok(self.implicit_array(start, array))
                 } else {
                     Err(Error::Expected {
                         expected: "end of input".to_string(),
                         found: format!("{:?}", self.current_token.as_ref().map(|(t, _)| t)),
-                        position: self.lexer.position(),
+                        position: self.token_position(),
                     })
                 }
             }
             _ => {
-                // Check if this is another value in an implicit array (space-separated)
-                if self.options.implicit_top_level && self.is_value_token() {
+                // Check if this is another value in an implicit array
+                if self.options.implicit_top_level
+                    && self.whitespace_separates_values()
+                    && self.is_value_token()
+                {
                     // Create an implicit array with the first value and continue parsing
+                    self.note_missing_comma();
                     let mut array = vec![first_value];
 
                     // Parse the remaining values
                     loop {
                         // Check for consecutive separators (which mean null values)
                         if self.is_separator() {
+                            self.check_array_len(array.len())?;
                             array.push(Value::Null);
-                            self.advance()?;
-                            self.skip_comments_and_newlines()?;
+                            self.lex_into(
+                                PathEntry::Index(array.len()),
+                                Self::advance_past_separator,
+                            )?;
                             if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
                                 break;
                             }
                             continue;
                         }
 
-                        array.push(self.parse_value()?);
+                        array.push(self.parse_value_at(PathEntry::Index(array.len()))?);
                         self.skip_comments()?;
 
                         if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
@@ -423,37 +924,199 @@ impl<'a> Parser<'a> {
 
                         // Check if there's a separator
                         if self.is_separator() {
-                            self.advance()?;
-                            self.skip_comments_and_newlines()?;
+                            self.lex_into(
+                                PathEntry::Index(array.len()),
+                                Self::advance_past_separator,
+                            )?;
                             if self.current_token.as_ref().map(|(t, _)| t) == Some(&Token::Eof) {
                                 break;
                             }
+                        } else if !self.whitespace_separates_values() {
+                            return Err(self.top_level_separator_error("separator or end of input"));
                         } else if !self.is_value_token() {
-                            return Err(Error::Expected {
-                                expected: "value, separator, or end of input".to_string(),
-                                found: format!("{:?}", self.current_token.as_ref().map(|(t, _)| t)),
-                                position: self.lexer.position(),
-                            });
+                            return Err(
+                                self.top_level_separator_error("value, separator, or end of input")
+                            );
+                        } else {
+                            self.note_missing_comma();
                         }
                     }
 
-                    Ok(Value::Array(array))
+                    Ok(self.implicit_array(start, array))
                 } else {
                     Err(Error::Expected {
                         expected: "end of input".to_string(),
                         found: format!("{:?}", self.current_token.as_ref().map(|(t, _)| t)),
-                        position: self.lexer.position(),
+                        position: self.token_position(),
                     })
                 }
             }
         }
     }
 
+    /// Builds the error for a top-level value followed by something other
+    /// than a separator, `expected` describing what may follow.
+    ///
+    /// Once the document has started as a value or implicit array, a later
+    /// `key:` never turns it into an implicit object, so a colon here gets a
+    /// hint instead of the bare token name.
+    fn top_level_separator_error(&self, expected: &str) -> Error {
+        let token = self.current_token.as_ref().map(|(t, _)| t);
+        let expected = if token == Some(&Token::Colon) {
+            format!("{expected} (an implicit top-level object must start with a key)")
+        } else {
+            expected.to_string()
+        };
+        Error::Expected {
+            expected,
+            found: format!("{token:?}"),
+            position: self.token_position(),
+        }
+    }
+
+    /// Adds a member to `object` according to
+    /// [`duplicate_keys`](ParserOptions::duplicate_keys); `position` is where
+    /// the key starts.
+    pub(super) fn insert_member(
+        &mut self,
+        object: &mut Members,
+        key: String,
+        value: Value,
+        position: usize,
+    ) -> Result<()> {
+        if let Some(budget) = &mut self.budget {
+            budget.charge_key(&key, self.state.previous_end)?;
+        }
+        match self.options.limits.max_object_members {
+            Some(max) if object.map.len() >= max && !object.map.contains_key(&key) => {
+                return Err(Error::LimitExceeded {
+                    what: "object member count".to_string(),
+                    limit: max,
+                    position,
+                })
+            }
+            _ => {}
+        }
+        if let Some(top_level) = &mut self.top_level_pairs {
+            if top_level.depth == self.state.depth {
+                // Every pair is kept, so the map only counts distinct keys
+                object.map.entry(key.clone()).or_insert(Value::Null);
+                top_level.pairs.push((key, value));
+                return Ok(());
+            }
+        }
+        match object.map.entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+            Entry::Occupied(mut entry) => match self.options.duplicate_keys {
+                DuplicateKeyPolicy::LastWins => {
+                    entry.insert(value);
+                }
+                DuplicateKeyPolicy::FirstWins => {}
+                DuplicateKeyPolicy::Error => {
+                    return Err(Error::DuplicateKey {
+                        key: entry.remove_entry().0,
+                        position,
+                    })
+                }
+                DuplicateKeyPolicy::CollectIntoArray => {
+                    if object.collected.insert(entry.key().clone()) {
+                        let first = std::mem::replace(entry.get_mut(), Value::Null);
+                        entry.insert(Value::Array(vec![first, value]));
+                    } else if let Value::Array(values) = entry.get_mut() {
+                        values.push(value);
+                    }
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Enforces [`max_key_len`](Limits::max_key_len) and
+    /// [`reject_blank_keys`](ParserOptions::reject_blank_keys) on an object
+    /// key starting at `position`.
+    pub(super) fn check_key(&self, key: &str, position: usize) -> Result<()> {
+        match self.options.limits.max_key_len {
+            Some(max) if key.len() > max => Err(Error::LimitExceeded {
+                what: "key length".to_string(),
+                limit: max,
+                position,
+            }),
+            _ if self.options.reject_blank_keys && key.trim().is_empty() => Err(Error::BlankKey {
+                key: key.to_string(),
+                position,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Enforces [`max_string_len`](Limits::max_string_len) on a string
+    /// value starting at `position`.
+    fn check_string_length(&self, value: &Value, position: usize) -> Result<()> {
+        match (self.options.limits.max_string_len, value) {
+            (Some(max), Value::String(s)) if s.len() > max => Err(Error::LimitExceeded {
+                what: "string length".to_string(),
+                limit: max,
+                position,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Extends the unquoted string value at `span`, the current token, over
+    /// the tokens after it that
+    /// [`unquoted_value_mode`](ParserOptions::unquoted_value_mode) takes in,
+    /// leaving the last of them current.
+    fn rest_of_line(&mut self, mut span: Span) -> Result<Span> {
+        let mode = self.options.unquoted_value_mode;
+        if mode == UnquotedValueMode::SingleToken {
+            return Ok(span);
+        }
+        loop {
+            let (token, next) = *self.lexer.peek_token()?;
+            if !mode.continues(token, &self.original_input[span.end..next.start]) {
+                return Ok(span);
+            }
+            self.advance()?;
+            span.end = next.end;
+        }
+    }
+
+    /// Parses the number token at `span`, honouring
+    /// [`preserve_number_precision`](ParserOptions::preserve_number_precision),
+    /// [`number_overflow`](ParserOptions::number_overflow) and
+    /// [`exponent_as_float`](ParserOptions::exponent_as_float).
+    fn parse_number(&mut self, span: Span) -> Result<Value> {
+        let mut value = parse_number_token(self.original_input, span)?;
+        self.note_number(span, &value);
+        if let Value::Number(number) = &value {
+            let text = &self.original_input[span.start..span.end];
+            check_range(text, number, span.start, &self.options)?;
+            if overflowed(text, number) {
+                self.note_overflow(span);
+            }
+            value = Value::Number(exponent_number(text, number.clone(), &self.options));
+        }
+        if self.options.preserve_number_precision {
+            exact_number(self.original_input, span, value)
+        } else {
+            Ok(value)
+        }
+    }
+
     pub(super) fn advance(&mut self) -> Result<()> {
+        if let Some((_, span)) = self.current_token {
+            self.state.previous_end = span.end;
+        }
         loop {
             let (token, span) = self.lexer.next_token()?;
+            if let Some(budget) = &mut self.budget {
+                budget.on_token(span.end)?;
+            }
             self.state.span = span; // Update parser state with the current token's span
             self.current_token = Some((token, span));
+            self.note_comments(span);
 
             match self.current_token.as_ref().map(|(t, _)| t) {
                 Some(&Token::SingleLineComment) | Some(&Token::MultiLineComment) => {
@@ -520,6 +1183,13 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
+    /// Consumes the current token, a separator or colon, along with the
+    /// comments and newlines after it.
+    pub(super) fn advance_past_separator(&mut self) -> Result<()> {
+        self.advance()?;
+        self.skip_comments_and_newlines()
+    }
+
     /// Checks if the current token is a separator (comma or newline when newline_as_comma is enabled).
     pub(super) fn is_separator(&self) -> bool {
         matches!(
@@ -532,6 +1202,29 @@ impl<'a> Parser<'a> {
             ))
     }
 
+    /// Checks whether the current token may follow the previous value with
+    /// only whitespace between them: always with
+    /// [`ParserOptions::space_separated_values`], and across a line break with
+    /// [`ParserOptions::newline_as_comma`].
+    fn whitespace_separates_values(&self) -> bool {
+        if self.options.space_separated_values {
+            return true;
+        }
+        self.options.newline_as_comma
+            && self.current_token.is_some_and(|(_, span)| {
+                let gap_start = self.state.previous_end.min(span.start);
+                self.original_input[gap_start..span.start].contains('\n')
+            })
+    }
+
+    /// Returns the value for input that holds no value, per
+    /// [`ParserOptions::empty_input_behavior`].
+    fn empty_input_value(&self) -> Result<Value> {
+        self.options
+            .empty_input_behavior
+            .value(self.original_input.len())
+    }
+
     /// Checks if the input contains only whitespace, newlines, and comments (effectively empty).
     #[allow(dead_code)]
     fn is_only_whitespace_and_newlines(&mut self) -> bool {
@@ -540,29 +1233,7 @@ impl<'a> Parser<'a> {
         let remaining_input = &self.original_input[current_pos..];
 
         // Create same type of lexer as the main parser
-        let needs_forgiving = self.options.allow_comments
-            || self.options.allow_trailing_commas
-            || self.options.allow_unquoted_keys
-            || self.options.allow_single_quotes
-            || self.options.implicit_top_level
-            || self.options.newline_as_comma;
-
-        let mut temp_lexer: Box<dyn JsonLexer> = if needs_forgiving {
-            let config = LexerConfig {
-                mode: if self.options.allow_comments {
-                    LexerMode::Forgiving
-                } else {
-                    LexerMode::Strict
-                },
-                collect_stats: false,
-                buffer_size: 8192,
-                max_depth: self.options.max_depth,
-                track_positions: true,
-            };
-            Box::new(FastLexer::new(remaining_input, config))
-        } else {
-            Box::new(Lexer::new(remaining_input))
-        };
+        let mut temp_lexer = create_parser_lexer(remaining_input, &self.options);
 
         loop {
             match temp_lexer.next_token() {
@@ -589,41 +1260,46 @@ impl<'a> Parser<'a> {
 
                     // Read the potential key
                     let potential_key = match self.current_token {
-                        Some((Token::String, span)) => {
-                            // Use the helper function to parse the string
-                            match parse_string_token(self.original_input, span, &self.options)? {
-                                Value::String(s) => s,
-                                _ => {
-                                    unreachable!("parse_string_token should always return a String")
-                                }
-                            }
-                        }
-                        Some((Token::UnquotedString, span)) => {
-                            // Use the span information directly - no quotes to remove
-                            self.original_input[span.start..span.end].to_string()
-                        }
-                        Some((Token::Number, span)) => {
-                            // Use the span information directly
-                            self.original_input[span.start..span.end].to_string()
-                        }
-                        _ => unreachable!(),
+                        Some((token, span)) => self.key_from_token(token, span)?,
+                        None => None,
                     };
 
                     // Save the current token info before advancing
                     let key_token = self.current_token;
+                    let start = self.state.span.start;
 
                     // Advance past the key token
                     self.advance()?;
                     self.skip_comments_and_newlines()?;
 
                     if let Some((Token::Colon, _)) = self.current_token {
-                        // It's an implicit object
-                        let mut object = FxHashMap::default();
+                        // It's an implicit object, whose members are not
+                        // nested in any container
+                        if let Some(top_level) = &mut self.top_level_pairs {
+                            top_level.depth = 0;
+                        }
+                        let Some(potential_key) = potential_key else {
+                            return Err(Error::Expected {
+                                expected: "string key".to_string(),
+                                found: match key_token {
+                                    Some((token, _)) => format!("{token:?}"),
+                                    None => "EOF".to_string(),
+                                },
+                                position: start,
+                            });
+                        };
+                        let mut object = Members::default();
+                        if let Some((token, span)) = key_token {
+                            self.note_key(token, span, &potential_key);
+                            self.check_key(&potential_key, span.start)?;
+                        }
 
                         // Parse first key-value pair
-                        self.advance()?; // Skip colon
-                        let value = self.parse_value()?;
-                        object.insert(potential_key, value);
+                        let key_text = key_token
+                            .map_or("", |(_, span)| &self.original_input[span.start..span.end]);
+                        self.lex_into(PathEntry::Key(key_text), Self::advance)?; // Skip colon
+                        let value = self.parse_value_at(PathEntry::Key(key_text))?;
+                        self.insert_member(&mut object, potential_key, value, start)?;
 
                         // Continue parsing object pairs
                         loop {
@@ -640,38 +1316,38 @@ impl<'a> Parser<'a> {
                                 if let Some((Token::Eof, _)) = self.current_token {
                                     break;
                                 }
+                            } else {
+                                self.note_missing_comma();
                             }
 
                             // Parse next key
+                            let key_span = self.state.span;
                             let key = match self.current_token {
-                                Some((Token::String, span)) => {
-                                    // Use the helper function to parse the string
-                                    let k = match parse_string_token(
-                                        self.original_input,
-                                        span,
-                                        &self.options,
-                                    )? {
-                                        Value::String(s) => s,
-                                        _ => unreachable!(
-                                            "parse_string_token should always return a String"
-                                        ),
-                                    };
-                                    self.advance()?;
-                                    k
+                                Some((token, span)) => {
+                                    let key = self.key_from_token(token, span)?;
+                                    if let Some(key) = &key {
+                                        self.note_key(token, span, key);
+                                        self.check_key(key, span.start)?;
+                                    }
+                                    key
                                 }
-                                Some((Token::UnquotedString, span)) => {
-                                    // Use the span information directly - no quotes to remove
-                                    let k = self.original_input[span.start..span.end].to_string();
+                                None => None,
+                            };
+                            let key = match key {
+                                Some(key) => {
                                     self.advance()?;
-                                    k
+                                    key
                                 }
-                                Some((Token::Number, span)) => {
-                                    // Use the span information directly
-                                    let k = self.original_input[span.start..span.end].to_string();
-                                    self.advance()?;
-                                    k
+                                // Once the document is an implicit object it stays one;
+                                // falling back to an array here would depend on what
+                                // happens to follow.
+                                _ => {
+                                    return Err(Error::Expected {
+                                        expected: "key of implicit top-level object".to_string(),
+                                        found: format!("{:?}", self.current_token),
+                                        position: self.token_position(),
+                                    })
                                 }
-                                _ => break,
                             };
 
                             // Expect colon
@@ -680,32 +1356,52 @@ impl<'a> Parser<'a> {
                                 return Err(Error::Expected {
                                     expected: ":".to_string(),
                                     found: format!("{:?}", self.current_token),
-                                    position: self.lexer.position(),
+                                    position: self.token_position(),
                                 });
                             }
-                            self.advance()?;
+                            // Skip the colon
+                            let key_text = &self.original_input[key_span.start..key_span.end];
+                            self.lex_into(PathEntry::Key(key_text), Self::advance)?;
 
                             // Parse value
-                            let value = self.parse_value()?;
-                            object.insert(key, value);
+                            let value = self.parse_value_at(PathEntry::Key(key_text))?;
+                            self.insert_member(&mut object, key, value, key_span.start)?;
                         }
 
-                        return Ok(Value::Object(object));
+                        self.note_implicit_object(start);
+                        return Ok(Value::Object(object.map));
                     } else {
                         // Not an implicit object, parse the original token as a value
                         let value = match key_token {
                             Some((Token::String, span)) => {
                                 // Use the helper function to parse the string
-                                parse_string_token(self.original_input, span, &self.options)?
+                                let value =
+                                    parse_string_token(self.original_input, span, &self.options)?;
+                                self.check_string_length(&value, span.start)?;
+                                self.note_string(span, &value);
+                                value
                             }
-                            Some((Token::UnquotedString, span)) => {
-                                // Handle unquoted strings as values
+                            Some((Token::UnquotedString, mut span)) => {
+                                // Handle unquoted strings as values, which
+                                // may run on over the current token
+                                let mode = self.options.unquoted_value_mode;
+                                while let Some((token, next)) = self.current_token {
+                                    let gap = &self.original_input[span.end..next.start];
+                                    if !mode.continues(token, gap) {
+                                        break;
+                                    }
+                                    span.end = next.end;
+                                    self.advance()?;
+                                }
                                 let s = self.original_input[span.start..span.end].to_string();
-                                Value::String(s)
+                                let value = Value::String(s);
+                                self.check_string_length(&value, span.start)?;
+                                self.note_unquoted_string(span);
+                                value
                             }
                             Some((Token::Number, span)) => {
                                 // Use the same number parsing logic as parse_number_token
-                                parse_number_token(self.original_input, span)?
+                                self.parse_number(span)?
                             }
                             _ => unreachable!(),
                         };
@@ -740,17 +1436,22 @@ impl<'a> Parser<'a> {
             }
             Some((Token::String, span)) => {
                 let value = parse_string_token(self.original_input, span, &self.options)?;
+                self.check_string_length(&value, span.start)?;
+                self.note_string(span, &value);
                 self.advance()?;
                 Ok(value)
             }
             Some((Token::UnquotedString, span)) => {
                 // Handle unquoted strings as values - extract from span
-                let s = self.original_input[span.start..span.end].to_string();
+                let span = self.rest_of_line(span)?;
+                let value = Value::String(self.original_input[span.start..span.end].to_string());
+                self.check_string_length(&value, span.start)?;
+                self.note_unquoted_string(span);
                 self.advance()?;
-                Ok(Value::String(s))
+                Ok(value)
             }
             Some((Token::Number, span)) => {
-                let value = parse_number_token(self.original_input, span)?;
+                let value = self.parse_number(span)?;
                 self.advance()?;
                 Ok(value)
             }
@@ -764,7 +1465,7 @@ impl<'a> Parser<'a> {
                     Err(Error::Expected {
                         expected: "value".to_string(),
                         found: "EOF".to_string(),
-                        position: self.lexer.position(),
+                        position: self.token_position(),
                     })
                 }
             }
@@ -776,29 +1477,181 @@ impl<'a> Parser<'a> {
                     Err(Error::Expected {
                         expected: "value".to_string(),
                         found: "EOF".to_string(),
-                        position: self.lexer.position(),
+                        position: self.token_position(),
                     })
                 }
             }
             _ => Err(Error::Expected {
                 expected: "value".to_string(),
                 found: format!("{:?}", self.current_token),
-                position: self.lexer.position(),
+                position: self.token_position(),
             }),
         }
     }
 
+    /// Returns the capacity to reserve for the container opened at `open`.
+    ///
+    /// Returns 0 unless [`ParserOptions::preallocate`] is enabled and the
+    /// input is at least [`PREALLOCATE_MIN_INPUT`] bytes long. A scan also
+    /// records the element counts of the containers nested in the one it
+    /// started at, so those are not scanned again when they are parsed.
+    pub(super) fn container_capacity_hint(&mut self, open: Span) -> usize {
+        if !self.options.preallocate || self.original_input.len() < PREALLOCATE_MIN_INPUT {
+            return 0;
+        }
+        // Containers are parsed in the order the scan found them; entries
+        // before `open` were brackets the parser did not treat as containers
+        while let Some(&(start, len)) = self.capacity_hints.front() {
+            if start > open.end {
+                break;
+            }
+            self.capacity_hints.pop_front();
+            if start == open.end {
+                return len;
+            }
+        }
+        scan_container_lens(self.original_input, open.end, &mut self.capacity_hints)
+    }
+
+    /// Where the current token starts, which errors about it report.
+    ///
+    /// The lexer has already moved past the token by then, so its own
+    /// position would point after it.
+    pub(super) fn token_position(&self) -> usize {
+        self.current_token
+            .map_or_else(|| self.lexer.position(), |(_, span)| span.start)
+    }
+
     pub(super) fn check_depth(&self) -> Result<()> {
         if self.state.depth >= self.options.max_depth {
-            Err(Error::DepthLimitExceeded(self.lexer.position()))
+            Err(Error::DepthLimitExceeded(self.token_position()))
         } else {
             Ok(())
         }
     }
 }
 
+/// The shortest input [`ParserOptions::preallocate`] scans.
+///
+/// Containers in smaller documents are too small for growing them to cost
+/// more than the scan.
+const PREALLOCATE_MIN_INPUT: usize = 4 * 1024;
+
+/// How many bytes of a container's source text [`ParserOptions::preallocate`]
+/// scans for its element count.
+///
+/// A container longer than this is pre-sized for the elements seen so far and
+/// grows as usual after that.
+const PREALLOCATE_SCAN_LIMIT: usize = 64 * 1024;
+
+/// The bytes [`scan_container_lens`] acts on; it skips over all others.
+const SCAN_STOPS: [bool; 256] = {
+    let bytes = b"\"',[]{}";
+    let mut stops = [false; 256];
+    let mut i = 0;
+    while i < bytes.len() {
+        stops[bytes[i] as usize] = true;
+        i += 1;
+    }
+    stops
+};
+
+/// Estimates the number of elements in the container whose body starts at
+/// `start`, and in each container nested in it.
+///
+/// Counts the commas at each container's own nesting level, skipping over
+/// strings, and stops at the matching closing delimiter or after
+/// [`PREALLOCATE_SCAN_LIMIT`] bytes. Nested containers closed within the scan
+/// replace the contents of `nested` as `(body start, estimate)` in the order
+/// they open.
+/// The results are only capacity hints: separators inside comments are
+/// counted and newline-separated elements are not.
+fn scan_container_lens(input: &str, start: usize, nested: &mut VecDeque<(usize, usize)>) -> usize {
+    fn estimate(commas: usize) -> usize {
+        if commas == 0 {
+            0
+        } else {
+            commas + 1
+        }
+    }
+
+    let body = input.as_bytes().get(start..).unwrap_or_default();
+    let body = &body[..body.len().min(PREALLOCATE_SCAN_LIMIT)];
+    nested.clear();
+    // Index in `nested` and comma count of each open nested container
+    let mut open: Vec<(usize, usize)> = Vec::new();
+    let mut commas = 0usize;
+    let mut closed = false;
+    let mut i = 0;
+
+    while i < body.len() {
+        let byte = body[i];
+        i += 1;
+        if !SCAN_STOPS[byte as usize] {
+            continue;
+        }
+        match byte {
+            b'"' | b'\'' => {
+                while i < body.len() {
+                    let inner = body[i];
+                    i += 1;
+                    if inner == b'\\' {
+                        i += 1;
+                    } else if inner == byte {
+                        break;
+                    }
+                }
+            }
+            b'[' | b'{' => {
+                open.push((nested.len(), 0));
+                nested.push_back((start + i, 0));
+            }
+            b']' | b'}' => match open.pop() {
+                Some((index, inner_commas)) => nested[index].1 = estimate(inner_commas),
+                None => {
+                    closed = true;
+                    break;
+                }
+            },
+            _ => match open.last_mut() {
+                Some((_, inner_commas)) => *inner_commas += 1,
+                None => commas += 1,
+            },
+        }
+    }
+
+    // Containers still open where the scan stopped are scanned when reached
+    if !closed {
+        if let Some(&(first_open, _)) = open.first() {
+            nested.truncate(first_open);
+        }
+    }
+    estimate(commas)
+}
+
+impl JsonParser for Parser<'_> {
+    fn parse_with(&mut self, input: &str, options: &ParserOptions) -> Result<Value> {
+        let mut parser = Parser::new(input, options.clone());
+        let result = parser.parse();
+        self.warnings = parser.take_warnings();
+        self.compat_warnings = parser.take_compat_warnings();
+        result
+    }
+
+    fn take_warnings(&mut self) -> Vec<Warning> {
+        Parser::take_warnings(self)
+    }
+
+    fn take_compat_warnings(&mut self) -> Vec<CompatWarning> {
+        Parser::take_compat_warnings(self)
+    }
+}
+
 /// Parses a JSON string with default options (all forgiving features enabled).
 ///
+/// This always uses the hybrid [`Parser`], which is also what
+/// [`ParserBackend::Auto`] selects under the default options.
+///
 /// # Examples
 ///
 /// ```
@@ -819,6 +1672,8 @@ pub fn parse(input: &str) -> Result<Value> {
 
 /// Parses a JSON string with custom options.
 ///
+/// The parser used is the one [`ParserOptions::backend`] selects.
+///
 /// # Arguments
 ///
 /// * `input` - The JSON string to parse
@@ -836,8 +1691,12 @@ pub fn parse(input: &str) -> Result<Value> {
 /// assert!(result.is_ok());
 /// ```
 pub fn parse_with_options(input: &str, options: ParserOptions) -> Result<Value> {
-    let mut parser = Parser::new(input, options);
-    parser.parse()
+    match options.backend.select(input, &options) {
+        ParserBackend::Auto | ParserBackend::Hybrid => Parser::new(input, options).parse(),
+        ParserBackend::Recursive => RecursiveDescentParser::new(input, options).parse(),
+        ParserBackend::Iterative => IterativeParser::new(input, options).parse(),
+        ParserBackend::OptimizedV3 => OptimizedParserV3::new(input, options).parse(),
+    }
 }
 
 /// Enhanced parsing with three-tier fallback strategy (serde_json → vexy_json → repair)
@@ -850,20 +1709,26 @@ pub fn parse_with_options(input: &str, options: ParserOptions) -> Result<Value>
 /// Returns an `EnhancedParseResult` that includes information about which
 /// parsing tier was used and any repairs that were applied.
 pub fn parse_with_fallback(input: &str, options: ParserOptions) -> EnhancedParseResult<Value> {
-    // Tier 1: Try serde_json for maximum performance on valid JSON
-    if let Ok(serde_value) = serde_json::from_str::<serde_json::Value>(input) {
-        // Convert serde_json::Value to vexy_json::Value
-        let vexy_json_value = convert_serde_to_vexy_json(serde_value);
-        return EnhancedParseResult::success(vexy_json_value, ParsingTier::Fast);
+    // Tier 1: Try serde_json for maximum performance on valid JSON, unless
+    // the options change what valid JSON reads as
+    if options.fast_tier_safe() {
+        if let Ok(serde_value) = serde_json::from_str::<serde_json::Value>(input) {
+            // Convert serde_json::Value to vexy_json::Value
+            let vexy_json_value = convert_serde_to_vexy_json(serde_value);
+            return EnhancedParseResult::success(vexy_json_value, ParsingTier::Fast);
+        }
     }
 
     // Tier 2: Try vexy_json for forgiving parsing
-    match parse_with_options(input, options.clone()) {
-        Ok(value) => EnhancedParseResult::success(value, ParsingTier::Forgiving),
+    let mut parser = options.backend.select(input, &options).parser();
+    match parser.parse_with(input, &options) {
+        Ok(value) => EnhancedParseResult::success(value, ParsingTier::Forgiving)
+            .with_warnings(parser.take_warnings())
+            .with_compat_warnings(parser.take_compat_warnings()),
         Err(error) => {
             // Tier 3: Try repair if enabled
             if options.enable_repair {
-                parse_with_repair(input, &options)
+                check_repair_budget(parse_with_repair(input, &options, error), &options)
             } else {
                 EnhancedParseResult::failure(Value::Null, vec![error], ParsingTier::Forgiving)
             }
@@ -871,42 +1736,168 @@ pub fn parse_with_fallback(input: &str, options: ParserOptions) -> EnhancedParse
     }
 }
 
-/// Parse with repair functionality for bracket mismatches and pattern-based recovery
-fn parse_with_repair(input: &str, options: &ParserOptions) -> EnhancedParseResult<Value> {
+/// Creates the bracket repairer configured by `options`.
+fn repairer_for(options: &ParserOptions) -> JsonRepairer {
+    match &options.repair_cache {
+        _ if options.fast_repair => JsonRepairer::new_without_cache(options.max_repairs),
+        Some(cache) => JsonRepairer::with_shared_cache(options.max_repairs, Arc::clone(cache)),
+        None => JsonRepairer::new(options.max_repairs),
+    }
+}
+
+/// Parse with repair functionality for bracket mismatches and pattern-based
+/// recovery, `error` being the error parsing `input` failed with
+fn parse_with_repair(
+    input: &str,
+    options: &ParserOptions,
+    error: Error,
+) -> EnhancedParseResult<Value> {
+    if options.normalize_unicode_punctuation {
+        let replacements = punctuation_repairs(input);
+        if !replacements.is_empty() {
+            return parse_with_punctuation_repair(input, replacements, options, error);
+        }
+    }
+
     // First, try the basic JsonRepairer for bracket mismatches
-    let mut repairer = if options.fast_repair {
-        JsonRepairer::new_without_cache(options.max_repairs)
-    } else {
-        JsonRepairer::new(options.max_repairs)
-    };
+    let mut repairer = repairer_for(options);
+    if let Ok((repaired_json, repairs)) = repairer.repair(input) {
+        if let Ok(value) = parse_with_options(&repaired_json, options.clone()) {
+            let repairs = repairs
+                .into_iter()
+                .map(|repair| RepairAction {
+                    cause: Some(error.clone()),
+                    ..repair
+                })
+                .collect();
+            return check_trailing_discard(
+                EnhancedParseResult::success_with_repairs(value, repairs, ParsingTier::Repair),
+                input,
+                options,
+            );
+        }
+    }
 
-    match repairer.repair(input) {
-        Ok((repaired_json, repairs)) => {
-            // Try to parse the repaired JSON with vexy_json
-            match parse_with_options(&repaired_json, options.clone()) {
-                Ok(value) => {
-                    EnhancedParseResult::success_with_repairs(value, repairs, ParsingTier::Repair)
-                }
-                Err(error) => {
-                    // Basic repair didn't work, try advanced pattern-based recovery
-                    parse_with_advanced_recovery(input, options, error, repairs)
-                }
-            }
+    // Basic repair didn't work, try advanced pattern-based recovery
+    parse_with_advanced_recovery(input, options, error)
+}
+
+/// Parses `input` making the word-processor punctuation replacements in
+/// `replacements` one at a time, in order, each once parsing fails at or
+/// after it, then repairing whatever else is wrong. Every position is reported against `input`.
+fn parse_with_punctuation_repair(
+    input: &str,
+    mut replacements: Vec<RepairAction>,
+    options: &ParserOptions,
+    error: Error,
+) -> EnhancedParseResult<Value> {
+    // The trailing discard is checked once positions refer to `input`
+    let inner = ParserOptions {
+        normalize_unicode_punctuation: false,
+        max_trailing_discard: None,
+        ..options.clone()
+    };
+    // A no-break space the parser reads as whitespace needs no repair
+    if options.allow_unicode_whitespace {
+        replacements.retain(|replacement| replacement.original != "\u{a0}");
+    }
+    let mut made: Vec<RepairAction> = Vec::new();
+    let mut text = input.to_string();
+    // The error parsing `text` fails with, positioned in `text`
+    let mut text_error = error;
+    loop {
+        let replaced: Vec<&RepairAction> = made.iter().collect();
+        let error = text_error
+            .clone()
+            .map_positions(&|position| original_position(&replaced, position));
+        // Parsing fails at or after the first replacement still to make,
+        // unless a string left open by a curly quote ran on past the error
+        let next = replacements.first().is_some_and(|replacement| match error {
+            Error::UnterminatedString(_) => true,
+            _ => error.position().map_or(true, |p| replacement.position <= p),
+        });
+        if !next {
+            break;
         }
-        Err(_repair_error) => {
-            // Basic repair failed, try advanced pattern-based recovery
-            // First try to parse to get the specific error
-            match parse_with_options(input, options.clone()) {
-                Ok(value) => {
-                    // Shouldn't happen, but handle gracefully
-                    EnhancedParseResult::success(value, ParsingTier::Repair)
-                }
-                Err(parse_error) => {
-                    parse_with_advanced_recovery(input, options, parse_error, vec![])
-                }
+        made.push(RepairAction {
+            cause: Some(error),
+            ..replacements.remove(0)
+        });
+
+        let replaced: Vec<&RepairAction> = made.iter().collect();
+        text = apply_repairs(input, &replaced).expect("replacements do not overlap");
+        match parse_with_options(&text, inner.clone()) {
+            Ok(value) => {
+                let result =
+                    EnhancedParseResult::success_with_repairs(value, made, ParsingTier::Repair);
+                return check_trailing_discard(result, input, options);
             }
+            Err(e) => text_error = e,
         }
     }
+
+    // No replacement is where parsing fails, so repair the text as it is
+    let mut result = parse_with_repair(&text, &inner, text_error);
+    let replaced: Vec<&RepairAction> = made.iter().collect();
+    let original = |position| original_position(&replaced, position);
+    let kept = text.len() - result.discarded_trailing_bytes;
+    result.discarded_trailing_bytes = input.len() - original(kept);
+    for repair in &mut result.repairs {
+        repair.position = original(repair.position);
+        repair.cause = repair.cause.take().map(|error| error.map_positions(&original));
+    }
+    result.errors = result
+        .errors
+        .into_iter()
+        .map(|error| error.map_positions(&original))
+        .collect();
+    result.repairs.extend(made);
+    result.repairs.sort_by_key(|repair| repair.position);
+    check_trailing_discard(result, input, options)
+}
+
+/// Turns a repaired result into a failure when it discarded more trailing
+/// input than `options.max_trailing_discard` allows.
+fn check_trailing_discard(
+    result: EnhancedParseResult<Value>,
+    input: &str,
+    options: &ParserOptions,
+) -> EnhancedParseResult<Value> {
+    let discarded = result.discarded_trailing_bytes;
+    match options.max_trailing_discard {
+        Some(limit) if discarded > limit => {
+            let error = Error::Expected {
+                expected: "end of input".to_string(),
+                found: format!("{discarded} bytes of trailing input"),
+                position: input.len() - discarded,
+            };
+            EnhancedParseResult::failure_with_repairs(
+                Value::Null,
+                vec![error],
+                result.repairs,
+                ParsingTier::Repair,
+            )
+        }
+        _ => result,
+    }
+}
+
+/// Turns a repaired result into a failure when it took more repairs than
+/// `options.max_repairs` allows, counting every stage of repair together.
+/// The repairs are not reported, as none of them is kept.
+fn check_repair_budget(
+    result: EnhancedParseResult<Value>,
+    options: &ParserOptions,
+) -> EnhancedParseResult<Value> {
+    if result.repairs.len() > options.max_repairs {
+        let error = match result.errors.first() {
+            Some(error) => error.clone(),
+            None => Error::MaxRepairsExceeded(options.max_repairs),
+        };
+        EnhancedParseResult::failure(Value::Null, vec![error], ParsingTier::Repair)
+    } else {
+        result
+    }
 }
 
 /// Use ErrorRecoveryEngineV2 for advanced pattern-based recovery
@@ -914,10 +1905,13 @@ fn parse_with_advanced_recovery(
     input: &str,
     options: &ParserOptions,
     original_error: Error,
-    previous_repairs: Vec<RepairAction>,
 ) -> EnhancedParseResult<Value> {
-    // Create the error recovery engine
-    let mut recovery_engine = ErrorRecoveryEngineV2::new();
+    // Create the error recovery engine; learned patterns are a tier of
+    // their own, in `parse_with_ml_recovery`
+    let mut recovery_engine = ErrorRecoveryEngineV2::with_config(RecoveryConfig {
+        enable_ml: false,
+        ..RecoveryConfig::default()
+    });
     
     // Build error context for the recovery engine
     let error_context = ErrorContext {
@@ -942,38 +1936,22 @@ fn parse_with_advanced_recovery(
     let suggestions = recovery_engine.suggest_recovery(&error_context);
     
     // Try each suggestion in order of confidence
-    let mut all_repairs = previous_repairs;
-    
     for suggestion in suggestions {
         // Try to parse the suggested fix first
         match parse_with_options(&suggestion.fixed_input, options.clone()) {
             Ok(value) => {
                 // Create repair action based on what was actually changed
-                let repair_action = RepairAction {
-                    position: suggestion.fix_location.start,
-                    action_type: suggestion.category.clone().into(),
-                    original: input[suggestion.fix_location.start..suggestion.fix_location.end.min(input.len())].to_string(),
-                    replacement: match suggestion.category {
-                        crate::error::SuggestionCategory::MissingBracket => {
-                            if suggestion.fixed_input.ends_with('}') {
-                                "}".to_string()
-                            } else if suggestion.fixed_input.ends_with(']') {
-                                "]".to_string()
-                            } else {
-                                "".to_string()
-                            }
-                        }
-                        crate::error::SuggestionCategory::UnmatchedQuote => "\"".to_string(),
-                        crate::error::SuggestionCategory::MissingComma => ",".to_string(),
-                        _ => "".to_string(),
-                    },
-                    description: suggestion.description.clone(),
-                };
+                let repair_action = RepairAction::between(
+                    input,
+                    &suggestion.fixed_input,
+                    suggestion.category.clone().into(),
+                    suggestion.description.clone(),
+                    original_error,
+                );
                 
-                all_repairs.push(repair_action);
                 return EnhancedParseResult::success_with_repairs(
                     value,
-                    all_repairs,
+                    vec![repair_action],
                     ParsingTier::Repair,
                 );
             }
@@ -985,12 +1963,7 @@ fn parse_with_advanced_recovery(
     }
     
     // All recovery attempts failed
-    EnhancedParseResult::failure_with_repairs(
-        Value::Null,
-        vec![original_error],
-        all_repairs,
-        ParsingTier::Repair,
-    )
+    EnhancedParseResult::failure(Value::Null, vec![original_error], ParsingTier::Repair)
 }
 
 /// Convert serde_json::Value to vexy_json::Value
@@ -1027,13 +2000,15 @@ pub fn parse_with_detailed_repair_tracking(
     input: &str,
     options: ParserOptions,
 ) -> EnhancedParseResult<Value> {
-    let mut repairer = JsonRepairer::new(options.max_repairs);
+    let mut repairer = repairer_for(&options);
 
     match repairer.repair_with_detailed_tracking(input) {
-        Ok((repaired_json, repairs)) => match parse_with_options(&repaired_json, options) {
-            Ok(value) => {
-                EnhancedParseResult::success_with_repairs(value, repairs, ParsingTier::Repair)
-            }
+        Ok((repaired_json, repairs)) => match parse_with_options(&repaired_json, options.clone()) {
+            Ok(value) => check_trailing_discard(
+                EnhancedParseResult::success_with_repairs(value, repairs, ParsingTier::Repair),
+                input,
+                &options,
+            ),
             Err(error) => EnhancedParseResult::failure_with_repairs(
                 Value::Null,
                 vec![error],
@@ -1,7 +1,9 @@
 // this_file: src/parser/number.rs
 
 use crate::ast::{Number, Value};
+use crate::error::types::shorten_found;
 use crate::error::{Error, Result, Span};
+use crate::parser::ParserOptions;
 
 #[inline]
 pub(super) fn parse_number_token(original_input: &str, span: Span) -> Result<Value> {
@@ -19,6 +21,12 @@ pub(super) fn parse_number_token(original_input: &str, span: Span) -> Result<Val
         return Ok(Value::Number(Number::Integer(parsed_int)));
     }
 
+    // Integers that fit in an i64 keep every digit rather than going through f64
+    let integer = number_slice.strip_suffix('.').unwrap_or(number_slice);
+    if let Some(number) = decimal_integer(integer) {
+        return Ok(Value::Number(number));
+    }
+
     // Normalize number format for Rust's parser
     let normalized_number = if has_trailing_decimal {
         // Convert "1." to "1.0" for Rust's parser
@@ -41,7 +49,7 @@ pub(super) fn parse_number_token(original_input: &str, span: Span) -> Result<Val
             // Also check that the conversion is lossless
             let in_range = f.is_finite()
                 && f >= (i64::MIN as f64)
-                && f <= (i64::MAX as f64)
+                && f < (i64::MAX as f64)
                 && (f as i64) as f64 == f;
             let is_neg_zero = f.is_sign_negative() && f == 0.0;
 
@@ -49,16 +57,11 @@ pub(super) fn parse_number_token(original_input: &str, span: Span) -> Result<Val
             let has_decimal_point = number_slice.contains('.');
             let has_exponent = number_slice.contains('e') || number_slice.contains('E');
 
-            // For negative zero, return Integer(0) not Float(-0.0)
-            if is_neg_zero && !has_decimal_point {
-                return Ok(Value::Number(Number::Integer(0)));
-            }
-
             // Only convert to integer if:
             // - No fractional part
             // - Is finite
             // - In i64 range
-            // - Not negative zero (already handled above)
+            // - Not negative zero, which only a float can hold
             // - Either no decimal point OR trailing decimal point (vexy_json compatibility)
             // - No exponent (scientific notation should be treated as float)
             if has_no_fract
@@ -81,6 +84,184 @@ pub(super) fn parse_number_token(original_input: &str, span: Span) -> Result<Val
     Ok(Value::Number(number_value))
 }
 
+/// Checks that `value`, as read from the number token at `span`, is exactly
+/// the number written there.
+///
+/// Decimal integers are re-read as `i64` so that values above 2^53 keep
+/// every digit; anything else must survive the trip through `f64`.
+pub(crate) fn exact_number(original_input: &str, span: Span, value: Value) -> Result<Value> {
+    let text = &original_input[span.start..span.end];
+    let cleaned = text.replace('_', "");
+    let prefix = cleaned.trim_start_matches(['-', '+']).get(..2);
+    if matches!(prefix, Some("0x" | "0X" | "0o" | "0O" | "0b" | "0B")) {
+        // Hex, octal and binary literals are only ever read as i64
+        return Ok(value);
+    }
+
+    let integer = cleaned.strip_suffix('.').unwrap_or(&cleaned);
+    if let Some(exact) = decimal_integer(integer) {
+        return Ok(Value::Number(exact));
+    }
+
+    let stored = match &value {
+        Value::Number(number) => number.as_f64(),
+        _ => return Ok(value),
+    };
+    let written = decimal_digits(&cleaned);
+    if written.is_some() && written == decimal_digits(&format!("{stored:e}")) {
+        Ok(value)
+    } else {
+        Err(Error::Expected {
+            expected: "a number that can be stored without rounding".to_string(),
+            found: shorten_found(text).into_owned(),
+            position: span.start,
+        })
+    }
+}
+
+/// Reads the decimal number `text` as every parser backend does: integers
+/// that fit in an `i64` stay exact and anything else becomes an `f64`.
+/// Negative zero, however it is written, keeps its sign as `Float(-0.0)`.
+///
+/// Returns `None` if `text` is not a number.
+pub(crate) fn decimal_number(text: &str) -> Option<Number> {
+    decimal_integer(text).or_else(|| text.parse::<f64>().ok().map(Number::Float))
+}
+
+/// Reads `text` as a decimal integer that fits in an `i64`, except that
+/// `-0` becomes `Float(-0.0)`.
+fn decimal_integer(text: &str) -> Option<Number> {
+    match text.parse::<i64>().ok()? {
+        0 if text.starts_with('-') => Some(Number::Float(-0.0)),
+        integer => Some(Number::Integer(integer)),
+    }
+}
+
+/// Reads the decimal number token `text` at `position` with
+/// [`decimal_number`], failing as [`check_range`] does for a number beyond
+/// the range of `f64`.
+pub(crate) fn read_number(text: &str, position: usize, options: &ParserOptions) -> Result<Number> {
+    let number = decimal_number(text).ok_or(Error::InvalidNumber(position))?;
+    check_range(text, &number, position, options)?;
+    Ok(exponent_number(text, number, options))
+}
+
+/// Turns `number`, read from the decimal number token `text`, into an
+/// integer if it was written with an exponent, is a whole number that fits
+/// in an `i64`, and [`ParserOptions::exponent_as_float`] is disabled.
+pub(crate) fn exponent_number(text: &str, number: Number, options: &ParserOptions) -> Number {
+    match number {
+        Number::Float(_) if !options.exponent_as_float && text.contains(['e', 'E']) => {
+            exponent_integer(text).map_or(number, Number::Integer)
+        }
+        number => number,
+    }
+}
+
+/// Reads the decimal number `text` as the whole number it stands for,
+/// scaling its digits with checked integer arithmetic so that none of them
+/// are rounded through an `f64`.
+///
+/// Returns `None` for fractions, negative zero and numbers beyond `i64`.
+fn exponent_integer(text: &str) -> Option<i64> {
+    let (negative, significant, scale) = decimal_digits(text)?;
+    if significant.is_empty() {
+        return (!negative).then_some(0);
+    }
+    let zeros = u32::try_from(scale.checked_sub(significant.len() as i64)?).ok()?;
+    // i128 holds the magnitude of i64::MIN, which i64 itself cannot
+    let magnitude = significant
+        .parse::<i128>()
+        .ok()?
+        .checked_mul(10i128.checked_pow(zeros)?)?;
+    i64::try_from(if negative { -magnitude } else { magnitude }).ok()
+}
+
+/// Whether `number`, read from `text`, is infinite only because the number
+/// written there is beyond the range of `f64`.
+pub(crate) fn overflowed(text: &str, number: &Number) -> bool {
+    matches!(number, Number::Float(f) if f.is_infinite())
+        && text.bytes().any(|b| b.is_ascii_digit())
+}
+
+/// Fails if `number`, read from the token `text` at `position`, overflowed
+/// to infinity and [`ParserOptions::number_overflow`] does not keep the
+/// infinity.
+pub(crate) fn check_range(
+    text: &str,
+    number: &Number,
+    position: usize,
+    options: &ParserOptions,
+) -> Result<()> {
+    if options.number_overflow.fails(options) && overflowed(text, number) {
+        return Err(Error::Expected {
+            expected: "a number within the range of f64".to_string(),
+            found: shorten_found(text).into_owned(),
+            position,
+        });
+    }
+    Ok(())
+}
+
+/// Whether `text` is a number as strict JSON writes it:
+/// `-?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?`.
+pub(crate) fn is_strict_number(text: &str) -> bool {
+    fn digits(s: &str) -> usize {
+        s.bytes().take_while(u8::is_ascii_digit).count()
+    }
+
+    let rest = text.strip_prefix('-').unwrap_or(text);
+    let integer = digits(rest);
+    if integer == 0 || (integer > 1 && rest.starts_with('0')) {
+        return false;
+    }
+    let mut rest = &rest[integer..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let count = digits(fraction);
+        if count == 0 {
+            return false;
+        }
+        rest = &fraction[count..];
+    }
+    if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        let count = digits(exponent);
+        if count == 0 {
+            return false;
+        }
+        rest = &exponent[count..];
+    }
+    rest.is_empty()
+}
+
+/// Splits a decimal number into its sign, its significant digits and the
+/// power of ten they are scaled by, so that numbers written differently
+/// compare equal when they have the same value.
+fn decimal_digits(text: &str) -> Option<(bool, String, i64)> {
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        // Exponents too large for an i32 are far outside the range of f64
+        Some(at) => (&unsigned[..at], unsigned[at + 1..].parse::<i32>().ok()? as i64),
+        None => (unsigned, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{whole}{fraction}");
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let significant = digits.trim_start_matches('0');
+    let scale = exponent + whole.len() as i64 - (digits.len() - significant.len()) as i64;
+    let significant = significant.trim_end_matches('0');
+    if significant.is_empty() {
+        return Some((negative, String::new(), 0));
+    }
+    Some((negative, significant.to_string(), scale))
+}
+
 /// Parse alternative number formats (hex, octal, binary, underscore separators)
 /// Returns None if not an alternative integer format, Some(i64) if parsed successfully as an integer.
 fn parse_alternative_number_format(input: &str, span: Span) -> Result<Option<i64>> {
@@ -161,6 +342,18 @@ mod tests {
     use super::*;
     use crate::error::Span;
 
+    #[test]
+    fn test_is_strict_number() {
+        for text in ["0", "-0", "42", "-1.5", "1e10", "2.5E-3", "0.0e+1"] {
+            assert!(is_strict_number(text), "{text}");
+        }
+        for text in [
+            "", "-", "+1", "01", ".5", "1.", "1e", "0x1F", "1_000", "1.5.2",
+        ] {
+            assert!(!is_strict_number(text), "{text}");
+        }
+    }
+
     #[test]
     fn test_parse_number_token_integers() {
         // Positive integers
@@ -170,7 +363,10 @@ mod tests {
         
         // Negative integers
         assert_eq!(parse_number_token("-42", Span::new(0, 3)).unwrap(), Value::Number(Number::Integer(-42)));
-        assert_eq!(parse_number_token("-0", Span::new(0, 2)).unwrap(), Value::Number(Number::Integer(0)));
+        assert!(matches!(
+            parse_number_token("-0", Span::new(0, 2)).unwrap(),
+            Value::Number(Number::Float(f)) if f == 0.0 && f.is_sign_negative()
+        ));
     }
 
     #[test]
@@ -1,29 +1,69 @@
 // this_file: src/parser/object.rs
 
 use crate::ast::{Token, Value};
-use crate::error::{Error, Result};
+use crate::error::{Error, Result, Span};
+use crate::parser::path::PathEntry;
 use crate::parser::string::parse_string_token;
 use crate::parser::Parser;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// The members of an object being parsed.
+#[derive(Default)]
+pub(crate) struct Members {
+    pub(super) map: FxHashMap<String, Value>,
+    /// Keys whose values have been gathered into an array under
+    /// [`DuplicateKeyPolicy::CollectIntoArray`](super::DuplicateKeyPolicy::CollectIntoArray).
+    pub(super) collected: FxHashSet<String>,
+}
 
 impl<'a> Parser<'a> {
+    /// Decodes the object key spelled by a token: the unescaped content of a
+    /// string, or an unquoted string or number exactly as written. Returns
+    /// `None` for tokens that cannot be keys, including unquoted strings
+    /// when [`allow_unquoted_keys`](super::ParserOptions::allow_unquoted_keys)
+    /// is off.
+    ///
+    /// Explicit and implicit top-level objects both read their keys here.
+    pub(super) fn key_from_token(&self, token: Token, span: Span) -> Result<Option<String>> {
+        match token {
+            Token::String => match parse_string_token(self.original_input, span, &self.options)? {
+                Value::String(s) => Ok(Some(s)),
+                _ => unreachable!("parse_string_token should always return a String"),
+            },
+            Token::UnquotedString if !self.options.allow_unquoted_keys => Ok(None),
+            Token::UnquotedString | Token::Number => {
+                Ok(Some(self.original_input[span.start..span.end].to_string()))
+            }
+            _ => Ok(None),
+        }
+    }
+
     pub(super) fn parse_object(&mut self) -> Result<Value> {
         self.check_depth()?;
         self.state.depth += 1;
 
-        if !matches!(self.current_token, Some((Token::LeftBrace, _))) {
-            return Err(Error::Expected {
-                expected: "{".to_string(),
-                found: match &self.current_token {
-                    Some((token, _)) => format!("{token:?}"),
-                    None => "EOF".to_string(),
-                },
-                position: self.lexer.position(),
-            });
-        }
+        let open = match self.current_token {
+            Some((Token::LeftBrace, span)) => span,
+            _ => {
+                return Err(Error::Expected {
+                    expected: "{".to_string(),
+                    found: match &self.current_token {
+                        Some((token, _)) => format!("{token:?}"),
+                        None => "EOF".to_string(),
+                    },
+                    position: self.token_position(),
+                });
+            }
+        };
         self.advance()?;
 
-        let mut object = FxHashMap::default();
+        let mut object = Members {
+            map: FxHashMap::with_capacity_and_hasher(
+                self.container_capacity_hint(open),
+                Default::default(),
+            ),
+            collected: FxHashSet::default(),
+        };
 
         loop {
             self.skip_comments_and_newlines()?;
@@ -50,106 +90,29 @@ impl<'a> Parser<'a> {
                 .into(),
             );
 
+            let key_span = self.state.span;
             let key = match self.current_token {
-                Some((Token::String, span)) => {
-                    // Use the helper function to parse the string
-                    let k = match parse_string_token(self.original_input, span, &self.options)? {
-                        Value::String(s) => s,
-                        _ => unreachable!("parse_string_token should always return a String"),
-                    };
-                    self.advance()?;
-                    k
-                }
-                Some((Token::UnquotedString, span)) if self.options.allow_unquoted_keys => {
-                    // Extract the unquoted string content from the span
-                    let k = self.original_input[span.start..span.end].to_string();
-                    self.advance()?;
-                    k
-                }
-                Some((Token::Number, _)) => {
-                    // For numbers as keys, convert to string
-                    let current_pos = self.lexer.position();
-                    let mut number_start = current_pos;
-                    if current_pos > 0 {
-                        number_start = current_pos - 1;
-                    }
-
-                    // Search backwards for the start of the number
-                    while number_start > 0 {
-                        let prev_pos = number_start - 1;
-                        if let Some(c) = self.original_input.chars().nth(prev_pos) {
-                            match c {
-                                '0'..='9' => {
-                                    number_start = prev_pos;
-                                }
-                                '.' => {
-                                    number_start = prev_pos;
-                                }
-                                'e' | 'E' => {
-                                    number_start = prev_pos;
-                                }
-                                // Hex digits can be part of hex numbers
-                                'A'..='F' | 'a'..='f' => {
-                                    number_start = prev_pos;
-                                }
-                                // Underscore separators can be part of numbers
-                                '_' => {
-                                    number_start = prev_pos;
-                                }
-                                // Prefix characters for hex, octal, binary (excluding b,B which are hex digits)
-                                'x' | 'X' | 'o' | 'O' => {
-                                    number_start = prev_pos;
-                                }
-                                '+' | '-' => {
-                                    if prev_pos == 0 {
-                                        number_start = 0;
-                                        break;
-                                    } else if prev_pos > 0 {
-                                        if let Some(before_sign) =
-                                            self.original_input.chars().nth(prev_pos - 1)
-                                        {
-                                            if before_sign == 'e' || before_sign == 'E' {
-                                                number_start = prev_pos;
-                                            } else {
-                                                break;
-                                            }
-                                        } else {
-                                            break;
-                                        }
-                                    } else {
-                                        break;
-                                    }
-                                }
-                                ',' | ']' | '}' | ':' | ' ' | '\t' | '\n' | '\r' => {
-                                    break;
-                                }
-                                _ => {
-                                    break;
-                                }
-                            }
-                        } else {
-                            break;
-                        }
+                Some((token, span)) => {
+                    let key = self.key_from_token(token, span)?;
+                    if let Some(key) = &key {
+                        self.note_key(token, span, key);
+                        self.check_key(key, span.start)?;
                     }
-
-                    // Extract the number string
-                    let number_str = &self.original_input[number_start..current_pos];
-                    let k = number_str.to_string();
-
-                    self.advance()?;
-                    k
-                }
-                _ => {
-                    return Err(Error::Expected {
-                        expected: "string key".to_string(),
-                        found: match &self.current_token {
-                            Some((token, _)) => format!("{token:?}"),
-                            None => "EOF".to_string(),
-                        },
-                        position: self.lexer.position(),
-                    });
+                    key
                 }
+                None => None,
+            };
+            let Some(key) = key else {
+                return Err(Error::Expected {
+                    expected: "string key".to_string(),
+                    found: match &self.current_token {
+                        Some((token, _)) => format!("{token:?}"),
+                        None => "EOF".to_string(),
+                    },
+                    position: self.token_position(),
+                });
             };
+            self.advance()?;
 
             // Parse colon
             self.skip_comments_and_newlines()?;
@@ -160,41 +123,41 @@ impl<'a> Parser<'a> {
                         Some((token, _)) => format!("{token:?}"),
                         None => "EOF".to_string(),
                     },
-                    position: self.lexer.position(),
+                    position: self.token_position(),
                 });
             }
-            self.advance()?;
-
-            // Skip comments and newlines after colon
-            self.skip_comments_and_newlines()?;
+            // Skip the colon, and the comments and newlines after it
+            let key_text = &self.original_input[key_span.start..key_span.end];
+            self.lex_into(PathEntry::Key(key_text), Self::advance_past_separator)?;
 
             // Parse value
-            let value = self.parse_value()?;
-            object.insert(key, value);
+            let value = self.parse_value_at(PathEntry::Key(key_text))?;
+            self.insert_member(&mut object, key, value, key_span.start)?;
 
             // Check for separator or end
             // Skip comments first, but don't skip newlines yet - we need to check if newlines are separators
             self.skip_comments()?;
 
             match self.current_token {
-                Some((Token::Comma, _)) => {
+                Some((Token::Comma, comma)) => {
                     self.advance()?;
                     self.skip_comments_and_newlines()?;
                     // Check for trailing comma
-                    if matches!(self.current_token, Some((Token::RightBrace, _)))
-                        && !self.options.allow_trailing_commas
-                    {
-                        return Err(Error::TrailingComma(self.lexer.position()));
+                    if matches!(self.current_token, Some((Token::RightBrace, _))) {
+                        if !self.options.allow_trailing_commas {
+                            return Err(Error::TrailingComma(comma.start));
+                        }
+                        self.note_trailing_comma(comma);
                     }
                 }
-                Some((Token::Newline, _)) if self.options.newline_as_comma => {
+                Some((Token::Newline, newline)) if self.options.newline_as_comma => {
                     self.advance()?;
                     self.skip_comments_and_newlines()?;
                     // Check for trailing newline
                     if matches!(self.current_token, Some((Token::RightBrace, _)))
                         && !self.options.allow_trailing_commas
                     {
-                        return Err(Error::TrailingComma(self.lexer.position()));
+                        return Err(Error::TrailingComma(newline.start));
                     }
                 }
                 Some((Token::RightBrace, _)) => continue,
@@ -219,6 +182,7 @@ impl<'a> Parser<'a> {
                             | Some((Token::Number, _)) => {
                                 // Found a key after comments/newlines, which means the newlines were separators
                                 // Continue to next iteration to parse this key-value pair
+                                self.note_missing_comma();
                                 continue;
                             }
                             _ => {
@@ -231,7 +195,7 @@ impl<'a> Parser<'a> {
                                         Some((token, _)) => format!("{token:?}"),
                                         None => "EOF".to_string(),
                                     },
-                                    position: self.lexer.position(),
+                                    position: self.token_position(),
                                 });
                             }
                         }
@@ -242,7 +206,7 @@ impl<'a> Parser<'a> {
                                 Some((token, _)) => format!("{token:?}"),
                                 None => "EOF".to_string(),
                             },
-                            position: self.lexer.position(),
+                            position: self.token_position(),
                         });
                     }
                 }
@@ -250,6 +214,6 @@ impl<'a> Parser<'a> {
         }
 
         self.state.depth -= 1;
-        Ok(Value::Object(object))
+        Ok(Value::Object(object.map))
     }
 }
@@ -4,14 +4,14 @@
 //! to reduce allocation overhead and branch prediction hints for better CPU
 //! pipeline utilization.
 
-use crate::ast::{Number, Token, Value};
+use crate::ast::{Token, Value};
 use crate::error::{Error, Result, Span};
 use crate::lexer::{JsonLexer, Lexer};
 use crate::optimization::{
-    extract_string_content, parse_number_optimized, unescape_string_optimized, OptimizedMemoryPool,
-    PoolStats,
+    extract_string_content, unescape_string_optimized, OptimizedMemoryPool, PoolStats,
 };
-use crate::parser::ParserOptions;
+use crate::parser::number::read_number;
+use crate::parser::{JsonParser, ParserOptions};
 use rustc_hash::FxHashMap;
 
 /// Branch prediction hints for hot paths
@@ -208,12 +208,7 @@ impl<'a> OptimizedParserV2<'a> {
     /// Parses a number using optimized routines
     fn parse_number_optimized(&mut self, span: Span) -> Result<Value> {
         let number_str = &self.input[span.start..span.end];
-
-        // Use optimized number parsing
-        match parse_number_optimized(number_str) {
-            Ok(value) => Ok(Value::Number(Number::Float(value))),
-            Err(_) => Err(Error::InvalidNumber(span.start)),
-        }
+        read_number(number_str, span.start, &self.options).map(Value::Number)
     }
 
     /// Parses an object with optimized string handling
@@ -422,6 +417,15 @@ impl<'a> OptimizedParserV2<'a> {
     }
 }
 
+impl JsonParser for OptimizedParserV2<'_> {
+    fn parse_with(&mut self, input: &str, options: &ParserOptions) -> Result<Value> {
+        let mut parser = OptimizedParserV2::new(input, options.clone());
+        let result = parser.parse();
+        self.stats = parser.stats;
+        result
+    }
+}
+
 /// Parses JSON with optimized memory pool v2
 pub fn parse_optimized_v2(input: &str) -> Result<Value> {
     let mut parser = OptimizedParserV2::new(input, ParserOptions::default());
@@ -446,6 +450,7 @@ pub fn parse_v2_with_stats(input: &str) -> Result<(Value, ParserStats, PoolStats
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ast::Number;
 
     #[test]
     fn test_optimized_parser_v2_simple() {
@@ -455,7 +460,7 @@ mod tests {
         match value {
             Value::Object(obj) => {
                 assert_eq!(obj.get("name"), Some(&Value::String("test".to_string())));
-                assert_eq!(obj.get("value"), Some(&Value::Number(Number::Float(42.0))));
+                assert_eq!(obj.get("value"), Some(&Value::Number(Number::Integer(42))));
             }
             _ => panic!("Expected object"),
         }
@@ -8,13 +8,14 @@
 //! - Compact string optimization
 //! - Efficient collection pre-sizing
 
-use crate::ast::{Number, Token, Value};
+use crate::ast::{Token, Value};
 use crate::error::{Error, Result, Span};
 use crate::lexer::Lexer;
 use crate::optimization::{
     extract_string_content, AllocationStats,
 };
-use crate::parser::ParserOptions;
+use crate::parser::number::read_number;
+use crate::parser::{is_blank, JsonParser, ParserOptions};
 use rustc_hash::FxHashMap;
 
 /// Advanced optimized parser with Memory Pool V3
@@ -64,7 +65,10 @@ impl<'a> OptimizedParserV3<'a> {
 
     /// Parses the input with advanced optimizations
     pub fn parse(&mut self) -> Result<Value> {
-        let (token, span) = self.next_token()?;
+        if is_blank(self.input, &self.options) {
+            return self.options.empty_input_behavior.value(self.input.len());
+        }
+        let (token, span) = self.next_token_after_newlines()?;
         self.parse_value((token, span))
     }
 
@@ -83,6 +87,26 @@ impl<'a> OptimizedParserV3<'a> {
         self.lexer.next_token_with_span()
     }
 
+    /// Returns the next token other than a line break.
+    fn next_token_after_newlines(&mut self) -> Result<(Token, Span)> {
+        loop {
+            let (token, span) = self.next_token()?;
+            if token != Token::Newline {
+                return Ok((token, span));
+            }
+        }
+    }
+
+    /// Skips line breaks, returning whether there were any.
+    fn skip_newlines(&mut self) -> Result<bool> {
+        let mut skipped = false;
+        while self.peek_token()?.0 == Token::Newline {
+            self.next_token()?;
+            skipped = true;
+        }
+        Ok(skipped)
+    }
+
     fn peek_token(&mut self) -> Result<(Token, Span)> {
         self.lexer
             .peek_with_span()
@@ -100,11 +124,6 @@ impl<'a> OptimizedParserV3<'a> {
             Token::Null => Ok(Value::Null),
             Token::LeftBracket => self.parse_array_optimized(),
             Token::LeftBrace => self.parse_object_optimized(),
-            Token::Newline => {
-                // Skip newlines and parse the next token
-                let (next_token, next_span) = self.next_token()?;
-                self.parse_value((next_token, next_span))
-            }
             _ => Err(Error::UnexpectedChar('?', token.1.start)),
         }
     }
@@ -129,20 +148,7 @@ impl<'a> OptimizedParserV3<'a> {
     /// Parse number with optimizations
     fn parse_number_optimized(&mut self, span: Span) -> Result<Value> {
         let number_str = &self.input[span.start..span.end];
-        
-        // Try integer parsing first (most common case)
-        if !number_str.contains('.') && !number_str.contains('e') && !number_str.contains('E') {
-            if let Ok(int_val) = number_str.parse::<i64>() {
-                return Ok(Value::Number(Number::Integer(int_val)));
-            }
-        }
-
-        // Fall back to float parsing
-        if let Ok(float_val) = number_str.parse::<f64>() {
-            Ok(Value::Number(Number::Float(float_val)))
-        } else {
-            Err(Error::InvalidNumber(span.start))
-        }
+        read_number(number_str, span.start, &self.options).map(Value::Number)
     }
 
     /// Parse array with small vector optimization
@@ -168,12 +174,8 @@ impl<'a> OptimizedParserV3<'a> {
         self.stats.presized_collections += 1;
 
         loop {
-            // Skip newlines
+            let newline = self.skip_newlines()?;
             let (next_token, span) = self.peek_token()?;
-            if next_token == Token::Newline {
-                self.next_token()?;
-                continue;
-            }
 
             if next_token == Token::RightBracket {
                 self.next_token()?; // consume ]
@@ -181,12 +183,10 @@ impl<'a> OptimizedParserV3<'a> {
             }
 
             if !first {
-                // Expect comma
+                // Expect a comma, or a line break under newline_as_comma
                 if next_token == Token::Comma {
                     self.next_token()?; // consume comma
-                } else if self.options.newline_as_comma && next_token == Token::Newline {
-                    self.next_token()?; // consume newline
-                } else {
+                } else if !(self.options.newline_as_comma && newline) {
                     return Err(Error::Expected {
                         expected: "comma or ]".to_string(),
                         found: format!("{next_token:?}"),
@@ -195,7 +195,7 @@ impl<'a> OptimizedParserV3<'a> {
                 }
             }
 
-            let (value_token, value_span) = self.next_token()?;
+            let (value_token, value_span) = self.next_token_after_newlines()?;
             let value = self.parse_value((value_token, value_span))?;
             elements.push(value);
             
@@ -224,12 +224,8 @@ impl<'a> OptimizedParserV3<'a> {
         let mut first = true;
 
         loop {
-            // Skip newlines
+            let newline = self.skip_newlines()?;
             let (next_token, span) = self.peek_token()?;
-            if next_token == Token::Newline {
-                self.next_token()?;
-                continue;
-            }
 
             if next_token == Token::RightBrace {
                 self.next_token()?; // consume }
@@ -237,12 +233,10 @@ impl<'a> OptimizedParserV3<'a> {
             }
 
             if !first {
-                // Expect comma
+                // Expect a comma, or a line break under newline_as_comma
                 if next_token == Token::Comma {
                     self.next_token()?; // consume comma
-                } else if self.options.newline_as_comma && next_token == Token::Newline {
-                    self.next_token()?; // consume newline
-                } else {
+                } else if !(self.options.newline_as_comma && newline) {
                     return Err(Error::Expected {
                         expected: "comma or }".to_string(),
                         found: format!("{next_token:?}"),
@@ -252,7 +246,7 @@ impl<'a> OptimizedParserV3<'a> {
             }
 
             // Parse key
-            let (key_token, key_span) = self.next_token()?;
+            let (key_token, key_span) = self.next_token_after_newlines()?;
             let key = match key_token {
                 Token::String => self.parse_string_key(key_span)?,
                 Token::UnquotedString if self.options.allow_unquoted_keys => {
@@ -269,7 +263,7 @@ impl<'a> OptimizedParserV3<'a> {
             };
 
             // Expect colon
-            let (colon_token, colon_span) = self.next_token()?;
+            let (colon_token, colon_span) = self.next_token_after_newlines()?;
             if colon_token != Token::Colon {
                 return Err(Error::Expected {
                     expected: "colon".to_string(),
@@ -279,7 +273,7 @@ impl<'a> OptimizedParserV3<'a> {
             }
 
             // Parse value
-            let (value_token, value_span) = self.next_token()?;
+            let (value_token, value_span) = self.next_token_after_newlines()?;
             let value = self.parse_value((value_token, value_span))?;
             
             object.insert(key, value);
@@ -316,6 +310,15 @@ impl<'a> OptimizedParserV3<'a> {
     }
 }
 
+impl JsonParser for OptimizedParserV3<'_> {
+    fn parse_with(&mut self, input: &str, options: &ParserOptions) -> Result<Value> {
+        let mut parser = OptimizedParserV3::new(input, options.clone());
+        let result = parser.parse();
+        self.stats = parser.stats;
+        result
+    }
+}
+
 /// Parse JSON using the advanced optimized parser
 pub fn parse_optimized_v3(input: &str) -> Result<Value> {
     let mut parser = OptimizedParserV3::new(input, ParserOptions::default());
@@ -0,0 +1,150 @@
+// this_file: crates/core/src/parser/pairs.rs
+
+//! Parsing a top-level object into its key-value pairs, repeats included.
+
+use crate::ast::Value;
+use crate::error::{Error, Result};
+use crate::parser::{Parser, ParserOptions};
+
+/// Members of the top-level object, recorded as [`parse_to_pairs`] parses.
+pub(crate) struct TopLevelPairs {
+    /// Parser depth at which the top-level object's members are inserted:
+    /// one inside braces, none for an implicit top-level object.
+    pub(super) depth: usize,
+    pub(super) pairs: Vec<(String, Value)>,
+}
+
+/// Parses a document holding an object, returning every key-value pair of
+/// that object in document order.
+///
+/// Repeated keys are all kept, whatever
+/// [`duplicate_keys`](ParserOptions::duplicate_keys) says; objects nested
+/// in the values still follow it. Both braced and implicit top-level objects
+/// are read, as [`parse_with_options`](crate::parse_with_options) would.
+///
+/// # Errors
+///
+/// Returns the first parse error, or [`Error::Expected`] at the start of the
+/// document if it holds anything other than an object.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{parse_to_pairs, ParserOptions};
+///
+/// let input = r#"{"Set-Cookie": "a=1", "Host": "example.com", "Set-Cookie": "b=2"}"#;
+/// let pairs = parse_to_pairs(input, ParserOptions::default()).unwrap();
+/// let cookies: Vec<_> = pairs
+///     .iter()
+///     .filter(|(key, _)| key == "Set-Cookie")
+///     .map(|(_, value)| value.as_str().unwrap())
+///     .collect();
+/// assert_eq!(cookies, ["a=1", "b=2"]);
+/// ```
+pub fn parse_to_pairs(input: &str, options: ParserOptions) -> Result<Vec<(String, Value)>> {
+    let mut parser = Parser::new(input, options);
+    parser.top_level_pairs = Some(TopLevelPairs {
+        depth: 1,
+        pairs: Vec::new(),
+    });
+    let found = match parser.parse()? {
+        Value::Object(_) => {
+            return Ok(parser
+                .top_level_pairs
+                .map(|top_level| top_level.pairs)
+                .unwrap_or_default())
+        }
+        Value::Array(_) => "array",
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+    };
+    let start = input.len()
+        - input
+            .trim_start_matches(|c: char| c.is_whitespace() || c == '\u{feff}')
+            .len();
+    Err(Error::Expected {
+        expected: "object".to_string(),
+        found: found.to_string(),
+        position: start,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_with_options, DuplicateKeyPolicy};
+
+    fn pairs(input: &str) -> Vec<(String, Value)> {
+        parse_to_pairs(input, ParserOptions::default()).unwrap()
+    }
+
+    fn pair(key: &str, value: &str) -> (String, Value) {
+        (key.to_string(), crate::parse(value).unwrap())
+    }
+
+    #[test]
+    fn test_every_pair_in_document_order() {
+        let expected = [
+            pair("a", "1"),
+            pair("b", "{\"c\": 2}"),
+            pair("a", "[3]"),
+            pair("d", "null"),
+            pair("a", "'four'"),
+        ];
+        for input in [
+            "{\"a\": 1, \"b\": {\"c\": 2}, \"a\": [3], \"d\": null, \"a\": \"four\"}",
+            "// header dump\na: 1\nb: {c: 2}\na: [3]\nd: null\na: 'four'\n",
+        ] {
+            assert_eq!(pairs(input), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_nested_objects_follow_the_policy() {
+        let options = ParserOptions {
+            duplicate_keys: DuplicateKeyPolicy::FirstWins,
+            ..Default::default()
+        };
+        let input = "{\"a\": {\"x\": 1, \"x\": 2}, \"a\": {\"x\": 3}}";
+        assert_eq!(
+            parse_to_pairs(input, options.clone()).unwrap(),
+            [pair("a", "{x: 1}"), pair("a", "{x: 3}")]
+        );
+
+        let error = ParserOptions {
+            duplicate_keys: DuplicateKeyPolicy::Error,
+            ..Default::default()
+        };
+        assert_eq!(pairs("a: 1\na: 2").len(), 2);
+        assert!(parse_to_pairs("a: 1\na: 2", error.clone()).is_ok());
+        assert_eq!(
+            parse_to_pairs("a: {b: 1, b: 2}", error.clone()).unwrap_err(),
+            parse_with_options("a: {b: 1, b: 2}", error).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_top_level_must_be_an_object() {
+        for (input, found) in [
+            ("[{\"a\": 1}]", "array"),
+            ("  \"a\"", "string"),
+            ("1", "number"),
+        ] {
+            match parse_to_pairs(input, ParserOptions::default()).unwrap_err() {
+                Error::Expected {
+                    expected,
+                    found: actual,
+                    position,
+                } => {
+                    assert_eq!(expected, "object");
+                    assert_eq!(actual, found);
+                    assert_eq!(position, input.len() - input.trim_start().len());
+                }
+                error => panic!("{error:?}"),
+            }
+        }
+        assert!(parse_to_pairs("{\"a\": ", ParserOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+// this_file: src/parser/path.rs
+
+//! Tracking the parser's location in the document for error reporting.
+
+use crate::ast::{JsonPath, PathSegment, Value};
+use crate::error::{Error, Result};
+use crate::optimization::unescape_string_optimized;
+use crate::parser::Parser;
+
+/// One step from the document root to the value being parsed.
+///
+/// Keys borrow their source text and are only decoded when a path is
+/// reported, so tracking allocates nothing while parsing succeeds.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum PathEntry<'a> {
+    /// Object member, holding the key's source text including any quotes.
+    Key(&'a str),
+    /// Array element.
+    Index(usize),
+}
+
+impl PathEntry<'_> {
+    fn to_segment(self) -> PathSegment {
+        match self {
+            PathEntry::Key(text) => PathSegment::Key(decode_key(text)),
+            PathEntry::Index(index) => PathSegment::Index(index),
+        }
+    }
+}
+
+fn decode_key(text: &str) -> String {
+    let quoted = text.len() >= 2 && (text.starts_with('"') || text.starts_with('\''));
+    if quoted {
+        let content = &text[1..text.len() - 1];
+        unescape_string_optimized(content).unwrap_or_else(|_| content.to_string())
+    } else {
+        text.to_string()
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Parses the value found at `entry` below the current location.
+    ///
+    /// While [tracking paths](Self::tracks_paths), `entry` stays on the
+    /// path stack if parsing fails.
+    pub(super) fn parse_value_at(&mut self, entry: PathEntry<'a>) -> Result<Value> {
+        if let PathEntry::Index(index) = entry {
+            self.check_array_len(index)?;
+        }
+        let value = if self.tracks_paths() {
+            self.path.push(entry);
+            let value = self.parse_value()?;
+            self.path.pop();
+            value
+        } else {
+            self.parse_value()?
+        };
+        if let Some(budget) = &mut self.budget {
+            budget.charge_value(&value, self.state.previous_end)?;
+        }
+        Ok(value)
+    }
+
+    /// Runs `lex`, which reads on to the first token of the value at
+    /// `entry`, with `entry` on the path, so that a lexical error there is
+    /// reported below it. As with [`parse_value_at`](Self::parse_value_at),
+    /// `entry` stays on the path stack if `lex` fails.
+    pub(super) fn lex_into(
+        &mut self,
+        entry: PathEntry<'a>,
+        lex: impl FnOnce(&mut Self) -> Result<()>,
+    ) -> Result<()> {
+        if !self.tracks_paths() {
+            return lex(self);
+        }
+        self.path.push(entry);
+        lex(self)?;
+        self.path.pop();
+        Ok(())
+    }
+
+    /// Returns the path from the document root to the value being parsed.
+    ///
+    /// Always the root unless [`ParserOptions::track_paths`](super::ParserOptions::track_paths)
+    /// is enabled or [`ParserOptions::limits`](super::ParserOptions::limits)
+    /// are set.
+    pub fn current_path(&self) -> JsonPath {
+        let mut path = JsonPath::new();
+        for entry in &self.path {
+            path.push(entry.to_segment());
+        }
+        path
+    }
+
+    /// Annotates `error` with the path at which it occurred.
+    pub(super) fn error_at_path(&self, error: Error) -> Error {
+        Error::AtPath {
+            path: self.current_path(),
+            source: Box::new(error),
+        }
+    }
+
+    /// Whether to keep the path stack: for every error under
+    /// [`track_paths`](super::ParserOptions::track_paths), and for the
+    /// errors of [`limits`](super::ParserOptions::limits) when any is set.
+    pub(super) fn tracks_paths(&self) -> bool {
+        self.options.track_paths || self.options.limits.is_limited()
+    }
+
+    /// Annotates `error`, which ended the parse, with its path if the
+    /// options ask for it.
+    pub(super) fn annotate_error(&self, error: Error) -> Error {
+        let limit =
+            matches!(error, Error::LimitExceeded { .. }) && self.options.limits.is_limited();
+        if self.options.track_paths || limit {
+            self.error_at_path(error)
+        } else {
+            error
+        }
+    }
+
+    /// Enforces [`max_array_len`](super::Limits::max_array_len) on the array
+    /// element at `index`, whose first token is current. The index stays on
+    /// the path if it is past the limit.
+    pub(super) fn check_array_len(&mut self, index: usize) -> Result<()> {
+        match self.options.limits.max_array_len {
+            Some(max) if index >= max => {
+                if self.tracks_paths() {
+                    self.path.push(PathEntry::Index(index));
+                }
+                Err(Error::LimitExceeded {
+                    what: "array length".to_string(),
+                    limit: max,
+                    position: self.token_position(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+// this_file: crates/core/src/parser/pool.rs
+
+//! A fixed number of parsers shared between threads.
+
+use crate::ast::Value;
+use crate::error::Result;
+use crate::parser::backend::AutoParser;
+use crate::parser::{JsonParser, ParserOptions};
+use std::sync::{Condvar, Mutex, PoisonError};
+
+/// Counters describing how a [`ParserPool`] has been used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParserPoolStats {
+    /// Parsers built so far; never more than the size of the pool
+    pub parsers_created: usize,
+    /// Calls to [`ParserPool::parse`] that have taken a parser
+    pub parses: u64,
+    /// Parses that took a parser an earlier parse had returned, with the
+    /// buffers that parse left
+    pub reuses: u64,
+    /// Parses that had to wait for a parser because all were in use
+    pub waits: u64,
+    /// Parsers currently waiting in the pool
+    pub idle: usize,
+    /// Bytes of parse buffers the idle parsers keep for their next parse:
+    /// the iterative parser's stack, and the hybrid parser's preallocation
+    /// hints
+    pub buffer_bytes: usize,
+}
+
+/// Parsers built and not in use, with the counters behind
+/// [`ParserPoolStats`].
+#[derive(Default)]
+struct PoolState {
+    idle: Vec<AutoParser>,
+    stats: ParserPoolStats,
+}
+
+/// A pool of up to `size` parsers sharing one set of options, for parsing
+/// from several threads at once.
+///
+/// Each call to [`parse`](Self::parse) takes an idle parser, building one if
+/// fewer than `size` exist, and returns it when done, so that concurrent
+/// requests reuse parsers, and the buffers they grew, rather than each
+/// allocating their own. When all parsers are in use, callers wait for one
+/// to be returned.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{ParserOptions, ParserPool};
+///
+/// let pool = ParserPool::new(4, ParserOptions::default());
+/// std::thread::scope(|scope| {
+///     for i in 0..8 {
+///         let pool = &pool;
+///         scope.spawn(move || {
+///             let value = pool.parse(&format!("{{id: {i}}}")).unwrap();
+///             assert_eq!(value["id"].as_i64(), Some(i));
+///         });
+///     }
+/// });
+/// assert!(pool.stats().parsers_created <= 4);
+/// ```
+pub struct ParserPool {
+    options: ParserOptions,
+    size: usize,
+    state: Mutex<PoolState>,
+    returned: Condvar,
+}
+
+impl ParserPool {
+    /// Creates a pool of at most `size` parsers, each parsing with `options`.
+    ///
+    /// A `size` of zero is taken as one.
+    pub fn new(size: usize, options: ParserOptions) -> Self {
+        ParserPool {
+            options,
+            size: size.max(1),
+            state: Mutex::new(PoolState::default()),
+            returned: Condvar::new(),
+        }
+    }
+
+    /// The most parsers this pool builds.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The options every parser in the pool parses with.
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// Parses `input` with a parser from the pool.
+    pub fn parse(&self, input: &str) -> Result<Value> {
+        let mut lease = self.take();
+        lease.parser().parse_with(input, &self.options)
+    }
+
+    /// Counters describing how the pool has been used.
+    pub fn stats(&self) -> ParserPoolStats {
+        let state = self.lock();
+        ParserPoolStats {
+            idle: state.idle.len(),
+            buffer_bytes: state
+                .idle
+                .iter()
+                .map(|parser| parser.buffers().capacity_bytes())
+                .sum(),
+            ..state.stats
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Takes an idle parser, builds one, or waits for one to be returned.
+    fn take(&self) -> Lease<'_> {
+        let mut state = self.lock();
+        let mut waited = false;
+        let parser = loop {
+            if let Some(parser) = state.idle.pop() {
+                state.stats.reuses += 1;
+                break parser;
+            }
+            if state.stats.parsers_created < self.size {
+                state.stats.parsers_created += 1;
+                break AutoParser::new(self.options.backend);
+            }
+            waited = true;
+            state = self
+                .returned
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        };
+        state.stats.parses += 1;
+        if waited {
+            state.stats.waits += 1;
+        }
+        Lease {
+            pool: self,
+            parser: Some(parser),
+        }
+    }
+}
+
+impl std::fmt::Debug for ParserPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParserPool")
+            .field("options", &self.options)
+            .field("size", &self.size)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// A parser taken from a [`ParserPool`], returned to it when dropped, even
+/// if parsing panics.
+struct Lease<'a> {
+    pool: &'a ParserPool,
+    parser: Option<AutoParser>,
+}
+
+impl Lease<'_> {
+    fn parser(&mut self) -> &mut AutoParser {
+        self.parser.as_mut().expect("parser is only taken on drop")
+    }
+}
+
+impl Drop for Lease<'_> {
+    fn drop(&mut self) {
+        if let Some(mut parser) = self.parser.take() {
+            // Warnings are not handed out by the pool, so don't keep them
+            parser.take_warnings();
+            parser.take_compat_warnings();
+            self.pool.lock().idle.push(parser);
+            self.pool.returned.notify_one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builds_parsers_only_as_needed() {
+        let pool = ParserPool::new(0, ParserOptions::default());
+        assert_eq!(pool.size(), 1);
+        for _ in 0..3 {
+            assert!(pool.parse("[1, 2]").is_ok());
+        }
+        assert!(pool.parse("[1,").is_err());
+        assert_eq!(
+            pool.stats(),
+            ParserPoolStats {
+                parsers_created: 1,
+                parses: 4,
+                reuses: 3,
+                waits: 0,
+                idle: 1,
+                buffer_bytes: 0,
+            }
+        );
+    }
+}
@@ -6,10 +6,11 @@
 //! that is easy to understand and maintain. It serves as an alternative to the
 //! main parser for educational purposes and as a reference implementation.
 
-use crate::ast::{Number, Token, Value};
+use crate::ast::{Token, Value};
 use crate::error::{Error, Result, Span};
 use crate::lexer::{JsonLexer, Lexer};
-use crate::parser::ParserOptions;
+use crate::parser::number::read_number;
+use crate::parser::{is_blank, JsonParser, ParserOptions};
 use rustc_hash::FxHashMap;
 
 /// A clean recursive descent parser for JSON.
@@ -18,6 +19,7 @@ use rustc_hash::FxHashMap;
 /// per grammar rule. It's designed to be readable and maintainable rather than
 /// optimized for maximum performance.
 pub struct RecursiveDescentParser<'a> {
+    input: &'a str,
     lexer: Lexer<'a>,
     current_token: Option<(Token, Span)>,
     options: ParserOptions,
@@ -28,6 +30,7 @@ impl<'a> RecursiveDescentParser<'a> {
     /// Creates a new recursive descent parser.
     pub fn new(input: &'a str, options: ParserOptions) -> Self {
         Self {
+            input,
             lexer: Lexer::new(input),
             current_token: None,
             options,
@@ -37,13 +40,19 @@ impl<'a> RecursiveDescentParser<'a> {
 
     /// Parses the input and returns the parsed value.
     pub fn parse(&mut self) -> Result<Value> {
+        if is_blank(self.input, &self.options) {
+            return self.options.empty_input_behavior.value(self.input.len());
+        }
         self.advance()?;
+        self.skip_newlines()?;
         let value = self.parse_value()?;
+        self.skip_newlines()?;
         self.expect_eof()?;
         Ok(value)
     }
 
-    /// Advances to the next token, skipping comments if allowed.
+    /// Advances to the next token, skipping comments if allowed and line
+    /// breaks unless they separate values.
     fn advance(&mut self) -> Result<()> {
         loop {
             let (token, span) = self.lexer.next_token_with_span()?;
@@ -57,12 +66,22 @@ impl<'a> RecursiveDescentParser<'a> {
                         return Err(Error::Custom("Comments are not allowed".to_string()));
                     }
                 }
+                Token::Newline if !self.options.newline_as_comma => continue,
                 _ => break,
             }
         }
         Ok(())
     }
 
+    /// Skips line breaks where they cannot separate values, which
+    /// [`advance`](Self::advance) only keeps under `newline_as_comma`.
+    fn skip_newlines(&mut self) -> Result<()> {
+        while matches!(self.peek(), Some(Token::Newline)) {
+            self.advance()?;
+        }
+        Ok(())
+    }
+
     /// Gets the current token without consuming it.
     fn peek(&self) -> Option<&Token> {
         self.current_token.as_ref().map(|(token, _)| token)
@@ -163,6 +182,7 @@ impl<'a> RecursiveDescentParser<'a> {
         self.depth += 1;
 
         self.expect(Token::LeftBrace)?;
+        self.skip_newlines()?;
         let mut object = FxHashMap::default();
 
         // Handle empty object
@@ -175,9 +195,11 @@ impl<'a> RecursiveDescentParser<'a> {
         loop {
             // Parse key
             let key = self.parse_object_key()?;
+            self.skip_newlines()?;
 
             // Expect colon
             self.expect(Token::Colon)?;
+            self.skip_newlines()?;
 
             // Parse value
             let value = self.parse_value()?;
@@ -187,6 +209,7 @@ impl<'a> RecursiveDescentParser<'a> {
             match self.peek() {
                 Some(Token::Comma) => {
                     self.advance()?;
+                    self.skip_newlines()?;
 
                     // Handle trailing comma
                     if let Some(Token::RightBrace) = self.peek() {
@@ -200,6 +223,7 @@ impl<'a> RecursiveDescentParser<'a> {
                 Some(Token::RightBrace) => break,
                 Some(Token::Newline) if self.options.newline_as_comma => {
                     self.advance()?;
+                    self.skip_newlines()?;
 
                     // Handle trailing newline
                     if let Some(Token::RightBrace) = self.peek() {
@@ -277,6 +301,7 @@ impl<'a> RecursiveDescentParser<'a> {
         self.depth += 1;
 
         self.expect(Token::LeftBracket)?;
+        self.skip_newlines()?;
         let mut array = Vec::new();
 
         // Handle empty array
@@ -294,6 +319,7 @@ impl<'a> RecursiveDescentParser<'a> {
             match self.peek() {
                 Some(Token::Comma) => {
                     self.advance()?;
+                    self.skip_newlines()?;
 
                     // Handle trailing comma
                     if let Some(Token::RightBracket) = self.peek() {
@@ -307,6 +333,7 @@ impl<'a> RecursiveDescentParser<'a> {
                 Some(Token::RightBracket) => break,
                 Some(Token::Newline) if self.options.newline_as_comma => {
                     self.advance()?;
+                    self.skip_newlines()?;
 
                     // Handle trailing newline
                     if let Some(Token::RightBracket) = self.peek() {
@@ -431,20 +458,9 @@ impl<'a> RecursiveDescentParser<'a> {
     fn parse_number(&mut self) -> Result<Value> {
         if let Some((Token::Number, span)) = self.current_token {
             let text = self.lexer.span_text(&span);
-
-            // Try to parse as integer first
-            if let Ok(int_val) = text.parse::<i64>() {
-                self.advance()?;
-                return Ok(Value::Number(Number::Integer(int_val)));
-            }
-
-            // Try to parse as float
-            if let Ok(float_val) = text.parse::<f64>() {
-                self.advance()?;
-                return Ok(Value::Number(Number::Float(float_val)));
-            }
-
-            Err(Error::InvalidNumber(span.start))
+            let number = read_number(text, span.start, &self.options)?;
+            self.advance()?;
+            Ok(Value::Number(number))
         } else {
             Err(Error::Expected {
                 expected: "number".to_string(),
@@ -473,6 +489,12 @@ impl<'a> RecursiveDescentParser<'a> {
     }
 }
 
+impl JsonParser for RecursiveDescentParser<'_> {
+    fn parse_with(&mut self, input: &str, options: &ParserOptions) -> Result<Value> {
+        RecursiveDescentParser::new(input, options.clone()).parse()
+    }
+}
+
 /// Parses JSON using the recursive descent parser.
 ///
 /// This function provides a clean, textbook-style recursive descent parser
@@ -502,6 +524,7 @@ pub fn parse_recursive(input: &str, options: ParserOptions) -> Result<Value> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ast::Number;
 
     #[test]
     fn test_parse_null() {
@@ -12,6 +12,8 @@ pub struct ParserState {
     pub depth: usize,
     /// The span of the current token
     pub span: Span,
+    /// End offset of the token before the current one
+    pub previous_end: usize,
 }
 
 impl ParserState {
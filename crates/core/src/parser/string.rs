@@ -7,7 +7,7 @@ use crate::parser::ParserOptions;
 
 /// Optimized helper function for parsing string tokens into Values
 #[inline]
-pub(super) fn parse_string_token(
+pub(crate) fn parse_string_token(
     original_input: &str,
     span: Span,
     options: &ParserOptions,
@@ -0,0 +1,259 @@
+// this_file: src/parser/warnings.rs
+
+//! Recording forgiving transformations as warnings, and constructs outside
+//! strict JSON as compatibility warnings.
+
+use crate::ast::{Token, Value};
+use crate::error::{CompatKind, CompatWarning, Span, Warning, WarningKind};
+use crate::format::write_string;
+use crate::parser::number::is_strict_number;
+use crate::parser::Parser;
+
+impl<'a> Parser<'a> {
+    /// Returns the warnings collected by the last call to
+    /// [`parse`](Self::parse).
+    ///
+    /// Always empty unless
+    /// [`ParserOptions::collect_warnings`](super::ParserOptions::collect_warnings)
+    /// is enabled.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Takes the warnings collected by the last call to
+    /// [`parse`](Self::parse), leaving none behind.
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Returns the compatibility warnings collected by the last call to
+    /// [`parse`](Self::parse), in the order the parser met them.
+    ///
+    /// Always empty unless
+    /// [`ParserOptions::collect_compat_warnings`](super::ParserOptions::collect_compat_warnings)
+    /// is enabled.
+    pub fn compat_warnings(&self) -> &[CompatWarning] {
+        &self.compat_warnings
+    }
+
+    /// Takes the compatibility warnings collected by the last call to
+    /// [`parse`](Self::parse), leaving none behind.
+    pub fn take_compat_warnings(&mut self) -> Vec<CompatWarning> {
+        std::mem::take(&mut self.compat_warnings)
+    }
+
+    fn warn(&mut self, kind: WarningKind, span: Span, message: String) {
+        if self.options.collect_warnings {
+            self.warnings.push(Warning {
+                kind,
+                message,
+                span,
+            });
+        }
+    }
+
+    /// Records a construct outside strict JSON at `span`; `replacement`
+    /// turns the text there into strict JSON.
+    fn compat(&mut self, kind: CompatKind, span: Span, replacement: impl FnOnce(&str) -> String) {
+        if self.options.collect_compat_warnings {
+            let replacement = replacement(&self.original_input[span.start..span.end]);
+            self.compat_warnings.push(CompatWarning {
+                kind,
+                span,
+                replacement,
+            });
+        }
+    }
+
+    /// Records the comments between the previous token and `next`, which the
+    /// lexer skips without producing tokens.
+    pub(super) fn note_comments(&mut self, next: Span) {
+        if !self.options.collect_compat_warnings {
+            return;
+        }
+        let bytes = self.original_input.as_bytes();
+        let end = next.start.min(bytes.len());
+        let mut i = self.state.previous_end.min(end);
+        while i < end {
+            let comment_end = match (bytes[i], bytes.get(i + 1)) {
+                (b'#', _) | (b'/', Some(b'/')) => bytes[i..end]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(end, |offset| i + offset),
+                (b'/', Some(b'*')) => {
+                    block_comment_end(&bytes[..end], i, self.options.allow_nested_block_comments)
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            };
+            self.compat(CompatKind::Comment, Span::new(i, comment_end), |_| {
+                String::new()
+            });
+            i = comment_end;
+        }
+    }
+
+    /// Records a comma at `comma` that closes its container.
+    pub(super) fn note_trailing_comma(&mut self, comma: Span) {
+        self.compat(CompatKind::TrailingComma, comma, |_| String::new());
+    }
+
+    /// Records an empty array element, read as `null`, ended by the
+    /// separator at `separator`.
+    pub(super) fn note_missing_value(&mut self, separator: Span) {
+        self.compat(CompatKind::MissingValue, separator, |_| "null,".to_string());
+    }
+
+    /// Records an object key spelled by a token other than a double-quoted
+    /// string.
+    pub(super) fn note_key(&mut self, token: Token, span: Span, key: &str) {
+        match token {
+            Token::UnquotedString | Token::Number => {
+                self.compat(CompatKind::UnquotedKey, span, |_| quoted(key))
+            }
+            Token::String => self.note_quotes(span, key),
+            _ => {}
+        }
+    }
+
+    /// Records `value`, read from the string token at `span`, if the
+    /// token uses single quotes.
+    pub(super) fn note_string(&mut self, span: Span, value: &Value) {
+        if let Value::String(s) = value {
+            self.note_quotes(span, s);
+        }
+    }
+
+    fn note_quotes(&mut self, span: Span, content: &str) {
+        if self.original_input[span.start..].starts_with('\'') {
+            self.compat(CompatKind::SingleQuotedString, span, |_| quoted(content));
+        }
+    }
+
+    /// Records an unquoted string value at `span`.
+    pub(super) fn note_unquoted_string(&mut self, span: Span) {
+        self.compat(CompatKind::UnquotedString, span, quoted);
+    }
+
+    /// Records `value`, read from the number token at `span`, if strict
+    /// JSON spells it differently.
+    pub(super) fn note_number(&mut self, span: Span, value: &Value) {
+        if !is_strict_number(&self.original_input[span.start..span.end]) {
+            self.compat(CompatKind::NonStandardNumber, span, |_| value.to_string());
+        }
+    }
+
+    /// Records a number at `span` too large for an `f64`, read as infinity.
+    pub(super) fn note_overflow(&mut self, span: Span) {
+        self.warn(
+            WarningKind::NumberOverflow,
+            span,
+            "number out of range read as infinity".to_string(),
+        );
+    }
+
+    /// Records the current token following the previous value without a
+    /// comma: across a line break acting as one, or with only spaces
+    /// between them.
+    pub(super) fn note_missing_comma(&mut self) {
+        let wanted = self.options.collect_warnings || self.options.collect_compat_warnings;
+        if !wanted {
+            return;
+        }
+        let Some((_, span)) = self.current_token else {
+            return;
+        };
+        let gap_start = self.state.previous_end.min(span.start);
+        let gap = &self.original_input[gap_start..span.start];
+        match gap.find('\n') {
+            Some(offset) if self.options.newline_as_comma => {
+                let newline = gap_start + offset;
+                let span = Span::new(newline, newline + 1);
+                self.warn(
+                    WarningKind::NewlineAsComma,
+                    span,
+                    "line break treated as a comma".to_string(),
+                );
+                self.compat(CompatKind::NewlineAsComma, span, |_| ",".to_string());
+            }
+            _ => {
+                let recorded = self.compat_warnings.len();
+                let span = Span::new(gap_start, gap_start);
+                self.compat(CompatKind::MissingComma, span, |_| ",".to_string());
+                // Comments in the gap are already recorded; keep to source order
+                let comments = self.compat_warnings[..recorded]
+                    .iter()
+                    .rev()
+                    .take_while(|warning| warning.span.start > gap_start)
+                    .count();
+                let added = self.compat_warnings.len() - recorded;
+                self.compat_warnings[recorded - comments..].rotate_right(added);
+            }
+        }
+    }
+
+    /// Records top-level members starting at `start` being wrapped in an
+    /// object, ending with the last token consumed.
+    pub(super) fn note_implicit_object(&mut self, start: usize) {
+        let span = Span::new(start, self.state.previous_end.max(start));
+        self.warn(
+            WarningKind::ImplicitObject,
+            span,
+            "top-level members wrapped in an implicit object".to_string(),
+        );
+        self.compat(CompatKind::ImplicitTopLevel, span, |text| {
+            format!("{{{text}}}")
+        });
+    }
+
+    /// Wraps top-level values starting at `start` in an array, recording
+    /// the transformation.
+    pub(super) fn implicit_array(&mut self, start: usize, array: Vec<Value>) -> Value {
+        let span = Span::new(start, self.state.previous_end.max(start));
+        self.warn(
+            WarningKind::ImplicitArray,
+            span,
+            format!(
+                "{} top-level values wrapped in an implicit array",
+                array.len()
+            ),
+        );
+        self.compat(CompatKind::ImplicitTopLevel, span, |text| {
+            format!("[{text}]")
+        });
+        Value::Array(array)
+    }
+}
+
+/// `s` as a JSON string literal.
+fn quoted(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    write_string(&mut out, s);
+    out
+}
+
+/// The end of the block comment opening at `start`, or the end of `bytes`
+/// if it does not close.
+fn block_comment_end(bytes: &[u8], start: usize, nested: bool) -> usize {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1]) {
+            (b'/', b'*') if depth == 0 || nested => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', b'/') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
@@ -10,6 +10,7 @@
 
 use crate::ast::Value;
 use crate::error::{Error, Result};
+use crate::parser::{parse_with_options, ParserOptions};
 use rustc_hash::FxHashMap;
 use std::any::Any;
 use std::sync::{Arc, RwLock};
@@ -39,14 +40,14 @@ pub trait ParserPlugin: Send + Sync {
         Ok(())
     }
 
-    /// Called for each key in an object
+    /// Called for each key in an object, with the path of the member it names
     fn on_object_key(&mut self, _key: &str, _path: &str) -> Result<()> {
         Ok(())
     }
 
-    /// Called for each string value
-    fn on_string(&mut self, value: &str, _path: &str) -> Result<String> {
-        Ok(value.to_string())
+    /// Called for each string value; may replace it with a value of any type
+    fn on_string(&mut self, value: &str, _path: &str) -> Result<Value> {
+        Ok(Value::String(value.to_string()))
     }
 
     /// Called for each number value
@@ -173,6 +174,19 @@ impl PluginRegistry {
         Ok(())
     }
 
+    /// Pass a string value through each plugin's `on_string` in registration
+    /// order, stopping once a plugin turns it into a non-string value
+    pub fn on_string(&mut self, value: &str, path: &str) -> Result<Value> {
+        let mut current = Value::String(value.to_string());
+        for plugin in &mut self.plugins {
+            match &current {
+                Value::String(s) => current = plugin.on_string(s, path)?,
+                _ => break,
+            }
+        }
+        Ok(current)
+    }
+
     /// Validate a value through all plugins
     pub fn validate(&self, value: &Value, path: &str) -> Result<()> {
         for plugin in &self.plugins {
@@ -180,6 +194,81 @@ impl PluginRegistry {
         }
         Ok(())
     }
+
+    /// Run the per-value hooks over `value` and everything inside it
+    fn visit(&mut self, value: &mut Value, path: &str) -> Result<()> {
+        match value {
+            Value::String(s) => *value = self.on_string(s, path)?,
+            Value::Array(arr) => {
+                for (i, item) in arr.iter_mut().enumerate() {
+                    self.visit(item, &format!("{path}[{i}]"))?;
+                }
+            }
+            Value::Object(obj) => {
+                let mut entries: Vec<_> = obj.iter_mut().collect();
+                entries.sort_unstable_by_key(|(key, _)| *key);
+                for (key, item) in entries {
+                    let path = format!("{path}.{key}");
+                    for plugin in &mut self.plugins {
+                        plugin.on_object_key(key, &path)?;
+                    }
+                    self.visit(item, &path)?;
+                }
+            }
+            _ => {}
+        }
+        self.validate(value, path)
+    }
+}
+
+/// Parses `input` with `options`, then runs the plugins in `registry` over
+/// the result.
+///
+/// Every plugin's `on_parse_start` sees the input first. Once the document
+/// has parsed, each object key goes to `on_object_key` with the path of the
+/// member it names, `$.a.b` for `b` in `{"a": {"b": 1}}`, each string to
+/// [`PluginRegistry::on_string`], which may replace it with a value of any
+/// type, and each value to `validate` after its contents. Last,
+/// `on_parse_end` sees the finished value. Object members are visited in
+/// sorted key order, the same on every run. Paths look like
+/// `$.events[2].created_at`. Numbers are not passed to `on_number`, as their
+/// source text is gone by then.
+///
+/// # Errors
+///
+/// Returns the parse error, or the first error a plugin returns.
+///
+/// # Examples
+///
+/// `DateTimePlugin` needs the `datetime` feature.
+///
+/// ```
+/// # #[cfg(feature = "datetime")]
+/// # {
+/// use vexy_json_core::plugin::{parse_with_plugins, DateTimeOutput, DateTimePlugin, PluginRegistry};
+/// use vexy_json_core::{parse, ParserOptions};
+///
+/// let mut registry = PluginRegistry::new();
+/// registry
+///     .register(Box::new(DateTimePlugin::new().with_output(DateTimeOutput::Tagged)))
+///     .unwrap();
+///
+/// let value = parse_with_plugins(r#"{"at": "2024-01-02T03:04:05Z"}"#, ParserOptions::default(), &mut registry).unwrap();
+/// assert_eq!(value, parse(r#"{"at": {"$date": "2024-01-02T03:04:05Z"}}"#).unwrap());
+/// # }
+/// ```
+pub fn parse_with_plugins(
+    input: &str,
+    options: ParserOptions,
+    registry: &mut PluginRegistry,
+) -> Result<Value> {
+    registry.execute_hook(PluginHook::BeforeParse, |plugin| {
+        plugin.on_parse_start(input)
+    })?;
+    let mut value = parse_with_options(input, options)?;
+    registry.visit(&mut value, "$")?;
+    registry.execute_hook(PluginHook::AfterParse, |plugin| plugin.on_parse_end(&value))?;
+    Ok(value)
 }
 
 impl Default for PluginRegistry {
@@ -199,9 +288,9 @@ pub fn create_shared_registry() -> SharedPluginRegistry {
 // Re-export plugin implementations
 pub mod plugins;
 
-pub use plugins::{
-    CommentPreservationPlugin, CustomNumberFormatPlugin, DateTimePlugin, SchemaValidationPlugin,
-};
+pub use plugins::{CommentPreservationPlugin, CustomNumberFormatPlugin, SchemaValidationPlugin};
+#[cfg(feature = "datetime")]
+pub use plugins::{DateTimeOutput, DateTimePlugin};
 
 #[cfg(test)]
 mod tests {
@@ -263,6 +352,87 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// Records what it is called with, rejecting negative numbers
+    #[derive(Default)]
+    struct RecordingPlugin {
+        calls: Vec<String>,
+    }
+
+    impl ParserPlugin for RecordingPlugin {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn on_parse_start(&mut self, input: &str) -> Result<()> {
+            self.calls.push(format!("start {input}"));
+            Ok(())
+        }
+
+        fn on_parse_end(&mut self, value: &Value) -> Result<()> {
+            self.calls.push(format!("end {value}"));
+            Ok(())
+        }
+
+        fn on_object_key(&mut self, key: &str, path: &str) -> Result<()> {
+            self.calls.push(format!("key {key} in {path}"));
+            Ok(())
+        }
+
+        fn on_string(&mut self, value: &str, path: &str) -> Result<Value> {
+            self.calls.push(format!("string {value} at {path}"));
+            Ok(Value::String(value.to_uppercase()))
+        }
+
+        fn validate(&self, value: &Value, path: &str) -> Result<()> {
+            match value.as_f64() {
+                Some(n) if n < 0.0 => Err(Error::Custom(format!("negative at {path}"))),
+                _ => Ok(()),
+            }
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_parse_with_plugins() {
+        let mut registry = PluginRegistry::new();
+        registry
+            .register(Box::new(RecordingPlugin::default()))
+            .unwrap();
+
+        let value =
+            parse_with_plugins("{a: ['x', 1]}", ParserOptions::default(), &mut registry).unwrap();
+        assert_eq!(value, crate::parse(r#"{"a": ["X", 1]}"#).unwrap());
+
+        let plugin = registry.get("recording").unwrap().as_any();
+        let calls = &plugin.downcast_ref::<RecordingPlugin>().unwrap().calls;
+        assert_eq!(
+            calls,
+            &[
+                "start {a: ['x', 1]}",
+                "key a in $.a",
+                "string x at $.a[0]",
+                r#"end {"a":["X",1]}"#,
+            ]
+        );
+
+        let value = parse_with_plugins("{a: {b: 1}}", ParserOptions::default(), &mut registry);
+        assert!(value.is_ok());
+        let plugin = registry.get("recording").unwrap().as_any();
+        let calls = &plugin.downcast_ref::<RecordingPlugin>().unwrap().calls;
+        assert!(calls.contains(&"key b in $.a.b".to_string()));
+
+        let error =
+            parse_with_plugins("[1, [-2]]", ParserOptions::default(), &mut registry).unwrap_err();
+        assert_eq!(error, Error::Custom("negative at $[1][0]".to_string()));
+    }
+
     #[test]
     fn test_plugin_hooks() {
         let mut registry = PluginRegistry::new();
@@ -1,11 +1,29 @@
 //! Date/time parsing plugin
 
-use crate::ast::Value;
+use crate::ast::{Number, Value};
 use crate::error::Result;
 use crate::plugin::ParserPlugin;
-use chrono::{DateTime, Datelike, NaiveDateTime, Timelike, Utc};
+use chrono::{DateTime, Datelike, NaiveDateTime, SecondsFormat, Timelike, Utc};
+use rustc_hash::FxHashMap;
 use std::any::Any;
 
+/// What the [`DateTimePlugin`] emits in place of a recognized date string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateTimeOutput {
+    /// An object with `year`, `month`, `day`, `hour`, `minute`, `second`,
+    /// `timestamp` and `iso8601` fields
+    #[default]
+    Structured,
+    /// The original string, unchanged
+    Original,
+    /// The date normalized to an RFC 3339 string in UTC
+    Rfc3339,
+    /// Milliseconds since the Unix epoch as an integer number
+    EpochMillis,
+    /// A tagged object `{"$date": "<RFC 3339>"}`
+    Tagged,
+}
+
 /// Date/time parsing plugin that converts ISO 8601 strings to structured date objects
 pub struct DateTimePlugin {
     /// Whether to parse dates in strings
@@ -14,9 +32,16 @@ pub struct DateTimePlugin {
     custom_formats: Vec<String>,
     /// Whether to preserve original string
     preserve_original: bool,
+    /// What to emit for recognized dates
+    output: DateTimeOutput,
+    /// Key patterns a string must be stored under to be converted; empty matches all
+    key_patterns: Vec<String>,
 }
 
 impl DateTimePlugin {
+    /// Key patterns commonly used for date fields
+    pub const COMMON_DATE_KEYS: &'static [&'static str] = &["*_at", "date", "timestamp"];
+
     /// Create a new datetime plugin with default settings
     pub fn new() -> Self {
         DateTimePlugin {
@@ -24,10 +49,13 @@ impl DateTimePlugin {
             custom_formats: vec![
                 "%Y-%m-%d".to_string(),
                 "%Y-%m-%d %H:%M:%S".to_string(),
+                "%Y-%m-%dT%H:%M:%S%.f".to_string(),
                 "%Y-%m-%dT%H:%M:%S%.fZ".to_string(),
                 "%Y-%m-%dT%H:%M:%S%.f%:z".to_string(),
             ],
             preserve_original: true,
+            output: DateTimeOutput::default(),
+            key_patterns: Vec::new(),
         }
     }
 
@@ -36,6 +64,50 @@ impl DateTimePlugin {
         self.custom_formats.push(format.to_string());
     }
 
+    /// Replace the chrono format strings tried after RFC 3339
+    pub fn with_formats<I, S>(mut self, formats: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.custom_formats = formats.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set what recognized dates are converted to
+    pub fn with_output(mut self, output: DateTimeOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Only convert strings stored under keys matching one of `patterns`.
+    ///
+    /// Patterns match the nearest object key in the value's path and may use
+    /// `*` as a wildcard, e.g. `*_at`. Array elements inherit the key of the
+    /// array that holds them.
+    pub fn with_key_patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.key_patterns = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether a string at `path` passes the key filter
+    fn key_matches(&self, path: &str) -> bool {
+        if self.key_patterns.is_empty() {
+            return true;
+        }
+        match key_from_path(path) {
+            Some(key) => self
+                .key_patterns
+                .iter()
+                .any(|pattern| wildcard_match(pattern, key)),
+            None => false,
+        }
+    }
+
     /// Try to parse a string as a date
     fn try_parse_date(&self, s: &str) -> Option<DateTime<Utc>> {
         // Try ISO 8601 first
@@ -45,7 +117,11 @@ impl DateTimePlugin {
 
         // Try custom formats
         for format in &self.custom_formats {
-            // Try as NaiveDateTime first
+            // Formats carrying an offset keep it when converting to UTC
+            if let Ok(dt) = DateTime::parse_from_str(s, format) {
+                return Some(dt.with_timezone(&Utc));
+            }
+            // Try as NaiveDateTime next
             if let Ok(dt) = NaiveDateTime::parse_from_str(s, format) {
                 return Some(DateTime::from_naive_utc_and_offset(dt, Utc));
             }
@@ -59,37 +135,52 @@ impl DateTimePlugin {
         None
     }
 
+    /// Convert a datetime to the configured output value
+    fn convert(&self, dt: DateTime<Utc>, original: &str) -> Value {
+        match self.output {
+            DateTimeOutput::Structured => self.datetime_to_value(dt, original),
+            DateTimeOutput::Original => Value::String(original.to_string()),
+            DateTimeOutput::Rfc3339 => Value::String(to_rfc3339(dt)),
+            DateTimeOutput::EpochMillis => Value::Number(Number::Integer(dt.timestamp_millis())),
+            DateTimeOutput::Tagged => {
+                let mut obj = FxHashMap::default();
+                obj.insert("$date".to_string(), Value::String(to_rfc3339(dt)));
+                Value::Object(obj)
+            }
+        }
+    }
+
     /// Convert a datetime to a structured value
     fn datetime_to_value(&self, dt: DateTime<Utc>, original: &str) -> Value {
-        let mut obj = rustc_hash::FxHashMap::default();
+        let mut obj = FxHashMap::default();
 
         obj.insert(
             "year".to_string(),
-            Value::Number(crate::ast::Number::Integer(dt.year() as i64)),
+            Value::Number(Number::Integer(dt.year() as i64)),
         );
         obj.insert(
             "month".to_string(),
-            Value::Number(crate::ast::Number::Integer(dt.month() as i64)),
+            Value::Number(Number::Integer(dt.month() as i64)),
         );
         obj.insert(
             "day".to_string(),
-            Value::Number(crate::ast::Number::Integer(dt.day() as i64)),
+            Value::Number(Number::Integer(dt.day() as i64)),
         );
         obj.insert(
             "hour".to_string(),
-            Value::Number(crate::ast::Number::Integer(dt.hour() as i64)),
+            Value::Number(Number::Integer(dt.hour() as i64)),
         );
         obj.insert(
             "minute".to_string(),
-            Value::Number(crate::ast::Number::Integer(dt.minute() as i64)),
+            Value::Number(Number::Integer(dt.minute() as i64)),
         );
         obj.insert(
             "second".to_string(),
-            Value::Number(crate::ast::Number::Integer(dt.second() as i64)),
+            Value::Number(Number::Integer(dt.second() as i64)),
         );
         obj.insert(
             "timestamp".to_string(),
-            Value::Number(crate::ast::Number::Integer(dt.timestamp())),
+            Value::Number(Number::Integer(dt.timestamp())),
         );
         obj.insert("iso8601".to_string(), Value::String(dt.to_rfc3339()));
 
@@ -112,25 +203,32 @@ impl ParserPlugin for DateTimePlugin {
         "datetime"
     }
 
-    fn transform_value(&mut self, value: &mut Value, _path: &str) -> Result<()> {
+    fn on_string(&mut self, value: &str, path: &str) -> Result<Value> {
+        if self.parse_dates && self.key_matches(path) {
+            if let Some(dt) = self.try_parse_date(value) {
+                return Ok(self.convert(dt, value));
+            }
+        }
+        Ok(Value::String(value.to_string()))
+    }
+
+    fn transform_value(&mut self, value: &mut Value, path: &str) -> Result<()> {
         if !self.parse_dates {
             return Ok(());
         }
 
         match value {
             Value::String(s) => {
-                if let Some(dt) = self.try_parse_date(s) {
-                    *value = self.datetime_to_value(dt, s);
-                }
+                *value = self.on_string(s, path)?;
             }
             Value::Array(arr) => {
-                for item in arr {
-                    self.transform_value(item, _path)?;
+                for (i, item) in arr.iter_mut().enumerate() {
+                    self.transform_value(item, &format!("{path}[{i}]"))?;
                 }
             }
             Value::Object(obj) => {
-                for (_, v) in obj.iter_mut() {
-                    self.transform_value(v, _path)?;
+                for (key, v) in obj.iter_mut() {
+                    self.transform_value(v, &format!("{path}.{key}"))?;
                 }
             }
             _ => {}
@@ -148,10 +246,47 @@ impl ParserPlugin for DateTimePlugin {
     }
 }
 
+/// Formats a date as RFC 3339 in UTC with a `Z` suffix
+fn to_rfc3339(dt: DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Returns the nearest object key in a path such as `$.events[2].created_at`
+fn key_from_path(path: &str) -> Option<&str> {
+    let mut path = path;
+    while path.ends_with(']') {
+        path = &path[..path.rfind('[')?];
+    }
+    let (_, key) = path.rsplit_once('.')?;
+    Some(key)
+}
+
+/// Matches `text` against a pattern where `*` matches any run of characters
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    // use crate::parse;
+    use crate::parse;
+    use crate::parser::ParserOptions;
+    use crate::plugin::{parse_with_plugins, PluginRegistry};
 
     #[test]
     fn test_datetime_parsing() {
@@ -164,15 +299,15 @@ mod tests {
         if let Value::Object(obj) = value {
             assert_eq!(
                 obj.get("year"),
-                Some(&Value::Number(crate::ast::Number::Integer(2023)))
+                Some(&Value::Number(Number::Integer(2023)))
             );
             assert_eq!(
                 obj.get("month"),
-                Some(&Value::Number(crate::ast::Number::Integer(12)))
+                Some(&Value::Number(Number::Integer(12)))
             );
             assert_eq!(
                 obj.get("day"),
-                Some(&Value::Number(crate::ast::Number::Integer(25)))
+                Some(&Value::Number(Number::Integer(25)))
             );
         } else {
             panic!("Expected object");
@@ -182,7 +317,7 @@ mod tests {
     #[test]
     fn test_custom_format() {
         let mut plugin = DateTimePlugin::new();
-        
+
         // Add the custom format for YYYY-MM-DD
         plugin.add_format("%Y-%m-%d");
 
@@ -194,7 +329,7 @@ mod tests {
             Value::Object(obj) => {
                 assert_eq!(
                     obj.get("year"),
-                    Some(&Value::Number(crate::ast::Number::Integer(2023)))
+                    Some(&Value::Number(Number::Integer(2023)))
                 );
                 assert!(obj.contains_key("_original"));
             }
@@ -217,4 +352,147 @@ mod tests {
 
         assert_eq!(value, Value::String("hello world".to_string()));
     }
+
+    fn tagged_plugin() -> DateTimePlugin {
+        DateTimePlugin::new()
+            .with_output(DateTimeOutput::Tagged)
+            .with_key_patterns(DateTimePlugin::COMMON_DATE_KEYS.iter().copied())
+    }
+
+    #[test]
+    fn test_output_kinds() {
+        let input = "2024-01-02T03:04:05Z";
+        let convert = |output| {
+            DateTimePlugin::new()
+                .with_output(output)
+                .on_string(input, "$")
+                .unwrap()
+        };
+
+        assert_eq!(
+            convert(DateTimeOutput::Original),
+            Value::String(input.to_string())
+        );
+        assert_eq!(
+            convert(DateTimeOutput::Rfc3339),
+            Value::String(input.to_string())
+        );
+        assert_eq!(
+            convert(DateTimeOutput::EpochMillis),
+            Value::Number(Number::Integer(1_704_164_645_000))
+        );
+        assert_eq!(
+            convert(DateTimeOutput::Tagged),
+            parse(r#"{"$date": "2024-01-02T03:04:05Z"}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_timezone_offsets_normalized_to_utc() {
+        let mut plugin = DateTimePlugin::new().with_output(DateTimeOutput::Rfc3339);
+
+        let value = plugin.on_string("2024-03-10T12:30:00+02:00", "$").unwrap();
+        assert_eq!(value, Value::String("2024-03-10T10:30:00Z".to_string()));
+
+        let mut plugin = DateTimePlugin::new()
+            .with_formats(["%d/%m/%Y %H:%M %z"])
+            .with_output(DateTimeOutput::EpochMillis);
+        let value = plugin.on_string("10/03/2024 12:30 -0100", "$").unwrap();
+        assert_eq!(value, Value::Number(Number::Integer(1_710_077_400_000)));
+    }
+
+    #[test]
+    fn test_ambiguous_strings_not_converted() {
+        let mut plugin = DateTimePlugin::new().with_output(DateTimeOutput::Rfc3339);
+
+        for input in ["2024.1", "1.2.3", "2024", "v2024-01-02", "12:30", ""] {
+            assert_eq!(
+                plugin.on_string(input, "$.date").unwrap(),
+                Value::String(input.to_string()),
+                "{input:?} should be left alone"
+            );
+        }
+    }
+
+    #[test]
+    fn test_key_filter() {
+        let mut value = parse(
+            r#"{
+                "created_at": "2024-01-02",
+                "date": "2024-01-02",
+                "name": "2024-01-02",
+                "release": {"timestamp": "2024-01-02T03:04:05Z", "version": "2024.1"}
+            }"#,
+        )
+        .unwrap();
+        tagged_plugin().transform_value(&mut value, "$").unwrap();
+
+        let expected = parse(
+            r#"{
+                "created_at": {"$date": "2024-01-02T00:00:00Z"},
+                "date": {"$date": "2024-01-02T00:00:00Z"},
+                "name": "2024-01-02",
+                "release": {"timestamp": {"$date": "2024-01-02T03:04:05Z"}, "version": "2024.1"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_arrays_of_dates() {
+        let mut value =
+            parse(r#"{"updated_at": ["2024-01-02", "2024-02-03T04:05:06Z", "never"]}"#).unwrap();
+        tagged_plugin().transform_value(&mut value, "$").unwrap();
+
+        let expected = parse(
+            r#"{"updated_at": [{"$date": "2024-01-02T00:00:00Z"}, {"$date": "2024-02-03T04:05:06Z"}, "never"]}"#,
+        )
+        .unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_dates_recognized_while_parsing() {
+        let input = r#"{
+            "created_at": "2024-01-02T03:04:05Z",
+            "name": "hello",
+            "tags": ["2024-01-02T03:04:05Z", "v2024.1"]
+        }"#;
+        let parse_with = |plugin: DateTimePlugin| {
+            let mut registry = PluginRegistry::new();
+            registry.register(Box::new(plugin)).unwrap();
+            parse_with_plugins(input, ParserOptions::default(), &mut registry).unwrap()
+        };
+
+        let all = parse_with(DateTimePlugin::new().with_output(DateTimeOutput::Tagged));
+        let expected = parse(
+            r#"{
+                "created_at": {"$date": "2024-01-02T03:04:05Z"},
+                "name": "hello",
+                "tags": [{"$date": "2024-01-02T03:04:05Z"}, "v2024.1"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(all, expected);
+
+        let by_key = parse_with(tagged_plugin());
+        assert_eq!(by_key["created_at"], expected["created_at"]);
+        assert_eq!(by_key["name"], Value::String("hello".to_string()));
+        assert_eq!(by_key["tags"], parse(input).unwrap()["tags"]);
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*_at", "created_at"));
+        assert!(!wildcard_match("*_at", "format"));
+        assert!(wildcard_match("date", "date"));
+        assert!(!wildcard_match("date", "dates"));
+        assert!(wildcard_match("*time*", "timestamp"));
+        assert_eq!(
+            key_from_path("$.events[2].created_at[0]"),
+            Some("created_at")
+        );
+        assert_eq!(key_from_path("$[0]"), None);
+    }
 }
@@ -2,10 +2,12 @@
 
 mod comment_preservation;
 mod custom_number;
+#[cfg(feature = "datetime")]
 mod datetime;
 mod schema_validation;
 
 pub use comment_preservation::CommentPreservationPlugin;
 pub use custom_number::CustomNumberFormatPlugin;
-pub use datetime::DateTimePlugin;
+#[cfg(feature = "datetime")]
+pub use datetime::{DateTimeOutput, DateTimePlugin};
 pub use schema_validation::SchemaValidationPlugin;
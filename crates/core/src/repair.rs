@@ -6,79 +6,140 @@
 //! capabilities including quote repair, type coercion, and confidence scoring.
 
 pub mod advanced;
+pub mod cache;
+pub(crate) mod punctuation;
+pub mod smart;
 
 use crate::error::repair::{RepairAction, RepairType};
-use rustc_hash::FxHashMap;
+use std::sync::{Arc, MutexGuard, PoisonError};
 
 // Re-export advanced repair functionality
 pub use advanced::{
     AdvancedJsonRepairer, RepairConfidence, RepairHistory, RepairHistoryEntry, RepairPreview,
     RepairStrategy, TypeCoercionRules,
 };
+pub use cache::{RepairCache, RepairCacheConfig, RepairCacheStats, SharedRepairCache};
+pub use smart::smart_repair;
 
 /// Simple JSON repair implementation focusing on bracket balancing.
 pub struct JsonRepairer {
     /// Maximum number of repairs to attempt
     max_repairs: usize,
-    /// Cache for frequently repaired patterns
-    repair_cache: FxHashMap<String, (String, Vec<RepairAction>)>,
-    /// Enable caching for performance optimization
-    cache_enabled: bool,
+    /// Cache for frequently repaired patterns, possibly shared with other repairers
+    cache: Option<SharedRepairCache>,
 }
 
 impl JsonRepairer {
-    /// Creates a new JSON repairer with the specified maximum repairs.
+    /// Creates a new JSON repairer with the specified maximum repairs and a
+    /// private cache using [`RepairCacheConfig::default`].
     pub fn new(max_repairs: usize) -> Self {
+        Self::with_cache_config(max_repairs, RepairCacheConfig::default())
+    }
+
+    /// Creates a new JSON repairer with caching disabled.
+    pub fn new_without_cache(max_repairs: usize) -> Self {
         Self {
             max_repairs,
-            repair_cache: FxHashMap::default(),
-            cache_enabled: true,
+            cache: None,
         }
     }
 
-    /// Creates a new JSON repairer with caching disabled.
-    pub fn new_without_cache(max_repairs: usize) -> Self {
+    /// Creates a new JSON repairer with a private cache bounded by `config`.
+    pub fn with_cache_config(max_repairs: usize, config: RepairCacheConfig) -> Self {
+        Self::with_shared_cache(max_repairs, RepairCache::shared(config))
+    }
+
+    /// Creates a new JSON repairer that stores results in `cache`, which may
+    /// be shared with other repairers.
+    pub fn with_shared_cache(max_repairs: usize, cache: SharedRepairCache) -> Self {
         Self {
             max_repairs,
-            repair_cache: FxHashMap::default(),
-            cache_enabled: false,
+            cache: Some(cache),
         }
     }
 
+    fn lock_cache(&self) -> Option<MutexGuard<'_, RepairCache>> {
+        // The cache holds no invariants a panicking repairer could break
+        self.cache
+            .as_ref()
+            .map(|cache| cache.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
     /// Clears the repair cache.
     pub fn clear_cache(&mut self) {
-        self.repair_cache.clear();
+        if let Some(mut cache) = self.lock_cache() {
+            cache.clear();
+        }
     }
 
     /// Returns the number of entries in the repair cache.
     pub fn cache_size(&self) -> usize {
-        self.repair_cache.len()
+        self.lock_cache().map_or(0, |cache| cache.len())
+    }
+
+    /// Returns hit, miss and eviction counts for the repair cache.
+    ///
+    /// Counts are those of the shared cache when one is used.
+    pub fn cache_stats(&self) -> RepairCacheStats {
+        self.lock_cache()
+            .map(|cache| cache.stats())
+            .unwrap_or_default()
+    }
+
+    /// Returns the cache this repairer stores results in, if any.
+    pub fn shared_cache(&self) -> Option<SharedRepairCache> {
+        self.cache.as_ref().map(Arc::clone)
     }
 
     /// Attempts to repair the given JSON string by balancing brackets.
     ///
-    /// Returns the repaired JSON string and a list of repair actions taken.
+    /// Returns the repaired JSON string and a list of repair actions taken,
+    /// or an error if the repair needs more than `max_repairs` actions,
+    /// whether or not it came from the cache.
     pub fn repair(&mut self, input: &str) -> Result<(String, Vec<RepairAction>), String> {
         // Check cache first if enabled
-        if self.cache_enabled {
-            if let Some((cached_result, cached_repairs)) = self.repair_cache.get(input) {
-                return Ok((cached_result.clone(), cached_repairs.clone()));
+        let cached = self.lock_cache().and_then(|mut cache| cache.get(input));
+        let result = match cached {
+            Some(cached) => cached,
+            None => {
+                let result = self.balance_brackets(input);
+                self.store(input, &result);
+                result
             }
+        };
+
+        // Check if we exceeded repair limit
+        if result.1.len() > self.max_repairs {
+            return Err("Maximum repair attempts exceeded".to_string());
         }
+        Ok(result)
+    }
 
+    /// Repairs `input` by dropping trailing closers and adding missing ones,
+    /// however many actions that takes.
+    fn balance_brackets(&self, input: &str) -> (String, Vec<RepairAction>) {
         let mut repairs = Vec::new();
         let mut repaired = input.to_string();
 
+        // Drop unmatched closing brackets left over after the document ends
+        if let Some(start) = self.find_trailing_closers(&repaired) {
+            repairs.push(RepairAction {
+                action_type: RepairType::DiscardTrailing,
+                position: start,
+                original: repaired[start..].to_string(),
+                replacement: String::new(),
+                description: "Removed unmatched closing brackets after the document".to_string(),
+                cause: None,
+            });
+            repaired.truncate(start);
+        }
+
         // Track bracket balance
         let balance = self.analyze_bracket_balance(&repaired);
 
-        // If brackets are balanced, no repair needed
+        // If brackets are balanced, no further repair needed
         if balance.is_balanced() {
-            let result = (repaired.clone(), repairs.clone());
-            if self.cache_enabled {
-                self.repair_cache.insert(input.to_string(), result.clone());
-            }
-            return Ok(result);
+            return (repaired, repairs);
         }
 
         // Attempt to fix unbalanced brackets
@@ -87,25 +148,21 @@ impl JsonRepairer {
                 action_type: RepairType::InsertBracket,
                 position: repaired.len(),
                 original: String::new(),
-                replacement: fixed.chars().skip(repaired.len()).collect(),
+                replacement: fixed[repaired.len()..].to_string(),
                 description: "Added missing closing brackets".to_string(),
+                cause: None,
             });
             repaired = fixed;
         }
 
-        // Check if we exceeded repair limit
-        if repairs.len() > self.max_repairs {
-            return Err("Maximum repair attempts exceeded".to_string());
-        }
-
-        let result = (repaired, repairs);
+        (repaired, repairs)
+    }
 
-        // Cache the result if enabled and cache isn't too large
-        if self.cache_enabled && self.repair_cache.len() < 1000 {
-            self.repair_cache.insert(input.to_string(), result.clone());
+    /// Caches `result` as the repair of `input`, subject to the cache's limits.
+    fn store(&self, input: &str, result: &(String, Vec<RepairAction>)) {
+        if let Some(mut cache) = self.lock_cache() {
+            cache.insert(input, result.clone());
         }
-
-        Ok(result)
     }
 
     /// Enhanced repair function with comprehensive action detection
@@ -169,6 +226,68 @@ impl JsonRepairer {
         }
     }
 
+    /// Finds where unmatched closing brackets begin after a complete top-level
+    /// container, returning `None` unless only closers and whitespace follow.
+    fn find_trailing_closers(&self, input: &str) -> Option<usize> {
+        let mut stack = Vec::new();
+        let mut closed_container = false;
+        let mut in_string = false;
+        let mut escape_next = false;
+        let mut quote_char = '"';
+
+        for (i, ch) in input.char_indices() {
+            if escape_next {
+                escape_next = false;
+                continue;
+            }
+
+            if ch == '\\' {
+                escape_next = true;
+                continue;
+            }
+
+            if in_string {
+                if ch == quote_char {
+                    in_string = false;
+                }
+                continue;
+            }
+
+            let closing = match ch {
+                '"' | '\'' => {
+                    in_string = true;
+                    quote_char = ch;
+                    continue;
+                }
+                '{' => {
+                    stack.push(BracketType::Brace);
+                    continue;
+                }
+                '[' => {
+                    stack.push(BracketType::Bracket);
+                    continue;
+                }
+                '}' => BracketType::Brace,
+                ']' => BracketType::Bracket,
+                _ => continue,
+            };
+
+            match stack.pop() {
+                Some(open) if open == closing => closed_container |= stack.is_empty(),
+                // Mismatched brackets are not a trailing-input problem
+                Some(_) => return None,
+                None => {
+                    let only_closers = input[i..]
+                        .chars()
+                        .all(|c| c == '}' || c == ']' || c.is_whitespace());
+                    return (closed_container && only_closers).then_some(i);
+                }
+            }
+        }
+
+        None
+    }
+
     /// Attempts to fix bracket balance by adding missing closing brackets in reverse order.
     fn fix_bracket_balance(&self, input: &str, balance: &BracketBalance) -> Option<String> {
         if balance.unmatched_stack.is_empty() {
@@ -282,6 +401,46 @@ mod tests {
         assert_eq!(repairer.cache_size(), 1);
     }
 
+    #[test]
+    fn test_cached_repairs_respect_max_repairs() {
+        let cache = RepairCache::shared(RepairCacheConfig::default());
+        let mut generous = JsonRepairer::with_shared_cache(1, Arc::clone(&cache));
+        let mut strict = JsonRepairer::with_shared_cache(0, Arc::clone(&cache));
+
+        for input in ["[1, [2]", "[1]]"] {
+            assert_eq!(generous.repair(input).unwrap().1.len(), 1, "{input}");
+            // The cached repair needs more actions than this repairer allows
+            assert!(strict.repair(input).is_err(), "{input}");
+        }
+        assert_eq!(cache.lock().unwrap().stats().hits, 2);
+        assert!(strict.repair("[1]").unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn test_shared_cache_bounds() {
+        let cache = RepairCache::shared(RepairCacheConfig {
+            max_entries: 3,
+            max_input_len_to_cache: 32,
+        });
+        let mut first = JsonRepairer::with_shared_cache(10, Arc::clone(&cache));
+        let mut second = JsonRepairer::with_shared_cache(10, Arc::clone(&cache));
+
+        for i in 0..5 {
+            first.repair(&format!("[{i}")).unwrap();
+        }
+        second.repair("[4").unwrap();
+        let stats = second.cache_stats();
+        assert_eq!(stats.size, 3);
+        assert_eq!(stats.evictions, 2);
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 5);
+
+        let huge = format!("[{}", "1, ".repeat(100));
+        first.repair(&huge).unwrap();
+        first.repair(&huge).unwrap();
+        assert_eq!(first.cache_stats(), stats);
+    }
+
     #[test]
     fn test_detailed_repair_tracking() {
         let mut repairer = JsonRepairer::new(10);
@@ -293,6 +452,22 @@ mod tests {
         assert_eq!(repairs[0].action_type, RepairType::InsertBracket);
     }
 
+    #[test]
+    fn test_trailing_closers_discarded() {
+        let mut repairer = JsonRepairer::new(10);
+        let (repaired, repairs) = repairer.repair(r#"{"a": "}"}]}"#).unwrap();
+
+        assert_eq!(repaired, r#"{"a": "}"}"#);
+        assert_eq!(repairs.len(), 1);
+        assert_eq!(repairs[0].action_type, RepairType::DiscardTrailing);
+        assert_eq!(repairs[0].original, "]}");
+
+        // Extra closers inside the document are left alone
+        let (repaired, repairs) = repairer.repair(r#"{"a": [1]]}"#).unwrap();
+        assert_eq!(repaired, r#"{"a": [1]]}"#);
+        assert!(repairs.is_empty());
+    }
+
     #[test]
     fn test_debug_bracket_balance() {
         let repairer = JsonRepairer::new(10);
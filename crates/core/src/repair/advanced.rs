@@ -180,7 +180,7 @@ impl AdvancedJsonRepairer {
 
         // Sort by position in reverse order (highest position first)
         // This ensures that later repairs don't affect earlier positions
-        applicable_strategies.sort_by(|a, b| b.action.position.cmp(&a.action.position));
+        applicable_strategies.sort_by_key(|s| std::cmp::Reverse(s.action.position));
 
         for strategy in applicable_strategies {
             repaired = self.apply_repair(&repaired, &strategy.action)?;
@@ -249,12 +249,7 @@ impl AdvancedJsonRepairer {
         }
 
         // Sort strategies by confidence (highest first)
-        strategies.sort_by(|a, b| {
-            b.confidence
-                .value()
-                .partial_cmp(&a.confidence.value())
-                .unwrap()
-        });
+        strategies.sort_by(|a, b| b.confidence.value().total_cmp(&a.confidence.value()));
 
         Ok(strategies)
     }
@@ -289,6 +284,7 @@ impl AdvancedJsonRepairer {
                             original: "'".to_string(),
                             replacement: "\"".to_string(),
                             description: "Replace single quotes with double quotes".to_string(),
+                            cause: None,
                         },
                         confidence: RepairConfidence::new(0.9),
                         alternatives: vec![],
@@ -302,6 +298,7 @@ impl AdvancedJsonRepairer {
                             replacement: "\"".to_string(),
                             description: "Replace closing single quote with double quote"
                                 .to_string(),
+                            cause: None,
                         },
                         confidence: RepairConfidence::new(0.9),
                         alternatives: vec![],
@@ -339,6 +336,7 @@ impl AdvancedJsonRepairer {
                                 description: format!(
                                     "Convert quoted number \"{number_str}\" to unquoted"
                                 ),
+                                cause: None,
                             },
                             confidence: RepairConfidence::new(0.8),
                             alternatives: vec![],
@@ -378,6 +376,7 @@ impl AdvancedJsonRepairer {
                                 original: String::new(),
                                 replacement: ",".to_string(),
                                 description: "Insert missing comma between elements".to_string(),
+                                cause: None,
                             },
                             confidence: RepairConfidence::new(0.85),
                             alternatives: vec![],
@@ -423,6 +422,7 @@ impl AdvancedJsonRepairer {
                         original: key_match.as_str().to_string(),
                         replacement: format!("\"{}\"", key_match.as_str()),
                         description: format!("Add quotes to key '{}'", key_match.as_str()),
+                        cause: None,
                     },
                     confidence: RepairConfidence::new(0.75),
                     alternatives: vec![],
@@ -0,0 +1,260 @@
+// this_file: crates/core/src/repair/cache.rs
+
+//! Bounded cache of repair results keyed by input.
+//!
+//! Services that repair many distinct documents should bound the cache with
+//! [`RepairCacheConfig`]; least recently used entries are evicted first. A
+//! [`SharedRepairCache`] can be handed to several [`JsonRepairer`]s, or to
+//! the parser through [`ParserOptions::repair_cache`].
+//!
+//! [`JsonRepairer`]: super::JsonRepairer
+//! [`ParserOptions::repair_cache`]: crate::ParserOptions::repair_cache
+
+use crate::error::repair::RepairAction;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A repair cache shared between repairers.
+pub type SharedRepairCache = Arc<Mutex<RepairCache>>;
+
+/// Repaired text and the repairs that produced it.
+pub type CachedRepair = (String, Vec<RepairAction>);
+
+/// Limits on what a [`RepairCache`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepairCacheConfig {
+    /// Maximum number of cached inputs; zero disables caching.
+    pub max_entries: usize,
+    /// Inputs longer than this many bytes are never cached.
+    pub max_input_len_to_cache: usize,
+}
+
+impl Default for RepairCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1000,
+            max_input_len_to_cache: 64 * 1024,
+        }
+    }
+}
+
+/// Counters describing how a [`RepairCache`] has been used.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepairCacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups of cacheable inputs that were not in the cache
+    pub misses: u64,
+    /// Entries dropped to stay within `max_entries`
+    pub evictions: u64,
+    /// Entries currently cached
+    pub size: usize,
+}
+
+impl RepairCacheStats {
+    /// Fraction of lookups answered from the cache, or zero before any lookup.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+struct CacheEntry {
+    repair: CachedRepair,
+    last_used: u64,
+}
+
+/// Least-recently-used cache of repair results.
+pub struct RepairCache {
+    config: RepairCacheConfig,
+    entries: FxHashMap<Arc<str>, CacheEntry>,
+    /// Inputs by the tick at which they were last used, oldest first
+    recency: BTreeMap<u64, Arc<str>>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl RepairCache {
+    /// Creates an empty cache with the given limits.
+    pub fn new(config: RepairCacheConfig) -> Self {
+        Self {
+            config,
+            entries: FxHashMap::default(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    /// Creates an empty cache that can be shared between repairers.
+    pub fn shared(config: RepairCacheConfig) -> SharedRepairCache {
+        Arc::new(Mutex::new(Self::new(config)))
+    }
+
+    /// Returns the cache's limits.
+    pub fn config(&self) -> &RepairCacheConfig {
+        &self.config
+    }
+
+    /// Whether `input` is small enough to be cached.
+    pub fn accepts(&self, input: &str) -> bool {
+        self.config.max_entries > 0 && input.len() <= self.config.max_input_len_to_cache
+    }
+
+    /// Looks up the repair of `input`, marking it as recently used.
+    ///
+    /// Inputs the cache would not accept are not counted as misses.
+    pub fn get(&mut self, input: &str) -> Option<CachedRepair> {
+        if !self.accepts(input) {
+            return None;
+        }
+        self.tick += 1;
+        let Some(entry) = self.entries.get_mut(input) else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+        let key = self
+            .recency
+            .remove(&entry.last_used)
+            .expect("cached input has a recency entry");
+        entry.last_used = self.tick;
+        self.recency.insert(self.tick, key);
+        Some(entry.repair.clone())
+    }
+
+    /// Caches the repair of `input`, evicting the least recently used entry
+    /// if the cache is full. Inputs the cache does not accept are ignored.
+    pub fn insert(&mut self, input: &str, repair: CachedRepair) {
+        if !self.accepts(input) {
+            return;
+        }
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(input) {
+            let key = self
+                .recency
+                .remove(&entry.last_used)
+                .expect("cached input has a recency entry");
+            entry.repair = repair;
+            entry.last_used = self.tick;
+            self.recency.insert(self.tick, key);
+            return;
+        }
+
+        while self.entries.len() >= self.config.max_entries {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            self.evictions += 1;
+        }
+        let key: Arc<str> = Arc::from(input);
+        self.recency.insert(self.tick, Arc::clone(&key));
+        self.entries.insert(
+            key,
+            CacheEntry {
+                repair,
+                last_used: self.tick,
+            },
+        );
+    }
+
+    /// Removes every entry, keeping the counters.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Returns the number of cached inputs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns hit, miss and eviction counts along with the current size.
+    pub fn stats(&self) -> RepairCacheStats {
+        RepairCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            size: self.entries.len(),
+        }
+    }
+}
+
+impl Default for RepairCache {
+    fn default() -> Self {
+        Self::new(RepairCacheConfig::default())
+    }
+}
+
+impl fmt::Debug for RepairCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RepairCache")
+            .field("config", &self.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repair(text: &str) -> CachedRepair {
+        (text.to_string(), Vec::new())
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = RepairCache::new(RepairCacheConfig {
+            max_entries: 2,
+            ..Default::default()
+        });
+        cache.insert("a", repair("A"));
+        cache.insert("b", repair("B"));
+        assert_eq!(cache.get("a"), Some(repair("A")));
+
+        cache.insert("c", repair("C"));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(repair("A")));
+        assert_eq!(cache.get("c"), Some(repair("C")));
+        assert_eq!(
+            cache.stats(),
+            RepairCacheStats {
+                hits: 3,
+                misses: 1,
+                evictions: 1,
+                size: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_large_inputs_are_not_cached() {
+        let mut cache = RepairCache::new(RepairCacheConfig {
+            max_input_len_to_cache: 8,
+            ..Default::default()
+        });
+        let large = "[".repeat(9);
+        cache.insert(&large, repair("x"));
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&large), None);
+        assert_eq!(cache.stats().misses, 0);
+    }
+}
@@ -0,0 +1,165 @@
+// this_file: crates/core/src/repair/punctuation.rs
+
+//! Undoing the typographic substitutions word processors make.
+//!
+//! JSON pasted from a document editor or chat client often has its quotes
+//! curled, a minus sign turned into a dash and spaces made unbreakable, so
+//! `{"a": -1}` arrives as `{“a”: –1}`.
+
+use crate::error::repair::{RepairAction, RepairType};
+
+#[derive(Clone, Copy)]
+enum State {
+    Code,
+    /// Inside a string opened by a straight quote, which is left as written.
+    Quoted(char),
+    /// Inside a string opened by a curly quote, which becomes this straight
+    /// quote.
+    Curly(char),
+    LineComment,
+    BlockComment,
+}
+
+/// The repairs replacing curly quotes, dashes before digits and no-break
+/// spaces in `input` with `"`, `'`, `-` and a space, ordered by position.
+///
+/// The content of strings is data, so it is never changed: a curly quote
+/// inside `"…"` stays, and an apostrophe such as the one in `“don’t”` is
+/// not taken for a quote. Comments are skipped too.
+pub(crate) fn punctuation_repairs(input: &str) -> Vec<RepairAction> {
+    let mut repairs = Vec::new();
+    let mut state = State::Code;
+    let mut previous = None;
+    let mut chars = input.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        let rest = &input[i + c.len_utf8()..];
+        let mut replacement = None;
+        state = match (state, c) {
+            (State::Code, '"' | '\'') => State::Quoted(c),
+            (State::Code, '/') if rest.starts_with('/') => State::LineComment,
+            (State::Code, '/') if rest.starts_with('*') => {
+                // Step over the `*` so that `/*/` is not taken as closed
+                chars.next();
+                state = State::BlockComment;
+                previous = None;
+                continue;
+            }
+            (State::Code, '“' | '”') => {
+                replacement = Some('"');
+                State::Curly('"')
+            }
+            // A right single quote after a letter is an apostrophe in an
+            // unquoted word
+            (State::Code, '‘' | '’') if !previous.is_some_and(char::is_alphanumeric) => {
+                replacement = Some('\'');
+                State::Curly('\'')
+            }
+            (State::Code, '–' | '—' | '−') if rest.starts_with(|c: char| c.is_ascii_digit()) =>
+            {
+                replacement = Some('-');
+                State::Code
+            }
+            (State::Code, '\u{a0}') => {
+                replacement = Some(' ');
+                State::Code
+            }
+            (State::Quoted(_) | State::Curly(_), '\\') => {
+                chars.next();
+                previous = None;
+                continue;
+            }
+            (State::Quoted(quote), c) if c == quote => State::Code,
+            (State::Curly('"'), '”' | '“') => {
+                replacement = Some('"');
+                State::Code
+            }
+            (State::Curly('"'), '"') => State::Code,
+            // Inside single quotes, a closing quote must end the value or
+            // key; any other is an apostrophe
+            (State::Curly('\''), '’' | '\'') if ends_string(rest) => {
+                replacement = (c == '’').then_some('\'');
+                State::Code
+            }
+            (State::LineComment, '\n') => State::Code,
+            (State::BlockComment, '/') if previous == Some('*') => State::Code,
+            (state, _) => state,
+        };
+        if let Some(straight) = replacement {
+            repairs.push(replace(i, c, straight));
+        }
+        previous = Some(c);
+    }
+    repairs
+}
+
+/// Whether a quote followed by `rest` closes a string.
+fn ends_string(rest: &str) -> bool {
+    rest.trim_start()
+        .chars()
+        .next()
+        .map_or(true, |next| matches!(next, ',' | ':' | '}' | ']'))
+}
+
+fn replace(position: usize, original: char, straight: char) -> RepairAction {
+    let (action_type, name) = match original {
+        '“' => (RepairType::ReplaceQuotes, "left double quotation mark"),
+        '”' => (RepairType::ReplaceQuotes, "right double quotation mark"),
+        '‘' => (RepairType::ReplaceQuotes, "left single quotation mark"),
+        '’' => (RepairType::ReplaceQuotes, "right single quotation mark"),
+        '–' => (RepairType::ReplaceText, "en dash"),
+        '—' => (RepairType::ReplaceText, "em dash"),
+        '−' => (RepairType::ReplaceText, "minus sign"),
+        _ => (RepairType::ReplaceText, "no-break space"),
+    };
+    RepairAction {
+        action_type,
+        position,
+        original: original.to_string(),
+        replacement: straight.to_string(),
+        description: format!("Replaced {name} with {straight:?}"),
+        cause: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::repair::apply_repairs;
+
+    fn normalize(input: &str) -> String {
+        let repairs = punctuation_repairs(input);
+        apply_repairs(input, &repairs.iter().collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_replacements() {
+        let input = "{“a”:\u{a0}[–1, — 2, ‘x’, don’t]}";
+        assert_eq!(normalize(input), "{\"a\": [-1, — 2, 'x', don’t]}");
+        assert_eq!(punctuation_repairs(input).len(), 6);
+    }
+
+    #[test]
+    fn test_strings_and_comments_are_kept() {
+        for input in [
+            "[\"“–1”\\\"“\", '‘x’ \\' ’']",
+            "// “a”\n/* ‘b’ –1 */ /*/ “c” */ 1",
+        ] {
+            assert_eq!(normalize(input), input);
+        }
+        assert_eq!(normalize("[“it’s ‘fine’”]"), "[\"it’s ‘fine’\"]");
+    }
+
+    #[test]
+    fn test_positions_refer_to_the_input() {
+        let input = "{“a”: –1, “b”: 2}";
+        let positions: Vec<_> = punctuation_repairs(input)
+            .into_iter()
+            .map(|repair| {
+                assert!(input[repair.position..].starts_with(&repair.original));
+                repair.position
+            })
+            .collect();
+        assert_eq!(positions, [1, 5, 10, 16, 20]);
+    }
+}
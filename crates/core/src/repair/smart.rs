@@ -0,0 +1,460 @@
+// this_file: crates/core/src/repair/smart.rs
+
+//! Pattern-aware repair built on the v2 error recovery engine.
+//!
+//! [`JsonRepairer`] only appends closing brackets, so input with a wrong
+//! closing bracket or a missing colon comes out of it still broken.
+//! [`smart_repair`] first fixes those structural mistakes from the token
+//! stream, then alternates bracket balancing with the suggestions of
+//! [`ErrorRecoveryEngineV2`] until the document parses.
+
+use super::JsonRepairer;
+use crate::ast::{Token, Value};
+use crate::error::recovery_v2::RecoveryConfig;
+use crate::error::repair::{EnhancedParseResult, ParsingTier, RepairAction, RepairType};
+use crate::error::{Error, ErrorContext, ErrorRecoveryEngineV2};
+use crate::lexer::{JsonLexer, LogosLexer};
+use crate::parser::{parse_with_fallback, parse_with_options, ParserOptions};
+
+/// Parses `input`, repairing it with pattern recognition when it is malformed.
+///
+/// Input the forgiving parser accepts is returned unchanged. Otherwise the
+/// following patterns are fixed, in this order:
+///
+/// - **Mismatched closing bracket**: a closer that matches an enclosing
+///   container closes the containers left open inside it (`[1, {"a": 2]`
+///   becomes `[1, {"a": 2}]`); a closer that matches no open container is
+///   replaced by the one expected (`{"a": 1]` becomes `{"a": 1}`).
+/// - **Missing colon**: an object key directly followed by a value gets a
+///   `:` inserted after it (`{"a" 1}` becomes `{"a": 1}`).
+/// - **Unclosed containers and trailing closers**, via [`JsonRepairer`].
+/// - **Anything else the v2 engine recognizes** (unterminated strings,
+///   missing commas, implicit top-level containers, ...). Suggestions that
+///   let parsing get further are kept even if the input does not parse yet,
+///   for at most [`RecoveryConfig::max_attempts`] rounds.
+///
+/// Every repair is reported with its position in `input`. No more than
+/// `options.max_repairs` repairs are applied; when that budget runs out or
+/// no pattern helps, the result of [`parse_with_fallback`] is returned.
+pub fn smart_repair(input: &str, options: ParserOptions) -> EnhancedParseResult<Value> {
+    let error = match parse_with_options(input, options.clone()) {
+        Ok(value) => return EnhancedParseResult::success(value, ParsingTier::Forgiving),
+        Err(error) => error,
+    };
+
+    match SmartRepairer::new(input, &options).run(error) {
+        Some((value, repairs)) => {
+            EnhancedParseResult::success_with_repairs(value, repairs, ParsingTier::Repair)
+        }
+        None => parse_with_fallback(input, options),
+    }
+}
+
+/// Repair state: the text so far and the edits that produced it.
+struct SmartRepairer<'a> {
+    text: String,
+    /// Applied edits, each positioned in the text as it was when applied.
+    applied: Vec<RepairAction>,
+    options: &'a ParserOptions,
+}
+
+impl<'a> SmartRepairer<'a> {
+    fn new(input: &str, options: &'a ParserOptions) -> Self {
+        Self {
+            text: input.to_string(),
+            applied: Vec::new(),
+            options,
+        }
+    }
+
+    fn run(mut self, mut error: Error) -> Option<(Value, Vec<RepairAction>)> {
+        // Structural edits are made one at a time, each once parsing fails
+        // at or after it, so each is paired with the error it fixes.
+        let mut structural = structural_edits(&self.text).into_iter().peekable();
+
+        let config = RecoveryConfig::default();
+        let mut engine = ErrorRecoveryEngineV2::with_config(config.clone());
+        let mut attempts = 0;
+        loop {
+            if self.applied.len() > self.options.max_repairs {
+                return None;
+            }
+            if !self.applied.is_empty() {
+                match parse_with_options(&self.text, self.options.clone()) {
+                    Ok(value) => return Some((value, self.into_repairs())),
+                    Err(e) => error = e,
+                }
+            }
+
+            let reached = error.position().unwrap_or(self.text.len());
+            if let Some(edit) =
+                structural.next_if(|edit| self.current_position(edit.position) <= reached)
+            {
+                let position = self.current_position(edit.position);
+                self.apply(RepairAction { position, ..edit }, &error);
+                continue;
+            }
+
+            if attempts > config.max_attempts {
+                return None;
+            }
+            attempts += 1;
+
+            let mut repairer = JsonRepairer::new_without_cache(self.options.max_repairs);
+            if let Ok((balanced, repairs)) = repairer.repair(&self.text) {
+                if !repairs.is_empty() && self.parses(&balanced) {
+                    self.text = balanced;
+                    self.applied.extend(repairs.into_iter().map(|repair| RepairAction {
+                        cause: Some(error.clone()),
+                        ..repair
+                    }));
+                    continue;
+                }
+            }
+
+            let context = ErrorContext {
+                position: reached.min(self.text.len()),
+                error: error.clone(),
+                input: self.text.clone(),
+                tokens_before: vec![],
+                partial_ast: None,
+                parsing_context: "top_level".to_string(),
+            };
+            let reached = context.position;
+            let suggestion = engine
+                .suggest_recovery(&context)
+                .into_iter()
+                .filter(|s| s.fixed_input != self.text)
+                .find(
+                    |s| match parse_with_options(&s.fixed_input, self.options.clone()) {
+                        Ok(_) => true,
+                        Err(e) => e.position().is_some_and(|pos| pos > reached),
+                    },
+                )?;
+            let (position, original, replacement) =
+                describe_change(&self.text, &suggestion.fixed_input);
+            self.text = suggestion.fixed_input;
+            self.applied.push(RepairAction {
+                action_type: suggestion.category.into(),
+                position,
+                original,
+                replacement,
+                description: suggestion.description,
+                cause: Some(error.clone()),
+            });
+        }
+    }
+
+    fn parses(&self, text: &str) -> bool {
+        parse_with_options(text, self.options.clone()).is_ok()
+    }
+
+    fn apply(&mut self, edit: RepairAction, cause: &Error) {
+        let end = edit.position + edit.original.len();
+        self.text
+            .replace_range(edit.position..end, &edit.replacement);
+        self.applied.push(RepairAction {
+            cause: Some(cause.clone()),
+            ..edit
+        });
+    }
+
+    /// Maps `position` in the original input forward through the applied
+    /// edits to the text as it is now.
+    fn current_position(&self, mut position: usize) -> usize {
+        for edit in &self.applied {
+            if edit.position + edit.original.len() <= position {
+                position = position + edit.replacement.len() - edit.original.len();
+            }
+        }
+        position
+    }
+
+    /// Maps every applied edit back to a position in the original input.
+    fn into_repairs(self) -> Vec<RepairAction> {
+        let mut repairs: Vec<RepairAction> = self
+            .applied
+            .iter()
+            .enumerate()
+            .map(|(i, edit)| {
+                let earlier = &self.applied[..i];
+                RepairAction {
+                    position: original_position(earlier, edit.position),
+                    cause: edit.cause.clone().map(|error| {
+                        error.map_positions(&|position| original_position(earlier, position))
+                    }),
+                    ..edit.clone()
+                }
+            })
+            .collect();
+        repairs.sort_by_key(|repair| repair.position);
+        repairs
+    }
+}
+
+/// Maps `position` back through `earlier` edits, most recent first.
+fn original_position(earlier: &[RepairAction], mut position: usize) -> usize {
+    for edit in earlier.iter().rev() {
+        let replaced_end = edit.position + edit.replacement.len();
+        if position >= replaced_end {
+            position = position - edit.replacement.len() + edit.original.len();
+        } else if position > edit.position {
+            position = edit.position;
+        }
+    }
+    position
+}
+
+/// Describes the single contiguous change turning `before` into `after`.
+fn describe_change(before: &str, after: &str) -> (usize, String, String) {
+    let prefix: usize = before
+        .chars()
+        .zip(after.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    let suffix: usize = before[prefix..]
+        .chars()
+        .rev()
+        .zip(after[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+    (
+        prefix,
+        before[prefix..before.len() - suffix].to_string(),
+        after[prefix..after.len() - suffix].to_string(),
+    )
+}
+
+/// An open container while scanning for structural mistakes.
+struct Frame {
+    opener: Token,
+    /// Whether an object is waiting for its next key.
+    expecting_key: bool,
+}
+
+impl Frame {
+    fn is_object(&self) -> bool {
+        self.opener == Token::LeftBrace
+    }
+
+    fn closer(&self) -> char {
+        if self.is_object() {
+            '}'
+        } else {
+            ']'
+        }
+    }
+}
+
+/// Finds mismatched closing brackets and missing colons in `input`.
+///
+/// Returns edits ordered by position, each positioned in `input`. Only the
+/// part of `input` before the first lexical error is scanned.
+fn structural_edits(input: &str) -> Vec<RepairAction> {
+    let mut lexer = LogosLexer::new(input);
+    let mut tokens = Vec::new();
+    while let Ok((token, span)) = lexer.next_token() {
+        match token {
+            Token::Eof => break,
+            Token::SingleLineComment | Token::MultiLineComment => {}
+            _ => tokens.push((token, span)),
+        }
+    }
+
+    let mut edits = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    for (i, &(token, span)) in tokens.iter().enumerate() {
+        match token {
+            Token::LeftBrace | Token::LeftBracket => {
+                value_consumed(&mut stack);
+                stack.push(Frame {
+                    opener: token,
+                    expecting_key: true,
+                });
+            }
+            Token::RightBrace | Token::RightBracket => {
+                let opener = if token == Token::RightBrace {
+                    Token::LeftBrace
+                } else {
+                    Token::LeftBracket
+                };
+                match stack.iter().rposition(|frame| frame.opener == opener) {
+                    Some(depth) => {
+                        if depth + 1 < stack.len() {
+                            let closers: String =
+                                stack[depth + 1..].iter().rev().map(Frame::closer).collect();
+                            edits.push(RepairAction {
+                                action_type: RepairType::InsertBracket,
+                                position: span.start,
+                                original: String::new(),
+                                description: format!(
+                                    "Closed containers left open with '{closers}'"
+                                ),
+                                replacement: closers,
+                                cause: None,
+                            });
+                        }
+                        stack.truncate(depth);
+                    }
+                    None => {
+                        if let Some(frame) = stack.pop() {
+                            let found = &input[span.start..span.end];
+                            let expected = frame.closer().to_string();
+                            edits.push(RepairAction {
+                                action_type: RepairType::ReplaceBracket,
+                                position: span.start,
+                                original: found.to_string(),
+                                description: format!(
+                                    "Replaced mismatched '{found}' with '{expected}'"
+                                ),
+                                replacement: expected,
+                                cause: None,
+                            });
+                        }
+                    }
+                }
+            }
+            Token::Comma | Token::Newline => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.expecting_key |= frame.is_object();
+                }
+            }
+            Token::Colon => {
+                if let Some(frame) = stack.last_mut() {
+                    frame.expecting_key = false;
+                }
+            }
+            _ => match stack.last_mut() {
+                Some(frame) if frame.is_object() && frame.expecting_key => {
+                    let next = tokens.get(i + 1).map(|(token, _)| *token);
+                    if next.is_some_and(starts_value) {
+                        edits.push(RepairAction {
+                            action_type: RepairType::InsertText,
+                            position: span.end,
+                            original: String::new(),
+                            replacement: ":".to_string(),
+                            description: "Inserted missing ':' after object key".to_string(),
+                            cause: None,
+                        });
+                        frame.expecting_key = false;
+                    }
+                }
+                _ => value_consumed(&mut stack),
+            },
+        }
+    }
+    edits
+}
+
+/// Marks the value of the innermost object member as read.
+fn value_consumed(stack: &mut [Frame]) {
+    if let Some(frame) = stack.last_mut() {
+        frame.expecting_key |= frame.is_object();
+    }
+}
+
+fn starts_value(token: Token) -> bool {
+    matches!(
+        token,
+        Token::LeftBrace
+            | Token::LeftBracket
+            | Token::String
+            | Token::UnquotedString
+            | Token::Number
+            | Token::True
+            | Token::False
+            | Token::Null
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(json: &str) -> Value {
+        parse_with_options(json, ParserOptions::default()).unwrap()
+    }
+
+    /// Whether the basic repairer's output for `input` parses.
+    fn basic_repair_parses(input: &str) -> bool {
+        let (repaired, _) = JsonRepairer::new(10).repair(input).unwrap();
+        parse_with_options(&repaired, ParserOptions::default()).is_ok()
+    }
+
+    #[test]
+    fn test_mismatched_bracket_types() {
+        let cases = [
+            (
+                r#"{"a": [1, 2}"#,
+                r#"{"a": [1, 2]}"#,
+                RepairType::InsertBracket,
+            ),
+            (
+                r#"[1, {"b": 2]"#,
+                r#"[1, {"b": 2}]"#,
+                RepairType::InsertBracket,
+            ),
+            (
+                r#"{"a": {"b": 1]}"#,
+                r#"{"a": {"b": 1}}"#,
+                RepairType::ReplaceBracket,
+            ),
+            ("[1, 2}", "[1, 2]", RepairType::ReplaceBracket),
+        ];
+        for (input, json, action_type) in cases {
+            assert!(!basic_repair_parses(input), "basic repair of {input}");
+
+            let result = smart_repair(input, ParserOptions::default());
+            assert!(result.is_success(), "smart repair of {input}");
+            assert_eq!(result.value, expected(json), "smart repair of {input}");
+            assert_eq!(result.repairs[0].action_type, action_type);
+        }
+    }
+
+    #[test]
+    fn test_missing_colons() {
+        let input = r#"{"a" 1, b {"c" [true]}}"#;
+        assert!(!basic_repair_parses(input));
+
+        let result = smart_repair(input, ParserOptions::default());
+        assert_eq!(result.value, expected(r#"{"a": 1, "b": {"c": [true]}}"#));
+        let positions: Vec<_> = result.repairs.iter().map(|r| r.position).collect();
+        assert_eq!(positions, [4, 9, 14]);
+        assert!(result
+            .repairs
+            .iter()
+            .all(|r| r.action_type == RepairType::InsertText && r.replacement == ":"));
+    }
+
+    #[test]
+    fn test_combines_patterns_with_engine() {
+        // Missing colon, then an unterminated string and unclosed object
+        let input = r#"{"a" {"b": "x"#;
+        let result = smart_repair(input, ParserOptions::default());
+        assert!(result.is_success());
+        assert_eq!(result.value, expected(r#"{"a": {"b": "x"}}"#));
+        assert_eq!(result.repairs[0].position, 4);
+    }
+
+    #[test]
+    fn test_valid_and_unrepairable_input() {
+        let result = smart_repair("{a: 1, // note\n}", ParserOptions::default());
+        assert_eq!(result.parsing_tier, ParsingTier::Forgiving);
+        assert!(!result.was_repaired());
+
+        let result = smart_repair(r#"{"a": }"#, ParserOptions::default());
+        assert!(!result.is_success());
+    }
+
+    #[test]
+    fn test_respects_repair_budget() {
+        let options = ParserOptions {
+            max_repairs: 1,
+            ..Default::default()
+        };
+        let result = smart_repair(r#"{"a" 1, "b" 2}"#, options);
+        assert!(!result.is_success());
+    }
+}
@@ -0,0 +1,216 @@
+// this_file: crates/core/src/schema.rs
+
+//! JSON Schema inference from sample documents.
+//!
+//! [`infer`] describes a document with the subset of JSON Schema that
+//! [`SchemaValidationPlugin`](crate::plugin::SchemaValidationPlugin)
+//! understands: `type`, `properties`, `required` and `items`. The result is
+//! meant as a starting point to edit, not a complete description of the data.
+
+use crate::ast::Value;
+use rustc_hash::FxHashMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Infers a schema that `value` satisfies.
+///
+/// Objects list every key as a property and require all of them. Array
+/// elements are unified into a single `items` schema: an object property
+/// is required only if every element has it, and elements of different
+/// types give a list of types. Numbers are typed `number`, whether or not
+/// they are integers.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{parse, schema};
+///
+/// let schema = schema::infer(&parse("{id: 1, tags: [a, b]}").unwrap());
+/// assert_eq!(
+///     schema,
+///     parse(r#"{
+///         "type": "object",
+///         "properties": {
+///             "id": {"type": "number"},
+///             "tags": {"type": "array", "items": {"type": "string"}}
+///         },
+///         "required": ["id", "tags"]
+///     }"#)
+///     .unwrap()
+/// );
+/// ```
+pub fn infer(value: &Value) -> Value {
+    let mut shape = Shape::default();
+    shape.add(value);
+    shape.to_schema()
+}
+
+/// What is known about the values seen at one place in a document.
+#[derive(Debug, Default)]
+struct Shape {
+    types: BTreeSet<&'static str>,
+    properties: BTreeMap<String, Shape>,
+    /// Keys present in every object seen; `None` until an object is seen
+    required: Option<BTreeSet<String>>,
+    /// The unified array elements; `None` until an element is seen
+    items: Option<Box<Shape>>,
+}
+
+impl Shape {
+    fn add(&mut self, value: &Value) {
+        self.types.insert(type_name(value));
+        match value {
+            Value::Object(object) => {
+                for (key, member) in object {
+                    self.properties.entry(key.clone()).or_default().add(member);
+                }
+                let keys = object.keys().cloned().collect();
+                self.required = Some(match self.required.take() {
+                    Some(required) => required.intersection(&keys).cloned().collect(),
+                    None => keys,
+                });
+            }
+            Value::Array(array) => {
+                for item in array {
+                    self.items.get_or_insert_with(Default::default).add(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn to_schema(&self) -> Value {
+        let mut schema = FxHashMap::default();
+        let ty = match self.types.len() {
+            1 => Value::String(self.types.iter().next().unwrap().to_string()),
+            _ => self
+                .types
+                .iter()
+                .map(|ty| Value::String(ty.to_string()))
+                .collect(),
+        };
+        schema.insert("type".to_string(), ty);
+
+        if self.types.contains("object") {
+            let properties = self
+                .properties
+                .iter()
+                .map(|(key, shape)| (key.clone(), shape.to_schema()))
+                .collect();
+            let required = self
+                .required
+                .iter()
+                .flatten()
+                .map(|key| Value::String(key.clone()))
+                .collect();
+            schema.insert("properties".to_string(), properties);
+            schema.insert("required".to_string(), required);
+        }
+        if let Some(items) = &self.items {
+            schema.insert("items".to_string(), items.to_schema());
+        }
+        Value::Object(schema)
+    }
+}
+
+/// The JSON Schema type name of `value`.
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use crate::plugin::{ParserPlugin, SchemaValidationPlugin};
+
+    const RECORD: &str = r#"{
+        "id": 42,
+        "name": "Ada",
+        "active": true,
+        "manager": null,
+        "address": {"city": "London", "zip": "N1"},
+        "roles": [
+            {"name": "admin", "level": 3},
+            {"name": "dev", "team": "core"}
+        ],
+        "scores": [1.5, 2, null]
+    }"#;
+
+    #[test]
+    fn test_infer_sample_record() {
+        let schema = infer(&parse(RECORD).unwrap());
+        let expected = parse(
+            r#"{
+            "type": "object",
+            "properties": {
+                "id": {"type": "number"},
+                "name": {"type": "string"},
+                "active": {"type": "boolean"},
+                "manager": {"type": "null"},
+                "address": {
+                    "type": "object",
+                    "properties": {
+                        "city": {"type": "string"},
+                        "zip": {"type": "string"}
+                    },
+                    "required": ["city", "zip"]
+                },
+                "roles": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                            "level": {"type": "number"},
+                            "team": {"type": "string"}
+                        },
+                        "required": ["name"]
+                    }
+                },
+                "scores": {"type": "array", "items": {"type": ["null", "number"]}}
+            },
+            "required": ["active", "address", "id", "manager", "name", "roles", "scores"]
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(schema, expected);
+    }
+
+    #[test]
+    fn test_record_validates_against_inferred_schema() {
+        let record = parse(RECORD).unwrap();
+        let plugin = SchemaValidationPlugin::new(infer(&record));
+        assert!(plugin.validate(&record, "$").is_ok());
+
+        let changed = parse(r#"{"id": "42", "name": "Ada"}"#).unwrap();
+        assert!(plugin.validate(&changed, "$").is_err());
+    }
+
+    #[test]
+    fn test_infer_empty_and_mixed_arrays() {
+        let schema = infer(&parse("[]").unwrap());
+        assert_eq!(schema, parse(r#"{"type": "array"}"#).unwrap());
+
+        let schema = infer(&parse(r#"[{"a": 1}, [true]]"#).unwrap());
+        let expected = parse(
+            r#"{
+            "type": "array",
+            "items": {
+                "type": ["array", "object"],
+                "properties": {"a": {"type": "number"}},
+                "required": ["a"],
+                "items": {"type": "boolean"}
+            }
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(schema, expected);
+    }
+}
@@ -0,0 +1,210 @@
+// this_file: src/streaming/aggregate.rs
+
+//! Aggregation over NDJSON event streams.
+//!
+//! [`NdJsonAggregator`] answers simple questions about a large NDJSON file,
+//! such as how many records it has and what a field adds up to, from the
+//! events of a [`StreamingNdJsonParser`] without building any record.
+
+use super::{StreamingEvent, StreamingNdJsonParser};
+use crate::ast::{JsonPath, PathSegment, Value};
+use crate::error::{Error, Result};
+use crate::parser::ParserOptions;
+use std::collections::BTreeMap;
+
+/// Totals computed by an [`NdJsonAggregator`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NdJsonAggregates {
+    /// Number of records
+    pub records: usize,
+    /// Sum of the numbers found at the sum path
+    pub sum: f64,
+    /// Number of records that had a number at the sum path
+    pub summed: usize,
+    /// Number of records for each value found at the group-by path.
+    ///
+    /// Strings are keyed by their contents and other scalars by their JSON
+    /// text. Records whose value there is missing or a container are not
+    /// counted.
+    pub groups: BTreeMap<String, usize>,
+}
+
+/// Where the next value sits within its parent container.
+enum Frame {
+    /// In an object, under the most recent key
+    Object(Option<String>),
+    /// In an array, at this index
+    Array(usize),
+}
+
+/// Counts, sums and groups the records of an NDJSON stream.
+///
+/// Memory use is bounded by the nesting depth of the records and, for
+/// group-by, the number of distinct group values.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::streaming::NdJsonAggregator;
+///
+/// let mut aggregator = NdJsonAggregator::new()
+///     .sum("$.amount".parse().unwrap())
+///     .group_by("$.type".parse().unwrap());
+/// aggregator.feed("{\"type\": \"sale\", \"amount\": 5}\n").unwrap();
+/// aggregator.feed("{\"type\": \"refund\", \"amount\": -2}\n").unwrap();
+///
+/// let totals = aggregator.finish().unwrap();
+/// assert_eq!(totals.records, 2);
+/// assert_eq!(totals.sum, 3.0);
+/// assert_eq!(totals.groups["sale"], 1);
+/// ```
+pub struct NdJsonAggregator {
+    parser: StreamingNdJsonParser,
+    sum_path: Option<JsonPath>,
+    group_path: Option<JsonPath>,
+    /// Containers enclosing the next value of the current record
+    stack: Vec<Frame>,
+    aggregates: NdJsonAggregates,
+}
+
+impl NdJsonAggregator {
+    /// Create an aggregator that only counts records
+    pub fn new() -> Self {
+        Self::with_options(ParserOptions::default())
+    }
+
+    /// Create an aggregator that parses lines with custom options
+    pub fn with_options(options: ParserOptions) -> Self {
+        Self {
+            parser: StreamingNdJsonParser::with_options(options),
+            sum_path: None,
+            group_path: None,
+            stack: Vec::new(),
+            aggregates: NdJsonAggregates::default(),
+        }
+    }
+
+    /// Also sum the numbers found at `path` in each record
+    pub fn sum(mut self, path: JsonPath) -> Self {
+        self.sum_path = Some(path);
+        self
+    }
+
+    /// Also count records by the value found at `path`
+    pub fn group_by(mut self, path: JsonPath) -> Self {
+        self.group_path = Some(path);
+        self
+    }
+
+    /// Feed a chunk of NDJSON input
+    pub fn feed(&mut self, chunk: &str) -> Result<()> {
+        self.parser.feed(chunk)?;
+        self.drain()
+    }
+
+    /// Signal end of input and return the totals
+    pub fn finish(mut self) -> Result<NdJsonAggregates> {
+        self.parser.finish()?;
+        self.drain()?;
+        Ok(self.aggregates)
+    }
+
+    /// Totals of the records seen so far
+    pub fn aggregates(&self) -> &NdJsonAggregates {
+        &self.aggregates
+    }
+
+    /// Process one event of an NDJSON stream, where
+    /// [`StreamingEvent::EndOfInput`] ends each record
+    pub fn process_event(&mut self, event: StreamingEvent) -> Result<()> {
+        match event {
+            StreamingEvent::ObjectKey(key) => {
+                if let Some(Frame::Object(current)) = self.stack.last_mut() {
+                    *current = Some(key);
+                }
+                return Ok(());
+            }
+            StreamingEvent::EndObject | StreamingEvent::EndArray => {
+                self.stack.pop();
+                self.next_sibling();
+                return Ok(());
+            }
+            StreamingEvent::EndOfInput => {
+                self.aggregates.records += 1;
+                self.stack.clear();
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        if self.sum_path.as_ref().is_some_and(|path| self.at(path)) {
+            if let StreamingEvent::Number(text) = &event {
+                self.aggregates.sum += number_value(text)?;
+                self.aggregates.summed += 1;
+            }
+        }
+        if self.group_path.as_ref().is_some_and(|path| self.at(path)) {
+            let group = match &event {
+                StreamingEvent::String(s) => Some(s.clone()),
+                StreamingEvent::Number(text) => Some(text.clone()),
+                StreamingEvent::Bool(b) => Some(b.to_string()),
+                StreamingEvent::Null => Some("null".to_string()),
+                _ => None,
+            };
+            if let Some(group) = group {
+                *self.aggregates.groups.entry(group).or_default() += 1;
+            }
+        }
+
+        match event {
+            StreamingEvent::StartObject => self.stack.push(Frame::Object(None)),
+            StreamingEvent::StartArray => self.stack.push(Frame::Array(0)),
+            _ => self.next_sibling(),
+        }
+        Ok(())
+    }
+
+    fn drain(&mut self) -> Result<()> {
+        while let Some(event) = self.parser.next_event()? {
+            self.process_event(event)?;
+        }
+        Ok(())
+    }
+
+    /// Moves past a finished value to the next one in its container
+    fn next_sibling(&mut self) {
+        match self.stack.last_mut() {
+            Some(Frame::Array(index)) => *index += 1,
+            Some(Frame::Object(key)) => *key = None,
+            None => {}
+        }
+    }
+
+    /// Whether the next value of the current record is at `path`
+    fn at(&self, path: &JsonPath) -> bool {
+        path.segments().len() == self.stack.len()
+            && path
+                .segments()
+                .iter()
+                .zip(&self.stack)
+                .all(|(segment, frame)| match (segment, frame) {
+                    (PathSegment::Key(want), Frame::Object(Some(key))) => want == key,
+                    (PathSegment::Index(want), Frame::Array(index)) => want == index,
+                    _ => false,
+                })
+    }
+}
+
+impl Default for NdJsonAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads a number event, accepting every form the parser does
+fn number_value(text: &str) -> Result<f64> {
+    match crate::parse(text)? {
+        Value::Number(number) => Ok(number.as_f64()),
+        _ => Err(Error::Custom(format!("Invalid number: {text}"))),
+    }
+}
@@ -18,13 +18,27 @@ impl<R: Read> BufferedStreamingParser<R> {
             Ok(0) => {
                 // End of input reached
                 self.end_of_input = true;
+                if !self.partial_utf8.is_empty() {
+                    return Err(Error::InvalidUtf8(self.lexer.position()));
+                }
                 // Flush any remaining tokens from the lexer
                 let tokens = self.lexer.flush()?;
                 self.token_buffer.extend(tokens);
             }
             Ok(n) => {
-                // Convert bytes to string (handle UTF-8 properly)
-                let input_str = std::str::from_utf8(&buffer[..n])
+                // Convert bytes to string, holding back a character split
+                // across reads until the rest of it arrives
+                let mut bytes = std::mem::take(&mut self.partial_utf8);
+                bytes.extend_from_slice(&buffer[..n]);
+                let valid_len = match std::str::from_utf8(&bytes) {
+                    Ok(_) => bytes.len(),
+                    Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                    Err(e) => {
+                        return Err(Error::InvalidUtf8(self.lexer.position() + e.valid_up_to()))
+                    }
+                };
+                self.partial_utf8 = bytes.split_off(valid_len);
+                let input_str = std::str::from_utf8(&bytes)
                     .map_err(|_| Error::InvalidUtf8(self.lexer.position()))?;
                 
                 // Accumulate input for token content extraction
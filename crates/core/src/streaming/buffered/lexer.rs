@@ -19,6 +19,8 @@ pub enum LexerState {
     InSingleLineComment,
     /// Inside a multi-line comment
     InMultiLineComment { star_seen: bool },
+    /// After a `/` that may start a comment
+    AfterSlash,
     /// Building a number
     InNumber,
     /// Building an identifier (for keywords and unquoted keys)
@@ -31,7 +33,7 @@ pub struct BufferedLexer {
     state: LexerState,
     /// Buffer for the current token being built
     token_buffer: String,
-    /// Position in the overall input stream
+    /// Byte offset in the overall input stream
     position: usize,
     /// Start position of current token
     token_start: usize,
@@ -55,9 +57,11 @@ impl BufferedLexer {
     /// Returns tokens that were completed and whether more input is needed
     pub fn feed(&mut self, input: &str) -> Result<(Vec<(Token, Span)>, bool)> {
         let mut tokens = Vec::new();
-        let mut chars = input.chars().peekable();
-        
-        while let Some(ch) = chars.next() {
+        let mut chars = input.chars();
+        // A character that ended a number or identifier, to lex again
+        let mut reprocess = None;
+
+        while let Some(ch) = reprocess.take().or_else(|| chars.next()) {
             match &self.state {
                 LexerState::Normal => {
                     match ch {
@@ -84,31 +88,14 @@ impl BufferedLexer {
                         }
                         
                         // Comments
-                        '/' if self.config.mode == LexerMode::Forgiving => {
-                            if let Some(&next_ch) = chars.peek() {
-                                match next_ch {
-                                    '/' => {
-                                        chars.next(); // consume second /
-                                        self.position += 1;
-                                        self.state = LexerState::InSingleLineComment;
-                                    }
-                                    '*' => {
-                                        chars.next(); // consume *
-                                        self.position += 1;
-                                        self.state = LexerState::InMultiLineComment { star_seen: false };
-                                    }
-                                    _ => {
-                                        // Not a comment, treat as error
-                                        return Err(Error::UnexpectedChar('/', self.position));
-                                    }
-                                }
-                            } else {
-                                // Need more input to determine if it's a comment
-                                self.token_buffer.push(ch);
-                                self.token_start = self.position;
-                                self.position += 1;
-                                return Ok((tokens, true));
-                            }
+                        '/' if self.config.mode == LexerMode::Forgiving
+                            && (self.config.comment_styles.line_slash
+                                || self.config.comment_styles.block) =>
+                        {
+                            self.state = LexerState::AfterSlash;
+                        }
+                        '#' if self.hash_comments() => {
+                            self.state = LexerState::InSingleLineComment;
                         }
                         
                         // Numbers
@@ -120,7 +107,7 @@ impl BufferedLexer {
                         }
                         
                         // Identifiers (null, true, false, unquoted keys)
-                        'a'..='z' | 'A'..='Z' | '_' => {
+                        'a'..='z' | 'A'..='Z' | '_' | '#' => {
                             self.state = LexerState::InIdentifier;
                             self.token_start = self.position;
                             self.token_buffer.clear();
@@ -153,6 +140,17 @@ impl BufferedLexer {
                     }
                 }
                 
+                LexerState::AfterSlash => match ch {
+                    '/' if self.config.comment_styles.line_slash => {
+                        self.state = LexerState::InSingleLineComment
+                    }
+                    '*' if self.config.comment_styles.block => {
+                        self.state = LexerState::InMultiLineComment { star_seen: false }
+                    }
+                    // Not a comment, treat as error
+                    _ => return Err(Error::UnexpectedChar('/', self.position - 1)),
+                },
+
                 LexerState::InSingleLineComment => {
                     if ch == '\n' {
                         self.state = LexerState::Normal;
@@ -180,13 +178,13 @@ impl BufferedLexer {
                         self.token_buffer.clear();
                         
                         // Reprocess this character
-                        self.position -= 1;
+                        reprocess = Some(ch);
                         continue;
                     }
                 }
                 
                 LexerState::InIdentifier => {
-                    if ch.is_alphanumeric() || ch == '_' {
+                    if ch.is_alphanumeric() || ch == '_' || (ch == '#' && !self.hash_comments()) {
                         self.token_buffer.push(ch);
                     } else {
                         // Identifier complete
@@ -211,13 +209,13 @@ impl BufferedLexer {
                         self.token_buffer.clear();
                         
                         // Reprocess this character
-                        self.position -= 1;
+                        reprocess = Some(ch);
                         continue;
                     }
                 }
             }
             
-            self.position += 1;
+            self.position += ch.len_utf8();
         }
         
         // Check if we need more input
@@ -238,6 +236,7 @@ impl BufferedLexer {
             LexerState::InMultiLineComment { .. } => {
                 Err(Error::UnexpectedEof(self.position))
             }
+            LexerState::AfterSlash => Err(Error::UnexpectedChar('/', self.position - 1)),
             LexerState::InNumber => {
                 let token = vec![(Token::Number, self.make_span_from_start())];
                 self.state = LexerState::Normal;
@@ -272,7 +271,29 @@ impl BufferedLexer {
     pub fn position(&self) -> usize {
         self.position
     }
+
+    /// Set the stream offset of the next input fed, when starting partway
+    /// through a stream
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// Get the stream offset where the token currently being read starts, or
+    /// the current position between tokens
+    pub fn unconsumed_start(&self) -> usize {
+        match self.state {
+            LexerState::InString { .. } | LexerState::InNumber | LexerState::InIdentifier => {
+                self.token_start
+            }
+            _ => self.position,
+        }
+    }
     
+    /// Whether `#` starts a comment rather than being part of a word
+    fn hash_comments(&self) -> bool {
+        self.config.mode == LexerMode::Forgiving && self.config.comment_styles.hash
+    }
+
     /// Create a span for a single character token
     fn make_span(&self, len: usize) -> Span {
         Span {
@@ -1,13 +1,14 @@
 // this_file: crates/core/src/streaming/buffered/mod.rs
 
 use crate::ast::{Token, Value};
-use crate::error::{Error, Result};
+use crate::error::{Error, Result, Span};
 use crate::lexer::{LexerConfig, LexerMode};
+use crate::parser::number::read_number;
 use crate::parser::ParserOptions;
-use crate::streaming::{ParserContext, StreamingEvent};
+use crate::streaming::{OpenContainer, ParserContext, StreamCheckpoint, StreamingEvent};
 // use rustc_hash::FxHashMap;
 use std::collections::VecDeque;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, Read, Seek, SeekFrom};
 
 pub mod buffer;
 pub mod lexer;
@@ -52,9 +53,10 @@ pub struct BufferedStreamingParser<R: Read> {
     /// Buffered lexer for tokenization
     lexer: BufferedLexer,
     /// Token buffer for parsed tokens
-    token_buffer: VecDeque<(Token, crate::error::Span)>,
-    /// Event buffer for generated events
-    event_buffer: VecDeque<StreamingEvent>,
+    token_buffer: VecDeque<(Token, Span)>,
+    /// Event buffer for generated events, with the span each came from and
+    /// the line that span ends on
+    event_buffer: VecDeque<(StreamingEvent, Span, usize)>,
     /// Parser state stack
     state_stack: Vec<ParserContext>,
     /// Whether we've reached the end of input
@@ -62,8 +64,18 @@ pub struct BufferedStreamingParser<R: Read> {
     /// Temporary state for parsing complex values
     #[allow(dead_code)]
     temp_state: TempParsingState,
-    /// Accumulated input for token content extraction
+    /// Input not yet consumed, for token content extraction
     input_accumulator: String,
+    /// Stream offset of the first byte of `input_accumulator`
+    accumulator_offset: usize,
+    /// Trailing bytes of a UTF-8 sequence split across reads
+    partial_utf8: Vec<u8>,
+    /// Stream offset up to which lines have been counted
+    line_offset: usize,
+    /// Line number at `line_offset`
+    line: usize,
+    /// Where the last event returned ended, for checkpoints
+    checkpoint: StreamCheckpoint,
 }
 
 impl<R: Read> BufferedStreamingParser<R> {
@@ -87,6 +99,10 @@ impl<R: Read> BufferedStreamingParser<R> {
             buffer_size,
             max_depth: config.parser_options.max_depth,
             track_positions: true,
+            unicode_whitespace: config.parser_options.allow_unicode_whitespace,
+            nested_comments: config.parser_options.allow_nested_block_comments,
+            max_comment_length: config.parser_options.max_comment_length,
+            comment_styles: config.parser_options.comment_styles,
         };
         
         BufferedStreamingParser {
@@ -99,29 +115,160 @@ impl<R: Read> BufferedStreamingParser<R> {
             end_of_input: false,
             temp_state: TempParsingState::default(),
             input_accumulator: String::new(),
+            accumulator_offset: 0,
+            partial_utf8: Vec::new(),
+            line_offset: 0,
+            line: 1,
+            checkpoint: StreamCheckpoint {
+                offset: 0,
+                line: 1,
+                containers: Vec::new(),
+            },
         }
     }
 
+    /// Creates a parser that carries on from `checkpoint`, seeking `reader`
+    /// to the checkpoint's offset.
+    ///
+    /// Events carry on as if the stream had been read from the start, and
+    /// spans stay offsets into the whole stream.
+    pub fn resume_from(
+        mut reader: R,
+        checkpoint: &StreamCheckpoint,
+        config: BufferedStreamingConfig,
+    ) -> Result<Self>
+    where
+        R: Seek,
+    {
+        reader
+            .seek(SeekFrom::Start(checkpoint.offset as u64))
+            .map_err(|e| Error::Custom(format!("IO error: {e}")))?;
+        let mut parser = Self::with_config(reader, config);
+        parser.lexer.set_position(checkpoint.offset);
+        parser.accumulator_offset = checkpoint.offset;
+        parser.line_offset = checkpoint.offset;
+        parser.line = checkpoint.line;
+        // Only the kind of each container matters here: a string is read
+        // as a key until a colon says otherwise
+        parser.state_stack = checkpoint
+            .containers
+            .iter()
+            .map(|container| match container {
+                OpenContainer::Object => ParserContext::Object {
+                    expecting_key: true,
+                },
+                OpenContainer::Array => ParserContext::Array {
+                    first_element: false,
+                },
+            })
+            .collect();
+        parser.checkpoint = checkpoint.clone();
+        Ok(parser)
+    }
+
+    /// Returns a checkpoint straight after the last event returned, for
+    /// [`resume_from`](Self::resume_from).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use vexy_json_core::streaming::{
+    ///     BufferedStreamingConfig, BufferedStreamingParser, StreamingEvent,
+    /// };
+    ///
+    /// let input = r#"{"a": [1, 2], "b": 3}"#;
+    /// let mut parser = BufferedStreamingParser::new(Cursor::new(input));
+    /// for _ in 0..4 {
+    ///     parser.next_event().unwrap();
+    /// }
+    /// let checkpoint = parser.checkpoint();
+    /// assert_eq!(checkpoint.containers.len(), 2);
+    ///
+    /// let mut parser = BufferedStreamingParser::resume_from(
+    ///     Cursor::new(input),
+    ///     &checkpoint,
+    ///     BufferedStreamingConfig::default(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     parser.collect_events().unwrap(),
+    ///     [
+    ///         StreamingEvent::Number("2".to_string()),
+    ///         StreamingEvent::EndArray,
+    ///         StreamingEvent::ObjectKey("b".to_string()),
+    ///         StreamingEvent::Number("3".to_string()),
+    ///         StreamingEvent::EndObject,
+    ///     ]
+    /// );
+    /// ```
+    pub fn checkpoint(&self) -> StreamCheckpoint {
+        self.checkpoint.clone()
+    }
+
     /// Returns the next streaming event, if available.
     pub fn next_event(&mut self) -> Result<Option<StreamingEvent>> {
-        // Return buffered events first
-        if let Some(event) = self.event_buffer.pop_front() {
-            return Ok(Some(event));
-        }
+        Ok(self.next_event_with_span()?.map(|(event, _)| event))
+    }
 
-        // If we've reached end of input and no more events, return None
-        if self.end_of_input && self.token_buffer.is_empty() {
-            return Ok(None);
-        }
+    /// Returns the next streaming event together with the span of input it
+    /// came from.
+    ///
+    /// Spans are byte offsets into the whole stream, across all reads.
+    /// Container events cover their bracket.
+    pub fn next_event_with_span(&mut self) -> Result<Option<(StreamingEvent, Span)>> {
+        loop {
+            // Return buffered events first
+            if let Some((event, span, line)) = self.event_buffer.pop_front() {
+                let containers = &mut self.checkpoint.containers;
+                match event {
+                    StreamingEvent::StartObject => containers.push(OpenContainer::Object),
+                    StreamingEvent::StartArray => containers.push(OpenContainer::Array),
+                    StreamingEvent::EndObject | StreamingEvent::EndArray => {
+                        containers.pop();
+                    }
+                    _ => {}
+                }
+                self.checkpoint.offset = span.end;
+                self.checkpoint.line = line;
+                return Ok(Some((event, span)));
+            }
 
-        // Try to fill buffers and generate more events
-        self.fill_buffers()?;
-        self.process_tokens()?;
+            // If we've reached end of input and no more events, return None
+            if self.end_of_input && self.token_buffer.is_empty() {
+                return Ok(None);
+            }
 
-        // Return the next event if available
-        Ok(self.event_buffer.pop_front())
+            // Read until a complete token yields an event; a short read may
+            // end mid-token
+            self.fill_buffers()?;
+            self.process_tokens()?;
+            self.discard_consumed_input();
+        }
     }
 
+    /// Drops input that no buffered or partial token refers to.
+    fn discard_consumed_input(&mut self) {
+        let keep_from = self
+            .token_buffer
+            .front()
+            .map_or_else(|| self.lexer.unconsumed_start(), |(_, span)| span.start);
+        self.count_lines_to(keep_from);
+        self.input_accumulator
+            .drain(..keep_from - self.accumulator_offset);
+        self.accumulator_offset = keep_from;
+    }
+
+    /// Counts the lines in the input up to stream offset `end`, which must
+    /// not have been discarded yet.
+    fn count_lines_to(&mut self, end: usize) {
+        if end > self.line_offset {
+            let start = self.line_offset - self.accumulator_offset;
+            let counted = &self.input_accumulator.as_bytes()[start..end - self.accumulator_offset];
+            self.line += counted.iter().filter(|&&b| b == b'\n').count();
+            self.line_offset = end;
+        }
+    }
 
     /// Processes tokens from the token buffer and generates events.
     fn process_tokens(&mut self) -> Result<()> {
@@ -134,7 +281,8 @@ impl<R: Read> BufferedStreamingParser<R> {
 
             let event = self.token_to_event(token, span)?;
             if let Some(event) = event {
-                self.event_buffer.push_back(event);
+                self.count_lines_to(span.end);
+                self.event_buffer.push_back((event, span, self.line));
             }
         }
 
@@ -142,9 +290,11 @@ impl<R: Read> BufferedStreamingParser<R> {
     }
 
     /// Extracts token content from input using span (for strings and numbers)
-    fn extract_token_content(&self, span: &crate::error::Span) -> String {
-        if span.start < self.input_accumulator.len() && span.end <= self.input_accumulator.len() {
-            self.input_accumulator[span.start..span.end].to_string()
+    fn extract_token_content(&self, span: &Span) -> String {
+        let start = span.start.wrapping_sub(self.accumulator_offset);
+        let end = span.end.wrapping_sub(self.accumulator_offset);
+        if start < self.input_accumulator.len() && end <= self.input_accumulator.len() {
+            self.input_accumulator[start..end].to_string()
         } else {
             // Fallback for out-of-bounds spans
             String::new()
@@ -152,7 +302,7 @@ impl<R: Read> BufferedStreamingParser<R> {
     }
 
     /// Converts a token to a streaming event.
-    fn token_to_event(&mut self, token: Token, span: crate::error::Span) -> Result<Option<StreamingEvent>> {
+    fn token_to_event(&mut self, token: Token, span: Span) -> Result<Option<StreamingEvent>> {
         match token {
             Token::LeftBrace => {
                 self.state_stack.push(ParserContext::Object {
@@ -260,26 +410,6 @@ impl<R: Read> BufferedStreamingParser<R> {
         }
     }
 
-    /// Extracts the content from a quoted string token (simplified version).
-    /// This is a basic implementation that may need improvement for full escape handling.
-    fn extract_string_content(&self, token_str: &str) -> Result<String> {
-        if token_str.len() < 2 {
-            return Err(Error::UnterminatedString(self.lexer.position()));
-        }
-
-        // Remove quotes
-        let content = &token_str[1..token_str.len() - 1];
-
-        // Basic unescaping (simplified)
-        Ok(content
-            .replace("\\\"", "\"")
-            .replace("\\'", "'")
-            .replace("\\\\", "\\")
-            .replace("\\n", "\n")
-            .replace("\\t", "\t")
-            .replace("\\r", "\r"))
-    }
-
     /// Returns the current position in the input stream.
     pub fn position(&self) -> usize {
         self.lexer.position()
@@ -309,7 +439,7 @@ impl<R: Read> BufferedStreamingParser<R> {
         let mut value_stack: Vec<Value> = Vec::new();
         let mut key_stack: Vec<String> = Vec::new();
 
-        while let Some(event) = self.next_event()? {
+        while let Some((event, span)) = self.next_event_with_span()? {
             match event {
                 StreamingEvent::StartObject => {
                     value_stack.push(Value::Object(rustc_hash::FxHashMap::default()));
@@ -351,13 +481,8 @@ impl<R: Read> BufferedStreamingParser<R> {
                     self.add_value_to_parent(&mut value_stack, &mut key_stack, value)?;
                 }
                 StreamingEvent::Number(n) => {
-                    let value = if let Ok(int_val) = n.parse::<i64>() {
-                        Value::Number(crate::ast::Number::Integer(int_val))
-                    } else if let Ok(float_val) = n.parse::<f64>() {
-                        Value::Number(crate::ast::Number::Float(float_val))
-                    } else {
-                        return Err(Error::InvalidNumber(self.lexer.position()));
-                    };
+                    let number = read_number(&n, span.start, &self.config.parser_options)?;
+                    let value = Value::Number(number);
                     self.add_value_to_parent(&mut value_stack, &mut key_stack, value)?;
                 }
                 StreamingEvent::Bool(b) => {
@@ -388,22 +513,19 @@ impl<R: Read> BufferedStreamingParser<R> {
         key_stack: &mut Vec<String>,
         value: Value,
     ) -> Result<()> {
-        if value_stack.is_empty() {
-            return Ok(());
-        }
-
-        match value_stack.last_mut().unwrap() {
-            Value::Object(ref mut obj) => {
+        match value_stack.last_mut() {
+            None => {}
+            Some(Value::Object(obj)) => {
                 if let Some(key) = key_stack.pop() {
                     obj.insert(key, value);
                 } else {
                     return Err(Error::UnexpectedChar('\0', self.lexer.position()));
                 }
             }
-            Value::Array(ref mut arr) => {
+            Some(Value::Array(arr)) => {
                 arr.push(value);
             }
-            _ => return Err(Error::UnexpectedChar('\0', self.lexer.position())),
+            Some(_) => return Err(Error::UnexpectedChar('\0', self.lexer.position())),
         }
 
         Ok(())
@@ -0,0 +1,35 @@
+// this_file: crates/core/src/streaming/checkpoint.rs
+
+//! Checkpoints for stopping a streaming parse and resuming it later.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Where a streaming parser had got to, for resuming it later, possibly in
+/// another process.
+///
+/// A checkpoint marks the point straight after the last value the parser
+/// handed out, so input after it is read again on resuming. Returned by
+/// [`NdJsonParser::checkpoint`](super::NdJsonParser::checkpoint) and
+/// [`BufferedStreamingParser::checkpoint`](super::BufferedStreamingParser::checkpoint).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StreamCheckpoint {
+    /// Byte offset in the whole stream to resume reading from
+    pub offset: usize,
+    /// Line number at `offset`, starting from 1
+    pub line: usize,
+    /// Containers open at `offset`, outermost first. Always empty for
+    /// NDJSON, where each value is on a line of its own.
+    pub containers: Vec<OpenContainer>,
+}
+
+/// A container left open at a [`StreamCheckpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OpenContainer {
+    /// An object
+    Object,
+    /// An array
+    Array,
+}
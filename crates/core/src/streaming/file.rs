@@ -0,0 +1,148 @@
+// this_file: crates/core/src/streaming/file.rs
+
+//! Streaming events from a file without reading it into memory.
+
+use crate::error::{Error, Result};
+use crate::parser::ParserOptions;
+use crate::streaming::{StreamingEvent, StreamingParser};
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+/// Bytes read from the file at a time.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Streams the events of the file at `path`, parsed with default options.
+///
+/// # Examples
+///
+/// ```no_run
+/// use vexy_json_core::streaming::{events_from_file, StreamingEvent};
+///
+/// let mut numbers = 0;
+/// for event in events_from_file("measurements.json").unwrap() {
+///     if let StreamingEvent::Number(_) = event.unwrap() {
+///         numbers += 1;
+///     }
+/// }
+/// println!("{numbers} numbers");
+/// ```
+pub fn events_from_file(path: impl AsRef<Path>) -> Result<FileEvents> {
+    events_from_file_with_options(path, ParserOptions::default())
+}
+
+/// Streams the events of the file at `path`, parsed with `options`.
+///
+/// Opening the file is the only I/O done up front; failing to open it is
+/// an [`Error::Io`].
+pub fn events_from_file_with_options(
+    path: impl AsRef<Path>,
+    options: ParserOptions,
+) -> Result<FileEvents> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|error| Error::io(path, error))?;
+    Ok(FileEvents {
+        file,
+        path: path.to_path_buf(),
+        parser: StreamingParser::with_options(options),
+        pending: Vec::new(),
+        bytes_read: 0,
+        decoded: 0,
+        eof: false,
+        done: false,
+    })
+}
+
+/// The events of a file, read a chunk at a time as they are needed.
+///
+/// Memory use depends on the size of a chunk and of the largest single
+/// token, not on the size of the file. A byte order mark at the start of
+/// the file is skipped, and invalid UTF-8 is an [`Error::InvalidUtf8`] at
+/// its byte offset in the file.
+///
+/// The last event is [`StreamingEvent::EndOfInput`]; iteration also stops
+/// after the first error.
+#[derive(Debug)]
+pub struct FileEvents {
+    file: File,
+    path: PathBuf,
+    parser: StreamingParser,
+    /// Bytes read but not yet fed to the parser: an incomplete UTF-8
+    /// sequence at the end of the last chunk
+    pending: Vec<u8>,
+    bytes_read: u64,
+    /// Bytes fed to the parser so far
+    decoded: usize,
+    eof: bool,
+    done: bool,
+}
+
+impl FileEvents {
+    /// Number of bytes read from the file so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    fn next_event(&mut self) -> Result<Option<StreamingEvent>> {
+        loop {
+            if let Some(event) = self.parser.next_event()? {
+                return Ok(Some(event));
+            }
+            if self.eof {
+                return Ok(None);
+            }
+            self.read_chunk()?;
+        }
+    }
+
+    /// Reads the next chunk and feeds the parser every complete character
+    /// in it, finishing the parse at the end of the file.
+    fn read_chunk(&mut self) -> Result<()> {
+        let start = self.pending.len();
+        self.pending.resize(start + CHUNK_SIZE, 0);
+        let read = loop {
+            match self.file.read(&mut self.pending[start..]) {
+                Ok(read) => break read,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(Error::io(&self.path, error)),
+            }
+        };
+        self.pending.truncate(start + read);
+        self.bytes_read += read as u64;
+        self.eof = read == 0;
+
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            // The rest of the character is in the next chunk
+            Err(error) if error.error_len().is_none() && !self.eof => error.valid_up_to(),
+            Err(error) => return Err(Error::InvalidUtf8(self.decoded + error.valid_up_to())),
+        };
+        let text = std::str::from_utf8(&self.pending[..valid])
+            .map_err(|error| Error::InvalidUtf8(self.decoded + error.valid_up_to()))?;
+        let text = match self.decoded {
+            0 => text.strip_prefix('\u{feff}').unwrap_or(text),
+            _ => text,
+        };
+        self.parser.feed(text)?;
+        self.decoded += valid;
+        self.pending.drain(..valid);
+
+        if self.eof {
+            self.parser.finish()?;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for FileEvents {
+    type Item = Result<StreamingEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.next_event();
+        self.done = !matches!(&result, Ok(Some(event)) if *event != StreamingEvent::EndOfInput);
+        result.transpose()
+    }
+}
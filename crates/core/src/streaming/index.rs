@@ -0,0 +1,346 @@
+// this_file: crates/core/src/streaming/index.rs
+
+//! Offset indexes for reading single values out of a large document.
+//!
+//! [`build_index`] streams through a document once, recording the byte span
+//! of every value whose path matches an [`IndexSpec`]. The [`JsonIndex`] it
+//! returns can be kept, on disk if need be, and used later to parse just one
+//! of those values, reading only its bytes.
+
+use super::{BufferedStreamingConfig, BufferedStreamingParser, StreamingEvent};
+use crate::ast::visitor::{parse_path, PathSelector};
+use crate::ast::{JsonPath, PathSegment, Value};
+use crate::error::{Error, Result, Span};
+use crate::parser::{parse_with_options, ParserOptions};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::str::FromStr;
+
+/// A path that may match many values, such as `$.items[*]`.
+///
+/// Written as [`JsonPath`] is, with `[*]` standing for any array index and
+/// `.*` for any object key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathPattern {
+    segments: Vec<PathSelector>,
+}
+
+impl PathPattern {
+    /// Whether the next value inside `frames` is at a matching path
+    fn matches(&self, frames: &[Frame]) -> bool {
+        self.segments.len() == frames.len()
+            && self
+                .segments
+                .iter()
+                .zip(frames)
+                .all(|(segment, frame)| match (segment, frame) {
+                    (PathSelector::Key(want), Frame::Object(Some(key))) => want == key,
+                    (PathSelector::AnyKey, Frame::Object(Some(_))) => true,
+                    (PathSelector::Index(want), Frame::Array(index)) => want == index,
+                    (PathSelector::AnyIndex, Frame::Array(_)) => true,
+                    _ => false,
+                })
+    }
+}
+
+impl FromStr for PathPattern {
+    type Err = Error;
+
+    fn from_str(pattern: &str) -> Result<Self> {
+        let invalid = || Error::Custom(format!("Invalid path pattern: {pattern}"));
+        let segments = parse_path(pattern).map_err(|_| invalid())?;
+        if segments.contains(&PathSelector::Descendants) {
+            return Err(invalid());
+        }
+        Ok(PathPattern { segments })
+    }
+}
+
+/// The values [`build_index`] records, by path.
+#[derive(Debug, Clone, Default)]
+pub struct IndexSpec {
+    patterns: Vec<PathPattern>,
+}
+
+impl IndexSpec {
+    /// Create a spec that indexes nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also index the values matching `pattern`
+    pub fn pattern(mut self, pattern: PathPattern) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+}
+
+/// Byte spans of values in a document, by path, for reading them without
+/// parsing the rest. Built by [`build_index`].
+///
+/// With the `serde` feature the index can be saved and loaded again.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JsonIndex {
+    /// Span of each indexed value, keyed by its path as [`JsonPath`]
+    /// displays it, which quotes keys as needed so no two paths collide
+    spans: BTreeMap<String, Span>,
+    /// Options to parse indexed values with
+    options: ParserOptions,
+}
+
+impl JsonIndex {
+    /// Number of indexed values
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Whether no value was indexed
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// The span of the value at `path` in the document, if it was indexed
+    pub fn span(&self, path: &JsonPath) -> Option<Span> {
+        self.spans.get(&path.to_string()).copied()
+    }
+
+    /// Parses the value at `path`, seeking `reader` to it and reading only
+    /// its bytes. Returns `None` if no value was indexed at `path`.
+    ///
+    /// `reader` must hold the document the index was built from. Error
+    /// positions are relative to the start of the value.
+    pub fn read_value<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        path: &JsonPath,
+    ) -> Result<Option<Value>> {
+        let Some(span) = self.span(path) else {
+            return Ok(None);
+        };
+        let io_error = |e: std::io::Error| Error::Custom(format!("IO error: {e}"));
+        reader
+            .seek(SeekFrom::Start(span.start as u64))
+            .map_err(io_error)?;
+        let mut bytes = Vec::with_capacity(span.end - span.start);
+        reader
+            .take((span.end - span.start) as u64)
+            .read_to_end(&mut bytes)
+            .map_err(io_error)?;
+        if bytes.len() < span.end - span.start {
+            return Err(Error::UnexpectedEof(span.start + bytes.len()));
+        }
+        self.parse_slice(&bytes).map(Some)
+    }
+
+    /// Parses the value at `path` out of `document`, such as a memory-mapped
+    /// file, touching only its bytes. Returns `None` if no value was indexed
+    /// at `path`.
+    pub fn value_in(&self, document: &[u8], path: &JsonPath) -> Result<Option<Value>> {
+        let Some(span) = self.span(path) else {
+            return Ok(None);
+        };
+        let bytes = document
+            .get(span.start..span.end)
+            .ok_or(Error::UnexpectedEof(document.len()))?;
+        self.parse_slice(bytes).map(Some)
+    }
+
+    fn parse_slice(&self, bytes: &[u8]) -> Result<Value> {
+        let text = std::str::from_utf8(bytes).map_err(|e| Error::InvalidUtf8(e.valid_up_to()))?;
+        parse_with_options(text, self.options.clone())
+    }
+}
+
+/// Where the next value sits within its parent container.
+enum Frame {
+    /// In an object, under the most recent key
+    Object(Option<String>),
+    /// In an array, at this index
+    Array(usize),
+}
+
+/// Moves past a finished value to the next one in its container
+fn next_sibling(frames: &mut [Frame]) {
+    match frames.last_mut() {
+        Some(Frame::Array(index)) => *index += 1,
+        Some(Frame::Object(key)) => *key = None,
+        None => {}
+    }
+}
+
+/// The path of the next value inside `frames`
+fn path_of(frames: &[Frame]) -> JsonPath {
+    let mut path = JsonPath::new();
+    for frame in frames {
+        path.push(match frame {
+            Frame::Object(key) => PathSegment::Key(key.clone().unwrap_or_default()),
+            Frame::Array(index) => PathSegment::Index(*index),
+        });
+    }
+    path
+}
+
+/// Reads the document in `reader` once, recording the span of every value
+/// matching `spec`.
+///
+/// Memory use depends on the nesting depth and the number of values
+/// indexed, not on the size of the document. Where a path occurs more than
+/// once, as with repeated keys, the first value is indexed.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use vexy_json_core::streaming::{build_index, IndexSpec};
+/// use vexy_json_core::ParserOptions;
+///
+/// let document = r#"{"items": [{"id": 1}, {"id": 2}, {"id": 3}]}"#;
+/// let spec = IndexSpec::new().pattern("$.items[*]".parse().unwrap());
+/// let index = build_index(Cursor::new(document), ParserOptions::default(), &spec).unwrap();
+/// assert_eq!(index.len(), 3);
+///
+/// let path = "$.items[2]".parse().unwrap();
+/// let item = index.read_value(&mut Cursor::new(document), &path).unwrap().unwrap();
+/// assert_eq!(item["id"].as_i64(), Some(3));
+/// ```
+pub fn build_index<R: Read>(
+    reader: R,
+    options: ParserOptions,
+    spec: &IndexSpec,
+) -> Result<JsonIndex> {
+    let config = BufferedStreamingConfig {
+        parser_options: options.clone(),
+        ..Default::default()
+    };
+    let mut parser = BufferedStreamingParser::with_config(reader, config);
+    let mut frames = Vec::new();
+    // Indexed containers not closed yet, with their depth, path and start
+    let mut open: Vec<(usize, JsonPath, usize)> = Vec::new();
+    let mut spans = BTreeMap::new();
+
+    while let Some((event, span)) = parser.next_event_with_span()? {
+        match event {
+            StreamingEvent::ObjectKey(key) => {
+                if let Some(Frame::Object(current)) = frames.last_mut() {
+                    *current = Some(key);
+                }
+                continue;
+            }
+            StreamingEvent::EndObject | StreamingEvent::EndArray => {
+                frames.pop();
+                if open
+                    .last()
+                    .is_some_and(|(depth, ..)| *depth == frames.len())
+                {
+                    let (_, path, start) = open.pop().unwrap();
+                    spans
+                        .entry(path.to_string())
+                        .or_insert(Span::new(start, span.end));
+                }
+                next_sibling(&mut frames);
+                continue;
+            }
+            StreamingEvent::EndOfInput => continue,
+            _ => {}
+        }
+
+        let matched = spec.patterns.iter().any(|pattern| pattern.matches(&frames));
+        match event {
+            StreamingEvent::StartObject | StreamingEvent::StartArray => {
+                if matched {
+                    open.push((frames.len(), path_of(&frames), span.start));
+                }
+                frames.push(if event == StreamingEvent::StartObject {
+                    Frame::Object(None)
+                } else {
+                    Frame::Array(0)
+                });
+            }
+            _ => {
+                if matched {
+                    spans.entry(path_of(&frames).to_string()).or_insert(span);
+                }
+                next_sibling(&mut frames);
+            }
+        }
+    }
+
+    if !frames.is_empty() {
+        return Err(Error::UnexpectedEof(parser.position()));
+    }
+    Ok(JsonIndex { spans, options })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn index(document: &str, patterns: &[&str]) -> JsonIndex {
+        let spec = patterns.iter().fold(IndexSpec::new(), |spec, pattern| {
+            spec.pattern(pattern.parse().unwrap())
+        });
+        build_index(Cursor::new(document), ParserOptions::default(), &spec).unwrap()
+    }
+
+    fn text_at<'a>(index: &JsonIndex, document: &'a str, path: &str) -> Option<&'a str> {
+        let span = index.span(&path.parse().unwrap())?;
+        Some(&document[span.start..span.end])
+    }
+
+    #[test]
+    fn test_patterns() {
+        let document = r#"{"a": {"x": [1, {"y": "two"}], "z": null}, "b": [true, []]}"#;
+        let index = index(document, &["$.a.*", "$.a.x[1].y", "$.b[*]", "$"]);
+        assert_eq!(index.len(), 6);
+        assert_eq!(text_at(&index, document, "$"), Some(document));
+        assert_eq!(
+            text_at(&index, document, "$.a.x"),
+            Some(r#"[1, {"y": "two"}]"#)
+        );
+        assert_eq!(text_at(&index, document, "$.a.z"), Some("null"));
+        assert_eq!(text_at(&index, document, "$.a.x[1].y"), Some("\"two\""));
+        assert_eq!(text_at(&index, document, "$.b[0]"), Some("true"));
+        assert_eq!(text_at(&index, document, "$.b[1]"), Some("[]"));
+        assert_eq!(text_at(&index, document, "$.a"), None);
+
+        for invalid in ["a[*]", "$.", "$[x]", "$[*", "$..a"] {
+            assert!(invalid.parse::<PathPattern>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_keys_with_dots_do_not_collide() {
+        let document = r#"{"a.b": 1, "a": {"b": 2}}"#;
+        let all = index(document, &["$.*", "$.a.*"]);
+        assert_eq!(all.len(), 3);
+        assert_eq!(text_at(&all, document, "$['a.b']"), Some("1"));
+        assert_eq!(text_at(&all, document, "$.a.b"), Some("2"));
+        assert_eq!(text_at(&all, document, "$.a"), Some(r#"{"b": 2}"#));
+
+        let quoted = index(document, &["$['a.b']"]);
+        assert_eq!(quoted.len(), 1);
+        assert_eq!(text_at(&quoted, document, "$['a.b']"), Some("1"));
+    }
+
+    #[test]
+    fn test_unclosed_document_fails() {
+        let spec = IndexSpec::new().pattern("$[*]".parse().unwrap());
+        assert!(build_index(Cursor::new("[1, 2"), ParserOptions::default(), &spec).is_err());
+    }
+
+    #[test]
+    fn test_value_in_slice() {
+        let document = "[10, {\"k\": \"v\"}]";
+        let index = index(document, &["$[*]"]);
+        let path = "$[1]".parse().unwrap();
+        let value = index.value_in(document.as_bytes(), &path).unwrap().unwrap();
+        assert_eq!(value, crate::parse("{\"k\": \"v\"}").unwrap());
+        let missing = "$[2]".parse().unwrap();
+        assert_eq!(index.value_in(document.as_bytes(), &missing).unwrap(), None);
+        assert!(index.value_in(b"[10]", &path).is_err());
+    }
+}
@@ -241,8 +241,7 @@ impl StreamingLexer {
                 self.emit_number_token(start_pos)?;
                 self.state = LexerState::Normal;
                 // Reprocess this character in normal state
-                self.position -= ch.len_utf8();
-                return self.feed_char(ch);
+                return self.process_normal(ch);
             }
         }
         Ok(())
@@ -259,8 +258,7 @@ impl StreamingLexer {
                 self.emit_identifier_token(start_pos)?;
                 self.state = LexerState::Normal;
                 // Reprocess this character in normal state
-                self.position -= ch.len_utf8();
-                return self.feed_char(ch);
+                return self.process_normal(ch);
             }
         }
         Ok(())
@@ -277,8 +275,7 @@ impl StreamingLexer {
             );
             self.state = LexerState::Normal;
             // Process newline in normal state
-            self.position -= ch.len_utf8();
-            return self.feed_char(ch);
+            return self.process_normal(ch);
         } else {
             self.buffer.push(ch);
         }
@@ -6,28 +6,43 @@
 //! incrementally, making it suitable for parsing large files or real-time
 //! data streams without loading the entire content into memory.
 
+mod aggregate;
 mod buffered;
+mod checkpoint;
 pub mod event_parser;
+mod file;
+mod index;
 mod ndjson;
 mod simple_lexer;
+mod validate;
 
+pub use aggregate::{NdJsonAggregates, NdJsonAggregator};
 pub use buffered::{
     parse_streaming, parse_streaming_with_config, BufferedStreamingConfig, BufferedStreamingParser,
     StreamingEventIterator,
 };
+pub use checkpoint::{OpenContainer, StreamCheckpoint};
 pub use event_parser::{
     EventDrivenParser, EventParserConfig, JsonEventHandler, ParserContext as EventParserContext,
     ParserState as EventParserState,
 };
-pub use ndjson::{NdJsonIterator, NdJsonParser, StreamingNdJsonParser};
+pub use file::{events_from_file, events_from_file_with_options, FileEvents};
+pub use index::{build_index, IndexSpec, JsonIndex, PathPattern};
+pub use ndjson::{ndjson_to_array, to_ndjson, NdJsonIterator, NdJsonParser, StreamingNdJsonParser};
 pub use simple_lexer::SimpleStreamingLexer;
+pub use validate::validate_stream;
 
 #[cfg(feature = "async")]
 pub use event_parser::AsyncEventDrivenParser;
 
-use crate::ast::{Token, Value};
-use crate::error::{Error, Result};
+use crate::ast::{JsonPath, PathSegment, Token, Value};
+use crate::error::{BudgetLimit, Error, Result, Span};
+use crate::parser::number::{decimal_number, read_number};
+use crate::parser::string::parse_string_token;
+use crate::parser::{is_cancelled, Limits};
 use rustc_hash::FxHashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 /// Events emitted by the streaming parser
 #[derive(Debug, Clone, PartialEq)]
@@ -65,14 +80,18 @@ pub struct StreamingParser {
     current_state: ParserState,
     /// Parser options
     options: crate::parser::ParserOptions,
-    /// Event queue
-    event_queue: Vec<StreamingEvent>,
+    /// Event queue, with the span of input each event came from
+    event_queue: Vec<(StreamingEvent, Span)>,
     /// Whether parsing is complete
     finished: bool,
     /// Current token being processed
-    current_token: Option<(Token, crate::error::Span)>,
-    /// Input string for extracting token content
+    current_token: Option<(Token, Span)>,
+    /// Input not yet consumed, for extracting token content
     input_buffer: String,
+    /// Stream offset of the first byte of `input_buffer`
+    buffer_offset: usize,
+    /// Flag that cancels parsing once set
+    cancel_flag: Option<Arc<AtomicBool>>,
 }
 
 /// Internal parser state
@@ -81,18 +100,30 @@ enum ParserState {
     /// Expecting a value (could be any JSON value)
     ExpectingValue,
     /// Inside an object, expecting key or closing brace
-    InObject { expecting_key: bool },
-    /// Inside an array, expecting value or closing bracket
-    InArray {
-        #[allow(dead_code)]
-        first_element: bool,
+    InObject {
+        expecting_key: bool,
+        separator: Separator,
     },
+    /// Inside an array, expecting value or closing bracket
+    InArray { separator: Separator },
     /// Between values (handling whitespace/commas)
     BetweenValues,
     /// Expecting a colon after object key
     ExpectingColon,
 }
 
+/// What came after the last member or element of a container
+#[derive(Debug, Clone, Copy)]
+enum Separator {
+    /// Nothing: the container has just opened, or extra commas were skipped
+    None,
+    /// A comma, which is a trailing comma if the container closes next
+    Comma(Span),
+    /// A line break, with `newline_as_comma`. A comma may still follow on
+    /// the next line, and then separates the same two values.
+    LineBreak,
+}
+
 /// Context for nested structures
 #[derive(Debug, Clone)]
 enum ParserContext {
@@ -113,6 +144,12 @@ impl StreamingParser {
     }
 
     /// Create a new streaming parser with custom options
+    ///
+    /// Comments, trailing commas, single quotes, unquoted keys and
+    /// `newline_as_comma` work as in the batch parser. Implicit top-level
+    /// objects and arrays (`a: 1`, `1, 2`) are not supported: with
+    /// `implicit_top_level` set, input that needs them is an error rather
+    /// than being read as something else.
     pub fn with_options(options: crate::parser::ParserOptions) -> Self {
         let lexer = SimpleStreamingLexer::with_options(options.clone());
         Self {
@@ -124,9 +161,17 @@ impl StreamingParser {
             finished: false,
             current_token: None,
             input_buffer: String::new(),
+            buffer_offset: 0,
+            cancel_flag: None,
         }
     }
 
+    /// Stop with [`Error::BudgetExceeded`] once `cancel_flag` is set
+    pub fn with_cancel_flag(mut self, cancel_flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = Some(cancel_flag);
+        self
+    }
+
     /// Feed a chunk of input to the parser
     pub fn feed(&mut self, chunk: &str) -> Result<()> {
         if self.finished {
@@ -142,6 +187,14 @@ impl StreamingParser {
         // Process any available tokens
         self.process_tokens()?;
 
+        // Drop input that no pending token refers to. Spans stay absolute, so
+        // the buffer remembers where in the stream it now starts.
+        let keep_from = self
+            .current_token
+            .map_or_else(|| self.lexer.unconsumed_start(), |(_, span)| span.start);
+        self.input_buffer.drain(..keep_from - self.buffer_offset);
+        self.buffer_offset = keep_from;
+
         Ok(())
     }
 
@@ -158,23 +211,46 @@ impl StreamingParser {
                 break;
             };
 
+            if is_cancelled(self.cancel_flag.as_ref()) {
+                return Err(Error::BudgetExceeded {
+                    limit: BudgetLimit::Cancelled,
+                    position: span.start,
+                });
+            }
+
             // Skip comments
             if matches!(token, Token::SingleLineComment | Token::MultiLineComment) {
                 self.current_token = None;
                 continue;
             }
 
+            // The lexer only emits line breaks with `newline_as_comma`. After
+            // a member or element they separate it from the next, as a comma
+            // would; anywhere else they are whitespace.
+            if matches!(token, Token::Newline) {
+                if matches!(self.current_state, ParserState::BetweenValues) {
+                    self.next_in_container(Separator::LineBreak);
+                }
+                self.current_token = None;
+                continue;
+            }
+
             // Process token based on current state
             let consumed = match &self.current_state {
                 ParserState::ExpectingValue => self.process_value(token, span)?,
-                ParserState::InObject { expecting_key } => {
+                ParserState::InObject {
+                    expecting_key,
+                    separator,
+                } => {
                     if *expecting_key {
-                        self.process_object_key(token, span)?
+                        self.process_object_key(token, span, *separator)?
                     } else {
                         self.process_value(token, span)?
                     }
                 }
-                ParserState::InArray { .. } => self.process_value(token, span)?,
+                ParserState::InArray { separator } => {
+                    self.process_array_element(token, span, *separator)?
+                }
                 ParserState::BetweenValues => self.process_between_values(token, span)?,
                 ParserState::ExpectingColon => {
                     if matches!(token, Token::Colon) {
@@ -190,65 +266,66 @@ impl StreamingParser {
                 }
             };
 
-            if consumed {
-                self.current_token = None;
-            } else {
-                // Token not consumed, stop processing
-                break;
+            if !consumed {
+                return Err(self.unexpected_token(token, span));
             }
+            self.current_token = None;
         }
         Ok(())
     }
 
     /// Process a value token
-    fn process_value(&mut self, token: Token, span: crate::error::Span) -> Result<bool> {
+    fn process_value(&mut self, token: Token, span: Span) -> Result<bool> {
         match token {
             Token::LeftBrace => {
-                self.event_queue.push(StreamingEvent::StartObject);
+                self.emit(StreamingEvent::StartObject, span);
                 self.state_stack.push(ParserContext::Object {
                     expecting_key: true,
                 });
                 self.current_state = ParserState::InObject {
                     expecting_key: true,
+                    separator: Separator::None,
                 };
                 Ok(true)
             }
             Token::LeftBracket => {
-                self.event_queue.push(StreamingEvent::StartArray);
+                self.emit(StreamingEvent::StartArray, span);
                 self.state_stack.push(ParserContext::Array {
                     first_element: true,
                 });
                 self.current_state = ParserState::InArray {
-                    first_element: true,
+                    separator: Separator::None,
                 };
                 Ok(true)
             }
             Token::String => {
                 // Extract actual string content from input buffer
                 let content = self.extract_string_content(span)?;
-                self.event_queue.push(StreamingEvent::String(content));
+                self.emit(StreamingEvent::String(content), span);
                 self.transition_after_value();
                 Ok(true)
             }
             Token::Number => {
                 // Extract actual number content from input buffer
                 let content = self.extract_token_content(span);
-                self.event_queue.push(StreamingEvent::Number(content));
+                // The text is passed on as written, once it is known to be in range
+                read_number(&content, span.start, &self.options)?;
+                self.emit(StreamingEvent::Number(content), span);
                 self.transition_after_value();
                 Ok(true)
             }
             Token::True => {
-                self.event_queue.push(StreamingEvent::Bool(true));
+                self.emit(StreamingEvent::Bool(true), span);
                 self.transition_after_value();
                 Ok(true)
             }
             Token::False => {
-                self.event_queue.push(StreamingEvent::Bool(false));
+                self.emit(StreamingEvent::Bool(false), span);
                 self.transition_after_value();
                 Ok(true)
             }
             Token::Null => {
-                self.event_queue.push(StreamingEvent::Null);
+                self.emit(StreamingEvent::Null, span);
                 self.transition_after_value();
                 Ok(true)
             }
@@ -257,13 +334,17 @@ impl StreamingParser {
     }
 
     /// Process an object key
-    fn process_object_key(&mut self, token: Token, span: crate::error::Span) -> Result<bool> {
+    fn process_object_key(
+        &mut self,
+        token: Token,
+        span: Span,
+        separator: Separator,
+    ) -> Result<bool> {
         match token {
             Token::String => {
                 // Extract actual string content from input buffer
                 let content = self.extract_string_content(span)?;
-                self.event_queue
-                    .push(StreamingEvent::ObjectKey(content));
+                self.emit(StreamingEvent::ObjectKey(content), span);
                 // After key, expect colon then value
                 self.current_state = ParserState::ExpectingColon;
                 Ok(true)
@@ -271,46 +352,73 @@ impl StreamingParser {
             Token::UnquotedString if self.options.allow_unquoted_keys => {
                 // Extract actual string content from input buffer
                 let content = self.extract_token_content(span);
-                self.event_queue
-                    .push(StreamingEvent::ObjectKey(content));
+                self.emit(StreamingEvent::ObjectKey(content), span);
                 self.current_state = ParserState::ExpectingColon;
                 Ok(true)
             }
             Token::RightBrace => {
                 // Empty object or trailing comma
-                self.event_queue.push(StreamingEvent::EndObject);
+                self.check_trailing_comma(separator)?;
+                self.emit(StreamingEvent::EndObject, span);
                 self.state_stack.pop();
                 self.transition_after_value();
                 Ok(true)
             }
+            Token::Comma => {
+                // Extra commas between members are skipped
+                let separator = match separator {
+                    Separator::LineBreak => Separator::Comma(span),
+                    _ => Separator::None,
+                };
+                self.current_state = ParserState::InObject {
+                    expecting_key: true,
+                    separator,
+                };
+                Ok(true)
+            }
             _ => Ok(false),
         }
     }
 
-    /// Process tokens between values
-    fn process_between_values(&mut self, token: Token, _span: crate::error::Span) -> Result<bool> {
+    /// Process an array element, or the bracket closing the array
+    fn process_array_element(
+        &mut self,
+        token: Token,
+        span: Span,
+        separator: Separator,
+    ) -> Result<bool> {
         match token {
+            Token::RightBracket => {
+                // Empty array or trailing comma
+                self.check_trailing_comma(separator)?;
+                self.emit(StreamingEvent::EndArray, span);
+                self.state_stack.pop();
+                self.transition_after_value();
+                Ok(true)
+            }
             Token::Comma => {
-                // Move to next value
-                if let Some(context) = self.state_stack.last() {
-                    match context {
-                        ParserContext::Object { .. } => {
-                            self.current_state = ParserState::InObject {
-                                expecting_key: true,
-                            };
-                        }
-                        ParserContext::Array { .. } => {
-                            self.current_state = ParserState::InArray {
-                                first_element: false,
-                            };
-                        }
+                // A comma with no element before it stands for null
+                let separator = match separator {
+                    Separator::LineBreak => Separator::Comma(span),
+                    _ => {
+                        self.emit(StreamingEvent::Null, span);
+                        Separator::None
                     }
-                }
+                };
+                self.current_state = ParserState::InArray { separator };
                 Ok(true)
             }
+            _ => self.process_value(token, span),
+        }
+    }
+
+    /// Process tokens between values
+    fn process_between_values(&mut self, token: Token, span: Span) -> Result<bool> {
+        match token {
+            Token::Comma => Ok(self.next_in_container(Separator::Comma(span))),
             Token::RightBrace => {
                 if matches!(self.state_stack.last(), Some(ParserContext::Object { .. })) {
-                    self.event_queue.push(StreamingEvent::EndObject);
+                    self.emit(StreamingEvent::EndObject, span);
                     self.state_stack.pop();
                     self.transition_after_value();
                     Ok(true)
@@ -320,7 +428,7 @@ impl StreamingParser {
             }
             Token::RightBracket => {
                 if matches!(self.state_stack.last(), Some(ParserContext::Array { .. })) {
-                    self.event_queue.push(StreamingEvent::EndArray);
+                    self.emit(StreamingEvent::EndArray, span);
                     self.state_stack.pop();
                     self.transition_after_value();
                     Ok(true)
@@ -337,8 +445,82 @@ impl StreamingParser {
         self.current_state = ParserState::BetweenValues;
     }
 
+    /// Move on to the next member or element after `separator`. Returns
+    /// `false` outside any container.
+    fn next_in_container(&mut self, separator: Separator) -> bool {
+        self.current_state = match self.state_stack.last() {
+            Some(ParserContext::Object { .. }) => ParserState::InObject {
+                expecting_key: true,
+                separator,
+            },
+            Some(ParserContext::Array { .. }) => ParserState::InArray { separator },
+            None => return false,
+        };
+        true
+    }
+
+    /// Rejects a comma right before a closing bracket unless trailing commas
+    /// are allowed. A line break there is only whitespace.
+    fn check_trailing_comma(&self, separator: Separator) -> Result<()> {
+        match separator {
+            Separator::Comma(comma) if !self.options.allow_trailing_commas => {
+                Err(Error::TrailingComma(comma.start))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The error for a token that cannot come next in the current state.
+    ///
+    /// Implicit top-level objects and arrays (`a: 1`, `1, 2`) are not
+    /// supported when streaming; input that needs them is an error rather
+    /// than being read as something else.
+    fn unexpected_token(&self, token: Token, span: Span) -> Error {
+        let at_top_level = self.state_stack.is_empty();
+        let implicit = match self.current_state {
+            ParserState::BetweenValues => true,
+            ParserState::ExpectingValue => {
+                matches!(token, Token::UnquotedString | Token::Comma)
+            }
+            _ => false,
+        };
+        if at_top_level && implicit && self.options.implicit_top_level {
+            return Error::Custom(format!(
+                "Implicit top-level values are not supported by the streaming parser (at position {})",
+                span.start
+            ));
+        }
+        let expected = match (&self.current_state, self.state_stack.last()) {
+            (ParserState::BetweenValues, Some(ParserContext::Object { .. })) => ", or }",
+            (ParserState::BetweenValues, Some(ParserContext::Array { .. })) => ", or ]",
+            (ParserState::BetweenValues, None) => "end of input",
+            (ParserState::InObject { .. }, _) => "string key or }",
+            (ParserState::InArray { .. }, _) => "value or ]",
+            _ => "value",
+        };
+        Error::Expected {
+            expected: expected.to_string(),
+            found: format!("{token:?}"),
+            position: span.start,
+        }
+    }
+
+    /// Queue an event produced by the token at `span`
+    fn emit(&mut self, event: StreamingEvent, span: Span) {
+        self.event_queue.push((event, span));
+    }
+
     /// Get the next event from the parser
     pub fn next_event(&mut self) -> Result<Option<StreamingEvent>> {
+        Ok(self.next_event_with_span()?.map(|(event, _)| event))
+    }
+
+    /// Get the next event together with the span of input it came from.
+    ///
+    /// Spans are byte offsets into the whole stream, across all fed chunks.
+    /// Container events cover their bracket; `EndOfInput` is an empty span at
+    /// the end of the stream.
+    pub fn next_event_with_span(&mut self) -> Result<Option<(StreamingEvent, Span)>> {
         // Process more tokens if needed
         self.process_tokens()?;
 
@@ -346,7 +528,8 @@ impl StreamingParser {
         if !self.event_queue.is_empty() {
             Ok(Some(self.event_queue.remove(0)))
         } else if self.finished && self.state_stack.is_empty() {
-            Ok(Some(StreamingEvent::EndOfInput))
+            let end = self.lexer.position();
+            Ok(Some((StreamingEvent::EndOfInput, Span { start: end, end })))
         } else {
             Ok(None)
         }
@@ -371,75 +554,30 @@ impl StreamingParser {
     }
     
     /// Extract token content from the input buffer
-    fn extract_token_content(&self, span: crate::error::Span) -> String {
-        if span.start < self.input_buffer.len() && span.end <= self.input_buffer.len() {
-            self.input_buffer[span.start..span.end].to_string()
+    fn extract_token_content(&self, span: Span) -> String {
+        let start = span.start.wrapping_sub(self.buffer_offset);
+        let end = span.end.wrapping_sub(self.buffer_offset);
+        if start < self.input_buffer.len() && end <= self.input_buffer.len() {
+            self.input_buffer[start..end].to_string()
         } else {
             // Fallback for out-of-bounds
             String::new()
         }
     }
     
-    /// Extract string content from the input buffer, removing quotes and processing escapes
-    fn extract_string_content(&self, span: crate::error::Span) -> Result<String> {
-        let raw = self.extract_token_content(span);
-        
-        // Remove surrounding quotes
-        let content = if (raw.starts_with('"') && raw.ends_with('"')) ||
-            (raw.starts_with('\'') && raw.ends_with('\'') && self.options.allow_single_quotes)
-        {
-            &raw[1..raw.len() - 1]
-        } else {
-            return Err(Error::Custom("Invalid string format".to_string()));
+    /// Extract string content from the input buffer, removing quotes and
+    /// processing escapes as the batch parser does
+    fn extract_string_content(&self, span: Span) -> Result<String> {
+        let local = Span {
+            start: span.start - self.buffer_offset,
+            end: span.end - self.buffer_offset,
         };
-        
-        // Process escape sequences
-        let mut result = String::new();
-        let mut chars = content.chars();
-        
-        while let Some(ch) = chars.next() {
-            if ch == '\\' {
-                match chars.next() {
-                    Some('"') => result.push('"'),
-                    Some('\\') => result.push('\\'),
-                    Some('/') => result.push('/'),
-                    Some('b') => result.push('\u{0008}'),
-                    Some('f') => result.push('\u{000C}'),
-                    Some('n') => result.push('\n'),
-                    Some('r') => result.push('\r'),
-                    Some('t') => result.push('\t'),
-                    Some('u') => {
-                        // Unicode escape sequence
-                        let hex: String = chars.by_ref().take(4).collect();
-                        if hex.len() != 4 {
-                            return Err(Error::Custom("Invalid unicode escape".to_string()));
-                        }
-                        match u32::from_str_radix(&hex, 16) {
-                            Ok(code) => {
-                                if let Some(unicode_char) = char::from_u32(code) {
-                                    result.push(unicode_char);
-                                } else {
-                                    return Err(Error::Custom(
-                                        "Invalid unicode code point".to_string(),
-                                    ));
-                                }
-                            }
-                            Err(_) => {
-                                return Err(Error::Custom("Invalid unicode escape".to_string()))
-                            }
-                        }
-                    }
-                    Some(ch) => {
-                        return Err(Error::Custom(format!("Invalid escape sequence: \\{ch}")))
-                    }
-                    None => return Err(Error::Custom("Incomplete escape sequence".to_string())),
-                }
-            } else {
-                result.push(ch);
-            }
+        match parse_string_token(&self.input_buffer, local, &self.options)
+            .map_err(|error| error.offset_by(self.buffer_offset))?
+        {
+            Value::String(s) => Ok(s),
+            _ => unreachable!("parse_string_token should always return a String"),
         }
-        
-        Ok(result)
     }
 }
 
@@ -460,6 +598,9 @@ impl Iterator for StreamingParser {
 pub struct StreamingValueBuilder {
     stack: Vec<BuilderState>,
     root: Option<Value>,
+    limits: Limits,
+    /// Start of the event being processed, for errors
+    position: usize,
 }
 
 enum BuilderState {
@@ -470,14 +611,42 @@ enum BuilderState {
 impl StreamingValueBuilder {
     /// Create a new value builder
     pub fn new() -> Self {
+        Self::with_limits(Limits::default())
+    }
+
+    /// Create a value builder that enforces `limits` as the parser does,
+    /// failing with [`Error::LimitExceeded`] wrapped in [`Error::AtPath`].
+    ///
+    /// Positions in those errors are the starts of the spans given to
+    /// [`process_event_with_span`](Self::process_event_with_span), and 0 for
+    /// events given to [`process_event`](Self::process_event).
+    pub fn with_limits(limits: Limits) -> Self {
         Self {
             stack: Vec::new(),
             root: None,
+            limits,
+            position: 0,
         }
     }
 
+    /// Process a streaming event found at `span`, as returned by
+    /// [`StreamingParser::next_event_with_span`]
+    pub fn process_event_with_span(&mut self, event: StreamingEvent, span: Span) -> Result<()> {
+        self.position = span.start;
+        self.process_event(event)
+    }
+
     /// Process a streaming event
     pub fn process_event(&mut self, event: StreamingEvent) -> Result<()> {
+        if !matches!(
+            event,
+            StreamingEvent::EndObject
+                | StreamingEvent::EndArray
+                | StreamingEvent::ObjectKey(_)
+                | StreamingEvent::EndOfInput
+        ) {
+            self.check_array_len()?;
+        }
         match event {
             StreamingEvent::StartObject => {
                 self.stack
@@ -501,6 +670,7 @@ impl StreamingValueBuilder {
                 }
             }
             StreamingEvent::ObjectKey(key) => {
+                self.check_key(&key)?;
                 if let Some(BuilderState::Object(_, ref mut pending_key)) = self.stack.last_mut() {
                     *pending_key = Some(key);
                 } else {
@@ -510,21 +680,18 @@ impl StreamingValueBuilder {
             StreamingEvent::Null => self.add_value(Value::Null)?,
             StreamingEvent::Bool(b) => self.add_value(Value::Bool(b))?,
             StreamingEvent::Number(n) => {
-                // Parse number string to Value::Number
-                let value = if n.contains('.') || n.contains('e') || n.contains('E') {
-                    Value::Number(crate::ast::Number::Float(
-                        n.parse()
-                            .map_err(|_| Error::Custom(format!("Invalid number: {n}")))?,
-                    ))
-                } else {
-                    Value::Number(crate::ast::Number::Integer(
-                        n.parse()
-                            .map_err(|_| Error::Custom(format!("Invalid number: {n}")))?,
-                    ))
-                };
-                self.add_value(value)?;
+                let number = decimal_number(&n)
+                    .ok_or_else(|| Error::Custom(format!("Invalid number: {n}")))?;
+                self.add_value(Value::Number(number))?;
+            }
+            StreamingEvent::String(s) => {
+                if let Some(max) = self.limits.max_string_len {
+                    if s.len() > max {
+                        return Err(self.limit_exceeded("string length", max));
+                    }
+                }
+                self.add_value(Value::String(s))?;
             }
-            StreamingEvent::String(s) => self.add_value(Value::String(s))?,
             StreamingEvent::EndOfInput => {
                 if !self.stack.is_empty() {
                     return Err(Error::Custom("Unexpected end of input".to_string()));
@@ -534,26 +701,73 @@ impl StreamingValueBuilder {
         Ok(())
     }
 
+    /// Enforces [`max_array_len`](Limits::max_array_len) on the value about
+    /// to start, if it is an array element.
+    fn check_array_len(&self) -> Result<()> {
+        match (self.limits.max_array_len, self.stack.last()) {
+            (Some(max), Some(BuilderState::Array(vec))) if vec.len() >= max => {
+                Err(self.limit_exceeded("array length", max))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Enforces [`max_key_len`](Limits::max_key_len) and
+    /// [`max_object_members`](Limits::max_object_members) on a key about to
+    /// be added to the current object.
+    fn check_key(&self, key: &str) -> Result<()> {
+        if let Some(max) = self.limits.max_key_len {
+            if key.len() > max {
+                return Err(self.limit_exceeded("key length", max));
+            }
+        }
+        match (self.limits.max_object_members, self.stack.last()) {
+            (Some(max), Some(BuilderState::Object(map, _)))
+                if map.len() >= max && !map.contains_key(key) =>
+            {
+                Err(self.limit_exceeded("object member count", max))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The error for a value past a limit, at the current path.
+    fn limit_exceeded(&self, what: &str, limit: usize) -> Error {
+        let mut path = JsonPath::new();
+        for state in &self.stack {
+            match state {
+                BuilderState::Object(_, Some(key)) => path.push(PathSegment::Key(key.clone())),
+                BuilderState::Object(_, None) => {}
+                BuilderState::Array(vec) => path.push(PathSegment::Index(vec.len())),
+            }
+        }
+        Error::AtPath {
+            path,
+            source: Box::new(Error::LimitExceeded {
+                what: what.to_string(),
+                limit,
+                position: self.position,
+            }),
+        }
+    }
+
     /// Add a value to the current container
     fn add_value(&mut self, value: Value) -> Result<()> {
-        if self.stack.is_empty() {
-            if self.root.is_some() {
+        match self.stack.last_mut() {
+            None if self.root.is_some() => {
                 return Err(Error::Custom("Multiple root values".to_string()));
             }
-            self.root = Some(value);
-        } else {
-            match self.stack.last_mut().unwrap() {
-                BuilderState::Object(map, pending_key) => {
-                    if let Some(key) = pending_key.take() {
-                        map.insert(key, value);
-                    } else {
-                        return Err(Error::Custom("Value without key in object".to_string()));
-                    }
-                }
-                BuilderState::Array(vec) => {
-                    vec.push(value);
+            None => self.root = Some(value),
+            Some(BuilderState::Object(map, pending_key)) => {
+                if let Some(key) = pending_key.take() {
+                    map.insert(key, value);
+                } else {
+                    return Err(Error::Custom("Value without key in object".to_string()));
                 }
             }
+            Some(BuilderState::Array(vec)) => {
+                vec.push(value);
+            }
         }
         Ok(())
     }
@@ -5,10 +5,12 @@
 //! NDJSON is a format where each line is a valid JSON value, allowing
 //! for streaming of multiple JSON objects without wrapping them in an array.
 
-use super::{StreamingEvent, StreamingParser};
+use super::{StreamCheckpoint, StreamingEvent, StreamingParser};
 use crate::ast::Value;
 use crate::error::{Error, Result};
+use crate::format::{to_compact_string, KeyOrder};
 use crate::parser::ParserOptions;
+use std::io::{self, Seek, SeekFrom, Write};
 
 /// Parser for Newline-Delimited JSON streams
 pub struct NdJsonParser {
@@ -21,6 +23,8 @@ pub struct NdJsonParser {
     finished: bool,
     /// Current line number for error reporting
     line_number: usize,
+    /// Stream offset of the start of the current line
+    offset: usize,
 }
 
 impl NdJsonParser {
@@ -36,9 +40,49 @@ impl NdJsonParser {
             options,
             finished: false,
             line_number: 1,
+            offset: 0,
         }
     }
 
+    /// Create a parser that carries on from `checkpoint`, seeking `reader`
+    /// to the checkpoint's offset.
+    ///
+    /// Feed the parser what `reader` holds from there on. Line numbers in
+    /// errors carry on from the checkpoint's.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{Cursor, Read};
+    /// use vexy_json_core::{NdJsonParser, ParserOptions};
+    ///
+    /// let input = "{\"id\": 1}\n{\"id\": 2}\n{\"id\": 3}\n";
+    /// let mut parser = NdJsonParser::new();
+    /// assert_eq!(parser.feed(&input[..15]).unwrap().len(), 1);
+    /// let checkpoint = parser.checkpoint();
+    ///
+    /// let mut reader = Cursor::new(input);
+    /// let mut parser =
+    ///     NdJsonParser::resume_from(&mut reader, &checkpoint, ParserOptions::default()).unwrap();
+    /// let mut rest = String::new();
+    /// reader.read_to_string(&mut rest).unwrap();
+    /// assert_eq!(parser.feed(&rest).unwrap().len(), 2);
+    /// ```
+    pub fn resume_from<R: Seek>(
+        reader: &mut R,
+        checkpoint: &StreamCheckpoint,
+        options: ParserOptions,
+    ) -> Result<Self> {
+        reader
+            .seek(SeekFrom::Start(checkpoint.offset as u64))
+            .map_err(|e| Error::Custom(format!("IO error: {e}")))?;
+        Ok(Self {
+            line_number: checkpoint.line,
+            offset: checkpoint.offset,
+            ..Self::with_options(options)
+        })
+    }
+
     /// Feed a chunk of input to the parser
     pub fn feed(&mut self, chunk: &str) -> Result<Vec<Value>> {
         if self.finished {
@@ -61,6 +105,7 @@ impl NdJsonParser {
                         }
                     }
                 }
+                self.offset += self.line_buffer.len() + 1;
                 self.line_buffer.clear();
                 self.line_number += 1;
             } else {
@@ -126,6 +171,17 @@ impl NdJsonParser {
     pub fn line_number(&self) -> usize {
         self.line_number
     }
+
+    /// Get a checkpoint after the last complete line, for
+    /// [`resume_from`](Self::resume_from). A line only partly fed is read
+    /// again on resuming.
+    pub fn checkpoint(&self) -> StreamCheckpoint {
+        StreamCheckpoint {
+            offset: self.offset,
+            line: self.line_number,
+            containers: Vec::new(),
+        }
+    }
 }
 
 /// Streaming NDJSON parser that emits events for each line
@@ -237,14 +293,14 @@ impl StreamingNdJsonParser {
 pub struct NdJsonIterator {
     parser: NdJsonParser,
     buffer: String,
-    input: Box<dyn Iterator<Item = std::result::Result<String, std::io::Error>>>,
+    input: Box<dyn Iterator<Item = std::result::Result<String, io::Error>>>,
 }
 
 impl NdJsonIterator {
     /// Create a new NDJSON iterator from a line iterator
     pub fn new<I>(input: I) -> Self
     where
-        I: Iterator<Item = std::result::Result<String, std::io::Error>> + 'static,
+        I: Iterator<Item = std::result::Result<String, io::Error>> + 'static,
     {
         Self {
             parser: NdJsonParser::new(),
@@ -256,7 +312,7 @@ impl NdJsonIterator {
     /// Create a new NDJSON iterator with custom options
     pub fn with_options<I>(input: I, options: ParserOptions) -> Self
     where
-        I: Iterator<Item = std::result::Result<String, std::io::Error>> + 'static,
+        I: Iterator<Item = std::result::Result<String, io::Error>> + 'static,
     {
         Self {
             parser: NdJsonParser::with_options(options),
@@ -305,6 +361,53 @@ impl Iterator for NdJsonIterator {
     }
 }
 
+/// Writes `values` as NDJSON: each one compactly on a line of its own,
+/// ending with a line feed.
+///
+/// Line breaks inside strings are written as `\n` escapes, so every value
+/// takes exactly one line. Nothing is written for no values.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{parse, to_ndjson};
+///
+/// let value = parse(r#"[{"id": 1, "note": "a\nb"}, [2, 3]]"#).unwrap();
+/// let mut out = Vec::new();
+/// to_ndjson(value.as_array().unwrap(), &mut out).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), "{\"id\":1,\"note\":\"a\\nb\"}\n[2,3]\n");
+/// ```
+pub fn to_ndjson<'a, W: Write>(
+    values: impl IntoIterator<Item = &'a Value>,
+    mut writer: W,
+) -> io::Result<()> {
+    for value in values {
+        writer.write_all(to_compact_string(value, &KeyOrder::default()).as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+/// Reads NDJSON `input` into an array holding the value of each line.
+///
+/// Blank lines are skipped, and lines may end with `\r\n` as well as `\n`.
+/// Input without any values gives an empty array.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{ndjson_to_array, parse};
+///
+/// let array = ndjson_to_array("{\"id\": 1}\r\n\r\n[2, 3]\r\n").unwrap();
+/// assert_eq!(array, parse(r#"[{"id": 1}, [2, 3]]"#).unwrap());
+/// ```
+pub fn ndjson_to_array(input: &str) -> Result<Value> {
+    let mut parser = NdJsonParser::new();
+    let mut values = parser.feed(input)?;
+    values.extend(parser.finish()?);
+    Ok(Value::Array(values))
+}
+
 impl Default for NdJsonParser {
     fn default() -> Self {
         Self::new()
@@ -377,4 +480,27 @@ mod tests {
         values.extend(parser.finish().unwrap());
         assert_eq!(values.len(), 2);
     }
+
+    #[test]
+    fn test_ndjson_round_trip() {
+        let input = "{\"a\":[1,{\"b\":null}]}\r\n[[],{}]\r\n\"line\\nbreak\"\r\n";
+        let array = ndjson_to_array(input).unwrap();
+        assert_eq!(array.as_array().map(Vec::len), Some(3));
+
+        let mut out = Vec::new();
+        to_ndjson(array.as_array().unwrap(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), input.replace("\r\n", "\n"));
+
+        assert_eq!(ndjson_to_array("").unwrap(), Value::Array(Vec::new()));
+        assert_eq!(
+            ndjson_to_array("\n \r\n").unwrap(),
+            Value::Array(Vec::new())
+        );
+        let mut out = Vec::new();
+        to_ndjson(&[], &mut out).unwrap();
+        assert!(out.is_empty());
+
+        let error = ndjson_to_array("{\"a\": 1}\n{oops\n").unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{error}");
+    }
 }
@@ -39,8 +39,13 @@ enum LexerState {
     InIdentifier { start_pos: usize, content: String },
     /// Inside a single-line comment
     InSingleLineComment { start_pos: usize },
-    /// Inside a multi-line comment
-    InMultiLineComment { start_pos: usize, star_seen: bool },
+    /// Inside a multi-line comment, `depth` comments deep
+    InMultiLineComment {
+        start_pos: usize,
+        depth: usize,
+        star_seen: bool,
+        slash_seen: bool,
+    },
     /// Potential comment start (seen /)
     PotentialComment { start_pos: usize },
 }
@@ -84,9 +89,11 @@ impl SimpleStreamingLexer {
             }
             LexerState::InMultiLineComment {
                 start_pos,
+                depth,
                 star_seen,
+                slash_seen,
             } => {
-                self.process_multi_line_comment(ch, start_pos, star_seen)?;
+                self.process_multi_line_comment(ch, start_pos, depth, star_seen, slash_seen)?;
             }
             LexerState::PotentialComment { start_pos } => {
                 self.process_potential_comment(ch, start_pos)?;
@@ -142,11 +149,19 @@ impl SimpleStreamingLexer {
                 };
             }
             // Comments
-            '/' if self.options.allow_comments => {
+            '/' if self.options.allow_comments
+                && (self.options.comment_styles.line_slash
+                    || self.options.comment_styles.block) =>
+            {
                 self.state = LexerState::PotentialComment {
                     start_pos: self.position,
                 };
             }
+            '#' if self.hash_comments() => {
+                self.state = LexerState::InSingleLineComment {
+                    start_pos: self.position,
+                };
+            }
             // Numbers
             '-' | '0'..='9' => {
                 self.state = LexerState::InNumber {
@@ -155,7 +170,7 @@ impl SimpleStreamingLexer {
                 };
             }
             // Identifiers (for keywords and unquoted strings)
-            'a'..='z' | 'A'..='Z' | '_' => {
+            'a'..='z' | 'A'..='Z' | '_' | '#' => {
                 self.state = LexerState::InIdentifier {
                     start_pos: self.position,
                     content: ch.to_string(),
@@ -222,8 +237,7 @@ impl SimpleStreamingLexer {
                 self.emit_token(Token::Number, start_pos, self.position);
                 self.state = LexerState::Normal;
                 // Reprocess this character in normal state
-                self.position -= ch.len_utf8();
-                return self.feed_char(ch);
+                return self.process_normal(ch);
             }
         }
         Ok(())
@@ -241,6 +255,10 @@ impl SimpleStreamingLexer {
                 content.push(ch);
                 self.state = LexerState::InIdentifier { start_pos, content };
             }
+            '#' if !self.hash_comments() => {
+                content.push(ch);
+                self.state = LexerState::InIdentifier { start_pos, content };
+            }
             _ => {
                 // End of identifier
                 let token = match content.as_str() {
@@ -261,8 +279,7 @@ impl SimpleStreamingLexer {
                 self.emit_token(token, start_pos, self.position);
                 self.state = LexerState::Normal;
                 // Reprocess this character in normal state
-                self.position -= ch.len_utf8();
-                return self.feed_char(ch);
+                return self.process_normal(ch);
             }
         }
         Ok(())
@@ -275,10 +292,9 @@ impl SimpleStreamingLexer {
             self.emit_token(Token::SingleLineComment, start_pos, self.position);
             self.state = LexerState::Normal;
             // Process newline in normal state
-            self.position -= ch.len_utf8();
-            return self.feed_char(ch);
+            return self.process_normal(ch);
         }
-        Ok(())
+        self.check_comment_length(ch, start_pos)
     }
 
     /// Process a character inside a multi-line comment
@@ -286,33 +302,70 @@ impl SimpleStreamingLexer {
         &mut self,
         ch: char,
         start_pos: usize,
+        depth: usize,
         star_seen: bool,
+        slash_seen: bool,
     ) -> Result<()> {
-        if star_seen && ch == '/' {
+        self.check_comment_length(ch, start_pos)?;
+        let depth = if star_seen && ch == '/' {
+            depth - 1
+        } else if slash_seen && ch == '*' && self.options.allow_nested_block_comments {
+            depth + 1
+        } else {
+            self.state = LexerState::InMultiLineComment {
+                start_pos,
+                depth,
+                star_seen: ch == '*',
+                slash_seen: ch == '/',
+            };
+            return Ok(());
+        };
+
+        if depth == 0 {
             // End of comment
             self.emit_token(Token::MultiLineComment, start_pos, self.position + 1);
             self.state = LexerState::Normal;
         } else {
+            // A delimiter cannot share its characters with the next one
             self.state = LexerState::InMultiLineComment {
                 start_pos,
-                star_seen: ch == '*',
+                depth,
+                star_seen: false,
+                slash_seen: false,
             };
         }
         Ok(())
     }
 
+    /// Enforces [`ParserOptions::max_comment_length`](crate::ParserOptions::max_comment_length)
+    /// on a comment starting at `start_pos` that is about to take in `ch`
+    fn check_comment_length(&self, ch: char, start_pos: usize) -> Result<()> {
+        match self.options.max_comment_length {
+            Some(max) if self.position + ch.len_utf8() - start_pos > max => {
+                Err(Error::LimitExceeded {
+                    what: "comment length".to_string(),
+                    limit: max,
+                    position: start_pos,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Process a potential comment start
     fn process_potential_comment(&mut self, ch: char, start_pos: usize) -> Result<()> {
         match ch {
-            '/' => {
+            '/' if self.options.comment_styles.line_slash => {
                 // Single-line comment
                 self.state = LexerState::InSingleLineComment { start_pos };
             }
-            '*' => {
+            '*' if self.options.comment_styles.block => {
                 // Multi-line comment
                 self.state = LexerState::InMultiLineComment {
                     start_pos,
+                    depth: 1,
                     star_seen: false,
+                    slash_seen: false,
                 };
             }
             _ => {
@@ -323,6 +376,11 @@ impl SimpleStreamingLexer {
         Ok(())
     }
 
+    /// Whether `#` starts a comment rather than being part of a word
+    fn hash_comments(&self) -> bool {
+        self.options.allow_comments && self.options.comment_styles.hash
+    }
+
     /// Emit a token
     fn emit_token(&mut self, token: Token, start: usize, end: usize) {
         self.pending_tokens.push((token, Span { start, end }));
@@ -343,6 +401,29 @@ impl SimpleStreamingLexer {
         !self.pending_tokens.is_empty()
     }
 
+    /// Get the number of bytes fed so far
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the stream offset of the earliest input still needed: the start of
+    /// the first pending token, or of the token currently being read.
+    pub fn unconsumed_start(&self) -> usize {
+        if let Some((_, span)) = self.pending_tokens.first() {
+            return span.start;
+        }
+        match &self.state {
+            LexerState::Normal => self.position,
+            LexerState::InString { start_pos, .. }
+            | LexerState::InNumber { start_pos, .. }
+            | LexerState::InIdentifier { start_pos, .. }
+            | LexerState::InSingleLineComment { start_pos }
+            | LexerState::InMultiLineComment { start_pos, .. }
+            | LexerState::PotentialComment { start_pos } => *start_pos,
+        }
+    }
+
     /// Finish lexing and emit any remaining tokens
     pub fn finish(&mut self) -> Result<()> {
         match &self.state {
@@ -0,0 +1,487 @@
+// this_file: crates/core/src/streaming/validate.rs
+
+//! Checking that a document is well formed without building it.
+
+use super::SimpleStreamingLexer;
+use crate::ast::Token;
+use crate::error::{Error, Result, Span};
+use crate::parser::number::read_number;
+use crate::parser::{parse_with_options, ParserOptions};
+use std::io::{ErrorKind, Read};
+
+/// Bytes asked of the reader at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Checks that the document in `reader` is well formed, without building
+/// any of it, and returns the first error found.
+///
+/// The input is read and lexed a chunk at a time, keeping only the
+/// containers open at each point, so memory use depends on the nesting
+/// depth and the longest token rather than on the size of the document.
+/// Error positions are byte offsets into the whole stream.
+///
+/// Structure follows `options` as [`parse_with_options`] does: comments,
+/// quotes, trailing commas, missing values, newlines as commas, unquoted
+/// keys, unquoted values running to the end of the line, implicit top-level
+/// objects and arrays, and the depth limit. Number tokens are checked; the
+/// escapes inside strings are not.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::streaming::validate_stream;
+/// use vexy_json_core::{Error, ParserOptions};
+///
+/// let options = ParserOptions::default();
+/// assert!(validate_stream("{a: [1, 2,], // ok\n b: 'c'}".as_bytes(), options.clone()).is_ok());
+///
+/// let error = validate_stream(r#"{"a": [1, 2} "#.as_bytes(), options).unwrap_err();
+/// assert_eq!(error.position(), Some(11));
+/// ```
+pub fn validate_stream<R: Read>(mut reader: R, options: ParserOptions) -> Result<()> {
+    let mut validator = Validator::new(options);
+    let mut buffer = vec![0; CHUNK_SIZE];
+    let mut partial_utf8 = Vec::new();
+
+    loop {
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(Error::Custom(format!("IO error: {e}"))),
+        };
+        // Hold back a character split across reads until the rest arrives
+        let mut bytes = std::mem::take(&mut partial_utf8);
+        bytes.extend_from_slice(&buffer[..n]);
+        let valid_len = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(Error::InvalidUtf8(validator.position() + e.valid_up_to())),
+        };
+        partial_utf8 = bytes.split_off(valid_len);
+        let chunk =
+            std::str::from_utf8(&bytes).map_err(|_| Error::InvalidUtf8(validator.position()))?;
+        validator.feed(chunk)?;
+    }
+
+    if !partial_utf8.is_empty() {
+        return Err(Error::InvalidUtf8(validator.position()));
+    }
+    validator.finish()
+}
+
+/// A container open at the current point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frame {
+    Object,
+    Array,
+    /// An object without braces around the whole document, as in `a: 1`
+    ImplicitObject,
+    /// An array without brackets around the whole document, as in `1, 2`
+    ImplicitArray,
+}
+
+/// What may come next.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    /// A value, or the end of the container
+    Value,
+    /// An object key, or the end of the object
+    Key,
+    /// The colon after a key
+    Colon,
+    /// A separator or the end of the container, after a value
+    AfterValue,
+}
+
+/// Checks the structure of a document fed to it in chunks.
+struct Validator {
+    lexer: SimpleStreamingLexer,
+    options: ParserOptions,
+    /// Containers open, innermost last
+    stack: Vec<Frame>,
+    state: State,
+    /// Start of the comma just read after a value, for trailing comma errors
+    comma: Option<usize>,
+    /// Whether the value just read could be the first key of an implicit
+    /// object
+    key_like: bool,
+    /// End of the unquoted string value just read, while the tokens after
+    /// it on the same line may still be part of it
+    unquoted_end: Option<usize>,
+    /// Input from the start of the token being lexed, or from `unquoted_end`
+    /// if earlier, for checking numbers and the gaps between words
+    text: String,
+    /// Stream offset of the first byte of `text`
+    text_offset: usize,
+}
+
+impl Validator {
+    fn new(options: ParserOptions) -> Self {
+        Self {
+            lexer: SimpleStreamingLexer::with_options(options.clone()),
+            options,
+            stack: Vec::new(),
+            state: State::Value,
+            comma: None,
+            key_like: false,
+            unquoted_end: None,
+            text: String::new(),
+            text_offset: 0,
+        }
+    }
+
+    /// Number of bytes fed so far
+    fn position(&self) -> usize {
+        self.lexer.position()
+    }
+
+    fn feed(&mut self, chunk: &str) -> Result<()> {
+        self.text.push_str(chunk);
+        self.lexer.feed_str(chunk)?;
+        self.process_tokens()?;
+        let mut keep_from = self.lexer.unconsumed_start();
+        if let Some(end) = self.unquoted_end {
+            keep_from = keep_from.min(end);
+        }
+        self.text.drain(..keep_from - self.text_offset);
+        self.text_offset = keep_from;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<()> {
+        self.lexer.finish()?;
+        self.process_tokens()?;
+        let end = self.lexer.position();
+
+        if self.state == State::Value && self.stack.is_empty() {
+            // Nothing but whitespace and comments
+            return parse_with_options("", self.options).map(drop);
+        }
+        let implicit_end = match self.stack.as_slice() {
+            [Frame::ImplicitObject] => self.state != State::Colon,
+            [Frame::ImplicitArray] => matches!(self.state, State::AfterValue | State::Value),
+            [] => self.state == State::AfterValue,
+            _ => false,
+        };
+        if !implicit_end {
+            return Err(Error::UnexpectedEof(end));
+        }
+        match self.comma {
+            Some(comma) if !self.options.allow_trailing_commas => Err(Error::TrailingComma(comma)),
+            _ => Ok(()),
+        }
+    }
+
+    fn process_tokens(&mut self) -> Result<()> {
+        while let Some((token, span)) = self.lexer.next_token() {
+            if let Some(end) = self.unquoted_end.take() {
+                let gap = &self.text[end - self.text_offset..span.start - self.text_offset];
+                if self.options.unquoted_value_mode.continues(token, gap) {
+                    self.unquoted_end = Some(span.end);
+                    continue;
+                }
+            }
+            match token {
+                Token::SingleLineComment | Token::MultiLineComment => {}
+                // Only lexed when newlines count as commas
+                Token::Newline => self.newline(),
+                _ => match self.state {
+                    State::Value => self.value(token, span)?,
+                    State::Key => self.key(token, span)?,
+                    State::Colon if token == Token::Colon => {
+                        self.state = State::Value;
+                        self.comma = None;
+                    }
+                    State::Colon => return Err(expected(":", token, span)),
+                    State::AfterValue => self.after_value(token, span)?,
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, token: Token, span: Span) -> Result<()> {
+        match (token, self.stack.last()) {
+            (Token::LeftBrace, _) => {
+                self.open(Frame::Object, span)?;
+                self.state = State::Key;
+            }
+            (Token::LeftBracket, _) => {
+                self.open(Frame::Array, span)?;
+                self.state = State::Value;
+            }
+            (Token::Number, _) => {
+                self.check_number(span)?;
+                self.end_value(true);
+            }
+            (Token::String, _) => self.end_value(true),
+            (Token::UnquotedString, _) => {
+                self.end_value(true);
+                self.unquoted_end = Some(span.end);
+            }
+            (Token::True | Token::False | Token::Null, _) => self.end_value(false),
+            // A separator with no value before it, which is not a trailing
+            // comma even when one follows the last value
+            (Token::Comma, Some(Frame::Array | Frame::ImplicitArray)) => self.comma = None,
+            (Token::Comma, Some(Frame::Object | Frame::ImplicitObject)) => {
+                self.state = State::Key;
+                self.comma = None;
+            }
+            (Token::RightBracket, Some(Frame::Array)) => self.close()?,
+            _ => return Err(expected("value", token, span)),
+        }
+        Ok(())
+    }
+
+    fn key(&mut self, token: Token, span: Span) -> Result<()> {
+        match (token, self.stack.last()) {
+            (Token::String | Token::UnquotedString | Token::Number, _) => {
+                self.state = State::Colon;
+                self.comma = None;
+            }
+            (Token::Comma, _) => self.comma = None,
+            (Token::RightBrace, Some(Frame::Object)) => self.close()?,
+            _ => return Err(expected("key", token, span)),
+        }
+        Ok(())
+    }
+
+    fn after_value(&mut self, token: Token, span: Span) -> Result<()> {
+        let implicit = self.options.implicit_top_level;
+        match (token, self.stack.last()) {
+            (Token::Comma, Some(Frame::Object | Frame::ImplicitObject)) => {
+                self.state = State::Key;
+                self.comma = Some(span.start);
+            }
+            (Token::Comma, Some(Frame::Array | Frame::ImplicitArray)) => {
+                self.state = State::Value;
+                self.comma = Some(span.start);
+            }
+            (Token::Comma, None) if implicit => {
+                self.stack.push(Frame::ImplicitArray);
+                self.state = State::Value;
+                self.comma = Some(span.start);
+            }
+            (Token::Colon, None) if implicit && self.key_like => {
+                self.stack.push(Frame::ImplicitObject);
+                self.state = State::Value;
+            }
+            (Token::RightBrace, Some(Frame::Object))
+            | (Token::RightBracket, Some(Frame::Array)) => self.close()?,
+            // Where newlines separate values, so does other whitespace
+            (_, Some(Frame::Object)) if self.options.newline_as_comma && starts_value(token) => {
+                self.state = State::Key;
+                self.key(token, span)?;
+            }
+            (_, Some(Frame::Array)) if self.options.newline_as_comma && starts_value(token) => {
+                self.state = State::Value;
+                self.value(token, span)?;
+            }
+            (_, Some(Frame::Object)) => return Err(expected(", or } or newline", token, span)),
+            (_, Some(Frame::Array)) => return Err(expected(", or ] or newline", token, span)),
+            (_, Some(Frame::ImplicitObject | Frame::ImplicitArray)) => {
+                return Err(expected(",", token, span))
+            }
+            (_, None) => return Err(expected("end of input", token, span)),
+        }
+        Ok(())
+    }
+
+    /// A newline where a comma could go stands for one, without counting
+    /// as a trailing comma before the end of the container
+    fn newline(&mut self) {
+        if self.state == State::AfterValue {
+            match self.stack.last() {
+                Some(Frame::Object | Frame::ImplicitObject) => self.state = State::Key,
+                Some(Frame::Array | Frame::ImplicitArray) => self.state = State::Value,
+                None => {}
+            }
+        }
+    }
+
+    fn open(&mut self, frame: Frame, span: Span) -> Result<()> {
+        let implicit = matches!(
+            self.stack.first(),
+            Some(Frame::ImplicitObject | Frame::ImplicitArray)
+        );
+        if self.stack.len() - usize::from(implicit) >= self.options.max_depth {
+            return Err(Error::DepthLimitExceeded(span.start));
+        }
+        self.stack.push(frame);
+        self.comma = None;
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        if let Some(comma) = self.comma {
+            if !self.options.allow_trailing_commas {
+                return Err(Error::TrailingComma(comma));
+            }
+        }
+        self.stack.pop();
+        self.end_value(false);
+        Ok(())
+    }
+
+    fn end_value(&mut self, key_like: bool) {
+        self.state = State::AfterValue;
+        self.comma = None;
+        self.key_like = key_like;
+    }
+
+    fn check_number(&self, span: Span) -> Result<()> {
+        let text = &self.text[span.start - self.text_offset..span.end - self.text_offset];
+        read_number(text, span.start, &self.options).map(drop)
+    }
+}
+
+fn starts_value(token: Token) -> bool {
+    matches!(
+        token,
+        Token::LeftBrace
+            | Token::LeftBracket
+            | Token::String
+            | Token::UnquotedString
+            | Token::Number
+            | Token::True
+            | Token::False
+            | Token::Null
+    )
+}
+
+fn expected(what: &str, token: Token, span: Span) -> Error {
+    Error::Expected {
+        expected: what.to_string(),
+        found: format!("{token:?}"),
+        position: span.start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::UnquotedValueMode;
+
+    #[test]
+    fn test_agrees_with_parser() {
+        let strict = ParserOptions {
+            allow_trailing_commas: false,
+            implicit_top_level: false,
+            newline_as_comma: false,
+            ..Default::default()
+        };
+        let rest_of_line = ParserOptions {
+            unquoted_value_mode: UnquotedValueMode::RestOfLine,
+            ..Default::default()
+        };
+        for input in [
+            "",
+            "  // only a comment\n",
+            "[]",
+            "{}",
+            "[1, [2, {\"a\": [3]}]]",
+            "[1,,2]",
+            "[,1]",
+            "[1,]",
+            "{a: 1,}",
+            "{a:1,,b:2}",
+            "{,}",
+            "[abc]",
+            "{'a': b}",
+            "{1: 2}",
+            "[-.5]",
+            "[1 2]",
+            "[1}",
+            "{\"a\" 1}",
+            "{\"a\":}",
+            "[1]]",
+            "]",
+            "[1",
+            "{\"a\": [1, 2}",
+            "[1]\n[2]",
+            "{\"a\":1\n\"b\":2}",
+            "[1\n]",
+            "[1\n,2]",
+            "a: 1, b: 2",
+            "a: 1\nb: [2]",
+            "1, 2, 3",
+            "1, 2,",
+            "a: 1,",
+            "a:",
+            "a: 1: 2",
+            "[1] 2",
+            "[1e]",
+            "[-]",
+            "/* open",
+            "\"open",
+            "[true false]",
+            "{\"a\": {\"b\": [null,]},}",
+            "{a:1 b:2}",
+            "1 2",
+            "a 1",
+            "[1,,]",
+            "{a:1,,}",
+            "{a:1}}",
+            "{a:1]",
+            "{a}",
+            "{a,b}",
+            "[{}{}]",
+            "{key: hello world}",
+            "{key: hello world  , b: 1}",
+            "{key: hello\nworld}",
+            "[hello 1 true, x]",
+            "{k: hello \"q\"}",
+            "{k: hello /* c */ world}",
+            "a b c",
+            "key: hello world\nother: a b",
+        ] {
+            for options in [
+                ParserOptions::default(),
+                strict.clone(),
+                rest_of_line.clone(),
+            ] {
+                let validated = validate_stream(input.as_bytes(), options.clone());
+                let parsed = parse_with_options(input, options);
+                assert_eq!(
+                    validated.is_ok(),
+                    parsed.is_ok(),
+                    "{input:?}: {validated:?} vs {parsed:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let position = |input: &str| {
+            validate_stream(input.as_bytes(), ParserOptions::default())
+                .unwrap_err()
+                .position()
+        };
+        assert_eq!(position("[1 : 2]"), Some(3));
+        assert_eq!(position("{\"a\": [1]]"), Some(9));
+        assert_eq!(position("[1, [2, \"é\"]: 3]"), Some(13));
+        assert_eq!(position("[1, 2"), Some(5));
+
+        let strict = ParserOptions {
+            allow_trailing_commas: false,
+            ..Default::default()
+        };
+        let error = validate_stream("[1, 2, ]".as_bytes(), strict).unwrap_err();
+        assert_eq!(error, Error::TrailingComma(5));
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let options = ParserOptions {
+            max_depth: 3,
+            ..Default::default()
+        };
+        assert!(validate_stream("[[[1]]]".as_bytes(), options.clone()).is_ok());
+        assert_eq!(
+            validate_stream("[[[[1]]]]".as_bytes(), options).unwrap_err(),
+            Error::DepthLimitExceeded(3)
+        );
+    }
+}
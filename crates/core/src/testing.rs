@@ -0,0 +1,69 @@
+// this_file: crates/core/src/testing.rs
+
+//! Assertions for tests of code that produces [`Value`]s, behind the
+//! `testing` cargo feature.
+
+use crate::ast::{DiffKind, Value};
+use crate::format::{to_compact_string, KeyOrder};
+use crate::parser::{parse_with_options, ParserOptions};
+
+/// Asserts that `value` survives being written as JSON and read back.
+///
+/// The value is written with [`to_compact_string`], keys sorted, and the
+/// text parsed as strict JSON with exact numbers. The result must equal
+/// `value` semantically, as [`Value::equals_semantic`] compares.
+///
+/// # Panics
+///
+/// If the text does not parse, or parses to a different value. The message
+/// shows the text and the parse error or each place the values differ.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{parse, testing::assert_roundtrip};
+///
+/// assert_roundtrip(&parse(r#"{"name": "Ada", "langs": ["en", "fr"]}"#).unwrap());
+/// ```
+pub fn assert_roundtrip(value: &Value) {
+    let json = to_compact_string(value, &KeyOrder::default());
+    let reparsed = match parse_with_options(&json, strict_options(value)) {
+        Ok(reparsed) => reparsed,
+        Err(error) => panic!("value does not round trip: {error}\n  written as: {json}"),
+    };
+    let differences = value.diff(&reparsed);
+    if !differences.is_empty() {
+        let differences: Vec<String> = differences
+            .iter()
+            .map(|entry| match &entry.kind {
+                DiffKind::Added(right) => format!("  {}: read back {right}", entry.path),
+                DiffKind::Removed(left) => format!("  {}: {left} was lost", entry.path),
+                DiffKind::Changed { left, right } => {
+                    format!("  {}: {left} read back as {right}", entry.path)
+                }
+            })
+            .collect();
+        panic!(
+            "value does not round trip\n{}\n  written as: {json}",
+            differences.join("\n")
+        );
+    }
+}
+
+/// Options accepting standard JSON only, nested as deeply as `value`.
+fn strict_options(value: &Value) -> ParserOptions {
+    let defaults = ParserOptions::default();
+    ParserOptions {
+        allow_comments: false,
+        allow_trailing_commas: false,
+        allow_unquoted_keys: false,
+        allow_single_quotes: false,
+        implicit_top_level: false,
+        newline_as_comma: false,
+        allow_unicode_whitespace: false,
+        enable_repair: false,
+        preserve_number_precision: true,
+        max_depth: defaults.max_depth.max(value.depth()),
+        ..defaults
+    }
+}
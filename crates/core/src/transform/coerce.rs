@@ -0,0 +1,268 @@
+// this_file: crates/core/src/transform/coerce.rs
+
+//! Converting strings that hold numbers, booleans or null into typed values.
+//!
+//! CSV conversions and form submissions often produce documents such as
+//! `{"age": "42", "active": "true"}`, in which every scalar is a string.
+
+use crate::ast::Value;
+use crate::error::Span;
+use crate::parser::number::{exact_number, is_strict_number, read_number};
+use crate::parser::ParserOptions;
+
+/// Which strings [`coerce_scalars`] converts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoerceOptions {
+    /// Whether to convert strings holding a number, such as `"42"` or
+    /// `"-1.5e3"`.
+    pub numbers: bool,
+    /// Whether to convert `"true"` and `"false"`.
+    pub booleans: bool,
+    /// Whether to convert `"null"`.
+    pub null: bool,
+    /// Whether to keep numbers written with leading zeros, such as ZIP codes
+    /// like `"01234"`, as strings. Otherwise they become numbers, losing the
+    /// zeros.
+    pub preserve_leading_zeros: bool,
+    /// Whether numbers written with an exponent, such as `"1e3"`, become
+    /// floats even when they are whole, as
+    /// [`ParserOptions::exponent_as_float`] has the parser read them.
+    pub exponent_as_float: bool,
+}
+
+impl Default for CoerceOptions {
+    fn default() -> Self {
+        Self {
+            numbers: true,
+            booleans: true,
+            null: true,
+            preserve_leading_zeros: true,
+            exponent_as_float: true,
+        }
+    }
+}
+
+/// Recursively converts string values in `value` that spell a number, a
+/// boolean or null into that value, as `options` allows.
+///
+/// Strings must match exactly: `" 42 "`, `"True"` and `"1."` stay strings.
+/// Numbers are read as the parser reads them in strict JSON, and stay
+/// strings unless an `i64` or `f64` holds them without rounding, so
+/// `"123456789012345678901234"` and `"1e400"` are left alone. Object keys
+/// are never changed.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::{parse, transform::{coerce_scalars, CoerceOptions}};
+///
+/// let mut value = parse(r#"{"age": "42", "active": "true", "zip": "01234"}"#).unwrap();
+/// coerce_scalars(&mut value, &CoerceOptions::default());
+/// assert_eq!(value, parse(r#"{"age": 42, "active": true, "zip": "01234"}"#).unwrap());
+/// ```
+pub fn coerce_scalars(value: &mut Value, options: &CoerceOptions) {
+    match value {
+        Value::Array(arr) => {
+            for item in arr.iter_mut() {
+                coerce_scalars(item, options);
+            }
+        }
+        Value::Object(obj) => {
+            for item in obj.values_mut() {
+                coerce_scalars(item, options);
+            }
+        }
+        Value::String(s) => {
+            if let Some(coerced) = coerce_string(s, options) {
+                *value = coerced;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn coerce_string(s: &str, options: &CoerceOptions) -> Option<Value> {
+    match s {
+        "true" | "false" if options.booleans => Some(Value::Bool(s == "true")),
+        "null" if options.null => Some(Value::Null),
+        _ if options.numbers => coerce_number(s, options),
+        _ => None,
+    }
+}
+
+fn coerce_number(s: &str, options: &CoerceOptions) -> Option<Value> {
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    let leading_zeros =
+        unsigned.starts_with('0') && unsigned[1..].starts_with(|c: char| c.is_ascii_digit());
+    if !leading_zeros {
+        return read_exact(s, options);
+    }
+    if options.preserve_leading_zeros {
+        return None;
+    }
+    // Drop the zeros, keeping one before a fraction or exponent
+    let zeros = unsigned.len() - unsigned.trim_start_matches('0').len();
+    let rest = &unsigned[zeros..];
+    let keep = if rest.starts_with(|c: char| c.is_ascii_digit()) {
+        0
+    } else {
+        1
+    };
+    let sign = &s[..s.len() - unsigned.len()];
+    read_exact(&format!("{sign}{}", &unsigned[zeros - keep..]), options)
+}
+
+/// Reads `text` as the number strict JSON spells with it, if one can be
+/// stored exactly.
+fn read_exact(text: &str, options: &CoerceOptions) -> Option<Value> {
+    if !is_strict_number(text) {
+        return None;
+    }
+    let parser_options = ParserOptions {
+        exponent_as_float: options.exponent_as_float,
+        ..Default::default()
+    };
+    let number = read_number(text, 0, &parser_options).ok()?;
+    exact_number(text, Span::new(0, text.len()), Value::Number(number)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Number;
+    use crate::parse;
+
+    #[test]
+    fn test_coerce_form_data() {
+        let mut value = parse(
+            r#"{"age": "42", "active": "true", "admin": "false", "note": "null",
+                "score": "-2.5", "name": "Ada", "tags": ["7", "x"], "42": "y"}"#,
+        )
+        .unwrap();
+        coerce_scalars(&mut value, &CoerceOptions::default());
+        assert_eq!(
+            value,
+            parse(
+                r#"{"age": 42, "active": true, "admin": false, "note": null,
+                    "score": -2.5, "name": "Ada", "tags": [7, "x"], "42": "y"}"#
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_inexact_spellings_stay_strings() {
+        for s in [
+            " 42",
+            "True",
+            "1.",
+            ".5",
+            "0x10",
+            "NaN",
+            "1e400",
+            "",
+            "-",
+            "+1",
+            "123456789012345678901234",
+            "0.10000000000000000001",
+        ] {
+            let mut value = Value::String(s.to_string());
+            coerce_scalars(&mut value, &CoerceOptions::default());
+            assert_eq!(value, Value::String(s.to_string()), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn test_large_integers_keep_every_digit() {
+        let mut value =
+            parse(r#"["9007199254740993", "-9223372036854775808", "00009007199254740993"]"#)
+                .unwrap();
+        let options = CoerceOptions {
+            preserve_leading_zeros: false,
+            ..Default::default()
+        };
+        coerce_scalars(&mut value, &options);
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::Number(Number::Integer(9007199254740993)),
+                Value::Number(Number::Integer(i64::MIN)),
+                Value::Number(Number::Integer(9007199254740993)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_exponents_follow_parser_options() {
+        let mut value = parse(r#"["1.5e3", "-2E2", "1.5e-1"]"#).unwrap();
+        coerce_scalars(&mut value, &CoerceOptions::default());
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::Number(Number::Float(1500.0)),
+                Value::Number(Number::Float(-200.0)),
+                Value::Number(Number::Float(0.15)),
+            ])
+        );
+
+        let mut value = parse(r#"["1.5e3", "-2E2", "1.5e-1"]"#).unwrap();
+        let options = CoerceOptions {
+            exponent_as_float: false,
+            ..Default::default()
+        };
+        coerce_scalars(&mut value, &options);
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::Number(Number::Integer(1500)),
+                Value::Number(Number::Integer(-200)),
+                Value::Number(Number::Float(0.15)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_each_kind_can_be_disabled() {
+        let mut value = parse(r#"["42", "true", "null"]"#).unwrap();
+        let options = CoerceOptions {
+            numbers: false,
+            booleans: false,
+            null: false,
+            ..Default::default()
+        };
+        coerce_scalars(&mut value, &options);
+        assert_eq!(value, parse(r#"["42", "true", "null"]"#).unwrap());
+
+        coerce_scalars(
+            &mut value,
+            &CoerceOptions {
+                booleans: true,
+                ..options
+            },
+        );
+        assert_eq!(value, parse(r#"["42", true, "null"]"#).unwrap());
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let input = r#"["01234", "-007", "00", "0", "0.5", "00.5", "-00.25"]"#;
+        let mut preserved = parse(input).unwrap();
+        coerce_scalars(&mut preserved, &CoerceOptions::default());
+        assert_eq!(
+            preserved,
+            parse(r#"["01234", "-007", "00", 0, 0.5, "00.5", "-00.25"]"#).unwrap()
+        );
+
+        let mut value = parse(input).unwrap();
+        let options = CoerceOptions {
+            preserve_leading_zeros: false,
+            ..Default::default()
+        };
+        coerce_scalars(&mut value, &options);
+        assert_eq!(
+            value,
+            parse(r#"[1234, -7, 0, 0, 0.5, 0.5, -0.25]"#).unwrap()
+        );
+        assert_eq!(value[0], Value::Number(Number::Integer(1234)));
+    }
+}
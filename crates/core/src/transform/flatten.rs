@@ -0,0 +1,240 @@
+// this_file: crates/core/src/transform/flatten.rs
+
+//! Flattening nested JSON into dotted-path keys and back.
+//!
+//! `{"a": {"b": [1, 2]}}` flattens to `{"a.b.0": 1, "a.b.1": 2}`. Dots and
+//! backslashes inside keys are escaped with a backslash so every path maps
+//! back to exactly one location. An empty key is written as `\e`, keeping it
+//! apart from the empty path a scalar root is stored under.
+
+use crate::ast::Value;
+use crate::error::{Error, Result};
+use rustc_hash::FxHashMap;
+
+/// Flattens `value` into a single-level object keyed by dotted paths.
+///
+/// Array indices become numeric path segments. Scalars and empty containers
+/// are kept as leaves, so [`unflatten`] can rebuild the original document. A
+/// root that is not a non-empty container is stored under the empty path.
+pub fn flatten(value: &Value) -> Value {
+    let mut flat = FxHashMap::default();
+    flatten_into(value, String::new(), &mut flat);
+    Value::Object(flat)
+}
+
+/// Rebuilds a nested document from the output of [`flatten`].
+///
+/// A container whose path segments are all array indices is rebuilt as an
+/// array, with indices missing from the input filled with `null`. Objects
+/// keyed only by such numbers therefore come back as arrays. So that a few
+/// large indices cannot ask for a huge array, one that would be more than
+/// half `null` is rebuilt as an object keyed by its indices instead.
+///
+/// # Errors
+///
+/// Returns an error if `value` is not an object, or if two paths disagree on
+/// the shape of the document (e.g. `a` and `a.b` both holding values).
+pub fn unflatten(value: &Value) -> Result<Value> {
+    let Value::Object(flat) = value else {
+        return Err(Error::Custom("Only objects can be unflattened".to_string()));
+    };
+
+    let mut root = None;
+    for (path, leaf) in flat {
+        insert(&mut root, &split_path(path), leaf.clone(), path)?;
+    }
+    Ok(root.map_or_else(|| Value::Object(FxHashMap::default()), Node::into_value))
+}
+
+fn flatten_into(value: &Value, path: String, flat: &mut FxHashMap<String, Value>) {
+    match value {
+        Value::Object(obj) if !obj.is_empty() => {
+            for (key, item) in obj {
+                flatten_into(item, join(&path, &escape_key(key)), flat);
+            }
+        }
+        Value::Array(arr) if !arr.is_empty() => {
+            for (index, item) in arr.iter().enumerate() {
+                flatten_into(item, join(&path, &index.to_string()), flat);
+            }
+        }
+        _ => {
+            flat.insert(path, value.clone());
+        }
+    }
+}
+
+fn join(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{path}.{segment}")
+    }
+}
+
+fn escape_key(key: &str) -> String {
+    if key.is_empty() {
+        return "\\e".to_string();
+    }
+    key.replace('\\', "\\\\").replace('.', "\\.")
+}
+
+/// Splits a path on unescaped dots, unescaping each segment. `\e` stands
+/// for nothing, so that it spells an empty key.
+fn split_path(path: &str) -> Vec<String> {
+    if path.is_empty() {
+        return Vec::new();
+    }
+
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => current.extend(chars.next().filter(|&c| c != 'e')),
+            '.' => segments.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    segments.push(current);
+    segments
+}
+
+/// Partially rebuilt document, distinguishing missing entries from `null` leaves.
+enum Node {
+    Leaf(Value),
+    Branch(FxHashMap<String, Node>),
+}
+
+impl Node {
+    /// Converts the tree into a value; branches whose keys are all array
+    /// indices become arrays.
+    fn into_value(self) -> Value {
+        let entries = match self {
+            Node::Leaf(value) => return value,
+            Node::Branch(entries) => entries,
+        };
+
+        let indices: Option<Vec<usize>> = entries
+            .keys()
+            .map(|key| key.parse::<usize>().ok().filter(|i| i.to_string() == *key))
+            .collect();
+        // Indices come from the input's keys, so an array at most half
+        // `null` bounds the memory a few keys can ask for
+        let len = indices.as_ref().and_then(|indices| {
+            let len = indices
+                .iter()
+                .max()
+                .map_or(Some(0), |max| max.checked_add(1))?;
+            (len <= entries.len().saturating_mul(2)).then_some(len)
+        });
+        match (indices, len) {
+            (Some(indices), Some(len)) => {
+                let mut items = vec![Value::Null; len];
+                for (index, (_, node)) in indices.into_iter().zip(entries) {
+                    items[index] = node.into_value();
+                }
+                Value::Array(items)
+            }
+            _ => Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, node)| (key, node.into_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn insert(slot: &mut Option<Node>, segments: &[String], leaf: Value, path: &str) -> Result<()> {
+    let conflict = || Error::Custom(format!("Conflicting paths at {path:?}"));
+
+    let Some((segment, rest)) = segments.split_first() else {
+        if slot.is_some() {
+            return Err(conflict());
+        }
+        *slot = Some(Node::Leaf(leaf));
+        return Ok(());
+    };
+
+    let Node::Branch(entries) = slot.get_or_insert_with(|| Node::Branch(FxHashMap::default()))
+    else {
+        return Err(conflict());
+    };
+    let mut child = entries.remove(segment);
+    insert(&mut child, rest, leaf, path)?;
+    entries.extend(child.map(|node| (segment.clone(), node)));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_flatten_paths() {
+        let value = parse(r#"{"a": {"b": [1, {"c": true}]}, "d": null}"#).unwrap();
+        let flat = flatten(&value);
+
+        let expected = parse(r#"{"a.b.0": 1, "a.b.1.c": true, "d": null}"#).unwrap();
+        assert_eq!(flat, expected);
+    }
+
+    #[test]
+    fn test_flatten_round_trip() {
+        let documents = [
+            r#"{"a": {"b": {"c": 1}}, "list": [1, [2, 3], {"x": "y"}], "n": null}"#,
+            r#"{"empty": {}, "none": [], "nested": [[], [{}]]}"#,
+            r#"{"dotted.key": {"back\\slash": 1}, "0": {"1x": 2}}"#,
+            r#"[{"a": 1}, {"a": 2}]"#,
+            "42",
+            "{}",
+            r#"{"": 1}"#,
+            r#"{"": {"": [""]}, "a": {"": null}}"#,
+        ];
+        for json in documents {
+            let value = parse(json).unwrap();
+            let restored = unflatten(&flatten(&value)).unwrap();
+            assert_eq!(restored, value, "round trip of {json}");
+        }
+    }
+
+    #[test]
+    fn test_empty_keys_stay_apart_from_the_root() {
+        let value = parse(r#"{"": 1, "a": {"": 2}}"#).unwrap();
+        let flat = flatten(&value);
+        assert_eq!(flat, parse(r#"{"\\e": 1, "a.\\e": 2}"#).unwrap());
+        assert_ne!(flat, flatten(&parse("1").unwrap()));
+    }
+
+    #[test]
+    fn test_unflatten_fills_missing_indices() {
+        let flat = parse(r#"{"a.2": 3, "a.0": 1}"#).unwrap();
+        assert_eq!(
+            unflatten(&flat).unwrap(),
+            parse(r#"{"a": [1, null, 3]}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unflatten_sparse_indices_stay_objects() {
+        for json in [
+            r#"{"a.18446744073709551615": 1}"#,
+            r#"{"a.1000000000000": 1, "a.0": 2}"#,
+            r#"{"a.4": 1, "a.0": 2}"#,
+        ] {
+            let flat = parse(json).unwrap();
+            let value = unflatten(&flat).unwrap();
+            assert!(value["a"].is_object(), "{json}");
+            assert_eq!(flatten(&value), flat, "{json}");
+        }
+    }
+
+    #[test]
+    fn test_unflatten_conflicts() {
+        assert!(unflatten(&parse(r#"{"a": 1, "a.b": 2}"#).unwrap()).is_err());
+        assert!(unflatten(&parse(r#"{"a.b": 1, "a": {}}"#).unwrap()).is_err());
+        assert!(unflatten(&parse("[1]").unwrap()).is_err());
+    }
+}
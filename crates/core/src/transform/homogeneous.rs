@@ -0,0 +1,224 @@
+// this_file: crates/core/src/transform/homogeneous.rs
+
+//! Checking that every array holds values of a single type.
+//!
+//! Columnar formats such as Arrow and Parquet store an array as one typed
+//! column, so an array that mixes types is best found before ingesting it.
+
+use crate::ast::{JsonPath, Number, PathSegment, Value};
+use std::fmt;
+
+/// The type of a value, as [`check_homogeneous`] compares them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Boolean,
+    /// A number held as an integer
+    Integer,
+    /// A number held as a float
+    Float,
+    /// A string
+    String,
+    /// An array, whatever its elements
+    Array,
+    /// An object, whatever its keys
+    Object,
+}
+
+impl ValueType {
+    /// The type of `value`.
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => ValueType::Null,
+            Value::Bool(_) => ValueType::Boolean,
+            Value::Number(Number::Integer(_)) => ValueType::Integer,
+            Value::Number(Number::Float(_)) => ValueType::Float,
+            Value::String(_) => ValueType::String,
+            Value::Array(_) => ValueType::Array,
+            Value::Object(_) => ValueType::Object,
+        }
+    }
+
+    fn is_number(self) -> bool {
+        matches!(self, ValueType::Integer | ValueType::Float)
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ValueType::Null => "null",
+            ValueType::Boolean => "boolean",
+            ValueType::Integer => "integer",
+            ValueType::Float => "float",
+            ValueType::String => "string",
+            ValueType::Array => "array",
+            ValueType::Object => "object",
+        })
+    }
+}
+
+/// How [`check_homogeneous_with_options`] compares types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HomogeneousOptions {
+    /// Whether integers and floats count as the same type, so that
+    /// `[1, 2.5]` passes.
+    pub numbers_compatible: bool,
+}
+
+impl Default for HomogeneousOptions {
+    fn default() -> Self {
+        Self {
+            numbers_compatible: true,
+        }
+    }
+}
+
+/// An array element whose type differs from that of the first element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatch {
+    /// Path of the array
+    pub path: JsonPath,
+    /// Index of the element within the array
+    pub index: usize,
+    /// Type of the first element of the array
+    pub expected: ValueType,
+    /// Type of this element
+    pub actual: ValueType,
+}
+
+impl TypeMismatch {
+    /// Path of the offending element.
+    pub fn element_path(&self) -> JsonPath {
+        let mut path = self.path.clone();
+        path.push(PathSegment::Index(self.index));
+        path
+    }
+}
+
+impl fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {} like the first element, found {}",
+            self.element_path(),
+            self.expected,
+            self.actual
+        )
+    }
+}
+
+/// Finds every array element in `value` whose type differs from that of
+/// the first element of its array, with integers and floats compatible.
+///
+/// See [`check_homogeneous_with_options`].
+pub fn check_homogeneous(value: &Value) -> Vec<TypeMismatch> {
+    check_homogeneous_with_options(value, &HomogeneousOptions::default())
+}
+
+/// Finds every array element in `value` whose type differs from that of
+/// the first element of its array.
+///
+/// Only the type of each element counts: objects with different keys, or
+/// arrays with different contents, are alike. Nested arrays are checked on
+/// their own, as are arrays inside objects. Mismatches are listed with
+/// object keys in sorted order, so the result does not depend on how the
+/// object was built.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::parse;
+/// use vexy_json_core::transform::{check_homogeneous, ValueType};
+///
+/// let value = parse(r#"{"ids": [1, 2.5, "3"], "rows": [{"a": 1}, {"b": 2}]}"#).unwrap();
+/// let mismatches = check_homogeneous(&value);
+/// assert_eq!(mismatches.len(), 1);
+/// assert_eq!(mismatches[0].element_path().to_string(), "$.ids[2]");
+/// assert_eq!(mismatches[0].actual, ValueType::String);
+/// ```
+pub fn check_homogeneous_with_options(
+    value: &Value,
+    options: &HomogeneousOptions,
+) -> Vec<TypeMismatch> {
+    let mut mismatches = Vec::new();
+    check_value(value, options, &mut JsonPath::root(), &mut mismatches);
+    mismatches
+}
+
+fn check_value(
+    value: &Value,
+    options: &HomogeneousOptions,
+    path: &mut JsonPath,
+    mismatches: &mut Vec<TypeMismatch>,
+) {
+    match value {
+        Value::Array(items) => {
+            if let Some(first) = items.first() {
+                let expected = ValueType::of(first);
+                for (index, item) in items.iter().enumerate().skip(1) {
+                    let actual = ValueType::of(item);
+                    let compatible = actual == expected
+                        || (options.numbers_compatible
+                            && actual.is_number()
+                            && expected.is_number());
+                    if !compatible {
+                        mismatches.push(TypeMismatch {
+                            path: path.clone(),
+                            index,
+                            expected,
+                            actual,
+                        });
+                    }
+                }
+            }
+            for (index, item) in items.iter().enumerate() {
+                path.push(PathSegment::Index(index));
+                check_value(item, options, path, mismatches);
+                path.pop();
+            }
+        }
+        Value::Object(obj) => {
+            let mut entries: Vec<_> = obj.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            for (key, item) in entries {
+                path.push(PathSegment::Key(key.clone()));
+                check_value(item, options, path, mismatches);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_numbers_compatible_by_flag() {
+        let value = parse("[1, 2.5, -3]").unwrap();
+        assert!(check_homogeneous(&value).is_empty());
+
+        let strict = HomogeneousOptions {
+            numbers_compatible: false,
+        };
+        let mismatches = check_homogeneous_with_options(&value, &strict);
+        assert_eq!(
+            mismatches,
+            vec![TypeMismatch {
+                path: JsonPath::root(),
+                index: 1,
+                expected: ValueType::Integer,
+                actual: ValueType::Float,
+            }]
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "$[1]: expected integer like the first element, found float"
+        );
+    }
+}
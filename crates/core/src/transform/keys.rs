@@ -0,0 +1,265 @@
+// this_file: crates/core/src/transform/keys.rs
+
+//! Rewriting object keys into one naming convention.
+//!
+//! APIs disagree on how to write a key made of several words, so the same
+//! field arrives as `userId`, `UserId` or `user_id`. [`normalize_keys`]
+//! brings every key of a document to one [`KeyCase`].
+
+use crate::ast::{JsonPath, PathSegment, Value};
+use rustc_hash::FxHashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A naming convention for object keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyCase {
+    /// `userId`: words run together, each after the first capitalized
+    CamelCase,
+    /// `user_id`: lowercase words joined by underscores
+    SnakeCase,
+    /// `user-id`: lowercase words joined by hyphens
+    KebabCase,
+    /// `userid`: the key lowercased, with its separators kept as written
+    LowerCase,
+}
+
+impl KeyCase {
+    /// Writes `key` in this convention.
+    ///
+    /// Words end at `_`, `-` and whitespace, before an uppercase letter
+    /// that follows a lowercase letter or a digit, and before the last
+    /// letter of a run of capitals that starts a new word, so `HTTPStatus`
+    /// is `HTTP` and `Status`. Digits stay with the word before them, so
+    /// `sha256Hash` becomes `sha256_hash` in snake case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vexy_json_core::transform::KeyCase;
+    ///
+    /// assert_eq!(KeyCase::SnakeCase.convert("HTTPStatus"), "http_status");
+    /// assert_eq!(KeyCase::CamelCase.convert("user_id"), "userId");
+    /// assert_eq!(KeyCase::KebabCase.convert("sha256Hash"), "sha256-hash");
+    /// assert_eq!(KeyCase::LowerCase.convert("User_Id"), "user_id");
+    /// ```
+    pub fn convert(self, key: &str) -> String {
+        match self {
+            KeyCase::CamelCase => {
+                let mut result = String::with_capacity(key.len());
+                for (i, word) in words(key).iter().enumerate() {
+                    let lower = word.to_lowercase();
+                    if i == 0 {
+                        result.push_str(&lower);
+                    } else {
+                        let mut chars = lower.chars();
+                        if let Some(first) = chars.next() {
+                            result.extend(first.to_uppercase());
+                            result.push_str(chars.as_str());
+                        }
+                    }
+                }
+                result
+            }
+            KeyCase::SnakeCase => join_lowercase(key, "_"),
+            KeyCase::KebabCase => join_lowercase(key, "-"),
+            KeyCase::LowerCase => key.to_lowercase(),
+        }
+    }
+}
+
+impl fmt::Display for KeyCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            KeyCase::CamelCase => "camelCase",
+            KeyCase::SnakeCase => "snake_case",
+            KeyCase::KebabCase => "kebab-case",
+            KeyCase::LowerCase => "lowercase",
+        })
+    }
+}
+
+impl FromStr for KeyCase {
+    type Err = String;
+
+    /// Reads a convention by the name [`Display`](fmt::Display) gives it,
+    /// or by its first word, such as `snake`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "camelCase" | "camel_case" | "camel" => Ok(KeyCase::CamelCase),
+            "snake_case" | "snake" => Ok(KeyCase::SnakeCase),
+            "kebab-case" | "kebab_case" | "kebab" => Ok(KeyCase::KebabCase),
+            "lowercase" | "lower" => Ok(KeyCase::LowerCase),
+            _ => Err(format!(
+                "unknown key case '{s}', expected camelCase, snake_case, kebab-case or lowercase"
+            )),
+        }
+    }
+}
+
+/// Splits `key` into the words [`KeyCase::convert`] describes.
+fn words(key: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = key.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, &(index, c)) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if let Some(start) = start.take() {
+                words.push(&key[start..index]);
+            }
+            continue;
+        }
+        if let Some(word_start) = start {
+            let previous = chars[i - 1].1;
+            let next_is_lower = chars.get(i + 1).is_some_and(|&(_, n)| n.is_lowercase());
+            let boundary = c.is_uppercase()
+                && (previous.is_lowercase()
+                    || previous.is_numeric()
+                    || (previous.is_uppercase() && next_is_lower));
+            if boundary {
+                words.push(&key[word_start..index]);
+                start = Some(index);
+            }
+        } else {
+            start = Some(index);
+        }
+    }
+    if let Some(start) = start {
+        words.push(&key[start..]);
+    }
+    words
+}
+
+fn join_lowercase(key: &str, separator: &str) -> String {
+    words(key)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Keys of one object that [`normalize_keys`] would give the same name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyCollision {
+    /// Path of the object
+    pub path: JsonPath,
+    /// The keys as written, sorted
+    pub keys: Vec<String>,
+    /// The name they all become
+    pub target: String,
+}
+
+impl fmt::Display for KeyCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<String> = self.keys.iter().map(|key| format!("{key:?}")).collect();
+        write!(
+            f,
+            "{}: keys {} all become {:?}",
+            self.path,
+            keys.join(", "),
+            self.target
+        )
+    }
+}
+
+/// Rewrites every object key in `value`, at any depth, in `case`.
+///
+/// When two keys of one object would get the same name, as `userId` and
+/// `user_id` do in snake case, that object keeps the keys as written and
+/// the collision is reported; other objects are still rewritten. Collisions
+/// are listed with object keys visited in sorted order.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::parse;
+/// use vexy_json_core::transform::{normalize_keys, KeyCase};
+///
+/// let mut value = parse(r#"{"userId": 1, "HTTPStatus": {"retryAfter": 2}}"#).unwrap();
+/// normalize_keys(&mut value, KeyCase::SnakeCase).unwrap();
+/// assert_eq!(value["user_id"].as_i64(), Some(1));
+/// assert_eq!(value["http_status"]["retry_after"].as_i64(), Some(2));
+///
+/// let mut value = parse(r#"{"rows": [{"userId": 1, "user_id": 2}]}"#).unwrap();
+/// let collisions = normalize_keys(&mut value, KeyCase::SnakeCase).unwrap_err();
+/// assert_eq!(
+///     collisions[0].to_string(),
+///     r#"$.rows[0]: keys "userId", "user_id" all become "user_id""#
+/// );
+/// assert_eq!(value["rows"][0]["userId"].as_i64(), Some(1));
+/// ```
+pub fn normalize_keys(value: &mut Value, case: KeyCase) -> Result<(), Vec<KeyCollision>> {
+    let mut collisions = Vec::new();
+    rewrite(value, case, &mut JsonPath::root(), &mut collisions);
+    if collisions.is_empty() {
+        Ok(())
+    } else {
+        Err(collisions)
+    }
+}
+
+fn rewrite(
+    value: &mut Value,
+    case: KeyCase,
+    path: &mut JsonPath,
+    collisions: &mut Vec<KeyCollision>,
+) {
+    match value {
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                path.push(PathSegment::Index(index));
+                rewrite(item, case, path, collisions);
+                path.pop();
+            }
+        }
+        Value::Object(obj) => {
+            let mut targets: FxHashMap<String, Vec<String>> = FxHashMap::default();
+            for key in obj.keys() {
+                targets
+                    .entry(case.convert(key))
+                    .or_default()
+                    .push(key.clone());
+            }
+            let mut clashes: Vec<(String, Vec<String>)> = targets
+                .into_iter()
+                .filter(|(_, keys)| keys.len() > 1)
+                .collect();
+            clashes.sort_unstable();
+            for (target, keys) in &mut clashes {
+                keys.sort_unstable();
+                collisions.push(KeyCollision {
+                    path: path.clone(),
+                    keys: keys.clone(),
+                    target: target.clone(),
+                });
+            }
+
+            let mut entries: Vec<(String, Value)> = std::mem::take(obj).into_iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            for (key, mut item) in entries {
+                path.push(PathSegment::Key(key.clone()));
+                rewrite(&mut item, case, path, collisions);
+                path.pop();
+                let clashing = clashes.iter().any(|(_, keys)| keys.contains(&key));
+                let key = if clashing { key } else { case.convert(&key) };
+                obj.insert(key, item);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        assert_eq!(words("HTTPStatus"), ["HTTP", "Status"]);
+        assert_eq!(words("userId"), ["user", "Id"]);
+        assert_eq!(words("__user--id  x"), ["user", "id", "x"]);
+        assert_eq!(words("sha256Hash"), ["sha256", "Hash"]);
+        assert_eq!(words("ID"), ["ID"]);
+        assert_eq!(words("_"), Vec::<&str>::new());
+    }
+}
@@ -6,10 +6,32 @@
 //! - Normalizing JSON into canonical forms
 //! - Cleaning up JSON data
 //! - Comparing JSON values
+//! - Sorting arrays into a canonical order
+//! - Flattening nested documents into dotted-path keys
+//! - Converting strings that hold numbers, booleans or null into typed values
+//! - Checking that arrays hold values of a single type
+//! - Rewriting object keys into one naming convention
+//! - Redacting values at given paths before logging
+//! - Rendering size-bounded previews of large documents
+//! - Reformatting forgiving input as minified or indented JSON
 
+pub mod coerce;
+pub mod flatten;
+pub mod homogeneous;
+pub mod keys;
 pub mod normalizer;
 pub mod optimizer;
+pub mod preview;
+pub mod redact;
+pub mod reformat;
+pub mod sort;
 
+pub use coerce::{coerce_scalars, CoerceOptions};
+pub use flatten::{flatten, unflatten};
+pub use homogeneous::{
+    check_homogeneous, check_homogeneous_with_options, HomogeneousOptions, TypeMismatch, ValueType,
+};
+pub use keys::{normalize_keys, KeyCase, KeyCollision};
 pub use normalizer::{
     normalize, normalize_with_options, CanonicalNormalizer, CleanupNormalizer, JsonNormalizer,
     NormalizerOptions,
@@ -18,3 +40,7 @@ pub use optimizer::{
     optimize, optimize_with_options, AstOptimizer, InternerStats, MemoryOptimizer,
     OptimizerOptions, OptimizerStats, PerformanceOptimizer, StringInterner,
 };
+pub use preview::to_preview_string;
+pub use redact::redact;
+pub use reformat::{reformat, reformat_with_options, FormatStyle};
+pub use sort::{sort_array_by, sort_arrays};
@@ -10,7 +10,10 @@
 //! - Sorting object keys for deterministic output
 
 use crate::ast::{Number, Value};
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::format::{to_string_with, FloatFormat, KeyOrder, KeyQuoting};
+use crate::transform::keys::{normalize_keys, KeyCase};
+use crate::transform::sort::compare_values as canonical_order;
 use rustc_hash::FxHashMap;
 use std::cmp::Ordering;
 
@@ -18,11 +21,29 @@ use std::cmp::Ordering;
 #[derive(Debug, Clone)]
 pub struct NormalizerOptions {
     /// Whether to sort object keys alphabetically.
+    ///
+    /// Objects do not keep key order, so this applies when writing the result
+    /// with [`JsonNormalizer::normalize_to_string`]. Unsorted, keys are
+    /// written in the order the normalized object iterates them, which is the
+    /// same on every run but differs between 32- and 64-bit platforms.
     pub sort_keys: bool,
-    /// Whether to remove null values from objects.
+    /// Whether to remove null values from objects and arrays.
     pub remove_null_values: bool,
     /// Whether to remove empty objects and arrays.
+    ///
+    /// Containers that become empty once their own contents are pruned are
+    /// removed as well.
     pub remove_empty_containers: bool,
+    /// Dotted key path by which to sort arrays of objects, e.g. `meta.id`.
+    ///
+    /// Applies, after pruning, only to arrays whose elements are all objects
+    /// and at least one of which has a value at the path; any other array
+    /// keeps its order. The sort is stable, and objects without a value at
+    /// the path keep their relative order after all others.
+    pub sort_arrays_by: Option<String>,
+    /// Whether to convert strings holding a JSON number, such as `"42"` or
+    /// `"-1.5e3"`, into numbers.
+    pub coerce_numeric_strings: bool,
     /// Whether to normalize numbers (convert integers to floats if requested).
     pub normalize_numbers: bool,
     /// Whether to use integers for whole numbers.
@@ -35,6 +56,18 @@ pub struct NormalizerOptions {
     pub deduplicate_arrays: bool,
     /// Maximum nesting depth for recursive normalization.
     pub max_depth: usize,
+    /// How [`JsonNormalizer::normalize_to_string`] writes floats. Values
+    /// themselves are not rounded.
+    pub float_format: FloatFormat,
+    /// Naming convention to rewrite every object key in, at any depth, as
+    /// [`normalize_keys`] does.
+    ///
+    /// Keys of one object that would get the same name are an error at the
+    /// path of the first such object, rather than one value being dropped.
+    pub key_case: Option<KeyCase>,
+    /// How [`JsonNormalizer::normalize_to_string`] quotes keys. With
+    /// [`KeyQuoting::MinimalJson5`] the result is JSON5 rather than JSON.
+    pub key_quoting: KeyQuoting,
 }
 
 impl Default for NormalizerOptions {
@@ -49,6 +82,30 @@ impl Default for NormalizerOptions {
             normalize_string_case: false,
             deduplicate_arrays: false,
             max_depth: 100,
+            sort_arrays_by: None,
+            coerce_numeric_strings: false,
+            float_format: FloatFormat::Shortest,
+            key_case: None,
+            key_quoting: KeyQuoting::Always,
+        }
+    }
+}
+
+impl NormalizerOptions {
+    /// Options producing a canonical form for comparison, as used by
+    /// [`CanonicalNormalizer::canonicalize`].
+    pub fn canonical() -> Self {
+        Self::default()
+    }
+
+    /// Options removing nulls, empty containers and surrounding whitespace,
+    /// as used by [`CleanupNormalizer::cleanup`].
+    pub fn cleanup() -> Self {
+        Self {
+            remove_null_values: true,
+            remove_empty_containers: true,
+            trim_strings: true,
+            ..Self::default()
         }
     }
 }
@@ -76,7 +133,42 @@ impl JsonNormalizer {
     /// Normalizes a JSON value according to the configured options.
     pub fn normalize(&mut self, value: &Value) -> Result<Value> {
         self.depth = 0;
-        self.normalize_value(value)
+        let mut normalized = self.normalize_value(value)?;
+        if let Some(case) = self.options.key_case {
+            if let Err(collisions) = normalize_keys(&mut normalized, case) {
+                let collision = &collisions[0];
+                let keys: Vec<String> = collision.keys.iter().map(|k| format!("{k:?}")).collect();
+                return Err(Error::AtPath {
+                    path: collision.path.clone(),
+                    source: Box::new(Error::Custom(format!(
+                        "keys {} all become {:?} in {case}",
+                        keys.join(", "),
+                        collision.target
+                    ))),
+                });
+            }
+        }
+        Ok(normalized)
+    }
+
+    /// Normalizes a JSON value and writes it as compact JSON, with object
+    /// keys sorted if [`NormalizerOptions::sort_keys`] is set, floats
+    /// written as [`NormalizerOptions::float_format`] asks and keys quoted
+    /// as [`NormalizerOptions::key_quoting`] asks.
+    pub fn normalize_to_string(&mut self, value: &Value) -> Result<String> {
+        let normalized = self.normalize(value)?;
+        let order = if self.options.sort_keys {
+            KeyOrder::default()
+        } else {
+            KeyOrder::from_value(&normalized)
+        };
+        Ok(to_string_with(
+            &normalized,
+            None,
+            &order,
+            self.options.float_format,
+            self.options.key_quoting,
+        ))
     }
 
     /// Internal method to normalize a value recursively.
@@ -98,10 +190,15 @@ impl JsonNormalizer {
     }
 
     /// Normalizes an object.
+    ///
+    /// Members are inserted in sorted key order, so equal objects iterate
+    /// alike however they were built.
     fn normalize_object(&mut self, obj: &FxHashMap<String, Value>) -> Result<Value> {
         let mut normalized = FxHashMap::default();
 
-        for (key, value) in obj {
+        let mut entries: Vec<_> = obj.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        for (key, value) in entries {
             let normalized_value = self.normalize_value(value)?;
 
             // Skip null values if configured
@@ -159,6 +256,20 @@ impl JsonNormalizer {
             normalized.dedup();
         }
 
+        if let Some(path) = &self.options.sort_arrays_by {
+            let path: Vec<&str> = path.split('.').collect();
+            let keyed = normalized.iter().all(Value::is_object)
+                && normalized.iter().any(|item| lookup(item, &path).is_some());
+            if keyed {
+                normalized.sort_by(|a, b| match (lookup(a, &path), lookup(b, &path)) {
+                    (Some(a), Some(b)) => canonical_order(a, b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                });
+            }
+        }
+
         Ok(Value::Array(normalized))
     }
 
@@ -174,6 +285,12 @@ impl JsonNormalizer {
             normalized = normalized.to_lowercase();
         }
 
+        if self.options.coerce_numeric_strings {
+            if let Some(n) = parse_json_number(&normalized) {
+                return self.normalize_number(&n);
+            }
+        }
+
         Value::String(normalized)
     }
 
@@ -188,7 +305,9 @@ impl JsonNormalizer {
                 }
             }
             Number::Float(f) => {
-                if self.options.prefer_integers && f.fract() == 0.0 && f.is_finite() {
+                // `i64::MAX as f64` rounds up to 2^63, which is out of range
+                let in_range = *f >= i64::MIN as f64 && *f < i64::MAX as f64;
+                if self.options.prefer_integers && f.fract() == 0.0 && in_range {
                     Value::Number(Number::Integer(*f as i64))
                 } else {
                     Value::Number(Number::Float(*f))
@@ -247,6 +366,50 @@ impl JsonNormalizer {
     }
 }
 
+/// Follows a key path through nested objects.
+fn lookup<'v>(value: &'v Value, path: &[&str]) -> Option<&'v Value> {
+    path.iter()
+        .try_fold(value, |current, key| current.as_object()?.get(*key))
+}
+
+/// Parses `s` if it is a number in strict JSON syntax.
+pub(super) fn parse_json_number(s: &str) -> Option<Number> {
+    fn digits(s: &str) -> (&str, &str) {
+        s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()))
+    }
+
+    let (int, rest) = digits(s.strip_prefix('-').unwrap_or(s));
+    if int.is_empty() || (int.len() > 1 && int.starts_with('0')) {
+        return None;
+    }
+    let rest = match rest.strip_prefix('.') {
+        Some(after) => match digits(after) {
+            ("", _) => return None,
+            (_, rest) => rest,
+        },
+        None => rest,
+    };
+    let rest = match rest.strip_prefix(['e', 'E']) {
+        Some(after) => match digits(after.strip_prefix(['+', '-']).unwrap_or(after)) {
+            ("", _) => return None,
+            (_, rest) => rest,
+        },
+        None => rest,
+    };
+    if !rest.is_empty() {
+        return None;
+    }
+
+    match s.parse::<i64>() {
+        Ok(i) => Some(Number::Integer(i)),
+        Err(_) => s
+            .parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .map(Number::Float),
+    }
+}
+
 impl Default for JsonNormalizer {
     fn default() -> Self {
         Self::new()
@@ -271,34 +434,15 @@ pub struct CanonicalNormalizer;
 impl CanonicalNormalizer {
     /// Creates a canonical form of JSON for comparison purposes.
     pub fn canonicalize(value: &Value) -> Result<Value> {
-        let options = NormalizerOptions {
-            sort_keys: true,
-            remove_null_values: false,
-            remove_empty_containers: false,
-            normalize_numbers: false,
-            prefer_integers: true,
-            trim_strings: false,
-            normalize_string_case: false,
-            deduplicate_arrays: false,
-            max_depth: 100,
-        };
-
-        let mut normalizer = JsonNormalizer::with_options(options);
+        let mut normalizer = JsonNormalizer::with_options(NormalizerOptions::canonical());
         normalizer.normalize(value)
     }
 
     /// Creates a canonical form for deep comparison (sorts everything).
     pub fn deep_canonicalize(value: &Value) -> Result<Value> {
         let options = NormalizerOptions {
-            sort_keys: true,
-            remove_null_values: false,
-            remove_empty_containers: false,
-            normalize_numbers: false,
-            prefer_integers: true,
-            trim_strings: false,
-            normalize_string_case: false,
             deduplicate_arrays: true,
-            max_depth: 100,
+            ..NormalizerOptions::canonical()
         };
 
         let mut normalizer = JsonNormalizer::with_options(options);
@@ -312,19 +456,7 @@ pub struct CleanupNormalizer;
 impl CleanupNormalizer {
     /// Cleans up JSON by removing null values and empty containers.
     pub fn cleanup(value: &Value) -> Result<Value> {
-        let options = NormalizerOptions {
-            sort_keys: true,
-            remove_null_values: true,
-            remove_empty_containers: true,
-            normalize_numbers: false,
-            prefer_integers: true,
-            trim_strings: true,
-            normalize_string_case: false,
-            deduplicate_arrays: false,
-            max_depth: 100,
-        };
-
-        let mut normalizer = JsonNormalizer::with_options(options);
+        let mut normalizer = JsonNormalizer::with_options(NormalizerOptions::cleanup());
         normalizer.normalize(value)
     }
 }
@@ -384,7 +516,7 @@ mod tests {
                 normalized_obj.get("keep"),
                 Some(&Value::String("value".to_string()))
             );
-            assert!(normalized_obj.get("remove").is_none());
+            assert!(!normalized_obj.contains_key("remove"));
         } else {
             panic!("Expected object");
         }
@@ -553,4 +685,194 @@ mod tests {
         // Should return original value when depth limit is exceeded
         assert_eq!(normalized, value);
     }
+
+    #[test]
+    fn test_sort_keys_controls_serialization() {
+        let value = crate::parse(r#"{"c": 3, "a": {"z": 1, "y": 2}, "b": 2}"#).unwrap();
+
+        let sorted = JsonNormalizer::new().normalize_to_string(&value).unwrap();
+        assert_eq!(sorted, r#"{"a":{"y":2,"z":1},"b":2,"c":3}"#);
+
+        let mut unsorted = JsonNormalizer::with_options(NormalizerOptions {
+            sort_keys: false,
+            ..Default::default()
+        });
+        let normalized = unsorted.normalize(&value).unwrap();
+        let expected =
+            crate::format::to_compact_string(&normalized, &KeyOrder::from_value(&normalized));
+        assert_eq!(unsorted.normalize_to_string(&value).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_float_format() {
+        let value = crate::parse(r#"{"a": 0.1, "b": 2.345, "c": 7}"#).unwrap();
+
+        let shortest = JsonNormalizer::new().normalize_to_string(&value).unwrap();
+        assert_eq!(shortest, r#"{"a":0.1,"b":2.345,"c":7}"#);
+
+        let mut fixed = JsonNormalizer::with_options(NormalizerOptions {
+            float_format: FloatFormat::Fixed(2),
+            ..Default::default()
+        });
+        assert_eq!(
+            fixed.normalize_to_string(&value).unwrap(),
+            r#"{"a":0.10,"b":2.35,"c":7}"#
+        );
+        // Only the text is rounded
+        assert_eq!(fixed.normalize(&value).unwrap(), value);
+    }
+
+    #[test]
+    fn test_key_quoting() {
+        let value = crate::parse(r#"{"validKey": 1, "123abc": {"a-b": [], "$id": null}}"#).unwrap();
+        let mut minimal = JsonNormalizer::with_options(NormalizerOptions {
+            key_quoting: KeyQuoting::MinimalJson5,
+            ..Default::default()
+        });
+        assert_eq!(
+            minimal.normalize_to_string(&value).unwrap(),
+            r#"{"123abc":{$id:null,"a-b":[]},validKey:1}"#
+        );
+        assert_eq!(
+            JsonNormalizer::new().normalize_to_string(&value).unwrap(),
+            r#"{"123abc":{"$id":null,"a-b":[]},"validKey":1}"#
+        );
+    }
+
+    #[test]
+    fn test_coerce_numeric_strings() {
+        let value = crate::parse(
+            r#"["42", "-7", "2.5", "-1.5e3", "99999999999999999999", "007", "1.", ".5", "1e", "0x10", "NaN", " 12 ", "abc"]"#,
+        )
+        .unwrap();
+        let options = NormalizerOptions {
+            coerce_numeric_strings: true,
+            ..Default::default()
+        };
+
+        let normalized = normalize_with_options(&value, options).unwrap();
+        let expected = crate::parse(
+            r#"[42, -7, 2.5, -1500, 1e20, "007", "1.", ".5", "1e", "0x10", "NaN", " 12 ", "abc"]"#,
+        )
+        .unwrap();
+        assert_eq!(normalized, expected);
+
+        let options = NormalizerOptions {
+            coerce_numeric_strings: true,
+            trim_strings: true,
+            ..Default::default()
+        };
+        let trimmed = normalize_with_options(&Value::String(" 12 ".to_string()), options);
+        assert_eq!(trimmed.unwrap(), Value::Number(Number::Integer(12)));
+    }
+
+    #[test]
+    fn test_sort_arrays_by_key_path() {
+        let value = crate::parse(
+            r#"{"rows": [
+                {"meta": {"id": 3}, "name": "c"},
+                {"name": "no meta"},
+                {"meta": {"id": 1}, "name": "a"},
+                {"meta": {}, "name": "no id"},
+                {"meta": {"id": 2}, "name": "b"}
+            ]}"#,
+        )
+        .unwrap();
+        let options = NormalizerOptions {
+            sort_arrays_by: Some("meta.id".to_string()),
+            ..Default::default()
+        };
+
+        let normalized = normalize_with_options(&value, options).unwrap();
+        let expected = crate::parse(
+            r#"{"rows": [
+                {"meta": {"id": 1}, "name": "a"},
+                {"meta": {"id": 2}, "name": "b"},
+                {"meta": {"id": 3}, "name": "c"},
+                {"name": "no meta"},
+                {"meta": {}, "name": "no id"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(normalized, expected);
+    }
+
+    #[test]
+    fn test_sort_arrays_by_leaves_other_arrays_alone() {
+        let value = crate::parse(
+            r#"{
+                "mixed": [{"id": 2}, "scalar", {"id": 1}],
+                "unkeyed": [{"name": "b"}, {"name": "a"}],
+                "scalars": [3, 1, 2],
+                "nested": [[{"id": 2}, {"id": 1}]]
+            }"#,
+        )
+        .unwrap();
+        let options = NormalizerOptions {
+            sort_arrays_by: Some("id".to_string()),
+            ..Default::default()
+        };
+
+        // Only the array of objects inside `nested` is sorted
+        let normalized = normalize_with_options(&value, options).unwrap();
+        let expected = crate::parse(
+            r#"{
+                "mixed": [{"id": 2}, "scalar", {"id": 1}],
+                "unkeyed": [{"name": "b"}, {"name": "a"}],
+                "scalars": [3, 1, 2],
+                "nested": [[{"id": 1}, {"id": 2}]]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(normalized, expected);
+    }
+
+    #[test]
+    fn test_pruning_happens_before_sorting() {
+        let value = crate::parse(
+            r#"[
+                {"id": null, "name": "null id"},
+                {"id": 2, "tags": []},
+                {"id": null},
+                {"id": 1, "extra": {"gone": null}}
+            ]"#,
+        )
+        .unwrap();
+        let options = NormalizerOptions {
+            remove_null_values: true,
+            remove_empty_containers: true,
+            sort_arrays_by: Some("id".to_string()),
+            ..Default::default()
+        };
+
+        // `{"id": null}` prunes to an empty object and is dropped, and the
+        // element whose id was pruned sorts after those that kept theirs.
+        let normalized = normalize_with_options(&value, options).unwrap();
+        let expected = crate::parse(r#"[{"id": 1}, {"id": 2}, {"name": "null id"}]"#).unwrap();
+        assert_eq!(normalized, expected);
+    }
+
+    #[test]
+    fn test_cleanup_then_canonicalize() {
+        let value = crate::parse(
+            r#"{"servers": [{"port": "8080", "host": " b "}, {"host": "a", "port": null}], "unused": {}}"#,
+        )
+        .unwrap();
+
+        let cleaned = normalize_with_options(&value, NormalizerOptions::cleanup()).unwrap();
+        let canonical = normalize_with_options(
+            &cleaned,
+            NormalizerOptions {
+                coerce_numeric_strings: true,
+                sort_arrays_by: Some("host".to_string()),
+                ..NormalizerOptions::canonical()
+            },
+        )
+        .unwrap();
+
+        let expected =
+            crate::parse(r#"{"servers": [{"host": "a"}, {"host": "b", "port": 8080}]}"#).unwrap();
+        assert_eq!(canonical, expected);
+        assert_eq!(cleaned, CleanupNormalizer::cleanup(&value).unwrap());
+    }
 }
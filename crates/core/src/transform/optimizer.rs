@@ -326,7 +326,7 @@ impl AstOptimizer {
                 if f.fract() == 0.0
                     && f.is_finite()
                     && *f >= i64::MIN as f64
-                    && *f <= i64::MAX as f64
+                    && *f < i64::MAX as f64
                 {
                     Value::Number(Number::Integer(*f as i64))
                 } else {
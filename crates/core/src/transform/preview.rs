@@ -0,0 +1,107 @@
+// this_file: crates/core/src/transform/preview.rs
+
+//! Rendering a bounded preview of a document, for logs and debuggers that
+//! should not print megabytes.
+
+use crate::ast::Value;
+use crate::format::{to_compact_string, KeyOrder};
+
+/// Writes `value` indented as [`to_pretty_string`](crate::format::to_pretty_string)
+/// does, keeping at most `max_elements` members of each array and object and
+/// `max_string_len` characters of each string.
+///
+/// A shortened string ends with `…` inside its quotes, and a shortened
+/// container ends with a `… (k more)` line counting what was left out.
+/// Object keys are written in sorted order and are never shortened. With
+/// markers, the result is no longer JSON; a document within both limits is
+/// written in full as valid JSON.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::parse;
+/// use vexy_json_core::transform::to_preview_string;
+///
+/// let value = parse(r#"{"id": "0123456789abcdef", "rows": [1, 2, 3, 4]}"#).unwrap();
+/// assert_eq!(
+///     to_preview_string(&value, 2, 8),
+///     "{\n  \"id\": \"01234567…\",\n  \"rows\": [\n    1,\n    2,\n    … (2 more)\n  ]\n}"
+/// );
+/// ```
+pub fn to_preview_string(value: &Value, max_elements: usize, max_string_len: usize) -> String {
+    let mut out = String::new();
+    write_preview(&mut out, value, max_elements, max_string_len, 0);
+    out
+}
+
+fn write_preview(
+    out: &mut String,
+    value: &Value,
+    max_elements: usize,
+    max_string_len: usize,
+    depth: usize,
+) {
+    let (open, close, len) = match value {
+        Value::Array(arr) if !arr.is_empty() => ('[', ']', arr.len()),
+        Value::Object(obj) if !obj.is_empty() => ('{', '}', obj.len()),
+        Value::String(s) => {
+            match s.char_indices().nth(max_string_len) {
+                Some((end, _)) => {
+                    let mut shortened = to_compact_string(
+                        &Value::String(s[..end].to_string()),
+                        &KeyOrder::default(),
+                    );
+                    shortened.insert(shortened.len() - 1, '…');
+                    out.push_str(&shortened);
+                }
+                None => out.push_str(&to_compact_string(value, &KeyOrder::default())),
+            }
+            return;
+        }
+        _ => {
+            out.push_str(&to_compact_string(value, &KeyOrder::default()));
+            return;
+        }
+    };
+
+    out.push(open);
+    let shown = len.min(max_elements);
+    match value {
+        Value::Array(arr) => {
+            for (i, item) in arr.iter().take(shown).enumerate() {
+                member_start(out, i, depth + 1);
+                write_preview(out, item, max_elements, max_string_len, depth + 1);
+            }
+        }
+        Value::Object(obj) => {
+            let mut entries: Vec<_> = obj.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            for (i, (key, item)) in entries.into_iter().take(shown).enumerate() {
+                member_start(out, i, depth + 1);
+                out.push_str(&to_compact_string(
+                    &Value::String(key.clone()),
+                    &KeyOrder::default(),
+                ));
+                out.push_str(": ");
+                write_preview(out, item, max_elements, max_string_len, depth + 1);
+            }
+        }
+        _ => unreachable!(),
+    }
+    if shown < len {
+        member_start(out, shown, depth + 1);
+        out.push_str(&format!("… ({} more)", len - shown));
+    }
+    out.push('\n');
+    out.push_str(&"  ".repeat(depth));
+    out.push(close);
+}
+
+/// Starts the line of the `index`th member of a container.
+fn member_start(out: &mut String, index: usize, depth: usize) {
+    if index > 0 {
+        out.push(',');
+    }
+    out.push('\n');
+    out.push_str(&"  ".repeat(depth));
+}
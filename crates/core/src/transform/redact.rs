@@ -0,0 +1,233 @@
+// this_file: crates/core/src/transform/redact.rs
+
+//! Replacing values at given paths, such as passwords and tokens, so a
+//! document can be logged without leaking them.
+
+use crate::ast::visitor::{self, PathSelector};
+use crate::ast::Value;
+use crate::error::Result;
+
+/// Replaces every value at one of `paths` with `replacement`, leaving the
+/// rest of the document as it was, and returns how many values were
+/// replaced.
+///
+/// A path is either JSONPath or a JSON Pointer:
+///
+/// - JSONPath starts with `$` and is followed by `.key`, `['key']`, `[0]`,
+///   or `.*` and `[*]` for every member or element, as
+///   [`JsonPath`](crate::ast::JsonPath) parses it. `..key` selects `key` at
+///   any depth below, so `$..token` finds every `token` in the document.
+/// - A JSON Pointer such as `/user/password` starts with `/`, writes `~` and
+///   `/` in keys as `~0` and `~1`, and addresses array elements by index.
+///
+/// Paths that select nothing are not an error, since a payload need not
+/// hold every field that would be redacted. A value under one that is
+/// already redacted is not counted again.
+///
+/// # Errors
+///
+/// Returns an error if a path is malformed; `value` is then left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::ast::Value;
+/// use vexy_json_core::parse;
+/// use vexy_json_core::transform::redact;
+///
+/// let mut value = parse(r#"{"user": {"name": "ann", "password": "hunter2"}}"#).unwrap();
+/// let hidden = Value::String("[REDACTED]".into());
+/// assert_eq!(redact(&mut value, &["$.user.password"], hidden).unwrap(), 1);
+/// assert_eq!(value["user"]["password"].as_str(), Some("[REDACTED]"));
+/// assert_eq!(value["user"]["name"].as_str(), Some("ann"));
+/// ```
+pub fn redact(value: &mut Value, paths: &[&str], replacement: Value) -> Result<usize> {
+    let paths = paths
+        .iter()
+        .map(|path| parse_path(path))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(paths
+        .iter()
+        .map(|steps| redact_at(value, steps, &replacement))
+        .sum())
+}
+
+/// One step of a parsed path.
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    selector: Selector,
+    /// Whether the selector applies at any depth below, as after `..`
+    descendants: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    /// An object member
+    Key(String),
+    /// An array element
+    Index(usize),
+    /// A JSON Pointer token: an object member, or an array element when the
+    /// token is an index
+    Token(String),
+    /// Every member or element
+    Any,
+}
+
+impl Selector {
+    fn matches_key(&self, key: &str) -> bool {
+        match self {
+            Selector::Key(name) | Selector::Token(name) => name == key,
+            Selector::Index(_) => false,
+            Selector::Any => true,
+        }
+    }
+
+    fn matches_index(&self, index: usize) -> bool {
+        match self {
+            Selector::Index(i) => *i == index,
+            // RFC 6901 indexes have no leading zeros
+            Selector::Token(token) => {
+                token.parse() == Ok(index) && (token == "0" || !token.starts_with('0'))
+            }
+            Selector::Key(_) => false,
+            Selector::Any => true,
+        }
+    }
+}
+
+fn redact_at(value: &mut Value, steps: &[Step], replacement: &Value) -> usize {
+    let Some((step, rest)) = steps.split_first() else {
+        *value = replacement.clone();
+        return 1;
+    };
+
+    let visit = |matched: bool, child: &mut Value| {
+        let mut count = 0;
+        // Searched before the match is redacted, so that a replacement is
+        // never searched and a redacted subtree never counted twice
+        if step.descendants && !(matched && rest.is_empty()) {
+            count += redact_at(child, steps, replacement);
+        }
+        if matched {
+            count += redact_at(child, rest, replacement);
+        }
+        count
+    };
+    match value {
+        Value::Object(obj) => obj
+            .iter_mut()
+            .map(|(key, child)| visit(step.selector.matches_key(key), child))
+            .sum(),
+        Value::Array(items) => items
+            .iter_mut()
+            .enumerate()
+            .map(|(index, child)| visit(step.selector.matches_index(index), child))
+            .sum(),
+        _ => 0,
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<Step>> {
+    if path.is_empty() || path.starts_with('/') {
+        return Ok(parse_pointer(path));
+    }
+
+    let mut steps = Vec::new();
+    let mut descendants = false;
+    for selector in visitor::parse_path(path)? {
+        let selector = match selector {
+            PathSelector::Descendants => {
+                descendants = true;
+                continue;
+            }
+            PathSelector::Key(key) => Selector::Key(key),
+            PathSelector::Index(index) => Selector::Index(index),
+            PathSelector::AnyKey | PathSelector::AnyIndex => Selector::Any,
+        };
+        steps.push(Step {
+            selector,
+            descendants,
+        });
+        descendants = false;
+    }
+    Ok(steps)
+}
+
+fn parse_pointer(pointer: &str) -> Vec<Step> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|token| Step {
+            selector: Selector::Token(token.replace("~1", "/").replace("~0", "~")),
+            descendants: false,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child(selector: Selector) -> Step {
+        Step {
+            selector,
+            descendants: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("$.a[0]['b.c'][*]").unwrap(),
+            [
+                child(Selector::Key("a".into())),
+                child(Selector::Index(0)),
+                child(Selector::Key("b.c".into())),
+                child(Selector::Any),
+            ]
+        );
+        assert_eq!(
+            parse_path("$..token..[1]").unwrap(),
+            [
+                Step {
+                    selector: Selector::Key("token".into()),
+                    descendants: true,
+                },
+                Step {
+                    selector: Selector::Index(1),
+                    descendants: true,
+                },
+            ]
+        );
+        assert_eq!(
+            parse_path("/a~1b/~01").unwrap(),
+            [
+                child(Selector::Token("a/b".into())),
+                child(Selector::Token("~1".into())),
+            ]
+        );
+        assert!(parse_path("$").unwrap().is_empty());
+        assert!(parse_path("").unwrap().is_empty());
+        assert_eq!(
+            parse_path(r"$['a]b']..['c\'d']").unwrap(),
+            [
+                child(Selector::Key("a]b".into())),
+                Step {
+                    selector: Selector::Key("c'd".into()),
+                    descendants: true,
+                },
+            ]
+        );
+        for invalid in [
+            "user.password",
+            "$.",
+            "$..",
+            "$[x]",
+            "$[0",
+            "$.a...",
+            "$['a]",
+        ] {
+            assert!(parse_path(invalid).is_err(), "{invalid}");
+        }
+    }
+}
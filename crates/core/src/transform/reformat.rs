@@ -0,0 +1,68 @@
+// this_file: crates/core/src/transform/reformat.rs
+
+//! Turning forgiving input into standard JSON, minified or indented, in one
+//! call.
+
+use crate::ast::Value;
+use crate::error::Result;
+use crate::format::{minify, prettify, to_compact_string, to_pretty_string, KeyOrder};
+use crate::parser::ParserOptions;
+
+/// How [`reformat`] lays out its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatStyle {
+    /// No insignificant whitespace
+    Minify,
+    /// One member per line, indented by `indent` spaces per level
+    Beautify {
+        /// Spaces per nesting level
+        indent: usize,
+    },
+}
+
+impl FormatStyle {
+    /// Writes `value` in this style, with keys in the order `order` gives.
+    pub fn format(self, value: &Value, order: &KeyOrder) -> String {
+        match self {
+            FormatStyle::Minify => to_compact_string(value, order),
+            FormatStyle::Beautify { indent } => to_pretty_string(value, indent, order),
+        }
+    }
+}
+
+/// Parses `input` forgivingly and writes it back as standard JSON in
+/// `style`.
+///
+/// Key order follows the source; comments and trailing commas are dropped.
+///
+/// # Errors
+///
+/// Returns the first parse error if `input` cannot be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use vexy_json_core::transform::{reformat, FormatStyle};
+///
+/// let input = "// retries\n{b: 1, a: [true,],}";
+/// assert_eq!(reformat(input, FormatStyle::Minify).unwrap(), r#"{"b":1,"a":[true]}"#);
+/// assert_eq!(
+///     reformat(input, FormatStyle::Beautify { indent: 2 }).unwrap(),
+///     "{\n  \"b\": 1,\n  \"a\": [\n    true\n  ]\n}"
+/// );
+/// ```
+pub fn reformat(input: &str, style: FormatStyle) -> Result<String> {
+    reformat_with_options(input, style, ParserOptions::default())
+}
+
+/// Like [`reformat`], parsing with `options`.
+pub fn reformat_with_options(
+    input: &str,
+    style: FormatStyle,
+    options: ParserOptions,
+) -> Result<String> {
+    match style {
+        FormatStyle::Minify => minify(input, options),
+        FormatStyle::Beautify { indent } => prettify(input, indent, options),
+    }
+}
@@ -0,0 +1,210 @@
+// this_file: crates/core/src/transform/sort.rs
+
+//! Array sorting for canonicalization.
+//!
+//! When array order is insignificant, sorting arrays before comparing two
+//! documents lets semantic diffs ignore reordering.
+
+use crate::ast::{Number, Value};
+use crate::error::{Error, Result};
+use std::cmp::Ordering;
+
+/// Recursively sorts every array in `value` into a canonical order.
+///
+/// Values of different types are ordered null, booleans, numbers, strings,
+/// arrays, objects. Numbers compare numerically and strings lexically; arrays
+/// and objects compare by their serialized form with object keys sorted.
+/// Nested arrays are sorted before their parents, so equal contents always
+/// produce equal serialized forms.
+pub fn sort_arrays(value: &mut Value) {
+    match value {
+        Value::Array(arr) => {
+            for item in arr.iter_mut() {
+                sort_arrays(item);
+            }
+            arr.sort_by(compare_values);
+        }
+        Value::Object(obj) => {
+            for item in obj.values_mut() {
+                sort_arrays(item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Sorts the array of objects at `path` by the value each holds under `key`.
+///
+/// `path` uses the `$.items[0].tags` form produced by
+/// [`JsonPath`](crate::ast::JsonPath). Key values compare as in
+/// [`sort_arrays`]; elements without the key, or that are not objects, keep
+/// their relative order after all others.
+///
+/// # Errors
+///
+/// Returns an error if `path` is malformed or does not resolve to an array.
+pub fn sort_array_by(value: &mut Value, path: &str, key: &str) -> Result<()> {
+    let target = resolve_path_mut(value, path)?;
+    let Value::Array(arr) = target else {
+        return Err(Error::Custom(format!(
+            "Value at path {path} is not an array"
+        )));
+    };
+
+    fn field<'v>(item: &'v Value, key: &str) -> Option<&'v Value> {
+        item.as_object().and_then(|obj| obj.get(key))
+    }
+    arr.sort_by(|a, b| match (field(a, key), field(b, key)) {
+        (Some(a), Some(b)) => compare_values(a, b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    Ok(())
+}
+
+/// Resolves a `$.key[0]` style path to a mutable reference.
+fn resolve_path_mut<'a>(value: &'a mut Value, path: &str) -> Result<&'a mut Value> {
+    let invalid = || Error::Custom(format!("Invalid path: {path}"));
+    let not_found = || Error::Custom(format!("No value at path {path}"));
+
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut current = value;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let index: usize = after[..end].parse().map_err(|_| invalid())?;
+            current = match current {
+                Value::Array(arr) => arr.get_mut(index).ok_or_else(not_found)?,
+                _ => return Err(not_found()),
+            };
+            rest = &after[end + 1..];
+        } else if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            current = match current {
+                Value::Object(obj) => obj.get_mut(&after[..end]).ok_or_else(not_found)?,
+                _ => return Err(not_found()),
+            };
+            rest = &after[end..];
+        } else {
+            return Err(invalid());
+        }
+    }
+
+    Ok(current)
+}
+
+/// Orders values for canonical sorting.
+pub(crate) fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => compare_numbers(a, b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(_), Value::Array(_)) | (Value::Object(_), Value::Object(_)) => {
+            canonical_string(a).cmp(&canonical_string(b))
+        }
+        _ => type_rank(a).cmp(&type_rank(b)),
+    }
+}
+
+/// Compares numbers numerically, with a total order over floats.
+fn compare_numbers(a: &Number, b: &Number) -> Ordering {
+    match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) => a.cmp(b),
+        (Number::Float(a), Number::Float(b)) => a.total_cmp(b),
+        (Number::Integer(a), Number::Float(b)) => (*a as f64).total_cmp(b),
+        (Number::Float(a), Number::Integer(b)) => a.total_cmp(&(*b as f64)),
+    }
+}
+
+fn type_rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+/// Serializes a value with object keys sorted, independent of map order.
+fn canonical_string(value: &Value) -> String {
+    match value {
+        Value::Array(arr) => {
+            let items: Vec<String> = arr.iter().map(canonical_string).collect();
+            format!("[{}]", items.join(","))
+        }
+        Value::Object(obj) => {
+            let mut entries: Vec<_> = obj.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let items: Vec<String> = entries
+                .into_iter()
+                .map(|(k, v)| format!("{k:?}:{}", canonical_string(v)))
+                .collect();
+            format!("{{{}}}", items.join(","))
+        }
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn test_sort_scalar_array() {
+        let mut value = parse("[3, 1, 2]").unwrap();
+        sort_arrays(&mut value);
+        assert_eq!(value, parse("[1, 2, 3]").unwrap());
+
+        let mut value = parse(r#"[10, "b", 2.5, null, "a", true, 2]"#).unwrap();
+        sort_arrays(&mut value);
+        assert_eq!(
+            value,
+            parse(r#"[null, true, 2, 2.5, 10, "a", "b"]"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sort_arrays_makes_documents_comparable() {
+        let mut a = parse(r#"{"tags": ["x", "y"], "items": [[2, 1], {"k": [4, 3]}]}"#).unwrap();
+        let mut b = parse(r#"{"items": [{"k": [3, 4]}, [1, 2]], "tags": ["y", "x"]}"#).unwrap();
+        assert_ne!(a, b);
+
+        sort_arrays(&mut a);
+        sort_arrays(&mut b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_sort_array_by_key() {
+        let mut value = parse(
+            r#"{"users": [{"id": 3, "name": "c"}, {"name": "none"}, {"id": 1, "name": "a"}, {"id": 2, "name": "b"}]}"#,
+        )
+        .unwrap();
+        sort_array_by(&mut value, "$.users", "id").unwrap();
+
+        let expected = parse(
+            r#"{"users": [{"id": 1, "name": "a"}, {"id": 2, "name": "b"}, {"id": 3, "name": "c"}, {"name": "none"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_sort_array_by_nested_path() {
+        let mut value = parse(r#"[{"rows": [{"id": "b"}, {"id": "a"}]}]"#).unwrap();
+        sort_array_by(&mut value, "$[0].rows", "id").unwrap();
+        assert_eq!(
+            value,
+            parse(r#"[{"rows": [{"id": "a"}, {"id": "b"}]}]"#).unwrap()
+        );
+
+        assert!(sort_array_by(&mut value, "$[0]", "id").is_err());
+        assert!(sort_array_by(&mut value, "$.missing", "id").is_err());
+        assert!(sort_array_by(&mut value, "rows", "id").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+// this_file: crates/core/tests/comment_styles_test.rs
+
+//! `//`, `/* */` and `#` comments can each be turned off, in the parser, the
+//! streaming parser and the comment-handling formatters alike.
+
+use vexy_json_core::ast::Value;
+use vexy_json_core::format::{
+    strip_comments, to_pretty_string_with_comments, KeyOrder, SourceComments, StripCommentsOptions,
+};
+use vexy_json_core::streaming::validate_stream;
+use vexy_json_core::{
+    parse_with_options, CommentStyles, Error, ParserOptions, StreamingEvent, StreamingParser,
+    StreamingValueBuilder,
+};
+
+const ALL: CommentStyles = CommentStyles {
+    line_slash: true,
+    block: true,
+    hash: true,
+};
+
+const NO_HASH: CommentStyles = CommentStyles {
+    line_slash: true,
+    block: true,
+    hash: false,
+};
+
+const ONLY_HASH: CommentStyles = CommentStyles {
+    line_slash: false,
+    block: false,
+    hash: true,
+};
+
+fn options(styles: CommentStyles) -> ParserOptions {
+    ParserOptions {
+        comment_styles: styles,
+        ..Default::default()
+    }
+}
+
+/// Reads `input` through [`StreamingParser`], which does not take line breaks
+/// as commas.
+fn parse_streaming(input: &str, styles: CommentStyles) -> Result<Value, Error> {
+    let mut parser = StreamingParser::with_options(ParserOptions {
+        newline_as_comma: false,
+        ..options(styles)
+    });
+    parser.feed(input)?;
+    parser.finish()?;
+    let mut builder = StreamingValueBuilder::new();
+    while let Some(event) = parser.next_event()? {
+        if event == StreamingEvent::EndOfInput {
+            break;
+        }
+        builder.process_event(event)?;
+    }
+    Ok(builder.finish()?.unwrap_or(Value::Null))
+}
+
+fn strip(input: &str, styles: CommentStyles) -> Result<String, Error> {
+    let options = StripCommentsOptions {
+        comment_styles: styles,
+        ..Default::default()
+    };
+    strip_comments(input, &options)
+}
+
+fn string(value: &Value) -> &str {
+    match value {
+        Value::String(s) => s,
+        other => panic!("expected a string, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_all_styles_by_default() {
+    assert_eq!(ParserOptions::default().comment_styles, ALL);
+    assert_eq!(StripCommentsOptions::default().comment_styles, ALL);
+
+    let input = "# settings\n{\n  a: 1, // one\n  b: /* two */ 2, # two\n}";
+    let value = parse_with_options(input, ParserOptions::default()).unwrap();
+    assert_eq!(value["a"].as_i64(), Some(1));
+    assert_eq!(value["b"].as_i64(), Some(2));
+    assert_eq!(strip(input, ALL).unwrap(), "{\n  a: 1,\n  b:  2\n}");
+}
+
+#[test]
+fn test_each_style_alone() {
+    let cases = [
+        ("[1, // c\n 2]", "line_slash"),
+        ("[1, /* c */ 2]", "block"),
+        ("[1, # c\n 2]", "hash"),
+    ];
+    for (only, name) in [
+        (
+            CommentStyles {
+                line_slash: true,
+                block: false,
+                hash: false,
+            },
+            "line_slash",
+        ),
+        (
+            CommentStyles {
+                line_slash: false,
+                block: true,
+                hash: false,
+            },
+            "block",
+        ),
+        (ONLY_HASH, "hash"),
+    ] {
+        for (input, style) in cases {
+            let enabled = style == name;
+            let parsed = parse_with_options(input, options(only));
+            let streamed = parse_streaming(input, only);
+            let validated = validate_stream(input.as_bytes(), options(only));
+            let stripped = strip(input, only);
+            if enabled {
+                let expected = parse_with_options("[1, 2]", ParserOptions::default());
+                assert_eq!(parsed, expected, "{input}");
+                assert_eq!(streamed, expected, "{input}");
+                assert!(validated.is_ok(), "{input}");
+                assert!(!stripped.unwrap().contains(" c"), "{input}");
+            } else if style == "hash" {
+                // Not a comment, so `#` and `c` are two more words
+                let items = parsed.unwrap();
+                assert_eq!(string(&items[1]), "#", "{name}");
+                assert_eq!(string(&items[2]), "c", "{name}");
+                assert_eq!(stripped.unwrap(), input, "{name}");
+            } else {
+                let position = input.find('/').unwrap();
+                assert_eq!(parsed, Err(Error::UnexpectedChar('/', position)), "{input}");
+                assert_eq!(
+                    streamed,
+                    Err(Error::UnexpectedChar('/', position)),
+                    "{input}"
+                );
+                assert_eq!(
+                    validated,
+                    Err(Error::UnexpectedChar('/', position)),
+                    "{input}"
+                );
+                assert_eq!(
+                    stripped,
+                    Err(Error::UnexpectedChar('/', position)),
+                    "{input}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_hash_in_strings_is_never_a_comment() {
+    let input = "{\"a\": \"x # y\", 'b': 'p#q', \"#c\": 1}";
+    for styles in [ALL, NO_HASH, ONLY_HASH] {
+        let value = parse_with_options(input, options(styles)).unwrap();
+        assert_eq!(string(&value["a"]), "x # y");
+        assert_eq!(string(&value["b"]), "p#q");
+        assert_eq!(value["#c"].as_i64(), Some(1));
+
+        assert_eq!(parse_streaming(input, styles).unwrap(), value);
+        assert_eq!(strip(input, styles).unwrap(), input);
+    }
+}
+
+#[test]
+fn test_hash_in_unquoted_values_when_disabled() {
+    let input = "{color: #fff, tag: a#b, #key: 1}";
+    let value = parse_with_options(input, options(NO_HASH)).unwrap();
+    assert_eq!(string(&value["color"]), "#fff");
+    assert_eq!(string(&value["tag"]), "a#b");
+    assert_eq!(value["#key"].as_i64(), Some(1));
+    assert!(validate_stream(input.as_bytes(), options(NO_HASH)).is_ok());
+    assert_eq!(strip(input, NO_HASH).unwrap(), input);
+
+    // The streaming parser reads unquoted keys only
+    let keys = parse_streaming("{#key: 1, a#b: 2}", NO_HASH).unwrap();
+    assert_eq!(keys["#key"].as_i64(), Some(1));
+    assert_eq!(keys["a#b"].as_i64(), Some(2));
+
+    // With hash comments on, the rest of the line is a comment
+    assert!(parse_with_options(input, ParserOptions::default()).is_err());
+    assert_eq!(strip(input, ALL).unwrap(), "{color:");
+}
+
+#[test]
+fn test_trailing_hash_comments_in_implicit_documents() {
+    let input = "# deploy settings\nhost: db-1 # primary\nport: 8080  # default\n";
+    let value = parse_with_options(input, options(ONLY_HASH)).unwrap();
+    assert_eq!(string(&value["host"]), "db-1");
+    assert_eq!(value["port"].as_i64(), Some(8080));
+    assert_eq!(strip(input, ONLY_HASH).unwrap(), "host: db-1\nport: 8080\n");
+}
+
+#[test]
+fn test_preserved_comments_follow_styles() {
+    let input = "{color: #fff, // hex\n n: 1 /* one */\n}";
+    let value = parse_with_options(input, options(NO_HASH)).unwrap();
+    let order = KeyOrder::from_source(input);
+
+    let comments = SourceComments::from_source_with_comment_styles(input, NO_HASH);
+    assert_eq!(
+        to_pretty_string_with_comments(&value, 2, &order, &comments),
+        "{\n  \"color\": \"#fff\", // hex\n  \"n\": 1 /* one */\n}"
+    );
+
+    // With hash comments on, `#fff, // hex` is a single comment
+    let comments = SourceComments::from_source(input);
+    let pretty = to_pretty_string_with_comments(&value, 2, &order, &comments);
+    assert!(pretty.contains("#fff, // hex"), "{pretty}");
+}
@@ -0,0 +1,106 @@
+// this_file: crates/core/tests/determinism_test.rs
+
+//! Serializing and walking a document gives byte-identical results every
+//! time, within a process and from one process to the next, whichever
+//! backend parsed it, even where keys are not sorted.
+
+use std::process::Command;
+use vexy_json_core::ast::{walk_with_path, JsonPath, PathVisitor, Value};
+use vexy_json_core::error::Result;
+use vexy_json_core::format::{to_compact_string, to_pretty_string, KeyOrder};
+use vexy_json_core::transform::{JsonNormalizer, NormalizerOptions};
+use vexy_json_core::{parse_with_options, ParserBackend, ParserOptions};
+
+const FIXTURE: &str = include_str!("fixtures/determinism.json");
+
+/// Set in the environment of the copies of this binary the tests start.
+const CHILD: &str = "VEXY_JSON_DETERMINISM_CHILD";
+const BEGIN: &str = "<<<determinism";
+const END: &str = "determinism>>>";
+
+/// Collects the path of every scalar, in the order they are visited.
+#[derive(Default)]
+struct Paths(Vec<String>);
+
+impl PathVisitor for Paths {
+    fn visit_value_with_path(&mut self, value: &Value, path: &JsonPath) -> Result<()> {
+        match value {
+            Value::Array(arr) => self.visit_array_with_path(arr, path),
+            Value::Object(obj) => self.visit_object_with_path(obj, path),
+            _ => {
+                self.0.push(path.to_string());
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Everything written from the fixture as parsed by `backend`.
+fn outputs(backend: ParserBackend) -> String {
+    let options = ParserOptions {
+        backend,
+        ..Default::default()
+    };
+    let value = parse_with_options(FIXTURE, options).unwrap();
+
+    let unsorted = JsonNormalizer::with_options(NormalizerOptions {
+        sort_keys: false,
+        ..Default::default()
+    })
+    .normalize_to_string(&value)
+    .unwrap();
+    let mut paths = Paths::default();
+    walk_with_path(&value, &mut paths).unwrap();
+
+    [
+        unsorted,
+        to_compact_string(&value, &KeyOrder::default()),
+        to_pretty_string(&value, 2, &KeyOrder::from_source(FIXTURE)),
+        value.to_string(),
+        paths.0.join(" "),
+    ]
+    .join("\n")
+}
+
+#[test]
+fn test_output_is_stable_in_process() {
+    let expected = outputs(ParserBackend::Auto);
+    for _ in 0..100 {
+        for backend in [
+            ParserBackend::Auto,
+            ParserBackend::Recursive,
+            ParserBackend::Iterative,
+            ParserBackend::OptimizedV3,
+        ] {
+            assert_eq!(outputs(backend), expected, "{backend:?}");
+        }
+    }
+}
+
+#[test]
+fn test_output_is_stable_across_processes() {
+    let expected = outputs(ParserBackend::Auto);
+    if std::env::var_os(CHILD).is_some() {
+        println!("{BEGIN}\n{expected}\n{END}");
+        return;
+    }
+
+    for _ in 0..2 {
+        let child = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "test_output_is_stable_across_processes",
+                "--exact",
+                "--nocapture",
+            ])
+            .env(CHILD, "1")
+            .output()
+            .unwrap();
+        assert!(child.status.success());
+        let stdout = String::from_utf8(child.stdout).unwrap();
+        let printed = stdout
+            .split_once(&format!("{BEGIN}\n"))
+            .and_then(|(_, rest)| rest.split_once(&format!("\n{END}")))
+            .map(|(printed, _)| printed);
+        assert_eq!(printed, Some(expected.as_str()));
+    }
+}
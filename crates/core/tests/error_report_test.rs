@@ -0,0 +1,168 @@
+// this_file: crates/core/tests/error_report_test.rs
+
+//! Plain-text error reports point at the right character, wherever the error
+//! is and whatever its span.
+
+use vexy_json_core::error::{display_width, plain_error_report, ErrorReporter, ReportConfig, Span};
+use vexy_json_core::{
+    parse_with_options, EmptyInputBehavior, Error, NumberOverflowPolicy, ParserOptions,
+};
+
+/// Width of the `   1 | ` gutter before each line of context
+const GUTTER: usize = 7;
+
+fn parse_error(input: &str) -> Error {
+    let options = ParserOptions {
+        empty_input_behavior: EmptyInputBehavior::Error,
+        ..Default::default()
+    };
+    parse_with_options(input, options).unwrap_err()
+}
+
+/// The context line the caret is under, and the text from the caret on,
+/// counting wide characters as two columns.
+fn caret(report: &str) -> (&str, String) {
+    let lines: Vec<&str> = report.lines().collect();
+    let at = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with("| ") && line.contains('^'))
+        .unwrap_or_else(|| panic!("no caret in:\n{report}"));
+    let column = lines[at][GUTTER..]
+        .chars()
+        .take_while(|&c| c == ' ')
+        .count();
+    let line = &lines[at - 1][GUTTER..];
+    let skipped = line
+        .char_indices()
+        .map(|(index, _)| index)
+        .chain([line.len()])
+        .find(|&index| display_width(&line[..index]) >= column)
+        .unwrap();
+    (line, line[skipped..].to_string())
+}
+
+#[test]
+fn test_error_at_position_zero() {
+    let input = "@[1]";
+    let report = plain_error_report(&parse_error(input), input);
+    assert!(report.starts_with("Error: Unexpected character '@' at position 0"));
+    assert_eq!(caret(&report), ("@[1]", "@[1]".to_string()));
+    assert!(report.contains("Suggestions:"));
+}
+
+#[test]
+fn test_error_at_end_of_input() {
+    let report = plain_error_report(&parse_error(""), "");
+    assert!(report.starts_with("Error:"), "{report}");
+
+    let input = "[1, 2";
+    let report = plain_error_report(&parse_error(input), input);
+    assert_eq!(caret(&report), ("[1, 2", String::new()));
+
+    // After a final line break, the caret is on the empty line that follows
+    let input = "[1,\n";
+    let report = plain_error_report(&parse_error(input), input);
+    assert!(report.contains("   1 | [1,\n   2 | \n"), "{report}");
+    assert_eq!(caret(&report), ("", String::new()));
+}
+
+#[test]
+fn test_error_after_multibyte_characters() {
+    let input = "{\"é\": \"日本\", \"k\": @}";
+    let report = plain_error_report(&parse_error(input), input);
+    assert_eq!(caret(&report).1, "@}");
+
+    let input = "{\"a\": 1,\n \"ü\": [\"😀\", @]}";
+    let report = plain_error_report(&parse_error(input), input);
+    assert!(report.contains("   2 | "), "{report}");
+    assert_eq!(caret(&report).1, "@]}");
+}
+
+#[test]
+fn test_span_inside_multibyte_character() {
+    let input = "[\"é\", 日本]";
+    let inside = input.find('日').unwrap() + 1;
+    let error = Error::Spanned {
+        source: Box::new(Error::UnexpectedChar('本', inside)),
+        span: Span::new(inside, inside + 4),
+    };
+    let report = plain_error_report(&error, input);
+    // Widened to the whole characters the span touches
+    assert_eq!(caret(&report).1, "日本]");
+    assert!(report.contains("^^"), "{report}");
+}
+
+#[test]
+fn test_odd_spans_do_not_panic() {
+    let inputs = ["", "é", "[1,\n", "a\r\nb\r\n", "🎉🎉", "{\"é\": @}\n\n"];
+    for input in inputs {
+        for position in 0..input.len() + 3 {
+            for span in [
+                Span::single(position),
+                Span::new(position, position + 3),
+                Span::new(position + 2, position),
+                Span::new(position, usize::MAX),
+            ] {
+                let error = Error::Spanned {
+                    source: Box::new(Error::UnexpectedChar('x', position)),
+                    span,
+                };
+                let report = plain_error_report(&error, input);
+                assert!(report.starts_with("Error:"), "{input:?} {span:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_long_line_is_cut_down() {
+    let half = "1,".repeat(2_500_000);
+    let input = format!("[{half}@{}]", half.replace("1,", ",1"));
+    let report = plain_error_report(&parse_error(&input), &input);
+    assert!(report.len() < 4096, "{} bytes", report.len());
+    let (line, pointed) = caret(&report);
+    assert!(
+        line.starts_with("…1,1,") && line.ends_with(",1…"),
+        "{report}"
+    );
+    assert_eq!(line.chars().count(), 2 * 120 + 3);
+    assert!(pointed.starts_with("@,1,1"), "{report}");
+
+    let config = ReportConfig::minimal()
+        .with_colors(false)
+        .with_context_width(4);
+    let config = ReportConfig {
+        include_context: true,
+        ..config
+    };
+    let report = ErrorReporter::new(config).generate_report(&parse_error(&input), &input);
+    assert!(
+        report.contains("   1 | …1,1,@,1,1…\n     |      ^\n"),
+        "{report}"
+    );
+}
+
+#[test]
+fn test_caret_after_tabs_and_wide_characters() {
+    let input = "{\t\"日本\": @}";
+    let report = plain_error_report(&parse_error(input), input);
+    assert!(report.contains("   1 | {    \"日本\": @}\n"), "{report}");
+    assert_eq!(caret(&report).1, "@}");
+}
+
+#[test]
+fn test_huge_token_is_cut_short() {
+    let input = format!("[1{}]", "0".repeat(10_000_000));
+    let options = ParserOptions {
+        number_overflow: NumberOverflowPolicy::Error,
+        ..Default::default()
+    };
+    let error = parse_with_options(&input, options).unwrap_err();
+    let report = plain_error_report(&error, &input);
+    assert!(report.len() < 4096, "{} bytes", report.len());
+    let header = report.lines().next().unwrap();
+    assert!(
+        header.contains(&format!("found 1{}… at", "0".repeat(79))),
+        "{header}"
+    );
+}
@@ -0,0 +1,89 @@
+// this_file: crates/core/tests/homogeneous_test.rs
+
+//! Finding arrays that mix value types, as columnar formats need to know
+//! before ingesting them.
+
+use vexy_json_core::ast::JsonPath;
+use vexy_json_core::parse;
+use vexy_json_core::transform::{
+    check_homogeneous, check_homogeneous_with_options, HomogeneousOptions, TypeMismatch, ValueType,
+};
+
+fn paths(mismatches: &[TypeMismatch]) -> Vec<String> {
+    mismatches
+        .iter()
+        .map(|mismatch| mismatch.element_path().to_string())
+        .collect()
+}
+
+#[test]
+fn test_objects_with_different_keys_pass() {
+    let value =
+        parse(r#"{"rows": [{"id": 1, "name": "a"}, {"id": 2}, {"other": [true]}, {}]}"#).unwrap();
+    assert!(check_homogeneous(&value).is_empty());
+}
+
+#[test]
+fn test_reports_path_types_and_index() {
+    let value = parse(r#"{"data": {"values": [true, false, "yes", null, true]}}"#).unwrap();
+    let mismatches = check_homogeneous(&value);
+    assert_eq!(
+        mismatches,
+        vec![
+            TypeMismatch {
+                path: "$.data.values".parse::<JsonPath>().unwrap(),
+                index: 2,
+                expected: ValueType::Boolean,
+                actual: ValueType::String,
+            },
+            TypeMismatch {
+                path: "$.data.values".parse::<JsonPath>().unwrap(),
+                index: 3,
+                expected: ValueType::Boolean,
+                actual: ValueType::Null,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_nested_arrays() {
+    // The outer array holds arrays only; each inner array is checked on its own
+    let value = parse(r#"[[1, 2], ["a", "b"], [3, "c"], [], [[1], [2, [3]]]]"#).unwrap();
+    assert_eq!(paths(&check_homogeneous(&value)), ["$[2][1]", "$[4][1][1]"]);
+
+    let value = parse(r#"[[1], 2, [[true, 1]]]"#).unwrap();
+    assert_eq!(paths(&check_homogeneous(&value)), ["$[1]", "$[2][0][1]"]);
+
+    // Arrays inside objects inside arrays
+    let value = parse(r#"[{"tags": ["a", 1]}, {"tags": ["b"]}, {"tags": [{}, []]}]"#).unwrap();
+    assert_eq!(
+        paths(&check_homogeneous(&value)),
+        ["$[0].tags[1]", "$[2].tags[1]"]
+    );
+}
+
+#[test]
+fn test_integer_and_float() {
+    let value = parse(r#"{"b": [1.5, 2], "a": [1, 2.5, "x"]}"#).unwrap();
+    // Keys are visited in sorted order
+    assert_eq!(paths(&check_homogeneous(&value)), ["$.a[2]"]);
+
+    let strict = HomogeneousOptions {
+        numbers_compatible: false,
+    };
+    let mismatches = check_homogeneous_with_options(&value, &strict);
+    assert_eq!(paths(&mismatches), ["$.a[1]", "$.a[2]", "$.b[1]"]);
+    assert_eq!(mismatches[2].expected, ValueType::Float);
+    assert_eq!(mismatches[2].actual, ValueType::Integer);
+}
+
+#[test]
+fn test_scalars_and_empty_arrays_pass() {
+    for input in ["1", "\"a\"", "null", "[]", "[null]", "{\"a\": []}"] {
+        assert!(
+            check_homogeneous(&parse(input).unwrap()).is_empty(),
+            "{input}"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+// this_file: crates/core/tests/key_case_test.rs
+
+//! Object keys are rewritten into one naming convention, with digits and
+//! acronyms split into words as a reader would, and keys that end up with
+//! the same name reported rather than merged.
+
+use vexy_json_core::ast::JsonPath;
+use vexy_json_core::transform::{
+    normalize_keys, JsonNormalizer, KeyCase, KeyCollision, NormalizerOptions,
+};
+use vexy_json_core::{parse, Error};
+
+fn convert_all(key: &str) -> [String; 4] {
+    [
+        KeyCase::CamelCase.convert(key),
+        KeyCase::SnakeCase.convert(key),
+        KeyCase::KebabCase.convert(key),
+        KeyCase::LowerCase.convert(key),
+    ]
+}
+
+#[test]
+fn test_acronyms() {
+    assert_eq!(
+        convert_all("HTTPStatus"),
+        ["httpStatus", "http_status", "http-status", "httpstatus"]
+    );
+    assert_eq!(
+        convert_all("parseJSONValue"),
+        [
+            "parseJsonValue",
+            "parse_json_value",
+            "parse-json-value",
+            "parsejsonvalue"
+        ]
+    );
+    assert_eq!(
+        convert_all("userID"),
+        ["userId", "user_id", "user-id", "userid"]
+    );
+    assert_eq!(convert_all("URL"), ["url", "url", "url", "url"]);
+}
+
+#[test]
+fn test_digits() {
+    assert_eq!(
+        convert_all("sha256Hash"),
+        ["sha256Hash", "sha256_hash", "sha256-hash", "sha256hash"]
+    );
+    assert_eq!(KeyCase::SnakeCase.convert("Address2Line"), "address2_line");
+    assert_eq!(KeyCase::SnakeCase.convert("line2"), "line2");
+    assert_eq!(KeyCase::CamelCase.convert("line_2"), "line2");
+    assert_eq!(KeyCase::SnakeCase.convert("2fa_code"), "2fa_code");
+}
+
+#[test]
+fn test_separators_and_round_trips() {
+    assert_eq!(KeyCase::CamelCase.convert("user_id"), "userId");
+    assert_eq!(KeyCase::CamelCase.convert("User-Id"), "userId");
+    assert_eq!(KeyCase::SnakeCase.convert("first name"), "first_name");
+    assert_eq!(KeyCase::KebabCase.convert("__private"), "private");
+    assert_eq!(KeyCase::LowerCase.convert("User_Id"), "user_id");
+    assert_eq!(KeyCase::SnakeCase.convert(""), "");
+    assert_eq!(KeyCase::CamelCase.convert("élanVital"), "élanVital");
+
+    for key in ["userId", "http_status", "retry-after", "sha256Hash"] {
+        let snake = KeyCase::SnakeCase.convert(key);
+        let camel = KeyCase::CamelCase.convert(&snake);
+        assert_eq!(KeyCase::SnakeCase.convert(&camel), snake);
+    }
+}
+
+#[test]
+fn test_normalize_nested_keys() {
+    let mut value =
+        parse(r#"{"userId": 1, "HTTPStatus": {"retryAfter": 2}, "rows": [{"zipCode": "x"}, 3]}"#)
+            .unwrap();
+    normalize_keys(&mut value, KeyCase::KebabCase).unwrap();
+    let expected = parse(
+        r#"{"user-id": 1, "http-status": {"retry-after": 2}, "rows": [{"zip-code": "x"}, 3]}"#,
+    )
+    .unwrap();
+    assert_eq!(value, expected);
+
+    // Values are never rewritten, only keys
+    let mut value = parse(r#"{"a": "userId"}"#).unwrap();
+    normalize_keys(&mut value, KeyCase::SnakeCase).unwrap();
+    assert_eq!(value["a"].as_str(), Some("userId"));
+}
+
+#[test]
+fn test_existing_collisions() {
+    let mut value = parse(
+        r#"{
+            "userId": 1, "user_id": 2, "UserID": 3,
+            "rows": [{"zipCode": 4, "zip_code": 5}],
+            "keptName": 6
+        }"#,
+    )
+    .unwrap();
+    let collisions = normalize_keys(&mut value, KeyCase::SnakeCase).unwrap_err();
+    assert_eq!(
+        collisions,
+        vec![
+            KeyCollision {
+                path: JsonPath::root(),
+                keys: vec!["UserID".into(), "userId".into(), "user_id".into()],
+                target: "user_id".into(),
+            },
+            KeyCollision {
+                path: "$.rows[0]".parse().unwrap(),
+                keys: vec!["zipCode".into(), "zip_code".into()],
+                target: "zip_code".into(),
+            },
+        ]
+    );
+    assert_eq!(
+        collisions[1].to_string(),
+        r#"$.rows[0]: keys "zipCode", "zip_code" all become "zip_code""#
+    );
+
+    // Colliding keys keep their values and names; the others are renamed
+    assert_eq!(value["userId"].as_i64(), Some(1));
+    assert_eq!(value["user_id"].as_i64(), Some(2));
+    assert_eq!(value["UserID"].as_i64(), Some(3));
+    assert_eq!(value["rows"][0]["zipCode"].as_i64(), Some(4));
+    assert_eq!(value["kept_name"].as_i64(), Some(6));
+
+    // A key already in the target case is no collision on its own
+    let mut value = parse(r#"{"user_id": 1, "name": 2}"#).unwrap();
+    assert!(normalize_keys(&mut value, KeyCase::SnakeCase).is_ok());
+}
+
+#[test]
+fn test_normalizer_key_case() {
+    let options = NormalizerOptions {
+        key_case: Some(KeyCase::CamelCase),
+        ..Default::default()
+    };
+    let value = parse(r#"{"http_status": {"retry_after": 1}}"#).unwrap();
+    let normalized = JsonNormalizer::with_options(options.clone())
+        .normalize(&value)
+        .unwrap();
+    assert_eq!(normalized["httpStatus"]["retryAfter"].as_i64(), Some(1));
+
+    let value = parse(r#"{"items": [{"user_id": 1, "userId": 2}]}"#).unwrap();
+    let error = JsonNormalizer::with_options(options)
+        .normalize(&value)
+        .unwrap_err();
+    assert!(matches!(error, Error::AtPath { .. }));
+    assert_eq!(
+        error.to_string(),
+        r#"Custom error: keys "userId", "user_id" all become "userId" in camelCase at path $.items[0]"#
+    );
+}
+
+#[test]
+fn test_key_case_names() {
+    for case in [
+        KeyCase::CamelCase,
+        KeyCase::SnakeCase,
+        KeyCase::KebabCase,
+        KeyCase::LowerCase,
+    ] {
+        assert_eq!(case.to_string().parse::<KeyCase>(), Ok(case));
+    }
+    assert_eq!("snake".parse::<KeyCase>(), Ok(KeyCase::SnakeCase));
+    assert!("SCREAMING_SNAKE".parse::<KeyCase>().is_err());
+}
+
+#[test]
+fn test_lookup_ignoring_case_and_by_alias() {
+    let value = parse(r#"{"HTTPStatus": 200, "user_id": 7}"#).unwrap();
+    assert_eq!(
+        value.get_ignore_case("httpstatus").and_then(|v| v.as_i64()),
+        Some(200)
+    );
+    assert_eq!(value.get_ignore_case("http_status"), None);
+    assert_eq!(
+        value
+            .get_by_aliases(&["userId", "user_id", "uid"])
+            .and_then(|v| v.as_i64()),
+        Some(7)
+    );
+    assert_eq!(value.get_by_aliases(&["userId", "uid"]), None);
+}
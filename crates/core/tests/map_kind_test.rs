@@ -0,0 +1,163 @@
+// this_file: crates/core/tests/map_kind_test.rs
+
+//! Parsing into values with objects held in each kind of map, and the
+//! ordering each one guarantees.
+
+use vexy_json_core::ast::{Hashed, MapLike, Sorted, SortedValue, Value, ValueG};
+use vexy_json_core::{parse, parse_into};
+
+const DOCUMENT: &str = "{
+    zeta: 1,
+    alpha: {y: true, x: null, w: [3, {k: 'v', b: 2}]},
+    mid: 'text',
+    // comment
+    beta: [],
+}";
+
+/// Keys of the object at `value`, in the order its map iterates them.
+fn keys<M: MapLike>(value: &ValueG<M>) -> Vec<&str> {
+    M::iter(value.as_object().expect("an object"))
+        .map(|(key, _)| key.as_str())
+        .collect()
+}
+
+#[test]
+fn test_every_map_kind_parses_the_same_value() {
+    let value = parse(DOCUMENT).unwrap();
+    let hashed: Value = parse_into(DOCUMENT).unwrap();
+    let sorted: SortedValue = parse_into(DOCUMENT).unwrap();
+    assert_eq!(hashed, value);
+    assert_eq!(sorted.clone().into_map::<Hashed>(), value);
+    assert_eq!(value.clone().into_map::<Sorted>(), sorted);
+
+    // The same text, and the same access through indexing and lookups
+    assert_eq!(sorted.to_string(), value.to_string());
+    assert_eq!(sorted["alpha"]["w"][1]["k"].as_str(), Some("v"));
+    assert_eq!(
+        sorted.get_entry("alpha").and_then(|a| a.get_entry("x")),
+        Some(&ValueG::Null)
+    );
+    assert_eq!(
+        sorted.get_ignore_case("ZETA").and_then(ValueG::as_i64),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_sorted_value_iterates_in_key_order() {
+    let sorted: SortedValue = parse_into(DOCUMENT).unwrap();
+    assert_eq!(keys(&sorted), ["alpha", "beta", "mid", "zeta"]);
+    assert_eq!(keys(&sorted["alpha"]), ["w", "x", "y"]);
+    assert_eq!(keys(&sorted["alpha"]["w"][1]), ["b", "k"]);
+
+    let from_str: SortedValue = DOCUMENT.parse().unwrap();
+    assert_eq!(from_str, sorted);
+}
+
+#[test]
+fn test_sorted_value_debug_and_builders() {
+    let mut value = SortedValue::Null;
+    value.extend([("b".to_string(), SortedValue::Bool(true))]);
+    value.extend([("a".to_string(), SortedValue::Null)]);
+    assert_eq!(
+        format!("{value:?}"),
+        r#"Object({"a": Null, "b": Bool(true)})"#
+    );
+    assert_eq!(value, parse_into("{b: true, a: null}").unwrap());
+}
+
+#[cfg(feature = "indexmap")]
+mod ordered {
+    use super::*;
+    use vexy_json_core::ast::{Ordered, OrderedValue};
+    use vexy_json_core::{parse_into_with_options, DuplicateKeyPolicy, ParserOptions};
+
+    #[test]
+    fn test_ordered_value_keeps_source_order() {
+        let ordered: OrderedValue = parse_into(DOCUMENT).unwrap();
+        assert_eq!(keys(&ordered), ["zeta", "alpha", "mid", "beta"]);
+        assert_eq!(keys(&ordered["alpha"]), ["y", "x", "w"]);
+        assert_eq!(keys(&ordered["alpha"]["w"][1]), ["k", "b"]);
+        assert_eq!(
+            ordered.to_string(),
+            r#"{"zeta":1,"alpha":{"y":true,"x":null,"w":[3,{"k":"v","b":2}]},"mid":"text","beta":[]}"#
+        );
+        assert_eq!(
+            ordered.clone().into_map::<Hashed>(),
+            parse(DOCUMENT).unwrap()
+        );
+        assert_eq!(DOCUMENT.parse::<OrderedValue>().unwrap(), ordered);
+
+        // Implicit top-level objects, too
+        let ordered: OrderedValue = parse_into("b: 1\na: 2\nc: 3").unwrap();
+        assert_eq!(keys(&ordered), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_ordered_value_repeated_keys() {
+        // A repeated key keeps its first place and, by default, its last value
+        let input = "{b: {y: 1, x: 2}, a: 0, b: {q: 3, p: 4}}";
+        let ordered: OrderedValue = parse_into(input).unwrap();
+        assert_eq!(keys(&ordered), ["b", "a"]);
+        assert_eq!(keys(&ordered["b"]), ["q", "p"]);
+
+        let options = ParserOptions {
+            duplicate_keys: DuplicateKeyPolicy::FirstWins,
+            ..Default::default()
+        };
+        let ordered: OrderedValue = parse_into_with_options(input, options).unwrap();
+        assert_eq!(keys(&ordered), ["b", "a"]);
+        assert_eq!(keys(&ordered["b"]), ["y", "x"]);
+    }
+
+    #[test]
+    fn test_ordered_value_follows_lexing_options() {
+        // Without hash comments, `a#b` is a value rather than `a` and a
+        // comment that runs to the end of the line
+        let mut options = ParserOptions::default();
+        options.comment_styles.hash = false;
+        let ordered: OrderedValue =
+            parse_into_with_options("{z: a#b, y: 1, x: 2}", options).unwrap();
+        assert_eq!(keys(&ordered), ["z", "y", "x"]);
+        assert_eq!(ordered["z"].as_str(), Some("a#b"));
+    }
+
+    #[test]
+    fn test_ordered_value_large_object() {
+        let count = 40_000;
+        let names: Vec<String> = (0..count).rev().map(|i| format!("k{i}")).collect();
+        let members: Vec<String> = names
+            .iter()
+            .map(|name| format!("{name}: [{{b: 1, a: 2}}]"))
+            .collect();
+        let input = format!("{{{}, k0: {{y: 1, x: 2}}}}", members.join(", "));
+
+        let ordered: OrderedValue = parse_into(&input).unwrap();
+        assert_eq!(keys(&ordered), names);
+        assert_eq!(keys(&ordered["k1"][0]), ["b", "a"]);
+        assert_eq!(keys(&ordered["k0"]), ["y", "x"]);
+    }
+
+    #[test]
+    fn test_ordered_value_remove_keeps_order() {
+        let mut ordered: OrderedValue = parse_into("{c: 1, b: 2, a: 3}").unwrap();
+        if let OrderedValue::Object(map) = &mut ordered {
+            assert_eq!(Ordered::remove(map, "c").and_then(|v| v.as_i64()), Some(1));
+        }
+        assert_eq!(keys(&ordered), ["b", "a"]);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize_in_map_order() {
+    let sorted: SortedValue = parse_into("{b: [1], a: null}").unwrap();
+    assert_eq!(
+        serde_json::to_string(&sorted).unwrap(),
+        r#"{"a":null,"b":[1]}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<SortedValue>(r#"{"b":[1],"a":null}"#).unwrap(),
+        sorted
+    );
+}
@@ -19,7 +19,7 @@ fn test_memory_pool_optimization_improvements() {
     assert!(result.is_ok(), "V3 parser should parse successfully");
 
     // Test with statistics to verify optimizations
-    let (value, stats, alloc_stats) = parse_v3_with_stats(json, ParserOptions::default()).unwrap();
+    let (value, stats, _alloc_stats) = parse_v3_with_stats(json, ParserOptions::default()).unwrap();
     
     // Verify the value is parsed correctly
     match value {
@@ -1,5 +1,7 @@
 // this_file: crates/core/tests/ml_integration_test.rs
 
+#![allow(clippy::field_reassign_with_default)]
+
 use vexy_json_core::parser::{parse_with_fallback, ParserOptions};
 use vexy_json_core::error::repair::ParsingTier;
 
@@ -46,6 +48,7 @@ fn test_ml_confidence_ordering() {
     }
 }
 
+#[cfg(feature = "ml-recovery")]
 #[test]
 fn test_ml_learning_capability() {
     use vexy_json_core::error::{ErrorContext, ErrorRecoveryEngineV2, Error};
@@ -1,5 +1,7 @@
 // this_file: crates/core/tests/ml_pattern_test.rs
 
+#![cfg(feature = "ml-recovery")]
+
 use vexy_json_core::error::{Error, ErrorContext};
 use vexy_json_core::error::ml_patterns::MLPatternRecognizer;
 
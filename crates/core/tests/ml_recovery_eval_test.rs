@@ -0,0 +1,156 @@
+// this_file: crates/core/tests/ml_recovery_eval_test.rs
+
+//! Measures how much of the broken JSON in `tests/fixtures/broken` the
+//! learned-pattern tier fixes, against rule-based repair alone. Each fixture
+//! has the document it was meant to be in `expected/`, so a repair that
+//! parses into some other document is counted apart from one that fixes it.
+//!
+//! Run with `cargo test -p vexy-json-core --features ml-recovery --test
+//! ml_recovery_eval_test` to see the report.
+
+#![cfg(feature = "ml-recovery")]
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use vexy_json_core::ast::Value;
+use vexy_json_core::error::repair::{EnhancedParseResult, ParsingTier};
+use vexy_json_core::parse;
+use vexy_json_core::parser::{parse_with_fallback, parse_with_ml_recovery, ParserOptions};
+
+struct Fixture {
+    name: String,
+    input: String,
+    expected: Value,
+}
+
+fn fixtures() -> Vec<Fixture> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/broken");
+    let mut fixtures: Vec<Fixture> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| {
+            let expected = dir.join("expected").join(path.file_name().unwrap());
+            Fixture {
+                name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+                input: fs::read_to_string(&path).unwrap(),
+                expected: parse(&fs::read_to_string(expected).unwrap()).unwrap(),
+            }
+        })
+        .collect();
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    fixtures
+}
+
+/// How many fixtures an engine fixed, parsed into some other document, or
+/// could not parse.
+#[derive(Default)]
+struct Tally {
+    fixed: usize,
+    wrong: usize,
+    failed: usize,
+}
+
+impl Tally {
+    fn add(&mut self, result: &EnhancedParseResult<Value>, expected: &Value) -> &'static str {
+        if !result.is_success() {
+            self.failed += 1;
+            "failed"
+        } else if result.value == *expected {
+            self.fixed += 1;
+            "fixed"
+        } else {
+            self.wrong += 1;
+            "parsed differently"
+        }
+    }
+}
+
+impl std::fmt::Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} fixed, {} parsed differently, {} failed",
+            self.fixed, self.wrong, self.failed
+        )
+    }
+}
+
+#[test]
+fn test_ml_recovery_against_rule_based_repair() {
+    let fixtures = fixtures();
+    let mut rules = Tally::default();
+    let mut ml = Tally::default();
+    let mut patterns = Vec::new();
+    for fixture in &fixtures {
+        let name = &fixture.name;
+        let by_rules = parse_with_fallback(&fixture.input, ParserOptions::default());
+        let (by_ml, pattern) = parse_with_ml_recovery(&fixture.input, ParserOptions::default());
+        rules.add(&by_rules, &fixture.expected);
+        let verdict = ml.add(&by_ml, &fixture.expected);
+
+        if by_rules.is_success() {
+            // The tier only runs where rule-based repair gave up
+            assert_eq!(
+                (&by_ml.value, &by_ml.repairs),
+                (&by_rules.value, &by_rules.repairs),
+                "{name}"
+            );
+            assert!(pattern.is_none(), "{name}");
+        }
+        if let Some(pattern) = pattern {
+            assert!(by_ml.is_success(), "{name}");
+            assert_eq!(by_ml.parsing_tier, ParsingTier::Repair, "{name}");
+            assert!(
+                by_ml
+                    .repairs
+                    .iter()
+                    .any(|repair| repair.description == pattern.suggestion.description),
+                "{name}"
+            );
+            patterns.push(format!("    {name}: {}, {verdict}", pattern.pattern));
+        }
+    }
+
+    let report = format!(
+        "broken JSON fixtures: {}\n  \
+         rule-based repair: {rules}\n  \
+         with ML recovery:  {ml}\n  \
+         learned patterns used:\n{}\n",
+        fixtures.len(),
+        patterns.join("\n")
+    );
+    // Written past the test harness's capture, so the report is always shown
+    std::io::stderr().write_all(report.as_bytes()).unwrap();
+
+    assert!(fixtures.len() >= 20, "{report}");
+    assert!(ml.fixed >= rules.fixed, "{report}");
+    assert!(ml.failed <= rules.failed, "{report}");
+}
+
+#[test]
+fn test_reports_the_matched_pattern() {
+    let input = r#"{"a": 'b"}"#;
+    assert!(!parse_with_fallback(input, ParserOptions::default()).is_success());
+
+    let (result, pattern) = parse_with_ml_recovery(input, ParserOptions::default());
+    let pattern = pattern.unwrap();
+    assert_eq!(pattern.pattern, "unmatched_quote");
+    assert!(pattern.score > 0.5 && pattern.score <= 1.0);
+    assert!(result.is_success());
+    // Positions refer to the input as written
+    assert_eq!(result.repairs[0].position, 6);
+    assert_eq!(result.repairs[0].replacement, "\"");
+}
+
+#[test]
+fn test_respects_enable_repair() {
+    let options = ParserOptions {
+        enable_repair: false,
+        ..Default::default()
+    };
+    let (result, pattern) = parse_with_ml_recovery(r#"{"a": 'b"}"#, options);
+    assert!(!result.is_success());
+    assert!(pattern.is_none());
+}
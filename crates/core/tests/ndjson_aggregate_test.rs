@@ -0,0 +1,61 @@
+// this_file: tests/ndjson_aggregate_test.rs
+
+use vexy_json_core::streaming::NdJsonAggregator;
+
+const TRANSACTIONS: &str = include_str!("fixtures/transactions.ndjson");
+
+fn aggregator() -> NdJsonAggregator {
+    NdJsonAggregator::new()
+        .sum("$.amount".parse().unwrap())
+        .group_by("$.type".parse().unwrap())
+}
+
+#[test]
+fn test_sum_and_group_by() {
+    let mut aggregator = aggregator();
+    aggregator.feed(TRANSACTIONS).unwrap();
+    let totals = aggregator.finish().unwrap();
+
+    assert_eq!(totals.records, 5);
+    // Nested "amount" and "type" keys are not at the top-level paths
+    assert_eq!(totals.summed, 4);
+    assert!((totals.sum - 126.0).abs() < 1e-9, "{}", totals.sum);
+    assert_eq!(
+        totals.groups.into_iter().collect::<Vec<_>>(),
+        [
+            ("refund".to_string(), 1),
+            ("sale".to_string(), 3),
+            ("transfer".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_chunks_split_mid_record() {
+    let mut whole = aggregator();
+    whole.feed(TRANSACTIONS).unwrap();
+    let whole = whole.finish().unwrap();
+
+    let mut chunked = aggregator();
+    for chunk in TRANSACTIONS.as_bytes().chunks(7) {
+        chunked.feed(std::str::from_utf8(chunk).unwrap()).unwrap();
+        assert!(chunked.aggregates().records <= whole.records);
+    }
+    assert_eq!(chunked.finish().unwrap(), whole);
+}
+
+#[test]
+fn test_count_only_and_array_paths() {
+    let mut counter = NdJsonAggregator::new();
+    counter.feed("[1, 2]\n[3]\n{}").unwrap();
+    assert_eq!(counter.aggregates().records, 2);
+    let totals = counter.finish().unwrap();
+    assert_eq!(totals.records, 3);
+    assert_eq!(totals.summed, 0);
+    assert!(totals.groups.is_empty());
+
+    let mut second = NdJsonAggregator::new().sum("$[1]".parse().unwrap());
+    second.feed("[1, 2]\n[3]\n[4, 5, 6]\n").unwrap();
+    let totals = second.finish().unwrap();
+    assert_eq!((totals.sum, totals.summed), (7.0, 2));
+}
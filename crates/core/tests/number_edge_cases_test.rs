@@ -0,0 +1,405 @@
+// this_file: crates/core/tests/number_edge_cases_test.rs
+
+//! Negative zero, subnormals and overflow read the same way in every
+//! backend, overflow follows [`ParserOptions::number_overflow`], and whole
+//! numbers written with an exponent follow
+//! [`ParserOptions::exponent_as_float`].
+
+use std::io::Cursor;
+use vexy_json_core::ast::{Number, Value};
+use vexy_json_core::format::{to_compact_string, KeyOrder};
+use vexy_json_core::optimization::{parse_number_fast, parse_number_optimized, parse_number_simd};
+use vexy_json_core::streaming::validate_stream;
+use vexy_json_core::{
+    parse_lazy_with_options, parse_optimized_v2_with_options, parse_optimized_with_options,
+    parse_streaming_with_config, parse_with_fallback, parse_with_options, BufferedStreamingConfig,
+    Error, NumberOverflowPolicy, ParserBackend, ParserOptions, ParsingTier, StreamingEvent,
+    StreamingParser, WarningKind,
+};
+
+/// What a literal reads as.
+#[derive(Debug, Clone, Copy)]
+enum Expected {
+    Integer(i64),
+    /// A float with exactly these bits, so that the sign of zero counts
+    Float(f64),
+    /// Beyond the range of `f64`: infinity with this sign when forgiving,
+    /// an error when strict
+    Overflow(f64),
+}
+
+const CASES: &[(&str, Expected)] = &[
+    ("0", Expected::Integer(0)),
+    ("-0", Expected::Float(-0.0)),
+    ("0.0", Expected::Float(0.0)),
+    ("-0.0", Expected::Float(-0.0)),
+    ("-0e0", Expected::Float(-0.0)),
+    ("-0E+5", Expected::Float(-0.0)),
+    ("-9223372036854775808", Expected::Integer(i64::MIN)),
+    ("9223372036854775807", Expected::Integer(i64::MAX)),
+    ("9223372036854775806", Expected::Integer(i64::MAX - 1)),
+    ("9007199254740993", Expected::Integer(9007199254740993)),
+    ("1e-323", Expected::Float(1e-323)),
+    ("5e-324", Expected::Float(5e-324)),
+    ("-5e-324", Expected::Float(-5e-324)),
+    (
+        "2.2250738585072014e-308",
+        Expected::Float(f64::MIN_POSITIVE),
+    ),
+    // Below half the smallest subnormal: the nearest f64 is zero
+    ("2e-324", Expected::Float(0.0)),
+    ("-1e-400", Expected::Float(-0.0)),
+    ("1E+308", Expected::Float(1e308)),
+    ("1.7976931348623157e308", Expected::Float(f64::MAX)),
+    ("-1.7976931348623157e308", Expected::Float(f64::MIN)),
+    ("1.7976931348623159e308", Expected::Overflow(f64::INFINITY)),
+    ("1e309", Expected::Overflow(f64::INFINITY)),
+    ("5e324", Expected::Overflow(f64::INFINITY)),
+    ("-1e309", Expected::Overflow(f64::NEG_INFINITY)),
+];
+
+fn strict() -> ParserOptions {
+    ParserOptions {
+        allow_comments: false,
+        allow_trailing_commas: false,
+        allow_unquoted_keys: false,
+        allow_single_quotes: false,
+        implicit_top_level: false,
+        newline_as_comma: false,
+        ..Default::default()
+    }
+}
+
+type Parse = fn(&str, ParserOptions) -> Result<Value, Error>;
+
+/// Every way of reading a whole document into a [`Value`].
+fn backends() -> Vec<(&'static str, Parse)> {
+    fn with_backend(
+        input: &str,
+        options: ParserOptions,
+        backend: ParserBackend,
+    ) -> Result<Value, Error> {
+        parse_with_options(input, ParserOptions { backend, ..options })
+    }
+    vec![
+        ("auto", |input, options| {
+            with_backend(input, options, ParserBackend::Auto)
+        }),
+        ("recursive", |input, options| {
+            with_backend(input, options, ParserBackend::Recursive)
+        }),
+        ("iterative", |input, options| {
+            with_backend(input, options, ParserBackend::Iterative)
+        }),
+        ("optimized", parse_optimized_with_options),
+        ("optimized v2", parse_optimized_v2_with_options),
+        ("optimized v3", |input, options| {
+            with_backend(input, options, ParserBackend::OptimizedV3)
+        }),
+        ("lazy", parse_lazy_with_options),
+        ("streaming", |input, options| {
+            let config = BufferedStreamingConfig {
+                parser_options: options,
+                ..Default::default()
+            };
+            // The buffered parser reads containers only
+            let input = format!("[{input}]");
+            let value = parse_streaming_with_config(Cursor::new(input), config).parse_to_value();
+            value
+                .map(|value| value[0].clone())
+                .map_err(|error| match error {
+                    Error::Expected {
+                        expected,
+                        found,
+                        position,
+                    } => Error::Expected {
+                        expected,
+                        found,
+                        position: position - 1,
+                    },
+                    error => error,
+                })
+        }),
+    ]
+}
+
+fn same_bits(actual: f64, expected: f64) -> bool {
+    actual.to_bits() == expected.to_bits()
+}
+
+fn assert_reads_as(value: &Value, expected: f64, context: &str) {
+    match value {
+        Value::Number(Number::Float(f)) => {
+            assert!(
+                same_bits(*f, expected),
+                "{context}: {f:e}, expected {expected:e}"
+            )
+        }
+        other => panic!("{context}: {other:?}, expected {expected:e}"),
+    }
+}
+
+#[test]
+fn test_edge_cases_in_every_backend() {
+    for (name, parse) in backends() {
+        for &(literal, expected) in CASES {
+            // Inside an array too, where the number token is not the whole input
+            for input in [literal.to_string(), format!("[{literal}]")] {
+                let context = format!("{name} {input}");
+                let unwrap = |value: Value| match value {
+                    Value::Array(mut items) if input.starts_with('[') => items.remove(0),
+                    value => value,
+                };
+                let forgiving = parse(&input, ParserOptions::default()).map(unwrap);
+                let strict_result = parse(&input, strict()).map(unwrap);
+                match expected {
+                    Expected::Integer(i) => {
+                        assert_eq!(
+                            forgiving,
+                            Ok(Value::Number(Number::Integer(i))),
+                            "{context}"
+                        );
+                        assert_eq!(
+                            strict_result,
+                            Ok(Value::Number(Number::Integer(i))),
+                            "{context}"
+                        );
+                    }
+                    Expected::Float(f) => {
+                        assert_reads_as(&forgiving.unwrap(), f, &context);
+                        assert_reads_as(&strict_result.unwrap(), f, &context);
+                    }
+                    Expected::Overflow(infinity) => {
+                        assert_reads_as(&forgiving.unwrap(), infinity, &context);
+                        let error = strict_result.unwrap_err();
+                        assert_eq!(
+                            error.position(),
+                            Some(input.find(literal).unwrap()),
+                            "{context}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_validation_follows_the_same_rules() {
+    for &(literal, expected) in CASES {
+        let input = format!("[1, {literal}]");
+        assert!(
+            validate_stream(input.as_bytes(), ParserOptions::default()).is_ok(),
+            "{input}"
+        );
+        let strict_result = validate_stream(input.as_bytes(), strict());
+        match expected {
+            Expected::Overflow(_) => assert_eq!(strict_result.unwrap_err().position(), Some(4)),
+            _ => assert!(strict_result.is_ok(), "{input}"),
+        }
+    }
+}
+
+#[test]
+fn test_number_helpers_keep_negative_zero() {
+    for text in ["-0", "-0.0", "-0e0"] {
+        assert!(same_bits(parse_number_simd(text).unwrap(), -0.0), "{text}");
+        assert!(
+            same_bits(parse_number_optimized(text).unwrap(), -0.0),
+            "{text}"
+        );
+        assert_reads_as(&parse_number_fast(text).unwrap(), -0.0, text);
+    }
+    assert!(same_bits(parse_number_simd("-12").unwrap(), -12.0));
+}
+
+#[test]
+fn test_overflow_is_noted_when_forgiving() {
+    let options = ParserOptions {
+        collect_warnings: true,
+        ..Default::default()
+    };
+    let result = parse_with_fallback("{\"big\": 1e400, \"small\": 1e-400}", options);
+    assert!(result.errors.is_empty());
+    assert_eq!(result.parsing_tier, ParsingTier::Forgiving);
+    assert_reads_as(&result.value["big"], f64::INFINITY, "big");
+    let warnings: Vec<_> = result
+        .warnings
+        .iter()
+        .map(|w| (w.kind, w.span.start))
+        .collect();
+    assert_eq!(warnings, [(WarningKind::NumberOverflow, 8)]);
+
+    let error = parse_with_options("{\"big\": 1e400}", strict()).unwrap_err();
+    assert!(error.to_string().contains("range of f64"), "{error}");
+}
+
+#[test]
+fn test_negative_zero_round_trips() {
+    let value = parse_with_options("-0", ParserOptions::default()).unwrap();
+    assert_eq!(value.to_string(), "-0.0");
+    let json = to_compact_string(&Value::Array(vec![value]), &KeyOrder::default());
+    assert_eq!(json, "[-0.0]");
+
+    let reread = parse_with_options(&json, strict()).unwrap();
+    assert_reads_as(&reread[0], -0.0, &json);
+    let (tier, value) = {
+        let result = parse_with_fallback(&json, ParserOptions::default());
+        (result.parsing_tier, result.value)
+    };
+    assert_eq!(tier, ParsingTier::Fast);
+    assert_reads_as(&value[0], -0.0, "fast tier");
+}
+
+/// Reads the single number in `input` through [`StreamingParser`], returning
+/// the text of its event.
+fn stream_number(input: &str, options: ParserOptions) -> Result<String, Error> {
+    let mut parser = StreamingParser::with_options(ParserOptions {
+        newline_as_comma: false,
+        ..options
+    });
+    parser.feed(input)?;
+    parser.finish()?;
+    let mut number = None;
+    while let Some(event) = parser.next_event()? {
+        match event {
+            StreamingEvent::Number(text) => number = Some(text),
+            StreamingEvent::EndOfInput => break,
+            _ => {}
+        }
+    }
+    Ok(number.expect("a number event"))
+}
+
+#[test]
+fn test_overflow_policy() {
+    let digits = "9".repeat(500);
+    let overflowing = [
+        ("1e400".to_string(), f64::INFINITY),
+        ("-1e400".to_string(), f64::NEG_INFINITY),
+        (digits.clone(), f64::INFINITY),
+        (format!("-{digits}"), f64::NEG_INFINITY),
+    ];
+    let policies = [
+        NumberOverflowPolicy::Auto,
+        NumberOverflowPolicy::Infinity,
+        NumberOverflowPolicy::Error,
+    ];
+    assert_eq!(
+        ParserOptions::default().number_overflow,
+        NumberOverflowPolicy::Auto
+    );
+
+    for (literal, infinity) in &overflowing {
+        let input = format!("[{literal}]");
+        for policy in policies {
+            for (base, strict) in [(ParserOptions::default(), false), (strict(), true)] {
+                let options = ParserOptions {
+                    number_overflow: policy,
+                    ..base
+                };
+                let fails = match policy {
+                    NumberOverflowPolicy::Auto => strict,
+                    NumberOverflowPolicy::Infinity => false,
+                    NumberOverflowPolicy::Error => true,
+                };
+                let context = format!("{literal:.12} {policy:?} strict={strict}");
+                for (name, parse) in backends() {
+                    let result = parse(&input, options.clone());
+                    if fails {
+                        let error = result.unwrap_err();
+                        assert_eq!(error.position(), Some(1), "{name} {context}");
+                        assert!(error.to_string().contains("range of f64"), "{error}");
+                    } else {
+                        let value = result.unwrap();
+                        assert_reads_as(&value[0], *infinity, &format!("{name} {context}"));
+                    }
+                }
+
+                let validated = validate_stream(input.as_bytes(), options.clone());
+                assert_eq!(validated.is_err(), fails, "validate {context}");
+                match stream_number(&input, options) {
+                    Ok(text) => {
+                        assert!(!fails, "streaming {context}");
+                        assert_eq!(&text, literal);
+                    }
+                    Err(error) => {
+                        assert!(fails, "streaming {context}: {error}");
+                        assert_eq!(error.position(), Some(1));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_integers_beyond_i64_read_as_nearest_f64() {
+    let digits = format!("1{}", "0".repeat(300));
+    let value = parse_with_options(&digits, strict()).unwrap();
+    assert_reads_as(&value, 1e300, &digits);
+
+    // Every digit is kept in the streaming parser's events
+    let digits = "123456789".repeat(40);
+    let text = stream_number(&format!("[{digits}]"), ParserOptions::default()).unwrap();
+    assert_eq!(text, digits);
+
+    // And the precision option refuses to round them
+    let options = ParserOptions {
+        preserve_number_precision: true,
+        ..Default::default()
+    };
+    assert!(parse_with_options(&digits, options).is_err());
+}
+
+#[test]
+fn test_exponent_as_float() {
+    assert!(ParserOptions::default().exponent_as_float);
+    // What each literal reads as with the option disabled
+    let cases = [
+        ("1e2", Some(100)),
+        ("1E2", Some(100)),
+        ("1.5e2", Some(150)),
+        ("-2.5E+1", Some(-25)),
+        ("25e-1", None),
+        ("12300e-2", Some(123)),
+        // Digits past what an f64 holds are kept
+        ("1234567890123456789e0", Some(1234567890123456789)),
+        ("9.223372036854775807e18", Some(i64::MAX)),
+        ("-9223372036854775808E0", Some(i64::MIN)),
+    ];
+
+    for (name, parse) in backends() {
+        for (literal, integer) in cases {
+            let input = format!("[{literal}]");
+            let context = format!("{name} {literal}");
+            let float = literal.parse::<f64>().unwrap();
+            for base in [ParserOptions::default(), strict()] {
+                let value = parse(&input, base.clone()).unwrap();
+                assert_reads_as(&value[0], float, &context);
+
+                let options = ParserOptions {
+                    exponent_as_float: false,
+                    ..base
+                };
+                let value = parse(&input, options).unwrap();
+                match integer {
+                    Some(i) => assert_eq!(value[0], Value::Number(Number::Integer(i)), "{context}"),
+                    None => assert_reads_as(&value[0], float, &context),
+                }
+            }
+        }
+
+        // Only numbers written with an exponent are affected, and only those
+        // an i64 holds exactly
+        let options = ParserOptions {
+            exponent_as_float: false,
+            ..strict()
+        };
+        let value = parse("[100.0, -0e0, 1e19, 100]", options).unwrap();
+        assert_reads_as(&value[0], 100.0, name);
+        assert_reads_as(&value[1], -0.0, name);
+        assert_reads_as(&value[2], 1e19, name);
+        assert_eq!(value[3], Value::Number(Number::Integer(100)), "{name}");
+    }
+}
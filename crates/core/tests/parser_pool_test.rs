@@ -0,0 +1,123 @@
+// this_file: crates/core/tests/parser_pool_test.rs
+
+//! A [`ParserPool`] shared between threads parses correctly and reuses its
+//! parsers.
+
+use std::sync::{Arc, Barrier};
+use vexy_json_core::{parse_with_options, ParserBackend, ParserOptions, ParserPool};
+
+const THREADS: usize = 8;
+const PARSES_PER_THREAD: usize = 200;
+
+fn document(thread: usize, i: usize) -> String {
+    format!("{{thread: {thread}, i: {i}, tags: ['t{thread}', 'n{i}'], nested: {{ok: true}}}}")
+}
+
+#[test]
+fn test_pool_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ParserPool>();
+}
+
+#[test]
+fn test_concurrent_parses_reuse_parsers() {
+    let size = 3;
+    let pool = ParserPool::new(size, ParserOptions::default());
+    let start = Barrier::new(THREADS);
+
+    std::thread::scope(|scope| {
+        for thread in 0..THREADS {
+            let (pool, start) = (&pool, &start);
+            scope.spawn(move || {
+                start.wait();
+                for i in 0..PARSES_PER_THREAD {
+                    let input = document(thread, i);
+                    let value = pool.parse(&input).unwrap();
+                    assert_eq!(value["thread"].as_i64(), Some(thread as i64));
+                    assert_eq!(value["i"].as_i64(), Some(i as i64));
+                    assert_eq!(value["tags"][1].as_str(), Some(format!("n{i}").as_str()));
+                    assert_eq!(value["nested"]["ok"].as_bool(), Some(true));
+                    // Errors are reported, and the parser still goes back
+                    assert!(pool.parse(&input[..input.len() - 1]).is_err());
+                }
+            });
+        }
+    });
+
+    let stats = pool.stats();
+    let total = (THREADS * PARSES_PER_THREAD * 2) as u64;
+    assert!(stats.parsers_created >= 1 && stats.parsers_created <= size);
+    assert_eq!(stats.parses, total);
+    assert_eq!(stats.reuses, total - stats.parsers_created as u64);
+    assert_eq!(stats.idle, stats.parsers_created);
+}
+
+#[test]
+fn test_parsers_keep_their_buffers() {
+    // The hybrid parser only preallocates for longer input
+    let long = format!("[{}]", ["[[{a: [[1, {b: [2]}]]}]]"; 300].join(", "));
+    for (options, input) in [
+        (
+            ParserOptions {
+                backend: ParserBackend::Iterative,
+                ..Default::default()
+            },
+            "[[{a: [[1, {b: [2]}]]}]]",
+        ),
+        (
+            ParserOptions {
+                preallocate: true,
+                ..Default::default()
+            },
+            long.as_str(),
+        ),
+    ] {
+        let pool = ParserPool::new(THREADS / 2, options.clone());
+        assert_eq!(pool.stats().buffer_bytes, 0);
+        assert!(pool.parse(input).is_ok());
+        let kept = pool.stats().buffer_bytes;
+        assert!(kept > 0, "{options:?}");
+
+        // Each parser grows its buffers once and reuses them from then on
+        let expected = parse_with_options(input, options.clone());
+        std::thread::scope(|scope| {
+            for _ in 0..THREADS {
+                let (pool, expected) = (&pool, &expected);
+                scope.spawn(move || {
+                    for _ in 0..10 {
+                        assert_eq!(&pool.parse(input), expected);
+                    }
+                });
+            }
+        });
+        let stats = pool.stats();
+        assert_eq!(stats.buffer_bytes, stats.parsers_created * kept);
+    }
+}
+
+#[test]
+fn test_parses_with_the_pool_options() {
+    let options = ParserOptions {
+        allow_comments: false,
+        backend: ParserBackend::Iterative,
+        ..Default::default()
+    };
+    let pool = Arc::new(ParserPool::new(2, options.clone()));
+    assert_eq!(pool.options().backend, ParserBackend::Iterative);
+
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let pool = Arc::clone(&pool);
+            std::thread::spawn(move || {
+                let input = format!("[{i}, /* not allowed */ 1]");
+                pool.parse(&input)
+            })
+        })
+        .collect();
+    for handle in handles {
+        let result = handle.join().unwrap();
+        assert!(result.is_err());
+    }
+    assert_eq!(pool.parse("[1, 2]"), parse_with_options("[1, 2]", options));
+    assert_eq!(pool.stats().parses, 5);
+}
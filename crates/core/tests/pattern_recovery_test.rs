@@ -1,5 +1,7 @@
 // this_file: crates/core/tests/pattern_recovery_test.rs
 
+#![allow(clippy::field_reassign_with_default)]
+
 use vexy_json_core::parser::{parse_with_fallback, ParserOptions};
 use vexy_json_core::error::repair::ParsingTier;
 
@@ -121,4 +123,67 @@ fn test_forgiving_path_no_recovery() {
     assert!(result.is_success());
     assert_eq!(result.parsing_tier, ParsingTier::Forgiving);
     assert!(result.repairs.is_empty());
-}
\ No newline at end of file
+}
+#[test]
+fn test_extra_closing_braces_report_discarded_bytes() {
+    let result = parse_with_fallback(r#"{"a":1}}}}"#, ParserOptions::default());
+
+    assert!(result.is_success());
+    assert_eq!(result.parsing_tier, ParsingTier::Repair);
+    assert_eq!(result.discarded_trailing_bytes, 3);
+    assert!(result.discarded_trailing_input());
+}
+
+#[test]
+fn test_extra_closing_brackets_report_discarded_bytes() {
+    let result = parse_with_fallback("[1, 2]] ]", ParserOptions::default());
+
+    assert!(result.is_success());
+    assert_eq!(result.discarded_trailing_bytes, 3);
+}
+
+#[test]
+fn test_well_formed_input_discards_nothing() {
+    let result = parse_with_fallback(r#"{"a": [1, 2"#, ParserOptions::default());
+
+    assert!(result.is_success());
+    assert_eq!(result.discarded_trailing_bytes, 0);
+}
+
+#[test]
+fn test_max_trailing_discard_rejects_substantial_garbage() {
+    let mut options = ParserOptions::default();
+    options.max_trailing_discard = Some(1);
+
+    let result = parse_with_fallback("[1]]", options.clone());
+    assert!(result.is_success());
+    assert_eq!(result.discarded_trailing_bytes, 1);
+
+    let result = parse_with_fallback(r#"{"a":1}}}}"#, options);
+    assert!(!result.is_success());
+    assert_eq!(result.discarded_trailing_bytes, 3);
+    assert_eq!(result.errors[0].position(), Some(7));
+}
+
+#[test]
+fn test_max_repairs_counts_every_stage() {
+    let mut options = ParserOptions::default();
+    options.max_repairs = 0;
+
+    // Pattern-based recovery would close the quote
+    let result = parse_with_fallback("\"%", options.clone());
+    assert!(!result.is_success());
+    assert!(matches!(
+        result.errors[..],
+        [vexy_json_core::Error::MaxRepairsExceeded(0)]
+    ));
+    assert!(result.repairs.is_empty());
+
+    // Two quotes normalized and the missing bracket inserted
+    options.max_repairs = 2;
+    assert!(!parse_with_fallback("{“a”: [1}", options.clone()).is_success());
+    options.max_repairs = 3;
+    let result = parse_with_fallback("{“a”: [1}", options);
+    assert!(result.is_success());
+    assert_eq!(result.repairs.len(), 3);
+}
@@ -0,0 +1,89 @@
+// this_file: crates/core/tests/preview_test.rs
+
+//! Previews keep the shape of a document while shortening long strings and
+//! large containers, and leave small documents as they are.
+
+use vexy_json_core::format::{to_pretty_string, KeyOrder};
+use vexy_json_core::parse;
+use vexy_json_core::transform::to_preview_string;
+
+#[test]
+fn test_small_documents_render_fully() {
+    for input in [
+        r#"{"name": "vexy", "tags": ["a", "b"], "nested": {"n": 1.5, "ok": true, "none": null}}"#,
+        r#"[[], {}, "", 0, -1e100]"#,
+        r#""just a string""#,
+        "42",
+    ] {
+        let value = parse(input).unwrap();
+        let preview = to_preview_string(&value, 10, 100);
+        assert_eq!(preview, to_pretty_string(&value, 2, &KeyOrder::default()));
+        assert_eq!(parse(&preview).unwrap(), value);
+    }
+
+    // Exactly at both limits is not truncated
+    let value = parse(r#"["abc", "def"]"#).unwrap();
+    assert_eq!(
+        to_preview_string(&value, 2, 3),
+        "[\n  \"abc\",\n  \"def\"\n]"
+    );
+}
+
+#[test]
+fn test_long_strings_are_shortened() {
+    let value =
+        parse(r#"{"log": "0123456789", "short": "ab", "emoji": "héllo wörld 🎉"}"#).unwrap();
+    assert_eq!(
+        to_preview_string(&value, 10, 4),
+        "{\n  \"emoji\": \"héll…\",\n  \"log\": \"0123…\",\n  \"short\": \"ab\"\n}"
+    );
+
+    // Escapes are kept whole
+    let value = parse(r#""a\"b\nc\td""#).unwrap();
+    assert_eq!(to_preview_string(&value, 10, 4), r#""a\"b\n…""#);
+    assert_eq!(to_preview_string(&value, 10, 0), r#""…""#);
+}
+
+#[test]
+fn test_large_containers_are_shortened() {
+    let numbers: Vec<String> = (0..1000).map(|n| n.to_string()).collect();
+    let value = parse(&format!(
+        r#"{{"rows": [{}], "meta": {{"a": 1, "b": 2, "c": 3, "d": 4}}}}"#,
+        numbers.join(",")
+    ))
+    .unwrap();
+    assert_eq!(
+        to_preview_string(&value, 3, 10),
+        r#"{
+  "meta": {
+    "a": 1,
+    "b": 2,
+    "c": 3,
+    … (1 more)
+  },
+  "rows": [
+    0,
+    1,
+    2,
+    … (997 more)
+  ]
+}"#
+    );
+
+    let value = parse(r#"[[1, 2, 3], {"x": [4, 5]}]"#).unwrap();
+    assert_eq!(
+        to_preview_string(&value, 1, 10),
+        "[\n  [\n    1,\n    … (2 more)\n  ],\n  … (1 more)\n]"
+    );
+    assert_eq!(to_preview_string(&value, 0, 10), "[\n  … (2 more)\n]");
+}
+
+#[test]
+fn test_preview_is_bounded() {
+    let long = "x".repeat(1_000_000);
+    let items: Vec<String> = (0..20).map(|_| format!(r#""{long}""#)).collect();
+    let value = parse(&format!("[{}]", items.join(","))).unwrap();
+    let preview = to_preview_string(&value, 5, 40);
+    assert!(preview.len() < 400, "{}", preview.len());
+    assert!(preview.ends_with("  … (15 more)\n]"));
+}
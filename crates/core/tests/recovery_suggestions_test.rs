@@ -0,0 +1,115 @@
+// this_file: crates/core/tests/recovery_suggestions_test.rs
+
+use vexy_json_core::{
+    analyze_error, error::RecoveryStrategy, parse, parse_with_options, ParserOptions,
+};
+
+fn strict_options() -> ParserOptions {
+    ParserOptions {
+        allow_comments: false,
+        allow_trailing_commas: false,
+        allow_unquoted_keys: false,
+        allow_single_quotes: false,
+        implicit_top_level: false,
+        newline_as_comma: false,
+        enable_repair: false,
+        ..Default::default()
+    }
+}
+
+/// Parses `input` strictly, applies the top suggestion and returns the fixed text.
+fn apply_top_fix(input: &str) -> (RecoveryStrategy, String) {
+    let error = parse_with_options(input, strict_options()).unwrap_err();
+    let suggestions = analyze_error(input, &error);
+    let top = suggestions
+        .first()
+        .unwrap_or_else(|| panic!("no suggestions for {input:?} ({error:?})"));
+    (top.strategy.clone(), top.apply(input))
+}
+
+#[test]
+fn test_add_closing_fix_parses() {
+    let (strategy, fixed) = apply_top_fix(r#"{"a": 1"#);
+    assert!(matches!(strategy, RecoveryStrategy::AddClosing { .. }));
+    assert!(parse(&fixed).is_ok(), "fixed text {fixed:?} should parse");
+
+    // Nested structures are closed innermost first
+    let (strategy, _) = apply_top_fix(r#"{"a": [1, 2"#);
+    assert!(matches!(
+        strategy,
+        RecoveryStrategy::AddClosing { delimiter: ']', .. }
+    ));
+
+    // A closer of the wrong type shuts the outer structure, so the inner
+    // one is closed before it
+    for (input, delimiter) in [(r#"{"a": [1, 2}"#, ']'), (r#"[{"a": 1]"#, '}')] {
+        let (strategy, fixed) = apply_top_fix(input);
+        assert_eq!(
+            strategy,
+            RecoveryStrategy::AddClosing {
+                position: input.len() - 1,
+                delimiter
+            }
+        );
+        assert!(
+            parse_with_options(&fixed, strict_options()).is_ok(),
+            "fixed text {fixed:?} should parse"
+        );
+    }
+}
+
+#[test]
+fn test_remove_trailing_comma_fix_parses() {
+    let (strategy, fixed) = apply_top_fix("[1, 2,]");
+    assert!(matches!(
+        strategy,
+        RecoveryStrategy::RemoveTrailingComma { .. }
+    ));
+    assert_eq!(fixed, "[1, 2]");
+    assert!(parse_with_options(&fixed, strict_options()).is_ok());
+}
+
+#[test]
+fn test_quote_key_fix_parses() {
+    let (strategy, fixed) = apply_top_fix("{name: 1}");
+    assert!(matches!(strategy, RecoveryStrategy::QuoteKey { .. }));
+    assert_eq!(fixed, r#"{"name": 1}"#);
+    assert!(parse_with_options(&fixed, strict_options()).is_ok());
+}
+
+#[test]
+fn test_replace_text_fix_parses() {
+    let (strategy, fixed) = apply_top_fix("{'a': 1}");
+    assert!(matches!(strategy, RecoveryStrategy::ReplaceText { .. }));
+    assert_eq!(fixed, r#"{"a": 1}"#);
+    assert!(parse_with_options(&fixed, strict_options()).is_ok());
+}
+
+#[test]
+fn test_insert_text_fix_parses() {
+    let (strategy, fixed) = apply_top_fix(r#"{"a" 1}"#);
+    assert!(matches!(strategy, RecoveryStrategy::InsertText { .. }));
+    assert!(parse_with_options(&fixed, strict_options()).is_ok());
+}
+
+#[test]
+fn test_add_comma_fix_parses() {
+    let (strategy, fixed) = apply_top_fix(r#"{"a": 1 "b": 2}"#);
+    assert!(matches!(strategy, RecoveryStrategy::AddComma { .. }));
+    assert!(parse_with_options(&fixed, strict_options()).is_ok());
+}
+
+#[test]
+fn test_suggestions_carry_metadata() {
+    let input = "[1, 2,]";
+    let error = parse_with_options(input, strict_options()).unwrap_err();
+    let suggestions = analyze_error(input, &error);
+
+    let top = &suggestions[0];
+    assert_eq!(top.description, "Remove trailing comma");
+    assert_eq!(top.confidence, top.strategy.confidence());
+    assert_eq!(&input[top.span.start..top.span.end], ",");
+    assert!(suggestions
+        .windows(2)
+        .all(|pair| pair[0].confidence >= pair[1].confidence));
+}
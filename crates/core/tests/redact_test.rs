@@ -0,0 +1,143 @@
+// this_file: crates/core/tests/redact_test.rs
+
+//! Values at JSONPath or JSON Pointer locations are replaced with a
+//! placeholder, and the rest of the document is left as it was.
+
+use vexy_json_core::ast::Value;
+use vexy_json_core::parse;
+use vexy_json_core::transform::redact;
+
+fn hidden() -> Value {
+    Value::String("[REDACTED]".into())
+}
+
+#[test]
+fn test_redact_user_password() {
+    let mut value = parse(
+        r#"{"user": {"name": "ann", "password": "hunter2", "roles": ["admin"]}, "password": "kept"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        redact(&mut value, &["$.user.password"], hidden()).unwrap(),
+        1
+    );
+
+    let expected = parse(
+        r#"{"user": {"name": "ann", "password": "[REDACTED]", "roles": ["admin"]}, "password": "kept"}"#,
+    )
+    .unwrap();
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn test_redact_secret_at_any_depth() {
+    let mut value = parse(
+        r#"{
+            "secret": 1,
+            "db": {"host": "x", "secret": {"key": "k", "secret": "inner"}},
+            "services": [{"name": "a", "secret": [1, 2]}, {"name": "b"}, "secret"]
+        }"#,
+    )
+    .unwrap();
+    // The secret inside a redacted secret is not counted on its own
+    assert_eq!(redact(&mut value, &["$..secret"], Value::Null).unwrap(), 3);
+
+    let expected = parse(
+        r#"{
+            "secret": null,
+            "db": {"host": "x", "secret": null},
+            "services": [{"name": "a", "secret": null}, {"name": "b"}, "secret"]
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn test_redact_below_any_depth() {
+    let mut value =
+        parse(r#"{"a": {"auth": {"token": "t1", "user": "u"}}, "b": [{"auth": {"token": "t2"}}]}"#)
+            .unwrap();
+    assert_eq!(redact(&mut value, &["$..auth.token"], hidden()).unwrap(), 2);
+    assert_eq!(value["a"]["auth"]["token"], hidden());
+    assert_eq!(value["a"]["auth"]["user"].as_str(), Some("u"));
+    assert_eq!(value["b"][0]["auth"]["token"], hidden());
+}
+
+#[test]
+fn test_redact_json_pointer() {
+    let mut value = parse(r#"{"users": [{"pass": 1}, {"pass": 2}], "a/b": {"~c": 3}}"#).unwrap();
+    let count = redact(&mut value, &["/users/1/pass", "/a~1b/~0c"], hidden()).unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(value["users"][0]["pass"].as_i64(), Some(1));
+    assert_eq!(value["users"][1]["pass"], hidden());
+    assert_eq!(value["a/b"]["~c"], hidden());
+
+    // Indexes are written without leading zeros
+    assert_eq!(
+        redact(&mut value, &["/users/00/pass"], hidden()).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_redact_wildcards_and_indexes() {
+    let mut value =
+        parse(r#"{"cards": [{"number": 1, "id": 1}, {"number": 2, "id": 2}]}"#).unwrap();
+    assert_eq!(
+        redact(
+            &mut value,
+            &["$.cards[*].number", "$.cards[1].id"],
+            hidden()
+        )
+        .unwrap(),
+        3
+    );
+    assert_eq!(value["cards"][0]["number"], hidden());
+    assert_eq!(value["cards"][0]["id"].as_i64(), Some(1));
+    assert_eq!(value["cards"][1]["id"], hidden());
+
+    let mut value = parse(r#"{"a.b": 1}"#).unwrap();
+    assert_eq!(redact(&mut value, &["$['a.b']"], hidden()).unwrap(), 1);
+    assert_eq!(value["a.b"], hidden());
+
+    let mut value = parse(r#"{"a": 1}"#).unwrap();
+    assert_eq!(redact(&mut value, &["$"], hidden()).unwrap(), 1);
+    assert_eq!(value, hidden());
+}
+
+#[test]
+fn test_missing_and_invalid_paths() {
+    let original = parse(r#"{"user": {"name": "ann"}, "list": [1]}"#).unwrap();
+
+    let mut value = original.clone();
+    let count = redact(
+        &mut value,
+        &["$.user.password", "$.list[3]", "$.user.name.first", "/nope"],
+        hidden(),
+    )
+    .unwrap();
+    assert_eq!(count, 0);
+    assert_eq!(value, original);
+
+    for invalid in ["user.password", "$.user[name]", "$.", "$..", "$[0"] {
+        let error = redact(&mut value, &["$.user.name", invalid], hidden()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("Custom error: Invalid path: {invalid}")
+        );
+        assert_eq!(value, original);
+    }
+}
+
+#[test]
+fn test_redact_quoted_keys() {
+    // Quoted keys may hold brackets, dots and escaped quotes
+    let mut value = parse(r#"{"a]b": 1, "a.b": 2, "it's": {"x": 3}, "a": {"b": 4}}"#).unwrap();
+    let paths = [r"$['a]b']", r#"$["a.b"]"#, r"$['it\'s'].x"];
+    assert_eq!(redact(&mut value, &paths, Value::Null).unwrap(), 3);
+
+    let expected =
+        parse(r#"{"a]b": null, "a.b": null, "it's": {"x": null}, "a": {"b": 4}}"#).unwrap();
+    assert_eq!(value, expected);
+}
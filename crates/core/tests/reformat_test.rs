@@ -0,0 +1,75 @@
+// this_file: crates/core/tests/reformat_test.rs
+
+//! Reformatting turns forgiving input into standard JSON, minified or
+//! indented, keeping the source's key order.
+
+use vexy_json_core::transform::{reformat, FormatStyle};
+
+const PRETTY: &str = r#"{
+  // Deployment settings
+  "name": "api",
+  "replicas": 3,
+  "ports": [
+    80,
+    443,
+  ],
+  "env": {
+    "LOG_LEVEL": "debug",
+    "EMPTY": "",
+  },
+  "sidecars": [],
+  "limits": {}
+}
+"#;
+
+const COMPACT: &str = r#"{"name":"api","replicas":3,"ports":[80,443],"env":{"LOG_LEVEL":"debug","EMPTY":""},"sidecars":[],"limits":{}}"#;
+
+#[test]
+fn test_minify_pretty_file() {
+    assert_eq!(reformat(PRETTY, FormatStyle::Minify).unwrap(), COMPACT);
+
+    let fixture = include_str!("fixtures/determinism.json");
+    let minified = reformat(fixture, FormatStyle::Minify).unwrap();
+    assert!(!minified.contains('\n'));
+    assert!(minified.starts_with(r#"{"service":"billing","version":3,"#));
+    assert_eq!(reformat(&minified, FormatStyle::Minify).unwrap(), minified);
+}
+
+#[test]
+fn test_beautify_compact_file() {
+    assert_eq!(
+        reformat(COMPACT, FormatStyle::Beautify { indent: 2 }).unwrap(),
+        r#"{
+  "name": "api",
+  "replicas": 3,
+  "ports": [
+    80,
+    443
+  ],
+  "env": {
+    "LOG_LEVEL": "debug",
+    "EMPTY": ""
+  },
+  "sidecars": [],
+  "limits": {}
+}"#
+    );
+
+    assert_eq!(
+        reformat("[1,{a:[]}]", FormatStyle::Beautify { indent: 4 }).unwrap(),
+        "[\n    1,\n    {\n        \"a\": []\n    }\n]"
+    );
+    assert_eq!(
+        reformat("\"x\"", FormatStyle::Beautify { indent: 2 }).unwrap(),
+        "\"x\""
+    );
+}
+
+#[test]
+fn test_round_trip_and_errors() {
+    let beautified = reformat(PRETTY, FormatStyle::Beautify { indent: 2 }).unwrap();
+    assert_eq!(reformat(&beautified, FormatStyle::Minify).unwrap(), COMPACT);
+
+    let error = reformat("{\"a\": @}", FormatStyle::Minify).unwrap_err();
+    assert_eq!(error.position(), Some(6));
+}
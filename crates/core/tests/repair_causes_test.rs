@@ -0,0 +1,101 @@
+// this_file: crates/core/tests/repair_causes_test.rs
+
+//! Each repair is paired with the parse error it fixed.
+
+use vexy_json_core::ast::Value;
+use vexy_json_core::error::Span;
+use vexy_json_core::repair::smart_repair;
+use vexy_json_core::{parse_with_fallback, EnhancedParseResult, Error, ParserOptions};
+
+/// Each repair as its replacement and position, with the span of its cause.
+fn causes(result: &EnhancedParseResult<Value>) -> Vec<(&str, usize, Option<Span>)> {
+    result
+        .repairs
+        .iter()
+        .map(|repair| {
+            let span = repair.cause_span();
+            (repair.replacement.as_str(), repair.position, span)
+        })
+        .collect()
+}
+
+#[test]
+fn test_two_repairs_reference_their_own_errors() {
+    // An en dash for a minus sign, and a missing closing bracket
+    let input = "[–1, 2";
+    let result = parse_with_fallback(input, ParserOptions::default());
+    assert!(result.is_success());
+    assert_eq!(result.repairs[0].cause, Some(Error::UnexpectedChar('–', 1)));
+    assert_eq!(
+        causes(&result),
+        [
+            ("-", 1, Some(Span::single(1))),
+            ("]", input.len(), Some(Span::single(input.len()))),
+        ]
+    );
+}
+
+#[test]
+fn test_curly_quotes() {
+    // Positions refer to the original input, where each curly quote is three
+    // bytes long
+    let input = "[“a”, “b”";
+    let result = parse_with_fallback(input, ParserOptions::default());
+    assert!(result.is_success());
+    let errors: Vec<_> = result.repairs.iter().map(|r| r.cause.clone()).collect();
+    assert_eq!(errors[0], Some(Error::UnexpectedChar('“', 1)));
+    // Without its closing quote, the string opened at 1 runs on
+    assert_eq!(errors[1], Some(Error::UnterminatedString(1)));
+    assert_eq!(errors[2], Some(Error::UnexpectedChar('“', 10)));
+    assert_eq!(errors[3], Some(Error::UnterminatedString(10)));
+    assert_eq!(
+        causes(&result),
+        [
+            ("\"", 1, Some(Span::single(1))),
+            ("\"", 5, Some(Span::single(1))),
+            ("\"", 10, Some(Span::single(10))),
+            ("\"", 14, Some(Span::single(10))),
+            ("]", 17, Some(Span::single(17))),
+        ]
+    );
+}
+
+#[test]
+fn test_wrong_closing_bracket() {
+    // Only the repair that made the input parse is reported
+    let input = r#"{"a": [1, 2}"#;
+    let result = parse_with_fallback(input, ParserOptions::default());
+    assert!(result.is_success());
+    assert_eq!(causes(&result), [("]", 11, Some(Span::single(11)))]);
+}
+
+#[test]
+fn test_smart_repairs() {
+    let input = r#"{"a" 1, b {"c" [true]}}"#;
+    let result = smart_repair(input, ParserOptions::default());
+    // Each missing ':' is found at the value after its key
+    assert_eq!(
+        causes(&result),
+        [
+            (":", 4, Some(Span::single(5))),
+            (":", 9, Some(Span::single(10))),
+            (":", 14, Some(Span::single(15))),
+        ]
+    );
+}
+
+#[test]
+fn test_punctuation_the_parser_accepts_is_kept() {
+    // A no-break space is whitespace to the parser, so only the missing
+    // bracket is repaired
+    let input = "[1,\u{a0}2";
+    let result = parse_with_fallback(input, ParserOptions::default());
+    assert_eq!(
+        result.value,
+        parse_with_fallback("[1, 2]", ParserOptions::default()).value
+    );
+    assert_eq!(
+        causes(&result),
+        [("]", input.len(), Some(Span::single(input.len())))]
+    );
+}
@@ -0,0 +1,134 @@
+// this_file: crates/core/tests/serde_value_test.rs
+
+//! Values serialize as the JSON they stand for, so they can be fields of
+//! serde structs and round-trip through self-describing formats. Formats
+//! that are not self-describing, such as bincode, cannot read them back.
+
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use vexy_json_core::ast::{Number, Value};
+use vexy_json_core::parse;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    extra: Value,
+    limit: Option<Value>,
+}
+
+const DOCUMENT: &str = r#"{
+    "name": "billing",
+    "replicas": 3,
+    "ratio": -0.25,
+    "tags": ["a", "é", ""],
+    "nested": {"empty": {}, "list": [[], null, true, false]},
+    "min": -9223372036854775808
+}"#;
+
+fn document() -> Value {
+    parse(DOCUMENT).unwrap()
+}
+
+#[test]
+fn test_value_field_in_serde_struct() {
+    let config = Config {
+        name: "api".to_string(),
+        extra: parse("{b: [1, 2.5], a: null}").unwrap(),
+        limit: None,
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(
+        json,
+        r#"{"name":"api","extra":{"a":null,"b":[1,2.5]},"limit":null}"#
+    );
+    assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+    let config: Config =
+        serde_json::from_str(r#"{"name": "x", "extra": "text", "limit": 10}"#).unwrap();
+    assert_eq!(config.extra, Value::String("text".to_string()));
+    assert_eq!(config.limit, Some(Value::Number(Number::Integer(10))));
+}
+
+#[test]
+fn test_round_trip_through_serde_json() {
+    let value = document();
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<Value>(&json).unwrap(), value);
+    // The same text the parser reads, not an enum with tagged variants
+    assert_eq!(parse(&json).unwrap(), value);
+    assert!(json.starts_with(r#"{"min":-9223372036854775808,"name":"billing","#));
+
+    let through_json: serde_json::Value = serde_json::to_value(&value).unwrap();
+    assert_eq!(through_json["nested"]["list"][2], serde_json::json!(true));
+    assert_eq!(
+        serde_json::from_value::<Value>(through_json).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn test_round_trip_through_cbor() {
+    let value = document();
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&value, &mut bytes).unwrap();
+    assert_eq!(
+        ciborium::from_reader::<Value, _>(&bytes[..]).unwrap(),
+        value
+    );
+
+    // CBOR holds integers beyond the range of i64
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&u64::MAX, &mut bytes).unwrap();
+    assert_eq!(
+        ciborium::from_reader::<Value, _>(&bytes[..]).unwrap(),
+        Value::Number(Number::Float(u64::MAX as f64))
+    );
+}
+
+#[test]
+fn test_bincode_cannot_read_values() {
+    // Bincode writes a value, but cannot say which kind comes back
+    let bytes = bincode::serialize(&document()).unwrap();
+    let error = bincode::deserialize::<Value>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("deserialize_any"), "{error}");
+}
+
+#[test]
+fn test_numbers() {
+    let value: Value =
+        serde_json::from_str("[9223372036854775807, 18446744073709551615, -1, 1e3]").unwrap();
+    assert_eq!(
+        value,
+        Value::Array(vec![
+            Value::Number(Number::Integer(i64::MAX)),
+            // Past i64::MAX, as the parser reads it
+            Value::Number(Number::Float(18446744073709551615.0)),
+            Value::Number(Number::Integer(-1)),
+            Value::Number(Number::Float(1000.0)),
+        ])
+    );
+    assert_eq!(
+        value,
+        parse("[9223372036854775807, 18446744073709551615, -1, 1e3]").unwrap()
+    );
+
+    assert_eq!(
+        serde_json::from_str::<Number>("42").unwrap(),
+        Number::Integer(42)
+    );
+    let error = serde_json::from_str::<Number>(r#""42""#).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with(r#"invalid type: string "42", expected a number"#),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_map_keys_must_be_strings() {
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&std::collections::BTreeMap::from([(1, 2)]), &mut bytes).unwrap();
+    assert!(ciborium::from_reader::<Value, _>(&bytes[..]).is_err());
+}
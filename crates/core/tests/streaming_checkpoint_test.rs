@@ -0,0 +1,122 @@
+// this_file: crates/core/tests/streaming_checkpoint_test.rs
+
+#![cfg(feature = "serde")]
+
+use std::io::{Cursor, Read};
+use vexy_json_core::streaming::{
+    BufferedStreamingConfig, BufferedStreamingParser, NdJsonParser, OpenContainer, StreamCheckpoint,
+};
+use vexy_json_core::ParserOptions;
+
+const TRANSACTIONS: &str = include_str!("fixtures/transactions.ndjson");
+
+/// Sends a checkpoint through JSON, as a job saving it between runs would.
+fn persisted(checkpoint: &StreamCheckpoint) -> StreamCheckpoint {
+    serde_json::from_str(&serde_json::to_string(checkpoint).unwrap()).unwrap()
+}
+
+#[test]
+fn test_ndjson_resumes_from_checkpoint() {
+    let mut single_pass = NdJsonParser::new();
+    let mut expected = single_pass.feed(TRANSACTIONS).unwrap();
+    expected.extend(single_pass.finish().unwrap());
+
+    for split in (0..=TRANSACTIONS.len()).filter(|&i| TRANSACTIONS.is_char_boundary(i)) {
+        let mut first = NdJsonParser::new();
+        let mut values = first.feed(&TRANSACTIONS[..split]).unwrap();
+        let checkpoint = persisted(&first.checkpoint());
+        assert!(checkpoint.offset <= split);
+        assert_eq!(
+            checkpoint.line,
+            TRANSACTIONS[..checkpoint.offset].matches('\n').count() + 1
+        );
+
+        let mut reader = Cursor::new(TRANSACTIONS);
+        let mut second =
+            NdJsonParser::resume_from(&mut reader, &checkpoint, ParserOptions::default()).unwrap();
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        values.extend(second.feed(&rest).unwrap());
+        values.extend(second.finish().unwrap());
+        assert_eq!(values, expected, "split at {split}");
+    }
+}
+
+#[test]
+fn test_ndjson_errors_keep_line_numbers_after_resuming() {
+    let input = format!("{TRANSACTIONS}{{\"id\": @}}\n");
+    let mut first = NdJsonParser::new();
+    first.feed(&input[..input.len() / 2]).unwrap();
+
+    let mut reader = Cursor::new(input.as_str());
+    let checkpoint = persisted(&first.checkpoint());
+    let mut second =
+        NdJsonParser::resume_from(&mut reader, &checkpoint, ParserOptions::default()).unwrap();
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest).unwrap();
+    let error = second.feed(&rest).unwrap_err();
+    assert!(error.to_string().contains("line 7:"), "{error}");
+}
+
+#[test]
+fn test_buffered_resumes_from_checkpoint() {
+    let input = "{\n  \"name\": \"checkpoints\",\n  /* spread\n     over lines */\n  \
+                 \"items\": [1, [2.5, {\"deep\": true}],\n    null],\n  \"end\": -3\n}\n[\"é\", {}]";
+    let expected = BufferedStreamingParser::new(Cursor::new(input))
+        .collect_events()
+        .unwrap();
+
+    let mut deepest = Vec::new();
+    for taken in 0..=expected.len() {
+        let mut first = BufferedStreamingParser::new(Cursor::new(input));
+        let mut events = Vec::new();
+        for _ in 0..taken {
+            events.push(first.next_event().unwrap().unwrap());
+        }
+        let checkpoint = persisted(&first.checkpoint());
+        assert_eq!(
+            checkpoint.line,
+            input[..checkpoint.offset].matches('\n').count() + 1
+        );
+        if checkpoint.containers.len() > deepest.len() {
+            deepest = checkpoint.containers.clone();
+        }
+
+        let mut second = BufferedStreamingParser::resume_from(
+            Cursor::new(input),
+            &checkpoint,
+            BufferedStreamingConfig::default(),
+        )
+        .unwrap();
+        events.extend(second.collect_events().unwrap());
+        assert_eq!(events, expected, "after {taken} events");
+    }
+    assert_eq!(
+        deepest,
+        [
+            OpenContainer::Object,
+            OpenContainer::Array,
+            OpenContainer::Array,
+            OpenContainer::Object,
+        ]
+    );
+}
+
+#[test]
+fn test_buffered_spans_stay_absolute_after_resuming() {
+    let input = r#"[10, "twenty", 30]"#;
+    let mut first = BufferedStreamingParser::new(Cursor::new(input));
+    first.next_event().unwrap();
+    first.next_event().unwrap();
+    let checkpoint = first.checkpoint();
+    assert_eq!(checkpoint.offset, 3);
+
+    let mut second = BufferedStreamingParser::resume_from(
+        Cursor::new(input),
+        &checkpoint,
+        BufferedStreamingConfig::default(),
+    )
+    .unwrap();
+    let (_, span) = second.next_event_with_span().unwrap().unwrap();
+    assert_eq!(&input[span.start..span.end], "\"twenty\"");
+}
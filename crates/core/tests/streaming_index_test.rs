@@ -0,0 +1,160 @@
+// this_file: crates/core/tests/streaming_index_test.rs
+
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use vexy_json_core::ast::JsonPath;
+use vexy_json_core::streaming::{build_index, IndexSpec};
+use vexy_json_core::{parse, ParserOptions};
+
+/// A reader that counts the bytes read through it.
+struct Counting<R> {
+    inner: R,
+    read: usize,
+}
+
+impl<R: Read> Read for Counting<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n;
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for Counting<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+/// A reader that returns at most `chunk` bytes per read.
+struct Chunked<'a> {
+    input: &'a [u8],
+    chunk: usize,
+}
+
+impl Read for Chunked<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.chunk.min(buf.len()).min(self.input.len());
+        buf[..n].copy_from_slice(&self.input[..n]);
+        self.input = &self.input[n..];
+        Ok(n)
+    }
+}
+
+fn path(path: &str) -> JsonPath {
+    path.parse().unwrap()
+}
+
+fn items_spec() -> IndexSpec {
+    IndexSpec::new().pattern("$.items[*]".parse().unwrap())
+}
+
+#[test]
+fn test_random_access_reads_only_the_element() {
+    let items: Vec<String> = (0..100_000)
+        .map(|i| {
+            format!(
+                "{{\"id\": {i}, \"name\": \"item {i}\", \"tags\": [\"t{}\"]}}",
+                i % 10
+            )
+        })
+        .collect();
+    let document = format!("{{\"count\": 100000, \"items\": [{}]}}", items.join(",\n"));
+
+    let index = build_index(
+        Cursor::new(document.as_bytes()),
+        ParserOptions::default(),
+        &items_spec(),
+    )
+    .unwrap();
+    assert_eq!(index.len(), 100_000);
+
+    let mut reader = Counting {
+        inner: Cursor::new(document.as_bytes()),
+        read: 0,
+    };
+    for i in [0, 1, 12_345, 99_999] {
+        reader.read = 0;
+        let value = index
+            .read_value(&mut reader, &path(&format!("$.items[{i}]")))
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, parse(&items[i]).unwrap());
+        assert_eq!(reader.read, items[i].len());
+    }
+    assert!(document.len() > 1000 * items[12_345].len());
+    assert_eq!(
+        index
+            .read_value(&mut reader, &path("$.items[100000]"))
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        index.read_value(&mut reader, &path("$.count")).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_spans_do_not_depend_on_read_boundaries() {
+    let document =
+        "{\"items\": [[], {}, [[1], {\"a\": [2]}], \"s\", 3.5, {\"b\": {}}], \"after\": [4]}";
+    let spec = || {
+        items_spec()
+            .pattern("$.items[2][*]".parse().unwrap())
+            .pattern("$.after".parse().unwrap())
+    };
+    let expected = build_index(Cursor::new(document), ParserOptions::default(), &spec()).unwrap();
+    assert_eq!(expected.len(), 9);
+
+    for chunk in 1..=document.len() {
+        let reader = Chunked {
+            input: document.as_bytes(),
+            chunk,
+        };
+        let index = build_index(reader, ParserOptions::default(), &spec()).unwrap();
+        for p in [
+            "$.items[0]",
+            "$.items[1]",
+            "$.items[2]",
+            "$.items[2][1]",
+            "$.items[5]",
+            "$.after",
+        ] {
+            assert_eq!(
+                index.span(&path(p)),
+                expected.span(&path(p)),
+                "{p} in chunks of {chunk}"
+            );
+        }
+    }
+    let span = expected.span(&path("$.items[2]")).unwrap();
+    assert_eq!(&document[span.start..span.end], "[[1], {\"a\": [2]}]");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_index_saved_and_loaded() {
+    use vexy_json_core::ast::Value;
+    use vexy_json_core::streaming::JsonIndex;
+
+    let document = "{\"items\": [\"zero\", {\"one\": 1}]}";
+    let index = build_index(
+        Cursor::new(document),
+        ParserOptions::default(),
+        &items_spec(),
+    )
+    .unwrap();
+    let saved = serde_json::to_string(&index).unwrap();
+    let loaded: JsonIndex = serde_json::from_str(&saved).unwrap();
+
+    assert_eq!(
+        loaded
+            .value_in(document.as_bytes(), &path("$.items[0]"))
+            .unwrap(),
+        Some(Value::String("zero".to_string()))
+    );
+    assert_eq!(
+        loaded.span(&path("$.items[1]")),
+        index.span(&path("$.items[1]"))
+    );
+}
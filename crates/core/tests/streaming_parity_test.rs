@@ -0,0 +1,204 @@
+// this_file: crates/core/tests/streaming_parity_test.rs
+
+//! Values built from streaming events match what the batch parser returns
+//! for the same forgiving input.
+
+use vexy_json_core::ast::Value;
+use vexy_json_core::error::{Error, Result};
+use vexy_json_core::streaming::{StreamingEvent, StreamingParser, StreamingValueBuilder};
+use vexy_json_core::{parse, parse_with_options, Limits, ParserOptions};
+
+const CORPUS: &[&str] = &[
+    r#"{"a": 1, "b": [true, false, null], "c": {"d": "e"}}"#,
+    r#"[1, 2, 3,]"#,
+    r#"{"a": 1, "b": 2,}"#,
+    r#"{"a": [1, {"b": 2,},], "c": [],}"#,
+    "[1, 2, 3,\n]",
+    "{'single': 'quotes', \"mixed\": 'it\\'s', 'x': \"y'z\"}",
+    "['a', 'b\\n', '\\u00e9', '']",
+    "{unquoted: 1, _under_score: 'two', camelCase2: [3]}",
+    "{\n  \"a\": 1\n  \"b\": 2\n}",
+    "[\n  1\n  2\n\n  3\n]",
+    "{\n  name: 'config',\n  items: [\n    'one'\n    'two',\n  ],\n}",
+    "{\"a\": 1 // comment\n \"b\": /* inline */ 2}",
+    "{\n  # hash comment\n  key: 'value'\n}",
+    "[1,,2]",
+    "[,1]",
+    "{,'a': 1,,}",
+    "[1\n,2]",
+    "[\n  1\n  , 2\n  ,\n]",
+    "{\n  \"a\": 1\n  , \"b\": 2\n}",
+    r#"{"nested": {"deep": [[[]], {}, [{"x": -1.5e3}]]}}"#,
+    "  'top-level string'  ",
+    "42\n",
+];
+
+/// Parses `input` with the streaming parser, fed `chunk` bytes at a time
+/// (rounded up to a character boundary), and builds the value its events
+/// describe.
+fn stream(input: &str, options: &ParserOptions, chunk: usize) -> Result<Value> {
+    let mut parser = StreamingParser::with_options(options.clone());
+    let mut rest = input;
+    while !rest.is_empty() {
+        let mut end = chunk.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        parser.feed(&rest[..end])?;
+        rest = &rest[end..];
+    }
+    parser.finish()?;
+
+    let mut builder = StreamingValueBuilder::new();
+    while let Some(event) = parser.next_event()? {
+        if event == StreamingEvent::EndOfInput {
+            break;
+        }
+        builder.process_event(event)?;
+    }
+    Ok(builder.finish()?.expect("a value"))
+}
+
+#[test]
+fn test_forgiving_corpus_matches_batch_parse() {
+    let options = ParserOptions::default();
+    for input in CORPUS {
+        let expected = parse(input).unwrap_or_else(|e| panic!("{input:?}: {e}"));
+        for chunk in [1, 3, input.len()] {
+            let streamed = stream(input, &options, chunk)
+                .unwrap_or_else(|e| panic!("{input:?} in chunks of {chunk}: {e}"));
+            assert_eq!(streamed, expected, "{input:?} in chunks of {chunk}");
+        }
+    }
+}
+
+#[test]
+fn test_disabled_features_are_errors() {
+    let cases = [
+        (
+            "[1, 2,]",
+            ParserOptions {
+                allow_trailing_commas: false,
+                ..Default::default()
+            },
+        ),
+        (
+            r#"{"a": 1,}"#,
+            ParserOptions {
+                allow_trailing_commas: false,
+                ..Default::default()
+            },
+        ),
+        (
+            "{'a': 1}",
+            ParserOptions {
+                allow_single_quotes: false,
+                ..Default::default()
+            },
+        ),
+        (
+            "{\"a\": 1\n\"b\": 2}",
+            ParserOptions {
+                newline_as_comma: false,
+                ..Default::default()
+            },
+        ),
+        (
+            "[1\n2]",
+            ParserOptions {
+                newline_as_comma: false,
+                ..Default::default()
+            },
+        ),
+    ];
+    for (input, options) in cases {
+        let options = ParserOptions {
+            enable_repair: false,
+            ..options
+        };
+        assert!(
+            parse_with_options(input, options.clone()).is_err(),
+            "{input:?}"
+        );
+        assert!(stream(input, &options, input.len()).is_err(), "{input:?}");
+    }
+
+    let options = ParserOptions {
+        allow_trailing_commas: false,
+        ..Default::default()
+    };
+    assert_eq!(stream("[1, 2,]", &options, 7), Err(Error::TrailingComma(5)));
+    // A line break before the closing bracket is not a trailing comma
+    assert_eq!(
+        stream("[\n  1,\n  2\n]", &options, 1).unwrap(),
+        parse("[1, 2]").unwrap()
+    );
+}
+
+#[test]
+fn test_implicit_top_level_is_unsupported() {
+    for input in ["a: 1", "\"a\": 1", "1, 2", "1\n2", "{}, {}"] {
+        assert!(parse(input).is_ok(), "{input:?}");
+        let error = stream(input, &ParserOptions::default(), input.len()).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Implicit top-level values are not supported"),
+            "{input:?}: {error}"
+        );
+    }
+
+    // Without implicit_top_level, the same input is an ordinary syntax error
+    let options = ParserOptions {
+        implicit_top_level: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        stream("1, 2", &options, 4),
+        Err(Error::Expected {
+            expected: "end of input".to_string(),
+            found: "Comma".to_string(),
+            position: 1,
+        })
+    );
+}
+
+#[test]
+fn test_builder_enforces_limits_as_parse_does() {
+    let options = ParserOptions {
+        limits: Limits {
+            max_array_len: Some(3),
+            max_object_members: Some(2),
+            max_key_len: Some(4),
+            max_string_len: Some(5),
+        },
+        ..Default::default()
+    };
+    let build = |input: &str| -> Result<Value> {
+        let mut parser = StreamingParser::with_options(options.clone());
+        parser.feed(input)?;
+        parser.finish()?;
+        let mut builder = StreamingValueBuilder::with_limits(options.limits);
+        while let Some((event, span)) = parser.next_event_with_span()? {
+            if event == StreamingEvent::EndOfInput {
+                break;
+            }
+            builder.process_event_with_span(event, span)?;
+        }
+        Ok(builder.finish()?.expect("a value"))
+    };
+
+    let within = "{a: [1, [2, 3, {}]], b: 'abcde'}";
+    assert_eq!(build(within).unwrap(), parse(within).unwrap());
+
+    for input in [
+        "{a: [1, [2, 3, 4, 5]]}",
+        "{a: [1, [2, 3, [4], 5]]}",
+        "[{a: 1, b: 2, c: 3}]",
+        "[{a: 1, longer: 2}]",
+        "{a: {b: ['abcdef']}}",
+    ] {
+        let expected = parse_with_options(input, options.clone()).unwrap_err();
+        assert_eq!(build(input).unwrap_err(), expected, "{input:?}");
+    }
+}
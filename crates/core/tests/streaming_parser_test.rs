@@ -1,5 +1,7 @@
 // this_file: tests/streaming_parser_test.rs
 
+#![allow(clippy::field_reassign_with_default)]
+
 use vexy_json_core::streaming::{StreamingParser, StreamingEvent, StreamingValueBuilder};
 use vexy_json_core::parser::ParserOptions;
 use vexy_json_core::ast::{Value, Number};
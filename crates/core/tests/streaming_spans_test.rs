@@ -0,0 +1,98 @@
+// this_file: crates/core/tests/streaming_spans_test.rs
+
+//! Tests for byte offsets reported with streaming events
+
+use std::io::{self, Read};
+use vexy_json_core::error::Span;
+use vexy_json_core::streaming::{BufferedStreamingParser, StreamingEvent, StreamingParser};
+
+const DOCUMENT: &str = r#"{"name": "Zoë", "tags": ["a", "b"], "n": -12.5e3, "ok": true, "nil": null, /* a comment */ "nested": {"deep": [1, {"x": "y"}]}}"#;
+
+/// Reader that returns at most `chunk` bytes per read, splitting characters
+struct ChunkedReader<'a> {
+    data: &'a [u8],
+    chunk: usize,
+}
+
+impl Read for ChunkedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.chunk.min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+fn collect_with_spans(parser: &mut StreamingParser) -> Vec<(StreamingEvent, Span)> {
+    let mut events = Vec::new();
+    while let Some((event, span)) = parser.next_event_with_span().unwrap() {
+        if event == StreamingEvent::EndOfInput {
+            break;
+        }
+        events.push((event, span));
+    }
+    events
+}
+
+fn parse_in_chunks(input: &str, chunk: usize) -> Vec<(StreamingEvent, Span)> {
+    let mut parser = StreamingParser::new();
+    let mut rest = input;
+    while !rest.is_empty() {
+        // Round up to a character boundary, as `feed` takes whole characters
+        let mut end = chunk.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end += 1;
+        }
+        parser.feed(&rest[..end]).unwrap();
+        rest = &rest[end..];
+    }
+    parser.finish().unwrap();
+    collect_with_spans(&mut parser)
+}
+
+#[test]
+fn test_streaming_spans_cover_tokens() {
+    let events = parse_in_chunks(DOCUMENT, DOCUMENT.len());
+    let texts: Vec<&str> = events
+        .iter()
+        .map(|(_, span)| &DOCUMENT[span.start..span.end])
+        .collect();
+    assert_eq!(
+        texts.join(" "),
+        r#"{ "name" "Zoë" "tags" [ "a" "b" ] "n" -12.5e3 "ok" true "nil" null "nested" { "deep" [ 1 { "x" "y" } ] } }"#
+    );
+    assert_eq!(events[2].0, StreamingEvent::String("Zoë".to_string()));
+}
+
+#[test]
+fn test_streaming_spans_are_absolute_across_chunks() {
+    let one_shot = parse_in_chunks(DOCUMENT, DOCUMENT.len());
+    assert_eq!(parse_in_chunks(DOCUMENT, 7), one_shot);
+    assert_eq!(parse_in_chunks(DOCUMENT, 1), one_shot);
+
+    let mut parser = StreamingParser::new();
+    parser.feed(DOCUMENT).unwrap();
+    parser.finish().unwrap();
+    collect_with_spans(&mut parser);
+    let (event, span) = parser.next_event_with_span().unwrap().unwrap();
+    assert_eq!(event, StreamingEvent::EndOfInput);
+    assert_eq!(span, Span::new(DOCUMENT.len(), DOCUMENT.len()));
+}
+
+#[test]
+fn test_buffered_spans_match_streaming_parser() {
+    let expected = parse_in_chunks(DOCUMENT, DOCUMENT.len());
+
+    for chunk in [7, 1, DOCUMENT.len()] {
+        let reader = ChunkedReader {
+            data: DOCUMENT.as_bytes(),
+            chunk,
+        };
+        let mut parser = BufferedStreamingParser::new(reader);
+        let mut events = Vec::new();
+        while let Some(event) = parser.next_event_with_span().unwrap() {
+            events.push(event);
+        }
+        assert_eq!(events, expected, "chunk size {chunk}");
+    }
+}
@@ -0,0 +1,115 @@
+// this_file: crates/core/tests/streaming_validate_test.rs
+
+use std::io::Read;
+use vexy_json_core::streaming::validate_stream;
+use vexy_json_core::{Error, ParserOptions};
+
+/// Generates a large document row by row, so it is never held in memory,
+/// handing out at most `read_size` bytes per read.
+struct Rows {
+    rows: usize,
+    /// Row whose array is closed with `}` instead of `]`
+    broken: Option<usize>,
+    next: usize,
+    pending: Vec<u8>,
+    read_size: usize,
+}
+
+impl Rows {
+    fn new(rows: usize, broken: Option<usize>, read_size: usize) -> Self {
+        Self {
+            rows,
+            broken,
+            next: 0,
+            pending: r#"{"meta": {"source": "générateur"}, "rows": ["#.as_bytes().to_vec(),
+            read_size,
+        }
+    }
+
+    fn row(i: usize, broken: bool) -> String {
+        let close = if broken { '}' } else { ']' };
+        let separator = if i == 0 { "" } else { ", " };
+        format!("{separator}{{\"id\": {i}, \"name\": \"ligne-{i}-é\", \"values\": [{i}, 1.5e3, null{close}}}")
+    }
+}
+
+impl Read for Rows {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pending.is_empty() && self.next <= self.rows {
+            if self.next < self.rows {
+                let broken = self.broken == Some(self.next);
+                self.pending = Self::row(self.next, broken).into_bytes();
+            } else {
+                self.pending = b"]}".to_vec();
+            }
+            self.next += 1;
+        }
+        let n = self.pending.len().min(buf.len()).min(self.read_size);
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_valid_large_document() {
+    let rows = Rows::new(100_000, None, usize::MAX);
+    validate_stream(rows, ParserOptions::default()).unwrap();
+}
+
+#[test]
+fn test_reports_position_of_error_deep_in_large_document() {
+    let broken = 87_654;
+    let mut prefix = Vec::new();
+    Rows::new(broken, None, usize::MAX)
+        .read_to_end(&mut prefix)
+        .unwrap();
+    // Everything before the broken row, without the closing `]}`
+    prefix.truncate(prefix.len() - 2);
+    let row = Rows::row(broken, true);
+    let expected = prefix.len() + row.find("null}").unwrap() + "null".len();
+
+    let rows = Rows::new(100_000, Some(broken), usize::MAX);
+    let error = validate_stream(rows, ParserOptions::default()).unwrap_err();
+    assert!(
+        matches!(&error, Error::Expected { position, .. } if *position == expected),
+        "{error:?}, expected position {expected}"
+    );
+}
+
+#[test]
+fn test_small_reads_split_characters() {
+    for read_size in [1, 2, 3, 7] {
+        validate_stream(Rows::new(50, None, read_size), ParserOptions::default()).unwrap();
+        let error = validate_stream(Rows::new(50, Some(42), read_size), ParserOptions::default())
+            .unwrap_err();
+        assert_eq!(
+            error.position(),
+            validate_stream(
+                Rows::new(50, Some(42), usize::MAX),
+                ParserOptions::default()
+            )
+            .unwrap_err()
+            .position()
+        );
+    }
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let mut input = br#"{"a": "ok", "b": ""#.to_vec();
+    let position = input.len();
+    input.extend_from_slice(b"\xff\"}");
+    assert_eq!(
+        validate_stream(input.as_slice(), ParserOptions::default()).unwrap_err(),
+        Error::InvalidUtf8(position)
+    );
+
+    // A character cut off by the end of the input
+    let truncated = "[\"é".as_bytes();
+    let truncated = &truncated[..truncated.len() - 1];
+    assert_eq!(
+        validate_stream(truncated, ParserOptions::default()).unwrap_err(),
+        Error::InvalidUtf8(2)
+    );
+}
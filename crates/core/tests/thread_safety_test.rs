@@ -0,0 +1,66 @@
+// this_file: crates/core/tests/thread_safety_test.rs
+
+//! Values, options and parse results can be shared across threads.
+
+use rayon::prelude::*;
+use vexy_json_core::ast::{Number, Value};
+use vexy_json_core::format::{to_compact_string, KeyOrder};
+use vexy_json_core::lazy::LazyValue;
+use vexy_json_core::streaming::{StreamCheckpoint, StreamingEvent};
+use vexy_json_core::{
+    parse_with_options, CompatWarning, EnhancedParseResult, Error, ParserOptions, RepairAction,
+    Warning,
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_public_types_are_send_and_sync() {
+    assert_send_sync::<Value>();
+    assert_send_sync::<Number>();
+    assert_send_sync::<ParserOptions>();
+    assert_send_sync::<Error>();
+    assert_send_sync::<EnhancedParseResult<Value>>();
+    assert_send_sync::<RepairAction>();
+    assert_send_sync::<Warning>();
+    assert_send_sync::<CompatWarning>();
+    assert_send_sync::<StreamingEvent>();
+    assert_send_sync::<StreamCheckpoint>();
+    assert_send_sync::<LazyValue>();
+}
+
+#[test]
+fn test_values_cross_a_thread_pool() {
+    let documents: Vec<String> = (0..1000)
+        .map(|i| {
+            format!(
+                "{{id: {i}, tags: ['t{}', null,], nested: {{even: {}}}}}",
+                i % 7,
+                i % 2 == 0
+            )
+        })
+        .collect();
+    let options = ParserOptions::default();
+
+    let values: Vec<Value> = documents
+        .par_iter()
+        .map(|document| parse_with_options(document, options.clone()).unwrap())
+        .collect();
+
+    assert_eq!(values.len(), documents.len());
+    let order = KeyOrder::from_source("{id: 0, tags: [], nested: {}}");
+    let serialized: Vec<String> = values
+        .par_iter()
+        .map(|value| to_compact_string(value, &order))
+        .collect();
+    for (i, json) in serialized.iter().enumerate() {
+        assert_eq!(
+            *json,
+            format!(
+                "{{\"id\":{i},\"tags\":[\"t{}\",null],\"nested\":{{\"even\":{}}}}}",
+                i % 7,
+                i % 2 == 0
+            )
+        );
+    }
+}
@@ -100,6 +100,13 @@ def main():
         results.append(parser.parse(json_str))
     print(f"Parsed multiple inputs: {results}")
 
+    # Example 11: Seeing what was repaired
+    print("\n11. Repairing a truncated document:")
+    result = parser.parse_detailed('{"items": [1, 2, 3')
+    print(f"Output: {result.data} (parsed by the {result.tier} tier)")
+    for repair in result.repairs:
+        print(f"  {repair.repair_type} at {repair.position}: {repair.description}")
+
 
 if __name__ == "__main__":
     main()
@@ -76,7 +76,8 @@ def load_config(filename=None):
     """Load configuration from file or use default template"""
     if filename:
         try:
-            config = vexy_json.load(filename)
+            with open(filename, "r", encoding="utf-8") as f:
+                config = vexy_json.load(f)
             print(f"Loaded configuration from: {filename}")
         except Exception as e:
             print(f"Error loading config file: {e}")
@@ -162,7 +163,8 @@ def main():
     # Example: Save configuration
     if not config_file:
         output_file = "config_example.json"
-        vexy_json.dump(config, output_file, indent=2)
+        with open(output_file, "w", encoding="utf-8") as f:
+            vexy_json.dump(config, f, indent=2)
         print(f"\nSaved example configuration to: {output_file}")
 
 
@@ -16,19 +16,32 @@ Features:
 - Streaming parser for large files
 - pandas DataFrame integration
 - JSON repair functionality
+
+Code written against the older `bindings/python` package can import
+`vexy_json.compat` in its place; see that module.
 """
 
 from ._vexy_json import (
-    parse_json as parse,
-    parse_with_options_py as parse_with_options,
+    parse,
+    parse_with_options,
+    parse_into_json_bytes,
+    parse_into,
     is_valid,
+    format_error,
+    stats,
     dumps,
     load,
     dump,
     loads_numpy,
     loads_numpy_zerocopy,
     loads_dataframe,
+    Options,
+    Parser,
+    ParseResult,
+    Repair,
     StreamingParser,
+    JSONDecodeError,
+    ParseError,
     __version__,
     __author__,
     __description__,
@@ -41,14 +54,24 @@ __all__ = [
     "parse",
     "loads",
     "parse_with_options",
+    "parse_into_json_bytes",
+    "parse_into",
     "is_valid",
+    "format_error",
+    "stats",
     "dumps",
     "load",
     "dump",
     "loads_numpy",
     "loads_numpy_zerocopy",
     "loads_dataframe",
+    "Options",
+    "Parser",
+    "ParseResult",
+    "Repair",
     "StreamingParser",
+    "JSONDecodeError",
+    "ParseError",
     "__version__",
     "__author__",
     "__description__",
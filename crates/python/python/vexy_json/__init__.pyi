@@ -1,4 +1,4 @@
-# this_file: crates/python/vexy_json.pyi
+# this_file: crates/python/python/vexy_json/__init__.pyi
 
 """
 Type stubs for vexy_json Python bindings.
@@ -6,7 +6,7 @@ Type stubs for vexy_json Python bindings.
 This file provides type hints for the vexy_json Python module, which is implemented in Rust.
 """
 
-from typing import Any, Dict, List, Union, Optional, IO, Iterator, ContextManager
+from typing import Any, Callable, Dict, List, Union, Optional, IO, Iterator, ContextManager, TypeVar
 from typing_extensions import Literal
 import numpy as np
 import pandas as pd
@@ -17,12 +17,130 @@ JSONValue = Union[None, bool, int, float, str, List['JSONValue'], Dict[str, 'JSO
 # File-like object type
 FileObject = Union[IO[str], IO[bytes]]
 
-def parse_json(input: str) -> JSONValue:
+T = TypeVar('T')
+
+class JSONDecodeError(ValueError):
+    """
+    Raised when a document cannot be parsed.
+
+    Carries the same attributes as the standard library's `json.JSONDecodeError`.
+    """
+
+    msg: str
+    doc: str
+    pos: int
+    lineno: int
+    colno: int
+
+    def __init__(self, msg: str, doc: str, pos: int) -> None: ...
+
+ParseError = JSONDecodeError
+
+class Options:
+    """
+    Parser options.
+
+    Every option can be given as a keyword argument and read or changed as an
+    attribute afterwards; options left out keep the forgiving defaults.
+
+    Example:
+        >>> import vexy_json
+        >>> options = vexy_json.Options(allow_comments=False, max_depth=32)
+        >>> options.max_depth
+        32
+    """
+
+    allow_comments: bool
+    allow_trailing_commas: bool
+    allow_unquoted_keys: bool
+    allow_single_quotes: bool
+    implicit_top_level: bool
+    newline_as_comma: bool
+    max_depth: int
+    enable_repair: bool
+    max_repairs: int
+    fast_repair: bool
+    report_repairs: bool
+    max_array_len: Optional[int]
+    max_object_members: Optional[int]
+    max_key_len: Optional[int]
+    max_string_len: Optional[int]
+
+    def __init__(
+        self,
+        *,
+        allow_comments: bool = True,
+        allow_trailing_commas: bool = True,
+        allow_unquoted_keys: bool = True,
+        allow_single_quotes: bool = True,
+        implicit_top_level: bool = True,
+        newline_as_comma: bool = True,
+        max_depth: int = 128,
+        enable_repair: bool = True,
+        max_repairs: int = 100,
+        fast_repair: bool = False,
+        report_repairs: bool = True,
+        max_array_len: Optional[int] = None,
+        max_object_members: Optional[int] = None,
+        max_key_len: Optional[int] = None,
+        max_string_len: Optional[int] = None,
+    ) -> None: ...
+    @staticmethod
+    def default() -> Options:
+        """The forgiving defaults, the same as `Options()`."""
+        ...
+    @staticmethod
+    def strict() -> Options:
+        """Standard JSON only: every forgiving extension and repair turned off."""
+        ...
+
+class Repair:
+    """A repair made to a document so that it would parse."""
+
+    repair_type: str
+    position: int
+    original: str
+    replacement: str
+    description: str
+
+class ParseResult:
+    """The outcome of `Parser.parse_detailed`."""
+
+    data: JSONValue
+    repairs: List[Repair]
+    tier: Literal["fast", "forgiving", "repair"]
+
+class Parser:
+    """
+    Reusable parser holding a set of `Options`.
+
+    Example:
+        >>> import vexy_json
+        >>> parser = vexy_json.Parser(allow_comments=False)
+        >>> parser.parse('[1, 2,]')
+        [1, 2]
+    """
+
+    def __init__(self, options: Optional[Options] = None, **kwargs: Any) -> None: ...
+    @property
+    def options(self) -> Options:
+        """A copy of the options this parser uses."""
+        ...
+    def parse(self, input: str) -> JSONValue:
+        """Parse a JSON string."""
+        ...
+    def parse_detailed(self, input: str) -> ParseResult:
+        """Parse a JSON string, reporting how it was parsed and any repairs made."""
+        ...
+
+def parse(input: str, *, detailed: bool = False) -> Union[JSONValue, ParseResult]:
     """
     Parse a JSON string with default options (all forgiving features enabled).
     
     Args:
         input: The JSON string to parse
+        detailed: Return a `ParseResult` with the repairs made and the parsing
+            tier instead of the document alone
         
     Returns:
         The parsed JSON as a Python object (dict, list, str, int, float, bool, or None)
@@ -38,48 +156,76 @@ def parse_json(input: str) -> JSONValue:
     """
     ...
 
-def parse_with_options_py(
-    input: str,
-    allow_comments: bool = True,
-    allow_trailing_commas: bool = True,
-    allow_unquoted_keys: bool = True,
-    allow_single_quotes: bool = True,
-    implicit_top_level: bool = True,
-    newline_as_comma: bool = True,
-    max_depth: int = 128,
-    enable_repair: bool = True,
-    max_repairs: int = 100,
-    fast_repair: bool = False,
-    report_repairs: bool = True,
-) -> JSONValue:
+def parse_with_options(
+    input: str, options: Optional[Options] = None, *, detailed: bool = False, **kwargs: Any
+) -> Union[JSONValue, ParseResult]:
     """
     Parse a JSON string with custom options.
     
     Args:
         input: The JSON string to parse
-        allow_comments: Allow single-line and multi-line comments. Defaults to True.
-        allow_trailing_commas: Allow trailing commas in arrays and objects. Defaults to True.
-        allow_unquoted_keys: Allow unquoted object keys. Defaults to True.
-        allow_single_quotes: Allow single-quoted strings. Defaults to True.
-        implicit_top_level: Allow implicit top-level objects/arrays. Defaults to True.
-        newline_as_comma: Treat newlines as commas. Defaults to True.
-        max_depth: Maximum nesting depth. Defaults to 128.
-        enable_repair: Enable JSON repair functionality. Defaults to True.
-        max_repairs: Maximum number of repairs to attempt. Defaults to 100.
-        fast_repair: Prefer speed over repair quality. Defaults to False.
-        report_repairs: Report all repairs made. Defaults to True.
+        options: The options to parse with. Defaults to the forgiving defaults.
+        detailed: Return a `ParseResult` with the repairs made and the parsing
+            tier instead of the document alone
+        **kwargs: Individual options, overriding those in `options`; see `Options`
         
     Returns:
         The parsed JSON as a Python object
         
     Raises:
-        ValueError: If the input is not valid JSON
+        JSONDecodeError: If the input is not valid JSON
+        TypeError: If a keyword argument is not an option
         
     Example:
         >>> import vexy_json
-        >>> result = vexy_json.parse_with_options('key: value', implicit_top_level=True)
-        >>> print(result)
+        >>> vexy_json.parse_with_options('key: value', implicit_top_level=True)
         {'key': 'value'}
+        >>> vexy_json.parse_with_options('{"a": 1}', vexy_json.Options.strict())
+        {'a': 1}
+    """
+    ...
+
+def parse_into_json_bytes(input: str) -> bytes:
+    """
+    Parse a JSON string straight to compact standard JSON bytes.
+
+    No Python objects are built for the document, so the bytes can be handed
+    to a fast decoder such as msgspec or pydantic. Object keys keep their
+    source order.
+
+    Args:
+        input: The JSON string to parse
+
+    Returns:
+        The document as standard JSON
+
+    Raises:
+        JSONDecodeError: If the input is not valid JSON
+
+    Example:
+        >>> import vexy_json
+        >>> vexy_json.parse_into_json_bytes("b: [x, 'y',], a: 1")
+        b'{"b":["x","y"],"a":1}'
+    """
+    ...
+
+def parse_into(input: str, cls: Callable[..., T]) -> T:
+    """
+    Parse a JSON object and construct `cls` from its members.
+
+    The members are passed as keyword arguments, which suits dataclasses,
+    TypedDicts, attrs classes and pydantic models.
+
+    Args:
+        input: The JSON string to parse
+        cls: The callable to construct
+
+    Returns:
+        The result of `cls(**members)`
+
+    Raises:
+        JSONDecodeError: If the input is not valid JSON
+        TypeError: If the document is not an object
     """
     ...
 
@@ -102,6 +248,45 @@ def is_valid(input: str) -> bool:
     """
     ...
 
+def format_error(input: str, error: JSONDecodeError) -> str:
+    """
+    Describe a parse error in plain text.
+
+    The report gives the message, the lines around the error with a caret
+    under it, the error code and suggestions for fixing it, without colors.
+
+    Args:
+        input: The document that failed to parse
+        error: The error raised while parsing it
+
+    Returns:
+        The report, over several lines
+    """
+    ...
+
+def stats(input: str) -> Dict[str, int]:
+    """
+    Parse a JSON string and describe the document without converting it.
+
+    Args:
+        input: The JSON string to parse
+
+    Returns:
+        Counts of each kind of value (objects, arrays, strings, numbers,
+        booleans, nulls), values in total, max_depth, total_keys,
+        longest_string, string_bytes and heap_bytes, an estimate of the
+        memory the parsed document occupies in Rust
+
+    Raises:
+        JSONDecodeError: If the input is not valid JSON
+
+    Example:
+        >>> import vexy_json
+        >>> vexy_json.stats('{"a": [1, 2]}')['max_depth']
+        2
+    """
+    ...
+
 def dumps(obj: Any, indent: Optional[int] = None) -> str:
     """
     Dumps a Python object to a JSON string.
@@ -126,13 +311,16 @@ def dumps(obj: Any, indent: Optional[int] = None) -> str:
     """
     ...
 
-def load(fp: FileObject, **kwargs: Any) -> JSONValue:
+def load(
+    fp: FileObject, options: Optional[Options] = None, **kwargs: Any
+) -> JSONValue:
     """
     Load JSON from a file-like object.
     
     Args:
         fp: A file-like object supporting .read()
-        **kwargs: Additional arguments passed to parse_with_options
+        options: The options to parse with. Defaults to the forgiving defaults.
+        **kwargs: Individual options, overriding those in `options`; see `Options`
         
     Returns:
         The parsed JSON as a Python object
@@ -241,35 +429,13 @@ class StreamingParser:
         ...         print(item)
     """
     
-    def __init__(
-        self,
-        allow_comments: bool = True,
-        allow_trailing_commas: bool = True,
-        allow_unquoted_keys: bool = True,
-        allow_single_quotes: bool = True,
-        implicit_top_level: bool = True,
-        newline_as_comma: bool = True,
-        max_depth: int = 128,
-        enable_repair: bool = True,
-        max_repairs: int = 100,
-        fast_repair: bool = False,
-        report_repairs: bool = True,
-    ) -> None:
+    def __init__(self, options: Optional[Options] = None, **kwargs: Any) -> None:
         """
         Create a new streaming parser.
         
         Args:
-            allow_comments: Allow single-line and multi-line comments
-            allow_trailing_commas: Allow trailing commas in arrays and objects
-            allow_unquoted_keys: Allow unquoted object keys
-            allow_single_quotes: Allow single-quoted strings
-            implicit_top_level: Allow implicit top-level objects/arrays
-            newline_as_comma: Treat newlines as commas
-            max_depth: Maximum nesting depth
-            enable_repair: Enable JSON repair functionality
-            max_repairs: Maximum number of repairs to attempt
-            fast_repair: Prefer speed over repair quality
-            report_repairs: Report all repairs made
+            options: The options to parse each document with
+            **kwargs: Individual options, overriding those in `options`; see `Options`
         """
         ...
     
@@ -320,10 +486,8 @@ class StreamingParser:
         """
         ...
 
-# Convenience aliases
-parse = parse_json
-parse_with_options = parse_with_options_py
-loads = parse_json
+# Standard json module compatibility
+loads = parse
 
 # Module metadata
 __version__: str
@@ -0,0 +1,88 @@
+"""
+Compatibility with older vexy_json Python APIs.
+
+The Python bindings used to ship as two packages, and a few names from them
+did not survive the merge. This module offers the whole current API plus
+those names, so older code keeps working with a one-line change::
+
+    import vexy_json.compat as vexy_json
+
+Each of the older names, apart from `dumps`, emits a DeprecationWarning
+naming its replacement. `dumps` and `dump` accept `sort_keys` but always
+write keys sorted.
+"""
+
+import functools
+import warnings
+
+from . import *  # noqa: F401,F403
+from . import __all__ as _current
+from . import __version__, dumps as _dumps, parse, parse_with_options
+
+# load, dump and dumps replace the current functions of the same name
+__all__ = _current + ["version", "parse_json", "parse_with_options_py"]
+
+
+def _deprecated(replacement):
+    def decorate(func):
+        @functools.wraps(func)
+        def wrapper(*args, **kwargs):
+            warnings.warn(
+                f"vexy_json.compat.{func.__name__} is deprecated; use {replacement}",
+                DeprecationWarning,
+                stacklevel=2,
+            )
+            return func(*args, **kwargs)
+
+        return wrapper
+
+    return decorate
+
+
+@_deprecated("vexy_json.__version__")
+def version():
+    """Return the version of vexy_json."""
+    return __version__
+
+
+@_deprecated("vexy_json.load with an open file")
+def load(filename, options=None, **kwargs):
+    """Load JSON from a file name, or from a file-like object."""
+    if hasattr(filename, "read"):
+        return parse_with_options(filename.read(), options, **kwargs)
+    with open(filename, "r", encoding="utf-8") as f:
+        return parse_with_options(f.read(), options, **kwargs)
+
+
+@_deprecated("vexy_json.dump with an open file")
+def dump(obj, filename, indent=None, sort_keys=False):
+    """Dump JSON to a file name, or to a file-like object.
+
+    Keys are always written sorted; `sort_keys` is accepted and ignored.
+    """
+    text = _dumps(obj, indent)
+    if hasattr(filename, "write"):
+        filename.write(text)
+        return
+    with open(filename, "w", encoding="utf-8") as f:
+        f.write(text)
+
+
+def dumps(obj, indent=None, sort_keys=False):
+    """Serialize to a JSON string.
+
+    Keys are always written sorted; `sort_keys` is accepted and ignored.
+    """
+    return _dumps(obj, indent)
+
+
+@_deprecated("vexy_json.parse")
+def parse_json(input):
+    """Parse a JSON string with the forgiving defaults."""
+    return parse(input)
+
+
+@_deprecated("vexy_json.parse_with_options")
+def parse_with_options_py(input, **kwargs):
+    """Parse a JSON string with options given as keyword arguments."""
+    return parse_with_options(input, **kwargs)
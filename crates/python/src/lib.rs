@@ -8,10 +8,102 @@
 
 use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyBool, PyDict, PyList};
+use pyo3::types::{PyBool, PyBytes, PyDict, PyList};
 use rustc_hash::FxHashMap;
 use vexy_json_core::ast::Value;
-use vexy_json_core::{parse, parse_with_options, ParserOptions};
+use vexy_json_core::error::{plain_error_report, Span};
+use vexy_json_core::format::{to_compact_string, to_pretty_string, KeyOrder};
+use vexy_json_core::{
+    parse, parse_with_fallback, parse_with_options, Limits, ParserOptions, RepairAction,
+};
+
+/// Raised when a document cannot be parsed.
+///
+/// Subclasses `ValueError` and carries the same attributes as the standard
+/// library's `json.JSONDecodeError`, so existing handlers keep working.
+///
+/// Attributes:
+///     msg (str): The unformatted error message
+///     doc (str): The document being parsed
+///     pos (int): Index into `doc` where parsing failed
+///     lineno (int): Line corresponding to `pos`, starting at 1
+///     colno (int): Column corresponding to `pos`, starting at 1
+#[pyclass(extends = PyValueError, name = "JSONDecodeError", module = "vexy_json")]
+struct JsonDecodeError {
+    #[pyo3(get)]
+    msg: String,
+    #[pyo3(get)]
+    doc: String,
+    #[pyo3(get)]
+    pos: usize,
+    #[pyo3(get)]
+    lineno: usize,
+    #[pyo3(get)]
+    colno: usize,
+    /// The parser's own error, for `format_error`; absent when raised
+    /// from Python
+    error: Option<vexy_json_core::Error>,
+}
+
+#[pymethods]
+impl JsonDecodeError {
+    #[new]
+    fn new(msg: String, doc: String, pos: usize) -> Self {
+        let before: Vec<char> = doc.chars().take(pos).collect();
+        let lineno = before.iter().filter(|&&c| c == '\n').count() + 1;
+        let colno = pos - before.iter().rposition(|&c| c == '\n').map_or(0, |i| i + 1) + 1;
+        Self {
+            msg,
+            doc,
+            pos,
+            lineno,
+            colno,
+            error: None,
+        }
+    }
+
+    fn __str__(&self) -> String {
+        format!(
+            "Parse error: {}: line {} column {} (char {})",
+            self.msg, self.lineno, self.colno, self.pos
+        )
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "JSONDecodeError(msg={:?}, pos={}, lineno={}, colno={})",
+            self.msg, self.pos, self.lineno, self.colno
+        )
+    }
+}
+
+/// Builds a `JSONDecodeError` for `error`, raised while parsing `doc`.
+fn decode_error(py: Python, error: &vexy_json_core::Error, doc: &str) -> PyErr {
+    let pos = char_index(doc, error.position().unwrap_or(0));
+    let mut exception = JsonDecodeError::new(error.to_string(), doc.to_string(), pos);
+    exception.error = Some(error.clone());
+    match Bound::new(py, exception) {
+        Ok(exception) => PyErr::from_value(exception.into_any()),
+        Err(err) => err,
+    }
+}
+
+/// Converts a character index into `doc`, as Python uses, to the byte
+/// offset core positions are.
+fn byte_index(doc: &str, char_pos: usize) -> usize {
+    doc.char_indices()
+        .nth(char_pos)
+        .map_or(doc.len(), |(i, _)| i)
+}
+
+/// Converts a byte offset into `doc`, as core positions are, to the
+/// character index Python uses.
+fn char_index(doc: &str, byte_pos: usize) -> usize {
+    let byte_pos = byte_pos.min(doc.len());
+    doc.char_indices()
+        .take_while(|(i, _)| *i < byte_pos)
+        .count()
+}
 
 /// Convert a vexy_json Value to a Python object
 fn value_to_python(py: Python, value: &Value) -> PyResult<PyObject> {
@@ -32,8 +124,11 @@ fn value_to_python(py: Python, value: &Value) -> PyResult<PyObject> {
             Ok(py_list.as_any().clone().unbind())
         }
         Value::Object(obj) => {
+            // Sorted, so dicts list their keys alike on every run and platform
+            let mut entries: Vec<_> = obj.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
             let py_dict = PyDict::new(py);
-            for (key, value) in obj {
+            for (key, value) in entries {
                 let py_value = value_to_python(py, value)?;
                 py_dict.set_item(key, py_value)?;
             }
@@ -77,27 +172,415 @@ fn python_to_value(py: Python, obj: &Bound<'_, PyAny>) -> PyResult<Value> {
     }
 }
 
+/// Parser options
+///
+/// Every option can be given as a keyword argument and read or changed as an
+/// attribute afterwards; options left out keep the forgiving defaults. The
+/// functions and classes taking `options` also accept the same keyword
+/// arguments, which override the matching fields.
+///
+/// Attributes:
+///     allow_comments (bool): Allow single-line and multi-line comments. Defaults to True.
+///     allow_trailing_commas (bool): Allow trailing commas in arrays and objects. Defaults to True.
+///     allow_unquoted_keys (bool): Allow unquoted object keys. Defaults to True.
+///     allow_single_quotes (bool): Allow single-quoted strings. Defaults to True.
+///     implicit_top_level (bool): Allow implicit top-level objects/arrays. Defaults to True.
+///     newline_as_comma (bool): Treat newlines as commas. Defaults to True.
+///     max_depth (int): Maximum nesting depth. Defaults to 128.
+///     enable_repair (bool): Enable JSON repair functionality. Defaults to True.
+///     max_repairs (int): Maximum number of repairs to attempt. Defaults to 100.
+///     fast_repair (bool): Prefer speed over repair quality. Defaults to False.
+///     report_repairs (bool): Report all repairs made. Defaults to True.
+///     max_array_len (int | None): Maximum elements in one array. Defaults to None, no limit.
+///     max_object_members (int | None): Maximum distinct keys in one object. Defaults to None.
+///     max_key_len (int | None): Maximum length in bytes of an object key. Defaults to None.
+///     max_string_len (int | None): Maximum length in bytes of a string value. Defaults to None.
+///
+/// A document past one of the limits raises JSONDecodeError, whose message
+/// names the limit and the path to the value, such as `$.items[1000]`.
+///
+/// Raises:
+///     TypeError: If a keyword argument is not one of the options above
+///
+/// Example:
+///     >>> import vexy_json
+///     >>> options = vexy_json.Options(allow_comments=False, max_depth=32)
+///     >>> options.max_depth
+///     32
+///     >>> vexy_json.Options.strict().allow_unquoted_keys
+///     False
+#[pyclass(module = "vexy_json")]
+#[derive(Clone)]
+struct Options {
+    #[pyo3(get, set)]
+    allow_comments: bool,
+    #[pyo3(get, set)]
+    allow_trailing_commas: bool,
+    #[pyo3(get, set)]
+    allow_unquoted_keys: bool,
+    #[pyo3(get, set)]
+    allow_single_quotes: bool,
+    #[pyo3(get, set)]
+    implicit_top_level: bool,
+    #[pyo3(get, set)]
+    newline_as_comma: bool,
+    #[pyo3(get, set)]
+    max_depth: usize,
+    #[pyo3(get, set)]
+    enable_repair: bool,
+    #[pyo3(get, set)]
+    max_repairs: usize,
+    #[pyo3(get, set)]
+    fast_repair: bool,
+    #[pyo3(get, set)]
+    report_repairs: bool,
+    #[pyo3(get, set)]
+    max_array_len: Option<usize>,
+    #[pyo3(get, set)]
+    max_object_members: Option<usize>,
+    #[pyo3(get, set)]
+    max_key_len: Option<usize>,
+    #[pyo3(get, set)]
+    max_string_len: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        let defaults = ParserOptions::default();
+        Self {
+            allow_comments: defaults.allow_comments,
+            allow_trailing_commas: defaults.allow_trailing_commas,
+            allow_unquoted_keys: defaults.allow_unquoted_keys,
+            allow_single_quotes: defaults.allow_single_quotes,
+            implicit_top_level: defaults.implicit_top_level,
+            newline_as_comma: defaults.newline_as_comma,
+            max_depth: defaults.max_depth,
+            enable_repair: defaults.enable_repair,
+            max_repairs: defaults.max_repairs,
+            fast_repair: defaults.fast_repair,
+            report_repairs: defaults.report_repairs,
+            max_array_len: defaults.limits.max_array_len,
+            max_object_members: defaults.limits.max_object_members,
+            max_key_len: defaults.limits.max_key_len,
+            max_string_len: defaults.limits.max_string_len,
+        }
+    }
+}
+
+impl Options {
+    /// Sets the option called `name` from a keyword argument.
+    fn set(&mut self, name: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        match name {
+            "allow_comments" => self.allow_comments = value.extract()?,
+            "allow_trailing_commas" => self.allow_trailing_commas = value.extract()?,
+            "allow_unquoted_keys" => self.allow_unquoted_keys = value.extract()?,
+            "allow_single_quotes" => self.allow_single_quotes = value.extract()?,
+            "implicit_top_level" => self.implicit_top_level = value.extract()?,
+            "newline_as_comma" => self.newline_as_comma = value.extract()?,
+            "max_depth" => self.max_depth = value.extract()?,
+            "enable_repair" => self.enable_repair = value.extract()?,
+            "max_repairs" => self.max_repairs = value.extract()?,
+            "fast_repair" => self.fast_repair = value.extract()?,
+            "report_repairs" => self.report_repairs = value.extract()?,
+            "max_array_len" => self.max_array_len = value.extract()?,
+            "max_object_members" => self.max_object_members = value.extract()?,
+            "max_key_len" => self.max_key_len = value.extract()?,
+            "max_string_len" => self.max_string_len = value.extract()?,
+            _ => return Err(PyTypeError::new_err(format!("unknown option '{name}'"))),
+        }
+        Ok(())
+    }
+
+    /// Combines an `options` argument with keyword overrides, for the
+    /// functions taking `options=None, **kwargs`.
+    fn resolve(
+        options: Option<PyRef<'_, Self>>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        let mut resolved = options.map(|options| options.clone()).unwrap_or_default();
+        for (name, value) in kwargs.into_iter().flatten() {
+            resolved.set(&name.extract::<String>()?, &value)?;
+        }
+        Ok(resolved)
+    }
+
+    fn parser_options(&self) -> ParserOptions {
+        ParserOptions {
+            allow_comments: self.allow_comments,
+            allow_trailing_commas: self.allow_trailing_commas,
+            allow_unquoted_keys: self.allow_unquoted_keys,
+            allow_single_quotes: self.allow_single_quotes,
+            implicit_top_level: self.implicit_top_level,
+            newline_as_comma: self.newline_as_comma,
+            max_depth: self.max_depth,
+            enable_repair: self.enable_repair,
+            max_repairs: self.max_repairs,
+            fast_repair: self.fast_repair,
+            report_repairs: self.report_repairs,
+            limits: Limits {
+                max_array_len: self.max_array_len,
+                max_object_members: self.max_object_members,
+                max_key_len: self.max_key_len,
+                max_string_len: self.max_string_len,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[pymethods]
+impl Options {
+    #[new]
+    #[pyo3(signature = (**kwargs))]
+    fn new(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        Self::resolve(None, kwargs)
+    }
+
+    /// The forgiving defaults, the same as `Options()`
+    #[staticmethod]
+    #[pyo3(name = "default")]
+    fn forgiving() -> Self {
+        Self::default()
+    }
+
+    /// Standard JSON only: every forgiving extension and repair turned off
+    #[staticmethod]
+    fn strict() -> Self {
+        Self {
+            allow_comments: false,
+            allow_trailing_commas: false,
+            allow_unquoted_keys: false,
+            allow_single_quotes: false,
+            implicit_top_level: false,
+            newline_as_comma: false,
+            enable_repair: false,
+            ..Self::default()
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        let flag = |value: bool| if value { "True" } else { "False" };
+        let limit = |value: Option<usize>| value.map_or("None".to_string(), |n| n.to_string());
+        format!(
+            "Options(allow_comments={}, allow_trailing_commas={}, allow_unquoted_keys={}, \
+             allow_single_quotes={}, implicit_top_level={}, newline_as_comma={}, max_depth={}, \
+             enable_repair={}, max_repairs={}, fast_repair={}, report_repairs={}, \
+             max_array_len={}, max_object_members={}, max_key_len={}, max_string_len={})",
+            flag(self.allow_comments),
+            flag(self.allow_trailing_commas),
+            flag(self.allow_unquoted_keys),
+            flag(self.allow_single_quotes),
+            flag(self.implicit_top_level),
+            flag(self.newline_as_comma),
+            self.max_depth,
+            flag(self.enable_repair),
+            self.max_repairs,
+            flag(self.fast_repair),
+            flag(self.report_repairs),
+            limit(self.max_array_len),
+            limit(self.max_object_members),
+            limit(self.max_key_len),
+            limit(self.max_string_len),
+        )
+    }
+}
+
+/// A repair made to a document so that it would parse
+///
+/// Attributes:
+///     repair_type (str): The kind of repair, such as "insert_bracket"
+///     position (int): Index into the document where the repair was made
+///     original (str): The text that was replaced
+///     replacement (str): The text that was put in its place
+///     description (str): A human-readable description of the repair
+#[pyclass(module = "vexy_json")]
+#[derive(Clone)]
+struct Repair {
+    #[pyo3(get)]
+    repair_type: String,
+    #[pyo3(get)]
+    position: usize,
+    #[pyo3(get)]
+    original: String,
+    #[pyo3(get)]
+    replacement: String,
+    #[pyo3(get)]
+    description: String,
+}
+
+impl Repair {
+    fn new(repair: &RepairAction, doc: &str) -> Self {
+        // "InsertBracket" becomes "insert_bracket"
+        let mut repair_type = String::new();
+        for c in format!("{:?}", repair.action_type).chars() {
+            if c.is_uppercase() && !repair_type.is_empty() {
+                repair_type.push('_');
+            }
+            repair_type.push(c.to_ascii_lowercase());
+        }
+        Self {
+            repair_type,
+            position: char_index(doc, repair.position),
+            original: repair.original.clone(),
+            replacement: repair.replacement.clone(),
+            description: repair.description.clone(),
+        }
+    }
+}
+
+#[pymethods]
+impl Repair {
+    fn __repr__(&self) -> String {
+        format!(
+            "Repair(repair_type={:?}, position={}, description={:?})",
+            self.repair_type, self.position, self.description
+        )
+    }
+}
+
+/// The outcome of `Parser.parse_detailed`, or of the parse functions when
+/// called with `detailed=True`
+///
+/// Attributes:
+///     data: The parsed document
+///     repairs (list[Repair]): Repairs made so that the document would parse
+///     tier (str): How the document was parsed: "fast" for standard JSON,
+///         "forgiving" for the forgiving parser, or "repair"
+#[pyclass(module = "vexy_json")]
+struct ParseResult {
+    #[pyo3(get)]
+    data: PyObject,
+    #[pyo3(get)]
+    repairs: Vec<Repair>,
+    #[pyo3(get)]
+    tier: &'static str,
+}
+
+#[pymethods]
+impl ParseResult {
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        Ok(format!(
+            "ParseResult(data={}, repairs={}, tier={:?})",
+            self.data.bind(py).repr()?,
+            self.repairs.len(),
+            self.tier
+        ))
+    }
+}
+
+/// Reusable parser holding a set of `Options`
+///
+/// Args:
+///     options (Options, optional): The options to parse with
+///     **kwargs: Individual options, overriding those in `options`
+///
+/// Example:
+///     >>> import vexy_json
+///     >>> parser = vexy_json.Parser(allow_comments=False)
+///     >>> parser.parse('[1, 2,]')
+///     [1, 2]
+///     >>> result = parser.parse_detailed('{"a": [1, 2}')
+///     >>> result.data, result.tier
+///     ({'a': [1, 2]}, 'repair')
+#[pyclass(module = "vexy_json")]
+struct Parser {
+    options: Options,
+}
+
+#[pymethods]
+impl Parser {
+    #[new]
+    #[pyo3(signature = (options = None, **kwargs))]
+    fn new(
+        options: Option<PyRef<'_, Options>>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            options: Options::resolve(options, kwargs)?,
+        })
+    }
+
+    /// A copy of the options this parser uses
+    #[getter]
+    fn options(&self) -> Options {
+        self.options.clone()
+    }
+
+    /// Parse a JSON string
+    ///
+    /// Raises:
+    ///     JSONDecodeError: If the input is not valid JSON (a ValueError subclass)
+    fn parse(&self, py: Python, input: &str) -> PyResult<PyObject> {
+        match parse_with_options(input, self.options.parser_options()) {
+            Ok(value) => value_to_python(py, &value),
+            Err(e) => Err(decode_error(py, &e, input)),
+        }
+    }
+
+    /// Parse a JSON string, reporting how it was parsed and any repairs made
+    ///
+    /// Returns:
+    ///     ParseResult: The document, the repairs and the parsing tier
+    ///
+    /// Raises:
+    ///     JSONDecodeError: If the input is not valid JSON (a ValueError subclass)
+    fn parse_detailed(&self, py: Python, input: &str) -> PyResult<ParseResult> {
+        parse_detailed(py, input, self.options.parser_options())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Parser({})", self.options.__repr__())
+    }
+}
+
+/// Parses `input` through the tiers of `parse_with_fallback`, keeping the
+/// repairs made and the tier that succeeded.
+fn parse_detailed(py: Python, input: &str, options: ParserOptions) -> PyResult<ParseResult> {
+    let result = parse_with_fallback(input, options);
+    if let Some(error) = result.errors.first() {
+        return Err(decode_error(py, error, input));
+    }
+    Ok(ParseResult {
+        data: value_to_python(py, &result.value)?,
+        repairs: result
+            .repairs
+            .iter()
+            .map(|repair| Repair::new(repair, input))
+            .collect(),
+        tier: result.parsing_tier.as_str(),
+    })
+}
+
 /// Parse a JSON string with default options (all forgiving features enabled)
 ///
 /// Args:
 ///     input (str): The JSON string to parse
+///     detailed (bool): Return a `ParseResult` with the repairs made and the
+///         parsing tier instead of the document alone
 ///
 /// Returns:
 ///     The parsed JSON as a Python object (dict, list, str, int, float, bool, or None)
 ///
 /// Raises:
-///     ValueError: If the input is not valid JSON
+///     JSONDecodeError: If the input is not valid JSON (a ValueError subclass)
 ///
 /// Example:
 ///     >>> import vexy_json
 ///     >>> result = vexy_json.parse('{"key": "value", trailing: true,}')
 ///     >>> print(result)
 ///     {'key': 'value', 'trailing': True}
+///     >>> vexy_json.parse('{"a": 1', detailed=True).tier
+///     'repair'
 #[pyfunction]
-fn parse_json(py: Python, input: &str) -> PyResult<PyObject> {
+#[pyo3(name = "parse", signature = (input, *, detailed = false))]
+fn parse_json(py: Python, input: &str, detailed: bool) -> PyResult<PyObject> {
+    if detailed {
+        let result = parse_detailed(py, input, ParserOptions::default())?;
+        return Ok(Bound::new(py, result)?.into_any().unbind());
+    }
     match parse(input) {
         Ok(value) => value_to_python(py, &value),
-        Err(e) => Err(PyValueError::new_err(format!("Parse error: {e}"))),
+        Err(e) => Err(decode_error(py, &e, input)),
     }
 }
 
@@ -105,87 +588,130 @@ fn parse_json(py: Python, input: &str) -> PyResult<PyObject> {
 ///
 /// Args:
 ///     input (str): The JSON string to parse
-///     allow_comments (bool, optional): Allow single-line and multi-line comments. Defaults to True.
-///     allow_trailing_commas (bool, optional): Allow trailing commas in arrays and objects. Defaults to True.
-///     allow_unquoted_keys (bool, optional): Allow unquoted object keys. Defaults to True.
-///     allow_single_quotes (bool, optional): Allow single-quoted strings. Defaults to True.
-///     implicit_top_level (bool, optional): Allow implicit top-level objects/arrays. Defaults to True.
-///     newline_as_comma (bool, optional): Treat newlines as commas. Defaults to True.
-///     max_depth (int, optional): Maximum nesting depth. Defaults to 128.
-///     enable_repair (bool, optional): Enable JSON repair functionality. Defaults to True.
-///     max_repairs (int, optional): Maximum number of repairs to attempt. Defaults to 100.
-///     fast_repair (bool, optional): Prefer speed over repair quality. Defaults to False.
-///     report_repairs (bool, optional): Report all repairs made. Defaults to True.
+///     options (Options, optional): The options to parse with
+///     detailed (bool): Return a `ParseResult` with the repairs made and the
+///         parsing tier instead of the document alone
+///     **kwargs: Individual options, overriding those in `options`; see `Options`
 ///
 /// Returns:
 ///     The parsed JSON as a Python object
 ///
 /// Raises:
-///     ValueError: If the input is not valid JSON
+///     JSONDecodeError: If the input is not valid JSON (a ValueError subclass)
+///     TypeError: If a keyword argument is not an option
 ///
 /// Example:
 ///     >>> import vexy_json
-///     >>> result = vexy_json.parse_with_options('key: value', implicit_top_level=True)
-///     >>> print(result)
+///     >>> vexy_json.parse_with_options('key: value', implicit_top_level=True)
 ///     {'key': 'value'}
+///     >>> vexy_json.parse_with_options('{"a": 1}', vexy_json.Options.strict())
+///     {'a': 1}
 #[pyfunction]
-#[pyo3(signature = (
-    input,
-    allow_comments = true,
-    allow_trailing_commas = true,
-    allow_unquoted_keys = true,
-    allow_single_quotes = true,
-    implicit_top_level = true,
-    newline_as_comma = true,
-    max_depth = 128,
-    enable_repair = true,
-    max_repairs = 100,
-    fast_repair = false,
-    report_repairs = true
-))]
-#[allow(clippy::too_many_arguments)]
+#[pyo3(
+    name = "parse_with_options",
+    signature = (input, options = None, *, detailed = false, **kwargs)
+)]
 fn parse_with_options_py(
     py: Python,
     input: &str,
-    allow_comments: bool,
-    allow_trailing_commas: bool,
-    allow_unquoted_keys: bool,
-    allow_single_quotes: bool,
-    implicit_top_level: bool,
-    newline_as_comma: bool,
-    max_depth: usize,
-    enable_repair: bool,
-    max_repairs: usize,
-    fast_repair: bool,
-    report_repairs: bool,
+    options: Option<PyRef<'_, Options>>,
+    detailed: bool,
+    kwargs: Option<&Bound<'_, PyDict>>,
 ) -> PyResult<PyObject> {
-    let options = ParserOptions {
-        allow_comments,
-        allow_trailing_commas,
-        allow_unquoted_keys,
-        allow_single_quotes,
-        implicit_top_level,
-        newline_as_comma,
-        max_depth,
-        enable_repair,
-        max_repairs,
-        fast_repair,
-        report_repairs,
-    };
-
-    match parse_with_options(input, options) {
+    let options = Options::resolve(options, kwargs)?;
+    if detailed {
+        let result = parse_detailed(py, input, options.parser_options())?;
+        return Ok(Bound::new(py, result)?.into_any().unbind());
+    }
+    match parse_with_options(input, options.parser_options()) {
         Ok(value) => value_to_python(py, &value),
-        Err(e) => Err(PyValueError::new_err(format!("Parse error: {e}"))),
+        Err(e) => Err(decode_error(py, &e, input)),
+    }
+}
+
+/// Parse a JSON string straight to compact standard JSON bytes
+///
+/// No Python objects are built for the document, so the bytes can be handed
+/// to a fast decoder such as `msgspec.json.decode` or pydantic's
+/// `model_validate_json`. Object keys keep their source order, and numbers
+/// that overflow to infinity, such as `1e999`, are written as `null`.
+///
+/// Args:
+///     input (str): The JSON string to parse
+///
+/// Returns:
+///     bytes: The document as standard JSON
+///
+/// Raises:
+///     JSONDecodeError: If the input is not valid JSON (a ValueError subclass)
+///
+/// Example:
+///     >>> import vexy_json
+///     >>> vexy_json.parse_into_json_bytes("b: [x, 'y',], a: 1")
+///     b'{"b":["x","y"],"a":1}'
+#[pyfunction]
+fn parse_into_json_bytes(py: Python, input: &str) -> PyResult<PyObject> {
+    let json = py.allow_threads(|| {
+        parse(input).map(|value| to_compact_string(&value, &KeyOrder::from_source(input)))
+    });
+    match json {
+        Ok(json) => Ok(PyBytes::new(py, json.as_bytes()).into_any().unbind()),
+        Err(e) => Err(decode_error(py, &e, input)),
+    }
+}
+
+/// Parse a JSON object and construct `cls` from its members
+///
+/// The members are passed as keyword arguments, which suits dataclasses,
+/// TypedDicts, attrs classes and pydantic models. Nested values are
+/// converted as by `parse`.
+///
+/// Args:
+///     input (str): The JSON string to parse
+///     cls (type): The callable to construct
+///
+/// Returns:
+///     The result of `cls(**members)`
+///
+/// Raises:
+///     JSONDecodeError: If the input is not valid JSON (a ValueError subclass)
+///     TypeError: If the document is not an object
+///
+/// Example:
+///     >>> import dataclasses, vexy_json
+///     >>> @dataclasses.dataclass
+///     ... class Point:
+///     ...     x: int
+///     ...     y: int
+///     >>> vexy_json.parse_into("x: 1, y: 2", Point)
+///     Point(x=1, y=2)
+#[pyfunction]
+fn parse_into(py: Python, input: &str, cls: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let object = match parse(input) {
+        Ok(Value::Object(object)) => object,
+        Ok(_) => {
+            return Err(PyTypeError::new_err(
+                "parse_into needs a document whose top level is an object",
+            ))
+        }
+        Err(e) => return Err(decode_error(py, &e, input)),
+    };
+    let kwargs = PyDict::new(py);
+    for (key, value) in &object {
+        kwargs.set_item(key, value_to_python(py, value)?)?;
     }
+    Ok(cls.call((), Some(&kwargs))?.unbind())
 }
 
 /// Check if a string is valid JSON/Vexy JSON
 ///
 /// Args:
 ///     input (str): The JSON string to validate
+///     return_error (bool, optional): Return the error instead of a bool. Defaults to False.
 ///
 /// Returns:
-///     bool: True if the input is valid, False otherwise
+///     bool: True if the input is valid, False otherwise. With `return_error=True`,
+///     the `JSONDecodeError` describing the problem, or None if the input is valid.
 ///
 /// Example:
 ///     >>> import vexy_json
@@ -193,9 +719,97 @@ fn parse_with_options_py(
 ///     True
 ///     >>> vexy_json.is_valid('invalid json')
 ///     False
+///     >>> vexy_json.is_valid('{"a": @}', return_error=True).colno
+///     7
 #[pyfunction]
-fn is_valid(input: &str) -> bool {
-    parse(input).is_ok()
+#[pyo3(signature = (input, return_error = false))]
+fn is_valid(py: Python, input: &str, return_error: bool) -> PyObject {
+    match parse(input) {
+        Ok(_) if return_error => py.None(),
+        Ok(_) => PyBool::new(py, true).as_any().clone().unbind(),
+        Err(e) if return_error => decode_error(py, &e, input).into_value(py).into_any(),
+        Err(_) => PyBool::new(py, false).as_any().clone().unbind(),
+    }
+}
+
+/// Describe a parse error in plain text
+///
+/// The report gives the message, the lines around the error with a caret
+/// under it, the error code and suggestions for fixing it, without colors.
+///
+/// Args:
+///     input (str): The document that failed to parse
+///     error (JSONDecodeError): The error raised while parsing it
+///
+/// Returns:
+///     str: The report, over several lines
+///
+/// Example:
+///     >>> import vexy_json
+///     >>> try:
+///     ...     vexy_json.parse('{"a": @}')
+///     ... except vexy_json.JSONDecodeError as e:
+///     ...     print(vexy_json.format_error('{"a": @}', e))
+///     Error: Unexpected character '@' at position 6
+///     <BLANKLINE>
+///     Context:
+///        1 | {"a": @}
+///          |       ^
+///     ...
+#[pyfunction]
+fn format_error(input: &str, error: PyRef<'_, JsonDecodeError>) -> String {
+    let error = match &error.error {
+        Some(error) => error.clone(),
+        // Raised from Python, so only the message and position are known
+        None => vexy_json_core::Error::Spanned {
+            source: Box::new(vexy_json_core::Error::Custom(error.msg.clone())),
+            span: Span::single(byte_index(input, error.pos)),
+        },
+    };
+    plain_error_report(&error, input)
+}
+
+/// Parse a JSON string and describe the document without converting it
+///
+/// Args:
+///     input (str): The JSON string to parse
+///
+/// Returns:
+///     dict: Counts of each kind of value (`objects`, `arrays`, `strings`,
+///     `numbers`, `booleans`, `nulls`), `values` in total, `max_depth`,
+///     `total_keys`, `longest_string`, `string_bytes` and `heap_bytes`, an
+///     estimate of the memory the parsed document occupies in Rust
+///
+/// Raises:
+///     JSONDecodeError: If the input is not valid JSON (a ValueError subclass)
+///
+/// Example:
+///     >>> import vexy_json
+///     >>> vexy_json.stats('{"a": [1, 2]}')['max_depth']
+///     2
+#[pyfunction]
+fn stats(py: Python, input: &str) -> PyResult<PyObject> {
+    let stats = py
+        .allow_threads(|| parse(input).map(|value| value.stats()))
+        .map_err(|e| decode_error(py, &e, input))?;
+    let dict = PyDict::new(py);
+    for (key, count) in [
+        ("objects", stats.objects),
+        ("arrays", stats.arrays),
+        ("strings", stats.strings),
+        ("numbers", stats.numbers),
+        ("booleans", stats.booleans),
+        ("nulls", stats.nulls),
+        ("values", stats.values()),
+        ("max_depth", stats.max_depth),
+        ("total_keys", stats.total_keys),
+        ("longest_string", stats.longest_string),
+        ("string_bytes", stats.string_bytes),
+        ("heap_bytes", stats.heap_bytes),
+    ] {
+        dict.set_item(key, count)?;
+    }
+    Ok(dict.into_any().unbind())
 }
 
 /// Dumps a Python object to a JSON string
@@ -221,176 +835,41 @@ fn is_valid(input: &str) -> bool {
 #[pyo3(signature = (obj, indent = None))]
 fn dumps(py: Python, obj: &Bound<'_, PyAny>, indent: Option<usize>) -> PyResult<String> {
     let value = python_to_value(py, obj)?;
+    let order = KeyOrder::default();
 
-    if let Some(spaces) = indent {
-        // Pretty printing with indentation
-        Ok(format_value_pretty(&value, spaces))
-    } else {
-        // Compact output
-        Ok(value.to_string())
-    }
-}
-
-/// Format a Value with pretty printing
-fn format_value_pretty(value: &Value, indent: usize) -> String {
-    format_value_with_indent(value, 0, indent)
-}
-
-fn format_value_with_indent(value: &Value, current_indent: usize, indent_size: usize) -> String {
-    let spaces = " ".repeat(current_indent);
-    let inner_spaces = " ".repeat(current_indent + indent_size);
-
-    match value {
-        Value::Null => "null".to_string(),
-        Value::Bool(b) => b.to_string(),
-        Value::Number(num) => match num {
-            vexy_json_core::ast::Number::Integer(i) => i.to_string(),
-            vexy_json_core::ast::Number::Float(f) => f.to_string(),
-        },
-        Value::String(s) => format!("\"{}\"", s.replace("\"", "\\\"")),
-        Value::Array(arr) => {
-            if arr.is_empty() {
-                "[]".to_string()
-            } else {
-                let mut result = "[\n".to_string();
-                for (i, item) in arr.iter().enumerate() {
-                    result.push_str(&inner_spaces);
-                    result.push_str(&format_value_with_indent(
-                        item,
-                        current_indent + indent_size,
-                        indent_size,
-                    ));
-                    if i < arr.len() - 1 {
-                        result.push(',');
-                    }
-                    result.push('\n');
-                }
-                result.push_str(&spaces);
-                result.push(']');
-                result
-            }
-        }
-        Value::Object(obj) => {
-            if obj.is_empty() {
-                "{}".to_string()
-            } else {
-                let mut result = "{\n".to_string();
-                let mut entries: Vec<_> = obj.iter().collect();
-                entries.sort_by_key(|(k, _)| *k);
-
-                for (i, (key, value)) in entries.iter().enumerate() {
-                    result.push_str(&inner_spaces);
-                    result.push_str(&format!("\"{key}\": "));
-                    result.push_str(&format_value_with_indent(
-                        value,
-                        current_indent + indent_size,
-                        indent_size,
-                    ));
-                    if i < entries.len() - 1 {
-                        result.push(',');
-                    }
-                    result.push('\n');
-                }
-                result.push_str(&spaces);
-                result.push('}');
-                result
-            }
-        }
-    }
+    Ok(match indent {
+        Some(spaces) => to_pretty_string(&value, spaces, &order),
+        None => to_compact_string(&value, &order),
+    })
 }
 
 /// Load JSON from a file-like object
 ///
 /// Args:
 ///     fp: A file-like object supporting .read()
-///     **kwargs: Additional arguments passed to parse_with_options
+///     options (Options, optional): The options to parse with
+///     **kwargs: Individual options, overriding those in `options`; see `Options`
 ///
 /// Returns:
 ///     The parsed JSON as a Python object
 ///
 /// Raises:
-///     ValueError: If the content is not valid JSON
+///     JSONDecodeError: If the content is not valid JSON (a ValueError subclass)
 ///
 /// Example:
 ///     >>> import vexy_json
 ///     >>> with open('data.json', 'r') as f:
 ///     ...     result = vexy_json.load(f)
 #[pyfunction]
-#[pyo3(signature = (fp, **kwargs))]
+#[pyo3(signature = (fp, options = None, **kwargs))]
 fn load(
     py: Python,
     fp: &Bound<'_, PyAny>,
+    options: Option<PyRef<'_, Options>>,
     kwargs: Option<&Bound<'_, PyDict>>,
 ) -> PyResult<PyObject> {
-    // Read content from file-like object
-    let content = fp.call_method0("read")?;
-    let content_str = content.extract::<String>()?;
-
-    // Parse with options if provided
-    if let Some(options) = kwargs {
-        let allow_comments = options
-            .get_item("allow_comments")?
-            .map(|v| v.extract::<bool>().unwrap_or(true))
-            .unwrap_or(true);
-        let allow_trailing_commas = options
-            .get_item("allow_trailing_commas")?
-            .map(|v| v.extract::<bool>().unwrap_or(true))
-            .unwrap_or(true);
-        let allow_unquoted_keys = options
-            .get_item("allow_unquoted_keys")?
-            .map(|v| v.extract::<bool>().unwrap_or(true))
-            .unwrap_or(true);
-        let allow_single_quotes = options
-            .get_item("allow_single_quotes")?
-            .map(|v| v.extract::<bool>().unwrap_or(true))
-            .unwrap_or(true);
-        let implicit_top_level = options
-            .get_item("implicit_top_level")?
-            .map(|v| v.extract::<bool>().unwrap_or(true))
-            .unwrap_or(true);
-        let newline_as_comma = options
-            .get_item("newline_as_comma")?
-            .map(|v| v.extract::<bool>().unwrap_or(true))
-            .unwrap_or(true);
-        let max_depth = options
-            .get_item("max_depth")?
-            .map(|v| v.extract::<usize>().unwrap_or(128))
-            .unwrap_or(128);
-        let enable_repair = options
-            .get_item("enable_repair")?
-            .map(|v| v.extract::<bool>().unwrap_or(true))
-            .unwrap_or(true);
-        let max_repairs = options
-            .get_item("max_repairs")?
-            .map(|v| v.extract::<usize>().unwrap_or(100))
-            .unwrap_or(100);
-        let fast_repair = options
-            .get_item("fast_repair")?
-            .map(|v| v.extract::<bool>().unwrap_or(false))
-            .unwrap_or(false);
-        let report_repairs = options
-            .get_item("report_repairs")?
-            .map(|v| v.extract::<bool>().unwrap_or(true))
-            .unwrap_or(true);
-
-        parse_with_options_py(
-            py,
-            &content_str,
-            allow_comments,
-            allow_trailing_commas,
-            allow_unquoted_keys,
-            allow_single_quotes,
-            implicit_top_level,
-            newline_as_comma,
-            max_depth,
-            enable_repair,
-            max_repairs,
-            fast_repair,
-            report_repairs,
-        )
-    } else {
-        parse_json(py, &content_str)
-    }
+    let content = fp.call_method0("read")?.extract::<String>()?;
+    parse_with_options_py(py, &content, options, false, kwargs)
 }
 
 /// Dump JSON to a file-like object
@@ -445,53 +924,21 @@ struct StreamingParser {
 #[pymethods]
 impl StreamingParser {
     /// Create a new streaming parser
+    ///
+    /// Args:
+    ///     options (Options, optional): The options to parse each document with
+    ///     **kwargs: Individual options, overriding those in `options`; see `Options`
     #[new]
-    #[pyo3(signature = (
-        allow_comments = true,
-        allow_trailing_commas = true,
-        allow_unquoted_keys = true,
-        allow_single_quotes = true,
-        implicit_top_level = true,
-        newline_as_comma = true,
-        max_depth = 128,
-        enable_repair = true,
-        max_repairs = 100,
-        fast_repair = false,
-        report_repairs = true
-    ))]
-    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (options = None, **kwargs))]
     fn new(
-        allow_comments: bool,
-        allow_trailing_commas: bool,
-        allow_unquoted_keys: bool,
-        allow_single_quotes: bool,
-        implicit_top_level: bool,
-        newline_as_comma: bool,
-        max_depth: usize,
-        enable_repair: bool,
-        max_repairs: usize,
-        fast_repair: bool,
-        report_repairs: bool,
-    ) -> Self {
-        let options = ParserOptions {
-            allow_comments,
-            allow_trailing_commas,
-            allow_unquoted_keys,
-            allow_single_quotes,
-            implicit_top_level,
-            newline_as_comma,
-            max_depth,
-            enable_repair,
-            max_repairs,
-            fast_repair,
-            report_repairs,
-        };
-
-        Self {
-            options,
+        options: Option<PyRef<'_, Options>>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<Self> {
+        Ok(Self {
+            options: Options::resolve(options, kwargs)?.parser_options(),
             buffer: String::new(),
             active: false,
-        }
+        })
     }
 
     /// Context manager entry
@@ -666,7 +1113,7 @@ impl LineIterator {
                 let py_obj = value_to_python(py, &value)?;
                 Ok(Some(py_obj))
             }
-            Err(e) => Err(PyValueError::new_err(format!("Parse error: {e}"))),
+            Err(e) => Err(decode_error(py, &e, trimmed)),
         }
     }
 }
@@ -705,7 +1152,7 @@ fn loads_numpy(py: Python, input: &str, dtype: Option<&str>) -> PyResult<PyObjec
     // Parse the JSON
     let value = match parse(input) {
         Ok(v) => v,
-        Err(e) => return Err(PyValueError::new_err(format!("Parse error: {e}"))),
+        Err(e) => return Err(decode_error(py, &e, input)),
     };
 
     // Convert to NumPy array
@@ -785,7 +1232,7 @@ fn loads_numpy_zerocopy(py: Python, input: &str, dtype: Option<&str>) -> PyResul
     // Parse the JSON
     let value = match parse(input) {
         Ok(v) => v,
-        Err(e) => return Err(PyValueError::new_err(format!("Parse error: {e}"))),
+        Err(e) => return Err(decode_error(py, &e, input)),
     };
 
     match value {
@@ -890,7 +1337,7 @@ fn loads_dataframe(py: Python, input: &str, _orient: &str) -> PyResult<PyObject>
     // Parse the JSON
     let value = match parse(input) {
         Ok(v) => v,
-        Err(e) => return Err(PyValueError::new_err(format!("Parse error: {e}"))),
+        Err(e) => return Err(decode_error(py, &e, input)),
     };
 
     // Convert to Python object
@@ -906,7 +1353,11 @@ fn loads_dataframe(py: Python, input: &str, _orient: &str) -> PyResult<PyObject>
 fn _vexy_json(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parse_json, m)?)?;
     m.add_function(wrap_pyfunction!(parse_with_options_py, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_into_json_bytes, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_into, m)?)?;
     m.add_function(wrap_pyfunction!(is_valid, m)?)?;
+    m.add_function(wrap_pyfunction!(format_error, m)?)?;
+    m.add_function(wrap_pyfunction!(stats, m)?)?;
     m.add_function(wrap_pyfunction!(dumps, m)?)?;
     m.add_function(wrap_pyfunction!(load, m)?)?;
     m.add_function(wrap_pyfunction!(dump, m)?)?;
@@ -916,15 +1367,16 @@ fn _vexy_json(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(loads_numpy_zerocopy, m)?)?;
     m.add_function(wrap_pyfunction!(loads_dataframe, m)?)?;
 
-    // Add streaming parser class
+    // Add parser classes
+    m.add_class::<Options>()?;
+    m.add_class::<Parser>()?;
+    m.add_class::<ParseResult>()?;
+    m.add_class::<Repair>()?;
     m.add_class::<StreamingParser>()?;
 
-    // Add convenience aliases
-    m.add("parse", m.getattr("parse_json")?)?;
-    m.add("parse_with_options", m.getattr("parse_with_options_py")?)?;
-
-    // Add standard json module compatibility
-    m.add("loads", m.getattr("parse_json")?)?;
+    // Add the error type, also under the name used by the older bindings
+    m.add_class::<JsonDecodeError>()?;
+    m.add("ParseError", m.getattr("JSONDecodeError")?)?;
 
     // Add version information
     m.add(
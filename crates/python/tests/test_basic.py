@@ -294,5 +294,158 @@ class TestErrorHandling:
                 vexy_json.parse(case)
 
 
+class TestJSONDecodeError:
+    """Test the json.JSONDecodeError compatible exception."""
+
+    DOC = '{\n  "név": "Árvíztűrő",\n  "emoji": "🎉",\n  "bad": @\n}'
+
+    def test_attributes(self):
+        """Positions count characters, not UTF-8 bytes."""
+        with pytest.raises(vexy_json.JSONDecodeError) as excinfo:
+            vexy_json.parse(self.DOC)
+
+        error = excinfo.value
+        assert isinstance(error, ValueError)
+        assert error.doc == self.DOC
+        assert error.pos == self.DOC.index("@") == 49
+        assert error.lineno == 4
+        assert error.colno == 10
+        assert "Unexpected character '@'" in error.msg
+        assert str(error).endswith(": line 4 column 10 (char 49)")
+
+    def test_raised_by_loads_and_load(self):
+        """All parsing entry points raise the same exception type."""
+        import io
+
+        for parse in (vexy_json.loads, vexy_json.parse_with_options):
+            with pytest.raises(vexy_json.JSONDecodeError) as excinfo:
+                parse(self.DOC)
+            assert excinfo.value.lineno == 4
+
+        with pytest.raises(vexy_json.JSONDecodeError) as excinfo:
+            vexy_json.load(io.StringIO(self.DOC))
+        assert excinfo.value.colno == 10
+
+    def test_constructor_matches_stdlib(self):
+        """The constructor takes the same arguments as json.JSONDecodeError."""
+        import json
+
+        doc = "[1,\n 2,\n ]x"
+        ours = vexy_json.JSONDecodeError("Expecting value", doc, 11)
+        theirs = json.JSONDecodeError("Expecting value", doc, 11)
+        assert (ours.lineno, ours.colno, ours.pos) == (theirs.lineno, theirs.colno, theirs.pos)
+
+    def test_parse_error_alias(self):
+        """The older ParseError name refers to the same class."""
+        assert vexy_json.ParseError is vexy_json.JSONDecodeError
+
+    def test_is_valid_return_error(self):
+        """is_valid can return the error instead of a bool."""
+        assert vexy_json.is_valid('{"a": 1}', return_error=True) is None
+
+        error = vexy_json.is_valid(self.DOC, return_error=True)
+        assert isinstance(error, vexy_json.JSONDecodeError)
+        assert (error.lineno, error.colno, error.pos) == (4, 10, 49)
+
+        assert vexy_json.is_valid(self.DOC) is False
+
+    def test_format_error(self):
+        """The report points at the character, after multi-byte ones."""
+        with pytest.raises(vexy_json.JSONDecodeError) as excinfo:
+            vexy_json.parse(self.DOC)
+
+        report = vexy_json.format_error(self.DOC, excinfo.value)
+        assert report.startswith("Error: Unexpected character '@'")
+        assert '   4 |   "bad": @\n     |          ^\n' in report
+        assert "Error Code: [E1001]" in report
+        assert "Suggestions:" in report
+        assert "\x1b[" not in report
+
+    def test_format_error_at_edges(self):
+        """Errors at the start and end of the document are shown too."""
+        error = vexy_json.is_valid("@", return_error=True)
+        assert "   1 | @\n     | ^\n" in vexy_json.format_error("@", error)
+
+        error = vexy_json.is_valid("[1,\n", return_error=True)
+        assert "   2 | \n     | ^\n" in vexy_json.format_error("[1,\n", error)
+
+    def test_format_error_raised_from_python(self):
+        """An error built in Python is reported from its message and position."""
+        doc = "[1,\n 2,\n ]x"
+        error = vexy_json.JSONDecodeError("Expecting value", doc, 10)
+        report = vexy_json.format_error(doc, error)
+        assert "Expecting value" in report
+        assert "   3 |  ]x\n     |   ^\n" in report
+
+
+class TestParseInto:
+    """Test parsing without a dict intermediate."""
+
+    def test_parse_into_json_bytes(self):
+        """Forgiving input comes back as standard JSON bytes in source order."""
+        import json
+
+        result = vexy_json.parse_into_json_bytes("// c\nb: [x, 'y',], a: {c: null}")
+        assert result == b'{"b":["x","y"],"a":{"c":null}}'
+        assert json.loads(result) == vexy_json.parse("b: [x, 'y'], a: {c: null}")
+
+        # Overflowed floats have no JSON spelling and come back as null
+        assert vexy_json.parse_into_json_bytes("[1e999, -1e999]") == b"[null,null]"
+
+        with pytest.raises(vexy_json.JSONDecodeError):
+            vexy_json.parse_into_json_bytes("[1, @]")
+
+    def test_parse_into_dataclass(self):
+        """Object members become keyword arguments."""
+        import dataclasses
+
+        @dataclasses.dataclass
+        class Point:
+            x: int
+            y: int
+            tags: list
+
+        point = vexy_json.parse_into("x: 1\ny: 2\ntags: [a, b]", Point)
+        assert point == Point(x=1, y=2, tags=["a", "b"])
+
+        with pytest.raises(TypeError):
+            vexy_json.parse_into("[1, 2]", Point)
+        with pytest.raises(TypeError):
+            vexy_json.parse_into("x: 1", Point)
+
+
+class TestDumps:
+    """Test serializing Python objects."""
+
+    def test_nul_round_trip(self):
+        """NUL characters are escaped on output and restored on input."""
+        import json
+
+        value = vexy_json.parse('"a\\u0000b"')
+        assert value == "a\x00b"
+        for text in (vexy_json.dumps([value]), vexy_json.dumps([value], indent=2)):
+            assert "\\u0000" in text
+            assert "\x00" not in text
+            assert json.loads(text) == [value]
+            assert vexy_json.parse(text) == [value]
+
+
+class TestStats:
+    """Test document introspection."""
+
+    def test_stats(self):
+        """Counts, depth and sizes come back as a dict of ints."""
+        stats = vexy_json.stats('{"a": [1, "xy", null], b: {c: true}}')
+        assert stats["objects"] == 2
+        assert stats["arrays"] == 1
+        assert stats["values"] == 7
+        assert stats["max_depth"] == 2
+        assert stats["string_bytes"] == 5
+        assert stats["heap_bytes"] > 0
+
+        with pytest.raises(vexy_json.JSONDecodeError):
+            vexy_json.stats("[1, @]")
+
+
 if __name__ == "__main__":
     pytest.main([__file__])
@@ -0,0 +1,51 @@
+"""
+Benchmarks for handing parsed documents to pydantic.
+
+Compares building Python objects with `parse` and validating them against
+validating the bytes from `parse_into_json_bytes`, which skips the dict
+intermediate. Run with `pytest tests/test_benchmark_parse_into.py
+--benchmark-only`; skipped unless pydantic and pytest-benchmark are installed.
+"""
+
+from typing import List
+
+import pytest
+import vexy_json
+
+pydantic = pytest.importorskip("pydantic")
+pytest.importorskip("pytest_benchmark")
+
+
+class Item(pydantic.BaseModel):
+    id: int
+    name: str
+    price: float
+    tags: List[str]
+
+
+class Catalog(pydantic.BaseModel):
+    items: List[Item]
+
+
+DOC = "items: [\n" + "".join(
+    f"  {{id: {i}, name: 'item {i}', price: {i}.5, tags: [a, b, c]}},\n"
+    for i in range(10_000)
+) + "]"
+
+
+def test_parse_then_validate(benchmark):
+    catalog = benchmark(lambda: Catalog.model_validate(vexy_json.parse(DOC)))
+    assert len(catalog.items) == 10_000
+
+
+def test_json_bytes_then_validate(benchmark):
+    catalog = benchmark(
+        lambda: Catalog.model_validate_json(vexy_json.parse_into_json_bytes(DOC))
+    )
+    assert len(catalog.items) == 10_000
+
+
+def test_results_match():
+    from_dict = Catalog.model_validate(vexy_json.parse(DOC))
+    from_bytes = Catalog.model_validate_json(vexy_json.parse_into_json_bytes(DOC))
+    assert from_dict == from_bytes
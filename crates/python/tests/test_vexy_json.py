@@ -1,22 +1,306 @@
-#!/usr/bin/env python3
-# Test file for vexy_json renaming
+"""
+Tests for the public API of the vexy_json package as a whole.
+"""
 
+import io
+import json
+import types
+import warnings
+
+import pytest
 import vexy_json
-from vexy_json import VexyJSONParser, VexyJSONConfig
 
 
-class VexyJSONWrapper:
-    """A wrapper for Vexy JSON functionality"""
+def _exercise_streaming_parser():
+    with vexy_json.StreamingParser(allow_comments=False) as parser:
+        items = list(parser.parse_lines(io.StringIO('{"a": 1}\n[2]\n')))
+    assert items == [{"a": 1}, [2]]
+
+
+def _exercise_loads_numpy():
+    pytest.importorskip("numpy")
+    assert vexy_json.loads_numpy("[1, 2, 3]").tolist() == [1, 2, 3]
+
+
+def _exercise_loads_numpy_zerocopy():
+    pytest.importorskip("numpy")
+    array = vexy_json.loads_numpy_zerocopy("[1.0, 2.5]", dtype="float64")
+    assert array.tolist() == [1.0, 2.5]
+
+
+def _exercise_loads_dataframe():
+    pytest.importorskip("pandas")
+    frame = vexy_json.loads_dataframe('[{"a": 1}, {"a": 2}]')
+    assert list(frame["a"]) == [1, 2]
+
+
+def _exercise_dump():
+    out = io.StringIO()
+    vexy_json.dump({"b": 1, "a": [2]}, out)
+    assert out.getvalue() == '{"a":[2],"b":1}'
+
+
+def _exercise_decode_error(cls):
+    with pytest.raises(cls) as info:
+        vexy_json.parse("[1, @]")
+    assert isinstance(info.value, ValueError)
+    assert info.value.pos == 4
+
+
+# One check per name in vexy_json.__all__
+EXERCISES = {
+    "parse": lambda: vexy_json.parse("a: [1, 2,]") == {"a": [1, 2]},
+    "loads": lambda: vexy_json.loads("'x'") == "x",
+    "parse_with_options": lambda: vexy_json.parse_with_options(
+        "[1, 2]", vexy_json.Options.strict(), max_depth=4
+    )
+    == [1, 2],
+    "parse_into_json_bytes": lambda: vexy_json.parse_into_json_bytes("a: 1")
+    == b'{"a":1}',
+    "parse_into": lambda: vexy_json.parse_into("a: 1", dict) == {"a": 1},
+    "is_valid": lambda: vexy_json.is_valid("[1,]") and not vexy_json.is_valid("[1,"),
+    "format_error": lambda: "^"
+    in vexy_json.format_error("[1,", vexy_json.is_valid("[1,", return_error=True)),
+    "stats": lambda: vexy_json.stats("[[1]]")["max_depth"] == 2,
+    "dumps": lambda: vexy_json.dumps({"a": None}, indent=2) == '{\n  "a": null\n}',
+    "load": lambda: vexy_json.load(io.StringIO("[1]"), allow_trailing_commas=False)
+    == [1],
+    "dump": _exercise_dump,
+    "loads_numpy": _exercise_loads_numpy,
+    "loads_numpy_zerocopy": _exercise_loads_numpy_zerocopy,
+    "loads_dataframe": _exercise_loads_dataframe,
+    "Options": lambda: vexy_json.Options(max_depth=3).max_depth == 3,
+    "Parser": lambda: vexy_json.Parser().parse("[1]") == [1],
+    "ParseResult": lambda: isinstance(
+        vexy_json.Parser().parse_detailed("[1]"), vexy_json.ParseResult
+    ),
+    "Repair": lambda: all(
+        isinstance(repair, vexy_json.Repair)
+        for repair in vexy_json.Parser().parse_detailed("[1, 2").repairs
+    ),
+    "StreamingParser": _exercise_streaming_parser,
+    "JSONDecodeError": lambda: _exercise_decode_error(vexy_json.JSONDecodeError),
+    "ParseError": lambda: _exercise_decode_error(vexy_json.ParseError),
+    "__version__": lambda: bool(vexy_json.__version__),
+    "__author__": lambda: bool(vexy_json.__author__),
+    "__description__": lambda: bool(vexy_json.__description__),
+}
+
+
+class TestPublicAPI:
+    """Test that the package exports each name once and that each works."""
+
+    def test_all_lists_every_public_name(self):
+        """Every public attribute is in __all__, and nothing else is."""
+        public = {
+            name
+            for name in dir(vexy_json)
+            if not name.startswith("_")
+            and not isinstance(getattr(vexy_json, name), types.ModuleType)
+        }
+        dunders = {name for name in vexy_json.__all__ if name.startswith("__")}
+        assert public | dunders == set(vexy_json.__all__)
+        assert len(vexy_json.__all__) == len(set(vexy_json.__all__))
+
+    def test_names_are_not_duplicated(self):
+        """Only the documented aliases share an object."""
+        names_by_object = {}
+        for name in vexy_json.__all__:
+            names_by_object.setdefault(id(getattr(vexy_json, name)), []).append(name)
+        shared = sorted(sorted(names) for names in names_by_object.values() if len(names) > 1)
+        assert shared == [["JSONDecodeError", "ParseError"], ["loads", "parse"]]
+
+    def test_native_module_has_no_aliases(self):
+        """The extension module registers each function under one name."""
+        from vexy_json import _vexy_json
+
+        native = {name for name in dir(_vexy_json) if not name.startswith("_")}
+        assert native == set(vexy_json.__all__) - {"loads"} - {
+            name for name in vexy_json.__all__ if name.startswith("__")
+        }
+
+    def test_every_name_is_exercised(self):
+        """The table below covers exactly the exported names."""
+        assert set(EXERCISES) == set(vexy_json.__all__)
+
+    @pytest.mark.parametrize("name", sorted(EXERCISES))
+    def test_exercise(self, name):
+        """Each exported name does what it is for."""
+        assert EXERCISES[name]() in (True, None)
+
+
+class TestOptions:
+    """Test the Options class."""
+
+    def test_default_options(self):
+        """Options() and Options.default() are the forgiving defaults."""
+        for opts in (vexy_json.Options(), vexy_json.Options.default()):
+            assert opts.allow_comments is True
+            assert opts.allow_trailing_commas is True
+            assert opts.allow_unquoted_keys is True
+            assert opts.max_depth == 128
+            assert opts.fast_repair is False
+
+    def test_strict_options(self):
+        """Options.strict() accepts standard JSON only."""
+        opts = vexy_json.Options.strict()
+        assert opts.allow_comments is False
+        assert opts.allow_trailing_commas is False
+        assert opts.allow_unquoted_keys is False
+        assert opts.enable_repair is False
+
+        assert vexy_json.parse_with_options('{"valid": true}', opts) == {"valid": True}
+        for invalid in ["{unquoted: true}", "[1,]", "// c\n1", "'x'", "[1, 2"]:
+            with pytest.raises(vexy_json.JSONDecodeError):
+                vexy_json.parse_with_options(invalid, opts)
+
+    def test_custom_options(self):
+        """Options take keyword arguments and can be changed afterwards."""
+        opts = vexy_json.Options(allow_comments=False, max_depth=50)
+        assert opts.allow_comments is False
+        assert opts.allow_trailing_commas is True
+        assert opts.max_depth == 50
+
+        opts.allow_comments = True
+        assert vexy_json.parse_with_options("[1] // c", opts) == [1]
+        assert "max_depth=50" in repr(opts)
+
+    def test_limits(self):
+        """Size limits are off by default and name the path when exceeded."""
+        opts = vexy_json.Options()
+        assert opts.max_array_len is None
+        assert opts.max_string_len is None
+
+        opts = vexy_json.Options(max_array_len=3, max_key_len=4)
+        assert vexy_json.parse_with_options("{a: [1, 2, 3]}", opts) == {"a": [1, 2, 3]}
+        with pytest.raises(vexy_json.JSONDecodeError, match=r"at path \$\.a\[3\]"):
+            vexy_json.parse_with_options("{a: [1, 2, 3, 4]}", opts)
+        with pytest.raises(vexy_json.JSONDecodeError, match="key length"):
+            vexy_json.parse_with_options("{abcde: 1}", opts)
+        with pytest.raises(vexy_json.JSONDecodeError, match="object member count"):
+            vexy_json.parse_with_options("{a: 1, b: 2}", max_object_members=1)
+        assert "max_array_len=3" in repr(opts)
+        assert "max_string_len=None" in repr(opts)
+
+    def test_unknown_option(self):
+        """Misspelled options are errors rather than ignored."""
+        with pytest.raises(TypeError):
+            vexy_json.Options(allow_comment=False)
+        with pytest.raises(TypeError):
+            vexy_json.parse_with_options("1", allow_comment=False)
+        with pytest.raises(TypeError):
+            vexy_json.StreamingParser(allow_comment=False)
+
+    def test_keywords_override_options(self):
+        """Keyword arguments win over the options object without changing it."""
+        opts = vexy_json.Options.strict()
+        assert vexy_json.parse_with_options("[1,]", opts, allow_trailing_commas=True) == [1]
+        assert opts.allow_trailing_commas is False
+        assert vexy_json.load(io.StringIO("[1,]"), opts, allow_trailing_commas=True) == [1]
+
+
+class TestParser:
+    """Test the Parser class."""
+
+    def test_parser_with_options(self):
+        """A parser keeps the options it was made with."""
+        parser = vexy_json.Parser(vexy_json.Options(allow_trailing_commas=False))
+        assert parser.parse('{"valid": true}') == {"valid": True}
+        with pytest.raises(vexy_json.JSONDecodeError):
+            parser.parse("[1,]")
+        assert parser.options.allow_trailing_commas is False
+        assert vexy_json.Parser(max_depth=8).options.max_depth == 8
+
+    def test_parser_reuse(self):
+        """One parser can parse many documents."""
+        parser = vexy_json.Parser()
+        inputs = ['{"a": 1}', "[1, 2, 3]", '"hello"', "true"]
+        assert [parser.parse(s) for s in inputs] == [{"a": 1}, [1, 2, 3], "hello", True]
+
+    def test_parse_detailed_tiers(self):
+        """parse_detailed reports how each document was parsed."""
+        parser = vexy_json.Parser()
+        assert parser.parse_detailed('{"a": 1}').tier == "fast"
+
+        result = parser.parse_detailed("{a: 1}")
+        assert (result.data, result.tier, result.repairs) == ({"a": 1}, "forgiving", [])
+
+    def test_parse_detailed_repairs(self):
+        """Repairs are reported with character positions."""
+        doc = '{"é": [1, 2}'
+        result = vexy_json.Parser().parse_detailed(doc)
+        assert result.data == {"é": [1, 2]}
+        assert result.tier == "repair"
+        assert result.repairs
+        for repair in result.repairs:
+            assert repair.repair_type.islower()
+            assert 0 <= repair.position <= len(doc)
+            assert repair.description
+
+    def test_parse_functions_detailed(self):
+        """The parse functions report the tier when asked to."""
+        result = vexy_json.parse("{a:1,", detailed=True)
+        assert isinstance(result, vexy_json.ParseResult)
+        assert (result.data, result.tier) == ({"a": 1}, "repair")
+        assert vexy_json.parse('{"a": 1}', detailed=True).tier == "fast"
+        assert vexy_json.parse('{"a": 1}') == {"a": 1}
+
+        result = vexy_json.parse_with_options("a: 1", detailed=True, implicit_top_level=True)
+        assert (result.data, result.tier) == ({"a": 1}, "forgiving")
+        with pytest.raises(vexy_json.JSONDecodeError):
+            vexy_json.parse_with_options("{a:1,", detailed=True, enable_repair=False)
+
+    def test_parse_detailed_error(self):
+        """Documents that cannot be parsed or repaired raise."""
+        parser = vexy_json.Parser(enable_repair=False)
+        with pytest.raises(vexy_json.JSONDecodeError):
+            parser.parse_detailed("{{{invalid}}}")
+
+
+class TestCompat:
+    """Test the vexy_json.compat module for code using older names."""
+
+    def test_reexports_current_api(self):
+        """compat can stand in for the package."""
+        from vexy_json import compat
+
+        for name in vexy_json.__all__:
+            assert hasattr(compat, name), name
+        assert compat.parse is vexy_json.parse
+        for name in compat.__all__:
+            assert hasattr(compat, name), name
+
+    def test_older_names_warn(self):
+        """Older names still work, with a DeprecationWarning."""
+        from vexy_json import compat
+
+        with pytest.deprecated_call():
+            assert compat.version() == vexy_json.__version__
+        with pytest.deprecated_call():
+            assert compat.parse_json("[1,]") == [1]
+        with pytest.deprecated_call():
+            assert compat.parse_with_options_py("[1]", allow_comments=False) == [1]
+
+    def test_file_names(self, tmp_path):
+        """load and dump accept file names as well as files."""
+        from vexy_json import compat
 
-    def __init__(self):
-        self.parser = VexyJSONParser()
-        self.config = VexyJSONConfig()
+        path = tmp_path / "data.json"
+        data = {"name": "Test", "values": [1, 2, 3]}
+        with pytest.deprecated_call():
+            compat.dump(data, str(path), indent=2, sort_keys=True)
+        assert json.loads(path.read_text()) == data
+        assert '  "name"' in path.read_text()
 
-    def parse(self, data):
-        # Parse vexy_json data
-        return self.parser.parse(data)
+        with pytest.deprecated_call():
+            assert compat.load(str(path)) == data
+        with pytest.deprecated_call():
+            assert compat.load(str(path), vexy_json.Options(enable_repair=False)) == data
 
+    def test_dumps_accepts_sort_keys(self):
+        """dumps takes the older sort_keys argument without warning."""
+        from vexy_json import compat
 
-# Test with a string that contains "vexy_json"
-test_string = "This is a vexy_json parser"
-print(f"Vexy JSON version: {vexy_json.__version__}")
+        with warnings.catch_warnings():
+            warnings.simplefilter("error")
+            assert compat.dumps({"b": 1, "a": 2}, sort_keys=True) == '{"a":2,"b":1}'
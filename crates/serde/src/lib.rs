@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 use vexy_json_core::ast::Value;
 
-// This is a placeholder for Serde integration.
-// Actual implementation would involve implementing Serialize/Deserialize for vexy_json_core::Value
-// or providing helper functions for conversion.
-
+/// A [`Value`] in a newtype.
+///
+/// `Value` implements `Serialize` and `Deserialize` itself, as the JSON it
+/// stands for, so it can be used in serde structs directly; this wrapper
+/// serializes the same way.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct SerdeValue(Value);
 
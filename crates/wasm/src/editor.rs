@@ -0,0 +1,337 @@
+//! Editor-facing diagnostics for the web playground, and parse results
+//! that say how the input was parsed.
+//!
+//! CodeMirror and Monaco address text in UTF-16 code units, while the parser
+//! reports byte offsets into the UTF-8 input. Everything here is converted
+//! with [`utf16_offset`] before it leaves Rust.
+
+use serde::Serialize;
+use vexy_json_core::ast::Value;
+use vexy_json_core::error::{Error, RepairAction, Span};
+use vexy_json_core::{parse_with_fallback, parse_with_options, Limits, ParserOptions};
+
+/// A problem to underline in the editor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// Start of the range, in UTF-16 code units
+    pub from: usize,
+    /// End of the range, in UTF-16 code units
+    pub to: usize,
+    /// Always `"error"` for now; named as both editors name it
+    pub severity: &'static str,
+    /// Human-readable description
+    pub message: String,
+    /// Stable error code such as `E1001`
+    pub code: &'static str,
+}
+
+/// A change the repairer made, for decorating the editor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RepairDecoration {
+    /// Start of the replaced text, in UTF-16 code units
+    pub from: usize,
+    /// End of the replaced text, in UTF-16 code units; equal to `from` for
+    /// insertions
+    pub to: usize,
+    /// `"insert"`, `"remove"` or `"replace"`
+    pub kind: &'static str,
+    /// The text that was replaced
+    pub before: String,
+    /// The text put in its place
+    pub after: String,
+    /// Human-readable description
+    pub description: String,
+}
+
+/// A parse error described for showing to a person.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    /// Human-readable description
+    pub message: String,
+    /// Stable error code such as `E1001`
+    pub code: &'static str,
+    /// Line of the error, starting at 1
+    pub line: usize,
+    /// Column of the error in UTF-16 code units, starting at 1
+    pub column: usize,
+    /// The numbered lines around the error, with a caret under it
+    pub snippet: String,
+    /// Ways to fix the error
+    pub suggestions: Vec<&'static str>,
+}
+
+/// A parsed document and the tier that parsed it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParseDetails {
+    /// The document
+    pub value: Value,
+    /// `"fast"` for standard JSON, `"forgiving"` for the forgiving parser,
+    /// or `"repair"` when the input had to be repaired first
+    pub tier: &'static str,
+}
+
+/// Lines of context shown above and below the error in a snippet
+const SNIPPET_CONTEXT_LINES: usize = 2;
+
+/// Converts a byte offset into `input` to a UTF-16 code unit offset.
+///
+/// Offsets past the end of the input are clamped to it, and offsets inside
+/// a multi-byte character are moved back to its start.
+pub fn utf16_offset(input: &str, byte_offset: usize) -> usize {
+    let mut end = byte_offset.min(input.len());
+    while !input.is_char_boundary(end) {
+        end -= 1;
+    }
+    input[..end].encode_utf16().count()
+}
+
+/// Reads parser options from their JSON form, using the defaults for
+/// missing fields and when no options are given.
+pub fn options_from_json(json: Option<&str>) -> Result<ParserOptions, String> {
+    match json {
+        Some(json) => serde_json::from_str(json).map_err(|e| format!("Invalid options: {e}")),
+        None => Ok(ParserOptions::default()),
+    }
+}
+
+/// Reads size limits from their JSON form, with no limit for missing
+/// fields and when no limits are given.
+pub fn limits_from_json(json: Option<&str>) -> Result<Limits, String> {
+    match json {
+        Some(json) => serde_json::from_str(json).map_err(|e| format!("Invalid limits: {e}")),
+        None => Ok(Limits::default()),
+    }
+}
+
+/// Returns the errors in `input`, or nothing if it parses.
+pub fn lint(input: &str, options: ParserOptions) -> Vec<Diagnostic> {
+    match parse_with_options(input, options) {
+        Ok(_) => Vec::new(),
+        Err(error) => vec![diagnostic(input, &error)],
+    }
+}
+
+/// Parses `input` with the fast, forgiving and repair tiers in turn,
+/// reporting which one succeeded.
+pub fn parse_detailed(input: &str, options: ParserOptions) -> Result<ParseDetails, Error> {
+    let mut result = parse_with_fallback(input, options);
+    if !result.errors.is_empty() {
+        return Err(result.errors.swap_remove(0));
+    }
+    Ok(ParseDetails {
+        value: result.value,
+        tier: result.parsing_tier.as_str(),
+    })
+}
+
+/// Explains why `input` fails to parse, or returns nothing if it parses.
+pub fn explain(input: &str, options: ParserOptions) -> Option<Explanation> {
+    let error = parse_with_options(input, options).err()?;
+    Some(explanation(input, &error))
+}
+
+/// Returns the repairs needed to parse `input`.
+///
+/// Empty when the input parses without repair, and also when even the
+/// repaired input fails to parse, since [`lint`] reports that error.
+pub fn repair_decorations(input: &str, options: ParserOptions) -> Vec<RepairDecoration> {
+    let result = parse_with_fallback(input, options);
+    if !result.errors.is_empty() {
+        return Vec::new();
+    }
+    result
+        .repairs
+        .iter()
+        .map(|repair| decoration(input, repair))
+        .collect()
+}
+
+fn diagnostic(input: &str, error: &Error) -> Diagnostic {
+    let (start, end) = match error.span() {
+        Some(span) if span.end > span.start + 1 => (span.start, span.end),
+        // A single position covers the whole character there, if any
+        Some(span) => {
+            let start = span.start.min(input.len());
+            let width = input
+                .get(start..)
+                .and_then(|rest| rest.chars().next())
+                .map_or(0, char::len_utf8);
+            (start, start + width)
+        }
+        None => (input.len(), input.len()),
+    };
+    Diagnostic {
+        from: utf16_offset(input, start),
+        to: utf16_offset(input, end),
+        severity: "error",
+        message: error.to_string(),
+        code: error.code().as_str(),
+    }
+}
+
+fn explanation(input: &str, error: &Error) -> Explanation {
+    let span = error.span().unwrap_or(Span::single(input.len()));
+    let window = span.context_window(input, SNIPPET_CONTEXT_LINES);
+    let start = span.start.min(input.len());
+    let line_start = input.as_bytes()[..start]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    Explanation {
+        message: error.to_string(),
+        code: error.code().as_str(),
+        line: window.first_line + window.error_line,
+        column: utf16_offset(input, start) - utf16_offset(input, line_start) + 1,
+        snippet: window.format_plain(),
+        suggestions: error.suggestions(),
+    }
+}
+
+fn decoration(input: &str, repair: &RepairAction) -> RepairDecoration {
+    let kind = match (repair.original.is_empty(), repair.replacement.is_empty()) {
+        (true, _) => "insert",
+        (false, true) => "remove",
+        (false, false) => "replace",
+    };
+    RepairDecoration {
+        from: utf16_offset(input, repair.position),
+        to: utf16_offset(input, repair.position + repair.original.len()),
+        kind,
+        before: repair.original.clone(),
+        after: repair.replacement.clone(),
+        description: repair.description.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf16_offset_counts_surrogate_pairs() {
+        let input = "a😀é\"𝄞\"";
+        // 'a' 1 byte, '😀' 4 bytes, 'é' 2 bytes, '"' 1 byte, '𝄞' 4 bytes
+        assert_eq!(utf16_offset(input, 0), 0);
+        assert_eq!(utf16_offset(input, 1), 1);
+        assert_eq!(utf16_offset(input, 5), 3);
+        assert_eq!(utf16_offset(input, 7), 4);
+        assert_eq!(utf16_offset(input, 8), 5);
+        assert_eq!(utf16_offset(input, 12), 7);
+        assert_eq!(utf16_offset(input, input.len()), 8);
+        assert_eq!(input.encode_utf16().count(), 8);
+
+        // Inside a character and past the end
+        assert_eq!(utf16_offset(input, 3), 1);
+        assert_eq!(utf16_offset(input, 100), 8);
+    }
+
+    #[test]
+    fn test_lint_reports_utf16_range() {
+        assert!(lint(r#"{"a": 1}"#, ParserOptions::default()).is_empty());
+
+        let diagnostics = lint(r#"{"😀": [1, @]}"#, ParserOptions::default());
+        assert_eq!(diagnostics.len(), 1);
+        let diagnostic = &diagnostics[0];
+        // '@' is at byte 13 but UTF-16 offset 11
+        assert_eq!((diagnostic.from, diagnostic.to), (11, 12));
+        assert_eq!(diagnostic.severity, "error");
+        assert_eq!(diagnostic.code, "E1001");
+        assert!(diagnostic.message.contains("'@'"));
+    }
+
+    #[test]
+    fn test_repair_decorations() {
+        assert!(repair_decorations("[1, 2]", ParserOptions::default()).is_empty());
+
+        let decorations = repair_decorations("[\"𝄞\", [2, 3", ParserOptions::default());
+        assert_eq!(decorations.len(), 1);
+        let decoration = &decorations[0];
+        // The insertion point is byte 14, after a four-byte character
+        assert_eq!((decoration.from, decoration.to), (12, 12));
+        assert_eq!(decoration.kind, "insert");
+        assert_eq!(decoration.before, "");
+
+        let decorations = repair_decorations("[1, 2]]", ParserOptions::default());
+        assert_eq!(decorations[0].kind, "remove");
+        assert_eq!((decorations[0].from, decorations[0].to), (6, 7));
+        assert_eq!(decorations[0].before, "]");
+    }
+
+    #[test]
+    fn test_parse_detailed_tiers() {
+        let details = parse_detailed(r#"{"a": [1, 2.5]}"#, ParserOptions::default()).unwrap();
+        assert_eq!(details.tier, "fast");
+        assert_eq!(
+            details.value,
+            vexy_json_core::parse("{a: [1, 2.5]}").unwrap()
+        );
+
+        assert_eq!(
+            parse_detailed("{a: 1}", ParserOptions::default())
+                .unwrap()
+                .tier,
+            "forgiving"
+        );
+
+        let details = parse_detailed("{a:1,", ParserOptions::default()).unwrap();
+        assert_eq!(details.tier, "repair");
+        assert_eq!(
+            serde_json::to_string(&details).unwrap(),
+            r#"{"value":{"a":1},"tier":"repair"}"#
+        );
+
+        let options = ParserOptions {
+            enable_repair: false,
+            ..Default::default()
+        };
+        assert!(parse_detailed("{a:1,", options).is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        assert_eq!(explain("[1, 2]", ParserOptions::default()), None);
+
+        let explanation = explain("{\"a\": 1,\n \"😀\": @}", ParserOptions::default()).unwrap();
+        assert_eq!(explanation.code, "E1001");
+        assert!(explanation.message.contains("'@'"));
+        // '@' follows a character of two UTF-16 code units
+        assert_eq!((explanation.line, explanation.column), (2, 8));
+        // and takes up two columns on screen
+        assert!(
+            explanation
+                .snippet
+                .contains("   2 |  \"😀\": @}\n     |        ^"),
+            "{}",
+            explanation.snippet
+        );
+        assert!(!explanation.suggestions.is_empty());
+
+        // At the end, after a final line break
+        let explanation = explain("[1,\n", ParserOptions::default()).unwrap();
+        assert_eq!((explanation.line, explanation.column), (2, 1));
+    }
+
+    #[test]
+    fn test_options_from_json() {
+        let options = options_from_json(Some(r#"{"allow_comments": false}"#)).unwrap();
+        assert!(!options.allow_comments);
+        assert!(options.allow_trailing_commas);
+        assert!(options_from_json(None).unwrap().allow_comments);
+        assert!(options_from_json(Some("[1]")).is_err());
+
+        // Limits, as an options field or on their own
+        let options = options_from_json(Some(r#"{"limits": {"max_array_len": 2}}"#)).unwrap();
+        assert_eq!(options.limits.max_array_len, Some(2));
+        let limits = limits_from_json(Some(r#"{"max_key_len": 4}"#)).unwrap();
+        assert_eq!(limits.max_key_len, Some(4));
+        assert_eq!(limits.max_array_len, None);
+        assert_eq!(limits_from_json(None).unwrap(), Limits::default());
+
+        let diagnostics = lint("{a: [1, 2, 3]}", options);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(
+            diagnostics[0].message.ends_with("at path $.a[2]"),
+            "{diagnostics:?}"
+        );
+    }
+}
@@ -1,7 +1,10 @@
-use serde_json::Value;
+use serde::Serialize;
+use vexy_json_core::format::{to_compact_string, to_pretty_string, KeyOrder, StripCommentsOptions};
 use vexy_json_core::{parse, parse_with_options, ParserOptions};
 use wasm_bindgen::prelude::*;
 
+mod editor;
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -17,17 +20,22 @@ macro_rules! console_log {
 #[wasm_bindgen]
 pub fn parse_json(input: &str) -> Result<String, JsValue> {
     match parse(input) {
-        Ok(value) => {
-            // Convert to serde_json::Value for proper JSON serialization
-            let json_value: Value = serde_json::from_str(&value.to_string())
-                .map_err(|e| JsValue::from_str(&e.to_string()))?;
-            serde_json::to_string(&json_value).map_err(|e| JsValue::from_str(&e.to_string()))
-        }
+        Ok(value) => Ok(to_compact_string(&value, &KeyOrder::default())),
         Err(e) => Err(JsValue::from_str(&format!("Parse error: {e}"))),
     }
 }
 
 /// Parse a JSON/Vexy JSON string with custom options
+///
+/// With `detailed` set, returns `{"value": ..., "tier": ...}` instead of
+/// the document alone, where `tier` is `"fast"` for standard JSON,
+/// `"forgiving"` for the forgiving parser, or `"repair"` when the input was
+/// repaired before it would parse.
+///
+/// `limits` is an object with any of `max_array_len`, `max_object_members`,
+/// `max_key_len` and `max_string_len`, or undefined for no limits. A
+/// document past one fails with an error naming the limit and the path to
+/// the value.
 #[wasm_bindgen]
 #[allow(clippy::too_many_arguments)]
 pub fn parse_json_with_options(
@@ -40,7 +48,11 @@ pub fn parse_json_with_options(
     newline_as_comma: bool,
     enable_repair: bool,
     max_depth: Option<u32>,
+    detailed: Option<bool>,
+    limits: JsValue,
 ) -> Result<String, JsValue> {
+    let limits = editor::limits_from_json(stringify(&limits)?.as_deref())
+        .map_err(|e| JsValue::from_str(&e))?;
     let options = ParserOptions {
         allow_comments,
         allow_trailing_commas,
@@ -53,15 +65,20 @@ pub fn parse_json_with_options(
         fast_repair: false,
         max_repairs: 100,
         report_repairs: false,
+        limits,
+        ..Default::default()
     };
 
+    if detailed == Some(true) {
+        return match editor::parse_detailed(input, options) {
+            Ok(details) => {
+                serde_json::to_string(&details).map_err(|e| JsValue::from_str(&e.to_string()))
+            }
+            Err(e) => Err(JsValue::from_str(&format!("Parse error: {e}"))),
+        };
+    }
     match parse_with_options(input, options) {
-        Ok(value) => {
-            // Convert to serde_json::Value for proper JSON serialization
-            let json_value: Value = serde_json::from_str(&value.to_string())
-                .map_err(|e| JsValue::from_str(&e.to_string()))?;
-            serde_json::to_string(&json_value).map_err(|e| JsValue::from_str(&e.to_string()))
-        }
+        Ok(value) => Ok(to_compact_string(&value, &KeyOrder::default())),
         Err(e) => Err(JsValue::from_str(&format!("Parse error: {e}"))),
     }
 }
@@ -72,6 +89,62 @@ pub fn validate_json(input: &str) -> bool {
     parse(input).is_ok()
 }
 
+/// Lint a JSON/Vexy JSON string for an editor
+///
+/// Returns an array of `{from, to, severity, message, code}` ranges in
+/// UTF-16 code units, as CodeMirror and Monaco expect; empty if the input
+/// parses. `options` is an object with any of the parser option fields
+/// listed by `get_parser_options`, or undefined for the defaults.
+#[wasm_bindgen]
+pub fn lint(input: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options = options_from_js(&options)?;
+    to_js(&editor::lint(input, options))
+}
+
+/// Describe the repairs needed to parse a JSON/Vexy JSON string
+///
+/// Returns an array of `{from, to, kind, before, after, description}`
+/// decorations in UTF-16 code units, where `kind` is `insert`, `remove` or
+/// `replace`. Empty if the input parses without repair or cannot be
+/// repaired. `options` is as for `lint`.
+#[wasm_bindgen]
+pub fn repair_annotations(input: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options = options_from_js(&options)?;
+    to_js(&editor::repair_decorations(input, options))
+}
+
+/// Explain why a JSON/Vexy JSON string fails to parse
+///
+/// Returns `{message, code, line, column, snippet, suggestions}`, where
+/// `line` and `column` start at 1 and `column` counts UTF-16 code units,
+/// `snippet` shows the lines around the error with a caret under it, and
+/// `suggestions` is an array of strings. Returns null if the input parses.
+/// `options` is as for `lint`.
+#[wasm_bindgen]
+pub fn explain_error(input: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let options = options_from_js(&options)?;
+    to_js(&editor::explain(input, options))
+}
+
+fn options_from_js(options: &JsValue) -> Result<ParserOptions, JsValue> {
+    editor::options_from_json(stringify(options)?.as_deref()).map_err(|e| JsValue::from_str(&e))
+}
+
+/// `value` as JSON text, or `None` if it is undefined or null.
+fn stringify(value: &JsValue) -> Result<Option<String>, JsValue> {
+    if value.is_undefined() || value.is_null() {
+        Ok(None)
+    } else {
+        Ok(Some(String::from(js_sys::JSON::stringify(value)?)))
+    }
+}
+
+/// Converts `value` to plain JS objects and arrays.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    let json = serde_json::to_string(value).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    js_sys::JSON::parse(&json)
+}
+
 /// Get parser options as a JSON object
 #[wasm_bindgen]
 pub fn get_parser_options() -> Result<String, JsValue> {
@@ -107,6 +180,15 @@ pub fn get_parser_options() -> Result<String, JsValue> {
         "max_depth": {
             "default": 128,
             "description": "Maximum nesting depth"
+        },
+        "limits": {
+            "default": {
+                "max_array_len": null,
+                "max_object_members": null,
+                "max_key_len": null,
+                "max_string_len": null
+            },
+            "description": "Maximum elements in an array, distinct keys in an object, and bytes in a key or string value; null for no limit"
         }
     });
 
@@ -119,17 +201,21 @@ pub fn stringify_value(input: &str, indent: Option<u32>) -> Result<String, JsVal
     // First parse with vexy_json to handle forgiving syntax
     let value = parse(input).map_err(|e| JsValue::from_str(&e.to_string()))?;
 
-    // Convert to serde_json::Value
-    let json_value: Value =
-        serde_json::from_str(&value.to_string()).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let order = KeyOrder::default();
+    Ok(match indent {
+        Some(indent) => to_pretty_string(&value, indent as usize, &order),
+        None => to_compact_string(&value, &order),
+    })
+}
 
-    // Stringify with or without pretty printing
-    if let Some(_indent_size) = indent {
-        // TODO: Implement custom indentation size
-        serde_json::to_string_pretty(&json_value).map_err(|e| JsValue::from_str(&e.to_string()))
-    } else {
-        serde_json::to_string(&json_value).map_err(|e| JsValue::from_str(&e.to_string()))
-    }
+/// Remove comments and trailing commas, leaving every other byte as written
+#[wasm_bindgen]
+pub fn strip_comments(input: &str, keep_newlines: bool) -> Result<String, JsValue> {
+    let options = StripCommentsOptions {
+        keep_newlines,
+        ..Default::default()
+    };
+    vexy_json_core::strip_comments(input, &options).map_err(|e| JsValue::from_str(&e.to_string()))
 }
 
 /// Get version information
@@ -171,6 +257,8 @@ pub fn parse_with_options_js(
         newline_as_comma,
         true, // enable_repair
         None, // max_depth
+        None, // detailed
+        JsValue::UNDEFINED,
     )
 }
 
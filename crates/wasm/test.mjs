@@ -7,7 +7,16 @@ const __dirname = dirname(__filename);
 
 // Dynamically import the WASM module
 const wasmModule = await import(join(__dirname, 'pkg', 'vexy_json_wasm.js'));
-const { default: init, parse_js, parse_with_options_js, is_valid, format } = wasmModule;
+const {
+  default: init,
+  parse_js,
+  parse_with_options_js,
+  is_valid,
+  format,
+  lint,
+  repair_annotations,
+  strip_comments,
+} = wasmModule;
 
 // Initialize WASM with the WASM file path
 const wasmPath = join(__dirname, 'pkg', 'vexy_json_wasm_bg.wasm');
@@ -67,4 +76,43 @@ console.log('Input: ', test6Input);
 console.log('Output:', test6);
 console.log('✓ Formatting works\n');
 
+// Test 7: Editor diagnostics
+console.log('Test 7: Editor diagnostics');
+const test7 = lint('{"😀": [1, @]}', { allow_comments: false });
+console.log('Input:  {"😀": [1, @]}');
+console.log('Output:', JSON.stringify(test7));
+if (test7.length !== 1 || test7[0].from !== 11 || test7[0].to !== 12) {
+  throw new Error('lint ranges should be in UTF-16 code units');
+}
+const test7Repairs = repair_annotations('{"a": [1, 2');
+console.log('Repairs:', JSON.stringify(test7Repairs));
+if (test7Repairs.length !== 1 || test7Repairs[0].kind !== 'insert') {
+  throw new Error('repair_annotations should describe the inserted brackets');
+}
+console.log('✓ Editor diagnostics work\n');
+
+// Test 8: NUL characters
+console.log('Test 8: NUL characters');
+const test8Input = '["a\\u0000b"]';
+for (const output of [parse_js(test8Input), format(test8Input)]) {
+  console.log('Output:', output);
+  if (JSON.parse(output)[0] !== 'a\u0000b') {
+    throw new Error('NUL characters should survive as the escape \\u0000');
+  }
+}
+console.log('✓ NUL characters are escaped\n');
+
+// Test 9: Stripping comments
+console.log('Test 9: Stripping comments');
+const test9Input = '{\n  // note\n  "url": "http://x", /* y */\n}';
+const test9 = strip_comments(test9Input, false);
+console.log('Output:', test9);
+if (test9 !== '{\n  "url": "http://x"\n}') {
+  throw new Error('Comments and the trailing comma should be removed');
+}
+if (strip_comments(test9Input, true).split('\n').length !== 4) {
+  throw new Error('keep_newlines should keep every line');
+}
+console.log('✓ Comments are stripped\n');
+
 console.log('All tests passed!');
\ No newline at end of file
@@ -21,6 +21,7 @@ fuzz_target!(|data: &[u8]| {
             max_repairs: 0,
             fast_repair: false,
             report_repairs: false,
+            ..Default::default()
         };
         let _ = parse_with_options(s, strict_options);
         
@@ -37,6 +38,7 @@ fuzz_target!(|data: &[u8]| {
             max_repairs: 100,
             fast_repair: false,
             report_repairs: true,
+            ..Default::default()
         };
         let _ = parse_with_options(s, forgiving_options);
     }
@@ -37,6 +37,7 @@ fuzz_target!(|data: &[u8]| {
                 max_repairs: 0,
                 fast_repair: false,
                 report_repairs: false,
+                ..Default::default()
             };
             let _ = vexy_json::parse_with_options(&number_input, strict_options);
         }
@@ -0,0 +1,45 @@
+#![no_main]
+
+//! Breaks well-formed documents in small ways and checks that the fallback
+//! parser copes: it must not panic, and must not apply more repairs than
+//! `max_repairs` allows.
+
+use libfuzzer_sys::fuzz_target;
+use vexy_json_core::ast::Value;
+use vexy_json_core::format::{to_compact_string, KeyOrder};
+use vexy_json_core::{parse_with_fallback, ParserOptions};
+
+/// Most mutations applied to one document.
+const MAX_MUTATIONS: usize = 8;
+
+fuzz_target!(|input: (Value, Vec<(usize, u8, u8)>, u8)| {
+    let (value, mutations, max_repairs) = input;
+    let mut bytes = to_compact_string(&value, &KeyOrder::default()).into_bytes();
+    for (index, byte, kind) in mutations.into_iter().take(MAX_MUTATIONS) {
+        let len = bytes.len();
+        match kind % 3 {
+            0 => bytes[index % len] = byte,
+            1 => bytes.insert(index % (len + 1), byte),
+            _ if len > 1 => {
+                bytes.remove(index % len);
+            }
+            _ => {}
+        }
+    }
+    let text = String::from_utf8_lossy(&bytes);
+
+    let max_repairs = usize::from(max_repairs % 16);
+    for fast_repair in [false, true] {
+        let options = ParserOptions {
+            max_repairs,
+            fast_repair,
+            ..Default::default()
+        };
+        let result = parse_with_fallback(&text, options);
+        assert!(
+            result.repairs.len() <= max_repairs,
+            "{} repairs with max_repairs {max_repairs}: {text:?}",
+            result.repairs.len()
+        );
+    }
+});
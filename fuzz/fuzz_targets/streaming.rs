@@ -1,7 +1,9 @@
 #![no_main]
 
 use libfuzzer_sys::fuzz_target;
-use vexy_json_core::streaming::{parse_streaming, BufferedStreamingConfig};
+use vexy_json_core::streaming::{
+    parse_streaming, parse_streaming_with_config, BufferedStreamingConfig,
+};
 use std::io::Cursor;
 
 fuzz_target!(|data: &[u8]| {
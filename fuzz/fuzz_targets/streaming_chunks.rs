@@ -0,0 +1,78 @@
+#![no_main]
+
+//! Feeds text to the streaming parser in chunks split at arbitrary points,
+//! checking that the value it builds matches parsing the whole text at
+//! once, both with the streaming parser and with `parse_with_options`.
+
+use libfuzzer_sys::fuzz_target;
+use vexy_json_core::ast::Value;
+use vexy_json_core::streaming::{StreamingEvent, StreamingParser, StreamingValueBuilder};
+use vexy_json_core::{parse_with_options, ParserOptions, Result};
+
+/// The streaming parser has no implicit top-level objects or arrays, so
+/// both parsers run without them.
+fn options() -> ParserOptions {
+    ParserOptions {
+        implicit_top_level: false,
+        preserve_number_precision: true,
+        ..Default::default()
+    }
+}
+
+/// Streams `text` in chunks ending at each of `cuts`, moved back to the
+/// nearest character boundary.
+fn stream(text: &str, cuts: &[u16]) -> Result<Option<Value>> {
+    let mut ends: Vec<usize> = cuts
+        .iter()
+        .map(|&cut| {
+            let mut end = usize::from(cut) % (text.len() + 1);
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            end
+        })
+        .collect();
+    ends.sort_unstable();
+    ends.push(text.len());
+
+    let mut parser = StreamingParser::with_options(options());
+    let mut builder = StreamingValueBuilder::new();
+    let mut start = 0;
+    for end in ends {
+        parser.feed(&text[start..end])?;
+        start = end;
+        drain(&mut parser, &mut builder)?;
+    }
+    parser.finish()?;
+    drain(&mut parser, &mut builder)?;
+    builder.finish()
+}
+
+/// Hands every event the parser has ready to the builder.
+fn drain(parser: &mut StreamingParser, builder: &mut StreamingValueBuilder) -> Result<()> {
+    while let Some(event) = parser.next_event()? {
+        if event == StreamingEvent::EndOfInput {
+            break;
+        }
+        builder.process_event(event)?;
+    }
+    Ok(())
+}
+
+fuzz_target!(|input: (&str, Vec<u16>)| {
+    let (text, cuts) = input;
+    let chunked = stream(text, &cuts);
+    let whole = stream(text, &[]);
+    match (&chunked, &whole) {
+        (Ok(chunked), Ok(whole)) => assert_eq!(chunked, whole, "{text:?} cut at {cuts:?}"),
+        (Err(_), Err(_)) => {}
+        _ => panic!("{text:?} cut at {cuts:?}: {chunked:?} but {whole:?} in one chunk"),
+    }
+
+    if let (Ok(Some(streamed)), Ok(parsed)) = (&whole, parse_with_options(text, options())) {
+        assert!(
+            streamed.equals_semantic(&parsed),
+            "{text:?}: streamed {streamed:?} but parsed {parsed:?}"
+        );
+    }
+});
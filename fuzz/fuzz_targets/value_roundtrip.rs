@@ -0,0 +1,11 @@
+#![no_main]
+
+//! Writes arbitrary values as JSON and checks that they read back the same.
+
+use libfuzzer_sys::fuzz_target;
+use vexy_json_core::ast::Value;
+use vexy_json_core::testing::assert_roundtrip;
+
+fuzz_target!(|value: Value| {
+    assert_roundtrip(&value);
+});
@@ -219,7 +219,7 @@ update_version() {
         run_cmd "sed -i.bak 's/^version = .*/version = \"$VERSION\"/' Cargo.toml" "Update root Cargo.toml version"
 
         # Update all crate Cargo.toml files
-        local crates=("crates/core" "crates/cli" "crates/wasm" "crates/serde" "crates/test-utils" "crates/c-api" "bindings/python")
+        local crates=("crates/core" "crates/cli" "crates/wasm" "crates/serde" "crates/test-utils" "crates/c-api" "crates/python")
         for crate in "${crates[@]}"; do
             if [[ -f "$crate/Cargo.toml" ]]; then
                 run_cmd "sed -i.bak 's/^version = .*/version = \"$VERSION\"/' $crate/Cargo.toml" "Update $crate version"
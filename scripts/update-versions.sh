@@ -38,7 +38,7 @@ update_file() {
 
 # Update Cargo.toml files - only update package version, not dependency versions
 echo "Updating Cargo.toml files..."
-for toml in Cargo.toml crates/*/Cargo.toml bindings/*/Cargo.toml; do
+for toml in Cargo.toml crates/*/Cargo.toml; do
     if [ -f "$toml" ]; then
         # Only update the version in the [package] section, not in dependencies
         # This matches version at the start of a line (package version)
@@ -59,7 +59,6 @@ update_file "Cargo.toml" 'vexy_json = { version = ".*"' "s/vexy_json = { version
 
 # Update Python bindings
 echo "Updating Python bindings..."
-update_file "bindings/python/pyproject.toml" '^version = ".*"' "s/^version = \".*\"/version = \"$VERSION\"/"
 update_file "crates/python/src/lib.rs" '__version__ = ".*"' "s/__version__ = \".*\"/__version__ = \"$VERSION\"/"
 
 # Update package.json files
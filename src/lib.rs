@@ -21,18 +21,40 @@
 //! ```
 
 // Re-export core functionality
-pub use vexy_json_core::{parse, parse_with_options, Error, Lexer, ParserOptions, Result};
+pub use vexy_json_core::{
+    parse, parse_bytes, parse_bytes_with_options, parse_bytes_with_warnings, parse_concatenated,
+    parse_concatenated_with_options, parse_file, parse_file_with_options, parse_into,
+    parse_into_with_options, parse_partial, parse_to_pairs, parse_with_budget, parse_with_options,
+    tokenize, tokenize_with_options, BudgetLimit, CommentStyles, DuplicateKeyPolicy,
+    EmptyInputBehavior, Error, InvalidUtf8Policy, JsonParser, Lexer, Limits, NumberOverflowPolicy,
+    ParseBudget, ParserBackend, ParserOptions, ParserPool, ParserPoolStats, Result,
+    UnquotedValueMode,
+};
 
 // Re-export streaming functionality
 pub use vexy_json_core::{
-    NdJsonParser, SimpleStreamingLexer, StreamingEvent, StreamingParser, StreamingValueBuilder,
+    events_from_file, events_from_file_with_options, ndjson_to_array, to_ndjson, FileEvents,
+    NdJsonParser, SimpleStreamingLexer, StreamCheckpoint, StreamingEvent, StreamingParser,
+    StreamingValueBuilder,
 };
 
 // Re-export AST types
-pub use vexy_json_core::ast::{Number, Token, Value};
+#[cfg(feature = "indexmap")]
+pub use vexy_json_core::ast::OrderedValue;
+pub use vexy_json_core::ast::{Number, SortedValue, Token, Value, ValueG};
 
 // Re-export error types
-pub use vexy_json_core::error::{Error as ParseError, Result as ParseResult, Span};
+pub use vexy_json_core::error::{
+    CompatKind, CompatWarning, Error as ParseError, Result as ParseResult, Span, Warning,
+    WarningKind,
+};
+
+// Re-export error recovery suggestions
+pub use vexy_json_core::{analyze_error, RecoverySuggestion};
+
+// Re-export test assertions if feature is enabled
+#[cfg(feature = "testing")]
+pub use vexy_json_core::testing;
 
 // Re-export serde functionality if feature is enabled
 #[cfg(feature = "serde")]
@@ -172,7 +172,10 @@ mod value_edge_cases {
     fn test_boundary_numbers() {
         // Test numbers at various boundaries
         assert_eq!(parse("0").unwrap(), n(0));
-        assert_eq!(parse("-0").unwrap(), n(0));
+        assert!(matches!(
+            parse("-0").unwrap(),
+            Value::Number(vexy_json::Number::Float(z)) if z == 0.0 && z.is_sign_negative()
+        ));
         assert_eq!(parse("1").unwrap(), n(1));
         assert_eq!(parse("-1").unwrap(), n(-1));
 
@@ -272,8 +275,8 @@ mod formatting_tolerance {
             let input = format!("{space}{{{space}key{space}: {space}value{space}}}{space}");
             let result = parse(&input);
             // Some unicode spaces might not be recognized, but shouldn't crash
-            if result.is_ok() {
-                assert_eq!(result.unwrap()["key"], s("value"));
+            if let Ok(value) = result {
+                assert_eq!(value["key"], s("value"));
             }
         }
     }
@@ -558,6 +561,7 @@ mod configuration_edge_cases {
             max_repairs: 0,
             fast_repair: false,
             report_repairs: false,
+            ..Default::default()
         };
 
         // Standard JSON should work
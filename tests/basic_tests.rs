@@ -1,3 +1,5 @@
+#![allow(clippy::approx_constant)]
+
 use vexy_json::{parse, Value};
 
 #[test]
@@ -0,0 +1,90 @@
+use vexy_json::{parse, parse_with_options, Error, ParserOptions, StreamingEvent, StreamingParser};
+
+fn nested(allow: bool) -> ParserOptions {
+    ParserOptions {
+        allow_nested_block_comments: allow,
+        ..Default::default()
+    }
+}
+
+/// Collects the events the streaming parser emits for `input`.
+fn stream(input: &str, options: ParserOptions) -> vexy_json::Result<Vec<StreamingEvent>> {
+    let mut parser = StreamingParser::with_options(options);
+    parser.feed(input)?;
+    parser.finish()?;
+    let mut events = Vec::new();
+    while let Some(event) = parser.next_event()? {
+        if event == StreamingEvent::EndOfInput {
+            return Ok(events);
+        }
+        events.push(event);
+    }
+    Ok(events)
+}
+
+#[test]
+fn test_non_nested_block_comments() {
+    let value = parse_with_options("[1, /* a /* b */ 2]", nested(false)).unwrap();
+    assert_eq!(value, parse("[1, 2]").unwrap());
+
+    // The first `*/` ends the comment, leaving a stray `*/`
+    let input = "[1, /* a /* b */ c */ 2]";
+    let error = parse_with_options(input, nested(false)).unwrap_err();
+    assert_eq!(error.position(), Some(19));
+    assert!(stream(input, nested(false)).is_err());
+}
+
+#[test]
+fn test_nested_block_comments() {
+    let input = "[1, /* a /* b */ c */ 2]";
+    let value = parse_with_options(input, nested(true)).unwrap();
+    assert_eq!(value, parse("[1, 2]").unwrap());
+    // Nesting is the default
+    assert_eq!(parse(input).unwrap(), value);
+    assert_eq!(
+        stream(input, nested(true)).unwrap(),
+        stream("[1, 2]", nested(true)).unwrap()
+    );
+
+    // Every nested comment needs its own `*/`
+    let error = parse_with_options("[1, /* a /* b */ 2]", nested(true)).unwrap_err();
+    assert_eq!(error, Error::UnexpectedEof(4));
+    assert!(stream("[1, /* a /* b */ 2]", nested(true)).is_err());
+
+    // `/*/` opens a comment but does not close it
+    let value = parse_with_options("[/*/ x */ 1]", nested(true)).unwrap();
+    assert_eq!(value, parse("[1]").unwrap());
+}
+
+#[test]
+fn test_max_comment_length() {
+    let options = ParserOptions {
+        max_comment_length: Some(10),
+        ..Default::default()
+    };
+    // Exactly ten bytes, delimiters included
+    let value = parse_with_options("/* 4567 */ 1 // 45678\n", options.clone()).unwrap();
+    assert_eq!(value, parse("1").unwrap());
+
+    let long_comment = format!("[1, /* {} */ 2]", "x".repeat(100));
+    let error = parse_with_options(&long_comment, options.clone()).unwrap_err();
+    assert_eq!(
+        error,
+        Error::LimitExceeded {
+            what: "comment length".to_string(),
+            limit: 10,
+            position: 4
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "comment length exceeds the limit of 10 at position 4"
+    );
+    assert!(stream(&long_comment, options.clone()).is_err());
+
+    let error = parse_with_options("1 # a long hash comment", options.clone()).unwrap_err();
+    assert_eq!(error.position(), Some(2));
+    let error = parse_with_options("1 // a long line comment\n", options.clone()).unwrap_err();
+    assert_eq!(error.position(), Some(2));
+    assert!(stream("1 // a long line comment\n", options).is_err());
+}
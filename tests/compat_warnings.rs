@@ -0,0 +1,141 @@
+use vexy_json::{parse, CompatKind, ParserOptions, Span};
+use vexy_json_core::parser::Parser;
+use vexy_json_core::{parse_with_fallback, ParsingTier};
+
+fn compat() -> ParserOptions {
+    ParserOptions {
+        collect_compat_warnings: true,
+        ..Default::default()
+    }
+}
+
+fn compat_warnings(input: &str) -> Vec<(CompatKind, Span, String)> {
+    let mut parser = Parser::new(input, compat());
+    parser.parse().unwrap();
+    parser
+        .take_compat_warnings()
+        .into_iter()
+        .map(|warning| (warning.kind, warning.span, warning.replacement))
+        .collect()
+}
+
+/// Applies every replacement except implicit top-level brackets.
+fn make_strict(input: &str) -> String {
+    let mut parser = Parser::new(input, compat());
+    parser.parse().unwrap();
+    let mut strict = input.to_string();
+    for warning in parser.compat_warnings().iter().rev() {
+        if warning.kind != CompatKind::ImplicitTopLevel {
+            strict.replace_range(warning.span.start..warning.span.end, &warning.replacement);
+        }
+    }
+    strict
+}
+
+#[test]
+fn test_each_construct_is_reported_with_replacement() {
+    let input = "// config\n{name: 'vexy', \"tags\": [1, 0x1F,], /* x */ mode: fast,}";
+    let expected = [
+        (CompatKind::Comment, Span::new(0, 9), ""),
+        (CompatKind::UnquotedKey, Span::new(11, 15), "\"name\""),
+        (
+            CompatKind::SingleQuotedString,
+            Span::new(17, 23),
+            "\"vexy\"",
+        ),
+        (CompatKind::NonStandardNumber, Span::new(37, 41), "31"),
+        (CompatKind::TrailingComma, Span::new(41, 42), ""),
+        (CompatKind::Comment, Span::new(45, 52), ""),
+        (CompatKind::UnquotedKey, Span::new(53, 57), "\"mode\""),
+        (CompatKind::UnquotedString, Span::new(59, 63), "\"fast\""),
+        (CompatKind::TrailingComma, Span::new(63, 64), ""),
+    ];
+    let warnings = compat_warnings(input);
+    assert_eq!(warnings.len(), expected.len(), "{warnings:?}");
+    for (warning, (kind, span, replacement)) in warnings.iter().zip(expected) {
+        assert_eq!(*warning, (kind, span, replacement.to_string()));
+    }
+
+    let strict = make_strict(input);
+    let result = parse_with_fallback(&strict, ParserOptions::default());
+    assert_eq!(result.parsing_tier, ParsingTier::Fast, "{strict}");
+    assert_eq!(result.value, parse(input).unwrap());
+}
+
+#[test]
+fn test_separators_and_implicit_top_level() {
+    let warnings = compat_warnings("a: [,1]\nb: 2");
+    let kinds: Vec<_> = warnings
+        .iter()
+        .map(|(kind, span, _)| (*kind, *span))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (CompatKind::UnquotedKey, Span::new(0, 1)),
+            (CompatKind::MissingValue, Span::new(4, 5)),
+            (CompatKind::NewlineAsComma, Span::new(7, 8)),
+            (CompatKind::UnquotedKey, Span::new(8, 9)),
+            (CompatKind::ImplicitTopLevel, Span::new(0, 12)),
+        ]
+    );
+    assert_eq!(warnings[1].2, "null,");
+    assert_eq!(warnings[4].2, "{a: [,1]\nb: 2}");
+
+    let warnings = compat_warnings("1, 2");
+    assert_eq!(warnings[0].0, CompatKind::ImplicitTopLevel);
+    assert_eq!(warnings[0].2, "[1, 2]");
+}
+
+#[test]
+fn test_values_without_commas() {
+    for (input, commas) in [
+        ("[1 2 3]", vec![2, 4]),
+        ("{a: 1 b: [true false]}", vec![5, 14]),
+        ("[1 /* c */ 2]", vec![2]),
+    ] {
+        let warnings = compat_warnings(input);
+        let missing: Vec<_> = warnings
+            .iter()
+            .filter(|(kind, _, _)| *kind == CompatKind::MissingComma)
+            .map(|(_, span, replacement)| {
+                assert_eq!(replacement, ",");
+                assert!(span.is_empty());
+                span.start
+            })
+            .collect();
+        assert_eq!(missing, commas, "{input}: {warnings:?}");
+        assert_eq!(parse(&make_strict(input)).unwrap(), parse(input).unwrap());
+    }
+
+    // A line break is reported as one, not as a missing comma
+    let warnings = compat_warnings("[1\n2]");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].0, CompatKind::NewlineAsComma);
+}
+
+#[test]
+fn test_strict_json_has_no_compat_warnings() {
+    let input = r#"{"a": [1, -2.5e3, "x\"y"], "b": {"c": null}}"#;
+    assert!(compat_warnings(input).is_empty());
+
+    // Nested comments are one comment
+    let warnings = compat_warnings("[/* a /* b */ c */ 1]");
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].1, Span::new(1, 18));
+}
+
+#[test]
+fn test_fallback_reports_compat_warnings_from_forgiving_tier() {
+    let result = parse_with_fallback("{a: 1}", compat());
+    assert_eq!(result.parsing_tier, ParsingTier::Forgiving);
+    assert_eq!(result.compat_warnings.len(), 1);
+    assert_eq!(
+        result.compat_warnings[0].to_string(),
+        "unquoted key at position 1; use \"a\" instead"
+    );
+
+    // Off by default
+    let result = parse_with_fallback("{a: 1}", ParserOptions::default());
+    assert!(result.compat_warnings.is_empty());
+}
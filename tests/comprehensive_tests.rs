@@ -5,6 +5,7 @@
 //! This module provides comprehensive test coverage of the vexy_json parser
 //! to ensure feature parity and comprehensive coverage of edge cases.
 
+#![allow(clippy::approx_constant)]
 use rustc_hash::FxHashMap;
 use vexy_json::{parse, parse_with_options, ParserOptions, Value};
 
@@ -128,8 +129,12 @@ mod comment_handling {
     fn test_multi_line_comments() {
         // Multi-line comments
         assert_eq!(parse("/* comment */").unwrap(), null());
+        let space_separated = ParserOptions {
+            space_separated_values: true,
+            ..Default::default()
+        };
         assert_eq!(
-            parse("a /* comment */ b").unwrap(),
+            parse_with_options("a /* comment */ b", space_separated).unwrap(),
             arr(vec![s("a"), s("b")])
         );
         assert_eq!(parse("/* line1\nline2 */").unwrap(), null());
@@ -242,7 +247,10 @@ mod number_handling {
     fn test_integer_variations() {
         // Various integer formats
         assert_eq!(parse("0").unwrap(), n(0));
-        assert_eq!(parse("-0").unwrap(), n(0));
+        assert!(matches!(
+            parse("-0").unwrap(),
+            Value::Number(vexy_json::Number::Float(z)) if z == 0.0 && z.is_sign_negative()
+        ));
         assert_eq!(parse("42").unwrap(), n(42));
         assert_eq!(parse("-42").unwrap(), n(-42));
         assert_eq!(parse("+42").unwrap(), n(42));
@@ -560,6 +568,7 @@ mod parser_options {
             max_repairs: 0,
             fast_repair: false,
             report_repairs: false,
+            ..Default::default()
         };
 
         // Valid JSON should work
@@ -587,6 +596,7 @@ mod parser_options {
             max_repairs: 0,
             fast_repair: false,
             report_repairs: false,
+            ..Default::default()
         };
 
         // Should work
@@ -668,15 +678,24 @@ mod error_handling {
     fn test_unicode_errors() {
         // Invalid unicode escape sequences
         let invalid_unicode = vec![
-            "\"\\u\"",      // Incomplete unicode
-            "\"\\uGGGG\"",  // Invalid hex
-            "\"\\u123\"",   // Too short
-            "\"\\u12345\"", // Too long
+            "\"\\u\"",     // Incomplete unicode
+            "\"\\uGGGG\"", // Invalid hex
+            "\"\\u123\"",  // Too short
         ];
 
         for input in invalid_unicode {
             let result = parse(input);
             assert!(result.is_err(), "Invalid unicode should fail: {input}");
         }
+
+        // Any character may follow the four hex digits, hex digits included
+        assert_eq!(
+            parse("\"\\u12345\"").unwrap(),
+            Value::String("\u{1234}5".to_string())
+        );
+        assert_eq!(
+            parse("\"a\\u0000b\"").unwrap(),
+            Value::String("a\0b".to_string())
+        );
     }
 }
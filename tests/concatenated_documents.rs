@@ -0,0 +1,75 @@
+use vexy_json::{parse, parse_concatenated, Value};
+use vexy_json_core::{is_concatenated, ParallelConfig, ParallelParser};
+
+/// Parses `input` both sequentially and in parallel, checking they agree.
+fn parse_both(input: &str) -> vexy_json::Result<Vec<Value>> {
+    let parser = ParallelParser::with_config(ParallelConfig {
+        max_threads: 2,
+        ..Default::default()
+    });
+    let sequential = parse_concatenated(input);
+    assert_eq!(parser.parse_concatenated(input), sequential);
+    sequential
+}
+
+#[test]
+fn test_brackets_and_quotes_in_strings() {
+    let input = r#"{"a":"}"}{"b":"\"}{","c":['x]']}["]\\",{}]"#;
+    let values = parse_both(input).unwrap();
+    assert_eq!(
+        values,
+        [
+            parse(r#"{"a": "}"}"#).unwrap(),
+            parse(r#"{"b": "\"}{", "c": ["x]"]}"#).unwrap(),
+            parse(r#"["]\\", {}]"#).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn test_whitespace_and_newline_separated_stream() {
+    let input = "\u{feff}{\"id\": 1}\n{\"id\": 2} {\"id\": 3}\r\n\n\t[4]  5\n\"six\"null";
+    let values = parse_both(input).unwrap();
+    assert_eq!(values.len(), 7);
+    assert_eq!(values[2], parse("{id: 3}").unwrap());
+    assert_eq!(values[4], parse("5").unwrap());
+    assert_eq!(values[5], Value::String("six".to_string()));
+    assert_eq!(values[6], Value::Null);
+
+    assert!(parse_both(" \n").unwrap().is_empty());
+}
+
+#[test]
+fn test_many_documents_keep_their_order() {
+    let input: String = (0..1000).map(|i| format!("{{\"i\":{i}}}")).collect();
+    let values = parse_both(&input).unwrap();
+    assert_eq!(values.len(), 1000);
+    for (i, value) in values.iter().enumerate() {
+        assert_eq!(value["i"], parse(&i.to_string()).unwrap());
+    }
+}
+
+#[test]
+fn test_error_positions_are_in_the_whole_input() {
+    let input = "{\"a\": 1}{\"b\": }";
+    let error = parse_both(input).unwrap_err();
+    // At the closing brace where the value should be
+    assert_eq!(error.position(), Some(14));
+
+    // A document left open runs to the end of the input
+    let input = "[1][2, {\"c\": \"]\"}";
+    let error = parse_both(input).unwrap_err();
+    assert_eq!(error.position(), Some(input.len()));
+}
+
+#[test]
+fn test_detection() {
+    assert!(is_concatenated(r#"{"a":1}{"b":2}"#));
+    assert!(is_concatenated(r#" [1][2]"#));
+    assert!(is_concatenated(r#"{"a":"}{"}[1]"#));
+    // Separated values are an implicit top-level array
+    assert!(!is_concatenated(r#"{"a":1}, {"b":2}"#));
+    assert!(!is_concatenated("{\"a\":1}\n{\"b\":2}"));
+    assert!(!is_concatenated(r#"{"a":1}"#));
+    assert!(!is_concatenated("1 2"));
+}
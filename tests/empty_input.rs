@@ -0,0 +1,56 @@
+use vexy_json::{parse, parse_with_options, EmptyInputBehavior, Error, ParserOptions, Value};
+
+const EMPTY_INPUTS: [&str; 5] = ["", "   ", "\n\n", " \t\r\n ", "// just a comment\n"];
+
+fn parse_empty(input: &str, behavior: EmptyInputBehavior) -> Result<Value, Error> {
+    let options = ParserOptions {
+        empty_input_behavior: behavior,
+        ..Default::default()
+    };
+    parse_with_options(input, options)
+}
+
+#[test]
+fn test_empty_input_is_null_by_default() {
+    for input in EMPTY_INPUTS {
+        assert_eq!(parse(input).unwrap(), Value::Null, "{input:?}");
+        assert_eq!(
+            parse_empty(input, EmptyInputBehavior::Null).unwrap(),
+            Value::Null,
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn test_empty_input_error() {
+    for input in EMPTY_INPUTS {
+        let error = parse_empty(input, EmptyInputBehavior::Error).unwrap_err();
+        assert_eq!(error, Error::UnexpectedEof(input.len()), "{input:?}");
+    }
+}
+
+#[test]
+fn test_empty_input_empty_containers() {
+    for input in EMPTY_INPUTS {
+        let object = parse_empty(input, EmptyInputBehavior::EmptyObject).unwrap();
+        assert!(
+            matches!(object, Value::Object(ref m) if m.is_empty()),
+            "{input:?}"
+        );
+
+        let array = parse_empty(input, EmptyInputBehavior::EmptyArray).unwrap();
+        assert!(
+            matches!(array, Value::Array(ref a) if a.is_empty()),
+            "{input:?}"
+        );
+    }
+}
+
+#[test]
+fn test_non_empty_input_is_unaffected() {
+    for behavior in [EmptyInputBehavior::Error, EmptyInputBehavior::EmptyObject] {
+        assert_eq!(parse_empty(" null ", behavior).unwrap(), Value::Null);
+        assert_eq!(parse_empty("1", behavior).unwrap().as_i64(), Some(1));
+    }
+}
@@ -1,5 +1,7 @@
 // this_file: tests/error_handling.rs
 
+#![allow(clippy::assertions_on_constants, clippy::field_reassign_with_default)]
+
 use vexy_json::{parse, parse_with_options, ParserOptions};
 
 /// Comprehensive error handling tests based on reference implementation tests from error.test.js
@@ -332,3 +334,54 @@ fn test_comment_error_cases() {
     assert!(parse("a").is_ok(), "Basic identifier parsing should work");
     assert!(parse("\"hello\"").is_ok(), "String parsing should work");
 }
+
+#[test]
+fn test_errors_point_at_the_offending_token() {
+    let strict = ParserOptions {
+        allow_comments: false,
+        allow_trailing_commas: false,
+        allow_unquoted_keys: false,
+        allow_single_quotes: false,
+        implicit_top_level: false,
+        newline_as_comma: false,
+        ..Default::default()
+    };
+    let forgiving = ParserOptions {
+        newline_as_comma: false,
+        ..Default::default()
+    };
+    let cases = [
+        ("{\"a\": }", "}"),
+        ("{\"a\"  1}", "1"),
+        ("{\"a\": 1 : 2}", ":"),
+        ("[1, 2 3]", "3"),
+        ("[1, 2}", "}"),
+        ("[1]  ]", "]"),
+        ("{\"a\": 1}  [2]", "[2]"),
+    ];
+    for options in [strict.clone(), forgiving] {
+        for (input, token) in cases {
+            let error = parse_with_options(input, options.clone()).unwrap_err();
+            let position = error.position().unwrap();
+            assert!(
+                input[position..].starts_with(token),
+                "{input:?}: {error} points at {:?}",
+                &input[position..]
+            );
+        }
+    }
+
+    // Trailing commas are reported at the comma itself
+    for input in ["[1, 2,  ]", "{\"a\": 1, }"] {
+        let error = parse_with_options(input, strict.clone()).unwrap_err();
+        assert_eq!(error.position(), input.rfind(','), "{input:?}");
+    }
+
+    // Nesting too deep is reported at the bracket that opens one level too many
+    let shallow = ParserOptions {
+        max_depth: 2,
+        ..Default::default()
+    };
+    let error = parse_with_options("[[ [1]]]", shallow).unwrap_err();
+    assert_eq!(error.position(), Some(3));
+}
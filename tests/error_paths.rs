@@ -0,0 +1,79 @@
+use vexy_json::{parse_with_options, Error, ParserOptions};
+use vexy_json_core::ast::PathSegment;
+
+fn tracking() -> ParserOptions {
+    ParserOptions {
+        track_paths: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_error_reports_path() {
+    let input = r#"{"items": [0, 1, 2, {"name": [1, }]}]}"#;
+    let untracked = parse_with_options(input, ParserOptions::default()).unwrap_err();
+    assert!(untracked.path().is_none());
+
+    let error = parse_with_options(input, tracking()).unwrap_err();
+    assert!(matches!(error, Error::AtPath { .. }));
+    assert_eq!(error.path().unwrap().to_string(), "$.items[3].name[1]");
+    assert!(error.to_string().ends_with(" at path $.items[3].name[1]"));
+    assert_eq!(error.position(), untracked.position());
+    assert_eq!(error.code(), untracked.code());
+}
+
+#[test]
+fn test_path_segments_decode_keys() {
+    let input = r#"{'it\'s': {"ab": {c: [1, 2, {d: ]}]}}}"#;
+    let error = parse_with_options(input, tracking()).unwrap_err();
+    assert_eq!(
+        error.path().unwrap().segments(),
+        [
+            PathSegment::Key("it's".to_string()),
+            PathSegment::Key("ab".to_string()),
+            PathSegment::Key("c".to_string()),
+            PathSegment::Index(2),
+            PathSegment::Key("d".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_path_through_implicit_top_level() {
+    let cases = [
+        ("a: 1\nb: {c: ]}", "$.b.c"),
+        ("1, 2, {a: ]}", "$[2].a"),
+        ("[1, 2", "$"),
+    ];
+    for (input, path) in cases {
+        let error = parse_with_options(input, tracking()).unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), path, "{input:?}");
+    }
+}
+
+#[test]
+fn test_lexer_error_reports_element_path() {
+    let cases = [
+        ("[1, 2, @]", "$[2]"),
+        ("[@]", "$[0]"),
+        ("[1,, @]", "$[2]"),
+        ("[1, /* note */ @]", "$[1]"),
+        (r#"{"a": [1, @]}"#, "$.a[1]"),
+        (r#"{"a b": @}"#, "$['a b']"),
+        ("1, @", "$[1]"),
+        ("a: 1\nb: @", "$.b"),
+    ];
+    for (input, path) in cases {
+        let error = parse_with_options(input, tracking()).unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), path, "{input:?}");
+    }
+}
+
+#[test]
+fn test_tracking_does_not_change_values() {
+    let input = r#"{"a": [1, {"b": [true, null]}], c: 'x'}"#;
+    assert_eq!(
+        parse_with_options(input, tracking()).unwrap(),
+        parse_with_options(input, ParserOptions::default()).unwrap()
+    );
+}
@@ -262,15 +262,11 @@ fn test_newline_separators() {
             Ok(val) => {
                 println!("✓ Newline separator {description} parsed: {val:?}");
                 match &val {
-                    Value::Object(obj) => {
-                        if description.contains("object") {
-                            assert!(!obj.is_empty(), "Should have multiple elements");
-                        }
+                    Value::Object(obj) if description.contains("object") => {
+                        assert!(!obj.is_empty(), "Should have multiple elements");
                     }
-                    Value::Array(arr) => {
-                        if description.contains("array") {
-                            assert!(!arr.is_empty(), "Should have multiple elements");
-                        }
+                    Value::Array(arr) if description.contains("array") => {
+                        assert!(!arr.is_empty(), "Should have multiple elements");
                     }
                     _ => {}
                 }
@@ -301,15 +297,11 @@ fn test_edge_cases() {
             Ok(val) => {
                 println!("✓ Edge case {description} parsed: {val:?}");
                 match &val {
-                    Value::Object(obj) => {
-                        if description == "empty object" {
-                            assert!(obj.is_empty());
-                        }
+                    Value::Object(obj) if description == "empty object" => {
+                        assert!(obj.is_empty());
                     }
-                    Value::Array(arr) => {
-                        if description == "empty array" {
-                            assert!(arr.is_empty());
-                        }
+                    Value::Array(arr) if description == "empty array" => {
+                        assert!(arr.is_empty());
                     }
                     _ => {}
                 }
@@ -0,0 +1,127 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use vexy_json::{
+    events_from_file, parse, parse_file, parse_file_with_options, Error, ParserOptions,
+    StreamingEvent,
+};
+
+/// A file in the temp dir that is removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: impl AsRef<[u8]>) -> Self {
+        let path = std::env::temp_dir().join(format!("vexy_json_{}_{name}", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn events(file: &TempFile) -> Vec<StreamingEvent> {
+    events_from_file(&file.0)
+        .unwrap()
+        .collect::<vexy_json::Result<_>>()
+        .unwrap()
+}
+
+#[test]
+fn test_parse_file() {
+    let file = TempFile::new(
+        "parse.json",
+        "\u{feff}// settings\n{name: 'vexy', sizes: [1, 2,]}",
+    );
+    assert_eq!(
+        parse_file(&file.0).unwrap(),
+        parse(r#"{"name": "vexy", "sizes": [1, 2]}"#).unwrap()
+    );
+
+    let strict = ParserOptions {
+        allow_comments: false,
+        ..Default::default()
+    };
+    assert!(parse_file_with_options(&file.0, strict).is_err());
+}
+
+#[test]
+fn test_missing_file_is_io_error() {
+    let missing = std::env::temp_dir().join("vexy_json_no_such_file.json");
+    for error in [
+        parse_file(&missing).unwrap_err(),
+        events_from_file(&missing).unwrap_err(),
+    ] {
+        assert_eq!(error.code().as_str(), "E1021");
+        match error {
+            Error::Io { kind, message } => {
+                assert_eq!(kind, ErrorKind::NotFound);
+                assert!(message.contains("vexy_json_no_such_file.json"), "{message}");
+            }
+            other => panic!("expected an I/O error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_events_from_large_file_are_read_incrementally() {
+    let count = 50_000;
+    let mut input = String::from("\u{feff}[");
+    for i in 0..count {
+        if i > 0 {
+            input.push(',');
+        }
+        input.push_str(&format!("{{\"id\": {i}, \"name\": \"é{i}\"}}"));
+    }
+    input.push(']');
+    let file = TempFile::new("large.json", &input);
+
+    let mut stream = events_from_file(&file.0).unwrap();
+    assert_eq!(stream.next().unwrap().unwrap(), StreamingEvent::StartArray);
+    let read_at_start = stream.bytes_read();
+    assert!(read_at_start < input.len() as u64 / 10, "{read_at_start}");
+
+    let mut ids = 0;
+    let mut last = None;
+    for event in stream.by_ref() {
+        match event.unwrap() {
+            StreamingEvent::Number(_) => ids += 1,
+            StreamingEvent::String(name) => assert!(name.starts_with('é'), "{name}"),
+            event => last = Some(event),
+        }
+    }
+    assert_eq!(ids, count);
+    assert_eq!(last, Some(StreamingEvent::EndOfInput));
+    assert_eq!(stream.bytes_read(), input.len() as u64);
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn test_character_split_across_chunks() {
+    // The two bytes of `é` straddle the end of the first 64 KiB chunk
+    let text = format!("{}é", "a".repeat(64 * 1024 - 3));
+    let file = TempFile::new("split.json", format!("[\"{text}\"]"));
+    assert_eq!(
+        events(&file),
+        [
+            StreamingEvent::StartArray,
+            StreamingEvent::String(text),
+            StreamingEvent::EndArray,
+            StreamingEvent::EndOfInput,
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_utf8_in_file() {
+    let file = TempFile::new("invalid.json", b"[\"ok\", \"\xFF\"]");
+    let error = events_from_file(&file.0)
+        .unwrap()
+        .find_map(Result::err)
+        .unwrap();
+    assert_eq!(error, Error::InvalidUtf8(8));
+    assert_eq!(parse_file(&file.0).unwrap_err(), Error::InvalidUtf8(8));
+}
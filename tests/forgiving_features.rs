@@ -1,3 +1,5 @@
+#![allow(clippy::field_reassign_with_default)]
+
 use rustc_hash::FxHashMap;
 use vexy_json::{parse, parse_with_options, ParserOptions, Value};
 
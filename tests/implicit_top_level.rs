@@ -0,0 +1,121 @@
+use vexy_json::{parse, parse_with_options, Error, Number, ParserOptions, Value};
+
+fn object(pairs: &[(&str, i64)]) -> Value {
+    let mut expected = String::from("{");
+    for (key, value) in pairs {
+        expected.push_str(&format!("\"{key}\": {value},"));
+    }
+    expected.push('}');
+    parse(&expected).unwrap()
+}
+
+#[test]
+fn test_key_value_lines_are_an_object() {
+    let expected = object(&[("a", 1), ("b", 2)]);
+    for input in [
+        "a: 1\nb: 2",
+        "a: 1\nb: 2\n",
+        "a: 1\r\nb: 2\r\n",
+        "\na: 1\n\nb: 2\n\n",
+        "a:1\nb:2",
+        "\"a\": 1\n'b': 2",
+        "a: 1 # first\nb: 2 // second",
+        "a: 1, b: 2",
+    ] {
+        assert_eq!(parse(input).unwrap(), expected, "input: {input:?}");
+    }
+}
+
+#[test]
+fn test_indentation_does_not_nest() {
+    // Indentation is not significant: indented lines are further members
+    let expected = object(&[("a", 1), ("b", 2), ("c", 3)]);
+    assert_eq!(parse("a: 1\n  b: 2\n\tc: 3").unwrap(), expected);
+    assert_eq!(parse("a:\n  1\nb:\n  2\nc: 3").unwrap(), expected);
+}
+
+#[test]
+fn test_implicit_object_never_becomes_array() {
+    for input in ["a: 1\ntrue", "a: 1\n[2]", "a: 1\n  {b: 2}", "a: 1, null"] {
+        let error = parse(input).unwrap_err();
+        assert!(
+            matches!(&error, Error::Expected { expected, .. } if expected == "key of implicit top-level object"),
+            "input: {input:?}, error: {error:?}"
+        );
+    }
+}
+
+#[test]
+fn test_key_after_top_level_value_is_an_error() {
+    // Only the first line can start an implicit object
+    for input in ["x\na: 1", "1, a: 2", "1\n2\na: 3", "x\n  a: 1"] {
+        let error = parse(input).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("an implicit top-level object must start with a key"),
+            "input: {input:?}, error: {error}"
+        );
+    }
+
+    // Values on separate lines still form an implicit array
+    assert_eq!(parse("x\ny").unwrap(), parse(r#"["x", "y"]"#).unwrap());
+}
+
+#[test]
+fn test_implicit_object_keys_match_braced_keys() {
+    for (input, keys) in [
+        ("2024: 1", &["2024"][..]),
+        ("2024.5: 1, 1e3: 2", &["2024.5", "1e3"]),
+        ("-1: 1, -2.5: 2", &["-1", "-2.5"]),
+        (r#""a\"b": 1, "c\nd": 2"#, &["a\"b", "c\nd"]),
+        (r"'a': 1, 'it\'s': 2", &["a", "it's"]),
+        (r#"x: 1, 7: 2, "q\"": 3, 'y': 4"#, &["x", "7", "q\"", "y"]),
+    ] {
+        let implicit = parse(input).unwrap();
+        assert_eq!(
+            implicit,
+            parse(&format!("{{{input}}}")).unwrap(),
+            "input: {input:?}"
+        );
+        let Value::Object(map) = implicit else {
+            panic!("expected an object for {input:?}");
+        };
+        for (key, value) in keys.iter().zip(1..) {
+            assert_eq!(map.get(*key), Some(&Value::Number(Number::Integer(value))));
+        }
+    }
+}
+
+#[test]
+fn test_implicit_object_respects_allow_unquoted_keys() {
+    let options = ParserOptions {
+        allow_unquoted_keys: false,
+        enable_repair: false,
+        ..Default::default()
+    };
+    for input in ["a: 1", "'a': 1\nb: 2", "{a: 1}"] {
+        assert!(
+            parse_with_options(input, options.clone()).is_err(),
+            "input: {input:?}"
+        );
+    }
+    assert_eq!(
+        parse_with_options("a: 1", options.clone()).unwrap_err(),
+        Error::Expected {
+            expected: "string key".to_string(),
+            found: "UnquotedString".to_string(),
+            position: 0,
+        }
+    );
+
+    // Quoted and numeric keys, and unquoted values, are still read
+    assert_eq!(
+        parse_with_options("'a': 1\n2: b", options.clone()).unwrap(),
+        parse(r#"{"a": 1, "2": "b"}"#).unwrap()
+    );
+    assert_eq!(
+        parse_with_options("hello", options).unwrap(),
+        Value::String("hello".to_string())
+    );
+}
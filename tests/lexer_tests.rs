@@ -1,6 +1,6 @@
 // this_file: tests/lexer_tests.rs
 
-use vexy_json::{parse, Value};
+use vexy_json::{parse, tokenize, tokenize_with_options, ParserOptions, Token, Value};
 
 ///
 /// These tests focus on the tokenization and lexing behavior of vexy_json,
@@ -584,3 +584,132 @@ fn test_lexer_comprehensive_diagnostic() {
         "Lexer should successfully parse at least some inputs"
     );
 }
+
+#[test]
+fn test_tokenize_recovers_comment_text() {
+    let input = "// leading\n{ /* block */ a: 1, # hash\n b: /** doc **/ 2 }";
+    let tokens = tokenize(input).unwrap();
+
+    let comments: Vec<(Token, &str)> = tokens
+        .iter()
+        .filter(|(token, _)| matches!(token, Token::SingleLineComment | Token::MultiLineComment))
+        .map(|(token, span)| (*token, &input[span.start..span.end]))
+        .collect();
+
+    assert_eq!(
+        comments,
+        vec![
+            (Token::SingleLineComment, "// leading"),
+            (Token::MultiLineComment, "/* block */"),
+            (Token::SingleLineComment, "# hash"),
+            (Token::MultiLineComment, "/** doc **/"),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_spans_cover_multiline_comments() {
+    let input = "/* line one\n * line two\n */\n42";
+    let tokens = tokenize(input).unwrap();
+
+    let (token, span) = tokens[0];
+    assert_eq!(token, Token::MultiLineComment);
+    assert_eq!(span.start, 0);
+    assert_eq!(span.end, input.find("*/").unwrap() + 2);
+    assert_eq!(tokens[1].0, Token::Newline);
+    assert_eq!(tokens.last().unwrap().0, Token::Number);
+}
+
+fn parser_tokens<'a>(input: &'a str, options: &ParserOptions) -> Vec<(Token, &'a str)> {
+    tokenize_with_options(input, options)
+        .unwrap()
+        .into_iter()
+        .map(|(token, span, text)| {
+            assert_eq!(&input[span.start..span.end], text);
+            (token, text)
+        })
+        .collect()
+}
+
+#[test]
+fn test_tokenize_with_options_skips_comments() {
+    let input = "{a: 1, // note
+ /* b */ 'c': 2}";
+    assert_eq!(
+        parser_tokens(input, &ParserOptions::default()),
+        [
+            (Token::LeftBrace, "{"),
+            (Token::UnquotedString, "a"),
+            (Token::Colon, ":"),
+            (Token::Number, "1"),
+            (Token::Comma, ","),
+            (Token::String, "'c'"),
+            (Token::Colon, ":"),
+            (Token::Number, "2"),
+            (Token::RightBrace, "}"),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_with_options_unquoted_strings() {
+    let input = "key-name: $value_1 true";
+    assert_eq!(
+        parser_tokens(input, &ParserOptions::default()),
+        [
+            (Token::UnquotedString, "key-name"),
+            (Token::Colon, ":"),
+            (Token::UnquotedString, "$value_1"),
+            (Token::True, "true"),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_with_options_newlines_as_commas() {
+    // The forgiving lexer drops line breaks; the parser splits values on the
+    // gap between tokens instead, so `1` and `2` become an implicit array
+    let input = "1\n2";
+    assert_eq!(
+        parser_tokens(input, &ParserOptions::default()),
+        [(Token::Number, "1"), (Token::Number, "2")]
+    );
+
+    // Strict options use the logos lexer, which keeps them
+    let strict = ParserOptions {
+        allow_comments: false,
+        allow_trailing_commas: false,
+        allow_unquoted_keys: false,
+        allow_single_quotes: false,
+        implicit_top_level: false,
+        newline_as_comma: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        parser_tokens("[1,\r\n2]", &strict),
+        [
+            (Token::LeftBracket, "["),
+            (Token::Number, "1"),
+            (Token::Comma, ","),
+            (Token::Newline, "\r"),
+            (Token::Newline, "\n"),
+            (Token::Number, "2"),
+            (Token::RightBracket, "]"),
+        ]
+    );
+    // Comments reach the parser too, which then rejects them
+    assert_eq!(
+        parser_tokens("// comment", &strict),
+        [(Token::SingleLineComment, "// comment")]
+    );
+}
+
+#[test]
+fn test_tokenize_with_stats() {
+    let input = "[1, 2]";
+    let (tokens, stats) =
+        vexy_json_core::tokenize_with_stats(input, &ParserOptions::default()).unwrap();
+    assert_eq!(stats.tokens_count, tokens.len());
+    assert_eq!(stats.tokens_count, 5);
+    assert_eq!(stats.bytes_processed, input.len());
+}
@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use vexy_json::{
+    parse_with_budget, BudgetLimit, Error, ParseBudget, ParserOptions, StreamingParser,
+};
+use vexy_json_core::{ParallelConfig, ParallelParser};
+
+/// An array of `n` small objects, large enough to take seconds to parse.
+fn large_input(n: usize) -> String {
+    let mut input = String::from("[");
+    for i in 0..n {
+        if i > 0 {
+            input.push(',');
+        }
+        input.push_str(&format!("{{\"id\": {i}, \"name\": \"item {i}\"}}"));
+    }
+    input.push(']');
+    input
+}
+
+fn budget_limit(error: Error) -> BudgetLimit {
+    match error {
+        Error::BudgetExceeded { limit, .. } => limit,
+        other => panic!("expected a budget error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_deadline_stops_large_parse() {
+    let input = large_input(1_000_000);
+    let budget = ParseBudget::new().with_timeout(Duration::from_millis(5));
+
+    let start = Instant::now();
+    let error = parse_with_budget(&input, ParserOptions::default(), budget).unwrap_err();
+    assert!(
+        start.elapsed() < Duration::from_secs(1),
+        "{:?}",
+        start.elapsed()
+    );
+
+    let position = error.position().unwrap();
+    assert!(position > 0 && position < input.len());
+    assert_eq!(budget_limit(error), BudgetLimit::Deadline);
+}
+
+#[test]
+fn test_cancel_flag_from_another_thread() {
+    let input = large_input(1_000_000);
+    let flag = Arc::new(AtomicBool::new(false));
+    let canceller = {
+        let flag = flag.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            flag.store(true, Ordering::Relaxed);
+        })
+    };
+
+    let budget = ParseBudget::new().with_cancel_flag(flag);
+    let error = parse_with_budget(&input, ParserOptions::default(), budget).unwrap_err();
+    canceller.join().unwrap();
+    assert_eq!(budget_limit(error), BudgetLimit::Cancelled);
+}
+
+#[test]
+fn test_memory_limit() {
+    let budget = ParseBudget::new().with_max_memory(4096);
+    let small = parse_with_budget("{a: [1, 2, 3]}", ParserOptions::default(), budget.clone());
+    assert!(small.is_ok());
+
+    let long_string = format!("[\"{}\"]", "x".repeat(5000));
+    let error = parse_with_budget(&long_string, ParserOptions::default(), budget).unwrap_err();
+    assert_eq!(error.code().as_str(), "E1020");
+    assert_eq!(budget_limit(error), BudgetLimit::Memory);
+}
+
+#[test]
+fn test_unlimited_budget_parses_normally() {
+    let value = parse_with_budget(
+        "a: 1, b: [true]",
+        ParserOptions::default(),
+        ParseBudget::new(),
+    );
+    assert_eq!(
+        value.unwrap(),
+        vexy_json::parse("{a: 1, b: [true]}").unwrap()
+    );
+}
+
+#[test]
+fn test_streaming_parser_honors_cancel_flag() {
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut parser = StreamingParser::new().with_cancel_flag(flag.clone());
+    parser.feed("[1, 2, ").unwrap();
+
+    flag.store(true, Ordering::Relaxed);
+    let error = parser.feed("3, 4]").unwrap_err();
+    assert_eq!(budget_limit(error), BudgetLimit::Cancelled);
+}
+
+#[test]
+fn test_parallel_parser_honors_cancel_flag() {
+    let flag = Arc::new(AtomicBool::new(true));
+    let parser = ParallelParser::with_config(ParallelConfig {
+        cancel_flag: Some(flag),
+        ..Default::default()
+    });
+    let error = parser.parse_ndjson("{\"a\": 1}\n{\"a\": 2}\n").unwrap_err();
+    assert_eq!(budget_limit(error), BudgetLimit::Cancelled);
+}
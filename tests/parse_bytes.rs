@@ -0,0 +1,75 @@
+use vexy_json::{
+    parse, parse_bytes, parse_bytes_with_options, parse_bytes_with_warnings, Error,
+    InvalidUtf8Policy, ParserOptions, Span, Value, WarningKind,
+};
+
+fn replacing() -> ParserOptions {
+    ParserOptions {
+        invalid_utf8_policy: InvalidUtf8Policy::Replace,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_valid_bytes_parse_like_str() {
+    let input = "{name: 'café', tags: [a, b]}";
+    assert_eq!(
+        parse_bytes(input.as_bytes()).unwrap(),
+        parse(input).unwrap()
+    );
+}
+
+#[test]
+fn test_invalid_continuation_byte_errors_by_default() {
+    // 0xC3 starts a two-byte sequence, but '(' is not a continuation byte
+    let input = b"{\"a\": \"caf\xC3(\"}";
+    assert_eq!(parse_bytes(input).unwrap_err(), Error::InvalidUtf8(10));
+    assert_eq!(
+        parse_bytes_with_options(input, ParserOptions::default()).unwrap_err(),
+        Error::InvalidUtf8(10)
+    );
+}
+
+#[test]
+fn test_invalid_continuation_byte_replaced() {
+    let input = b"{\"a\": \"caf\xC3(\", \"b\": \"\xE2\x82x\xFF\"}";
+    let (value, warnings) = parse_bytes_with_warnings(input, replacing()).unwrap();
+    assert_eq!(value["a"], Value::String("caf\u{FFFD}(".to_string()));
+    assert_eq!(value["b"], Value::String("\u{FFFD}x\u{FFFD}".to_string()));
+
+    let replaced: Vec<_> = warnings
+        .iter()
+        .map(|warning| (warning.kind, warning.span))
+        .collect();
+    assert_eq!(
+        replaced,
+        [
+            (WarningKind::InvalidUtf8, Span::new(10, 11)),
+            (WarningKind::InvalidUtf8, Span::new(21, 23)),
+            (WarningKind::InvalidUtf8, Span::new(24, 25)),
+        ]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "invalid UTF-8 replaced with U+FFFD at position 10"
+    );
+}
+
+#[test]
+fn test_truncated_sequence_replaced() {
+    // The input ends partway through a four-byte sequence
+    let input = b"[1] // \xF0\x9F\x98";
+    let (value, warnings) = parse_bytes_with_warnings(input, replacing()).unwrap();
+    assert_eq!(value, parse("[1]").unwrap());
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].span, Span::new(7, 10));
+}
+
+#[test]
+fn test_replacement_outside_strings_is_a_parse_error() {
+    let error = parse_bytes_with_options(b"[1, \xFF]", replacing()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Unexpected character U+FFFD REPLACEMENT CHARACTER at position 4"
+    );
+}
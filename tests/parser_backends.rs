@@ -0,0 +1,41 @@
+use vexy_json::{parse, parse_with_options, ParserBackend, ParserOptions};
+
+#[test]
+fn test_backends_agree_on_strict_json() {
+    let input = r#"{"name": "vexy", "tags": ["a", "b"], "n": -1.5e2, "ok": true, "none": null}"#;
+    let options = ParserOptions::default();
+    let expected = parse(input).unwrap();
+    for backend in ParserBackend::ALL {
+        let mut parser = backend.parser();
+        assert_eq!(
+            parser.parse_with(input, &options).unwrap(),
+            expected,
+            "{backend:?}"
+        );
+    }
+}
+
+#[test]
+fn test_parse_with_options_uses_selected_backend() {
+    let with_backend = |backend| ParserOptions {
+        backend,
+        ..Default::default()
+    };
+    // Only the hybrid parser supports implicit top-level objects
+    assert!(parse_with_options("a: 1", with_backend(ParserBackend::Auto)).is_ok());
+    assert!(parse_with_options("a: 1", with_backend(ParserBackend::Hybrid)).is_ok());
+    assert!(parse_with_options("a: 1", with_backend(ParserBackend::Recursive)).is_err());
+    assert!(parse_with_options("a: 1", with_backend(ParserBackend::Iterative)).is_err());
+}
+
+#[test]
+fn test_hybrid_backend_keeps_warnings() {
+    let options = ParserOptions {
+        collect_warnings: true,
+        ..Default::default()
+    };
+    let mut parser = ParserBackend::Auto.parser();
+    parser.parse_with("a: 1\nb: 2", &options).unwrap();
+    assert!(!parser.take_warnings().is_empty());
+    assert!(parser.take_warnings().is_empty());
+}
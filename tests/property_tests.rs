@@ -52,10 +52,8 @@ proptest! {
         // Test string values
         if !string_val.is_empty() {
             let string_json = format!("\"{}\"", string_val.replace("\"", "\\\""));
-            if let Ok(parsed) = parse(&string_json) {
-                if let Value::String(s) = parsed {
-                    prop_assert_eq!(s, string_val);
-                }
+            if let Ok(Value::String(s)) = parse(&string_json) {
+                prop_assert_eq!(s, string_val);
             }
         }
 
@@ -77,19 +75,15 @@ proptest! {
         // Test float values
         if float_val.is_finite() {
             let float_json = float_val.to_string();
-            if let Ok(parsed) = parse(&float_json) {
-                if let Value::Number(Number::Float(f)) = parsed {
-                    prop_assert!((f - float_val).abs() < 0.001);
-                }
+            if let Ok(Value::Number(Number::Float(f))) = parse(&float_json) {
+                prop_assert!((f - float_val).abs() < 0.001);
             }
         }
 
         // Test boolean values
         let bool_json = bool_val.to_string();
-        if let Ok(parsed) = parse(&bool_json) {
-            if let Value::Bool(b) = parsed {
-                prop_assert_eq!(b, bool_val);
-            }
+        if let Ok(Value::Bool(b)) = parse(&bool_json) {
+            prop_assert_eq!(b, bool_val);
         }
     }
 
@@ -124,19 +118,17 @@ proptest! {
             }
             let obj_json = format!("{{{}}}", obj_parts.join(", "));
 
-            if let Ok(parsed) = parse(&obj_json) {
-                if let Value::Object(obj) = parsed {
-                    // Object length should equal the number of unique keys
-                    prop_assert_eq!(obj.len(), unique_keys.len());
-                    // Only check values for the last occurrence of each key
-                    let mut key_to_value: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
-                    for (key, value) in keys.iter().take(min_len).zip(values.iter().take(min_len)) {
-                        key_to_value.insert(key, value);
-                    }
-                    for (key, expected_value) in key_to_value {
-                        if let Some(Value::String(actual_value)) = obj.get(key) {
-                            prop_assert_eq!(actual_value, expected_value);
-                        }
+            if let Ok(Value::Object(obj)) = parse(&obj_json) {
+                // Object length should equal the number of unique keys
+                prop_assert_eq!(obj.len(), unique_keys.len());
+                // Only check values for the last occurrence of each key
+                let mut key_to_value: std::collections::HashMap<&str, &str> = std::collections::HashMap::new();
+                for (key, value) in keys.iter().take(min_len).zip(values.iter().take(min_len)) {
+                    key_to_value.insert(key, value);
+                }
+                for (key, expected_value) in key_to_value {
+                    if let Some(Value::String(actual_value)) = obj.get(key) {
+                        prop_assert_eq!(actual_value, expected_value);
                     }
                 }
             }
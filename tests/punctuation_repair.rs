@@ -0,0 +1,109 @@
+use vexy_json::{parse, ParserOptions};
+use vexy_json_core::error::repair::RepairType;
+use vexy_json_core::{parse_with_fallback, ParsingTier};
+
+/// A config snippet as it reads after a round trip through a word processor.
+const PASTED_FROM_DOCS: &str = "{
+  “service”: “billing”,
+  “owner”: “Ops team’s on-call”,
+  “retries”: 3,
+  “backoff”: {“min_ms”: 250,\u{a0}“max_ms”: 8000},
+  “offset_hours”: –5,
+  “drift”: —0.25,
+  “banner”: \"Say “hello” – it’s free\",
+  // don’t change “these” in comments
+  ‘labels’: [‘eu’, ‘it’s’]
+}";
+
+#[test]
+fn test_pasted_from_docs() {
+    let result = parse_with_fallback(PASTED_FROM_DOCS, ParserOptions::default());
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.parsing_tier, ParsingTier::Repair);
+    assert_eq!(
+        result.value,
+        parse(
+            r#"{"service": "billing", "owner": "Ops team’s on-call", "retries": 3,
+                "backoff": {"min_ms": 250, "max_ms": 8000}, "offset_hours": -5,
+                "drift": -0.25, "banner": "Say “hello” – it’s free",
+                "labels": ["eu", "it’s"]}"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_each_replacement_is_reported_where_it_was_made() {
+    let result = parse_with_fallback(PASTED_FROM_DOCS, ParserOptions::default());
+    let quotes = result
+        .repairs
+        .iter()
+        .filter(|repair| repair.action_type == RepairType::ReplaceQuotes)
+        .count();
+    // Eleven double-quoted strings and three single-quoted ones
+    assert_eq!(quotes, 28);
+
+    let others: Vec<_> = result
+        .repairs
+        .iter()
+        .filter(|repair| repair.action_type == RepairType::ReplaceText)
+        .map(|repair| (repair.original.as_str(), repair.replacement.as_str()))
+        .collect();
+    // The no-break space is whitespace to the parser, so it is left as is
+    assert_eq!(others, [("–", "-"), ("—", "-")]);
+
+    for repair in &result.repairs {
+        assert_eq!(
+            &PASTED_FROM_DOCS[repair.position..][..repair.original.len()],
+            repair.original
+        );
+    }
+    assert!(result
+        .repairs
+        .windows(2)
+        .all(|pair| pair[0].position < pair[1].position));
+}
+
+#[test]
+fn test_straight_quoted_strings_are_left_alone() {
+    let input = "{\"quote\": \"“as is” — ‘really’\", 'x': '–1'}";
+    let result = parse_with_fallback(input, ParserOptions::default());
+    // Valid as written, so no tier ever needs to repair it
+    assert_eq!(result.parsing_tier, ParsingTier::Forgiving);
+    assert!(result.repairs.is_empty());
+    assert_eq!(
+        result.value,
+        parse("{\"quote\": \"“as is” — ‘really’\", \"x\": \"–1\"}").unwrap()
+    );
+}
+
+#[test]
+fn test_positions_refer_to_the_original_input() {
+    let input = "{“a”: –1}}";
+    let result = parse_with_fallback(input, ParserOptions::default());
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!(result.discarded_trailing_bytes, 1);
+    let discard = result.repairs.last().unwrap();
+    assert_eq!(discard.action_type, RepairType::DiscardTrailing);
+    assert_eq!(discard.position, input.len() - 1);
+
+    // An error found after normalizing points into the input as given
+    let input = "{“a”: “b”, “c”: @}";
+    let result = parse_with_fallback(input, ParserOptions::default());
+    let position = result.errors[0].position().unwrap();
+    assert_eq!(&input[position..], "@}");
+}
+
+#[test]
+fn test_normalization_can_be_disabled() {
+    let options = ParserOptions {
+        normalize_unicode_punctuation: false,
+        ..Default::default()
+    };
+    let result = parse_with_fallback("{“a”: 1}", options);
+    assert!(!result.errors.is_empty());
+    assert!(result
+        .repairs
+        .iter()
+        .all(|repair| repair.action_type != RepairType::ReplaceQuotes));
+}
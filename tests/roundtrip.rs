@@ -0,0 +1,104 @@
+use rustc_hash::FxHashMap;
+use vexy_json::{parse, Number, Value};
+use vexy_json_core::testing::assert_roundtrip;
+
+fn object(members: &[(&str, Value)]) -> Value {
+    Value::Object(
+        members
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_scalars_and_empty_containers() {
+    for value in [
+        Value::Null,
+        Value::Bool(true),
+        Value::Bool(false),
+        Value::String(String::new()),
+        Value::Array(vec![]),
+        Value::Object(FxHashMap::default()),
+        Value::Array(vec![
+            Value::Array(vec![]),
+            Value::Object(FxHashMap::default()),
+        ]),
+        object(&[("", Value::Object(FxHashMap::default()))]),
+    ] {
+        assert_roundtrip(&value);
+    }
+}
+
+#[test]
+fn test_deeply_nested() {
+    // Deeper than the parser's default limit of 128
+    let mut value = Value::Null;
+    for depth in 0..200 {
+        value = if depth % 2 == 0 {
+            Value::Array(vec![value, Value::Number(Number::Integer(depth))])
+        } else {
+            object(&[("next", value)])
+        };
+    }
+    assert_roundtrip(&value);
+}
+
+#[test]
+fn test_unicode() {
+    let strings = [
+        "héllo wörld",
+        "日本語のテキスト",
+        "emoji 👩‍💻🎉 and flags 🇫🇷",
+        "e\u{301} combining accent",
+        "right-to-left שלום",
+        "line\nbreaks\r\tand \u{0}\u{1f} controls",
+        "separators \u{2028} \u{2029} and \u{feff}",
+        "quotes \" ' and \\ backslashes",
+        "\u{ffff}\u{10ffff}",
+    ];
+    for s in strings {
+        assert_roundtrip(&Value::String(s.to_string()));
+        assert_roundtrip(&object(&[(s, Value::String(s.to_string()))]));
+    }
+}
+
+#[test]
+fn test_numbers() {
+    let numbers = [
+        Number::Integer(0),
+        Number::Integer(i64::MAX),
+        Number::Integer(i64::MIN),
+        Number::Integer((1 << 53) + 1),
+        Number::Integer(-(1 << 53) - 1),
+        Number::Float(0.1),
+        Number::Float(-0.0),
+        Number::Float(1e300),
+        Number::Float(-1.7976931348623157e308),
+        Number::Float(f64::MIN_POSITIVE),
+        Number::Float(5e-324),
+        Number::Float(9007199254740993.0),
+    ];
+    assert_roundtrip(&Value::Array(
+        numbers.into_iter().map(Value::Number).collect(),
+    ));
+}
+
+#[test]
+fn test_parsed_documents() {
+    assert_roundtrip(
+        &parse(
+            r#"{
+                // Forgiving input still writes out as standard JSON
+                name: 'vexy', tags: ['a', 'b',], nested: {deep: [1, 2.5, null]},
+            }"#,
+        )
+        .unwrap(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "value does not round trip")]
+fn test_non_finite_numbers_do_not_round_trip() {
+    assert_roundtrip(&Value::Array(vec![Value::Number(Number::Float(f64::NAN))]));
+}
@@ -0,0 +1,56 @@
+use vexy_json::{parse, parse_with_options, Error, ParserOptions, Value};
+
+fn space_separated() -> ParserOptions {
+    ParserOptions {
+        space_separated_values: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_space_separated_values_rejected_by_default() {
+    for input in [r#""a" "b""#, "1 2 3", "true false", "a /* comment */ b"] {
+        match parse(input) {
+            Err(Error::Expected { expected, .. }) => {
+                assert_eq!(expected, "end of input", "{input:?}")
+            }
+            other => panic!("{input:?} parsed as {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_space_separated_values_opt_in() {
+    let value = parse_with_options("1 2 3", space_separated()).unwrap();
+    let items: Vec<_> = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_i64())
+        .collect();
+    assert_eq!(items, [Some(1), Some(2), Some(3)]);
+
+    assert_eq!(
+        parse_with_options(r#""a" "b""#, space_separated()).unwrap(),
+        Value::Array(vec![Value::String("a".into()), Value::String("b".into())])
+    );
+}
+
+#[test]
+fn test_space_separated_values_require_implicit_top_level() {
+    let options = ParserOptions {
+        implicit_top_level: false,
+        ..space_separated()
+    };
+    assert!(parse_with_options("1 2 3", options).is_err());
+}
+
+#[test]
+fn test_newline_separated_values_still_form_array() {
+    for input in ["1\n2\n3", "1\n2, 3", "1 // one\n2\n3"] {
+        let value = parse(input).unwrap();
+        assert_eq!(value.as_array().map(Vec::len), Some(3), "{input:?}");
+    }
+    assert!(parse("1\n2 3").is_err());
+    assert!(parse_with_options("1\n2 3", space_separated()).is_ok());
+}
@@ -0,0 +1,366 @@
+use vexy_json::{
+    parse, parse_to_pairs, parse_with_options, DuplicateKeyPolicy, Error, Limits, Number,
+    ParserOptions, Value,
+};
+
+fn with_duplicates(policy: DuplicateKeyPolicy) -> ParserOptions {
+    ParserOptions {
+        duplicate_keys: policy,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_duplicate_key_policies() {
+    let input = r#"{"a": 1, "b": 2, "a": 3}"#;
+    let last = parse_with_options(input, with_duplicates(DuplicateKeyPolicy::LastWins)).unwrap();
+    assert_eq!(last, parse("{a: 3, b: 2}").unwrap());
+    let first = parse_with_options(input, with_duplicates(DuplicateKeyPolicy::FirstWins)).unwrap();
+    assert_eq!(first, parse("{a: 1, b: 2}").unwrap());
+
+    let error = parse_with_options(input, with_duplicates(DuplicateKeyPolicy::Error)).unwrap_err();
+    assert_eq!(
+        error,
+        Error::DuplicateKey {
+            key: "a".to_string(),
+            position: 17
+        }
+    );
+    assert_eq!(error.to_string(), r#"Duplicate key "a" at position 17"#);
+    assert_eq!(error.code().as_str(), "E1018");
+}
+
+#[test]
+fn test_duplicate_key_in_implicit_object() {
+    let error = parse_with_options(
+        "a: 1\nb: 2\na: 3",
+        with_duplicates(DuplicateKeyPolicy::Error),
+    )
+    .unwrap_err();
+    assert_eq!(error.position(), Some(10));
+}
+
+#[test]
+fn test_collect_duplicates_into_array() {
+    let options = with_duplicates(DuplicateKeyPolicy::CollectIntoArray);
+    let inputs = [
+        r#"{"h": "a", "x": 1, "h": ["b"], "y": {"h": 0, "h": 1}, "h": "c"}"#,
+        "h: 'a'\nx: 1\nh: ['b']\ny: {h: 0, h: 1}\nh: 'c'",
+    ];
+    for input in inputs {
+        let value = parse_with_options(input, options.clone()).unwrap();
+        assert_eq!(
+            value,
+            parse(r#"{"h": ["a", ["b"], "c"], "x": 1, "y": {"h": [0, 1]}}"#).unwrap(),
+            "{input}"
+        );
+
+        let pairs = parse_to_pairs(input, ParserOptions::default()).unwrap();
+        let keys: Vec<&str> = pairs.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["h", "x", "h", "y", "h"]);
+        assert_eq!(pairs[2].1, parse("['b']").unwrap());
+    }
+
+    // An array written once is not gathered with the values after it
+    let value = parse_with_options(r#"{"a": [1, 2], "a": 3}"#, options.clone()).unwrap();
+    assert_eq!(value, parse("{a: [[1, 2], 3]}").unwrap());
+
+    // A gathered array writes out the same as one written as an array
+    let gathered = parse_with_options(r#"{"a": 1, "a": 2}"#, options.clone()).unwrap();
+    let written = parse_with_options(r#"{"a": [1, 2]}"#, options).unwrap();
+    assert_eq!(gathered, written);
+}
+
+#[test]
+fn test_max_string_length() {
+    let options = ParserOptions {
+        limits: Limits {
+            max_string_len: Some(5),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    // Keys are not limited, and the limit is on the unescaped value
+    let value = parse_with_options(r#"{"long key": "abcde"}"#, options.clone()).unwrap();
+    assert_eq!(value["long key"], Value::String("abcde".to_string()));
+
+    let error = parse_with_options("[abc, 'abcdef']", options.clone()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "string length exceeds the limit of 5 at position 6 at path $[1]"
+    );
+    assert!(parse_with_options("unquoted_words", options).is_err());
+}
+
+#[test]
+fn test_max_object_members() {
+    let options = ParserOptions {
+        limits: Limits {
+            max_object_members: Some(100),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let members = |n: usize| (0..n).map(|i| format!("k{i}: {i}")).collect::<Vec<_>>();
+
+    let hundred = format!("{{{}}}", members(100).join(", "));
+    let value = parse_with_options(&hundred, options.clone()).unwrap();
+    assert_eq!(value.as_object().map(|o| o.len()), Some(100));
+
+    // The error points at the 101st key
+    let input = format!("{{{}}}", members(101).join(", "));
+    let error = parse_with_options(&input, options.clone()).unwrap_err();
+    let position = input.find("k100").unwrap();
+    let Error::AtPath { path, source } = &error else {
+        panic!("{error:?}");
+    };
+    assert_eq!(path.to_string(), "$");
+    assert_eq!(
+        **source,
+        Error::LimitExceeded {
+            what: "object member count".to_string(),
+            limit: 100,
+            position
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        format!("object member count exceeds the limit of 100 at position {position} at path $")
+    );
+
+    let implicit = members(101).join("\n");
+    let error = parse_with_options(&implicit, options.clone()).unwrap_err();
+    assert_eq!(error.position(), implicit.find("k100"));
+
+    // Repeated keys count once
+    let repeated = format!("{{{}, k0: 0}}", members(100).join(", "));
+    assert!(parse_with_options(&repeated, options).is_ok());
+}
+
+#[test]
+fn test_max_key_length() {
+    let options = ParserOptions {
+        limits: Limits {
+            max_key_len: Some(8),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    // The limit is on the unescaped key, and values are not limited
+    let value = parse_with_options(r#"{"\tbcdefgh": "a long value"}"#, options.clone());
+    assert_eq!(
+        value.unwrap()["\tbcdefgh"],
+        Value::String("a long value".into())
+    );
+
+    let error = parse_with_options(r#"{"a": 1, "abcdefghi": 2}"#, options.clone()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "key length exceeds the limit of 8 at position 9 at path $"
+    );
+    let error = parse_with_options("a: 1\nabcdefghi: 2", options.clone()).unwrap_err();
+    assert_eq!(error.position(), Some(5));
+    let error = parse_with_options("abcdefghi: 1", options.clone()).unwrap_err();
+    assert_eq!(error.position(), Some(0));
+
+    // A long top-level value is not a key
+    assert!(parse_with_options("abcdefghi", options).is_ok());
+}
+
+#[test]
+fn test_max_array_len() {
+    let options = ParserOptions {
+        limits: Limits {
+            max_array_len: Some(3),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let value = parse_with_options("{items: [1, [2, 3, 4], 5]}", options.clone()).unwrap();
+    assert_eq!(value["items"][1][2], Value::Number(Number::Integer(4)));
+
+    // One element over the limit names its index
+    let input = "{items: [1, [2, 3, 4, 5]]}";
+    let error = parse_with_options(input, options.clone()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "array length exceeds the limit of 3 at position 22 at path $.items[1][3]"
+    );
+    assert_eq!(error.position(), input.find('5'));
+
+    // Elements left empty between commas count, as do those of implicit
+    // top-level arrays
+    let error = parse_with_options("[1,,,,2]", options.clone()).unwrap_err();
+    assert!(error.to_string().ends_with("at path $[3]"), "{error}");
+    let error = parse_with_options("1, 2, 3, 4", options.clone()).unwrap_err();
+    assert!(error.to_string().ends_with("at path $[3]"), "{error}");
+
+    // Standard JSON is held to the limits by the fallback parser too
+    let result = vexy_json_core::parse_with_fallback("[1, 2, 3, 4]", options.clone());
+    assert_eq!(result.errors.len(), 1, "{:?}", result.value);
+    assert!(result.errors[0].to_string().ends_with("at path $[3]"));
+
+    // Other errors are not given a path unless track_paths asks for one
+    let error = parse_with_options("[1, 2", options).unwrap_err();
+    assert!(!matches!(error, Error::AtPath { .. }), "{error}");
+}
+
+#[test]
+fn test_limits_leave_deep_small_documents_alone() {
+    let options = ParserOptions {
+        limits: Limits {
+            max_array_len: Some(2),
+            max_object_members: Some(1),
+            max_key_len: Some(1),
+            max_string_len: Some(1),
+        },
+        ..Default::default()
+    };
+    let depth = 50;
+    let input = format!("{}'x'{}", "{a: [".repeat(depth), "]}".repeat(depth));
+    let value = parse_with_options(&input, options.clone()).unwrap();
+    assert_eq!(value, parse(&input).unwrap());
+
+    let hardened = ParserOptions {
+        limits: Limits::hardened(),
+        ..Default::default()
+    };
+    assert_eq!(parse_with_options(&input, hardened).unwrap(), value);
+
+    // The limits apply at every depth
+    let deeper = input.replacen("'x'", "'xy'", 1);
+    let error = parse_with_options(&deeper, options).unwrap_err();
+    let path = format!("${}", ".a[0]".repeat(depth));
+    assert!(error.to_string().ends_with(&path), "{error}");
+}
+
+#[test]
+fn test_reject_blank_keys() {
+    let options = ParserOptions {
+        reject_blank_keys: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        parse(r#"{"":1}"#).unwrap()[""],
+        Value::Number(Number::Integer(1))
+    );
+    assert_eq!(
+        parse(r#"{"  ":2}"#).unwrap()["  "],
+        Value::Number(Number::Integer(2))
+    );
+
+    let error = parse_with_options(r#"{"":1}"#, options.clone()).unwrap_err();
+    assert_eq!(
+        error,
+        Error::BlankKey {
+            key: String::new(),
+            position: 1
+        }
+    );
+    assert_eq!(error.code().as_str(), "E1022");
+    let error = parse_with_options(r#"{"a": {"  ":2}}"#, options.clone()).unwrap_err();
+    assert_eq!(error.to_string(), r#"Blank key "  " at position 7"#);
+    let error = parse_with_options("a: 1\n'\t': 2", options.clone()).unwrap_err();
+    assert_eq!(error.position(), Some(5));
+
+    // Keys with any other character, and blank string values, are accepted
+    assert!(parse_with_options(r#"{" a ": "  "}"#, options).is_ok());
+}
+
+#[test]
+fn test_preserve_number_precision() {
+    let options = ParserOptions {
+        preserve_number_precision: true,
+        ..Default::default()
+    };
+    // Integers that fit in an i64 are exact either way
+    assert_eq!(
+        parse("9007199254740993").unwrap(),
+        Value::Number(Number::Integer(9007199254740993))
+    );
+    assert_eq!(
+        parse_with_options("9007199254740993", options.clone()).unwrap(),
+        Value::Number(Number::Integer(9007199254740993))
+    );
+
+    for exact in ["0.1", "-2.50", "1e22", "1.5E-3", "0x1F", "-0.0"] {
+        assert_eq!(
+            parse_with_options(exact, options.clone()).unwrap(),
+            parse(exact).unwrap(),
+            "{exact}"
+        );
+    }
+    for rounded in ["12345678901234567890", "0.10000000000000000001", "1e400"] {
+        let error = parse_with_options(&format!("[{rounded}]"), options.clone()).unwrap_err();
+        assert_eq!(error.position(), Some(1), "{rounded}");
+    }
+}
+
+/// Parses `input` through every tier of `parse_with_fallback`.
+fn fallback(input: &str, options: ParserOptions) -> Result<Value, Error> {
+    vexy_json_core::parse_with_fallback(input, options).into_result()
+}
+
+#[test]
+fn test_fallback_honours_duplicate_keys() {
+    let input = r#"{"a":1,"a":2}"#;
+    assert!(matches!(
+        fallback(input, with_duplicates(DuplicateKeyPolicy::Error)),
+        Err(Error::DuplicateKey { .. })
+    ));
+    assert_eq!(
+        fallback(input, with_duplicates(DuplicateKeyPolicy::FirstWins)).unwrap(),
+        parse("{a: 1}").unwrap()
+    );
+    assert_eq!(
+        fallback(input, with_duplicates(DuplicateKeyPolicy::CollectIntoArray)).unwrap(),
+        parse("{a: [1, 2]}").unwrap()
+    );
+}
+
+#[test]
+fn test_fallback_honours_reject_blank_keys() {
+    let options = ParserOptions {
+        reject_blank_keys: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        fallback(r#"{"":1}"#, options),
+        Err(Error::BlankKey { .. })
+    ));
+}
+
+#[test]
+fn test_fallback_honours_preserve_number_precision() {
+    let options = ParserOptions {
+        preserve_number_precision: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        fallback("[9007199254740993]", options.clone()).unwrap()[0],
+        Value::Number(Number::Integer(9007199254740993))
+    );
+    assert!(fallback("[12345678901234567890]", options).is_err());
+}
+
+#[test]
+fn test_fallback_honours_exponent_as_float() {
+    let options = ParserOptions {
+        exponent_as_float: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        fallback("[1e2]", options).unwrap()[0],
+        Value::Number(Number::Integer(100))
+    );
+}
+
+#[test]
+fn test_fallback_honours_max_depth() {
+    let options = ParserOptions {
+        max_depth: 4,
+        ..Default::default()
+    };
+    let deep = format!("{}{}", "[".repeat(10), "]".repeat(10));
+    assert!(fallback(&deep, options).is_err());
+}
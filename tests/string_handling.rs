@@ -47,8 +47,8 @@ fn test_quote_escaping() {
 fn test_unicode_escapes() {
     // Test unicode escape sequences (if supported by vexy_json)
     let unicode_test = parse(r#""\u0061""#);
-    if unicode_test.is_ok() {
-        assert_eq!(unicode_test.unwrap(), Value::String("a".to_string()));
+    if let Ok(value) = unicode_test {
+        assert_eq!(value, Value::String("a".to_string()));
     } else {
         println!("vexy_json doesn't support unicode escapes - that's OK");
     }
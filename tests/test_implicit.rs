@@ -1,3 +1,5 @@
+#![allow(clippy::field_reassign_with_default)]
+
 use vexy_json_core::{parse, parse_with_options, ParserOptions};
 
 fn main() {
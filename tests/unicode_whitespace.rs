@@ -0,0 +1,57 @@
+use vexy_json::{parse, parse_with_options, Error, ParserOptions};
+
+fn strict() -> ParserOptions {
+    ParserOptions {
+        allow_comments: false,
+        allow_trailing_commas: false,
+        allow_unquoted_keys: false,
+        allow_single_quotes: false,
+        implicit_top_level: false,
+        newline_as_comma: false,
+        allow_unicode_whitespace: false,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_leading_bom_is_whitespace() {
+    let expected = parse(r#"{"a": [1, 2]}"#).unwrap();
+    let input = "\u{FEFF}{\"a\": [1, 2]}";
+    assert_eq!(parse(input).unwrap(), expected);
+    assert_eq!(parse_with_options(input, strict()).unwrap(), expected);
+
+    // Error positions still index into the original input
+    let error = parse_with_options("\u{FEFF}[1, ]", strict()).unwrap_err();
+    assert_eq!(error.position(), Some(5));
+}
+
+#[test]
+fn test_allow_unicode_whitespace() {
+    let input = "{\n\u{A0}\u{A0}\"a\":\u{2009}1,\u{2028}\"b\": [2\u{2003}]\u{FEFF}}";
+    let value = parse(input).unwrap();
+    assert_eq!(value, parse(r#"{"a": 1, "b": [2]}"#).unwrap());
+
+    let options = ParserOptions {
+        allow_unicode_whitespace: false,
+        ..Default::default()
+    };
+    let error = parse_with_options(input, options).unwrap_err();
+    assert_eq!(error, Error::UnexpectedChar('\u{A0}', 2));
+    assert_eq!(
+        error.to_string(),
+        "Unexpected character U+00A0 NO-BREAK SPACE at position 2"
+    );
+}
+
+#[test]
+fn test_invisible_character_named_in_error() {
+    let error = parse("{na\u{200B}me: 1}").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Unexpected character U+200B ZERO WIDTH SPACE at position 3"
+    );
+
+    // Visible characters are still quoted
+    let error = parse("[1, @]").unwrap_err();
+    assert_eq!(error.to_string(), "Unexpected character '@' at position 4");
+}
@@ -0,0 +1,94 @@
+use vexy_json::{parse_with_options, Error, ParserOptions, UnquotedValueMode, Value};
+
+fn parse_in(input: &str, mode: UnquotedValueMode) -> Result<Value, Error> {
+    let options = ParserOptions {
+        unquoted_value_mode: mode,
+        ..Default::default()
+    };
+    parse_with_options(input, options)
+}
+
+fn string(value: &Value) -> &str {
+    match value {
+        Value::String(s) => s,
+        other => panic!("expected a string, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_single_token_is_the_default() {
+    assert_eq!(
+        ParserOptions::default().unquoted_value_mode,
+        UnquotedValueMode::SingleToken
+    );
+    let error = parse_in("{key: hello world}", UnquotedValueMode::SingleToken).unwrap_err();
+    assert_eq!(
+        error,
+        Error::Expected {
+            expected: ":".to_string(),
+            found: "RightBrace".to_string(),
+            position: 17,
+        }
+    );
+}
+
+#[test]
+fn test_rest_of_line_takes_in_every_word() {
+    let value = parse_in("{key: hello world}", UnquotedValueMode::RestOfLine).unwrap();
+    assert_eq!(string(&value["key"]), "hello world");
+
+    let value = parse_in(
+        "{key: hello  big\tworld 42 true}",
+        UnquotedValueMode::RestOfLine,
+    )
+    .unwrap();
+    assert_eq!(string(&value["key"]), "hello  big\tworld 42 true");
+}
+
+#[test]
+fn test_rest_of_line_stops_at_separators() {
+    let value = parse_in(
+        "{a: hello world  , b: x y\n c: one // note\n d: 1}",
+        UnquotedValueMode::RestOfLine,
+    )
+    .unwrap();
+    assert_eq!(string(&value["a"]), "hello world");
+    assert_eq!(string(&value["b"]), "x y");
+    assert_eq!(string(&value["c"]), "one");
+    assert_eq!(value["d"].as_i64(), Some(1));
+
+    let value = parse_in("[hello world, x, 1 2]", UnquotedValueMode::RestOfLine).unwrap();
+    let items: Vec<_> = value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(Value::to_string)
+        .collect();
+    assert_eq!(items, ["\"hello world\"", "\"x\"", "1", "2"]);
+}
+
+#[test]
+fn test_rest_of_line_at_top_level() {
+    let value = parse_in(
+        "key: hello world\nother: a b",
+        UnquotedValueMode::RestOfLine,
+    )
+    .unwrap();
+    assert_eq!(string(&value["key"]), "hello world");
+    assert_eq!(string(&value["other"]), "a b");
+
+    let value = parse_in("a b c", UnquotedValueMode::RestOfLine).unwrap();
+    assert_eq!(string(&value), "a b c");
+}
+
+#[test]
+fn test_rest_of_line_leaves_quoted_strings_and_numbers_alone() {
+    for input in ["{k: hello \"q\"}", "{k: 1 hello}"] {
+        assert!(
+            parse_in(input, UnquotedValueMode::RestOfLine).is_err(),
+            "{input}"
+        );
+    }
+    let value = parse_in("{k: 'hello world'}", UnquotedValueMode::RestOfLine).unwrap();
+    assert_eq!(string(&value["k"]), "hello world");
+}
@@ -0,0 +1,89 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use vexy_json::{parse, Value};
+
+/// Counts the bytes and allocations requested of the system allocator.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Tests share the counters, so they take turns.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// Runs `f`, returning its result with the bytes and allocations it made.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, usize) {
+    let bytes = ALLOCATED.load(Ordering::SeqCst);
+    let count = ALLOCATIONS.load(Ordering::SeqCst);
+    let result = f();
+    (
+        result,
+        ALLOCATED.load(Ordering::SeqCst) - bytes,
+        ALLOCATIONS.load(Ordering::SeqCst) - count,
+    )
+}
+
+fn generated_document() -> Value {
+    let records: Vec<String> = (0..500)
+        .map(|i| {
+            format!(
+                r#"{{"id": {i}, "name": "user-{i}", "active": {}, "scores": [{i}, 1.5, null],
+                    "address": {{"city": "City number {i}", "zip": "{:05}"}}}}"#,
+                i % 2 == 0,
+                i * 7
+            )
+        })
+        .collect();
+    parse(&format!(
+        r#"{{"records": [{}], "total": 500}}"#,
+        records.join(",")
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_heap_estimate_matches_measured_allocation() {
+    let _serial = SERIAL.lock().unwrap();
+    let value = generated_document();
+    // A clone allocates exactly the heap the copy holds
+    let (copy, measured, _) = measure(|| value.clone());
+    let estimate = copy.stats().heap_bytes;
+    assert!(
+        estimate >= measured / 2 && estimate <= measured * 2,
+        "estimated {estimate} bytes, measured {measured}"
+    );
+}
+
+#[test]
+fn test_stats_does_not_copy_the_document() {
+    let _serial = SERIAL.lock().unwrap();
+    let value = generated_document();
+    let (stats, bytes, allocations) = measure(|| value.stats());
+    let (depth, _, _) = measure(|| value.depth());
+    let (count, _, _) = measure(|| value.len_recursive());
+
+    // Only the walk's stack, one entry per level of nesting, is allocated
+    assert!(allocations <= 4, "{allocations} allocations");
+    assert!(bytes < 1024, "{bytes} bytes");
+    assert_eq!(stats.objects, 1001);
+    assert_eq!(stats.arrays, 501);
+    assert_eq!(depth, stats.max_depth);
+    assert_eq!(depth, 4);
+    assert_eq!(count, stats.values());
+}
@@ -0,0 +1,97 @@
+use vexy_json::{ParserOptions, Span, WarningKind};
+use vexy_json_core::parser::Parser;
+use vexy_json_core::{parse_with_fallback, ParsingTier};
+
+fn collecting() -> ParserOptions {
+    ParserOptions {
+        collect_warnings: true,
+        ..Default::default()
+    }
+}
+
+fn warnings(input: &str) -> Vec<(WarningKind, Span)> {
+    let mut parser = Parser::new(input, collecting());
+    parser.parse().unwrap();
+    parser
+        .warnings()
+        .iter()
+        .map(|warning| (warning.kind, warning.span))
+        .collect()
+}
+
+#[test]
+fn test_newline_as_comma_warns_at_line_break() {
+    let input = "[1\n  2, 3]";
+    assert_eq!(
+        warnings(input),
+        [(WarningKind::NewlineAsComma, Span::new(2, 3))]
+    );
+
+    let input = "{a: 1\nb: 2}";
+    assert_eq!(
+        warnings(input),
+        [(WarningKind::NewlineAsComma, Span::new(5, 6))]
+    );
+
+    // Explicit commas, even before a line break, are not transformations
+    assert!(warnings("[1,\n2]").is_empty());
+    assert!(warnings("{\"a\": [1, 2]}").is_empty());
+}
+
+#[test]
+fn test_implicit_object_warns_over_members() {
+    let input = "a: 1\nb: [2]\n";
+    assert_eq!(
+        warnings(input),
+        [
+            (WarningKind::NewlineAsComma, Span::new(4, 5)),
+            (WarningKind::ImplicitObject, Span::new(0, 11)),
+        ]
+    );
+}
+
+#[test]
+fn test_implicit_array_warns_over_values() {
+    let mut parser = Parser::new("1, 2,\n3", collecting());
+    parser.parse().unwrap();
+    let warnings = parser.take_warnings();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, WarningKind::ImplicitArray);
+    assert_eq!(warnings[0].span, Span::new(0, 7));
+    assert_eq!(
+        warnings[0].to_string(),
+        "3 top-level values wrapped in an implicit array at position 0"
+    );
+    assert!(parser.warnings().is_empty());
+
+    let kinds: Vec<_> = self::warnings("1\n2")
+        .into_iter()
+        .map(|(kind, _)| kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        [WarningKind::NewlineAsComma, WarningKind::ImplicitArray]
+    );
+}
+
+#[test]
+fn test_warnings_are_opt_in() {
+    let mut parser = Parser::new("a: 1\nb: 2", ParserOptions::default());
+    parser.parse().unwrap();
+    assert!(parser.warnings().is_empty());
+}
+
+#[test]
+fn test_fallback_returns_warnings() {
+    let result = parse_with_fallback("a: 1\nb: 2", collecting());
+    assert_eq!(result.parsing_tier, ParsingTier::Forgiving);
+    let kinds: Vec<_> = result.warnings.iter().map(|warning| warning.kind).collect();
+    assert_eq!(
+        kinds,
+        [WarningKind::NewlineAsComma, WarningKind::ImplicitObject]
+    );
+
+    let result = parse_with_fallback(r#"{"a": 1}"#, collecting());
+    assert_eq!(result.parsing_tier, ParsingTier::Fast);
+    assert!(result.warnings.is_empty());
+}